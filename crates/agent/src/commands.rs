@@ -1,3 +1,56 @@
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, TimeZone, Utc, Weekday};
+use serde::Serialize;
+use serde_json::Value;
+
+/// Default cap on the serialized size of the context block sent to the gateway.
+pub const MAX_CONTEXT_BYTES: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ContextMessage {
+    pub ts: String,
+    pub user: String,
+    pub text: String,
+    pub thread_ts: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChannelContext {
+    pub channel: String,
+    pub messages: Vec<ContextMessage>,
+}
+
+impl ChannelContext {
+    /// Builds a context block, dropping the oldest messages until the
+    /// serialized form fits in `max_bytes`.
+    pub fn new(channel: &str, messages: Vec<ContextMessage>, max_bytes: usize) -> Self {
+        let mut context = Self {
+            channel: channel.to_string(),
+            messages,
+        };
+
+        let mut size = context.serialized_len();
+        let mut drop_count = 0;
+        while size > max_bytes && drop_count < context.messages.len() {
+            // Each dropped entry also removes its separating comma.
+            size = size.saturating_sub(Self::message_len(&context.messages[drop_count]) + 1);
+            drop_count += 1;
+        }
+        context.messages.drain(..drop_count);
+        context
+    }
+
+    fn serialized_len(&self) -> usize {
+        serde_json::to_vec(self)
+            .map(|v| v.len())
+            .unwrap_or(usize::MAX)
+    }
+
+    fn message_len(message: &ContextMessage) -> usize {
+        serde_json::to_vec(message).map(|v| v.len()).unwrap_or(0)
+    }
+}
+
 pub fn process_command(text: &str) -> Option<(String, Vec<String>)> {
     if !text.starts_with('/') {
         return None;
@@ -18,46 +71,227 @@ pub fn is_agent_mention(text: &str) -> bool {
     text.to_lowercase().contains("@zeroclaw") || text.to_lowercase().contains("@zc")
 }
 
+/// The question in an `@zeroclaw` message: the text with the first mention
+/// and any `:` or `,` right after it removed.
+pub fn strip_agent_mention(text: &str) -> String {
+    let Some((start, len)) = ["@zeroclaw", "@zc"].iter().find_map(|mention| {
+        find_ignore_ascii_case(text, mention).map(|start| (start, mention.len()))
+    }) else {
+        return text.trim().to_string();
+    };
+    let before = text[..start].trim_end();
+    let after = text[start + len..]
+        .trim_start_matches([':', ','])
+        .trim_start();
+    match (before.is_empty(), after.is_empty()) {
+        (true, _) => after.trim_end().to_string(),
+        (false, true) => before.to_string(),
+        (false, false) => format!("{before} {}", after.trim_end()),
+    }
+}
+
+/// Byte offset of `needle` (ASCII) in `text`, ignoring ASCII case. Offsets
+/// from a lowercased copy can't be used: lowercasing changes the length of
+/// some characters.
+fn find_ignore_ascii_case(text: &str, needle: &str) -> Option<usize> {
+    text.char_indices().map(|(i, _)| i).find(|&i| {
+        text.as_bytes()
+            .get(i..i + needle.len())
+            .is_some_and(|window| window.eq_ignore_ascii_case(needle.as_bytes()))
+    })
+}
+
 #[derive(Debug, Clone)]
 pub enum CommandType {
-    Resume { channel: Option<String> },
-    Draft { intent: String },
-    Search { query: String },
+    Resume {
+        channel: Option<String>,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    },
+    Draft {
+        intent: String,
+    },
+    Search {
+        query: String,
+    },
+    Tldr {
+        thread_ts: Option<String>,
+    },
+    Translate {
+        target_lang: String,
+        text: String,
+    },
+    /// A message addressed to `@zeroclaw` in the input, mention removed.
+    Mention {
+        text: String,
+    },
     Unknown(String),
 }
 
 impl CommandType {
-    pub fn from_command(name: &str, args: &[String]) -> Self {
-        match name.to_lowercase().as_str() {
-            "resume" | "résume" | "summarize" => Self::Resume {
-                channel: args.first().map(|s| {
-                    if let Some(stripped) = s.strip_prefix('#') {
-                        stripped.to_string()
-                    } else {
-                        s.clone()
-                    }
-                }),
-            },
+    /// Parses a slash command. Fails only when `/resume` is given a time
+    /// range it cannot understand.
+    pub fn from_command(name: &str, args: &[String]) -> Result<Self> {
+        Ok(match name.to_lowercase().as_str() {
+            "resume" | "résume" | "summarize" => Self::parse_resume(args, &Local::now())?,
             "draft" => Self::Draft {
                 intent: args.join(" "),
             },
             "cherche" | "search" => Self::Search {
                 query: args.join(" "),
             },
+            "tldr" => Self::Tldr {
+                thread_ts: args.first().cloned(),
+            },
+            "translate" => Self::Translate {
+                target_lang: args.first().cloned().unwrap_or_default(),
+                text: args.get(1..).unwrap_or_default().join(" "),
+            },
             _ => Self::Unknown(name.to_string()),
+        })
+    }
+
+    /// `/resume [#channel] [range]`: a `#name` anywhere is the channel, else a
+    /// leading bare word that does not start a range; every other word must
+    /// form the range.
+    fn parse_resume<Tz: TimeZone>(args: &[String], now: &DateTime<Tz>) -> Result<Self> {
+        let channel_pos = args
+            .iter()
+            .position(|arg| arg.starts_with('#'))
+            .or_else(|| args.first().filter(|arg| !starts_range(arg)).map(|_| 0));
+        let channel = channel_pos.map(|pos| args[pos].trim_start_matches('#').to_string());
+        let range: Vec<&str> = args
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| Some(*i) != channel_pos)
+            .map(|(_, arg)| arg.as_str())
+            .collect();
+        let (since, until) = parse_time_range(&range, now)?;
+        Ok(Self::Resume {
+            channel,
+            since,
+            until,
+        })
+    }
+
+    /// Fills in the target thread for `/tldr` when none was given explicitly.
+    pub fn resolve_thread(self, selected_thread: Option<String>) -> Result<Self> {
+        match self {
+            Self::Tldr { thread_ts: None } => selected_thread
+                .map(|ts| Self::Tldr {
+                    thread_ts: Some(ts),
+                })
+                .ok_or_else(|| {
+                    anyhow!("No thread selected — select a threaded message or open a thread, then run /tldr")
+                }),
+            other => Ok(other),
+        }
+    }
+
+    /// Fills in the target language and the selected message's text for
+    /// `/translate` when they were not given explicitly.
+    pub fn resolve_translation(
+        self,
+        default_lang: &str,
+        selected_text: Option<String>,
+    ) -> Result<Self> {
+        match self {
+            Self::Translate { target_lang, text } => {
+                let target_lang = if target_lang.is_empty() {
+                    default_lang.to_string()
+                } else {
+                    target_lang
+                };
+                let text = if text.trim().is_empty() {
+                    selected_text
+                        .filter(|text| !text.trim().is_empty())
+                        .ok_or_else(|| {
+                            anyhow!("No message selected — move the cursor to a message, then run /translate")
+                        })?
+                } else {
+                    text
+                };
+                Ok(Self::Translate { target_lang, text })
+            }
+            other => Ok(other),
         }
     }
 
+    /// Payload for `/tldr`: the thread parent followed by all of its replies.
+    pub fn to_thread_payload(
+        &self,
+        active_channel: &str,
+        user: &str,
+        thread: &[ContextMessage],
+    ) -> Value {
+        let history = thread
+            .iter()
+            .map(|message| format!("{}: {}", message.user, message.text.replace('\n', " ")))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let context = ChannelContext::new(active_channel, thread.to_vec(), MAX_CONTEXT_BYTES);
+        self.to_webhook_payload(active_channel, &history, user, Some(&context))
+    }
+
+    /// Webhook payload for the gateway: the prompt, plus an optional block of
+    /// recent channel messages so ZeroClaw does not need its own Slack access.
+    pub fn to_webhook_payload(
+        &self,
+        active_channel: &str,
+        history: &str,
+        user: &str,
+        context: Option<&ChannelContext>,
+    ) -> Value {
+        let mut payload = serde_json::json!({
+            "message": self.to_agent_prompt(active_channel, history, user),
+        });
+        if matches!(self, Self::Mention { .. }) {
+            payload["source"] = Value::String("mention".to_string());
+        }
+        if let Self::Resume { since, until, .. } = self {
+            if let Some(since) = since {
+                payload["since"] = Value::String(since.to_rfc3339());
+            }
+            if let Some(until) = until {
+                payload["until"] = Value::String(until.to_rfc3339());
+            }
+        }
+        if let Some(context) = context {
+            payload["context"] = serde_json::to_value(context).unwrap_or(Value::Null);
+        }
+        payload
+    }
+
     pub fn to_agent_prompt(&self, active_channel: &str, history: &str, user: &str) -> String {
         match self {
-            CommandType::Resume { channel } => {
+            CommandType::Resume {
+                channel,
+                since,
+                until,
+            } => {
                 let target_channel = channel
                     .as_deref()
                     .filter(|s| !s.is_empty())
                     .unwrap_or(active_channel);
+                let window = match (since, until) {
+                    (None, None) => "the recent discussion".to_string(),
+                    (Some(since), None) => format!(
+                        "the discussion since {} UTC",
+                        since.format("%Y-%m-%d %H:%M")
+                    ),
+                    (None, Some(until)) => format!(
+                        "the discussion before {} UTC",
+                        until.format("%Y-%m-%d %H:%M")
+                    ),
+                    (Some(since), Some(until)) => format!(
+                        "the discussion between {} and {} UTC",
+                        since.format("%Y-%m-%d %H:%M"),
+                        until.format("%Y-%m-%d %H:%M")
+                    ),
+                };
                 format!(
                     "You are ZeroClaw helping inside Slack for user {user}.\n\
-                     Summarize the recent discussion in channel #{target_channel}.\n\
+                     Summarize {window} in channel #{target_channel}.\n\
                      Focus on decisions, action items, blockers, and open questions.\n\
                      If the context is insufficient, say that explicitly.\n\n\
                      Recent Slack messages:\n{history}"
@@ -81,6 +315,30 @@ impl CommandType {
                      Recent Slack context:\n{history}"
                 )
             }
+            CommandType::Tldr { .. } => {
+                format!(
+                    "You are ZeroClaw helping inside Slack for user {user} in channel #{active_channel}.\n\
+                     Write a short TL;DR of the Slack thread below: outcome, decisions, action items, open questions.\n\
+                     If the thread is inconclusive, say that explicitly.\n\n\
+                     Thread (parent first):\n{history}"
+                )
+            }
+            CommandType::Translate { target_lang, text } => {
+                format!(
+                    "You are ZeroClaw helping inside Slack for user {user} in channel #{active_channel}.\n\
+                     Translate the Slack message below into {target_lang}.\n\
+                     Keep mentions, links, emoji, and code unchanged. Return only the translation.\n\n\
+                     Message:\n{text}"
+                )
+            }
+            CommandType::Mention { text } => {
+                format!(
+                    "You are ZeroClaw helping inside Slack. User {user} mentioned you in channel #{active_channel}:\n\
+                     {text}\n\
+                     Reply as a concise Slack thread reply, ready to post.\n\n\
+                     Recent Slack context:\n{history}"
+                )
+            }
             CommandType::Unknown(name) => {
                 format!(
                     "The user sent an unsupported Slack agent command '/{name}' in channel #{active_channel}. \
@@ -91,17 +349,167 @@ impl CommandType {
     }
 }
 
+/// Resolved `since`/`until` bounds of a `/resume` window.
+pub type TimeRange = (Option<DateTime<Utc>>, Option<DateTime<Utc>>);
+
+const RANGE_HINT: &str = "try 'last 24h', 'since monday', 'yesterday' or '2024-03-01..2024-03-03'";
+
+/// Whether `word` opens a `/resume` time range rather than naming a channel.
+fn starts_range(word: &str) -> bool {
+    let word = word.to_lowercase();
+    matches!(
+        word.as_str(),
+        "last" | "since" | "until" | "today" | "yesterday"
+    ) || word.contains("..")
+        || parse_date(&word).is_some()
+}
+
+/// Resolves a `/resume` time range against `now`, in `now`'s time zone:
+/// `last 24h` / `last 3 days` / `last week`, `since <day>`, `until <day>`,
+/// `since <day> until <day>`, `today`, `yesterday`, or `<date>..<date>`
+/// (both days inclusive). A day is `today`, `yesterday`, a weekday (the most
+/// recent one, today included) or `YYYY-MM-DD`. No words means no range.
+pub fn parse_time_range<Tz: TimeZone>(words: &[&str], now: &DateTime<Tz>) -> Result<TimeRange> {
+    let lowered: Vec<String> = words.iter().map(|word| word.to_lowercase()).collect();
+    let words: Vec<&str> = lowered.iter().map(String::as_str).collect();
+    let phrase = words.join(" ");
+    let invalid = || anyhow!("Could not understand the time range '{phrase}' — {RANGE_HINT}");
+    let day = |word: &str| start_of_day(word, now).ok_or_else(invalid);
+
+    let (since, until) = match words.as_slice() {
+        [] => return Ok((None, None)),
+        ["last", amount @ ..] => {
+            let span = parse_span(amount).ok_or_else(invalid)?;
+            (Some(now.with_timezone(&Utc) - span), None)
+        }
+        ["since", from] => (Some(day(from)?), None),
+        ["until", to] => (None, Some(day(to)?)),
+        ["since", from, "until", to] => (
+            Some(day(from)?),
+            Some(end_of_day(to, now).ok_or_else(invalid)?),
+        ),
+        ["today"] => (Some(day("today")?), None),
+        ["yesterday"] => (Some(day("yesterday")?), Some(day("today")?)),
+        [span] if span.contains("..") => {
+            let (from, to) = span.split_once("..").ok_or_else(invalid)?;
+            (
+                Some(day(from)?),
+                Some(end_of_day(to, now).ok_or_else(invalid)?),
+            )
+        }
+        _ => return Err(invalid()),
+    };
+    if let Some(since) = since {
+        if since > now.with_timezone(&Utc) {
+            bail!("The time range '{phrase}' starts in the future");
+        }
+    }
+    if let (Some(since), Some(until)) = (since, until) {
+        if since >= until {
+            bail!("The time range '{phrase}' ends before it starts");
+        }
+    }
+    Ok((since, until))
+}
+
+/// `24h`, `3 days`, `90m`, `2w`, or a bare unit meaning one of it.
+fn parse_span(words: &[&str]) -> Option<Duration> {
+    let joined = words.concat();
+    let split = joined
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(joined.len());
+    let (amount, unit) = joined.split_at(split);
+    let amount: i64 = if amount.is_empty() {
+        1
+    } else {
+        amount.parse().ok().filter(|amount| *amount > 0)?
+    };
+    let unit = match unit {
+        "m" | "min" | "mins" | "minute" | "minutes" => Duration::minutes(1),
+        "h" | "hr" | "hrs" | "hour" | "hours" => Duration::hours(1),
+        "d" | "day" | "days" => Duration::days(1),
+        "w" | "week" | "weeks" => Duration::weeks(1),
+        _ => return None,
+    };
+    unit.checked_mul(i32::try_from(amount).ok()?)
+}
+
+fn parse_date(word: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(word, "%Y-%m-%d").ok()
+}
+
+fn parse_weekday(word: &str) -> Option<Weekday> {
+    match word {
+        "mon" | "monday" => Some(Weekday::Mon),
+        "tue" | "tues" | "tuesday" => Some(Weekday::Tue),
+        "wed" | "wednesday" => Some(Weekday::Wed),
+        "thu" | "thur" | "thurs" | "thursday" => Some(Weekday::Thu),
+        "fri" | "friday" => Some(Weekday::Fri),
+        "sat" | "saturday" => Some(Weekday::Sat),
+        "sun" | "sunday" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// The calendar day `word` names, relative to `now`.
+fn resolve_day<Tz: TimeZone>(word: &str, now: &DateTime<Tz>) -> Option<NaiveDate> {
+    let today = now.date_naive();
+    match word {
+        "today" => Some(today),
+        "yesterday" => today.pred_opt(),
+        _ => parse_weekday(word)
+            .map(|weekday| {
+                let back = (today.weekday().num_days_from_monday() + 7
+                    - weekday.num_days_from_monday())
+                    % 7;
+                today - Duration::days(i64::from(back))
+            })
+            .or_else(|| parse_date(word)),
+    }
+}
+
+fn start_of_day<Tz: TimeZone>(word: &str, now: &DateTime<Tz>) -> Option<DateTime<Utc>> {
+    let midnight = resolve_day(word, now)?.and_hms_opt(0, 0, 0)?;
+    now.timezone()
+        .from_local_datetime(&midnight)
+        .earliest()
+        .map(|at| at.with_timezone(&Utc))
+}
+
+/// Midnight after the day `word` names, so the whole day is included.
+fn end_of_day<Tz: TimeZone>(word: &str, now: &DateTime<Tz>) -> Option<DateTime<Utc>> {
+    let next = resolve_day(word, now)?.succ_opt()?.and_hms_opt(0, 0, 0)?;
+    now.timezone()
+        .from_local_datetime(&next)
+        .earliest()
+        .map(|at| at.with_timezone(&Utc))
+}
+
 #[cfg(test)]
 mod tests {
-    use super::CommandType;
+    use super::{
+        parse_time_range, strip_agent_mention, ChannelContext, CommandType, ContextMessage,
+        TimeRange,
+    };
+    use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+
+    fn context_message(ts: &str, text: &str) -> ContextMessage {
+        ContextMessage {
+            ts: ts.to_string(),
+            user: "alice".to_string(),
+            text: text.to_string(),
+            thread_ts: None,
+        }
+    }
 
     #[test]
     fn resume_without_arg_uses_active_channel() {
-        let prompt = CommandType::Resume { channel: None }.to_agent_prompt(
-            "general",
-            "alice: hello",
-            "U456",
-        );
+        let prompt = CommandType::Resume {
+            channel: None,
+            since: None,
+            until: None,
+        }
+        .to_agent_prompt("general", "alice: hello", "U456");
         assert!(prompt.contains("channel #general"));
         assert!(prompt.contains("alice: hello"));
         assert!(prompt.contains("U456"));
@@ -111,9 +519,325 @@ mod tests {
     fn resume_with_arg_uses_requested_channel() {
         let prompt = CommandType::Resume {
             channel: Some("general".to_string()),
+            since: None,
+            until: None,
         }
         .to_agent_prompt("random", "alice: hello", "U456");
         assert!(prompt.contains("channel #general"));
         assert!(!prompt.contains("channel #random"));
     }
+
+    #[test]
+    fn resume_prefers_hash_prefixed_channel_argument() {
+        let args = vec!["yesterday".to_string(), "#dev".to_string()];
+        match CommandType::from_command("resume", &args).unwrap() {
+            CommandType::Resume { channel, since, .. } => {
+                assert_eq!(channel.as_deref(), Some("dev"));
+                assert!(since.is_some());
+            }
+            other => panic!("unexpected command {other:?}"),
+        }
+    }
+
+    #[test]
+    fn webhook_payload_includes_context_block() {
+        let context = ChannelContext::new(
+            "general",
+            vec![context_message("1.0", "hello")],
+            super::MAX_CONTEXT_BYTES,
+        );
+        let payload = CommandType::Resume {
+            channel: None,
+            since: None,
+            until: None,
+        }
+        .to_webhook_payload("general", "alice: hello", "U456", Some(&context));
+        assert!(payload["message"].as_str().is_some());
+        assert_eq!(payload["context"]["channel"], "general");
+        assert_eq!(payload["context"]["messages"][0]["ts"], "1.0");
+        assert_eq!(payload["context"]["messages"][0]["user"], "alice");
+        assert!(payload["context"]["messages"][0]["thread_ts"].is_null());
+
+        let without = CommandType::Resume {
+            channel: None,
+            since: None,
+            until: None,
+        }
+        .to_webhook_payload("general", "", "U456", None);
+        assert!(without.get("context").is_none());
+    }
+
+    /// Wednesday 2024-03-06 09:30 at UTC+01:00.
+    fn wednesday_morning() -> DateTime<FixedOffset> {
+        FixedOffset::east_opt(3600)
+            .unwrap()
+            .with_ymd_and_hms(2024, 3, 6, 9, 30, 0)
+            .unwrap()
+    }
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    fn range(phrase: &str) -> anyhow::Result<TimeRange> {
+        let words: Vec<&str> = phrase.split_whitespace().collect();
+        parse_time_range(&words, &wednesday_morning())
+    }
+
+    #[test]
+    fn relative_spans_count_back_from_now() {
+        let now = utc(2024, 3, 6, 8, 30);
+        assert_eq!(
+            range("last 24h").unwrap(),
+            (Some(utc(2024, 3, 5, 8, 30)), None)
+        );
+        assert_eq!(
+            range("last 3 days").unwrap().0,
+            Some(utc(2024, 3, 3, 8, 30))
+        );
+        assert_eq!(
+            range("last 90m").unwrap().0,
+            Some(now - chrono::Duration::minutes(90))
+        );
+        assert_eq!(range("last week").unwrap().0, Some(utc(2024, 2, 28, 8, 30)));
+        assert_eq!(
+            range("Last 2 Hours").unwrap().0,
+            Some(utc(2024, 3, 6, 6, 30))
+        );
+    }
+
+    #[test]
+    fn named_days_start_at_local_midnight() {
+        // Local midnight is 23:00 UTC the day before.
+        assert_eq!(
+            range("today").unwrap(),
+            (Some(utc(2024, 3, 5, 23, 0)), None)
+        );
+        assert_eq!(
+            range("yesterday").unwrap(),
+            (Some(utc(2024, 3, 4, 23, 0)), Some(utc(2024, 3, 5, 23, 0)))
+        );
+        assert_eq!(
+            range("since monday").unwrap().0,
+            Some(utc(2024, 3, 3, 23, 0))
+        );
+        // The current weekday means today, not a week ago.
+        assert_eq!(range("since wed").unwrap().0, Some(utc(2024, 3, 5, 23, 0)));
+        assert_eq!(
+            range("since thursday").unwrap().0,
+            Some(utc(2024, 2, 28, 23, 0))
+        );
+        assert_eq!(
+            range("until yesterday").unwrap(),
+            (None, Some(utc(2024, 3, 4, 23, 0)))
+        );
+    }
+
+    #[test]
+    fn date_ranges_include_both_days() {
+        assert_eq!(
+            range("2024-03-01..2024-03-03").unwrap(),
+            (Some(utc(2024, 2, 29, 23, 0)), Some(utc(2024, 3, 3, 23, 0)))
+        );
+        assert_eq!(
+            range("since 2024-03-01 until tuesday").unwrap(),
+            (Some(utc(2024, 2, 29, 23, 0)), Some(utc(2024, 3, 5, 23, 0)))
+        );
+        assert_eq!(range("").unwrap(), (None, None));
+    }
+
+    #[test]
+    fn unparseable_ranges_are_errors() {
+        for phrase in [
+            "lsat 24h",
+            "last",
+            "last 0h",
+            "last 3 fortnights",
+            "since",
+            "since someday",
+            "2024-03-01..",
+            "2024-03-05..2024-03-01",
+            "since 2099-01-01",
+            "last 24h please",
+        ] {
+            assert!(range(phrase).is_err(), "{phrase:?} was accepted");
+        }
+        let err = range("since someday").unwrap_err().to_string();
+        assert!(err.contains("since someday"));
+        assert!(err.contains("last 24h"));
+    }
+
+    #[test]
+    fn resume_splits_channel_from_range() {
+        let args = |list: &[&str]| list.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        let parse = |list: &[&str]| CommandType::parse_resume(&args(list), &wednesday_morning());
+
+        match parse(&["#general", "last", "24h"]).unwrap() {
+            CommandType::Resume {
+                channel,
+                since,
+                until,
+            } => {
+                assert_eq!(channel.as_deref(), Some("general"));
+                assert_eq!(since, Some(utc(2024, 3, 5, 8, 30)));
+                assert_eq!(until, None);
+            }
+            other => panic!("unexpected command {other:?}"),
+        }
+        match parse(&["since", "monday"]).unwrap() {
+            CommandType::Resume { channel, since, .. } => {
+                assert_eq!(channel, None);
+                assert!(since.is_some());
+            }
+            other => panic!("unexpected command {other:?}"),
+        }
+        match parse(&["random", "today"]).unwrap() {
+            CommandType::Resume { channel, .. } => assert_eq!(channel.as_deref(), Some("random")),
+            other => panic!("unexpected command {other:?}"),
+        }
+        assert!(parse(&["#general", "overnight"]).is_err());
+        assert!(CommandType::from_command("resume", &args(&["#general", "lsat", "24h"])).is_err());
+    }
+
+    #[test]
+    fn webhook_payload_carries_the_resolved_window() {
+        let command = CommandType::Resume {
+            channel: None,
+            since: Some(utc(2024, 3, 5, 8, 30)),
+            until: Some(utc(2024, 3, 6, 8, 30)),
+        };
+        let payload = command.to_webhook_payload("general", "", "U456", None);
+        assert_eq!(payload["since"], "2024-03-05T08:30:00+00:00");
+        assert_eq!(payload["until"], "2024-03-06T08:30:00+00:00");
+        assert!(payload["message"]
+            .as_str()
+            .unwrap()
+            .contains("between 2024-03-05 08:30 and 2024-03-06 08:30 UTC"));
+    }
+
+    #[test]
+    fn mention_is_stripped_from_the_question() {
+        assert_eq!(
+            strip_agent_mention("@zeroclaw: when is the release?"),
+            "when is the release?"
+        );
+        assert_eq!(strip_agent_mention("@ZC, summarize this"), "summarize this");
+        assert_eq!(strip_agent_mention("thoughts @zc?"), "thoughts ?");
+        assert_eq!(
+            strip_agent_mention("hey @zeroclaw what broke"),
+            "hey what broke"
+        );
+        assert_eq!(strip_agent_mention("no mention "), "no mention");
+        assert_eq!(
+            strip_agent_mention("İİ @ZeroClaw: ça marche?"),
+            "İİ ça marche?"
+        );
+    }
+
+    #[test]
+    fn mention_payload_is_marked_as_a_mention() {
+        let payload = CommandType::Mention {
+            text: "what broke?".to_string(),
+        }
+        .to_webhook_payload("general", "alice: deploy failed", "U456", None);
+        assert_eq!(payload["source"], "mention");
+        let message = payload["message"].as_str().unwrap();
+        assert!(message.contains("what broke?"));
+        assert!(message.contains("#general"));
+        assert!(message.contains("alice: deploy failed"));
+
+        let other = CommandType::Resume {
+            channel: None,
+            since: None,
+            until: None,
+        }
+        .to_webhook_payload("general", "", "U456", None);
+        assert!(other.get("source").is_none());
+    }
+
+    #[test]
+    fn context_drops_oldest_messages_to_fit_cap() {
+        let messages = (0..20)
+            .map(|i| context_message(&format!("{i}.0"), &"x".repeat(100)))
+            .collect::<Vec<_>>();
+        let context = ChannelContext::new("general", messages, 1024);
+
+        assert!(serde_json::to_vec(&context).unwrap().len() <= 1024);
+        assert!(!context.messages.is_empty());
+        assert_eq!(context.messages.last().unwrap().ts, "19.0");
+    }
+
+    #[test]
+    fn tldr_parses_explicit_thread() {
+        let args = vec!["1700000000.000100".to_string()];
+        match CommandType::from_command("tldr", &args).unwrap() {
+            CommandType::Tldr { thread_ts } => {
+                assert_eq!(thread_ts.as_deref(), Some("1700000000.000100"))
+            }
+            other => panic!("unexpected command {other:?}"),
+        }
+    }
+
+    #[test]
+    fn tldr_without_thread_is_a_user_facing_error() {
+        let command = CommandType::from_command("tldr", &[]).unwrap();
+        let err = command.resolve_thread(None).unwrap_err();
+        assert!(err.to_string().contains("No thread selected"));
+
+        let resolved = CommandType::from_command("tldr", &[])
+            .unwrap()
+            .resolve_thread(Some("1.0".to_string()))
+            .unwrap();
+        assert!(matches!(resolved, CommandType::Tldr { thread_ts: Some(ref ts) } if ts == "1.0"));
+    }
+
+    #[test]
+    fn tldr_payload_contains_parent_and_replies() {
+        let mut reply = context_message("2.0", "second");
+        reply.thread_ts = Some("1.0".to_string());
+        let thread = vec![context_message("1.0", "parent"), reply];
+        let payload = CommandType::Tldr {
+            thread_ts: Some("1.0".to_string()),
+        }
+        .to_thread_payload("general", "U456", &thread);
+
+        let message = payload["message"].as_str().unwrap();
+        assert!(message.contains("TL;DR"));
+        assert!(message.contains("alice: parent\nalice: second"));
+        assert_eq!(payload["context"]["messages"][0]["text"], "parent");
+        assert_eq!(payload["context"]["messages"][1]["thread_ts"], "1.0");
+    }
+
+    #[test]
+    fn translate_uses_default_language_and_selected_text() {
+        let command = CommandType::from_command("translate", &[])
+            .unwrap()
+            .resolve_translation("en", Some("Bonjour à tous".to_string()))
+            .unwrap();
+        let prompt = command.to_agent_prompt("general", "", "U456");
+        assert!(prompt.contains("into en"));
+        assert!(prompt.contains("Bonjour à tous"));
+
+        let args = vec!["de".to_string()];
+        match CommandType::from_command("translate", &args)
+            .unwrap()
+            .resolve_translation("en", Some("hello".to_string()))
+            .unwrap()
+        {
+            CommandType::Translate { target_lang, text } => {
+                assert_eq!(target_lang, "de");
+                assert_eq!(text, "hello");
+            }
+            other => panic!("unexpected command {other:?}"),
+        }
+    }
+
+    #[test]
+    fn translate_without_selection_is_a_user_facing_error() {
+        let err = CommandType::from_command("translate", &[])
+            .unwrap()
+            .resolve_translation("en", None)
+            .unwrap_err();
+        assert!(err.to_string().contains("No message selected"));
+    }
 }
@@ -1,15 +1,129 @@
 use anyhow::{anyhow, Result};
+use futures::stream::{self, BoxStream, Stream, StreamExt};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use slack_zc_slack::redact::redact_sensitive;
+use std::future::Future;
 use std::time::Duration;
+use thiserror::Error;
+use tokio::time::Instant;
 use tracing::{debug, info};
 
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const HEALTH_CHECK_BUDGET: Duration = Duration::from_secs(2);
+const MAX_RESPONSE_CHARS: usize = 20_000;
+/// Enough for a gateway's explanation without flooding the error popup.
+const MAX_ERROR_DETAIL_CHARS: usize = 300;
+const MAX_ATTEMPTS: u32 = 4;
+const BASE_BACKOFF: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Error)]
+pub enum GatewayError {
+    #[error("ZeroClaw bearer expired or was revoked — re-pair with the gateway")]
+    BearerExpired,
+
+    #[error("ZeroClaw gateway unavailable ({0})")]
+    Unavailable(reqwest::StatusCode),
+
+    #[error("ZeroClaw gateway is running but needs a pairing code")]
+    PairingRequired,
+}
+
+fn is_unavailable(status: reqwest::StatusCode) -> bool {
+    matches!(
+        status,
+        reqwest::StatusCode::BAD_GATEWAY | reqwest::StatusCode::SERVICE_UNAVAILABLE
+    )
+}
+
+/// The human part of a failed response's body: `error` and `detail` from a
+/// JSON body (`message` as a fallback), else the raw text. Redacted and cut
+/// short; `None` when the body is empty.
+async fn error_detail(response: reqwest::Response) -> Option<String> {
+    let body = response.text().await.unwrap_or_default();
+    let body = body.trim();
+    let detail = match serde_json::from_str::<Value>(body) {
+        Ok(json) => {
+            let field = |key: &str| {
+                json.get(key)
+                    .and_then(Value::as_str)
+                    .map(str::trim)
+                    .filter(|text| !text.is_empty())
+            };
+            match (field("error"), field("detail").or_else(|| field("message"))) {
+                (Some(error), Some(detail)) => format!("{}: {}", error, detail),
+                (Some(text), None) | (None, Some(text)) => text.to_string(),
+                (None, None) => body.to_string(),
+            }
+        }
+        Err(_) => body.to_string(),
+    };
+    if detail.is_empty() {
+        return None;
+    }
+    let detail = redact_sensitive(&detail);
+    if detail.chars().count() <= MAX_ERROR_DETAIL_CHARS {
+        return Some(detail);
+    }
+    let mut cut: String = detail.chars().take(MAX_ERROR_DETAIL_CHARS).collect();
+    cut.push('…');
+    Some(cut)
+}
+
+/// Connect failures and 502/503 are worth retrying: the gateway is usually
+/// still binding its port or restarting. Everything else fails fast.
+fn is_transient(error: &anyhow::Error) -> bool {
+    if let Some(GatewayError::Unavailable(_)) = error.downcast_ref::<GatewayError>() {
+        return true;
+    }
+    error
+        .downcast_ref::<reqwest::Error>()
+        .is_some_and(|e| e.is_connect())
+}
+
+/// Runs `operation` with exponential backoff until it succeeds, fails with a
+/// non-transient error, or the next attempt would not fit in `budget`. Each
+/// attempt receives the time left so per-request timeouts share the budget.
+async fn with_retry<T, F, Fut>(budget: Duration, mut operation: F) -> Result<T>
+where
+    F: FnMut(Duration) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let deadline = Instant::now() + budget;
+    let mut attempt = 0;
+
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        match operation(remaining).await {
+            Ok(result) => return Ok(result),
+            Err(e) => {
+                attempt += 1;
+                let delay = BASE_BACKOFF * 2u32.pow(attempt - 1);
+                if attempt >= MAX_ATTEMPTS
+                    || !is_transient(&e)
+                    || Instant::now() + delay >= deadline
+                {
+                    return Err(e);
+                }
+                debug!(
+                    attempt,
+                    ?delay,
+                    "Retrying gateway request after error: {}",
+                    redact_sensitive(&e.to_string())
+                );
+                tokio::time::sleep(delay).await;
+            }
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct GatewayClient {
     http: Client,
     base_url: String,
     bearer: Option<String>,
+    request_timeout: Duration,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,15 +133,18 @@ pub struct PairResponse {
 
 impl GatewayClient {
     pub fn new(port: u16) -> Self {
+        // The gateway is always on loopback; never send it through a proxy.
         let http = Client::builder()
             .user_agent("slack-zc/0.2")
             .connect_timeout(Duration::from_secs(5))
+            .no_proxy()
             .build()
             .unwrap_or_else(|_| Client::new());
         Self {
             http,
             base_url: format!("http://127.0.0.1:{}", port),
             bearer: None,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
         }
     }
 
@@ -36,35 +153,67 @@ impl GatewayClient {
         self
     }
 
-    pub async fn pair(&mut self, code: &str) -> Result<String> {
-        let response = self
-            .http
-            .post(format!("{}/pair", self.base_url))
-            .header("X-Pairing-Code", code)
-            .send()
-            .await?;
+    /// Request-level timeout for webhook calls. Connect timeouts stay short.
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
 
-        if !response.status().is_success() {
-            return Err(anyhow!("Pairing failed: {}", response.status()));
-        }
+    pub fn request_timeout(&self) -> Duration {
+        self.request_timeout
+    }
+
+    pub async fn pair(&mut self, code: &str) -> Result<String> {
+        let url = format!("{}/pair", self.base_url);
+        let data: PairResponse = with_retry(self.request_timeout, |remaining| {
+            let request = self
+                .http
+                .post(&url)
+                .header("X-Pairing-Code", code)
+                .timeout(remaining);
+            async move {
+                let response = request.send().await?;
+                let status = response.status();
+                if is_unavailable(status) {
+                    return Err(GatewayError::Unavailable(status).into());
+                }
+                if !status.is_success() {
+                    return Err(match error_detail(response).await {
+                        Some(detail) => anyhow!("Pairing failed: {}: {}", status, detail),
+                        None => anyhow!("Pairing failed: {}", status),
+                    });
+                }
+                Ok(response.json().await?)
+            }
+        })
+        .await?;
 
-        let data: PairResponse = response.json().await?;
         info!("Successfully paired with ZeroClaw gateway");
         self.bearer = Some(data.token.clone());
         Ok(data.token)
     }
 
     pub async fn health_check(&self) -> Result<bool> {
-        let mut request = self.http.get(format!("{}/health", self.base_url));
-
-        if let Some(ref bearer) = self.bearer {
-            request = request.header("Authorization", format!("Bearer {}", bearer));
-        }
+        let url = format!("{}/health", self.base_url);
+        let result = with_retry(HEALTH_CHECK_BUDGET, |remaining| {
+            let mut request = self.http.get(&url).timeout(remaining);
+            if let Some(ref bearer) = self.bearer {
+                request = request.header("Authorization", format!("Bearer {}", bearer));
+            }
+            async move {
+                let status = request.send().await?.status();
+                if is_unavailable(status) {
+                    return Err(GatewayError::Unavailable(status).into());
+                }
+                Ok(status.is_success())
+            }
+        })
+        .await;
 
-        match request.send().await {
-            Ok(response) => Ok(response.status().is_success()),
+        match result {
+            Ok(healthy) => Ok(healthy),
             Err(e) => {
-                debug!("Health check failed: {}", e);
+                debug!("Health check failed: {}", redact_sensitive(&e.to_string()));
                 Ok(false)
             }
         }
@@ -106,48 +255,154 @@ impl GatewayClient {
         Ok(response.status().is_success())
     }
 
+    /// True when the gateway answers API calls without any bearer, i.e. it
+    /// runs with pairing disabled.
+    pub async fn accepts_unauthenticated(&self) -> Result<bool> {
+        let response = self
+            .http
+            .get(format!("{}/api/status", self.base_url))
+            .send()
+            .await?;
+
+        Ok(response.status().is_success())
+    }
+
     pub async fn send_to_agent(&self, payload: &serde_json::Value) -> Result<String> {
+        let response = self.send_webhook(payload, false).await?;
+
+        let text = response.text().await?;
+        Ok(bound_response(extract_response_text(text)))
+    }
+
+    /// Streams the agent response as text deltas.
+    ///
+    /// Asks the gateway for `text/event-stream`; SSE `data:` frames and
+    /// chunked plain-text bodies are yielded as they arrive, while a JSON
+    /// body falls back to the buffered behavior and yields a single item.
+    pub fn send_to_agent_streaming(
+        &self,
+        payload: &serde_json::Value,
+    ) -> impl Stream<Item = Result<String>> + Send + 'static {
+        let client = self.clone();
+        let payload = payload.clone();
+
+        stream::once(async move { client.open_webhook_stream(&payload).await }).flat_map(|opened| {
+            match opened {
+                Ok(deltas) => deltas,
+                Err(e) => stream::once(async move { Err(e) }).boxed(),
+            }
+        })
+    }
+
+    async fn open_webhook_stream(
+        &self,
+        payload: &serde_json::Value,
+    ) -> Result<BoxStream<'static, Result<String>>> {
+        let response = self.send_webhook(payload, true).await?;
+
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        if content_type.starts_with("text/event-stream") {
+            debug!("Gateway webhook responded with an event stream");
+            let mut decoder = SseDecoder::default();
+            let deltas = response
+                .bytes_stream()
+                .map(move |chunk| match chunk {
+                    Ok(bytes) => Ok(decoder.push(&bytes)),
+                    Err(e) => Err(anyhow!(e)),
+                })
+                .flat_map(|decoded| match decoded {
+                    Ok(events) => stream::iter(events.into_iter().map(Ok)).boxed(),
+                    Err(e) => stream::once(async move { Err(e) }).boxed(),
+                })
+                .take_while(|event| {
+                    let done = matches!(event, Ok(SseEvent::Done));
+                    async move { !done }
+                })
+                .filter_map(|event| async move {
+                    match event {
+                        Ok(SseEvent::Data(data)) => extract_stream_delta(&data).map(Ok),
+                        Ok(SseEvent::Done) => None,
+                        Err(e) => Some(Err(e)),
+                    }
+                });
+            return Ok(deltas.boxed());
+        }
+
+        if content_type.starts_with("application/json") || content_type.is_empty() {
+            let text = response.text().await?;
+            let text = bound_response(extract_response_text(text));
+            return Ok(stream::once(async move { Ok(text) }).boxed());
+        }
+
+        debug!("Gateway webhook responded with chunked {}", content_type);
+        let mut decoder = TextDecoder::default();
+        let deltas = response
+            .bytes_stream()
+            .map(move |chunk| match chunk {
+                Ok(bytes) => Ok(decoder.push(&bytes)),
+                Err(e) => Err(anyhow!(e)),
+            })
+            .take_while(|decoded| {
+                let spent = matches!(decoded, Ok(None));
+                async move { !spent }
+            })
+            .filter_map(|decoded| async move {
+                match decoded {
+                    Ok(Some(text)) => (!text.is_empty()).then_some(Ok(text)),
+                    Ok(None) => None,
+                    Err(e) => Some(Err(e)),
+                }
+            });
+        Ok(deltas.boxed())
+    }
+
+    /// Posts to `/webhook`, retrying transient failures within the request
+    /// timeout. Only the response head is awaited here.
+    async fn send_webhook(
+        &self,
+        payload: &serde_json::Value,
+        streaming: bool,
+    ) -> Result<reqwest::Response> {
+        with_retry(self.request_timeout, |remaining| {
+            let request = self.webhook_request(streaming, remaining).json(payload);
+            async move { Self::check_webhook_status(request.send().await?).await }
+        })
+        .await
+    }
+
+    fn webhook_request(&self, streaming: bool, timeout: Duration) -> reqwest::RequestBuilder {
         let mut request = self.http.post(format!("{}/webhook", self.base_url));
         if let Some(bearer) = self.bearer.as_ref() {
             request = request.header("Authorization", format!("Bearer {}", bearer));
         }
+        if streaming {
+            request = request.header(reqwest::header::ACCEPT, "text/event-stream");
+        }
+        request.timeout(timeout)
+    }
 
-        let response = request
-            .timeout(Duration::from_secs(55))
-            .json(payload)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            let body = body.trim();
-            if body.is_empty() {
-                return Err(anyhow!("Webhook failed: {}", status));
-            }
-            return Err(anyhow!("Webhook failed: {}: {}", status, body));
+    async fn check_webhook_status(response: reqwest::Response) -> Result<reqwest::Response> {
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+        if status == reqwest::StatusCode::UNAUTHORIZED {
+            return Err(GatewayError::BearerExpired.into());
+        }
+        if is_unavailable(status) {
+            return Err(GatewayError::Unavailable(status).into());
         }
 
-        let text = response.text().await?;
-        let parsed_text = match serde_json::from_str::<Value>(&text) {
-            Ok(Value::Object(map)) => map
-                .get("response")
-                .and_then(Value::as_str)
-                .map(ToOwned::to_owned)
-                .or_else(|| {
-                    map.get("message")
-                        .and_then(Value::as_str)
-                        .map(ToOwned::to_owned)
-                })
-                .unwrap_or(text),
-            _ => text,
-        };
-        let bounded = if parsed_text.chars().count() > 20_000 {
-            parsed_text.chars().take(20_000).collect()
-        } else {
-            parsed_text
-        };
-        Ok(bounded)
+        match error_detail(response).await {
+            Some(detail) => Err(anyhow!("Webhook failed: {}: {}", status, detail)),
+            None => Err(anyhow!("Webhook failed: {}", status)),
+        }
     }
 
     pub fn is_paired(&self) -> bool {
@@ -158,3 +413,335 @@ impl GatewayClient {
         self.bearer.as_ref()
     }
 }
+
+fn extract_response_text(text: String) -> String {
+    match serde_json::from_str::<Value>(&text) {
+        Ok(Value::Object(map)) => map
+            .get("response")
+            .and_then(Value::as_str)
+            .map(ToOwned::to_owned)
+            .or_else(|| {
+                map.get("message")
+                    .and_then(Value::as_str)
+                    .map(ToOwned::to_owned)
+            })
+            .unwrap_or(text),
+        _ => text,
+    }
+}
+
+/// Pulls the text delta out of one SSE `data:` payload. JSON payloads are
+/// read defensively (`delta`, then `response`, then `message`); anything
+/// else is taken as raw text.
+fn extract_stream_delta(data: &str) -> Option<String> {
+    let delta = match serde_json::from_str::<Value>(data) {
+        Ok(Value::Object(map)) => ["delta", "response", "message", "content"]
+            .iter()
+            .find_map(|key| map.get(*key).and_then(Value::as_str))
+            .map(ToOwned::to_owned)?,
+        Ok(Value::String(text)) => text,
+        _ => data.to_string(),
+    };
+    (!delta.is_empty()).then_some(delta)
+}
+
+fn bound_response(text: String) -> String {
+    if text.chars().count() > MAX_RESPONSE_CHARS {
+        text.chars().take(MAX_RESPONSE_CHARS).collect()
+    } else {
+        text
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum SseEvent {
+    Data(String),
+    Done,
+}
+
+/// Incremental decoder for `text/event-stream` bodies. Bytes are buffered
+/// until a blank line terminates an event so multi-byte characters split
+/// across network chunks are decoded intact.
+#[derive(Default)]
+struct SseDecoder {
+    buffer: Vec<u8>,
+}
+
+impl SseDecoder {
+    fn push(&mut self, bytes: &[u8]) -> Vec<SseEvent> {
+        self.buffer.extend_from_slice(bytes);
+        let mut events = Vec::new();
+
+        while let Some((end, separator_len)) = Self::find_event_end(&self.buffer) {
+            let raw: Vec<u8> = self.buffer.drain(..end + separator_len).collect();
+            let raw = String::from_utf8_lossy(&raw[..end]);
+
+            let data = raw
+                .lines()
+                .filter_map(|line| line.strip_prefix("data:"))
+                .map(|value| value.strip_prefix(' ').unwrap_or(value))
+                .collect::<Vec<_>>();
+            if data.is_empty() {
+                continue;
+            }
+
+            let data = data.join("\n");
+            if data.trim() == "[DONE]" {
+                events.push(SseEvent::Done);
+            } else {
+                events.push(SseEvent::Data(data));
+            }
+        }
+
+        events
+    }
+
+    fn find_event_end(buffer: &[u8]) -> Option<(usize, usize)> {
+        let lf = buffer.windows(2).position(|w| w == b"\n\n").map(|i| (i, 2));
+        let crlf = buffer
+            .windows(4)
+            .position(|w| w == b"\r\n\r\n")
+            .map(|i| (i, 4));
+        match (lf, crlf) {
+            (Some(a), Some(b)) => Some(if a.0 <= b.0 { a } else { b }),
+            (a, b) => a.or(b),
+        }
+    }
+}
+
+/// Incremental decoder for chunked plain-text bodies. An incomplete UTF-8
+/// sequence at the end of a chunk is held back for the next one, and output
+/// stops once `MAX_RESPONSE_CHARS` have been yielded.
+struct TextDecoder {
+    pending: Vec<u8>,
+    remaining: usize,
+}
+
+impl Default for TextDecoder {
+    fn default() -> Self {
+        Self {
+            pending: Vec::new(),
+            remaining: MAX_RESPONSE_CHARS,
+        }
+    }
+}
+
+impl TextDecoder {
+    /// The text completed by `bytes`, or `None` once the bound is reached.
+    fn push(&mut self, bytes: &[u8]) -> Option<String> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.pending.extend_from_slice(bytes);
+
+        let mut text = String::new();
+        loop {
+            match std::str::from_utf8(&self.pending) {
+                Ok(complete) => {
+                    text.push_str(complete);
+                    self.pending.clear();
+                    break;
+                }
+                Err(e) => {
+                    let valid = e.valid_up_to();
+                    text.push_str(&String::from_utf8_lossy(&self.pending[..valid]));
+                    match e.error_len() {
+                        Some(len) => {
+                            text.push(char::REPLACEMENT_CHARACTER);
+                            self.pending.drain(..valid + len);
+                        }
+                        None => {
+                            self.pending.drain(..valid);
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        let text: String = text.chars().take(self.remaining).collect();
+        self.remaining -= text.chars().count();
+        Some(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sse_decoder_joins_events_split_across_chunks() {
+        let mut decoder = SseDecoder::default();
+        assert!(decoder.push(b"data: {\"delta\":\"Hel").is_empty());
+        let events = decoder.push(b"lo\"}\n\ndata: world\n\ndata: [DONE]\n\n");
+        assert_eq!(
+            events,
+            vec![
+                SseEvent::Data("{\"delta\":\"Hello\"}".to_string()),
+                SseEvent::Data("world".to_string()),
+                SseEvent::Done,
+            ]
+        );
+    }
+
+    #[test]
+    fn sse_decoder_keeps_multibyte_characters_intact() {
+        let mut decoder = SseDecoder::default();
+        let bytes = "data: résumé\r\n\r\n".as_bytes();
+        let (head, tail) = bytes.split_at(9);
+        assert!(decoder.push(head).is_empty());
+        assert_eq!(
+            decoder.push(tail),
+            vec![SseEvent::Data("résumé".to_string())]
+        );
+    }
+
+    #[test]
+    fn text_decoder_holds_back_a_split_code_point() {
+        let mut decoder = TextDecoder::default();
+        let bytes = "café ünï".as_bytes();
+        let (head, tail) = bytes.split_at(4);
+        assert_eq!(decoder.push(head), Some("caf".to_string()));
+        assert_eq!(decoder.push(tail), Some("é ünï".to_string()));
+
+        let mut decoder = TextDecoder {
+            remaining: 4,
+            ..TextDecoder::default()
+        };
+        assert_eq!(decoder.push(b"abc"), Some("abc".to_string()));
+        assert_eq!(decoder.push(b"def"), Some("d".to_string()));
+        assert_eq!(decoder.push(b"ghi"), None);
+    }
+
+    #[test]
+    fn stream_delta_prefers_delta_then_response() {
+        assert_eq!(
+            extract_stream_delta(r#"{"delta":"a","response":"b"}"#),
+            Some("a".to_string())
+        );
+        assert_eq!(
+            extract_stream_delta(r#"{"response":"b"}"#),
+            Some("b".to_string())
+        );
+        assert_eq!(extract_stream_delta("plain"), Some("plain".to_string()));
+        assert_eq!(extract_stream_delta(r#"{"delta":""}"#), None);
+    }
+
+    fn gateway_for(server: &wiremock::MockServer) -> GatewayClient {
+        GatewayClient::new(server.address().port())
+    }
+
+    #[tokio::test]
+    async fn send_to_agent_retries_unavailable_then_succeeds() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        // Mounted first, so the first two requests get a 503.
+        Mock::given(method("POST"))
+            .and(path("/webhook"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/webhook"))
+            .and(header("authorization", "Bearer token"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"response": "ok"})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let gateway = gateway_for(&server).with_bearer("token".to_string());
+        let response = gateway
+            .send_to_agent(&serde_json::json!({"message": "hi"}))
+            .await
+            .unwrap();
+
+        assert_eq!(response, "ok");
+    }
+
+    #[tokio::test]
+    async fn send_to_agent_reports_expired_bearer_without_retrying() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/webhook"))
+            .respond_with(ResponseTemplate::new(401))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let gateway = gateway_for(&server).with_bearer("stale".to_string());
+        let err = gateway
+            .send_to_agent(&serde_json::json!({"message": "hi"}))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<GatewayError>(),
+            Some(GatewayError::BearerExpired)
+        ));
+    }
+
+    #[tokio::test]
+    async fn failed_requests_surface_the_gateway_explanation() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(422).set_body_json(serde_json::json!({
+                "error": "missing_channel_access",
+                "detail": "bot is not in #incident-4412",
+            })))
+            .mount(&server)
+            .await;
+
+        let mut gateway = gateway_for(&server).with_bearer("token".to_string());
+        let err = gateway
+            .send_to_agent(&serde_json::json!({"message": "hi"}))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Webhook failed: 422 Unprocessable Entity: missing_channel_access: \
+             bot is not in #incident-4412"
+        );
+
+        let err = gateway.pair("123456").await.unwrap_err();
+        assert!(err
+            .to_string()
+            .ends_with("missing_channel_access: bot is not in #incident-4412"));
+    }
+
+    #[tokio::test]
+    async fn retries_stop_when_budget_is_spent() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/webhook"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let gateway = gateway_for(&server).with_request_timeout(Duration::from_millis(400));
+        let err = gateway
+            .send_to_agent(&serde_json::json!({"message": "hi"}))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<GatewayError>(),
+            Some(GatewayError::Unavailable(_))
+        ));
+    }
+}
@@ -2,5 +2,5 @@ pub mod commands;
 pub mod gateway;
 pub mod runner;
 
-pub use gateway::GatewayClient;
+pub use gateway::{GatewayClient, GatewayError};
 pub use runner::{AgentRunner, AgentStatus};
@@ -1,34 +1,108 @@
-use crate::gateway::GatewayClient;
+use crate::gateway::{GatewayClient, GatewayError};
 use anyhow::{anyhow, Result};
 use regex::Regex;
 use serde::Deserialize;
+use slack_zc_slack::redact::redact_sensitive;
+use std::collections::VecDeque;
 use std::fs;
 use std::net::TcpListener;
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::Duration;
 use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
 use tokio::process::Command;
-use tokio::sync::mpsc;
-use tracing::{debug, info};
+use tokio::sync::{mpsc, oneshot, watch};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+const MAX_LOG_LINES: usize = 500;
+const ERROR_LOG_LINES: usize = 5;
+const DEFAULT_PAIRING_TIMEOUT: Duration = Duration::from_secs(30);
+/// How many ports past the configured one we try when it is taken.
+const FALLBACK_PORTS: u16 = 10;
+
+/// How long a freshly spawned gateway gets to start answering.
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(10);
+
+type OutputLog = Arc<Mutex<VecDeque<String>>>;
+
+/// Polls the authenticated status until the gateway accepts the bearer or
+/// `wait` runs out. Refused connections mean the child has not bound its
+/// port yet, so they are retried like a "no" rather than returned.
+async fn wait_for_auth(gateway: &GatewayClient, wait: Duration) -> Result<bool> {
+    let deadline = tokio::time::Instant::now() + wait;
+    while tokio::time::Instant::now() < deadline {
+        match gateway.api_auth_check().await {
+            Ok(true) => return Ok(true),
+            Ok(false) => {}
+            Err(e)
+                if e.downcast_ref::<reqwest::Error>()
+                    .is_some_and(reqwest::Error::is_connect) => {}
+            Err(e) => return Err(e),
+        }
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+    Ok(false)
+}
+
+/// The first port from `requested` through `requested + FALLBACK_PORTS`
+/// that `is_free` accepts.
+fn pick_port(requested: u16, is_free: impl Fn(u16) -> bool) -> Option<u16> {
+    (requested..=requested.saturating_add(FALLBACK_PORTS)).find(|&port| is_free(port))
+}
 
+/// Whether a gateway could bind `port` on localhost right now.
+pub fn port_is_free(port: u16) -> bool {
+    TcpListener::bind(("127.0.0.1", port)).is_ok()
+}
+
+/// Drains one output pipe into the runner's log, forwarding lines to `tx`
+/// for as long as someone is listening.
 async fn pump_output<R>(
     reader: R,
     source: &'static str,
     tx: mpsc::UnboundedSender<(&'static str, String)>,
+    log: OutputLog,
 ) where
     R: AsyncRead + Unpin + Send + 'static,
 {
+    // Pairing codes are one-time secrets; keep them out of the stored log.
+    let code_re = Regex::new(r"\b(?:[0-9][\s-]?){5}[0-9]\b").ok();
     let mut lines = BufReader::new(reader).lines();
     while let Ok(Some(line)) = lines.next_line().await {
+        if let Ok(mut log) = log.lock() {
+            if log.len() >= MAX_LOG_LINES {
+                log.pop_front();
+            }
+            let stored = match code_re.as_ref() {
+                Some(re) if line.to_lowercase().contains("code") => {
+                    re.replace_all(&line, "******").into_owned()
+                }
+                _ => line.clone(),
+            };
+            log.push_back(format!("[{}] {}", source, redact_sensitive(&stored)));
+        }
         let _ = tx.send((source, line));
     }
 }
 
-fn extract_pairing_code(line: &str, re: &Regex) -> Option<String> {
-    re.captures(line)
-        .and_then(|caps| caps.get(1))
-        .map(|m| m.as_str().to_string())
+/// Pulls a 6-digit pairing code out of one line of gateway output.
+/// Accepts grouped forms like "Pairing code: 123 456" or "code 123-456".
+pub fn parse_pairing_code(line: &str) -> Option<String> {
+    static PAIRING_RE: OnceLock<Regex> = OnceLock::new();
+    let re = PAIRING_RE.get_or_init(|| {
+        Regex::new(r"(?i)(?:pair(?:ing)?[\s._-]*code|code)\D{0,12}\b((?:[0-9][\s-]?){5}[0-9])\b")
+            .unwrap()
+    });
+    let digits: String = re
+        .captures(line)?
+        .get(1)?
+        .as_str()
+        .chars()
+        .filter(char::is_ascii_digit)
+        .collect();
+    (digits.len() == 6).then_some(digits)
 }
 
 #[derive(Debug, Deserialize)]
@@ -36,11 +110,59 @@ struct AuthProfilesFile {
     active_profiles: Option<std::collections::HashMap<String, String>>,
 }
 
+/// Owns a spawned gateway process and reports when it exits on its own.
+struct Supervisor {
+    stop: Option<oneshot::Sender<()>>,
+    exited: watch::Receiver<Option<String>>,
+    task: JoinHandle<()>,
+}
+
+impl Supervisor {
+    fn spawn(mut child: tokio::process::Child) -> Self {
+        let (stop_tx, stop_rx) = oneshot::channel::<()>();
+        let (exit_tx, exit_rx) = watch::channel(None);
+
+        let task = tokio::spawn(async move {
+            tokio::select! {
+                status = child.wait() => {
+                    let status = match status {
+                        Ok(status) => status.to_string(),
+                        Err(e) => e.to_string(),
+                    };
+                    info!("ZeroClaw gateway exited unexpectedly ({})", status);
+                    let _ = exit_tx.send(Some(status));
+                }
+                // Either an explicit stop or the runner being dropped.
+                _ = stop_rx => {
+                    let _ = child.kill().await;
+                    let _ = child.wait().await;
+                    info!("ZeroClaw gateway stopped");
+                }
+            }
+        });
+
+        Self {
+            stop: Some(stop_tx),
+            exited: exit_rx,
+            task,
+        }
+    }
+
+    async fn stop(mut self) {
+        if let Some(stop) = self.stop.take() {
+            let _ = stop.send(());
+        }
+        let _ = self.task.await;
+    }
+}
+
 pub struct AgentRunner {
     binary_path: String,
     gateway_port: u16,
-    child: Option<tokio::process::Child>,
+    supervisor: Option<Supervisor>,
     gateway: Option<GatewayClient>,
+    output_log: OutputLog,
+    pairing_timeout: Duration,
 }
 
 #[derive(Debug, Clone)]
@@ -49,6 +171,7 @@ pub enum AgentStatus {
     Starting,
     Pairing,
     Active,
+    Restarting { attempt: u32, max: u32 },
     Error(String),
 }
 
@@ -57,11 +180,60 @@ impl AgentRunner {
         Self {
             binary_path,
             gateway_port,
-            child: None,
+            supervisor: None,
             gateway: None,
+            output_log: Arc::new(Mutex::new(VecDeque::new())),
+            pairing_timeout: DEFAULT_PAIRING_TIMEOUT,
         }
     }
 
+    /// How long `start_and_pair` watches the gateway output for a code
+    /// before asking the user to type it in.
+    pub fn with_pairing_timeout(mut self, timeout: Duration) -> Self {
+        self.pairing_timeout = timeout;
+        self
+    }
+
+    /// Port the gateway is (or will be) reached on. Differs from the
+    /// configured one when that was taken by another service.
+    pub fn gateway_port(&self) -> u16 {
+        self.gateway_port
+    }
+
+    /// Makes sure the gateway we are about to spawn can bind its port. A
+    /// ZeroClaw gateway already there should be attached to, not replaced;
+    /// anything else moves us to the next free port in the fallback range.
+    async fn claim_port(&mut self) -> Result<()> {
+        let requested = self.gateway_port;
+        if port_is_free(requested) {
+            return Ok(());
+        }
+        if GatewayClient::new(requested)
+            .health_check()
+            .await
+            .unwrap_or(false)
+        {
+            return Err(anyhow!(
+                "A ZeroClaw gateway is already running on port {}; attach to it instead of starting another",
+                requested
+            ));
+        }
+        let last = requested.saturating_add(FALLBACK_PORTS);
+        let Some(port) = pick_port(requested, port_is_free) else {
+            return Err(anyhow!(
+                "Port {} is in use by another service (not a ZeroClaw gateway) and ports up to {} are taken too; set zeroclaw.gateway_port to a free port",
+                requested,
+                last
+            ));
+        };
+        warn!(
+            "Port {} is in use by another service (not a ZeroClaw gateway); using port {}",
+            requested, port
+        );
+        self.gateway_port = port;
+        Ok(())
+    }
+
     pub async fn check_binary(&self) -> Result<()> {
         let output = Command::new(&self.binary_path)
             .arg("--version")
@@ -78,7 +250,13 @@ impl AgentRunner {
         }
     }
 
+    /// Spawns a gateway and pairs with the code it prints on stdout or
+    /// stderr. If no code shows up within the pairing timeout the gateway is
+    /// kept running and `GatewayError::PairingRequired` is returned, so the
+    /// caller can collect the code by hand and finish with
+    /// `pair_with_running_gateway`.
     pub async fn start_and_pair(&mut self) -> Result<GatewayClient> {
+        self.claim_port().await?;
         info!("Starting ZeroClaw gateway on port {}", self.gateway_port);
 
         let mut child = Command::new(&self.binary_path)
@@ -87,50 +265,41 @@ impl AgentRunner {
             .arg(self.gateway_port.to_string())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
+            // Don't leave an unpaired gateway holding the port if pairing fails.
+            .kill_on_drop(true)
             .spawn()?;
 
-        let stdout = child
-            .stdout
-            .take()
-            .ok_or_else(|| anyhow!("Failed to capture stdout"))?;
-        let stderr = child
-            .stderr
-            .take()
-            .ok_or_else(|| anyhow!("Failed to capture stderr"))?;
-
-        let (tx, mut rx) = mpsc::unbounded_channel();
-        tokio::spawn(pump_output(stdout, "stdout", tx.clone()));
-        tokio::spawn(pump_output(stderr, "stderr", tx));
+        let mut rx = self.capture_output(&mut child)?;
 
-        let re = Regex::new(r"(?i)(?:pair(?:ing)?[\s._-]*code|code)\D{0,12}([0-9]{6})").unwrap();
-
-        let deadline = tokio::time::Instant::now() + Duration::from_secs(15);
+        let deadline = tokio::time::Instant::now() + self.pairing_timeout;
         let code = loop {
             if let Ok(Some(status)) = child.try_wait() {
-                return Err(anyhow!(
+                return Err(self.with_recent_output(format!(
                     "ZeroClaw exited before emitting a pairing code (status: {})",
                     status
-                ));
+                )));
             }
 
             let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
             if remaining.is_zero() {
-                return Err(anyhow!("Timeout waiting for ZeroClaw pairing code"));
+                info!("No pairing code detected, waiting for manual entry");
+                self.supervisor = Some(Supervisor::spawn(child));
+                return Err(GatewayError::PairingRequired.into());
             }
 
             match tokio::time::timeout(remaining.min(Duration::from_millis(250)), rx.recv()).await {
                 Ok(Some((source, line))) => {
-                    debug!("ZeroClaw {}: {}", source, line);
-                    if let Some(code) = extract_pairing_code(&line, &re) {
+                    debug!("ZeroClaw {}: {}", source, redact_sensitive(&line));
+                    if let Some(code) = parse_pairing_code(&line) {
                         break code;
                     }
                 }
                 Ok(None) => {
                     if let Ok(Some(status)) = child.try_wait() {
-                        return Err(anyhow!(
+                        return Err(self.with_recent_output(format!(
                             "ZeroClaw exited before emitting a pairing code (status: {})",
                             status
-                        ));
+                        )));
                     }
                 }
                 Err(_) => {}
@@ -140,50 +309,92 @@ impl AgentRunner {
         info!("ZeroClaw pairing code obtained (redacted)");
 
         let mut gateway = GatewayClient::new(self.gateway_port);
-        gateway.pair(&code).await?;
+        if let Err(e) = gateway.pair(&code).await {
+            return Err(self.with_recent_output(e.to_string()));
+        }
 
-        self.child = Some(child);
+        self.supervisor = Some(Supervisor::spawn(child));
         self.gateway = Some(gateway.clone());
 
         Ok(gateway)
     }
 
     pub async fn start_with_bearer(&mut self, bearer: &str) -> Result<GatewayClient> {
-        info!("Starting ZeroClaw gateway with existing bearer");
+        self.claim_port().await?;
+        info!(
+            "Starting ZeroClaw gateway on port {} with existing bearer",
+            self.gateway_port
+        );
 
-        let child = Command::new(&self.binary_path)
+        let mut child = Command::new(&self.binary_path)
             .arg("gateway")
             .arg("--port")
             .arg(self.gateway_port.to_string())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
+            .kill_on_drop(true)
             .spawn()?;
+        self.capture_output(&mut child)?;
 
         let gateway = GatewayClient::new(self.gateway_port).with_bearer(bearer.to_string());
-        let deadline = tokio::time::Instant::now() + Duration::from_secs(10);
-        let mut ready = false;
-
-        while tokio::time::Instant::now() < deadline {
-            if gateway.api_auth_check().await? {
-                ready = true;
-                break;
-            }
-            tokio::time::sleep(Duration::from_millis(250)).await;
-        }
-
-        if !ready {
-            return Err(anyhow!(
-                "Gateway health check failed after waiting for startup"
+        if !wait_for_auth(&gateway, STARTUP_TIMEOUT).await? {
+            return Err(self.with_recent_output(
+                "Gateway health check failed after waiting for startup".into(),
             ));
         }
 
-        self.child = Some(child);
+        self.supervisor = Some(Supervisor::spawn(child));
         self.gateway = Some(gateway.clone());
 
         info!("ZeroClaw gateway started and authenticated");
         Ok(gateway)
     }
 
+    /// Starts draining the child's stdout and stderr into the output log.
+    /// The returned receiver sees every line until it is dropped.
+    fn capture_output(
+        &self,
+        child: &mut tokio::process::Child,
+    ) -> Result<mpsc::UnboundedReceiver<(&'static str, String)>> {
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| anyhow!("Failed to capture stdout"))?;
+        let stderr = child
+            .stderr
+            .take()
+            .ok_or_else(|| anyhow!("Failed to capture stderr"))?;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(pump_output(
+            stdout,
+            "stdout",
+            tx.clone(),
+            self.output_log.clone(),
+        ));
+        tokio::spawn(pump_output(stderr, "stderr", tx, self.output_log.clone()));
+        Ok(rx)
+    }
+
+    fn with_recent_output(&self, message: String) -> anyhow::Error {
+        let recent = self.recent_output(ERROR_LOG_LINES);
+        if recent.is_empty() {
+            return anyhow!(message);
+        }
+        anyhow!("{}\nLast gateway output:\n{}", message, recent.join("\n"))
+    }
+
+    /// Up to `limit` of the most recent gateway output lines, oldest first.
+    pub fn recent_output(&self, limit: usize) -> Vec<String> {
+        let Ok(log) = self.output_log.lock() else {
+            return Vec::new();
+        };
+        log.iter()
+            .skip(log.len().saturating_sub(limit))
+            .cloned()
+            .collect()
+    }
+
     fn zeroclaw_home_dir() -> Result<PathBuf> {
         let home = std::env::var("HOME")
             .map_err(|_| anyhow!("HOME is not set; cannot locate ZeroClaw config"))?;
@@ -338,7 +549,8 @@ impl AgentRunner {
     pub async fn start_embedded_gateway(&mut self) -> Result<GatewayClient> {
         let config_dir = self.prepare_embedded_config_dir()?;
         let requested_port = self.gateway_port;
-        let gateway_port = if GatewayClient::new(requested_port).health_check().await? {
+        // Whatever holds the port, gateway or not, the embedded one steps aside.
+        let gateway_port = if !port_is_free(requested_port) {
             let listener = TcpListener::bind(("127.0.0.1", 0))
                 .map_err(|e| anyhow!("failed to reserve free port for embedded gateway: {}", e))?;
             let port = listener
@@ -355,7 +567,7 @@ impl AgentRunner {
             gateway_port
         );
 
-        let child = Command::new(&self.binary_path)
+        let mut child = Command::new(&self.binary_path)
             .arg("gateway")
             .arg("--config-dir")
             .arg(&config_dir)
@@ -363,10 +575,13 @@ impl AgentRunner {
             .arg(gateway_port.to_string())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
+            .kill_on_drop(true)
             .spawn()?;
 
+        self.capture_output(&mut child)?;
+
         let gateway = GatewayClient::new(gateway_port);
-        let deadline = tokio::time::Instant::now() + Duration::from_secs(10);
+        let deadline = tokio::time::Instant::now() + STARTUP_TIMEOUT;
         let mut ready = false;
 
         while tokio::time::Instant::now() < deadline {
@@ -378,12 +593,12 @@ impl AgentRunner {
         }
 
         if !ready {
-            return Err(anyhow!(
-                "Embedded gateway health check failed after waiting for startup"
+            return Err(self.with_recent_output(
+                "Embedded gateway health check failed after waiting for startup".into(),
             ));
         }
 
-        self.child = Some(child);
+        self.supervisor = Some(Supervisor::spawn(child));
         self.gateway_port = gateway_port;
         self.gateway = Some(gateway.clone());
 
@@ -410,6 +625,17 @@ impl AgentRunner {
         Ok(gateway)
     }
 
+    /// Whether something already answers `/health` on our port.
+    pub async fn probe_running_gateway(&self) -> bool {
+        GatewayClient::new(self.gateway_port)
+            .health_check()
+            .await
+            .unwrap_or(false)
+    }
+
+    /// Attaches to a gateway someone else started. Fails with
+    /// [`GatewayError::PairingRequired`] when it needs a pairing code; see
+    /// [`AgentRunner::pair_with_running_gateway`].
     pub async fn connect_to_running_gateway(&mut self) -> Result<GatewayClient> {
         info!(
             "Attempting to connect to existing ZeroClaw gateway on port {}",
@@ -417,38 +643,41 @@ impl AgentRunner {
         );
 
         let gateway = GatewayClient::new(self.gateway_port);
+        if !gateway.health_check().await? {
+            return Err(anyhow!(
+                "ZeroClaw gateway not accessible on port {}. Make sure it's running.",
+                self.gateway_port
+            ));
+        }
 
-        // Check if gateway is running and not paired
-        match gateway.check_pairing_status().await {
-            Ok(paired) => {
-                if paired {
-                    info!("Gateway is already paired but no bearer token stored");
-                    return Err(anyhow!(
-                        "Gateway already paired, but no valid runtime bearer is available."
-                    ));
-                }
-                // Not paired - gateway is running and waiting for a pairing code
-                info!("Gateway is running and waiting for pairing code");
-                Err(anyhow!(
-                    "Gateway needs pairing. Check your terminal for the 6-digit code."
-                ))
-            }
-            Err(_) => {
-                info!(
-                    "No running ZeroClaw gateway detected on port {}",
-                    self.gateway_port
-                );
-                Err(anyhow!(
-                    "ZeroClaw gateway not accessible. Make sure it's running."
-                ))
-            }
+        if gateway.accepts_unauthenticated().await.unwrap_or(false) {
+            info!("Existing gateway does not require pairing");
+            self.gateway = Some(gateway.clone());
+            return Ok(gateway);
         }
+
+        Err(GatewayError::PairingRequired.into())
+    }
+
+    /// Pairs with a gateway that is already running, using the code the user
+    /// read from that gateway's output. A process we spawned stays supervised.
+    pub async fn pair_with_running_gateway(&mut self, code: &str) -> Result<GatewayClient> {
+        let mut gateway = GatewayClient::new(self.gateway_port);
+        gateway.pair(code).await?;
+        self.gateway = Some(gateway.clone());
+        info!("Paired with existing ZeroClaw gateway");
+        Ok(gateway)
     }
 
     pub fn get_gateway(&self) -> Option<&GatewayClient> {
         self.gateway.as_ref()
     }
 
+    /// Bearer of the connected gateway, if it required pairing.
+    pub fn bearer(&self) -> Option<&str> {
+        self.gateway.as_ref()?.get_bearer().map(String::as_str)
+    }
+
     pub fn status(&self) -> AgentStatus {
         match &self.gateway {
             None => AgentStatus::Unavailable,
@@ -457,11 +686,37 @@ impl AgentRunner {
         }
     }
 
+    /// Resolves with the exit status once a gateway we spawned dies on its
+    /// own. `None` when this runner did not spawn the process.
+    pub fn exit_watcher(&self) -> Option<watch::Receiver<Option<String>>> {
+        self.supervisor.as_ref().map(|s| s.exited.clone())
+    }
+
+    /// Stops any gateway we own and starts a fresh one, reusing the bearer
+    /// when there is one.
+    pub async fn restart(&mut self) -> Result<GatewayClient> {
+        let bearer = self.bearer().map(ToOwned::to_owned);
+        let owned = self.supervisor.is_some();
+        self.shutdown().await;
+
+        // A gateway we merely attached to is someone else's: reattach to it.
+        if !owned && self.probe_running_gateway().await {
+            return match bearer {
+                Some(bearer) => self.connect_with_bearer(&bearer).await,
+                None => self.connect_to_running_gateway().await,
+            };
+        }
+
+        match bearer {
+            Some(bearer) => self.start_with_bearer(&bearer).await,
+            None => self.start_embedded_gateway().await,
+        }
+    }
+
+    /// Stops the gateway process if we spawned it; attached gateways keep running.
     pub async fn shutdown(&mut self) {
-        if let Some(mut child) = self.child.take() {
-            let _ = child.kill().await;
-            let _ = child.wait().await;
-            info!("ZeroClaw gateway stopped");
+        if let Some(supervisor) = self.supervisor.take() {
+            supervisor.stop().await;
         }
         self.gateway = None;
     }
@@ -469,9 +724,98 @@ impl AgentRunner {
 
 impl Drop for AgentRunner {
     fn drop(&mut self) {
-        if let Some(mut child) = self.child.take() {
-            let _ = child.start_kill();
-        }
+        // Dropping the supervisor's stop sender makes it kill the child.
+        self.supervisor = None;
         self.gateway = None;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_plain_pairing_code() {
+        assert_eq!(
+            parse_pairing_code("Pairing code: 482913"),
+            Some("482913".to_string())
+        );
+        assert_eq!(
+            parse_pairing_code("[gateway] pair code=004217 (expires in 5m)"),
+            Some("004217".to_string())
+        );
+    }
+
+    #[test]
+    fn parses_grouped_pairing_code() {
+        assert_eq!(
+            parse_pairing_code("Pairing code: 123 456"),
+            Some("123456".to_string())
+        );
+        assert_eq!(
+            parse_pairing_code("Enter code 123-456 in your client"),
+            Some("123456".to_string())
+        );
+    }
+
+    #[test]
+    fn ignores_lines_without_a_code() {
+        assert_eq!(parse_pairing_code("listening on 127.0.0.1:58080"), None);
+        assert_eq!(parse_pairing_code("exit code 1"), None);
+        assert_eq!(parse_pairing_code("error code 1234567"), None);
+    }
+
+    #[test]
+    fn taken_port_falls_back_within_the_range() {
+        assert_eq!(pick_port(8080, |_| true), Some(8080));
+        assert_eq!(pick_port(8080, |port| port > 8082), Some(8083));
+        assert_eq!(pick_port(8080, |port| port == 8090), Some(8090));
+        assert_eq!(pick_port(8080, |port| port > 8090), None);
+    }
+
+    #[tokio::test]
+    async fn port_held_by_another_service_moves_the_gateway() {
+        let holder = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let taken = holder.local_addr().unwrap().port();
+        let mut runner = AgentRunner::new("zeroclaw".to_string(), taken);
+
+        match runner.claim_port().await {
+            Ok(()) => {
+                assert_ne!(runner.gateway_port(), taken);
+                assert!(runner.gateway_port() <= taken.saturating_add(FALLBACK_PORTS));
+            }
+            // Every port in the range may be busy on a crowded machine.
+            Err(e) => assert!(e.to_string().contains(&format!("Port {}", taken))),
+        }
+    }
+
+    #[tokio::test]
+    async fn readiness_waits_for_a_gateway_that_binds_late() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let port = TcpListener::bind(("127.0.0.1", 0))
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let server = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(600)).await;
+            let listener = TcpListener::bind(("127.0.0.1", port)).unwrap();
+            let server = MockServer::builder().listener(listener).start().await;
+            Mock::given(method("GET"))
+                .and(path("/api/status"))
+                .and(header("Authorization", "Bearer fresh"))
+                .respond_with(ResponseTemplate::new(200))
+                .mount(&server)
+                .await;
+            server
+        });
+
+        let gateway = GatewayClient::new(port).with_bearer("fresh".to_string());
+        assert!(wait_for_auth(&gateway, Duration::from_secs(5))
+            .await
+            .unwrap());
+        drop(server.await.unwrap());
+    }
+}
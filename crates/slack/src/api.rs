@@ -1,19 +1,43 @@
-use crate::types::{Channel, FileInfo, Message, User};
+use crate::types::{Channel, FileInfo, Message, PostingRestriction, Reminder, SavedItem, User};
+use crate::users::UserDirectory;
 use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
 use rand::Rng;
 use reqwest::Client;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 use tokio::sync::RwLock;
 
-const SLACK_API_BASE: &str = "https://slack.com/api";
-const USER_CACHE_TTL: Duration = Duration::from_secs(600);
+/// Web API root used unless overridden with [`SlackApi::with_base_url`].
+pub const SLACK_API_BASE: &str = "https://slack.com/api";
 const MAX_RETRIES: u32 = 3;
 const BASE_DELAY_MS: u64 = 1000;
 const MAX_BACKOFF_MS: u64 = 30_000;
 
+/// Turns a group DM's API name (`mpdm-alice--bob--carol-1`) into
+/// `alice, bob, carol`, using display names where the handle is known.
+pub fn mpim_display_name(api_name: &str, users: &HashMap<String, User>) -> String {
+    let trimmed = api_name.strip_prefix("mpdm-").unwrap_or(api_name);
+    let trimmed = match trimmed.rsplit_once('-') {
+        Some((rest, suffix)) if suffix.chars().all(|c| c.is_ascii_digit()) => rest,
+        _ => trimmed,
+    };
+    trimmed
+        .split("--")
+        .filter(|handle| !handle.is_empty())
+        .map(|handle| {
+            users
+                .values()
+                .find(|user| user.name == handle)
+                .map(|user| user.display_name())
+                .unwrap_or_else(|| handle.to_string())
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -25,7 +49,9 @@ mod tests {
         let attempt_count = Arc::new(AtomicU32::new(0));
         let attempt_count_clone = attempt_count.clone();
 
-        let result: Result<&str, _> = with_retry(move || {
+        let health = std::sync::Mutex::new(ApiHealth::default());
+
+        let result: Result<&str, _> = with_retry(&health, move || {
             let attempt_count = attempt_count_clone.clone();
             async move {
                 let count = attempt_count.fetch_add(1, Ordering::SeqCst);
@@ -40,6 +66,9 @@ mod tests {
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "success");
+        let health = health.lock().unwrap();
+        assert!(health.last_success.is_some());
+        assert!(health.rate_limited_until.is_some());
     }
 
     #[tokio::test]
@@ -47,7 +76,7 @@ mod tests {
         let attempt_count = Arc::new(AtomicU32::new(0));
         let attempt_count_clone = attempt_count.clone();
 
-        let result: Result<&str, _> = with_retry(move || {
+        let result: Result<&str, _> = with_retry(&Default::default(), move || {
             let attempt_count = attempt_count_clone.clone();
             async move {
                 attempt_count.fetch_add(1, Ordering::SeqCst);
@@ -64,7 +93,7 @@ mod tests {
         let attempt_count = Arc::new(AtomicU32::new(0));
         let attempt_count_clone = attempt_count.clone();
 
-        let result: Result<&str, _> = with_retry(move || {
+        let result: Result<&str, _> = with_retry(&Default::default(), move || {
             let attempt_count = attempt_count_clone.clone();
             async move {
                 attempt_count.fetch_add(1, Ordering::SeqCst);
@@ -94,7 +123,7 @@ mod tests {
         let attempt_count = Arc::new(AtomicU32::new(0));
         let attempt_count_clone = attempt_count.clone();
 
-        let result: Result<&str, _> = with_retry(move || {
+        let result: Result<&str, _> = with_retry(&Default::default(), move || {
             let attempt_count = attempt_count_clone.clone();
             async move {
                 let count = attempt_count.fetch_add(1, Ordering::SeqCst);
@@ -110,6 +139,85 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_mpim_display_name_resolves_handles() {
+        let mut users = HashMap::new();
+        users.insert(
+            "U1".to_string(),
+            User {
+                id: "U1".to_string(),
+                name: "alice".to_string(),
+                display_name: "Alice A".to_string(),
+                real_name: String::new(),
+                email: None,
+                is_admin: false,
+                is_owner: false,
+            },
+        );
+
+        assert_eq!(
+            mpim_display_name("mpdm-alice--bob--carol-1", &users),
+            "Alice A, bob, carol"
+        );
+        assert_eq!(
+            mpim_display_name("mpdm-dave--erin-12", &HashMap::new()),
+            "dave, erin"
+        );
+    }
+
+    #[test]
+    fn test_parse_channel_keeps_shared_channels_without_a_name() {
+        let shared = serde_json::json!({
+            "id": "C0SHARED",
+            "is_channel": true,
+            "is_ext_shared": true,
+            "is_shared": true,
+            "context_team_id": "T0PARTNER",
+            "topic": {"value": "Joint launch"},
+        });
+        let channel = SlackApi::new().parse_channel(&shared, false).unwrap();
+        assert_eq!(channel.id, "C0SHARED");
+        assert_eq!(channel.name, "C0SHARED");
+        assert_eq!(channel.topic.as_deref(), Some("Joint launch"));
+        assert!(channel.is_shared && channel.is_ext_shared && !channel.is_org_shared);
+        assert!(channel.is_any_shared());
+
+        let unnamed = serde_json::json!({"name": "no-id"});
+        assert!(SlackApi::new().parse_channel(&unnamed, false).is_none());
+    }
+
+    #[test]
+    fn test_parse_channel_reads_posting_restrictions() {
+        let announcements = serde_json::json!({
+            "id": "C0NEWS",
+            "name": "announcements",
+            "is_member": true,
+            "properties": {
+                "posting_restricted_to": {"type": ["admin"], "user": ["U0COMMS"]},
+                "threads_restricted_to": {"type": [], "user": []},
+            },
+        });
+        let channel = SlackApi::new()
+            .parse_channel(&announcements, false)
+            .unwrap();
+        assert_eq!(channel.is_member, Some(true));
+        let posting = channel.posting_restriction(false).unwrap();
+        assert!(posting.allows("U0COMMS", false, false));
+        assert!(posting.allows("U0ANY", true, false));
+        assert!(!posting.allows("U0ANY", false, false));
+        assert_eq!(posting.describe(), "admins and 1 person");
+        // An empty thread restriction leaves replies open.
+        assert!(channel.posting_restriction(true).is_none());
+
+        let legacy = serde_json::json!({"id": "C0OLD", "name": "old", "is_read_only": true});
+        let channel = SlackApi::new().parse_channel(&legacy, false).unwrap();
+        assert_eq!(
+            channel.posting_restricted_to,
+            Some(PostingRestriction::admins_only())
+        );
+        assert_eq!(channel.is_member, None);
+    }
+
     #[test]
     fn test_parse_retry_after_extracts_seconds() {
         assert_eq!(
@@ -119,6 +227,18 @@ mod tests {
         assert_eq!(parse_retry_after("no header here"), None);
     }
 
+    #[test]
+    fn test_error_detail_names_the_missing_scope() {
+        let data = serde_json::json!({"ok": false, "error": "missing_scope",
+            "needed": "reminders:write", "provided": "chat:write"});
+        assert_eq!(
+            error_detail(&data),
+            "missing_scope (needed: reminders:write)"
+        );
+        let data = serde_json::json!({"ok": false, "error": "invalid_time"});
+        assert_eq!(error_detail(&data), "invalid_time");
+    }
+
     #[tokio::test]
     async fn test_user_cache_returns_cached_users() {
         let api = SlackApi::new();
@@ -128,17 +248,172 @@ mod tests {
 
         assert_eq!(users1.len(), users2.len());
     }
+
+    #[tokio::test]
+    async fn test_list_channels_pages_and_retries_against_base_url() {
+        use serde_json::json;
+        use wiremock::matchers::{header, method, path, query_param, query_param_is_missing};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let page = |ids: &[&str], next: &str| {
+            let channels: Vec<_> = ids.iter().map(|id| json!({"id": id, "name": id})).collect();
+            ResponseTemplate::new(200).set_body_json(json!({
+                "ok": true,
+                "channels": channels,
+                "response_metadata": {"next_cursor": next},
+            }))
+        };
+
+        // Mounted first, so the first request is rate limited once.
+        Mock::given(method("GET"))
+            .and(path("/api/conversations.list"))
+            .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "1"))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/conversations.list"))
+            .and(header("authorization", "Bearer xoxp-test"))
+            .and(query_param_is_missing("cursor"))
+            .respond_with(page(&["C1", "C2"], "page2"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/conversations.list"))
+            .and(query_param("cursor", "page2"))
+            .respond_with(page(&["C3"], ""))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let api = SlackApi::new().with_base_url(format!("{}/api/", server.uri()));
+        let channels = api.list_channels("xoxp-test").await.unwrap();
+
+        let ids: Vec<_> = channels.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, ["C1", "C2", "C3"]);
+    }
+
+    #[tokio::test]
+    async fn test_history_names_bots_once_via_bots_info() {
+        use serde_json::json;
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let bot_post = |ts: &str| json!({"ts": ts, "bot_id": "B0CI", "text": "build passed"});
+        Mock::given(method("GET"))
+            .and(path("/conversations.history"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "ok": true,
+                "messages": [bot_post("1730000060.000100"), bot_post("1730000000.000100")],
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users.list"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({"ok": true, "members": []})),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/bots.info"))
+            .and(query_param("bot", "B0CI"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "ok": true,
+                "bot": {"id": "B0CI", "name": "CI"},
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let api = SlackApi::new().with_base_url(server.uri());
+        for _ in 0..2 {
+            let messages = api.get_history("xoxp-test", "C1", 10).await.unwrap();
+            assert_eq!(messages.len(), 2);
+            assert!(messages.iter().all(|m| m.is_bot && m.username == "CI"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_history_around_stitches_both_sides_of_the_anchor() {
+        use serde_json::json;
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let post = |ts: &str| json!({"ts": ts, "user": "U1", "text": ts});
+        Mock::given(method("GET"))
+            .and(path("/conversations.history"))
+            .and(query_param("latest", "1730000100.000100"))
+            .and(query_param("inclusive", "true"))
+            .and(query_param("limit", "3"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "ok": true,
+                "messages": [post("1730000100.000100"), post("1730000050.000100")],
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/conversations.history"))
+            .and(query_param("oldest", "1730000100.000100"))
+            .and(query_param("inclusive", "false"))
+            .and(query_param("limit", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "ok": true,
+                "messages": [post("1730000300.000100"), post("1730000200.000100")],
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users.list"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({"ok": true, "members": []})),
+            )
+            .mount(&server)
+            .await;
+
+        let api = SlackApi::new().with_base_url(server.uri());
+        let messages = api
+            .get_history_around("xoxp-test", "C1", "1730000100.000100", 2, 2)
+            .await
+            .unwrap();
+        let ts: Vec<_> = messages.iter().map(|m| m.ts.as_str()).collect();
+        assert_eq!(
+            ts,
+            [
+                "1730000050.000100",
+                "1730000100.000100",
+                "1730000200.000100",
+                "1730000300.000100"
+            ]
+        );
+    }
 }
 
-struct UserCache {
-    users: HashMap<String, User>,
-    updated_at: Option<Instant>,
+/// When the Web API last answered, and until when Slack asked us to back
+/// off. Shared by clones of one [`SlackApi`].
+#[derive(Debug, Clone, Default)]
+pub struct ApiHealth {
+    pub last_success: Option<DateTime<Utc>>,
+    pub rate_limited_until: Option<DateTime<Utc>>,
 }
 
 #[derive(Clone)]
 pub struct SlackApi {
     client: Client,
-    user_cache: Arc<RwLock<UserCache>>,
+    base_url: String,
+    proxy: Option<String>,
+    /// One [`UserDirectory`] per token, shared with every clone.
+    user_directories: Arc<std::sync::Mutex<HashMap<String, Arc<UserDirectory>>>>,
+    /// `bots.info` names by bot id; bots are rarely renamed, so no TTL.
+    bot_names: Arc<RwLock<HashMap<String, String>>>,
+    health: Arc<std::sync::Mutex<ApiHealth>>,
 }
 
 impl Default for SlackApi {
@@ -158,9 +433,27 @@ fn calculate_backoff(attempt: u32) -> Duration {
     Duration::from_millis((exponential + jitter).min(MAX_BACKOFF_MS))
 }
 
+/// The `error` code of a failed call, naming the scope Slack wanted when
+/// the code is `missing_scope`.
+fn error_detail(data: &Value) -> String {
+    let code = data
+        .get("error")
+        .and_then(|v| v.as_str())
+        .unwrap_or("unknown");
+    match data.get("needed").and_then(|v| v.as_str()) {
+        Some(needed) if code == "missing_scope" => format!("{code} (needed: {needed})"),
+        _ => code.to_string(),
+    }
+}
+
+fn is_rate_limited(error: &anyhow::Error) -> bool {
+    let msg = error.to_string();
+    msg.contains("429") || msg.contains("rate_limited")
+}
+
 fn retry_decision(error: &anyhow::Error) -> RetryDecision {
     let msg = error.to_string();
-    if msg.contains("429") || msg.contains("rate_limited") {
+    if is_rate_limited(error) {
         if let Some(after) = parse_retry_after(&msg) {
             return RetryDecision::Retry(Duration::from_secs(after));
         }
@@ -193,7 +486,9 @@ fn is_transient_network_error(error: &anyhow::Error) -> bool {
         || msg.contains("eof")
 }
 
-async fn with_retry<T, F, Fut>(mut operation: F) -> Result<T>
+/// Retries `operation` per [`retry_decision`], recording successes and
+/// rate-limit cooldowns in `health`.
+async fn with_retry<T, F, Fut>(health: &std::sync::Mutex<ApiHealth>, mut operation: F) -> Result<T>
 where
     F: FnMut() -> Fut,
     Fut: std::future::Future<Output = Result<T>>,
@@ -202,12 +497,27 @@ where
 
     loop {
         match operation().await {
-            Ok(result) => return Ok(result),
+            Ok(result) => {
+                if let Ok(mut health) = health.lock() {
+                    health.last_success = Some(Utc::now());
+                }
+                return Ok(result);
+            }
             Err(e) => {
+                let decision = retry_decision(&e);
+                if is_rate_limited(&e) {
+                    if let (Ok(mut health), RetryDecision::Retry(delay)) =
+                        (health.lock(), &decision)
+                    {
+                        health.rate_limited_until = chrono::Duration::from_std(*delay)
+                            .ok()
+                            .map(|delay| Utc::now() + delay);
+                    }
+                }
                 if attempts >= MAX_RETRIES {
                     return Err(e);
                 }
-                match retry_decision(&e) {
+                match decision {
                     RetryDecision::Fail => return Err(e),
                     RetryDecision::Retry(override_delay) => {
                         let delay = if override_delay.is_zero() {
@@ -215,7 +525,12 @@ where
                         } else {
                             override_delay
                         };
-                        tracing::debug!(attempt = attempts, ?delay, "Retrying after error: {e}");
+                        tracing::debug!(
+                            attempt = attempts,
+                            ?delay,
+                            "Retrying after error: {}",
+                            crate::redact::redact_sensitive(&e.to_string())
+                        );
                         tokio::time::sleep(delay).await;
                         attempts += 1;
                     }
@@ -227,53 +542,87 @@ where
 
 impl SlackApi {
     pub fn new() -> Self {
-        let client = Client::builder()
+        Self {
+            client: Self::build_client(None),
+            base_url: SLACK_API_BASE.to_string(),
+            proxy: None,
+            user_directories: Arc::default(),
+            bot_names: Arc::new(RwLock::new(HashMap::new())),
+            health: Arc::default(),
+        }
+    }
+
+    /// Sends every call to `base_url` instead of slack.com, e.g. a proxy
+    /// that rewrites the Slack domain or a local mock server.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Routes calls, the OAuth exchange and the Socket Mode websocket through
+    /// `proxy` (`http://host:port`) instead of the proxy environment variables.
+    pub fn with_proxy(mut self, proxy: impl Into<String>) -> Self {
+        let proxy = proxy.into();
+        if proxy.trim().is_empty() {
+            return self;
+        }
+        self.client = Self::build_client(Some(&proxy));
+        self.proxy = Some(proxy);
+        self
+    }
+
+    pub fn health(&self) -> ApiHealth {
+        self.health
+            .lock()
+            .map(|health| health.clone())
+            .unwrap_or_default()
+    }
+
+    /// The explicit proxy, if one was set with [`SlackApi::with_proxy`].
+    pub fn proxy(&self) -> Option<&str> {
+        self.proxy.as_deref()
+    }
+
+    fn build_client(proxy: Option<&str>) -> Client {
+        let builder = Client::builder()
             .user_agent("slack-zc/0.2")
             .connect_timeout(Duration::from_secs(5))
-            .timeout(Duration::from_secs(20))
+            .timeout(Duration::from_secs(20));
+        crate::proxy::apply_proxy(builder, proxy)
             .build()
-            .unwrap_or_else(|_| Client::new());
-        Self {
-            client,
-            user_cache: Arc::new(RwLock::new(UserCache {
-                users: HashMap::new(),
-                updated_at: None,
-            })),
-        }
+            .unwrap_or_else(|_| Client::new())
+    }
+
+    pub(crate) fn http(&self) -> &Client {
+        &self.client
+    }
+
+    pub(crate) fn url(&self, method: &str) -> String {
+        format!("{}/{}", self.base_url, method)
+    }
+
+    /// The user directory for `token`'s workspace, created on first use.
+    /// Clones of this client, such as the one a socket holds, share it.
+    pub fn user_directory(&self, token: &str) -> Arc<UserDirectory> {
+        let mut directories = self
+            .user_directories
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        directories.entry(token.to_string()).or_default().clone()
     }
 
     async fn get_users_cached(&self, token: &str) -> HashMap<String, User> {
-        {
-            let cache = self.user_cache.read().await;
-            if let Some(updated_at) = cache.updated_at {
-                if updated_at.elapsed() < USER_CACHE_TTL {
-                    return cache.users.clone();
-                }
-            }
-        }
-        let mut cache = self.user_cache.write().await;
-        // Double-check after acquiring write lock
-        if let Some(updated_at) = cache.updated_at {
-            if updated_at.elapsed() < USER_CACHE_TTL {
-                return cache.users.clone();
-            }
-        }
-        match self.list_users(token).await {
-            Ok(users) => {
-                let users_map: HashMap<String, User> =
-                    users.into_iter().map(|u| (u.id.clone(), u)).collect();
-                cache.users = users_map.clone();
-                cache.updated_at = Some(Instant::now());
-                users_map
-            }
-            Err(_) => cache.users.clone(),
-        }
+        self.user_directory(token).users(self, token).await
     }
 
     pub async fn test_auth(&self, token: &str) -> Result<(String, String, String)> {
         let response = self
             .client
-            .post(format!("{}/auth.test", SLACK_API_BASE))
+            .post(self.url("auth.test"))
             .header("Authorization", format!("Bearer {}", token))
             .send()
             .await?;
@@ -309,14 +658,16 @@ impl SlackApi {
         &self,
         token: &str,
         cursor: Option<&str>,
+        include_archived: bool,
     ) -> Result<(Vec<Channel>, Option<String>)> {
-        with_retry(|| async {
+        with_retry(&self.health, || async {
+            let exclude_archived = if include_archived { "false" } else { "true" };
             let mut req = self
                 .client
-                .get(format!("{}/conversations.list", SLACK_API_BASE))
+                .get(self.url("conversations.list"))
                 .header("Authorization", format!("Bearer {}", token))
                 .query(&[("types", "public_channel,private_channel")])
-                .query(&[("exclude_archived", "true")])
+                .query(&[("exclude_archived", exclude_archived)])
                 .query(&[("limit", "200")]);
 
             if let Some(c) = cursor {
@@ -363,31 +714,7 @@ impl SlackApi {
                 if is_archived {
                     continue;
                 }
-                let id = c.get("id").and_then(|v| v.as_str()).unwrap_or("unknown_id");
-                let name = c.get("name").and_then(|v| v.as_str()).unwrap_or_else(|| {
-                    c.get("id")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("unknown_name")
-                });
-                page_channels.push(Channel {
-                    id: id.to_string(),
-                    name: name.to_string(),
-                    is_dm: false,
-                    is_group: c.get("is_group").and_then(|v| v.as_bool()).unwrap_or(false),
-                    is_im: c.get("is_im").and_then(|v| v.as_bool()).unwrap_or(false),
-                    unread_count: 0,
-                    purpose: c
-                        .get("purpose")
-                        .and_then(|p| p.get("value"))
-                        .and_then(|v| v.as_str())
-                        .map(String::from),
-                    topic: c
-                        .get("topic")
-                        .and_then(|t| t.get("value"))
-                        .and_then(|v| v.as_str())
-                        .map(String::from),
-                    user: None,
-                });
+                page_channels.extend(self.parse_channel(c, false));
             }
 
             let next_cursor = data
@@ -408,12 +735,12 @@ impl SlackApi {
         cursor: Option<&str>,
     ) -> Result<(Vec<Channel>, Option<String>)> {
         let users_map = self.get_users_cached(token).await;
-        with_retry(|| async {
+        with_retry(&self.health, || async {
             let mut req = self
                 .client
-                .get(format!("{}/conversations.list", SLACK_API_BASE))
+                .get(self.url("conversations.list"))
                 .header("Authorization", format!("Bearer {}", token))
-                .query(&[("types", "im")])
+                .query(&[("types", "im,mpim")])
                 .query(&[("limit", "200")]);
 
             if let Some(c) = cursor {
@@ -452,6 +779,15 @@ impl SlackApi {
 
             let mut page_dms = Vec::new();
             for c in channels.iter() {
+                if c.get("is_mpim").and_then(|v| v.as_bool()).unwrap_or(false) {
+                    if let Some(mut channel) = self.parse_channel(c, false) {
+                        channel.name = mpim_display_name(&channel.name, &users_map);
+                        channel.is_dm = true;
+                        channel.is_mpim = true;
+                        page_dms.push(channel);
+                    }
+                    continue;
+                }
                 if let Some(user_id) = c.get("user").and_then(|u| u.as_str()) {
                     let mut channel = self.parse_channel(c, true).unwrap_or_else(|| Channel {
                         id: String::new(),
@@ -459,10 +795,24 @@ impl SlackApi {
                         is_dm: true,
                         is_group: false,
                         is_im: true,
+                        is_mpim: false,
                         unread_count: 0,
+                        mention_count: 0,
                         purpose: None,
                         topic: None,
                         user: Some(user_id.to_string()),
+                        member_count: None,
+                        created: None,
+                        creator: None,
+                        is_shared: false,
+                        is_ext_shared: false,
+                        is_org_shared: false,
+                        is_archived: false,
+                        last_read: None,
+                        latest_ts: None,
+                        is_member: None,
+                        posting_restricted_to: None,
+                        threads_restricted_to: None,
                     });
                     if let Some(user) = users_map.get(user_id) {
                         channel.name = user.display_name();
@@ -500,8 +850,9 @@ impl SlackApi {
                 page_count,
                 cursor
             );
-            let (page_channels, next_cursor) =
-                self.list_channels_page(token, cursor.as_deref()).await?;
+            let (page_channels, next_cursor) = self
+                .list_channels_page(token, cursor.as_deref(), false)
+                .await?;
             tracing::info!(
                 "Page {} returned {} channels",
                 page_count,
@@ -537,27 +888,40 @@ impl SlackApi {
         Ok(all_channels)
     }
 
+    /// Lenient `conversations.*` parsing: only the id is required. Shared
+    /// and external channels can arrive without a `name`, so the id stands
+    /// in and the fallback is logged.
     fn parse_channel(&self, c: &Value, is_dm: bool) -> Option<Channel> {
-        let name = if is_dm {
-            // For DMs, try to get username from user_id, fallback to ID
-            c.get("user").and_then(|u| u.as_str()).map(String::from)
-        } else {
-            // For regular channels, use name or fallback to ID
-            c.get("name")
-                .and_then(|n| n.as_str())
-                .map(String::from)
-                .or_else(|| c.get("id").and_then(|i| i.as_str()).map(String::from))
+        let Some(id) = c.get("id").and_then(|i| i.as_str()) else {
+            tracing::debug!("Skipping conversation without an id: {}", c);
+            return None;
         };
-
-        let name = name?;
+        // DMs are named after the other user; channels after themselves.
+        let key = if is_dm { "user" } else { "name" };
+        let name = c
+            .get(key)
+            .and_then(|n| n.as_str())
+            .filter(|n| !n.is_empty())
+            .map(String::from)
+            .unwrap_or_else(|| {
+                tracing::debug!("Conversation {} has no {}; showing its id", id, key);
+                id.to_string()
+            });
+        let properties = c.get("properties");
+        let read_only = c
+            .get("is_read_only")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
 
         Some(Channel {
-            id: c.get("id")?.as_str()?.to_string(),
+            id: id.to_string(),
             name,
             is_dm,
             is_group: c.get("is_group").and_then(|v| v.as_bool()).unwrap_or(false),
             is_im: c.get("is_im").and_then(|v| v.as_bool()).unwrap_or(false),
+            is_mpim: c.get("is_mpim").and_then(|v| v.as_bool()).unwrap_or(false),
             unread_count: 0,
+            mention_count: 0,
             purpose: c
                 .get("purpose")
                 .and_then(|p| p.get("value"))
@@ -569,7 +933,85 @@ impl SlackApi {
                 .and_then(|v| v.as_str())
                 .map(String::from),
             user: c.get("user").and_then(|v| v.as_str()).map(String::from),
+            member_count: c
+                .get("num_members")
+                .and_then(|v| v.as_u64())
+                .map(|v| v as u32),
+            created: c.get("created").and_then(|v| v.as_i64()),
+            creator: c.get("creator").and_then(|v| v.as_str()).map(String::from),
+            is_shared: c
+                .get("is_shared")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            is_ext_shared: c
+                .get("is_ext_shared")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            is_org_shared: c
+                .get("is_org_shared")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            is_archived: c
+                .get("is_archived")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            last_read: c
+                .get("last_read")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            latest_ts: c
+                .get("latest")
+                .and_then(|v| v.get("ts"))
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            is_member: c.get("is_member").and_then(|v| v.as_bool()),
+            posting_restricted_to: properties
+                .and_then(|p| p.get("posting_restricted_to"))
+                .and_then(PostingRestriction::from_slack_api)
+                .or_else(|| read_only.then(PostingRestriction::admins_only)),
+            threads_restricted_to: properties
+                .and_then(|p| p.get("threads_restricted_to"))
+                .and_then(PostingRestriction::from_slack_api),
+        })
+    }
+
+    /// Full details for one conversation, including its member count.
+    pub async fn get_channel_info(&self, token: &str, channel_id: &str) -> Result<Channel> {
+        with_retry(&self.health, || async {
+            let response = self
+                .client
+                .get(self.url("conversations.info"))
+                .header("Authorization", format!("Bearer {}", token))
+                .query(&[("channel", channel_id)])
+                .query(&[("include_num_members", "true")])
+                .send()
+                .await?;
+
+            let status = response.status();
+            let data: Value = response.json().await?;
+
+            if !data.get("ok").and_then(|v| v.as_bool()).unwrap_or(false) {
+                let error_msg = data
+                    .get("error")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("unknown");
+                if error_msg == "rate_limited" || status.as_u16() == 429 {
+                    return Err(anyhow!("429"));
+                }
+                return Err(anyhow!("Failed to get channel info: {}", error_msg));
+            }
+
+            let channel = data
+                .get("channel")
+                .ok_or_else(|| anyhow!("Failed to get channel info: missing channel"))?;
+            let is_im = channel
+                .get("is_im")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            self.parse_channel(channel, is_im)
+                .ok_or_else(|| anyhow!("Failed to get channel info: malformed channel"))
         })
+        .await
     }
 
     pub async fn list_dms(&self, token: &str) -> Result<Vec<Channel>> {
@@ -594,20 +1036,81 @@ impl SlackApi {
         token: &str,
         channel_id: &str,
         limit: u32,
+    ) -> Result<Vec<Message>> {
+        self.get_history_between(token, channel_id, None, None, limit)
+            .await
+    }
+
+    /// Up to `limit` messages between the `oldest` and `latest` ts bounds
+    /// (inclusive), newest ones first when the range holds more; returned
+    /// oldest first like `get_history`.
+    pub async fn get_history_between(
+        &self,
+        token: &str,
+        channel_id: &str,
+        oldest: Option<&str>,
+        latest: Option<&str>,
+        limit: u32,
+    ) -> Result<Vec<Message>> {
+        self.history_page(token, channel_id, oldest, latest, true, limit)
+            .await
+    }
+
+    /// The conversation around one message: up to `before` messages before
+    /// `ts`, the message itself, then up to `after` messages following it,
+    /// oldest first. Slack answers an `oldest`-only query with the messages
+    /// right after it, so two calls cover both sides.
+    pub async fn get_history_around(
+        &self,
+        token: &str,
+        channel_id: &str,
+        ts: &str,
+        before: u32,
+        after: u32,
+    ) -> Result<Vec<Message>> {
+        let mut messages = self
+            .history_page(token, channel_id, None, Some(ts), true, before + 1)
+            .await?;
+        if after > 0 {
+            let newer = self
+                .history_page(token, channel_id, Some(ts), None, false, after)
+                .await?;
+            messages.extend(newer);
+        }
+        Ok(messages)
+    }
+
+    async fn history_page(
+        &self,
+        token: &str,
+        channel_id: &str,
+        oldest: Option<&str>,
+        latest: Option<&str>,
+        inclusive: bool,
+        limit: u32,
     ) -> Result<Vec<Message>> {
         let channel_id = channel_id.to_string();
         let token = token.to_string();
+        let mut bounds = vec![("inclusive", inclusive.to_string())];
+        if let Some(oldest) = oldest {
+            bounds.push(("oldest", oldest.to_string()));
+        }
+        if let Some(latest) = latest {
+            bounds.push(("latest", latest.to_string()));
+        }
 
-        with_retry(move || {
+        with_retry(&self.health, move || {
             let channel_id = channel_id.clone();
             let token = token.clone();
+            let bounds = bounds.clone();
             async move {
                 let response = self
                     .client
-                    .get(format!("{}/conversations.history", SLACK_API_BASE))
+                    .get(self.url("conversations.history"))
                     .header("Authorization", format!("Bearer {}", token))
                     .query(&[("channel", channel_id.as_str())])
                     .query(&[("limit", limit.to_string())])
+                    .query(&bounds)
                     .send()
                     .await?;
 
@@ -632,11 +1135,13 @@ impl SlackApi {
                     .unwrap_or(&empty);
                 let users_map = self.get_users_cached(&token).await;
 
-                Ok(messages
+                let mut messages: Vec<Message> = messages
                     .iter()
                     .filter_map(|m| Message::from_slack_api(m, &users_map))
                     .rev()
-                    .collect())
+                    .collect();
+                self.name_unnamed_bots(&token, &mut messages).await;
+                Ok(messages)
             }
         })
         .await
@@ -647,14 +1152,14 @@ impl SlackApi {
         let text = text.to_string();
         let token = token.to_string();
 
-        with_retry(move || {
+        with_retry(&self.health, move || {
             let channel_id = channel_id.clone();
             let text = text.clone();
             let token = token.clone();
             async move {
                 let response = self
                     .client
-                    .post(format!("{}/chat.postMessage", SLACK_API_BASE))
+                    .post(self.url("chat.postMessage"))
                     .header("Authorization", format!("Bearer {}", token))
                     .json(&serde_json::json!({
                         "channel": channel_id,
@@ -698,7 +1203,7 @@ impl SlackApi {
         let thread_ts = thread_ts.to_string();
         let token = token.to_string();
 
-        with_retry(move || {
+        with_retry(&self.health, move || {
             let channel_id = channel_id.clone();
             let text = text.clone();
             let thread_ts = thread_ts.clone();
@@ -706,7 +1211,7 @@ impl SlackApi {
             async move {
                 let response = self
                     .client
-                    .post(format!("{}/chat.postMessage", SLACK_API_BASE))
+                    .post(self.url("chat.postMessage"))
                     .header("Authorization", format!("Bearer {}", token))
                     .json(&serde_json::json!({
                         "channel": channel_id,
@@ -742,12 +1247,12 @@ impl SlackApi {
     pub async fn list_users(&self, token: &str) -> Result<Vec<User>> {
         let token = token.to_string();
 
-        with_retry(move || {
+        with_retry(&self.health, move || {
             let token = token.clone();
             async move {
                 let response = self
                     .client
-                    .get(format!("{}/users.list", SLACK_API_BASE))
+                    .get(self.url("users.list"))
                     .header("Authorization", format!("Bearer {}", token))
                     .send()
                     .await?;
@@ -772,39 +1277,79 @@ impl SlackApi {
                     .and_then(|v| v.as_array())
                     .unwrap_or(&empty);
 
-                Ok(members
-                    .iter()
-                    .filter_map(|u| {
-                        let profile = u.get("profile")?;
-                        Some(User {
-                            id: u.get("id")?.as_str()?.to_string(),
-                            name: u.get("name")?.as_str()?.to_string(),
-                            display_name: profile
-                                .get("display_name")
-                                .and_then(|v| v.as_str())
-                                .unwrap_or("")
-                                .to_string(),
-                            real_name: profile
-                                .get("real_name")
-                                .and_then(|v| v.as_str())
-                                .unwrap_or("")
-                                .to_string(),
-                            email: profile
-                                .get("email")
-                                .and_then(|v| v.as_str())
-                                .map(String::from),
-                        })
-                    })
-                    .collect())
+                Ok(members.iter().filter_map(User::from_slack_api).collect())
             }
         })
         .await
     }
 
+    /// Display name of an app or integration bot, from `bots.info`.
+    pub async fn get_bot_info(&self, token: &str, bot_id: &str) -> Result<String> {
+        if let Some(name) = self.bot_names.read().await.get(bot_id) {
+            return Ok(name.clone());
+        }
+
+        let response = self
+            .client
+            .get(self.url("bots.info"))
+            .header("Authorization", format!("Bearer {}", token))
+            .query(&[("bot", bot_id)])
+            .send()
+            .await?;
+
+        let data: Value = response.json().await?;
+
+        if !data.get("ok").and_then(|v| v.as_bool()).unwrap_or(false) {
+            let error_msg = data
+                .get("error")
+                .and_then(|v| v.as_str())
+                .unwrap_or("unknown");
+            return Err(anyhow!("Failed to get bot info: {}", error_msg));
+        }
+
+        let name = data
+            .get("bot")
+            .and_then(|bot| bot.get("name"))
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow!("No bot name in response"))?
+            .to_string();
+        self.bot_names
+            .write()
+            .await
+            .insert(bot_id.to_string(), name.clone());
+        Ok(name)
+    }
+
+    /// Replaces bare bot ids left by payloads without a `username`.
+    async fn name_unnamed_bots(&self, token: &str, messages: &mut [Message]) {
+        let unnamed =
+            |m: &Message| m.is_bot && m.username == m.user_id && m.user_id.starts_with('B');
+        let mut names = HashMap::new();
+        for message in messages.iter().filter(|m| unnamed(m)) {
+            if names.contains_key(&message.user_id) {
+                continue;
+            }
+            match self.get_bot_info(token, &message.user_id).await {
+                Ok(name) => {
+                    names.insert(message.user_id.clone(), name);
+                }
+                Err(e) => {
+                    tracing::debug!("Failed to resolve bot {}: {}", message.user_id, e);
+                    names.insert(message.user_id.clone(), message.user_id.clone());
+                }
+            }
+        }
+        for message in messages.iter_mut() {
+            if let Some(name) = names.get(&message.user_id) {
+                message.username = name.clone();
+            }
+        }
+    }
+
     pub async fn get_user(&self, token: &str, user_id: &str) -> Result<User> {
         let response = self
             .client
-            .get(format!("{}/users.info", SLACK_API_BASE))
+            .get(self.url("users.info"))
             .header("Authorization", format!("Bearer {}", token))
             .query(&[("user", user_id)])
             .send()
@@ -813,39 +1358,9 @@ impl SlackApi {
         let data: Value = response.json().await?;
 
         if data.get("ok").and_then(|v| v.as_bool()).unwrap_or(false) {
-            let user = data
-                .get("user")
-                .ok_or_else(|| anyhow!("No user in response"))?;
-            let profile = user
-                .get("profile")
-                .ok_or_else(|| anyhow!("No profile in response"))?;
-
-            Ok(User {
-                id: user
-                    .get("id")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("")
-                    .to_string(),
-                name: user
-                    .get("name")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("")
-                    .to_string(),
-                display_name: profile
-                    .get("display_name")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("")
-                    .to_string(),
-                real_name: profile
-                    .get("real_name")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("")
-                    .to_string(),
-                email: profile
-                    .get("email")
-                    .and_then(|v| v.as_str())
-                    .map(String::from),
-            })
+            data.get("user")
+                .and_then(User::from_slack_api)
+                .ok_or_else(|| anyhow!("No user in response"))
         } else {
             Err(anyhow!(
                 "Failed to get user: {:?}",
@@ -857,7 +1372,7 @@ impl SlackApi {
     pub async fn get_socket_mode_url(&self, xapp_token: &str) -> Result<String> {
         let response = self
             .client
-            .post(format!("{}/apps.connections.open", SLACK_API_BASE))
+            .post(self.url("apps.connections.open"))
             .header("Authorization", format!("Bearer {}", xapp_token))
             .send()
             .await?;
@@ -877,6 +1392,65 @@ impl SlackApi {
         }
     }
 
+    /// WebSocket URL from `rtm.connect`. Only classic tokens may use RTM;
+    /// others fail with Slack's error code, e.g. `not_allowed_token_type`.
+    /// The workspace icon URL, or `None` when the team uses Slack's default.
+    pub async fn team_icon(&self, token: &str) -> Result<Option<String>> {
+        let response = self
+            .client
+            .get(self.url("team.info"))
+            .header("Authorization", format!("Bearer {}", token))
+            .send()
+            .await?;
+
+        let data: Value = response.json().await?;
+
+        if data.get("ok").and_then(|v| v.as_bool()).unwrap_or(false) {
+            let icon = data.get("team").and_then(|team| team.get("icon"));
+            let is_default = icon
+                .and_then(|icon| icon.get("image_default"))
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            Ok(icon
+                .filter(|_| !is_default)
+                .and_then(|icon| icon.get("image_88"))
+                .and_then(|v| v.as_str())
+                .map(String::from))
+        } else {
+            Err(anyhow!(
+                "team.info failed: {}",
+                data.get("error")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("unknown_error")
+            ))
+        }
+    }
+
+    pub async fn rtm_connect(&self, token: &str) -> Result<String> {
+        let response = self
+            .client
+            .get(self.url("rtm.connect"))
+            .header("Authorization", format!("Bearer {}", token))
+            .send()
+            .await?;
+
+        let data: Value = response.json().await?;
+
+        if data.get("ok").and_then(|v| v.as_bool()).unwrap_or(false) {
+            data.get("url")
+                .and_then(|v| v.as_str())
+                .map(String::from)
+                .ok_or_else(|| anyhow!("No URL in response"))
+        } else {
+            Err(anyhow!(
+                "rtm.connect failed: {}",
+                data.get("error")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("unknown_error")
+            ))
+        }
+    }
+
     pub async fn update_message(
         &self,
         token: &str,
@@ -889,7 +1463,7 @@ impl SlackApi {
         let text = text.to_string();
         let token = token.to_string();
 
-        with_retry(move || {
+        with_retry(&self.health, move || {
             let channel_id = channel_id.clone();
             let ts = ts.clone();
             let text = text.clone();
@@ -897,7 +1471,7 @@ impl SlackApi {
             async move {
                 let response = self
                     .client
-                    .post(format!("{}/chat.update", SLACK_API_BASE))
+                    .post(self.url("chat.update"))
                     .header("Authorization", format!("Bearer {}", token))
                     .json(&serde_json::json!({
                         "channel": channel_id,
@@ -932,14 +1506,14 @@ impl SlackApi {
         let ts = ts.to_string();
         let token = token.to_string();
 
-        with_retry(move || {
+        with_retry(&self.health, move || {
             let channel_id = channel_id.clone();
             let ts = ts.clone();
             let token = token.clone();
             async move {
                 let response = self
                     .client
-                    .post(format!("{}/chat.delete", SLACK_API_BASE))
+                    .post(self.url("chat.delete"))
                     .header("Authorization", format!("Bearer {}", token))
                     .json(&serde_json::json!({
                         "channel": channel_id,
@@ -980,7 +1554,7 @@ impl SlackApi {
         let reaction = reaction.to_string();
         let token = token.to_string();
 
-        with_retry(move || {
+        with_retry(&self.health, move || {
             let channel_id = channel_id.clone();
             let ts = ts.clone();
             let reaction = reaction.clone();
@@ -988,7 +1562,7 @@ impl SlackApi {
             async move {
                 let response = self
                     .client
-                    .post(format!("{}/reactions.add", SLACK_API_BASE))
+                    .post(self.url("reactions.add"))
                     .header("Authorization", format!("Bearer {}", token))
                     .json(&serde_json::json!({
                         "channel": channel_id,
@@ -1030,7 +1604,7 @@ impl SlackApi {
         let reaction = reaction.to_string();
         let token = token.to_string();
 
-        with_retry(move || {
+        with_retry(&self.health, move || {
             let channel_id = channel_id.clone();
             let ts = ts.clone();
             let reaction = reaction.clone();
@@ -1038,7 +1612,7 @@ impl SlackApi {
             async move {
                 let response = self
                     .client
-                    .post(format!("{}/reactions.remove", SLACK_API_BASE))
+                    .post(self.url("reactions.remove"))
                     .header("Authorization", format!("Bearer {}", token))
                     .json(&serde_json::json!({
                         "channel": channel_id,
@@ -1068,34 +1642,34 @@ impl SlackApi {
         .await
     }
 
-    pub async fn get_thread_replies(
-        &self,
-        token: &str,
-        channel_id: &str,
-        thread_ts: &str,
-    ) -> Result<Vec<Message>> {
+    /// Moves the channel's read cursor to `ts` (`conversations.mark`).
+    pub async fn mark_read(&self, token: &str, channel_id: &str, ts: &str) -> Result<()> {
         let channel_id = channel_id.to_string();
-        let thread_ts = thread_ts.to_string();
+        let ts = ts.to_string();
         let token = token.to_string();
 
-        with_retry(move || {
+        with_retry(&self.health, move || {
             let channel_id = channel_id.clone();
-            let thread_ts = thread_ts.clone();
+            let ts = ts.clone();
             let token = token.clone();
             async move {
                 let response = self
                     .client
-                    .get(format!("{}/conversations.replies", SLACK_API_BASE))
+                    .post(self.url("conversations.mark"))
                     .header("Authorization", format!("Bearer {}", token))
-                    .query(&[("channel", channel_id.as_str())])
-                    .query(&[("ts", thread_ts.as_str())])
+                    .json(&serde_json::json!({
+                        "channel": channel_id,
+                        "ts": ts,
+                    }))
                     .send()
                     .await?;
 
                 let status = response.status();
                 let data: Value = response.json().await?;
 
-                if !data.get("ok").and_then(|v| v.as_bool()).unwrap_or(false) {
+                if data.get("ok").and_then(|v| v.as_bool()).unwrap_or(false) {
+                    Ok(())
+                } else {
                     let error_msg = data
                         .get("error")
                         .and_then(|v| v.as_str())
@@ -1103,16 +1677,170 @@ impl SlackApi {
                     if error_msg == "rate_limited" || status.as_u16() == 429 {
                         return Err(anyhow!("429"));
                     }
-                    return Err(anyhow!("Failed to get thread replies: {}", error_msg));
+                    Err(anyhow!("Failed to mark channel: {}", error_msg))
                 }
+            }
+        })
+        .await
+    }
+
+    /// Joins a public channel so the user can post in it.
+    pub async fn join_channel(&self, token: &str, channel_id: &str) -> Result<()> {
+        let channel_id = channel_id.to_string();
+        let token = token.to_string();
+
+        with_retry(&self.health, move || {
+            let channel_id = channel_id.clone();
+            let token = token.clone();
+            async move {
+                let response = self
+                    .client
+                    .post(self.url("conversations.join"))
+                    .header("Authorization", format!("Bearer {}", token))
+                    .json(&serde_json::json!({ "channel": channel_id }))
+                    .send()
+                    .await?;
+
+                let status = response.status();
+                let data: Value = response.json().await?;
+
+                if data.get("ok").and_then(|v| v.as_bool()).unwrap_or(false) {
+                    Ok(())
+                } else {
+                    let error_msg = data
+                        .get("error")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("unknown");
+                    if error_msg == "rate_limited" || status.as_u16() == 429 {
+                        return Err(anyhow!("429"));
+                    }
+                    Err(anyhow!("Failed to join channel: {}", error_msg))
+                }
+            }
+        })
+        .await
+    }
+
+    /// Leaves a channel (`conversations.leave`).
+    pub async fn leave_channel(&self, token: &str, channel_id: &str) -> Result<()> {
+        let channel_id = channel_id.to_string();
+        let token = token.to_string();
+
+        with_retry(&self.health, move || {
+            let channel_id = channel_id.clone();
+            let token = token.clone();
+            async move {
+                let response = self
+                    .client
+                    .post(self.url("conversations.leave"))
+                    .header("Authorization", format!("Bearer {}", token))
+                    .json(&serde_json::json!({ "channel": channel_id }))
+                    .send()
+                    .await?;
+
+                let status = response.status();
+                let data: Value = response.json().await?;
+
+                if data.get("ok").and_then(|v| v.as_bool()).unwrap_or(false) {
+                    Ok(())
+                } else {
+                    let error_msg = error_detail(&data);
+                    if error_msg == "rate_limited" || status.as_u16() == 429 {
+                        return Err(anyhow!("429"));
+                    }
+                    Err(anyhow!("Failed to leave channel: {}", error_msg))
+                }
+            }
+        })
+        .await
+    }
+
+    /// Sets a channel's topic (`conversations.setTopic`).
+    pub async fn set_channel_topic(
+        &self,
+        token: &str,
+        channel_id: &str,
+        topic: &str,
+    ) -> Result<()> {
+        let channel_id = channel_id.to_string();
+        let topic = topic.to_string();
+        let token = token.to_string();
+
+        with_retry(&self.health, move || {
+            let channel_id = channel_id.clone();
+            let topic = topic.clone();
+            let token = token.clone();
+            async move {
+                let response = self
+                    .client
+                    .post(self.url("conversations.setTopic"))
+                    .header("Authorization", format!("Bearer {}", token))
+                    .json(&serde_json::json!({ "channel": channel_id, "topic": topic }))
+                    .send()
+                    .await?;
+
+                let status = response.status();
+                let data: Value = response.json().await?;
+
+                if data.get("ok").and_then(|v| v.as_bool()).unwrap_or(false) {
+                    Ok(())
+                } else {
+                    let error_msg = error_detail(&data);
+                    if error_msg == "rate_limited" || status.as_u16() == 429 {
+                        return Err(anyhow!("429"));
+                    }
+                    Err(anyhow!("Failed to set topic: {}", error_msg))
+                }
+            }
+        })
+        .await
+    }
+
+    pub async fn get_thread_replies(
+        &self,
+        token: &str,
+        channel_id: &str,
+        thread_ts: &str,
+    ) -> Result<Vec<Message>> {
+        let channel_id = channel_id.to_string();
+        let thread_ts = thread_ts.to_string();
+        let token = token.to_string();
+
+        with_retry(&self.health, move || {
+            let channel_id = channel_id.clone();
+            let thread_ts = thread_ts.clone();
+            let token = token.clone();
+            async move {
+                let response = self
+                    .client
+                    .get(self.url("conversations.replies"))
+                    .header("Authorization", format!("Bearer {}", token))
+                    .query(&[("channel", channel_id.as_str())])
+                    .query(&[("ts", thread_ts.as_str())])
+                    .send()
+                    .await?;
+
+                let status = response.status();
+                let data: Value = response.json().await?;
+
+                if !data.get("ok").and_then(|v| v.as_bool()).unwrap_or(false) {
+                    let error_msg = data
+                        .get("error")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("unknown");
+                    if error_msg == "rate_limited" || status.as_u16() == 429 {
+                        return Err(anyhow!("429"));
+                    }
+                    return Err(anyhow!("Failed to get thread replies: {}", error_msg));
+                }
+
+                let empty: Vec<serde_json::Value> = Vec::new();
+                let messages = data
+                    .get("messages")
+                    .and_then(|v| v.as_array())
+                    .unwrap_or(&empty);
+                let users_map = self.get_users_cached(&token).await;
 
-                let empty: Vec<serde_json::Value> = Vec::new();
-                let messages = data
-                    .get("messages")
-                    .and_then(|v| v.as_array())
-                    .unwrap_or(&empty);
-                let users_map = self.get_users_cached(&token).await;
-
                 Ok(messages
                     .iter()
                     .filter_map(|m| Message::from_slack_api(m, &users_map))
@@ -1155,7 +1883,7 @@ impl SlackApi {
 
             let response = self
                 .client
-                .post(format!("{}/files.upload", SLACK_API_BASE))
+                .post(self.url("files.upload"))
                 .header("Authorization", format!("Bearer {}", token))
                 .multipart(form)
                 .send()
@@ -1180,7 +1908,7 @@ impl SlackApi {
 
         let response = self
             .client
-            .post(format!("{}/files.upload", SLACK_API_BASE))
+            .post(self.url("files.upload"))
             .header("Authorization", format!("Bearer {}", token))
             .multipart(form)
             .send()
@@ -1202,10 +1930,308 @@ impl SlackApi {
         }
     }
 
+    /// Saves a message for later (stars it).
+    pub async fn save_message(&self, token: &str, channel_id: &str, ts: &str) -> Result<()> {
+        self.star("stars.add", token, channel_id, ts).await
+    }
+
+    pub async fn unsave_message(&self, token: &str, channel_id: &str, ts: &str) -> Result<()> {
+        self.star("stars.remove", token, channel_id, ts).await
+    }
+
+    async fn star(
+        &self,
+        method: &'static str,
+        token: &str,
+        channel_id: &str,
+        ts: &str,
+    ) -> Result<()> {
+        let channel_id = channel_id.to_string();
+        let ts = ts.to_string();
+        let token = token.to_string();
+
+        with_retry(&self.health, move || {
+            let channel_id = channel_id.clone();
+            let ts = ts.clone();
+            let token = token.clone();
+            async move {
+                let response = self
+                    .client
+                    .post(self.url(method))
+                    .header("Authorization", format!("Bearer {}", token))
+                    .json(&serde_json::json!({
+                        "channel": channel_id,
+                        "timestamp": ts,
+                    }))
+                    .send()
+                    .await?;
+
+                let status = response.status();
+                let data: Value = response.json().await?;
+
+                if data.get("ok").and_then(|v| v.as_bool()).unwrap_or(false) {
+                    Ok(())
+                } else {
+                    let error_msg = error_detail(&data);
+                    if error_msg == "rate_limited" || status.as_u16() == 429 {
+                        return Err(anyhow!("429"));
+                    }
+                    // Already in the state asked for.
+                    if error_msg == "already_starred" || error_msg == "not_starred" {
+                        return Ok(());
+                    }
+                    Err(anyhow!("Failed to update saved items: {}", error_msg))
+                }
+            }
+        })
+        .await
+    }
+
+    /// The user's saved messages, newest save first.
+    pub async fn list_saved(&self, token: &str) -> Result<Vec<SavedItem>> {
+        let token = token.to_string();
+
+        with_retry(&self.health, move || {
+            let token = token.clone();
+            async move {
+                let response = self
+                    .client
+                    .get(self.url("stars.list"))
+                    .header("Authorization", format!("Bearer {}", token))
+                    .query(&[("limit", "200")])
+                    .send()
+                    .await?;
+
+                let status = response.status();
+                let data: Value = response.json().await?;
+
+                if !data.get("ok").and_then(|v| v.as_bool()).unwrap_or(false) {
+                    let error_msg = error_detail(&data);
+                    if error_msg == "rate_limited" || status.as_u16() == 429 {
+                        return Err(anyhow!("429"));
+                    }
+                    return Err(anyhow!("Failed to list saved items: {}", error_msg));
+                }
+
+                let users_map = self.get_users_cached(&token).await;
+                Ok(data
+                    .get("items")
+                    .and_then(|v| v.as_array())
+                    .map(|items| {
+                        items
+                            .iter()
+                            .filter_map(|item| SavedItem::from_slack_api(item, &users_map))
+                            .collect()
+                    })
+                    .unwrap_or_default())
+            }
+        })
+        .await
+    }
+
+    /// Sets a reminder for the signed-in user. `time` is a Unix timestamp.
+    /// Returns the reminder id.
+    pub async fn add_reminder(&self, token: &str, text: &str, time: i64) -> Result<String> {
+        let text = text.to_string();
+        let token = token.to_string();
+
+        with_retry(&self.health, move || {
+            let text = text.clone();
+            let token = token.clone();
+            async move {
+                let response = self
+                    .client
+                    .post(self.url("reminders.add"))
+                    .header("Authorization", format!("Bearer {}", token))
+                    .json(&serde_json::json!({ "text": text, "time": time }))
+                    .send()
+                    .await?;
+
+                let status = response.status();
+                let data: Value = response.json().await?;
+
+                if data.get("ok").and_then(|v| v.as_bool()).unwrap_or(false) {
+                    data.get("reminder")
+                        .and_then(|r| r.get("id"))
+                        .and_then(|v| v.as_str())
+                        .map(String::from)
+                        .ok_or_else(|| anyhow!("No reminder id in response"))
+                } else {
+                    let error_msg = error_detail(&data);
+                    if error_msg == "rate_limited" || status.as_u16() == 429 {
+                        return Err(anyhow!("429"));
+                    }
+                    Err(anyhow!("Failed to add reminder: {}", error_msg))
+                }
+            }
+        })
+        .await
+    }
+
+    pub async fn list_reminders(&self, token: &str) -> Result<Vec<Reminder>> {
+        let token = token.to_string();
+
+        with_retry(&self.health, move || {
+            let token = token.clone();
+            async move {
+                let response = self
+                    .client
+                    .get(self.url("reminders.list"))
+                    .header("Authorization", format!("Bearer {}", token))
+                    .send()
+                    .await?;
+
+                let status = response.status();
+                let data: Value = response.json().await?;
+
+                if data.get("ok").and_then(|v| v.as_bool()).unwrap_or(false) {
+                    Ok(data
+                        .get("reminders")
+                        .and_then(|v| v.as_array())
+                        .map(|list| list.iter().filter_map(Reminder::from_slack_api).collect())
+                        .unwrap_or_default())
+                } else {
+                    let error_msg = error_detail(&data);
+                    if error_msg == "rate_limited" || status.as_u16() == 429 {
+                        return Err(anyhow!("429"));
+                    }
+                    Err(anyhow!("Failed to list reminders: {}", error_msg))
+                }
+            }
+        })
+        .await
+    }
+
+    pub async fn delete_reminder(&self, token: &str, reminder_id: &str) -> Result<()> {
+        let reminder_id = reminder_id.to_string();
+        let token = token.to_string();
+
+        with_retry(&self.health, move || {
+            let reminder_id = reminder_id.clone();
+            let token = token.clone();
+            async move {
+                let response = self
+                    .client
+                    .post(self.url("reminders.delete"))
+                    .header("Authorization", format!("Bearer {}", token))
+                    .json(&serde_json::json!({ "reminder": reminder_id }))
+                    .send()
+                    .await?;
+
+                let status = response.status();
+                let data: Value = response.json().await?;
+
+                if data.get("ok").and_then(|v| v.as_bool()).unwrap_or(false) {
+                    Ok(())
+                } else {
+                    let error_msg = error_detail(&data);
+                    if error_msg == "rate_limited" || status.as_u16() == 429 {
+                        return Err(anyhow!("429"));
+                    }
+                    Err(anyhow!("Failed to delete reminder: {}", error_msg))
+                }
+            }
+        })
+        .await
+    }
+
+    /// `users.setPresence`: `"auto"` lets Slack go by activity again,
+    /// `"away"` marks the user away until set back.
+    pub async fn set_presence(&self, token: &str, presence: &str) -> Result<()> {
+        let presence = presence.to_string();
+        let token = token.to_string();
+
+        with_retry(&self.health, move || {
+            let presence = presence.clone();
+            let token = token.clone();
+            async move {
+                let response = self
+                    .client
+                    .post(self.url("users.setPresence"))
+                    .header("Authorization", format!("Bearer {}", token))
+                    .json(&serde_json::json!({ "presence": presence }))
+                    .send()
+                    .await?;
+
+                let status = response.status();
+                let data: Value = response.json().await?;
+
+                if data.get("ok").and_then(|v| v.as_bool()).unwrap_or(false) {
+                    Ok(())
+                } else {
+                    let error_msg = error_detail(&data);
+                    if error_msg == "rate_limited" || status.as_u16() == 429 {
+                        return Err(anyhow!("429"));
+                    }
+                    Err(anyhow!("Failed to set presence: {}", error_msg))
+                }
+            }
+        })
+        .await
+    }
+
+    /// Posts `content` as a text snippet, in a thread when `thread_ts` is
+    /// set. Returns the file id.
+    pub async fn upload_snippet(
+        &self,
+        token: &str,
+        channel_id: &str,
+        thread_ts: Option<&str>,
+        filename: &str,
+        content: &str,
+    ) -> Result<String> {
+        let mut form = vec![
+            ("channels", channel_id.to_string()),
+            ("filename", filename.to_string()),
+            ("title", filename.to_string()),
+            ("filetype", "text".to_string()),
+            ("content", content.to_string()),
+        ];
+        if let Some(ts) = thread_ts {
+            form.push(("thread_ts", ts.to_string()));
+        }
+        let token = token.to_string();
+
+        with_retry(&self.health, move || {
+            let form = form.clone();
+            let token = token.clone();
+            async move {
+                let response = self
+                    .client
+                    .post(self.url("files.upload"))
+                    .header("Authorization", format!("Bearer {}", token))
+                    .form(&form)
+                    .send()
+                    .await?;
+
+                let status = response.status();
+                let data: Value = response.json().await?;
+
+                if data.get("ok").and_then(|v| v.as_bool()).unwrap_or(false) {
+                    data.get("file")
+                        .and_then(|f| f.get("id"))
+                        .and_then(|v| v.as_str())
+                        .map(String::from)
+                        .ok_or_else(|| anyhow!("No file id in response"))
+                } else {
+                    let error_msg = data
+                        .get("error")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("unknown");
+                    if error_msg == "rate_limited" || status.as_u16() == 429 {
+                        return Err(anyhow!("429"));
+                    }
+                    Err(anyhow!("Failed to upload snippet: {}", error_msg))
+                }
+            }
+        })
+        .await
+    }
+
     pub async fn get_file_info(&self, token: &str, file_id: &str) -> Result<FileInfo> {
         let response = self
             .client
-            .get(format!("{}/files.info", SLACK_API_BASE))
+            .get(self.url("files.info"))
             .header("Authorization", format!("Bearer {}", token))
             .query(&[("file", file_id)])
             .send()
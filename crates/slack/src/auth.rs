@@ -1,13 +1,17 @@
+use crate::api::SlackApi;
 use crate::types::Workspace;
 use anyhow::{anyhow, Result};
-use directories::ProjectDirs;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 use std::fs::{self, File};
 use std::io::Write;
-use std::path::PathBuf;
-use std::time::Duration;
+use std::path::{Path, PathBuf};
+
+/// Leading bytes of a session archive, versioned for future formats.
+const ARCHIVE_MAGIC: &[u8] = b"SLACKZC-SESSION-1\n";
+const ARCHIVE_SALT_LEN: usize = 16;
 
 fn zeroclaw_state_dir() -> Option<PathBuf> {
     std::env::var("ZEROCLAW_CONFIG_DIR")
@@ -133,10 +137,36 @@ pub fn load_openclaw_bearer() -> Option<String> {
     None
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Session {
     pub workspaces: Vec<Workspace>,
+    /// Bearer shared by workspaces without one of their own in `agents`;
+    /// the only one sessions saved before per-workspace settings have.
     pub zeroclaw_bearer: Option<String>,
+    #[serde(default)]
+    pub credentials: StoredCredentials,
+    /// ZeroClaw settings by team id.
+    #[serde(default)]
+    pub agents: HashMap<String, WorkspaceAgent>,
+}
+
+/// One workspace's ZeroClaw settings; what is unset follows the config
+/// and the shared bearer.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WorkspaceAgent {
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    #[serde(default)]
+    pub bearer: Option<String>,
+}
+
+/// App credentials entered during onboarding, encrypted with the session
+/// rather than kept in config.toml.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StoredCredentials {
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    pub llm_api_key: Option<String>,
 }
 
 impl Session {
@@ -152,7 +182,19 @@ impl Session {
         Ok(Some(session))
     }
 
+    /// Writes the session unless this instance runs read-only (a quiet
+    /// no-op) or another instance has taken the session over (an error).
     pub fn save(&self) -> Result<()> {
+        if crate::lock::is_read_only() {
+            tracing::debug!("Read-only instance; not saving the session");
+            return Ok(());
+        }
+        if let Some(holder) = crate::lock::other_holder() {
+            return Err(anyhow!(
+                "another slack-zc instance (pid {}) owns the session; not saving",
+                holder.pid
+            ));
+        }
         let path = Self::session_path()?;
         let json = serde_json::to_vec(self)?;
         let encrypted = Self::encrypt(&json)?;
@@ -161,18 +203,13 @@ impl Session {
     }
 
     pub fn session_path() -> Result<PathBuf> {
-        let proj_dirs = ProjectDirs::from("com", "slack-zc", "slack-zc")
-            .ok_or_else(|| anyhow!("Could not determine project directories"))?;
-        let data_dir = proj_dirs.data_dir();
-        fs::create_dir_all(data_dir)?;
+        let data_dir = crate::profile::data_dir()?;
+        fs::create_dir_all(&data_dir)?;
         Ok(data_dir.join("session.json"))
     }
 
     fn secret_key_path() -> Result<PathBuf> {
-        let proj_dirs = ProjectDirs::from("com", "slack-zc", "slack-zc")
-            .ok_or_else(|| anyhow!("Could not determine project directories"))?;
-        let data_dir = proj_dirs.data_dir();
-        Ok(data_dir.join(".secret_key"))
+        Ok(crate::profile::data_dir()?.join(".secret_key"))
     }
 
     fn get_or_create_key() -> Result<[u8; 32]> {
@@ -204,13 +241,21 @@ impl Session {
     }
 
     fn encrypt(plaintext: &[u8]) -> Result<Vec<u8>> {
+        Self::encrypt_with_key(&Self::get_or_create_key()?, plaintext)
+    }
+
+    fn decrypt(ciphertext: &[u8]) -> Result<Vec<u8>> {
+        Self::decrypt_with_key(&Self::get_or_create_key()?, ciphertext)
+    }
+
+    /// AES-256-GCM with a random nonce prepended to the ciphertext.
+    fn encrypt_with_key(key: &[u8; 32], plaintext: &[u8]) -> Result<Vec<u8>> {
         use aes_gcm::{
             aead::{Aead, KeyInit},
             Aes256Gcm, Nonce,
         };
 
-        let key = Self::get_or_create_key()?;
-        let cipher = Aes256Gcm::new(aes_gcm::aead::Key::<Aes256Gcm>::from_slice(&key));
+        let cipher = Aes256Gcm::new(aes_gcm::aead::Key::<Aes256Gcm>::from_slice(key));
 
         let mut nonce_bytes = [0u8; 12];
         rand::thread_rng().fill(&mut nonce_bytes);
@@ -225,7 +270,7 @@ impl Session {
         Ok(result)
     }
 
-    fn decrypt(ciphertext: &[u8]) -> Result<Vec<u8>> {
+    fn decrypt_with_key(key: &[u8; 32], ciphertext: &[u8]) -> Result<Vec<u8>> {
         use aes_gcm::{
             aead::{Aead, KeyInit},
             Aes256Gcm, Nonce,
@@ -235,8 +280,7 @@ impl Session {
             return Err(anyhow!("Invalid ciphertext"));
         }
 
-        let key = Self::get_or_create_key()?;
-        let cipher = Aes256Gcm::new(aes_gcm::aead::Key::<Aes256Gcm>::from_slice(&key));
+        let cipher = Aes256Gcm::new(aes_gcm::aead::Key::<Aes256Gcm>::from_slice(key));
 
         let nonce = Nonce::from_slice(&ciphertext[..12]);
         let plaintext = cipher
@@ -246,26 +290,161 @@ impl Session {
         Ok(plaintext)
     }
 
-    fn write_secure_file(path: &PathBuf, bytes: &[u8]) -> Result<()> {
-        let mut file = File::create(path)?;
-        file.write_all(bytes)?;
+    /// Writes `bytes` to `path` readable only by the current user. The data
+    /// goes to a temporary file in the same directory that is synced and
+    /// renamed over `path`, so a crash leaves either the old or new file.
+    pub fn write_secure_file(path: &Path, bytes: &[u8]) -> Result<()> {
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| anyhow!("{} is not a file path", path.display()))?;
+        let tmp_path = path.with_file_name(format!(
+            ".{}.{}.tmp",
+            file_name.to_string_lossy(),
+            std::process::id()
+        ));
+
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        // Created private, so the contents are never briefly exposed.
         #[cfg(unix)]
         {
-            use std::os::unix::fs::PermissionsExt;
-            let mut perms = file.metadata()?.permissions();
-            perms.set_mode(0o600);
-            fs::set_permissions(path, perms)?;
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let written = (|| -> Result<()> {
+            let mut file = options.open(&tmp_path)?;
+            file.write_all(bytes)?;
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                let mut perms = file.metadata()?.permissions();
+                perms.set_mode(0o600);
+                fs::set_permissions(&tmp_path, perms)?;
+            }
+            file.sync_all()?;
+            fs::rename(&tmp_path, path)?;
+            Ok(())
+        })();
+        if written.is_err() {
+            let _ = fs::remove_file(&tmp_path);
+        }
+        written?;
+
+        // Persist the rename itself; not every platform can open a directory.
+        #[cfg(unix)]
+        if let Some(dir) = path.parent() {
+            if let Ok(dir) = File::open(dir) {
+                let _ = dir.sync_all();
+            }
         }
         Ok(())
     }
 
+    /// Serializes the session into a portable archive encrypted with a key
+    /// derived from `passphrase`, independent of this machine's key file.
+    pub fn export_archive(&self, passphrase: &str) -> Result<Vec<u8>> {
+        let mut salt = [0u8; ARCHIVE_SALT_LEN];
+        rand::thread_rng().fill(&mut salt);
+        let key = Self::archive_key(passphrase, &salt)?;
+        let json = serde_json::to_vec(self)?;
+
+        let mut archive = ARCHIVE_MAGIC.to_vec();
+        archive.extend_from_slice(&salt);
+        archive.extend_from_slice(&Self::encrypt_with_key(&key, &json)?);
+        Ok(archive)
+    }
+
+    /// Opens an archive written by [`Session::export_archive`].
+    pub fn import_archive(archive: &[u8], passphrase: &str) -> Result<Self> {
+        let rest = archive
+            .strip_prefix(ARCHIVE_MAGIC)
+            .ok_or_else(|| anyhow!("Not a slack-zc session archive"))?;
+        if rest.len() < ARCHIVE_SALT_LEN {
+            return Err(anyhow!("Session archive is truncated"));
+        }
+        let (salt, ciphertext) = rest.split_at(ARCHIVE_SALT_LEN);
+        let key = Self::archive_key(passphrase, salt)?;
+        let json = Self::decrypt_with_key(&key, ciphertext)
+            .map_err(|_| anyhow!("Wrong passphrase or corrupted archive"))?;
+        Ok(serde_json::from_slice(&json)?)
+    }
+
+    fn archive_key(passphrase: &str, salt: &[u8]) -> Result<[u8; 32]> {
+        let mut key = [0u8; 32];
+        argon2::Argon2::default()
+            .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+            .map_err(|e| anyhow!("Key derivation failed: {}", e))?;
+        Ok(key)
+    }
+
+    /// Merges `other` into this session: workspaces by team_id (imported
+    /// ones win), and the bearer and credentials only where unset here.
+    /// The active workspace stays as it was unless there was none.
+    pub fn merge(&mut self, other: Session) {
+        let active = self.get_active_workspace().map(|w| w.team_id.clone());
+        for workspace in other.workspaces {
+            self.add_workspace(workspace);
+        }
+        if let Some(team_id) = active {
+            self.set_active_workspace(&team_id);
+        }
+        if self.zeroclaw_bearer.is_none() {
+            self.zeroclaw_bearer = other.zeroclaw_bearer;
+        }
+        for (team_id, agent) in other.agents {
+            self.agents.entry(team_id).or_insert(agent);
+        }
+        let credentials = &mut self.credentials;
+        credentials.client_id = credentials.client_id.take().or(other.credentials.client_id);
+        credentials.client_secret = credentials
+            .client_secret
+            .take()
+            .or(other.credentials.client_secret);
+        credentials.llm_api_key = credentials
+            .llm_api_key
+            .take()
+            .or(other.credentials.llm_api_key);
+    }
+
+    /// The bearer to connect to ZeroClaw with while `team_id` is active.
+    pub fn zeroclaw_bearer_for(&self, team_id: Option<&str>) -> Option<&str> {
+        team_id
+            .and_then(|team_id| self.agents.get(team_id))
+            .and_then(|agent| agent.bearer.as_deref())
+            .or(self.zeroclaw_bearer.as_deref())
+    }
+
+    /// Whether `team_id` opted in or out of the agent; `None` when it
+    /// follows the config.
+    pub fn agent_enabled_for(&self, team_id: &str) -> Option<bool> {
+        self.agents.get(team_id).and_then(|agent| agent.enabled)
+    }
+
+    pub fn agent_mut(&mut self, team_id: &str) -> &mut WorkspaceAgent {
+        self.agents.entry(team_id.to_string()).or_default()
+    }
+
     pub fn add_workspace(&mut self, workspace: Workspace) {
         if let Some(idx) = self
             .workspaces
             .iter()
             .position(|w| w.team_id == workspace.team_id)
         {
+            // Signing in again keeps the threads followed, channels muted
+            // and reactions used so far.
+            let threads = std::mem::take(&mut self.workspaces[idx].threads);
+            let muted = std::mem::take(&mut self.workspaces[idx].muted_channels);
+            let reactions = std::mem::take(&mut self.workspaces[idx].recent_reactions);
             self.workspaces[idx] = workspace;
+            if self.workspaces[idx].threads.is_empty() {
+                self.workspaces[idx].threads = threads;
+            }
+            if self.workspaces[idx].muted_channels.is_empty() {
+                self.workspaces[idx].muted_channels = muted;
+            }
+            if self.workspaces[idx].recent_reactions.is_empty() {
+                self.workspaces[idx].recent_reactions = reactions;
+            }
         } else {
             self.workspaces.push(workspace);
         }
@@ -289,6 +468,7 @@ impl Session {
         let initial_len = self.workspaces.len();
         self.workspaces.retain(|w| w.team_id != team_id);
         if self.workspaces.len() != initial_len {
+            self.agents.remove(team_id);
             if self.workspaces.is_empty() {
                 self.zeroclaw_bearer = None;
             } else if let Some(first) = self.workspaces.first_mut() {
@@ -303,6 +483,7 @@ impl Session {
     pub fn clear_all(&mut self) -> Result<()> {
         self.workspaces.clear();
         self.zeroclaw_bearer = None;
+        self.agents.clear();
         self.save()
     }
 
@@ -342,22 +523,17 @@ pub struct Team {
     pub name: String,
 }
 
+/// Exchanges an OAuth code using `api`'s base URL and proxy settings.
 pub async fn exchange_oauth_code(
+    api: &SlackApi,
     client_id: &str,
     client_secret: &str,
     code: &str,
     redirect_uri: &str,
 ) -> Result<OAuthResponse> {
-    use reqwest::Client;
-
-    let client = Client::builder()
-        .user_agent("slack-zc/0.2")
-        .connect_timeout(Duration::from_secs(5))
-        .timeout(Duration::from_secs(20))
-        .build()
-        .unwrap_or_else(|_| Client::new());
-    let response = client
-        .post("https://slack.com/api/oauth.v2.access")
+    let response = api
+        .http()
+        .post(api.url("oauth.v2.access"))
         .form(&[
             ("client_id", client_id),
             ("client_secret", client_secret),
@@ -375,3 +551,98 @@ pub async fn exchange_oauth_code(
 
     Ok(data)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspace(team_id: &str, token: &str, active: bool) -> Workspace {
+        Workspace {
+            team_id: team_id.to_string(),
+            team_name: team_id.to_lowercase(),
+            xoxp_token: token.to_string(),
+            xapp_token: "xapp-test".to_string(),
+            user_id: None,
+            active,
+            last_channel_id: None,
+            icon_url: None,
+            threads: Vec::new(),
+            muted_channels: Vec::new(),
+            recent_reactions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_archive_round_trips_with_the_passphrase_only() {
+        let session = Session {
+            workspaces: vec![workspace("T1", "xoxp-one", true)],
+            zeroclaw_bearer: Some("bearer".to_string()),
+            credentials: StoredCredentials::default(),
+            agents: HashMap::new(),
+        };
+        let archive = session.export_archive("correct horse").unwrap();
+        assert!(!archive.windows(b"xoxp-one".len()).any(|w| w == b"xoxp-one"));
+
+        let restored = Session::import_archive(&archive, "correct horse").unwrap();
+        assert_eq!(restored.workspaces[0].xoxp_token, "xoxp-one");
+        assert_eq!(restored.zeroclaw_bearer.as_deref(), Some("bearer"));
+        assert!(Session::import_archive(&archive, "wrong").is_err());
+        assert!(Session::import_archive(b"not an archive", "correct horse").is_err());
+    }
+
+    #[test]
+    fn test_merge_replaces_by_team_id_and_keeps_the_active_workspace() {
+        let mut local = Session {
+            workspaces: vec![
+                workspace("T1", "xoxp-old", false),
+                workspace("T2", "xoxp-two", true),
+            ],
+            ..Session::default()
+        };
+        let imported = Session {
+            workspaces: vec![
+                workspace("T1", "xoxp-new", true),
+                workspace("T3", "xoxp-three", false),
+            ],
+            zeroclaw_bearer: Some("bearer".to_string()),
+            ..Session::default()
+        };
+
+        local.merge(imported);
+
+        let tokens: Vec<_> = local
+            .workspaces
+            .iter()
+            .map(|w| w.xoxp_token.as_str())
+            .collect();
+        assert_eq!(tokens, ["xoxp-new", "xoxp-two", "xoxp-three"]);
+        assert_eq!(local.get_active_workspace().unwrap().team_id, "T2");
+        assert_eq!(local.zeroclaw_bearer.as_deref(), Some("bearer"));
+    }
+
+    #[test]
+    fn test_workspace_agent_settings_fall_back_to_the_shared_bearer() {
+        let mut session = Session {
+            workspaces: vec![workspace("T1", "xoxp-one", true)],
+            zeroclaw_bearer: Some("shared".to_string()),
+            ..Session::default()
+        };
+        assert_eq!(session.zeroclaw_bearer_for(Some("T1")), Some("shared"));
+        assert_eq!(session.agent_enabled_for("T1"), None);
+
+        let agent = session.agent_mut("T1");
+        agent.enabled = Some(false);
+        agent.bearer = Some("own".to_string());
+        assert_eq!(session.zeroclaw_bearer_for(Some("T1")), Some("own"));
+        assert_eq!(session.zeroclaw_bearer_for(Some("T2")), Some("shared"));
+        assert_eq!(session.agent_enabled_for("T1"), Some(false));
+
+        // Sessions saved before the map existed still load.
+        let old: Session =
+            serde_json::from_str(r#"{"workspaces": [], "zeroclaw_bearer": "shared"}"#).unwrap();
+        assert!(old.agents.is_empty());
+
+        assert!(session.remove_workspace("T1"));
+        assert!(session.agents.is_empty());
+    }
+}
@@ -0,0 +1,129 @@
+//! Splitting text that is too long for one Slack message.
+
+/// Most characters `chat.postMessage` takes before refusing with
+/// `msg_too_long`.
+pub const MAX_MESSAGE_CHARS: usize = 4000;
+
+const FENCE: &str = "```";
+/// Room kept in each piece for a re-opened and a closing fence.
+const FENCE_OVERHEAD: usize = 2 * (FENCE.len() + 1);
+
+/// Splits `text` into pieces of at most `limit` characters. Breaks at the
+/// last line end before the limit, else the last whitespace, else (a long
+/// URL, say) at the limit itself. A code block cut in two is closed at the
+/// end of one piece and re-opened at the start of the next, so each piece
+/// renders on its own.
+pub fn split_message(text: &str, limit: usize) -> Vec<String> {
+    let fenced = text.contains(FENCE);
+    let budget = if fenced {
+        limit.saturating_sub(FENCE_OVERHEAD)
+    } else {
+        limit
+    }
+    .max(1);
+
+    let mut chunks = Vec::new();
+    let mut in_code = false;
+    let mut rest = text.trim_end().trim_start_matches(['\n', '\r']);
+    while !rest.is_empty() {
+        let (piece, next) = if rest.chars().count() > budget {
+            take_piece(rest, budget)
+        } else {
+            (rest, "")
+        };
+        let piece = piece.trim_end();
+        if !piece.is_empty() {
+            let mut chunk = String::with_capacity(piece.len() + FENCE_OVERHEAD);
+            if in_code {
+                chunk.push_str(FENCE);
+                chunk.push('\n');
+            }
+            chunk.push_str(piece);
+            if piece.matches(FENCE).count() % 2 == 1 {
+                in_code = !in_code;
+            }
+            if in_code && !next.is_empty() {
+                chunk.push('\n');
+                chunk.push_str(FENCE);
+            }
+            chunks.push(chunk);
+        }
+        rest = next;
+    }
+    chunks
+}
+
+/// The longest prefix of `text` within `budget` characters that ends at a
+/// break point, and what follows it.
+fn take_piece(text: &str, budget: usize) -> (&str, &str) {
+    // The first character past the budget still counts as a break point.
+    let (end, next) = text.char_indices().nth(budget).unwrap_or_default();
+    let window = &text[..end + next.len_utf8()];
+    if let Some(idx) = window.rfind('\n').filter(|&idx| idx > 0) {
+        // Indentation on the next line is kept; only the line end goes.
+        return (&text[..idx], &text[idx + 1..]);
+    }
+    let cut = window
+        .rfind(char::is_whitespace)
+        .filter(|&idx| idx > 0)
+        .unwrap_or(end);
+    let rest = text[cut..].trim_start_matches(|c: char| c.is_whitespace() && c != '\n');
+    (&text[..cut], rest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_text_is_one_chunk() {
+        assert_eq!(split_message("hello there", 20), vec!["hello there"]);
+        assert!(split_message("   ", 20).is_empty());
+    }
+
+    #[test]
+    fn test_splits_at_whitespace_under_the_limit_in_order() {
+        let text = "one two three four five six";
+        let chunks = split_message(text, 9);
+        assert_eq!(chunks, vec!["one two", "three", "four five", "six"]);
+        assert!(chunks.iter().all(|c| c.chars().count() <= 9));
+        assert_eq!(chunks.join(" "), text);
+    }
+
+    #[test]
+    fn test_word_longer_than_the_limit_is_cut() {
+        let chunks = split_message("ab cdefghij k", 4);
+        assert_eq!(chunks, vec!["ab", "cdef", "ghij", "k"]);
+    }
+
+    #[test]
+    fn test_limit_counts_characters_not_bytes() {
+        let text = "é".repeat(6);
+        let chunks = split_message(&text, 4);
+        assert_eq!(chunks, vec!["éééé", "éé"]);
+    }
+
+    #[test]
+    fn test_prefers_line_ends_and_keeps_indentation() {
+        let text = "Error: boom\n  at one (a.rs:1)\n  at two (b.rs:2)";
+        let chunks = split_message(text, 30);
+        assert_eq!(
+            chunks,
+            vec!["Error: boom\n  at one (a.rs:1)", "  at two (b.rs:2)"]
+        );
+    }
+
+    #[test]
+    fn test_code_block_is_closed_and_reopened_across_pieces() {
+        let lines: Vec<String> = (0..8).map(|i| format!("line {i}")).collect();
+        let text = format!("trace:\n```\n{}\n```\ndone", lines.join("\n"));
+        let chunks = split_message(&text, 40);
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.chars().count() <= 40, "{chunk:?}");
+            assert_eq!(chunk.matches(FENCE).count() % 2, 0, "{chunk:?}");
+        }
+        assert!(chunks[1].starts_with("```\n"));
+        assert!(chunks.last().unwrap().ends_with("```\ndone"));
+    }
+}
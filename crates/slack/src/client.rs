@@ -0,0 +1,344 @@
+use crate::api::{ApiHealth, SlackApi};
+use crate::types::{Channel, Message, Reminder, SavedItem, User};
+use crate::users::UserDirectory;
+use anyhow::Result;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Web API calls the TUI makes, so it can run against a fake in tests.
+/// [`SlackApi`] is the real implementation.
+#[async_trait]
+pub trait SlackClient: Send + Sync {
+    /// Last successful call and any rate-limit cooldown. Fakes report none.
+    fn api_health(&self) -> ApiHealth {
+        ApiHealth::default()
+    }
+
+    /// The user directory shared with the socket for `token`'s workspace.
+    /// Fakes get a fresh, empty one.
+    fn user_directory(&self, _token: &str) -> Arc<UserDirectory> {
+        Arc::new(UserDirectory::new())
+    }
+
+    /// Returns `(team_id, team_name, user_id)` for `token`.
+    async fn test_auth(&self, token: &str) -> Result<(String, String, String)>;
+
+    async fn list_channels_page(
+        &self,
+        token: &str,
+        cursor: Option<&str>,
+        include_archived: bool,
+    ) -> Result<(Vec<Channel>, Option<String>)>;
+
+    async fn list_dms_page(
+        &self,
+        token: &str,
+        cursor: Option<&str>,
+    ) -> Result<(Vec<Channel>, Option<String>)>;
+
+    async fn list_channels(&self, token: &str) -> Result<Vec<Channel>>;
+
+    async fn get_channel_info(&self, token: &str, channel_id: &str) -> Result<Channel>;
+
+    /// Latest `limit` messages, oldest first.
+    async fn get_history(&self, token: &str, channel_id: &str, limit: u32) -> Result<Vec<Message>>;
+
+    /// Messages between the optional `oldest`/`latest` bounds, oldest first.
+    async fn get_history_between(
+        &self,
+        token: &str,
+        channel_id: &str,
+        oldest: Option<&str>,
+        latest: Option<&str>,
+        limit: u32,
+    ) -> Result<Vec<Message>>;
+
+    /// Up to `before` messages before `ts`, the message itself and up to
+    /// `after` following it, oldest first.
+    async fn get_history_around(
+        &self,
+        token: &str,
+        channel_id: &str,
+        ts: &str,
+        before: u32,
+        after: u32,
+    ) -> Result<Vec<Message>>;
+
+    /// Posts `text` and returns the new message's ts.
+    async fn send_message(&self, token: &str, channel_id: &str, text: &str) -> Result<String>;
+
+    async fn send_message_to_thread(
+        &self,
+        token: &str,
+        channel_id: &str,
+        text: &str,
+        thread_ts: &str,
+    ) -> Result<String>;
+
+    async fn update_message(
+        &self,
+        token: &str,
+        channel_id: &str,
+        ts: &str,
+        text: &str,
+    ) -> Result<()>;
+
+    async fn delete_message(&self, token: &str, channel_id: &str, ts: &str) -> Result<()>;
+
+    async fn add_reaction(
+        &self,
+        token: &str,
+        channel_id: &str,
+        ts: &str,
+        reaction: &str,
+    ) -> Result<()>;
+
+    async fn remove_reaction(
+        &self,
+        token: &str,
+        channel_id: &str,
+        ts: &str,
+        reaction: &str,
+    ) -> Result<()>;
+
+    async fn mark_read(&self, token: &str, channel_id: &str, ts: &str) -> Result<()>;
+
+    async fn join_channel(&self, token: &str, channel_id: &str) -> Result<()>;
+
+    async fn leave_channel(&self, token: &str, channel_id: &str) -> Result<()>;
+
+    async fn set_channel_topic(&self, token: &str, channel_id: &str, topic: &str) -> Result<()>;
+
+    async fn add_reminder(&self, token: &str, text: &str, time: i64) -> Result<String>;
+
+    async fn list_reminders(&self, token: &str) -> Result<Vec<Reminder>>;
+
+    async fn delete_reminder(&self, token: &str, reminder_id: &str) -> Result<()>;
+
+    async fn set_presence(&self, token: &str, presence: &str) -> Result<()>;
+
+    async fn save_message(&self, token: &str, channel_id: &str, ts: &str) -> Result<()>;
+
+    async fn unsave_message(&self, token: &str, channel_id: &str, ts: &str) -> Result<()>;
+
+    async fn list_saved(&self, token: &str) -> Result<Vec<SavedItem>>;
+
+    async fn upload_snippet(
+        &self,
+        token: &str,
+        channel_id: &str,
+        thread_ts: Option<&str>,
+        filename: &str,
+        content: &str,
+    ) -> Result<String>;
+
+    async fn get_thread_replies(
+        &self,
+        token: &str,
+        channel_id: &str,
+        thread_ts: &str,
+    ) -> Result<Vec<Message>>;
+
+    async fn list_users(&self, token: &str) -> Result<Vec<User>>;
+
+    async fn get_user(&self, token: &str, user_id: &str) -> Result<User>;
+
+    /// RTM WebSocket URL, used only to send typing events.
+    async fn rtm_connect(&self, token: &str) -> Result<String>;
+
+    async fn team_icon(&self, token: &str) -> Result<Option<String>>;
+}
+
+#[async_trait]
+impl SlackClient for SlackApi {
+    fn api_health(&self) -> ApiHealth {
+        SlackApi::health(self)
+    }
+
+    fn user_directory(&self, token: &str) -> Arc<UserDirectory> {
+        SlackApi::user_directory(self, token)
+    }
+
+    async fn test_auth(&self, token: &str) -> Result<(String, String, String)> {
+        SlackApi::test_auth(self, token).await
+    }
+
+    async fn list_channels_page(
+        &self,
+        token: &str,
+        cursor: Option<&str>,
+        include_archived: bool,
+    ) -> Result<(Vec<Channel>, Option<String>)> {
+        SlackApi::list_channels_page(self, token, cursor, include_archived).await
+    }
+
+    async fn list_dms_page(
+        &self,
+        token: &str,
+        cursor: Option<&str>,
+    ) -> Result<(Vec<Channel>, Option<String>)> {
+        SlackApi::list_dms_page(self, token, cursor).await
+    }
+
+    async fn list_channels(&self, token: &str) -> Result<Vec<Channel>> {
+        SlackApi::list_channels(self, token).await
+    }
+
+    async fn get_channel_info(&self, token: &str, channel_id: &str) -> Result<Channel> {
+        SlackApi::get_channel_info(self, token, channel_id).await
+    }
+
+    async fn get_history(&self, token: &str, channel_id: &str, limit: u32) -> Result<Vec<Message>> {
+        SlackApi::get_history(self, token, channel_id, limit).await
+    }
+
+    async fn get_history_between(
+        &self,
+        token: &str,
+        channel_id: &str,
+        oldest: Option<&str>,
+        latest: Option<&str>,
+        limit: u32,
+    ) -> Result<Vec<Message>> {
+        SlackApi::get_history_between(self, token, channel_id, oldest, latest, limit).await
+    }
+
+    async fn get_history_around(
+        &self,
+        token: &str,
+        channel_id: &str,
+        ts: &str,
+        before: u32,
+        after: u32,
+    ) -> Result<Vec<Message>> {
+        SlackApi::get_history_around(self, token, channel_id, ts, before, after).await
+    }
+
+    async fn send_message(&self, token: &str, channel_id: &str, text: &str) -> Result<String> {
+        SlackApi::send_message(self, token, channel_id, text).await
+    }
+
+    async fn send_message_to_thread(
+        &self,
+        token: &str,
+        channel_id: &str,
+        text: &str,
+        thread_ts: &str,
+    ) -> Result<String> {
+        SlackApi::send_message_to_thread(self, token, channel_id, text, thread_ts).await
+    }
+
+    async fn update_message(
+        &self,
+        token: &str,
+        channel_id: &str,
+        ts: &str,
+        text: &str,
+    ) -> Result<()> {
+        SlackApi::update_message(self, token, channel_id, ts, text).await
+    }
+
+    async fn delete_message(&self, token: &str, channel_id: &str, ts: &str) -> Result<()> {
+        SlackApi::delete_message(self, token, channel_id, ts).await
+    }
+
+    async fn add_reaction(
+        &self,
+        token: &str,
+        channel_id: &str,
+        ts: &str,
+        reaction: &str,
+    ) -> Result<()> {
+        SlackApi::add_reaction(self, token, channel_id, ts, reaction).await
+    }
+
+    async fn remove_reaction(
+        &self,
+        token: &str,
+        channel_id: &str,
+        ts: &str,
+        reaction: &str,
+    ) -> Result<()> {
+        SlackApi::remove_reaction(self, token, channel_id, ts, reaction).await
+    }
+
+    async fn mark_read(&self, token: &str, channel_id: &str, ts: &str) -> Result<()> {
+        SlackApi::mark_read(self, token, channel_id, ts).await
+    }
+
+    async fn join_channel(&self, token: &str, channel_id: &str) -> Result<()> {
+        SlackApi::join_channel(self, token, channel_id).await
+    }
+
+    async fn leave_channel(&self, token: &str, channel_id: &str) -> Result<()> {
+        SlackApi::leave_channel(self, token, channel_id).await
+    }
+
+    async fn set_channel_topic(&self, token: &str, channel_id: &str, topic: &str) -> Result<()> {
+        SlackApi::set_channel_topic(self, token, channel_id, topic).await
+    }
+
+    async fn add_reminder(&self, token: &str, text: &str, time: i64) -> Result<String> {
+        SlackApi::add_reminder(self, token, text, time).await
+    }
+
+    async fn list_reminders(&self, token: &str) -> Result<Vec<Reminder>> {
+        SlackApi::list_reminders(self, token).await
+    }
+
+    async fn delete_reminder(&self, token: &str, reminder_id: &str) -> Result<()> {
+        SlackApi::delete_reminder(self, token, reminder_id).await
+    }
+
+    async fn set_presence(&self, token: &str, presence: &str) -> Result<()> {
+        SlackApi::set_presence(self, token, presence).await
+    }
+
+    async fn save_message(&self, token: &str, channel_id: &str, ts: &str) -> Result<()> {
+        SlackApi::save_message(self, token, channel_id, ts).await
+    }
+
+    async fn unsave_message(&self, token: &str, channel_id: &str, ts: &str) -> Result<()> {
+        SlackApi::unsave_message(self, token, channel_id, ts).await
+    }
+
+    async fn list_saved(&self, token: &str) -> Result<Vec<SavedItem>> {
+        SlackApi::list_saved(self, token).await
+    }
+
+    async fn upload_snippet(
+        &self,
+        token: &str,
+        channel_id: &str,
+        thread_ts: Option<&str>,
+        filename: &str,
+        content: &str,
+    ) -> Result<String> {
+        SlackApi::upload_snippet(self, token, channel_id, thread_ts, filename, content).await
+    }
+
+    async fn get_thread_replies(
+        &self,
+        token: &str,
+        channel_id: &str,
+        thread_ts: &str,
+    ) -> Result<Vec<Message>> {
+        SlackApi::get_thread_replies(self, token, channel_id, thread_ts).await
+    }
+
+    async fn list_users(&self, token: &str) -> Result<Vec<User>> {
+        SlackApi::list_users(self, token).await
+    }
+
+    async fn get_user(&self, token: &str, user_id: &str) -> Result<User> {
+        SlackApi::get_user(self, token, user_id).await
+    }
+
+    async fn rtm_connect(&self, token: &str) -> Result<String> {
+        SlackApi::rtm_connect(self, token).await
+    }
+
+    async fn team_icon(&self, token: &str) -> Result<Option<String>> {
+        SlackApi::team_icon(self, token).await
+    }
+}
@@ -19,6 +19,21 @@ pub enum ApiError {
 
     #[error("Timeout: {0}")]
     Timeout(String),
+
+    #[error("Not in channel: {0}")]
+    NotInChannel(String),
+
+    #[error("Message too long: {0}")]
+    MessageTooLong(String),
+
+    #[error("Restricted action: {0}")]
+    Restricted(String),
+
+    #[error("Channel archived: {0}")]
+    Archived(String),
+
+    #[error("Missing scope: {scope}")]
+    MissingScope { scope: String },
 }
 
 impl ApiError {
@@ -30,6 +45,13 @@ impl ApiError {
             ApiError::Validation(_) => "Invalid input. Please check your message.",
             ApiError::Api(_) => "Server error. Please try again later.",
             ApiError::Timeout(_) => "Request timed out. Please try again.",
+            ApiError::NotInChannel(_) => "You're not a member of this channel. Join it to post.",
+            ApiError::MessageTooLong(_) => {
+                "Message is over Slack's 4000-character limit. Split it to send."
+            }
+            ApiError::Restricted(_) => "Posting here is restricted by a workspace admin.",
+            ApiError::Archived(_) => "This channel is archived and takes no new messages.",
+            ApiError::MissingScope { .. } => "The Slack app lacks a permission this needs.",
         }
     }
 
@@ -48,12 +70,30 @@ pub fn map_anyhow_error(e: anyhow::Error) -> ApiError {
 }
 
 pub fn map_anyhow_error_ref(e: &anyhow::Error) -> ApiError {
-    let msg = e.to_string();
-    if msg.contains("429") || msg.contains("rate_limited") {
+    let msg = crate::redact::redact_sensitive(&e.to_string());
+    // chat.* refusals first: they are not retryable, whatever else the text says.
+    if msg.contains("not_in_channel") {
+        ApiError::NotInChannel(msg)
+    } else if msg.contains("msg_too_long") {
+        ApiError::MessageTooLong(msg)
+    } else if msg.contains("restricted_action") {
+        ApiError::Restricted(msg)
+    } else if msg.contains("is_archived") {
+        ApiError::Archived(msg)
+    } else if msg.contains("missing_scope") {
+        let scope = msg
+            .split("needed: ")
+            .nth(1)
+            .map(|rest| rest.trim_end_matches(')').to_string())
+            .unwrap_or_default();
+        ApiError::MissingScope { scope }
+    } else if msg.contains("429") || msg.contains("rate_limited") {
         ApiError::RateLimited { retry_after: 60 }
     } else if msg.contains("not_authed")
         || msg.contains("invalid_auth")
         || msg.contains("token_revoked")
+        || msg.contains("token_expired")
+        || msg.contains("account_inactive")
     {
         ApiError::Auth(msg)
     } else if msg.contains("timeout") || msg.contains("timed out") || msg.contains("connection") {
@@ -64,3 +104,39 @@ pub fn map_anyhow_error_ref(e: &anyhow::Error) -> ApiError {
         ApiError::Api(msg)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    #[test]
+    fn test_chat_refusals_get_specific_guidance() {
+        let cases = [
+            ("not_in_channel", "not a member"),
+            ("msg_too_long", "4000-character"),
+            ("restricted_action", "restricted by a workspace admin"),
+            ("is_archived", "archived"),
+        ];
+        for (code, hint) in cases {
+            let error = map_anyhow_error(anyhow!("Failed to send message: {code}"));
+            assert!(
+                error.user_message().contains(hint),
+                "{code}: {}",
+                error.user_message()
+            );
+            assert!(!error.is_retryable(), "{code} should not be retried");
+        }
+    }
+
+    #[test]
+    fn test_missing_scope_keeps_the_scope_name() {
+        let error = map_anyhow_error(anyhow!(
+            "Failed to add reminder: missing_scope (needed: reminders:write)"
+        ));
+        assert!(
+            matches!(error, ApiError::MissingScope { ref scope } if scope == "reminders:write"),
+            "{error:?}"
+        );
+    }
+}
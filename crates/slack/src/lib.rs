@@ -1,8 +1,16 @@
 pub mod api;
 pub mod auth;
+pub mod chunk;
+pub mod client;
 pub mod error;
+pub mod lock;
+pub mod profile;
+pub mod proxy;
+pub mod redact;
 pub mod socket;
 pub mod types;
+pub mod users;
 
+pub use client::SlackClient;
 pub use error::{ApiError, ApiResult};
 pub use types::*;
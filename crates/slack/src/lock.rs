@@ -0,0 +1,182 @@
+//! Advisory lock that keeps two instances from rewriting one session.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use std::fs;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+const LOCK_FILE: &str = "instance.lock";
+
+static READ_ONLY: AtomicBool = AtomicBool::new(false);
+
+/// Another live instance and when it took the lock.
+#[derive(Debug, Clone)]
+pub struct LockHolder {
+    pub pid: u32,
+    pub since: Option<DateTime<Utc>>,
+}
+
+/// Held for the life of the process; removes the lock file on drop unless
+/// another instance has taken it over since.
+#[derive(Debug)]
+pub struct InstanceLock {
+    path: PathBuf,
+}
+
+impl InstanceLock {
+    /// Takes the lock for the current profile, clearing one left by a dead
+    /// process. Returns the holder when a live instance has it.
+    pub fn acquire() -> Result<std::result::Result<Self, LockHolder>> {
+        Self::acquire_in(&crate::profile::data_dir()?)
+    }
+
+    /// Takes the lock even though `acquire` found it held.
+    pub fn take_over() -> Result<Self> {
+        Self::take_over_in(&crate::profile::data_dir()?)
+    }
+
+    fn acquire_in(dir: &Path) -> Result<std::result::Result<Self, LockHolder>> {
+        fs::create_dir_all(dir)?;
+        let path = dir.join(LOCK_FILE);
+        // The holder is written out first and linked into place, so the lock
+        // is never seen empty: a half-written one would look stale.
+        let tmp_path = dir.join(format!(".{}.{}.tmp", LOCK_FILE, std::process::id()));
+        let mut tmp = fs::File::create(&tmp_path)?;
+        tmp.write_all(lock_contents().as_bytes())?;
+        tmp.sync_all()?;
+        drop(tmp);
+        let result = Self::link_in(&tmp_path, path);
+        let _ = fs::remove_file(&tmp_path);
+        result
+    }
+
+    fn link_in(tmp_path: &Path, path: PathBuf) -> Result<std::result::Result<Self, LockHolder>> {
+        // Two tries: the second follows clearing a stale lock.
+        for _ in 0..2 {
+            match fs::hard_link(tmp_path, &path) {
+                Ok(()) => return Ok(Ok(Self { path })),
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => match read_holder(&path) {
+                    Some(holder) if is_alive(holder.pid) => return Ok(Err(holder)),
+                    _ => {
+                        tracing::info!("Removing stale instance lock {}", path.display());
+                        let _ = fs::remove_file(&path);
+                    }
+                },
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Err(anyhow!("Could not create {}", path.display()))
+    }
+
+    fn take_over_in(dir: &Path) -> Result<Self> {
+        fs::create_dir_all(dir)?;
+        let path = dir.join(LOCK_FILE);
+        crate::auth::Session::write_secure_file(&path, lock_contents().as_bytes())?;
+        Ok(Self { path })
+    }
+}
+
+impl Drop for InstanceLock {
+    fn drop(&mut self) {
+        if read_holder(&self.path).is_some_and(|holder| holder.pid == std::process::id()) {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// Stops session saves for this process, after the user chose to run
+/// alongside another instance.
+pub fn set_read_only() {
+    READ_ONLY.store(true, Ordering::Relaxed);
+}
+
+pub fn is_read_only() -> bool {
+    READ_ONLY.load(Ordering::Relaxed)
+}
+
+/// The live instance holding the session lock, when it is not this one.
+/// Used before each save, so an instance that was taken over stops writing.
+pub fn other_holder() -> Option<LockHolder> {
+    let path = crate::profile::data_dir().ok()?.join(LOCK_FILE);
+    read_holder(&path).filter(|holder| holder.pid != std::process::id() && is_alive(holder.pid))
+}
+
+fn lock_contents() -> String {
+    format!("{}\n{}\n", std::process::id(), Utc::now().to_rfc3339())
+}
+
+fn read_holder(path: &Path) -> Option<LockHolder> {
+    let text = fs::read_to_string(path).ok()?;
+    let mut lines = text.lines();
+    let pid = lines.next()?.trim().parse().ok()?;
+    let since = lines
+        .next()
+        .and_then(|line| DateTime::parse_from_rfc3339(line.trim()).ok())
+        .map(|at| at.with_timezone(&Utc));
+    Some(LockHolder { pid, since })
+}
+
+#[cfg(unix)]
+fn is_alive(pid: u32) -> bool {
+    // Zero or anything past i32::MAX would make kill() address a group.
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+    if pid <= 0 {
+        return false;
+    }
+    // Signal 0 only checks; EPERM still means the process exists.
+    let signalled = unsafe { libc::kill(pid, 0) } == 0;
+    signalled || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+#[cfg(not(unix))]
+fn is_alive(_pid: u32) -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("slack-zc-lock-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_live_lock_is_reported_and_released_on_drop() {
+        let dir = temp_dir("live");
+        let lock = InstanceLock::acquire_in(&dir).unwrap().unwrap();
+        // Our own pid is alive, so a second acquire sees a holder.
+        let holder = InstanceLock::acquire_in(&dir).unwrap().unwrap_err();
+        assert_eq!(holder.pid, std::process::id());
+        assert!(holder.since.is_some());
+
+        // Only the lock itself is left behind, never the file it was
+        // written to first.
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        drop(lock);
+        assert!(!dir.join(LOCK_FILE).exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_stale_lock_from_a_dead_pid_is_replaced() {
+        let dir = temp_dir("stale");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(LOCK_FILE), "999999999\n2024-01-01T00:00:00Z\n").unwrap();
+
+        let lock = InstanceLock::acquire_in(&dir).unwrap();
+        assert!(lock.is_ok());
+        let holder = read_holder(&dir.join(LOCK_FILE)).unwrap();
+        assert_eq!(holder.pid, std::process::id());
+        drop(lock);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
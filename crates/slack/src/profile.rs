@@ -0,0 +1,86 @@
+//! Named profiles, each with its own config, session, key file and caches.
+
+use anyhow::{anyhow, Result};
+use directories::ProjectDirs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+pub const DEFAULT_PROFILE: &str = "default";
+
+static PROFILE: OnceLock<String> = OnceLock::new();
+
+/// Selects the profile for this process. Call it once at startup, before
+/// anything resolves a path.
+pub fn set_profile(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(anyhow!(
+            "Invalid profile name {:?}: use letters, digits, '-' and '_'",
+            name
+        ));
+    }
+    PROFILE
+        .set(name.to_string())
+        .map_err(|_| anyhow!("A profile was already selected"))
+}
+
+pub fn current() -> &'static str {
+    PROFILE.get().map(String::as_str).unwrap_or(DEFAULT_PROFILE)
+}
+
+pub fn is_default() -> bool {
+    current() == DEFAULT_PROFILE
+}
+
+pub fn config_dir() -> Result<PathBuf> {
+    Ok(scoped(project_dirs()?.config_dir()))
+}
+
+pub fn data_dir() -> Result<PathBuf> {
+    Ok(scoped(project_dirs()?.data_dir()))
+}
+
+pub fn cache_dir() -> Result<PathBuf> {
+    Ok(scoped(project_dirs()?.cache_dir()))
+}
+
+/// Log file name in the working directory, e.g. `slack-zc-work.log`.
+pub fn log_file_name() -> String {
+    if is_default() {
+        "slack-zc.log".to_string()
+    } else {
+        format!("slack-zc-{}.log", current())
+    }
+}
+
+fn project_dirs() -> Result<ProjectDirs> {
+    ProjectDirs::from("com", "slack-zc", "slack-zc")
+        .ok_or_else(|| anyhow!("Could not determine project directories"))
+}
+
+/// The default profile keeps the top-level directories so installs from
+/// before profiles existed are picked up unchanged; others get
+/// `profiles/<name>` beneath them.
+fn scoped(base: &Path) -> PathBuf {
+    if is_default() {
+        base.to_path_buf()
+    } else {
+        base.join("profiles").join(current())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_names_cannot_escape_the_directory() {
+        for name in ["", "../work", "a/b", "work space"] {
+            assert!(set_profile(name).is_err(), "{name:?} was accepted");
+        }
+        assert_eq!(current(), DEFAULT_PROFILE);
+    }
+}
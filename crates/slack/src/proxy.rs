@@ -0,0 +1,166 @@
+use anyhow::{anyhow, Result};
+use base64::Engine;
+use reqwest::{ClientBuilder, NoProxy, Proxy};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+use tracing::{info, warn};
+use url::Url;
+
+/// Largest CONNECT reply we are willing to read before giving up.
+const MAX_CONNECT_RESPONSE: usize = 8 * 1024;
+
+fn env_var(names: &[&str]) -> Option<String> {
+    names
+        .iter()
+        .find_map(|name| std::env::var(name).ok())
+        .filter(|value| !value.trim().is_empty())
+}
+
+/// Proxy for outbound Slack traffic: `explicit` (from config) first, then
+/// `HTTPS_PROXY`, `HTTP_PROXY` and `ALL_PROXY` in either case.
+pub fn resolve_proxy(explicit: Option<&str>) -> Option<String> {
+    explicit
+        .filter(|proxy| !proxy.trim().is_empty())
+        .map(str::to_string)
+        .or_else(|| {
+            env_var(&[
+                "HTTPS_PROXY",
+                "https_proxy",
+                "HTTP_PROXY",
+                "http_proxy",
+                "ALL_PROXY",
+                "all_proxy",
+            ])
+        })
+}
+
+/// Whether `host` bypasses the proxy. Loopback always does; otherwise
+/// `NO_PROXY` entries match exactly or as a domain suffix, and `*` matches all.
+pub fn bypasses_proxy(host: &str, no_proxy: Option<&str>) -> bool {
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    if host.eq_ignore_ascii_case("localhost")
+        || host
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback())
+    {
+        return true;
+    }
+    no_proxy.unwrap_or_default().split(',').any(|entry| {
+        let entry = entry.trim().trim_start_matches('*').trim_start_matches('.');
+        if entry.is_empty() {
+            return false;
+        }
+        host.eq_ignore_ascii_case(entry)
+            || host
+                .to_ascii_lowercase()
+                .ends_with(&format!(".{}", entry.to_ascii_lowercase()))
+    }) || no_proxy.is_some_and(|list| list.trim() == "*")
+}
+
+/// Applies an explicit proxy to `builder`. Without one reqwest already
+/// honours the proxy environment variables, `NO_PROXY` included.
+pub fn apply_proxy(builder: ClientBuilder, explicit: Option<&str>) -> ClientBuilder {
+    let Some(proxy) = explicit.filter(|proxy| !proxy.trim().is_empty()) else {
+        return builder;
+    };
+    match Proxy::all(proxy) {
+        Ok(proxy) => builder.proxy(proxy.no_proxy(NoProxy::from_env())),
+        Err(e) => {
+            warn!("Ignoring invalid proxy setting: {}", e);
+            builder
+        }
+    }
+}
+
+/// Opens the Socket Mode websocket, tunnelling through an HTTP proxy with
+/// CONNECT when one applies to the target host.
+pub async fn connect_websocket(
+    url: &str,
+    explicit: Option<&str>,
+) -> Result<WebSocketStream<MaybeTlsStream<TcpStream>>> {
+    let target = Url::parse(url)?;
+    let host = target
+        .host_str()
+        .ok_or_else(|| anyhow!("websocket URL has no host"))?
+        .to_string();
+    let port = target
+        .port_or_known_default()
+        .ok_or_else(|| anyhow!("websocket URL has no port"))?;
+
+    let no_proxy = env_var(&["NO_PROXY", "no_proxy"]);
+    let proxy = resolve_proxy(explicit).filter(|_| !bypasses_proxy(&host, no_proxy.as_deref()));
+    let Some(proxy) = proxy else {
+        let (stream, _) = tokio_tungstenite::connect_async(url).await?;
+        return Ok(stream);
+    };
+
+    let proxy = Url::parse(&proxy)?;
+    if proxy.scheme() != "http" {
+        return Err(anyhow!(
+            "unsupported proxy scheme {}:// for the websocket; use an http:// proxy",
+            proxy.scheme()
+        ));
+    }
+    let proxy_host = proxy
+        .host_str()
+        .ok_or_else(|| anyhow!("proxy URL has no host"))?;
+    let proxy_port = proxy.port_or_known_default().unwrap_or(80);
+    info!(
+        "Tunnelling Socket Mode through proxy {}:{}",
+        proxy_host, proxy_port
+    );
+
+    let mut stream = TcpStream::connect((proxy_host, proxy_port)).await?;
+    let mut request = format!(
+        "CONNECT {host}:{port} HTTP/1.1\r\nHost: {host}:{port}\r\n",
+        host = host,
+        port = port
+    );
+    if !proxy.username().is_empty() {
+        let credentials = format!("{}:{}", proxy.username(), proxy.password().unwrap_or(""));
+        request.push_str(&format!(
+            "Proxy-Authorization: Basic {}\r\n",
+            base64::engine::general_purpose::STANDARD.encode(credentials)
+        ));
+    }
+    request.push_str("\r\n");
+    stream.write_all(request.as_bytes()).await?;
+
+    let mut response = Vec::new();
+    let mut buf = [0u8; 512];
+    while !response.ends_with(b"\r\n\r\n") {
+        let read = stream.read(&mut buf).await?;
+        if read == 0 || response.len() > MAX_CONNECT_RESPONSE {
+            return Err(anyhow!("proxy closed the CONNECT tunnel"));
+        }
+        response.extend_from_slice(&buf[..read]);
+    }
+    let status_line = String::from_utf8_lossy(&response);
+    let status_line = status_line.lines().next().unwrap_or_default();
+    if status_line.split_whitespace().nth(1) != Some("200") {
+        return Err(anyhow!("proxy refused the tunnel: {}", status_line));
+    }
+
+    let (stream, _) = tokio_tungstenite::client_async_tls(url, stream).await?;
+    Ok(stream)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bypasses_proxy;
+
+    #[test]
+    fn loopback_and_no_proxy_hosts_skip_the_proxy() {
+        assert!(bypasses_proxy("127.0.0.1", None));
+        assert!(bypasses_proxy("localhost", None));
+        assert!(bypasses_proxy("[::1]", None));
+        assert!(!bypasses_proxy("wss-primary.slack.com", None));
+        assert!(bypasses_proxy(
+            "wss-primary.slack.com",
+            Some("internal.corp, .slack.com")
+        ));
+        assert!(!bypasses_proxy("slack.com.evil.io", Some("slack.com")));
+        assert!(bypasses_proxy("anything", Some("*")));
+    }
+}
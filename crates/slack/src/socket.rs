@@ -1,34 +1,138 @@
 use crate::api::SlackApi;
-use crate::types::Message;
+use crate::types::{CallInfo, Message};
+use crate::users::UserDirectory;
 use anyhow::{anyhow, Result};
 use futures::{SinkExt, StreamExt};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::mpsc;
 use tokio::time::{sleep, timeout};
-use tokio_tungstenite::{connect_async, tungstenite::protocol::Message as WsMessage};
+use tokio_tungstenite::tungstenite::protocol::Message as WsMessage;
 use tracing::{debug, error, info, warn};
 
-const USER_CACHE_TTL: Duration = Duration::from_secs(600);
+/// Minimum gap between [`SlackEvent::Activity`] reports for one socket.
+const ACTIVITY_INTERVAL: Duration = Duration::from_secs(5);
+/// Envelope and event ids remembered to drop redeliveries; Slack retries
+/// within minutes, far fewer frames than this.
+const RECENT_ENVELOPES: usize = 256;
+
+/// The last [`RECENT_ENVELOPES`] ids handled, oldest first out.
+#[derive(Default)]
+struct RecentEnvelopes {
+    order: VecDeque<String>,
+    ids: HashSet<String>,
+}
+
+impl RecentEnvelopes {
+    /// Records the frame's envelope and event ids; false when either was
+    /// already handled, i.e. the frame is a retry.
+    fn first_time(&mut self, data: &Value) -> bool {
+        let ids: Vec<&str> = [
+            data.get("envelope_id"),
+            data.get("payload").and_then(|p| p.get("event_id")),
+        ]
+        .into_iter()
+        .flatten()
+        .filter_map(|v| v.as_str())
+        .collect();
+        if ids.iter().any(|id| self.ids.contains(*id)) {
+            return false;
+        }
+        for id in ids {
+            self.ids.insert(id.to_string());
+            self.order.push_back(id.to_string());
+            if self.order.len() > RECENT_ENVELOPES {
+                if let Some(oldest) = self.order.pop_front() {
+                    self.ids.remove(&oldest);
+                }
+            }
+        }
+        true
+    }
+}
+
+/// Where a workspace's Socket Mode connection stands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionState {
+    Connecting,
+    Connected,
+    /// `attempt` counts failures since the last good connection; the next
+    /// try starts after `retry_in`.
+    Reconnecting {
+        attempt: u32,
+        retry_in: Duration,
+    },
+}
 
 #[derive(Debug, Clone)]
 pub enum SlackEvent {
-    Message { channel: String, message: Message },
-    UserTyping { channel: String, user: String },
-    ChannelJoined { channel: String },
-    ChannelLeft { channel: String },
-    Connected,
-    Disconnected,
+    Message {
+        channel: String,
+        message: Box<Message>,
+    },
+    UserTyping {
+        channel: String,
+        user: String,
+    },
+    ChannelJoined {
+        channel: String,
+    },
+    ChannelLeft {
+        channel: String,
+    },
+    /// `channel_archive`/`channel_unarchive` and their private-channel twins.
+    ChannelArchived {
+        channel: String,
+        archived: bool,
+    },
+    /// A huddle or call announcement at `ts` changed, e.g. it ended.
+    CallUpdated {
+        channel: String,
+        ts: String,
+        call: CallInfo,
+    },
+    /// A `channel_topic` or `channel_purpose` message; only the changed field is set.
+    ChannelTopicChanged {
+        channel: String,
+        topic: Option<String>,
+        purpose: Option<String>,
+    },
+    /// `im_marked`: a DM's read position moved to `ts`. Sent for reads
+    /// Slack chooses to report, so it may be missing or late.
+    ImMarked {
+        channel: String,
+        ts: String,
+    },
+    /// `reaction_added`/`reaction_removed` on the message at `ts`.
+    ReactionChanged {
+        channel: String,
+        ts: String,
+        user: String,
+        name: String,
+        added: bool,
+    },
+    /// The socket for workspace `team_id` changed state.
+    ConnectionState {
+        team_id: String,
+        state: ConnectionState,
+    },
+    /// Frames are arriving on `team_id`'s socket; sent at most every
+    /// [`ACTIVITY_INTERVAL`].
+    Activity {
+        team_id: String,
+    },
 }
 
 pub struct SocketModeClient {
     api: SlackApi,
+    team_id: String,
     xapp_token: String,
     xoxp_token: String,
     event_tx: mpsc::UnboundedSender<SlackEvent>,
-    user_display_names: RwLock<HashMap<String, String>>,
-    user_cache_updated_at: RwLock<Option<Instant>>,
+    /// Names for message authors; the API client's directory when unset.
+    users: Option<Arc<UserDirectory>>,
 }
 
 impl SocketModeClient {
@@ -39,26 +143,65 @@ impl SocketModeClient {
     ) -> Self {
         Self {
             api: SlackApi::new(),
+            team_id: String::new(),
             xapp_token,
             xoxp_token,
             event_tx,
-            user_display_names: RwLock::new(HashMap::new()),
-            user_cache_updated_at: RwLock::new(None),
+            users: None,
         }
     }
 
+    /// Uses `api` for `apps.connections.open`, so a base URL override applies.
+    pub fn with_api(mut self, api: SlackApi) -> Self {
+        self.api = api;
+        self
+    }
+
+    /// Resolves author names from `users`, the directory the app and its
+    /// API client already share for this workspace.
+    pub fn with_users(mut self, users: Arc<UserDirectory>) -> Self {
+        self.users = Some(users);
+        self
+    }
+
+    /// Tags connection events with the workspace this socket belongs to.
+    pub fn with_team_id(mut self, team_id: impl Into<String>) -> Self {
+        self.team_id = team_id.into();
+        self
+    }
+
     pub async fn run(self) {
         let mut backoff = Duration::from_secs(1);
         let max_backoff = Duration::from_secs(30);
+        let mut attempt = 0;
 
+        // Kept across reconnects: Slack may redeliver on the new socket what
+        // the old one already handled.
+        let mut seen = RecentEnvelopes::default();
+
+        self.send_state(ConnectionState::Connecting);
         loop {
-            match self.connect_and_listen().await {
+            let mut connected = false;
+            let result = self.connect_and_listen(&mut connected, &mut seen).await;
+            if connected {
+                attempt = 0;
+            }
+            attempt += 1;
+            match result {
                 Ok(()) => {
                     info!("Socket mode connection closed gracefully");
                     backoff = Duration::from_secs(1);
+                    self.send_state(ConnectionState::Reconnecting {
+                        attempt,
+                        retry_in: Duration::ZERO,
+                    });
                 }
                 Err(e) => {
                     error!("Socket mode error: {}. Reconnecting in {:?}", e, backoff);
+                    self.send_state(ConnectionState::Reconnecting {
+                        attempt,
+                        retry_in: backoff,
+                    });
                     sleep(backoff).await;
                     backoff = std::cmp::min(backoff * 2, max_backoff);
                 }
@@ -66,37 +209,60 @@ impl SocketModeClient {
         }
     }
 
-    async fn connect_and_listen(&self) -> Result<()> {
+    fn send_state(&self, state: ConnectionState) {
+        let _ = self.event_tx.send(SlackEvent::ConnectionState {
+            team_id: self.team_id.clone(),
+            state,
+        });
+    }
+
+    /// Sets `connected` once the websocket is up, so `run` can tell a
+    /// dropped connection from one that never opened.
+    async fn connect_and_listen(
+        &self,
+        connected: &mut bool,
+        seen: &mut RecentEnvelopes,
+    ) -> Result<()> {
         let url = self.api.get_socket_mode_url(&self.xapp_token).await?;
         info!(
             "Connecting to Socket Mode at {}",
             Self::redact_socket_url(&url)
         );
 
-        let (ws_stream, _) = connect_async(&url).await?;
+        let ws_stream = crate::proxy::connect_websocket(&url, self.api.proxy()).await?;
         info!("WebSocket connected");
 
-        let _ = self.event_tx.send(SlackEvent::Connected);
+        *connected = true;
+        self.send_state(ConnectionState::Connected);
 
         let (mut write, mut read) = ws_stream.split();
+        let mut last_activity: Option<Instant> = None;
 
         loop {
             match timeout(Duration::from_secs(60), read.next()).await {
                 Ok(Some(Ok(WsMessage::Text(text)))) => {
                     debug!("Received websocket frame ({} bytes)", text.len());
-
-                    if let Err(e) = self.handle_message(&text).await {
-                        warn!("Error handling message: {}", e);
+                    if last_activity.is_none_or(|at| at.elapsed() >= ACTIVITY_INTERVAL) {
+                        last_activity = Some(Instant::now());
+                        let _ = self.event_tx.send(SlackEvent::Activity {
+                            team_id: self.team_id.clone(),
+                        });
                     }
 
-                    if let Ok(data) = serde_json::from_str::<Value>(&text) {
-                        if let Some(envelope_id) = data.get("envelope_id").and_then(|v| v.as_str())
-                        {
-                            let ack = serde_json::json!({
-                                "envelope_id": envelope_id,
-                            });
-                            write.send(WsMessage::Text(ack.to_string().into())).await?;
+                    let data = match serde_json::from_str::<Value>(&text) {
+                        Ok(data) => data,
+                        Err(e) => {
+                            warn!("Dropping unreadable Socket Mode frame: {}", e);
+                            continue;
                         }
+                    };
+                    // Acked before handling, as Slack recommends: a slow
+                    // handler must not make it retry the envelope.
+                    if let Some(ack) = Self::ack_for(&data) {
+                        write.send(WsMessage::Text(ack.into())).await?;
+                    }
+                    if let Err(e) = self.handle_envelope(&data, seen).await {
+                        warn!("Error handling message: {}", e);
                     }
                 }
                 Ok(Some(Ok(WsMessage::Close(_)))) => {
@@ -117,13 +283,30 @@ impl SocketModeClient {
             }
         }
 
-        let _ = self.event_tx.send(SlackEvent::Disconnected);
         Ok(())
     }
 
-    async fn handle_message(&self, text: &str) -> Result<()> {
-        let data: Value = serde_json::from_str(text)?;
+    /// The acknowledgement for a frame that carries an envelope.
+    fn ack_for(data: &Value) -> Option<String> {
+        let envelope_id = data.get("envelope_id")?.as_str()?;
+        Some(serde_json::json!({ "envelope_id": envelope_id }).to_string())
+    }
 
+    /// Handles a frame unless it is a retry of one already handled.
+    async fn handle_envelope(&self, data: &Value, seen: &mut RecentEnvelopes) -> Result<()> {
+        if !seen.first_time(data) {
+            debug!(
+                "Skipping redelivered envelope (retry_attempt {})",
+                data.get("retry_attempt")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(0)
+            );
+            return Ok(());
+        }
+        self.handle_message(data).await
+    }
+
+    async fn handle_message(&self, data: &Value) -> Result<()> {
         if data.get("type").and_then(|v| v.as_str()) == Some("hello") {
             info!("Socket mode handshake successful");
             return Ok(());
@@ -138,11 +321,61 @@ impl SocketModeClient {
         let event_type = event.get("type").and_then(|v| v.as_str());
 
         match event_type {
-            Some("message") => {
-                if event.get("subtype").is_none() {
-                    if let Some((channel, message)) = self.parse_message(event).await {
-                        let _ = self.event_tx.send(SlackEvent::Message { channel, message });
-                    }
+            Some("message")
+                if matches!(
+                    event.get("subtype").and_then(|v| v.as_str()),
+                    None | Some("bot_message")
+                        | Some("huddle_thread")
+                        | Some("channel_join" | "group_join")
+                        | Some("channel_leave" | "group_leave")
+                        | Some("channel_name" | "group_name")
+                ) =>
+            {
+                if let Some((channel, message)) = self.parse_message(event).await {
+                    let _ = self.event_tx.send(SlackEvent::Message {
+                        channel,
+                        message: Box::new(message),
+                    });
+                }
+            }
+            Some("message")
+                if matches!(
+                    event.get("subtype").and_then(|v| v.as_str()),
+                    Some("channel_topic") | Some("channel_purpose")
+                ) =>
+            {
+                if let Some(channel) = event.get("channel").and_then(|v| v.as_str()) {
+                    let field =
+                        |name: &str| event.get(name).and_then(|v| v.as_str()).map(String::from);
+                    let _ = self.event_tx.send(SlackEvent::ChannelTopicChanged {
+                        channel: channel.to_string(),
+                        topic: field("topic"),
+                        purpose: field("purpose"),
+                    });
+                }
+                // The change also shows in the channel as a system line.
+                if let Some((channel, message)) = self.parse_message(event).await {
+                    let _ = self.event_tx.send(SlackEvent::Message {
+                        channel,
+                        message: Box::new(message),
+                    });
+                }
+            }
+            // Huddles and calls update their announcement in place as people
+            // join and leave, and when they end.
+            Some("message")
+                if event.get("subtype").and_then(|v| v.as_str()) == Some("message_changed") =>
+            {
+                let channel = event.get("channel").and_then(|v| v.as_str());
+                let changed = event.get("message");
+                let ts = changed.and_then(|m| m.get("ts")).and_then(|v| v.as_str());
+                let call = changed.and_then(CallInfo::from_slack_api);
+                if let (Some(channel), Some(ts), Some(call)) = (channel, ts, call) {
+                    let _ = self.event_tx.send(SlackEvent::CallUpdated {
+                        channel: channel.to_string(),
+                        ts: ts.to_string(),
+                        call,
+                    });
                 }
             }
             Some("user_typing") => {
@@ -163,6 +396,51 @@ impl SocketModeClient {
                     });
                 }
             }
+            Some(
+                kind @ ("channel_archive" | "channel_unarchive" | "group_archive"
+                | "group_unarchive"),
+            ) => {
+                if let Some(ch) = event.get("channel").and_then(|v| v.as_str()) {
+                    let _ = self.event_tx.send(SlackEvent::ChannelArchived {
+                        channel: ch.to_string(),
+                        archived: kind.ends_with("_archive"),
+                    });
+                }
+            }
+            Some("im_marked") => {
+                let channel = event.get("channel").and_then(|v| v.as_str());
+                let ts = event.get("ts").and_then(|v| v.as_str());
+                if let (Some(ch), Some(ts)) = (channel, ts) {
+                    let _ = self.event_tx.send(SlackEvent::ImMarked {
+                        channel: ch.to_string(),
+                        ts: ts.to_string(),
+                    });
+                }
+            }
+            Some(kind @ ("reaction_added" | "reaction_removed")) => {
+                let item = event.get("item");
+                let field = |value: Option<&Value>, key: &str| {
+                    value
+                        .and_then(|v| v.get(key))
+                        .and_then(|v| v.as_str())
+                        .map(String::from)
+                };
+                // Reactions on files and file comments have no message ts.
+                if let (Some(channel), Some(ts), Some(user), Some(name)) = (
+                    field(item, "channel"),
+                    field(item, "ts"),
+                    field(Some(event), "user"),
+                    field(Some(event), "reaction"),
+                ) {
+                    let _ = self.event_tx.send(SlackEvent::ReactionChanged {
+                        channel,
+                        ts,
+                        user,
+                        name,
+                        added: kind == "reaction_added",
+                    });
+                }
+            }
             Some("member_left_channel") => {
                 let channel = event.get("channel").and_then(|v| v.as_str());
                 if let Some(ch) = channel {
@@ -178,40 +456,18 @@ impl SocketModeClient {
     }
 
     async fn parse_message(&self, event: &Value) -> Option<(String, Message)> {
-        let ts = event.get("ts")?.as_str()?.to_string();
-        let user_id = event.get("user")?.as_str()?.to_string();
-        let text = event.get("text")?.as_str()?.to_string();
         let channel = event.get("channel")?.as_str()?.to_string();
-
-        let username = self.resolve_username(&user_id).await;
-
-        let message = Message {
-            ts,
-            user_id,
-            username,
-            text,
-            thread_ts: event
-                .get("thread_ts")
-                .and_then(|t| t.as_str())
-                .map(String::from),
-            timestamp: chrono::DateTime::from_timestamp(
-                event
-                    .get("ts")?
-                    .as_str()?
-                    .split('.')
-                    .next()?
-                    .parse::<i64>()
-                    .ok()?,
-                0,
-            )?,
-            is_agent: false,
-            reactions: Vec::new(),
-            is_edited: false,
-            is_deleted: false,
-            files: Vec::new(),
-            reply_count: None,
-            last_read: None,
-        };
+        let mut message = Message::from_slack_api(event, &HashMap::new())?;
+
+        if !message.is_bot {
+            message.username = self.resolve_username(&message.user_id).await;
+        } else if message.username == message.user_id {
+            if let Some(bot_id) = event.get("bot_id").and_then(|v| v.as_str()) {
+                if let Ok(name) = self.api.get_bot_info(&self.xoxp_token, bot_id).await {
+                    message.username = name;
+                }
+            }
+        }
 
         Some((channel, message))
     }
@@ -224,52 +480,58 @@ impl SocketModeClient {
     }
 
     async fn resolve_username(&self, user_id: &str) -> String {
-        {
-            let cache = self.user_display_names.read().await;
-            if let Some(name) = cache.get(user_id) {
-                return name.clone();
-            }
-        }
-
-        if self.should_refresh_user_cache().await {
-            if let Err(e) = self.refresh_user_cache().await {
-                debug!("Failed to refresh user cache: {}", e);
-            }
-        }
-
-        let cache = self.user_display_names.read().await;
-        cache
-            .get(user_id)
-            .cloned()
+        let users = self
+            .users
+            .clone()
+            .unwrap_or_else(|| self.api.user_directory(&self.xoxp_token));
+        users
+            .display_name(&self.api, &self.xoxp_token, user_id)
+            .await
             .unwrap_or_else(|| user_id.to_string())
     }
+}
 
-    async fn should_refresh_user_cache(&self) -> bool {
-        let updated_at = *self.user_cache_updated_at.read().await;
-        match updated_at {
-            Some(ts) => ts.elapsed() >= USER_CACHE_TTL,
-            None => true,
-        }
-    }
-
-    async fn refresh_user_cache(&self) -> Result<()> {
-        let users = self.api.list_users(&self.xoxp_token).await?;
-        let next_cache: HashMap<String, String> = users
-            .into_iter()
-            .map(|u| {
-                let display_name = u.display_name();
-                (u.id, display_name)
-            })
-            .collect();
-
-        {
-            let mut cache = self.user_display_names.write().await;
-            *cache = next_cache;
+/// Sends a typing event over the RTM WebSocket at `url` (from
+/// [`SlackApi::rtm_connect`]) for each channel id received, until `channels`
+/// closes. Socket Mode has no client-to-server events and the Web API has no
+/// typing method, so RTM is the only route.
+pub async fn run_rtm_typing(
+    api: &SlackApi,
+    url: &str,
+    mut channels: mpsc::UnboundedReceiver<String>,
+) -> Result<()> {
+    info!(
+        "Connecting to RTM for typing at {}",
+        SocketModeClient::redact_socket_url(url)
+    );
+    let ws_stream = crate::proxy::connect_websocket(url, api.proxy()).await?;
+    let (mut write, mut read) = ws_stream.split();
+    let mut next_id: u64 = 1;
+
+    loop {
+        tokio::select! {
+            channel = channels.recv() => {
+                let Some(channel) = channel else {
+                    return Ok(());
+                };
+                let event = serde_json::json!({
+                    "id": next_id,
+                    "type": "typing",
+                    "channel": channel,
+                });
+                next_id += 1;
+                write.send(WsMessage::Text(event.to_string().into())).await?;
+            }
+            // Incoming RTM events duplicate Socket Mode and are dropped;
+            // reading keeps pings answered.
+            frame = read.next() => match frame {
+                Some(Ok(WsMessage::Close(_))) | None => {
+                    return Err(anyhow!("RTM connection closed"));
+                }
+                Some(Err(e)) => return Err(anyhow!("RTM WebSocket error: {}", e)),
+                Some(Ok(_)) => {}
+            },
         }
-
-        let mut updated_at = self.user_cache_updated_at.write().await;
-        *updated_at = Some(Instant::now());
-        Ok(())
     }
 }
 
@@ -288,11 +550,19 @@ mod tests {
 
     #[test]
     fn test_slack_event_enum_variants() {
-        let _event1 = SlackEvent::Connected;
-        let _event2 = SlackEvent::Disconnected;
+        let _event1 = SlackEvent::ConnectionState {
+            team_id: "T123".to_string(),
+            state: ConnectionState::Reconnecting {
+                attempt: 2,
+                retry_in: Duration::from_secs(4),
+            },
+        };
+        let _event2 = SlackEvent::Activity {
+            team_id: "T123".to_string(),
+        };
         let _event3 = SlackEvent::Message {
             channel: "C123".to_string(),
-            message: Message {
+            message: Box::new(Message {
                 ts: "123.456".to_string(),
                 user_id: "U123".to_string(),
                 username: "test".to_string(),
@@ -300,13 +570,22 @@ mod tests {
                 thread_ts: None,
                 timestamp: chrono::Utc::now(),
                 is_agent: false,
+                is_bot: false,
                 reactions: Vec::new(),
                 is_edited: false,
                 is_deleted: false,
                 files: Vec::new(),
                 reply_count: None,
+                latest_reply: None,
+                reply_users: Vec::new(),
                 last_read: None,
-            },
+                rendered_blocks: Vec::new(),
+                is_system: false,
+                subtype: None,
+                call: None,
+                raw: None,
+                delivery: None,
+            }),
         };
     }
 
@@ -324,4 +603,114 @@ mod tests {
             _ => panic!("Expected ChannelJoined variant"),
         }
     }
+
+    fn envelope(envelope_id: &str, event_id: &str, retry_attempt: u64) -> Value {
+        serde_json::json!({
+            "envelope_id": envelope_id,
+            "type": "events_api",
+            "retry_attempt": retry_attempt,
+            "payload": {
+                "event_id": event_id,
+                "event": {
+                    "type": "message",
+                    "subtype": "bot_message",
+                    "channel": "C123",
+                    "bot_id": "B1",
+                    "username": "deploy-bot",
+                    "text": "Deployed",
+                    "ts": "1730000000.000100",
+                },
+            },
+        })
+    }
+
+    #[tokio::test]
+    async fn redelivered_envelopes_emit_one_message() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let client = SocketModeClient::new("xapp-test".to_string(), "xoxp-test".to_string(), tx);
+        let mut seen = RecentEnvelopes::default();
+
+        // The same envelope twice, then Slack's retry of it under a new
+        // envelope id but the same event id.
+        for frame in [
+            envelope("env-1", "Ev1", 0),
+            envelope("env-1", "Ev1", 0),
+            envelope("env-2", "Ev1", 1),
+        ] {
+            client.handle_envelope(&frame, &mut seen).await.unwrap();
+        }
+        client
+            .handle_envelope(&envelope("env-3", "Ev2", 0), &mut seen)
+            .await
+            .unwrap();
+
+        let mut messages = 0;
+        while let Ok(event) = rx.try_recv() {
+            assert!(matches!(event, SlackEvent::Message { .. }));
+            messages += 1;
+        }
+        assert_eq!(messages, 2);
+    }
+
+    #[tokio::test]
+    async fn reaction_events_name_the_message_and_user() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let client = SocketModeClient::new("xapp-test".to_string(), "xoxp-test".to_string(), tx);
+        let mut seen = RecentEnvelopes::default();
+        let reaction = |id: &str, kind: &str, item: Value| {
+            serde_json::json!({
+                "envelope_id": id,
+                "payload": {"event": {
+                    "type": kind, "user": "U1", "reaction": "eyes", "item": item,
+                }},
+            })
+        };
+        let on_message = serde_json::json!({"type": "message", "channel": "C1", "ts": "1.2"});
+        for frame in [
+            reaction("env-1", "reaction_added", on_message.clone()),
+            reaction("env-2", "reaction_removed", on_message),
+            reaction(
+                "env-3",
+                "reaction_added",
+                serde_json::json!({"type": "file", "file": "F1"}),
+            ),
+        ] {
+            client.handle_envelope(&frame, &mut seen).await.unwrap();
+        }
+
+        let mut changes = Vec::new();
+        while let Ok(SlackEvent::ReactionChanged {
+            channel,
+            ts,
+            user,
+            name,
+            added,
+        }) = rx.try_recv()
+        {
+            changes.push((channel, ts, user, name, added));
+        }
+        let change = |added| ("C1".into(), "1.2".into(), "U1".into(), "eyes".into(), added);
+        assert_eq!(changes, [change(true), change(false)]);
+    }
+
+    #[test]
+    fn every_envelope_is_acked_even_when_handling_would_fail() {
+        let no_event = serde_json::json!({"envelope_id": "env-9", "payload": {}});
+        assert_eq!(
+            SocketModeClient::ack_for(&no_event).as_deref(),
+            Some(r#"{"envelope_id":"env-9"}"#)
+        );
+        assert!(SocketModeClient::ack_for(&serde_json::json!({"type": "hello"})).is_none());
+    }
+
+    #[test]
+    fn recent_envelopes_forget_the_oldest() {
+        let mut seen = RecentEnvelopes::default();
+        for i in 0..=RECENT_ENVELOPES {
+            assert!(seen.first_time(&serde_json::json!({ "envelope_id": format!("env-{i}") })));
+        }
+        assert!(seen.first_time(&serde_json::json!({"envelope_id": "env-0"})));
+        assert!(!seen
+            .first_time(&serde_json::json!({ "envelope_id": format!("env-{RECENT_ENVELOPES}") })));
+    }
 }
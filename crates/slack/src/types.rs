@@ -1,6 +1,15 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static KEEP_RAW: AtomicBool = AtomicBool::new(false);
+
+/// Makes [`Message::from_slack_api`] keep each message's original payload
+/// in [`Message::raw`], for inspecting what the parsed fields leave out.
+pub fn set_keep_raw(keep: bool) {
+    KEEP_RAW.store(keep, Ordering::Relaxed);
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Channel {
@@ -9,13 +18,72 @@ pub struct Channel {
     pub is_dm: bool,
     pub is_group: bool,
     pub is_im: bool,
+    /// Multi-person DM; also has `is_dm` set.
+    #[serde(default)]
+    pub is_mpim: bool,
     pub unread_count: u32,
+    /// Unread messages that mention the current user.
+    #[serde(default)]
+    pub mention_count: u32,
     pub purpose: Option<String>,
     pub topic: Option<String>,
     pub user: Option<String>,
+    /// Filled lazily from `conversations.info`.
+    #[serde(default)]
+    pub member_count: Option<u32>,
+    /// Unix seconds.
+    #[serde(default)]
+    pub created: Option<i64>,
+    #[serde(default)]
+    pub creator: Option<String>,
+    /// Shared beyond this workspace, by Slack Connect or an org share.
+    #[serde(default)]
+    pub is_shared: bool,
+    /// Slack Connect: members from other organizations can read it.
+    #[serde(default)]
+    pub is_ext_shared: bool,
+    /// Shared across workspaces of the same Enterprise Grid org.
+    #[serde(default)]
+    pub is_org_shared: bool,
+    /// Read-only; only listed when archived channels are shown.
+    #[serde(default)]
+    pub is_archived: bool,
+    /// For DMs, the `last_read` from `conversations.info` or an `im_marked`
+    /// event, taken as how far the other person has read. Best effort:
+    /// Slack reports it late or not at all.
+    #[serde(default, skip_serializing)]
+    pub last_read: Option<String>,
+    /// `ts` of the newest message known in the channel, from `latest` in
+    /// `conversations.info`; the app keeps it current and in its cache.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latest_ts: Option<String>,
+    /// Whether the current user has joined; `None` when Slack did not say.
+    #[serde(default)]
+    pub is_member: Option<bool>,
+    /// Who may start new messages, in announcement-style channels; from
+    /// `properties.posting_restricted_to` or the older `is_read_only`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub posting_restricted_to: Option<PostingRestriction>,
+    /// Who may reply in threads, when Slack limits that too.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threads_restricted_to: Option<PostingRestriction>,
 }
 
 impl Channel {
+    pub fn is_any_shared(&self) -> bool {
+        self.is_shared || self.is_ext_shared || self.is_org_shared
+    }
+
+    /// The restriction a post goes through: the thread one for a reply,
+    /// the channel one otherwise.
+    pub fn posting_restriction(&self, in_thread: bool) -> Option<&PostingRestriction> {
+        if in_thread {
+            self.threads_restricted_to.as_ref()
+        } else {
+            self.posting_restricted_to.as_ref()
+        }
+    }
+
     pub fn display_name(&self) -> String {
         if self.is_dm {
             format!("@ {}", self.name)
@@ -25,6 +93,78 @@ impl Channel {
     }
 }
 
+/// The roles (`admin`, `owner`…) and users Slack lets post where it
+/// restricts posting.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PostingRestriction {
+    #[serde(default)]
+    pub types: Vec<String>,
+    #[serde(default)]
+    pub users: Vec<String>,
+}
+
+impl PostingRestriction {
+    /// Reads Slack's `{"type": [...], "user": [...]}`; `None` when neither
+    /// list names anyone, which Slack sends for channels anyone can post in.
+    pub fn from_slack_api(value: &serde_json::Value) -> Option<Self> {
+        let list = |key: &str| -> Vec<String> {
+            value
+                .get(key)
+                .and_then(|v| v.as_array())
+                .map(|items| {
+                    items
+                        .iter()
+                        .filter_map(|v| v.as_str())
+                        .map(String::from)
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let restriction = Self {
+            types: list("type"),
+            users: list("user"),
+        };
+        (!restriction.types.is_empty() || !restriction.users.is_empty()).then_some(restriction)
+    }
+
+    /// Only workspace admins and owners, as the legacy `is_read_only` means.
+    pub fn admins_only() -> Self {
+        Self {
+            types: vec!["admin".to_string()],
+            users: Vec::new(),
+        }
+    }
+
+    /// Whether `user_id` may post. Admins count as any role below owner;
+    /// owners as any role.
+    pub fn allows(&self, user_id: &str, is_admin: bool, is_owner: bool) -> bool {
+        self.users.iter().any(|user| user == user_id)
+            || self.types.iter().any(|role| match role.as_str() {
+                "owner" => is_owner,
+                _ => is_admin || is_owner,
+            })
+    }
+
+    /// Who may post, for telling the user: "admins", "owners and 2 people".
+    pub fn describe(&self) -> String {
+        let mut who: Vec<String> = self
+            .types
+            .iter()
+            .map(|role| match role.as_str() {
+                "admin" => "admins".to_string(),
+                "owner" => "owners".to_string(),
+                other => format!("{other}s"),
+            })
+            .collect();
+        match self.users.len() {
+            0 => {}
+            1 => who.push("1 person".to_string()),
+            n => who.push(format!("{n} people")),
+        }
+        who.join(" and ")
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Message {
     pub ts: String,
@@ -34,53 +174,110 @@ pub struct Message {
     pub thread_ts: Option<String>,
     pub timestamp: DateTime<Utc>,
     pub is_agent: bool,
+    /// Posted by a bot, app, workflow or webhook; `user_id` then holds the
+    /// `bot_id` (or `app_id`) when the payload has no `user`.
+    #[serde(default)]
+    pub is_bot: bool,
     pub reactions: Vec<Reaction>,
     pub is_edited: bool,
     pub is_deleted: bool,
     pub files: Vec<File>,
     pub reply_count: Option<u32>,
+    /// Timestamp of the newest reply, for thread parents.
+    #[serde(default)]
+    pub latest_reply: Option<String>,
+    /// Ids of the users who replied, for thread parents. Slack lists only
+    /// the first few participants.
+    #[serde(default)]
+    pub reply_users: Vec<String>,
     pub last_read: Option<String>,
+    /// Block Kit blocks and legacy attachments flattened to mrkdwn lines.
+    #[serde(default)]
+    pub rendered_blocks: Vec<String>,
+    /// Join, leave, topic, purpose and rename notices. `text` then holds
+    /// only what happened ("joined"), drawn after the user's name.
+    #[serde(default)]
+    pub is_system: bool,
+    /// Slack's `subtype`, e.g. `channel_join`; `None` for plain posts.
+    #[serde(default)]
+    pub subtype: Option<String>,
+    /// Set for huddle and call announcements, which are drawn as a
+    /// system line instead of text.
+    #[serde(default)]
+    pub call: Option<CallInfo>,
+    /// The payload as Slack sent it, when [`set_keep_raw`] is on. Never
+    /// written to the message cache.
+    #[serde(skip)]
+    pub raw: Option<String>,
+    /// Set on messages typed in this client while their post is tracked;
+    /// `None` for everything read from Slack. Never sent or cached.
+    #[serde(skip)]
+    pub delivery: Option<DeliveryState>,
+}
+
+/// How far a post typed in this client has got.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryState {
+    /// On its way; the message still has a local `ts`.
+    Sending,
+    /// Slack accepted it and the message carries Slack's `ts`.
+    Sent,
+    /// Slack refused it or could not be reached.
+    Failed,
 }
 
 impl Message {
+    /// Counts a live reply from `user_id` on this thread parent, so its
+    /// summary stays current without refetching the thread.
+    pub fn note_reply(&mut self, user_id: &str, ts: &str) {
+        self.reply_count = Some(self.reply_count.unwrap_or(0) + 1);
+        if !user_id.is_empty() && !self.reply_users.iter().any(|id| id == user_id) {
+            self.reply_users.push(user_id.to_string());
+        }
+        if self
+            .latest_reply
+            .as_deref()
+            .is_none_or(|latest| latest < ts)
+        {
+            self.latest_reply = Some(ts.to_string());
+        }
+    }
+
+    /// A post still waiting on Slack, or refused by it: it has no real
+    /// `ts` yet, so it must not be used as a read cursor or thread parent.
+    pub fn is_unconfirmed(&self) -> bool {
+        matches!(
+            self.delivery,
+            Some(DeliveryState::Sending | DeliveryState::Failed)
+        )
+    }
+
     pub fn from_slack_api(msg: &serde_json::Value, users: &HashMap<String, User>) -> Option<Self> {
         let ts = msg.get("ts")?.as_str()?.to_string();
-        let user_id = msg.get("user")?.as_str()?.to_string();
+        let str_field = |key: &str| msg.get(key).and_then(|v| v.as_str());
+        let is_bot = str_field("bot_id").is_some() || str_field("subtype") == Some("bot_message");
+        let user_id = str_field("user")
+            .or_else(|| str_field("bot_id"))
+            .or_else(|| str_field("app_id"))?
+            .to_string();
         let username = users
             .get(&user_id)
             .map(|u| u.display_name())
+            .or_else(|| is_bot.then(|| Self::bot_username(msg)).flatten())
             .unwrap_or_else(|| user_id.clone());
-        let text = msg.get("text")?.as_str()?.to_string();
+        let subtype = str_field("subtype").map(String::from);
+        let system_text = subtype
+            .as_deref()
+            .and_then(|subtype| Self::system_text(subtype, msg));
+        let is_system = system_text.is_some();
+        let text = system_text.unwrap_or_else(|| Self::display_text(msg));
         let thread_ts = msg
             .get("thread_ts")
             .and_then(|t| t.as_str())
             .map(String::from);
         let timestamp = DateTime::from_timestamp(ts.split('.').next()?.parse::<i64>().ok()?, 0)?;
 
-        let reactions: Vec<Reaction> = msg
-            .get("reactions")
-            .and_then(|r| r.as_array())
-            .map(|arr| {
-                arr.iter()
-                    .filter_map(|r| {
-                        Some(Reaction {
-                            name: r.get("name")?.as_str()?.to_string(),
-                            count: r.get("count")?.as_u64()? as u32,
-                            users: r
-                                .get("users")
-                                .and_then(|u| u.as_array())
-                                .map(|users| {
-                                    users
-                                        .iter()
-                                        .filter_map(|u| u.as_str().map(String::from))
-                                        .collect()
-                                })
-                                .unwrap_or_default(),
-                        })
-                    })
-                    .collect()
-            })
-            .unwrap_or_default();
+        let reactions = Self::parse_list(msg, "reactions", Reaction::from_slack_api);
 
         let is_edited = msg.get("edited").is_some();
         let is_deleted = msg.get("deleted_at").is_some()
@@ -89,36 +286,20 @@ impl Message {
                 .and_then(|v| v.as_bool())
                 .unwrap_or(false);
 
-        let files: Vec<File> = msg
-            .get("files")
-            .and_then(|f| f.as_array())
-            .map(|arr| {
-                arr.iter()
-                    .filter_map(|f| {
-                        Some(File {
-                            id: f.get("id")?.as_str()?.to_string(),
-                            name: f.get("name")?.as_str()?.to_string(),
-                            mimetype: f.get("mimetype").and_then(|m| m.as_str()).map(String::from),
-                            url_private: f
-                                .get("url_private")
-                                .and_then(|u| u.as_str())
-                                .map(String::from),
-                            url_private_download: f
-                                .get("url_private_download")
-                                .and_then(|u| u.as_str())
-                                .map(String::from),
-                            size: f.get("size")?.as_u64()? as u32,
-                        })
-                    })
-                    .collect()
-            })
-            .unwrap_or_default();
+        let files = Self::parse_list(msg, "files", File::from_slack_api);
 
         let reply_count = msg
             .get("reply_count")
             .and_then(|r| r.as_u64())
             .map(|v| v as u32);
 
+        let latest_reply = msg
+            .get("latest_reply")
+            .and_then(|r| r.as_str())
+            .map(String::from);
+
+        let reply_users = Self::parse_list(msg, "reply_users", |u| u.as_str().map(String::from));
+
         let last_read = msg
             .get("last_read")
             .and_then(|r| r.as_str())
@@ -132,14 +313,295 @@ impl Message {
             thread_ts,
             timestamp,
             is_agent: false,
+            is_bot,
             reactions,
             is_edited,
             is_deleted,
             files,
             reply_count,
+            latest_reply,
+            reply_users,
             last_read,
+            rendered_blocks: Self::render_blocks(msg),
+            is_system,
+            subtype,
+            call: CallInfo::from_slack_api(msg),
+            raw: KEEP_RAW.load(Ordering::Relaxed).then(|| msg.to_string()),
+            delivery: None,
+        })
+    }
+
+    /// Parses each entry of the array under `key`, skipping the ones
+    /// `parse` rejects rather than the whole message.
+    fn parse_list<T>(
+        msg: &serde_json::Value,
+        key: &str,
+        parse: impl Fn(&serde_json::Value) -> Option<T>,
+    ) -> Vec<T> {
+        msg.get(key)
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.iter().filter_map(parse).collect())
+            .unwrap_or_default()
+    }
+
+    /// Readable lines for a message's `blocks` and `attachments`. `rich_text`
+    /// blocks are skipped because `text` already carries the same content.
+    fn render_blocks(msg: &serde_json::Value) -> Vec<String> {
+        let mut lines = Vec::new();
+        let array = |value: &serde_json::Value, key: &str| -> Vec<serde_json::Value> {
+            value
+                .get(key)
+                .and_then(|v| v.as_array())
+                .cloned()
+                .unwrap_or_default()
+        };
+
+        for block in array(msg, "blocks") {
+            let text = block_text(block.get("text"));
+            match block.get("type").and_then(|t| t.as_str()) {
+                Some("rich_text") => {}
+                Some("divider") => lines.push("───".to_string()),
+                Some("header") => lines.extend(text.map(|t| format!("*{}*", t))),
+                Some("section") => {
+                    lines.extend(text);
+                    let fields: Vec<String> = array(&block, "fields")
+                        .iter()
+                        .filter_map(|field| block_text(Some(field)))
+                        .collect();
+                    lines.extend(fields);
+                }
+                Some("context") => {
+                    let parts: Vec<String> = array(&block, "elements")
+                        .iter()
+                        .filter_map(|element| block_text(Some(element)))
+                        .collect();
+                    if !parts.is_empty() {
+                        lines.push(parts.join(" · "));
+                    }
+                }
+                Some("image") => {
+                    let alt = block.get("alt_text").and_then(|v| v.as_str());
+                    lines.push(format!("[image: {}]", alt.unwrap_or("untitled")));
+                }
+                Some("actions") => {
+                    let buttons: Vec<String> = array(&block, "elements")
+                        .iter()
+                        .filter_map(|element| block_text(element.get("text")))
+                        .map(|label| format!("[{}]", label))
+                        .collect();
+                    if !buttons.is_empty() {
+                        lines.push(buttons.join(" "));
+                    }
+                }
+                _ => {
+                    let mut raw = Vec::new();
+                    collect_text_fields(&block, &mut raw);
+                    lines.extend(raw);
+                }
+            }
+        }
+
+        for attachment in array(msg, "attachments") {
+            let field = |key: &str| {
+                attachment
+                    .get(key)
+                    .and_then(|v| v.as_str())
+                    .filter(|v| !v.trim().is_empty())
+                    .map(String::from)
+            };
+            lines.extend(field("pretext"));
+            lines.extend(field("title").map(|title| format!("*{}*", title)));
+            lines.extend(field("text"));
+            for item in array(&attachment, "fields") {
+                let title = item.get("title").and_then(|v| v.as_str()).unwrap_or("");
+                let value = item.get("value").and_then(|v| v.as_str()).unwrap_or("");
+                match (title.is_empty(), value.is_empty()) {
+                    (false, false) => lines.push(format!("{}: {}", title, value)),
+                    (true, false) => lines.push(value.to_string()),
+                    (false, true) => lines.push(title.to_string()),
+                    (true, true) => {}
+                }
+            }
+            lines.extend(field("footer"));
+            // Blocks nested in an attachment render like top-level ones.
+            if attachment.get("blocks").is_some() {
+                lines.extend(Self::render_blocks(&attachment));
+            }
+        }
+        lines
+    }
+
+    /// What an informational subtype says happened, without the actor.
+    fn system_text(subtype: &str, msg: &serde_json::Value) -> Option<String> {
+        let field = |key: &str| {
+            msg.get(key)
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+        };
+        let set_or_cleared = |what: &str, value: Option<&str>| match value {
+            Some(value) => format!("set the {} to “{}”", what, value),
+            None => format!("cleared the {}", what),
+        };
+        Some(match subtype {
+            "channel_join" | "group_join" => "joined".to_string(),
+            "channel_leave" | "group_leave" => "left".to_string(),
+            "channel_topic" | "group_topic" => set_or_cleared("topic", field("topic")),
+            "channel_purpose" | "group_purpose" => set_or_cleared("purpose", field("purpose")),
+            "channel_name" | "group_name" => {
+                format!("renamed the channel to #{}", field("name")?)
+            }
+            _ => return None,
         })
     }
+
+    /// The line drawn for a system message, e.g. "→ alice joined".
+    pub fn system_line(&self) -> String {
+        let glyph = match self.subtype.as_deref() {
+            Some("channel_join" | "group_join") => "→",
+            Some("channel_leave" | "group_leave") => "←",
+            _ => "✎",
+        };
+        format!("{} {} {}", glyph, self.username, self.text)
+    }
+
+    /// Name a bot payload carries itself, before any `bots.info` lookup.
+    fn bot_username(msg: &serde_json::Value) -> Option<String> {
+        msg.get("username")
+            .or_else(|| msg.get("bot_profile").and_then(|p| p.get("name")))
+            .and_then(|v| v.as_str())
+            .filter(|name| !name.is_empty())
+            .map(String::from)
+    }
+
+    /// `text`, or for attachment-only posts the first attachment's title,
+    /// pretext or fallback so the message is not shown blank.
+    fn display_text(msg: &serde_json::Value) -> String {
+        let text = msg.get("text").and_then(|t| t.as_str()).unwrap_or_default();
+        if !text.trim().is_empty() {
+            return text.to_string();
+        }
+        msg.get("attachments")
+            .and_then(|a| a.as_array())
+            .into_iter()
+            .flatten()
+            .find_map(|attachment| {
+                ["title", "pretext", "fallback", "text"]
+                    .iter()
+                    .filter_map(|key| attachment.get(key).and_then(|v| v.as_str()))
+                    .find(|value| !value.trim().is_empty())
+            })
+            .unwrap_or(text)
+            .to_string()
+    }
+}
+
+/// A huddle (`subtype: "huddle_thread"`) or third-party call (a `call`
+/// block) announced in a channel.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CallInfo {
+    /// "Huddle", or the call's own name, e.g. "Zoom meeting".
+    pub title: String,
+    pub participants: usize,
+    pub ended: bool,
+}
+
+impl CallInfo {
+    pub fn from_slack_api(msg: &serde_json::Value) -> Option<Self> {
+        let count = |value: &serde_json::Value, keys: [&str; 2]| {
+            keys.iter()
+                .filter_map(|key| value.get(*key).and_then(|v| v.as_array()))
+                .map(Vec::len)
+                .max()
+                .unwrap_or(0)
+        };
+        let ended = |value: &serde_json::Value| {
+            value.get("has_ended").and_then(|v| v.as_bool()) == Some(true)
+                || value
+                    .get("date_end")
+                    .and_then(|v| v.as_i64())
+                    .is_some_and(|end| end > 0)
+        };
+
+        if msg.get("subtype").and_then(|v| v.as_str()) == Some("huddle_thread") {
+            let room = msg.get("room").cloned().unwrap_or_default();
+            // Once a huddle ends `participants` empties; the history keeps everyone.
+            return Some(Self {
+                title: "Huddle".to_string(),
+                participants: count(&room, ["participants", "participant_history"]),
+                ended: ended(&room),
+            });
+        }
+
+        let call = msg
+            .get("blocks")
+            .and_then(|b| b.as_array())?
+            .iter()
+            .find(|block| block.get("type").and_then(|t| t.as_str()) == Some("call"))?
+            .get("call")
+            .and_then(|c| c.get("v1"))
+            .cloned()
+            .unwrap_or_default();
+        let title = call
+            .get("name")
+            .and_then(|v| v.as_str())
+            .filter(|name| !name.trim().is_empty())
+            .unwrap_or("Call");
+        Some(Self {
+            title: title.to_string(),
+            participants: count(&call, ["active_participants", "all_participants"]),
+            ended: ended(&call),
+        })
+    }
+
+    /// The line shown in place of the message, e.g.
+    /// "📞 Huddle started by @alice — 3 participants".
+    pub fn summary(&self, started_by: &str) -> String {
+        let mut line = if self.ended {
+            format!("📞 {} ended (started by @{})", self.title, started_by)
+        } else {
+            format!("📞 {} started by @{}", self.title, started_by)
+        };
+        match self.participants {
+            0 => {}
+            1 => line.push_str(" — 1 participant"),
+            n => line.push_str(&format!(" — {} participants", n)),
+        }
+        line
+    }
+}
+
+/// `text` of a Block Kit text object (`plain_text` or `mrkdwn`).
+fn block_text(object: Option<&serde_json::Value>) -> Option<String> {
+    object?
+        .get("text")
+        .and_then(|t| t.as_str())
+        .filter(|t| !t.trim().is_empty())
+        .map(String::from)
+}
+
+/// Every text object inside an unknown block, so it degrades to its words.
+fn collect_text_fields(value: &serde_json::Value, out: &mut Vec<String>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            if let Some(text) = map.get("text").and_then(|t| t.as_str()) {
+                if !text.trim().is_empty() {
+                    out.push(text.to_string());
+                }
+            }
+            for (key, child) in map {
+                if key != "text" || child.is_object() {
+                    collect_text_fields(child, out);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                collect_text_fields(item, out);
+            }
+        }
+        _ => {}
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -149,6 +611,25 @@ pub struct Reaction {
     pub users: Vec<String>,
 }
 
+impl Reaction {
+    pub fn from_slack_api(reaction: &serde_json::Value) -> Option<Self> {
+        Some(Self {
+            name: reaction.get("name")?.as_str()?.to_string(),
+            count: reaction.get("count")?.as_u64()? as u32,
+            users: reaction
+                .get("users")
+                .and_then(|u| u.as_array())
+                .map(|users| {
+                    users
+                        .iter()
+                        .filter_map(|u| u.as_str().map(String::from))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct File {
     pub id: String,
@@ -159,6 +640,25 @@ pub struct File {
     pub size: u32,
 }
 
+impl File {
+    /// Only `id` is required: tombstoned, external and over-the-limit files
+    /// come without a name or size but should still show.
+    pub fn from_slack_api(file: &serde_json::Value) -> Option<Self> {
+        let str_field = |key: &str| file.get(key).and_then(|v| v.as_str()).map(String::from);
+        let id = str_field("id")?;
+        Some(Self {
+            name: str_field("name")
+                .or_else(|| str_field("title"))
+                .unwrap_or_else(|| id.clone()),
+            mimetype: str_field("mimetype"),
+            url_private: str_field("url_private"),
+            url_private_download: str_field("url_private_download"),
+            size: file.get("size").and_then(|v| v.as_u64()).unwrap_or(0) as u32,
+            id,
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct FileInfo {
     pub id: String,
@@ -171,6 +671,65 @@ pub struct FileInfo {
     pub filetype: Option<String>,
 }
 
+/// A message the user saved for later (`stars.list`).
+#[derive(Debug, Clone)]
+pub struct SavedItem {
+    pub channel_id: String,
+    pub message: Message,
+}
+
+impl SavedItem {
+    /// Only saved messages; saved files and channels are skipped.
+    pub fn from_slack_api(item: &serde_json::Value, users: &HashMap<String, User>) -> Option<Self> {
+        if item.get("type").and_then(|v| v.as_str()) != Some("message") {
+            return None;
+        }
+        Some(Self {
+            channel_id: item.get("channel")?.as_str()?.to_string(),
+            message: Message::from_slack_api(item.get("message")?, users)?,
+        })
+    }
+}
+
+/// A `reminders.list` entry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reminder {
+    pub id: String,
+    pub text: String,
+    /// When it fires; `None` for recurring reminders, which have no
+    /// single time.
+    pub time: Option<DateTime<Utc>>,
+    pub recurring: bool,
+    pub complete: bool,
+}
+
+impl Reminder {
+    pub fn from_slack_api(reminder: &serde_json::Value) -> Option<Self> {
+        let id = reminder.get("id")?.as_str()?.to_string();
+        let complete = reminder
+            .get("complete_ts")
+            .and_then(|v| v.as_i64())
+            .is_some_and(|ts| ts > 0);
+        Some(Self {
+            id,
+            text: reminder
+                .get("text")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            time: reminder
+                .get("time")
+                .and_then(|v| v.as_i64())
+                .and_then(|secs| DateTime::from_timestamp(secs, 0)),
+            recurring: reminder
+                .get("recurring")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            complete,
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Thread {
     pub parent_ts: String,
@@ -201,9 +760,58 @@ pub struct User {
     pub display_name: String,
     pub real_name: String,
     pub email: Option<String>,
+    /// Workspace admin or owner, which restricted channels let post.
+    #[serde(default)]
+    pub is_admin: bool,
+    #[serde(default)]
+    pub is_owner: bool,
 }
 
 impl User {
+    /// Parses a `users.*` member. Only the id is required: deleted and
+    /// external users may lack a `name` or `profile`, and each fallback is
+    /// logged with the id.
+    pub fn from_slack_api(user: &serde_json::Value) -> Option<Self> {
+        let Some(id) = user.get("id").and_then(|v| v.as_str()) else {
+            tracing::debug!("Skipping user without an id");
+            return None;
+        };
+        let name = user
+            .get("name")
+            .and_then(|v| v.as_str())
+            .map(String::from)
+            .unwrap_or_else(|| {
+                tracing::debug!("User {} has no name; showing their id", id);
+                id.to_string()
+            });
+        let profile = user.get("profile");
+        if profile.is_none() {
+            tracing::debug!("User {} has no profile", id);
+        }
+        let field = |key: &str| {
+            profile
+                .and_then(|p| p.get(key))
+                .and_then(|v| v.as_str())
+                .map(String::from)
+        };
+
+        Some(Self {
+            id: id.to_string(),
+            name,
+            display_name: field("display_name").unwrap_or_default(),
+            real_name: field("real_name").unwrap_or_default(),
+            email: field("email"),
+            is_admin: user
+                .get("is_admin")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            is_owner: user
+                .get("is_owner")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+        })
+    }
+
     pub fn display_name(&self) -> String {
         if !self.display_name.is_empty() {
             self.display_name.clone()
@@ -226,6 +834,183 @@ pub struct Workspace {
     pub active: bool,
     #[serde(default)]
     pub last_channel_id: Option<String>,
+    /// Workspace icon from `team.info`, kept for terminals that can draw
+    /// images; the topbar shows initials either way.
+    #[serde(default)]
+    pub icon_url: Option<String>,
+    /// Threads the user has posted in, newest activity first.
+    #[serde(default)]
+    pub threads: Vec<SubscribedThread>,
+    /// Channels muted with `/mute`: no unread count in the sidebar, and
+    /// skipped by mark-all-read.
+    #[serde(default)]
+    pub muted_channels: Vec<String>,
+    /// Reactions the user has added, here or from another client, which
+    /// order the reaction picker.
+    #[serde(default)]
+    pub recent_reactions: Vec<ReactionUse>,
+}
+
+/// Most threads a workspace follows; the least recently active go first.
+pub const MAX_SUBSCRIBED_THREADS: usize = 200;
+
+/// Most reactions a workspace remembers; the lowest scored go first.
+pub const MAX_RECENT_REACTIONS: usize = 30;
+
+/// How often and how lately the user added one reaction.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReactionUse {
+    pub name: String,
+    pub uses: u32,
+    /// Unix seconds.
+    pub last_used: i64,
+}
+
+impl ReactionUse {
+    /// Uses, halved for each week since the last one, so a reaction used
+    /// often long ago gives way to one used a few times lately.
+    pub fn score(&self, now: i64) -> f64 {
+        let weeks = (now - self.last_used).max(0) as f64 / (7 * 24 * 3600) as f64;
+        f64::from(self.uses) * 0.5f64.powf(weeks)
+    }
+}
+
+/// A thread followed because the user replied in it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SubscribedThread {
+    pub channel_id: String,
+    pub thread_ts: String,
+    /// Newest reply seen; orders the Threads view.
+    pub last_reply_ts: String,
+    #[serde(default)]
+    pub last_user: Option<String>,
+    #[serde(default)]
+    pub last_text: String,
+    /// Replies from others since the thread was last opened.
+    #[serde(default)]
+    pub unread: u32,
+}
+
+impl SubscribedThread {
+    /// When the thread was started, for loading history around its parent.
+    pub fn parent_time(&self) -> Option<DateTime<Utc>> {
+        let secs = self.thread_ts.split('.').next()?.parse::<i64>().ok()?;
+        DateTime::from_timestamp(secs, 0)
+    }
+}
+
+impl Workspace {
+    pub fn is_muted(&self, channel_id: &str) -> bool {
+        self.muted_channels.iter().any(|id| id == channel_id)
+    }
+
+    /// Mutes or unmutes `channel_id`; returns whether it is muted now.
+    pub fn toggle_mute(&mut self, channel_id: &str) -> bool {
+        match self.muted_channels.iter().position(|id| id == channel_id) {
+            Some(pos) => {
+                self.muted_channels.remove(pos);
+                false
+            }
+            None => {
+                self.muted_channels.push(channel_id.to_string());
+                true
+            }
+        }
+    }
+
+    /// Follows a thread after the user replied in it; their own reply
+    /// leaves nothing unread.
+    pub fn subscribe_thread(&mut self, channel_id: &str, thread_ts: &str, ts: &str, text: &str) {
+        let pos = self
+            .threads
+            .iter()
+            .position(|t| t.channel_id == channel_id && t.thread_ts == thread_ts);
+        let mut thread = match pos {
+            Some(pos) => self.threads.remove(pos),
+            None => SubscribedThread {
+                channel_id: channel_id.to_string(),
+                thread_ts: thread_ts.to_string(),
+                ..Default::default()
+            },
+        };
+        if ts >= thread.last_reply_ts.as_str() {
+            thread.last_reply_ts = ts.to_string();
+            thread.last_user = self.user_id.clone();
+            thread.last_text = text.to_string();
+        }
+        thread.unread = 0;
+        self.threads.insert(0, thread);
+        self.threads.truncate(MAX_SUBSCRIBED_THREADS);
+    }
+
+    /// Records someone else's reply in a followed thread, counting it as
+    /// unread when asked. Returns whether the thread is followed.
+    pub fn note_thread_reply(&mut self, channel_id: &str, message: &Message, unread: bool) -> bool {
+        let Some(thread_ts) = message.thread_ts.as_deref() else {
+            return false;
+        };
+        let Some(pos) = self
+            .threads
+            .iter()
+            .position(|t| t.channel_id == channel_id && t.thread_ts == thread_ts)
+        else {
+            return false;
+        };
+        let mut thread = self.threads.remove(pos);
+        // Replies come off the socket as they are posted: this is the newest.
+        thread.last_reply_ts = message.ts.clone();
+        thread.last_user = Some(message.user_id.clone());
+        thread.last_text = message.text.clone();
+        if unread {
+            thread.unread += 1;
+        }
+        self.threads.insert(0, thread);
+        true
+    }
+
+    /// Counts a reaction the user added at `now` (Unix seconds).
+    pub fn note_reaction(&mut self, name: &str, now: i64) {
+        match self.recent_reactions.iter_mut().find(|r| r.name == name) {
+            Some(used) => {
+                used.uses += 1;
+                used.last_used = now;
+            }
+            None => self.recent_reactions.push(ReactionUse {
+                name: name.to_string(),
+                uses: 1,
+                last_used: now,
+            }),
+        }
+        if self.recent_reactions.len() > MAX_RECENT_REACTIONS {
+            let ranked = self.top_reactions(now);
+            let keep: Vec<String> = ranked[..MAX_RECENT_REACTIONS]
+                .iter()
+                .map(|name| name.to_string())
+                .collect();
+            self.recent_reactions
+                .retain(|used| keep.contains(&used.name));
+        }
+    }
+
+    /// Reaction names by score at `now`, the most recent first on a tie.
+    pub fn top_reactions(&self, now: i64) -> Vec<&str> {
+        let mut ranked: Vec<&ReactionUse> = self.recent_reactions.iter().collect();
+        ranked.sort_by(|a, b| {
+            b.score(now)
+                .total_cmp(&a.score(now))
+                .then(b.last_used.cmp(&a.last_used))
+        });
+        ranked.into_iter().map(|used| used.name.as_str()).collect()
+    }
+
+    /// Clears the unread replies of a followed thread. Returns whether any
+    /// were cleared.
+    pub fn mark_thread_read(&mut self, channel_id: &str, thread_ts: &str) -> bool {
+        self.threads
+            .iter_mut()
+            .find(|t| t.channel_id == channel_id && t.thread_ts == thread_ts)
+            .is_some_and(|t| std::mem::take(&mut t.unread) > 0)
+    }
 }
 
 #[derive(Debug)]
@@ -248,3 +1033,689 @@ impl WorkspaceState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        set_keep_raw, CallInfo, Message, Reminder, SavedItem, User, Workspace,
+        MAX_RECENT_REACTIONS, MAX_SUBSCRIBED_THREADS,
+    };
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    macro_rules! fixture {
+        ($name:literal) => {
+            (
+                $name,
+                include_str!(concat!("../tests/fixtures/messages/", $name, ".json")),
+            )
+        };
+    }
+
+    /// Payloads captured from `conversations.history` and
+    /// `conversations.replies`, trimmed of ids that do not matter here.
+    const FIXTURES: [(&str, &str); 8] = [
+        fixture!("plain"),
+        fixture!("edited"),
+        fixture!("thread_parent"),
+        fixture!("thread_parent_bare"),
+        fixture!("thread_reply"),
+        fixture!("reactions_and_files"),
+        fixture!("bot_with_blocks"),
+        fixture!("missing_text"),
+    ];
+
+    struct Expected {
+        user_id: &'static str,
+        username: &'static str,
+        text: &'static str,
+        thread_ts: Option<&'static str>,
+        reply_count: Option<u32>,
+        latest_reply: Option<&'static str>,
+        reply_users: &'static [&'static str],
+        last_read: Option<&'static str>,
+        is_edited: bool,
+        is_bot: bool,
+        reactions: &'static [(&'static str, u32)],
+        /// Id, name and size of each file.
+        files: &'static [(&'static str, &'static str, u32)],
+        rendered_blocks: &'static [&'static str],
+    }
+
+    const PLAIN: Expected = Expected {
+        user_id: "U0ALICE",
+        username: "alice",
+        text: "",
+        thread_ts: None,
+        reply_count: None,
+        latest_reply: None,
+        reply_users: &[],
+        last_read: None,
+        is_edited: false,
+        is_bot: false,
+        reactions: &[],
+        files: &[],
+        rendered_blocks: &[],
+    };
+
+    fn fixture_users() -> HashMap<String, User> {
+        ["alice", "bob", "carol"]
+            .into_iter()
+            .map(|name| {
+                let id = format!("U0{}", name.to_uppercase());
+                let user = User::from_slack_api(&json!({"id": id, "name": name})).unwrap();
+                (id, user)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fixtures_parse_as_expected() {
+        let cases = [
+            (
+                "plain",
+                Expected {
+                    text: "Morning! Standup in 5",
+                    ..PLAIN
+                },
+            ),
+            (
+                "edited",
+                Expected {
+                    user_id: "U0BOB",
+                    username: "bob",
+                    text: "The deploy is *done* now",
+                    is_edited: true,
+                    ..PLAIN
+                },
+            ),
+            (
+                "thread_parent",
+                Expected {
+                    text: "Who owns the billing alerts?",
+                    thread_ts: Some("1730000200.000300"),
+                    reply_count: Some(3),
+                    latest_reply: Some("1730000500.000600"),
+                    reply_users: &["U0BOB", "U0CAROL"],
+                    last_read: Some("1730000400.000500"),
+                    ..PLAIN
+                },
+            ),
+            (
+                "thread_parent_bare",
+                Expected {
+                    user_id: "U0CAROL",
+                    username: "carol",
+                    text: "Anyone around for a review?",
+                    thread_ts: Some("1730000250.000350"),
+                    reply_count: Some(1),
+                    ..PLAIN
+                },
+            ),
+            (
+                "thread_reply",
+                Expected {
+                    user_id: "U0BOB",
+                    username: "bob",
+                    text: "Payments team, ask in #payments",
+                    thread_ts: Some("1730000200.000300"),
+                    ..PLAIN
+                },
+            ),
+            (
+                "reactions_and_files",
+                Expected {
+                    user_id: "U0CAROL",
+                    username: "carol",
+                    text: "Latency graph from last night",
+                    // The nameless reaction is skipped, not the message.
+                    reactions: &[("eyes", 2), ("chart_with_upwards_trend", 1)],
+                    files: &[
+                        ("F0GRAPH", "latency.png", 48213),
+                        ("F0GONE", "F0GONE", 0),
+                        ("F0LIMIT", "old-notes.txt", 0),
+                    ],
+                    ..PLAIN
+                },
+            ),
+            (
+                "bot_with_blocks",
+                Expected {
+                    user_id: "B0CI",
+                    username: "ci-bot",
+                    is_bot: true,
+                    rendered_blocks: &[
+                        "*Build failed*",
+                        "`main` at <https://ci.example.com/b/981|#981>",
+                        "3 tests failed",
+                    ],
+                    ..PLAIN
+                },
+            ),
+            (
+                "missing_text",
+                Expected {
+                    files: &[("F0DOC", "plan.pdf", 1024)],
+                    ..PLAIN
+                },
+            ),
+        ];
+        assert_eq!(cases.len(), FIXTURES.len());
+
+        let users = fixture_users();
+        for (name, expected) in cases {
+            let (_, payload) = FIXTURES
+                .iter()
+                .find(|(fixture, _)| *fixture == name)
+                .unwrap();
+            let payload: serde_json::Value = serde_json::from_str(payload).unwrap();
+            let message = Message::from_slack_api(&payload, &users)
+                .unwrap_or_else(|| panic!("{} was dropped", name));
+            assert_eq!(message.ts, payload["ts"].as_str().unwrap(), "{}", name);
+            assert_eq!(message.user_id, expected.user_id, "{}", name);
+            assert_eq!(message.username, expected.username, "{}", name);
+            assert_eq!(message.text, expected.text, "{}", name);
+            assert_eq!(message.thread_ts.as_deref(), expected.thread_ts, "{}", name);
+            assert_eq!(message.reply_count, expected.reply_count, "{}", name);
+            assert_eq!(
+                message.latest_reply.as_deref(),
+                expected.latest_reply,
+                "{}",
+                name
+            );
+            assert_eq!(message.reply_users, expected.reply_users, "{}", name);
+            assert_eq!(message.last_read.as_deref(), expected.last_read, "{}", name);
+            assert_eq!(message.is_edited, expected.is_edited, "{}", name);
+            assert_eq!(message.is_bot, expected.is_bot, "{}", name);
+            assert!(!message.is_deleted && !message.is_system, "{}", name);
+            let reactions: Vec<_> = message
+                .reactions
+                .iter()
+                .map(|r| (r.name.as_str(), r.count))
+                .collect();
+            assert_eq!(reactions, expected.reactions, "{}", name);
+            let files: Vec<_> = message
+                .files
+                .iter()
+                .map(|f| (f.id.as_str(), f.name.as_str(), f.size))
+                .collect();
+            assert_eq!(files, expected.files, "{}", name);
+            assert_eq!(
+                message.rendered_blocks, expected.rendered_blocks,
+                "{}",
+                name
+            );
+        }
+    }
+
+    /// A small xorshift generator so the fuzz test needs no extra crate and
+    /// fails the same way every run.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+            &items[self.next() as usize % items.len()]
+        }
+    }
+
+    /// Keys and strings the parser looks at, so random payloads reach past
+    /// the first `?`.
+    const KEYS: &[&str] = &[
+        "ts",
+        "user",
+        "bot_id",
+        "app_id",
+        "subtype",
+        "text",
+        "thread_ts",
+        "reactions",
+        "files",
+        "name",
+        "count",
+        "users",
+        "id",
+        "size",
+        "title",
+        "edited",
+        "deleted_at",
+        "is_deleted",
+        "reply_count",
+        "latest_reply",
+        "reply_users",
+        "last_read",
+        "blocks",
+        "attachments",
+        "type",
+        "elements",
+        "fields",
+        "fallback",
+        "pretext",
+        "footer",
+        "username",
+        "bot_profile",
+        "topic",
+        "purpose",
+        "call",
+        "room",
+        "value",
+        "alt_text",
+    ];
+    const STRINGS: &[&str] = &[
+        "",
+        " ",
+        "1730000000.000100",
+        "1730000000",
+        ".",
+        "-1.5",
+        "99999999999999999999.1",
+        "-9223372036854775808.0",
+        "bot_message",
+        "channel_join",
+        "channel_topic",
+        "channel_name",
+        "huddle_thread",
+        "file_share",
+        "header",
+        "section",
+        "context",
+        "divider",
+        "actions",
+        "rich_text",
+        "image",
+        "mrkdwn",
+        "plain_text",
+        "button",
+        "video",
+        "call",
+        "U0ALICE",
+        "é🦀\u{0}",
+        "<https://x|y>",
+    ];
+
+    fn random_value(rng: &mut Rng, depth: u32) -> serde_json::Value {
+        let kind = if depth == 0 {
+            rng.next() % 5
+        } else {
+            rng.next() % 7
+        };
+        match kind {
+            0 => serde_json::Value::Null,
+            1 => json!(rng.next().is_multiple_of(2)),
+            2 => rng
+                .pick(&[
+                    json!(0),
+                    json!(-1),
+                    json!(u64::MAX),
+                    json!(i64::MIN),
+                    json!(1.5e300),
+                ])
+                .clone(),
+            3 | 4 => json!(rng.pick(STRINGS)),
+            5 => (0..rng.next() % 4)
+                .map(|_| random_value(rng, depth - 1))
+                .collect(),
+            _ => random_object(rng, depth - 1),
+        }
+    }
+
+    fn random_object(rng: &mut Rng, depth: u32) -> serde_json::Value {
+        let mut object = serde_json::Map::new();
+        for _ in 0..rng.next() % 8 {
+            object.insert(rng.pick(KEYS).to_string(), random_value(rng, depth));
+        }
+        serde_json::Value::Object(object)
+    }
+
+    #[test]
+    fn arbitrary_payloads_never_panic() {
+        let users = fixture_users();
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        for _ in 0..5_000 {
+            let _ = Message::from_slack_api(&random_object(&mut rng, 4), &users);
+        }
+        // Valid payloads with one field swapped for junk.
+        for (_, payload) in FIXTURES {
+            let payload: serde_json::Value = serde_json::from_str(payload).unwrap();
+            for _ in 0..500 {
+                let mut mutated = payload.clone();
+                let junk = random_value(&mut rng, 3);
+                mutated[*rng.pick(KEYS)] = junk;
+                let _ = Message::from_slack_api(&mutated, &users);
+            }
+        }
+    }
+
+    #[test]
+    fn bot_posts_without_a_user_are_kept() {
+        let webhook = json!({
+            "ts": "1730000000.000100",
+            "subtype": "bot_message",
+            "bot_id": "B0DEPLOY",
+            "username": "deploy-bot",
+            "text": "",
+            "attachments": [{"fallback": "Deploy finished", "title": "api v2.3 is live"}],
+        });
+        let message = Message::from_slack_api(&webhook, &HashMap::new()).unwrap();
+        assert!(message.is_bot);
+        assert_eq!(message.user_id, "B0DEPLOY");
+        assert_eq!(message.username, "deploy-bot");
+        assert_eq!(message.text, "api v2.3 is live");
+
+        let workflow =
+            json!({"ts": "1730000001.000100", "app_id": "A0FLOW", "text": "Standup time"});
+        let message = Message::from_slack_api(&workflow, &HashMap::new()).unwrap();
+        assert_eq!(message.user_id, "A0FLOW");
+        assert_eq!(message.text, "Standup time");
+    }
+
+    #[test]
+    fn live_replies_update_the_thread_summary() {
+        let (_, payload) = FIXTURES[3];
+        let payload: serde_json::Value = serde_json::from_str(payload).unwrap();
+        let mut parent = Message::from_slack_api(&payload, &fixture_users()).unwrap();
+        assert!(parent.reply_users.is_empty() && parent.latest_reply.is_none());
+
+        parent.note_reply("U0BOB", "1730000300.000400");
+        parent.note_reply("U0ALICE", "1730000400.000500");
+        parent.note_reply("U0BOB", "1730000350.000450");
+        assert_eq!(parent.reply_count, Some(4));
+        assert_eq!(parent.reply_users, ["U0BOB", "U0ALICE"]);
+        assert_eq!(parent.latest_reply.as_deref(), Some("1730000400.000500"));
+    }
+
+    #[test]
+    fn github_blocks_render_as_lines() {
+        let payload = json!({
+            "ts": "1730000000.000100",
+            "bot_id": "B0GITHUB",
+            "text": "",
+            "blocks": [
+                {"type": "header", "text": {"type": "plain_text", "text": "Pull request opened"}},
+                {"type": "section", "text": {"type": "mrkdwn", "text": "<https://github.com/acme/api/pull/42|#42 Fix retries>"},
+                 "fields": [
+                    {"type": "mrkdwn", "text": "*Author*\nalice"},
+                    {"type": "mrkdwn", "text": "*Reviewers*\nbob"}
+                 ]},
+                {"type": "divider"},
+                {"type": "context", "elements": [
+                    {"type": "image", "image_url": "https://github.com/a.png", "alt_text": "acme"},
+                    {"type": "mrkdwn", "text": "acme/api"},
+                    {"type": "plain_text", "text": "2 files changed"}
+                ]},
+                {"type": "actions", "elements": [
+                    {"type": "button", "text": {"type": "plain_text", "text": "Review"}}
+                ]},
+                {"type": "video", "title": {"type": "plain_text", "text": "Demo recording"}}
+            ]
+        });
+        let message = Message::from_slack_api(&payload, &HashMap::new()).unwrap();
+        assert_eq!(
+            message.rendered_blocks,
+            [
+                "*Pull request opened*",
+                "<https://github.com/acme/api/pull/42|#42 Fix retries>",
+                "*Author*\nalice",
+                "*Reviewers*\nbob",
+                "───",
+                "acme/api · 2 files changed",
+                "[Review]",
+                "Demo recording",
+            ]
+        );
+    }
+
+    #[test]
+    fn pagerduty_attachments_render_fields_and_footer() {
+        let payload = json!({
+            "ts": "1730000000.000100",
+            "bot_id": "B0PAGER",
+            "text": "",
+            "attachments": [{
+                "color": "#ff0000",
+                "fallback": "Triggered: API latency high",
+                "title": "Triggered: API latency high",
+                "text": "p99 above 2s for 5 minutes",
+                "fields": [
+                    {"title": "Service", "value": "api-gateway", "short": true},
+                    {"title": "Urgency", "value": "high", "short": true}
+                ],
+                "footer": "PagerDuty"
+            }]
+        });
+        let message = Message::from_slack_api(&payload, &HashMap::new()).unwrap();
+        assert_eq!(message.text, "Triggered: API latency high");
+        assert_eq!(
+            message.rendered_blocks,
+            [
+                "*Triggered: API latency high*",
+                "p99 above 2s for 5 minutes",
+                "Service: api-gateway",
+                "Urgency: high",
+                "PagerDuty",
+            ]
+        );
+    }
+
+    #[test]
+    fn deleted_users_without_a_profile_are_kept() {
+        let deleted = json!({"id": "U0GONE", "name": "former.contractor", "deleted": true});
+        let user = User::from_slack_api(&deleted).unwrap();
+        assert_eq!(user.display_name(), "former.contractor");
+        assert_eq!(user.real_name, "");
+
+        let nameless = json!({"id": "U0EXT", "profile": {"real_name": "Ext Person"}});
+        let user = User::from_slack_api(&nameless).unwrap();
+        assert_eq!(user.name, "U0EXT");
+        assert_eq!(user.display_name(), "Ext Person");
+
+        assert!(User::from_slack_api(&json!({"name": "no-id"})).is_none());
+    }
+
+    #[test]
+    fn rich_text_blocks_are_not_repeated() {
+        let payload = json!({
+            "ts": "1730000000.000100",
+            "user": "U1",
+            "text": "hello",
+            "blocks": [{"type": "rich_text", "elements": [{"type": "rich_text_section",
+                "elements": [{"type": "text", "text": "hello"}]}]}]
+        });
+        let message = Message::from_slack_api(&payload, &HashMap::new()).unwrap();
+        assert!(message.rendered_blocks.is_empty());
+    }
+
+    #[test]
+    fn saved_items_keep_only_messages() {
+        let item = json!({"type": "message", "channel": "C1",
+            "message": {"ts": "1730000000.000100", "user": "U1", "text": "todo"}});
+        let saved = SavedItem::from_slack_api(&item, &HashMap::new()).unwrap();
+        assert_eq!(saved.channel_id, "C1");
+        assert_eq!(saved.message.text, "todo");
+
+        let file = json!({"type": "file", "file": {"id": "F1"}});
+        assert!(SavedItem::from_slack_api(&file, &HashMap::new()).is_none());
+    }
+
+    #[test]
+    fn join_and_topic_notices_become_system_lines() {
+        let users = HashMap::from([(
+            "U1".to_string(),
+            User::from_slack_api(&json!({"id": "U1", "name": "alice"})).unwrap(),
+        )]);
+        let join = json!({"ts": "1730000000.000100", "user": "U1", "subtype": "channel_join",
+            "text": "<@U1> has joined the channel"});
+        let message = Message::from_slack_api(&join, &users).unwrap();
+        assert!(message.is_system);
+        assert_eq!(message.system_line(), "→ alice joined");
+
+        let topic = json!({"ts": "1730000000.000200", "user": "U1", "subtype": "channel_topic",
+            "topic": "Release week", "text": "set the channel topic: Release week"});
+        let message = Message::from_slack_api(&topic, &users).unwrap();
+        assert_eq!(
+            message.system_line(),
+            "✎ alice set the topic to “Release week”"
+        );
+
+        let cleared = json!({"ts": "1730000000.000300", "user": "U1",
+            "subtype": "channel_purpose", "purpose": ""});
+        let message = Message::from_slack_api(&cleared, &users).unwrap();
+        assert_eq!(message.text, "cleared the purpose");
+
+        let bot = json!({"ts": "1730000000.000400", "bot_id": "B1", "subtype": "bot_message",
+            "text": "deploy done"});
+        let message = Message::from_slack_api(&bot, &users).unwrap();
+        assert!(!message.is_system);
+        assert_eq!(message.subtype.as_deref(), Some("bot_message"));
+    }
+
+    #[test]
+    fn huddles_and_calls_become_call_lines() {
+        let huddle = json!({"ts": "1730000000.000100", "user": "U1", "text": "",
+            "subtype": "huddle_thread",
+            "room": {"id": "R1", "participants": ["U1", "U2", "U3"], "has_ended": false}});
+        let message = Message::from_slack_api(&huddle, &HashMap::new()).unwrap();
+        let call = message.call.unwrap();
+        assert_eq!(
+            call.summary("alice"),
+            "📞 Huddle started by @alice — 3 participants"
+        );
+
+        // Ended: `participants` is empty, the history still counts.
+        let ended = json!({"subtype": "huddle_thread", "room": {"participants": [],
+            "participant_history": ["U1", "U2"], "date_end": 1730000600}});
+        let call = CallInfo::from_slack_api(&ended).unwrap();
+        assert!(call.ended);
+        assert_eq!(
+            call.summary("alice"),
+            "📞 Huddle ended (started by @alice) — 2 participants"
+        );
+
+        let zoom = json!({"blocks": [{"type": "call", "call_id": "R2",
+            "call": {"v1": {"name": "Zoom meeting", "active_participants": [{"slack_id": "U1"}],
+            "date_end": 0}}}]});
+        let call = CallInfo::from_slack_api(&zoom).unwrap();
+        assert_eq!(
+            call.summary("bob"),
+            "📞 Zoom meeting started by @bob — 1 participant"
+        );
+
+        let plain = json!({"ts": "1730000000.000200", "user": "U1", "text": "hi"});
+        assert!(Message::from_slack_api(&plain, &HashMap::new())
+            .unwrap()
+            .call
+            .is_none());
+    }
+
+    #[test]
+    fn reminders_parse_time_and_completion() {
+        let pending = json!({"id": "Rm1", "text": "follow up", "time": 1730003600,
+            "recurring": false, "complete_ts": 0});
+        let reminder = Reminder::from_slack_api(&pending).unwrap();
+        assert_eq!(reminder.text, "follow up");
+        assert_eq!(reminder.time.unwrap().timestamp(), 1730003600);
+        assert!(!reminder.complete);
+
+        let done = json!({"id": "Rm2", "text": "standup", "recurring": true,
+            "complete_ts": 1730000000});
+        let reminder = Reminder::from_slack_api(&done).unwrap();
+        assert!(reminder.recurring && reminder.complete && reminder.time.is_none());
+        assert!(Reminder::from_slack_api(&json!({"text": "no id"})).is_none());
+    }
+
+    #[test]
+    fn raw_payload_is_kept_only_when_asked() {
+        let payload = json!({
+            "ts": "1730000000.000100",
+            "user": "U1",
+            "text": "deploy done",
+            "metadata": {"event_type": "deploy", "event_payload": {"id": 7}}
+        });
+        let message = Message::from_slack_api(&payload, &HashMap::new()).unwrap();
+        assert!(message.raw.is_none());
+
+        set_keep_raw(true);
+        let message = Message::from_slack_api(&payload, &HashMap::new()).unwrap();
+        set_keep_raw(false);
+        let raw: serde_json::Value = serde_json::from_str(message.raw.as_deref().unwrap()).unwrap();
+        assert_eq!(raw, payload);
+        // The cache never sees it.
+        assert!(!serde_json::to_string(&message)
+            .unwrap()
+            .contains("metadata"));
+    }
+
+    #[test]
+    fn recent_reactions_favour_frequent_and_recent_use() {
+        let mut ws: Workspace = serde_json::from_value(json!({
+            "team_id": "T1", "team_name": "team", "xoxp_token": "xoxp",
+            "xapp_token": "xapp", "active": true
+        }))
+        .unwrap();
+        assert!(ws.recent_reactions.is_empty());
+
+        const WEEK: i64 = 7 * 24 * 3600;
+        let now = 1_730_000_000;
+        // Four old uses of :tada: are worth less than two this week.
+        for _ in 0..4 {
+            ws.note_reaction("tada", now - 4 * WEEK);
+        }
+        ws.note_reaction("eyes", now - 60);
+        ws.note_reaction("eyes", now - 60);
+        ws.note_reaction("+1", now);
+        assert_eq!(ws.top_reactions(now), ["eyes", "+1", "tada"]);
+        // Same score: the latest wins.
+        ws.note_reaction("+1", now);
+        assert_eq!(ws.top_reactions(now)[..2], ["+1", "eyes"]);
+
+        for i in 0..MAX_RECENT_REACTIONS {
+            ws.note_reaction(&format!("custom_{i}"), now);
+        }
+        assert_eq!(ws.recent_reactions.len(), MAX_RECENT_REACTIONS);
+        assert!(!ws.recent_reactions.iter().any(|used| used.name == "tada"));
+    }
+
+    #[test]
+    fn followed_threads_keep_the_most_recently_active() {
+        // Sessions saved before threads were followed still load.
+        let mut ws: Workspace = serde_json::from_value(json!({
+            "team_id": "T1", "team_name": "team", "xoxp_token": "xoxp",
+            "xapp_token": "xapp", "user_id": "U_ME", "active": true
+        }))
+        .unwrap();
+        assert!(ws.threads.is_empty());
+
+        for i in 0..=MAX_SUBSCRIBED_THREADS {
+            let ts = format!("17300{:05}.000100", i);
+            ws.subscribe_thread("C1", &ts, &ts, "reply");
+        }
+        assert_eq!(ws.threads.len(), MAX_SUBSCRIBED_THREADS);
+        assert!(!ws
+            .threads
+            .iter()
+            .any(|t| t.thread_ts == "1730000000.000100"));
+
+        let oldest = ws.threads.last().unwrap().thread_ts.clone();
+        let mut reply = Message::from_slack_api(
+            &json!({"ts": "1740000000.000100", "user": "U2", "text": "ping",
+                "thread_ts": oldest}),
+            &HashMap::new(),
+        )
+        .unwrap();
+        assert!(ws.note_thread_reply("C1", &reply, true));
+        assert_eq!(ws.threads[0].thread_ts, oldest);
+        assert_eq!(ws.threads[0].unread, 1);
+        assert_eq!(ws.threads[0].last_user.as_deref(), Some("U2"));
+        assert!(ws.mark_thread_read("C1", &oldest));
+        assert!(!ws.mark_thread_read("C1", &oldest));
+
+        reply.thread_ts = Some("1600000000.000100".to_string());
+        assert!(!ws.note_thread_reply("C1", &reply, true));
+    }
+}
@@ -0,0 +1,245 @@
+//! One user list per workspace, shared by the Web API client, the socket
+//! and the TUI so `users.list` is fetched once and every name agrees.
+
+use crate::api::SlackApi;
+use crate::types::User;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{OnceLock, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::{watch, Mutex};
+use tracing::debug;
+
+/// How long a fetched user list is used before `users.list` is called again.
+pub const USER_TTL: Duration = Duration::from_secs(600);
+/// Wait after a failed fetch, so unknown ids don't retry on every message.
+const RETRY_AFTER: Duration = Duration::from_secs(30);
+
+#[derive(Default)]
+struct Directory {
+    users: HashMap<String, User>,
+    fetched_at: Option<Instant>,
+    failed_at: Option<Instant>,
+}
+
+impl Directory {
+    fn is_fresh(&self) -> bool {
+        self.fetched_at.is_some_and(|at| at.elapsed() < USER_TTL)
+            || self.failed_at.is_some_and(|at| at.elapsed() < RETRY_AFTER)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct UsersCache {
+    team_id: String,
+    saved_at: DateTime<Utc>,
+    users: Vec<User>,
+}
+
+/// A workspace's users by id. Get the shared one for a token with
+/// [`SlackApi::user_directory`].
+pub struct UserDirectory {
+    directory: RwLock<Directory>,
+    /// Held while `users.list` runs, so concurrent misses share one fetch.
+    refreshing: Mutex<()>,
+    /// Set by [`UserDirectory::persist_as`]; the list is then kept on disk.
+    team_id: OnceLock<String>,
+    /// Bumped whenever the list changes.
+    changes: watch::Sender<u64>,
+}
+
+impl Default for UserDirectory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UserDirectory {
+    pub fn new() -> Self {
+        Self {
+            directory: RwLock::default(),
+            refreshing: Mutex::new(()),
+            team_id: OnceLock::new(),
+            changes: watch::channel(0).0,
+        }
+    }
+
+    /// Notified each time the list changes, e.g. to redraw names.
+    pub fn subscribe(&self) -> watch::Receiver<u64> {
+        self.changes.subscribe()
+    }
+
+    /// The users known now, without fetching.
+    pub fn snapshot(&self) -> HashMap<String, User> {
+        self.read(|dir| dir.users.clone())
+    }
+
+    /// Keeps the list in the cache directory under `team_id`, starting from
+    /// the copy saved last time. That copy resolves names at once but still
+    /// counts as stale, so the next lookup refreshes it.
+    pub fn persist_as(&self, team_id: &str) {
+        if self.team_id.set(team_id.to_string()).is_err() {
+            return;
+        }
+        match load_users(team_id) {
+            Ok(Some(users)) => {
+                let loaded = self.write(|dir| {
+                    if !dir.users.is_empty() {
+                        return false;
+                    }
+                    dir.users = users.into_iter().map(|u| (u.id.clone(), u)).collect();
+                    true
+                });
+                if loaded {
+                    self.changes.send_modify(|generation| *generation += 1);
+                }
+            }
+            Ok(None) => {}
+            Err(e) => debug!("Ignoring user cache for {}: {:#}", team_id, e),
+        }
+    }
+
+    /// Every user, fetching the list first when it is stale. A failed fetch
+    /// keeps the last list.
+    pub async fn users(&self, api: &SlackApi, token: &str) -> HashMap<String, User> {
+        self.refresh_if_stale(api, token).await;
+        self.snapshot()
+    }
+
+    /// The display name for `user_id`. An unknown id refreshes a stale list
+    /// once; it does not force a fetch while the list is fresh.
+    pub async fn display_name(&self, api: &SlackApi, token: &str, user_id: &str) -> Option<String> {
+        let lookup = |dir: &Directory| dir.users.get(user_id).map(User::display_name);
+        if let Some(name) = self.read(lookup) {
+            return Some(name);
+        }
+        self.refresh_if_stale(api, token).await;
+        self.read(lookup)
+    }
+
+    async fn refresh_if_stale(&self, api: &SlackApi, token: &str) {
+        if self.read(Directory::is_fresh) {
+            return;
+        }
+        let _guard = self.refreshing.lock().await;
+        // Another caller may have refreshed while this one waited.
+        if self.read(Directory::is_fresh) {
+            return;
+        }
+        match api.list_users(token).await {
+            Ok(users) => {
+                if let Some(team_id) = self.team_id.get() {
+                    if let Err(e) = save_users(team_id, &users) {
+                        debug!("Failed to save user cache for {}: {:#}", team_id, e);
+                    }
+                }
+                self.write(|dir| {
+                    dir.users = users.into_iter().map(|u| (u.id.clone(), u)).collect();
+                    dir.fetched_at = Some(Instant::now());
+                    dir.failed_at = None;
+                });
+                self.changes.send_modify(|generation| *generation += 1);
+            }
+            Err(e) => {
+                debug!("Failed to refresh users: {}", e);
+                self.write(|dir| dir.failed_at = Some(Instant::now()));
+            }
+        }
+    }
+
+    fn read<T>(&self, f: impl FnOnce(&Directory) -> T) -> T {
+        let dir = self.directory.read().unwrap_or_else(|e| e.into_inner());
+        f(&dir)
+    }
+
+    fn write<T>(&self, f: impl FnOnce(&mut Directory) -> T) -> T {
+        let mut dir = self.directory.write().unwrap_or_else(|e| e.into_inner());
+        f(&mut dir)
+    }
+}
+
+fn users_cache_path(team_id: &str) -> Result<PathBuf> {
+    let dir = crate::profile::cache_dir().context("unable to resolve slack-zc cache directory")?;
+    Ok(dir.join(format!("{team_id}.users.json")))
+}
+
+fn load_users(team_id: &str) -> Result<Option<Vec<User>>> {
+    let path = users_cache_path(team_id)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("failed to read user cache {}", path.display()))?;
+    let cached: UsersCache = serde_json::from_str(&content)
+        .with_context(|| format!("failed to parse user cache {}", path.display()))?;
+    Ok((cached.team_id == team_id).then_some(cached.users))
+}
+
+fn save_users(team_id: &str, users: &[User]) -> Result<()> {
+    let path = users_cache_path(team_id)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).with_context(|| {
+            format!("failed to create user cache directory {}", parent.display())
+        })?;
+    }
+    let payload = UsersCache {
+        team_id: team_id.to_string(),
+        saved_at: Utc::now(),
+        users: users.to_vec(),
+    };
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, serde_json::to_vec(&payload)?)
+        .with_context(|| format!("failed to write user cache {}", tmp_path.display()))?;
+    fs::rename(&tmp_path, &path)
+        .with_context(|| format!("failed to replace user cache {}", path.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn one_fetch_serves_the_api_and_the_socket() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users.list"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "ok": true,
+                "members": [{
+                    "id": "U1",
+                    "name": "alice",
+                    "profile": {"display_name": "Alice"}
+                }],
+                "response_metadata": {"next_cursor": ""}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let api = SlackApi::new().with_base_url(server.uri());
+        let directory = api.user_directory("xoxp-1");
+        let mut changes = directory.subscribe();
+
+        // A clone, as the socket holds, shares the same directory.
+        let socket_api = api.clone();
+        let name = socket_api
+            .user_directory("xoxp-1")
+            .display_name(&socket_api, "xoxp-1", "U1")
+            .await;
+        assert_eq!(name.as_deref(), Some("Alice"));
+        assert!(changes.has_changed().unwrap());
+        changes.mark_unchanged();
+
+        assert!(directory.users(&api, "xoxp-1").await.contains_key("U1"));
+        assert_eq!(directory.display_name(&api, "xoxp-1", "U_GONE").await, None);
+        assert!(!changes.has_changed().unwrap());
+        assert!(api.user_directory("xoxp-2").snapshot().is_empty());
+    }
+}
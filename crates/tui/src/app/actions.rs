@@ -1,5 +1,22 @@
 use super::*;
-use std::time::Instant;
+use crate::keybinds::SlashCommand;
+use crate::ui::sanitize::sanitize_text;
+use anyhow::anyhow;
+use slack_zc_slack::chunk::{split_message, MAX_MESSAGE_CHARS};
+use slack_zc_slack::ApiError;
+use std::time::{Duration, Instant};
+
+/// Past this many characters a post goes through the long-message dialog,
+/// leaving some margin under Slack's limit for entity expansion.
+const LONG_MESSAGE_CHARS: usize = 3900;
+/// Messages loaded on each side of a message opened in context.
+const CONTEXT_BEFORE: u32 = 20;
+const CONTEXT_AFTER: u32 = 20;
+/// How long a message opened in context stays highlighted.
+const CONTEXT_HIGHLIGHT: Duration = Duration::from_secs(3);
+
+/// How often `[presence] keep_active` tells Slack the user is around.
+const PRESENCE_PING_INTERVAL: Duration = Duration::from_secs(5 * 60);
 
 impl App {
     pub(super) fn switch_workspace(&mut self, idx: usize) {
@@ -9,10 +26,21 @@ impl App {
                 idx,
                 self.workspaces[idx].workspace.team_name
             );
-            self.active_workspace = idx;
+            let had_agent = self.agent_enabled();
+            // Loads started for the workspace left behind no longer apply.
+            self.tasks.invalidate();
+            self.active_workspace_id = Some(self.workspaces[idx].workspace.team_id.clone());
+            self.workspace_picker_cursor = idx;
             self.channels = self.workspaces[idx].channels.clone();
-            self.selected_channel = None;
+            self.selected_channel_id = None;
             self.scroll_offset = 0;
+            self.saved_items.clear();
+            self.refresh_saved();
+            // The gateway is shared and keeps running; it is only started
+            // here when the workspace left behind had the agent off.
+            if !had_agent && self.agent_enabled() && self.agent_runner.is_none() {
+                self.start_zeroclaw_auto();
+            }
 
             if let Some(ref mut session) = self.session {
                 if let Some(ws) = self.workspaces.get(idx) {
@@ -27,35 +55,160 @@ impl App {
         }
     }
 
-    pub(super) fn select_channel(&mut self, idx: usize) {
-        self.selected_channel = Some(idx);
+    /// Picks up the stored copy of a workspace (fresh tokens after signing
+    /// in again) and connects it, clearing any issue it had.
+    pub(super) fn reconnect_workspace(&mut self, team_id: &str) {
+        let stored = self
+            .session
+            .as_ref()
+            .and_then(|session| session.workspaces.iter().find(|w| w.team_id == team_id))
+            .cloned();
+        let idx = match self
+            .workspaces
+            .iter()
+            .position(|ws| ws.workspace.team_id == team_id)
+        {
+            Some(idx) => {
+                if let Some(workspace) = stored {
+                    self.workspaces[idx].workspace = workspace;
+                }
+                idx
+            }
+            None => {
+                let Some(workspace) = stored else {
+                    return;
+                };
+                self.workspaces.push(WorkspaceState::new(workspace));
+                self.workspaces.len() - 1
+            }
+        };
+        self.workspace_issues
+            .retain(|issue| issue.team_id != team_id);
+        if self.workspace_issues.is_empty() {
+            self.workspace_issues_view = None;
+        }
+        self.connect_workspace(idx);
+    }
+
+    /// Opens OAuth onboarding with the stored app credentials to replace a
+    /// workspace's rejected token.
+    pub(super) fn reauthenticate_workspace(&mut self, team_id: &str) {
+        let mut onboarding = OnboardingState::new();
+        onboarding.client_id = self.config.slack.client_id.clone();
+        onboarding.client_secret = self.config.slack.client_secret.clone();
+        onboarding.current_screen =
+            if onboarding.client_id.is_empty() || onboarding.client_secret.is_empty() {
+                OnboardingScreen::SlackCredentials
+            } else {
+                OnboardingScreen::OAuthFlow
+            };
+        onboarding.reauth_team = Some(team_id.to_string());
+        self.onboarding = Some(onboarding);
+        self.workspace_issues_view = None;
+    }
+
+    /// Forgets a workspace: stops its socket and drops it from the session.
+    pub(super) fn remove_workspace(&mut self, team_id: &str) {
+        let Some(idx) = self
+            .workspaces
+            .iter()
+            .position(|ws| ws.workspace.team_id == team_id)
+        else {
+            return;
+        };
+        let mut ws = self.workspaces.remove(idx);
+        if let Some(task) = ws.socket_task.take() {
+            task.abort();
+        }
+        tracing::info!("Removing workspace {}", ws.workspace.team_name);
+        self.channels_loading.remove(team_id);
+        self.user_directories.remove(team_id);
+        self.workspace_issues
+            .retain(|issue| issue.team_id != team_id);
+        if self.workspace_issues.is_empty() {
+            self.workspace_issues_view = None;
+        } else if let Some(view) = self.workspace_issues_view.as_mut() {
+            view.selected = view.selected.min(self.workspace_issues.len() - 1);
+        }
+
+        if let Some(ref mut session) = self.session {
+            if session.remove_workspace(team_id) {
+                if let Err(e) = session.save() {
+                    self.report_error("Failed to save workspace removal", e);
+                }
+            }
+        }
+
+        if self.active_workspace_id.as_deref() == Some(team_id) {
+            if self.workspaces.is_empty() {
+                self.active_workspace_id = None;
+                self.channels.clear();
+                self.selected_channel_id = None;
+            } else {
+                self.switch_workspace(0);
+            }
+        } else if let Some(active) = self.active_workspace_index() {
+            self.workspace_picker_cursor = active;
+        }
+    }
+
+    pub(super) fn select_channel(&mut self, channel_id: &str) {
+        // A channel kept only because it was open goes once left.
+        if self
+            .removed_channel
+            .as_deref()
+            .is_some_and(|gone| gone != channel_id)
+        {
+            if let Some(gone) = self.removed_channel.take() {
+                self.drop_channel(&gone);
+            }
+        }
+        self.selected_channel_id = Some(channel_id.to_string());
         self.scroll_offset = 0;
+        self.context_anchor = None;
 
-        if let Some(channel) = self.channels.get(idx) {
+        if let Some(channel) = self.selected_channel() {
             tracing::info!("Selecting channel {} ({})", channel.name, channel.id);
             let channel_id = channel.id.clone();
+            let unread = channel.unread_count > 0;
 
-            if let Some(ref mut session) = self.session {
-                if let Some(ws) = self.workspaces.get_mut(self.active_workspace) {
-                    ws.workspace.last_channel_id = Some(channel_id.clone());
-                    if let Some(active_ws) = session
-                        .workspaces
-                        .iter_mut()
-                        .find(|w| w.team_id == ws.workspace.team_id)
-                    {
-                        active_ws.last_channel_id = Some(channel_id.clone());
-                    }
-                    if let Err(e) = session.save() {
-                        tracing::error!("Failed to save last channel: {}", e);
-                    }
+            if let Some(ws) = self.active_workspace_mut() {
+                ws.workspace.last_channel_id = Some(channel_id.clone());
+            }
+            if let (Some(session), Some(team_id)) =
+                (self.session.as_mut(), self.active_workspace_id.as_deref())
+            {
+                if let Some(active_ws) =
+                    session.workspaces.iter_mut().find(|w| w.team_id == team_id)
+                {
+                    active_ws.last_channel_id = Some(channel_id.clone());
                 }
+                if let Err(e) = session.save() {
+                    tracing::error!("Failed to save last channel: {}", e);
+                }
+            }
+
+            self.note_external_channel(&channel_id);
+            // Asked again on every open: who may post, and how far the
+            // other person in a DM has read, can change in the meantime.
+            self.channel_info_requested.remove(&channel_id);
+            self.fetch_channel_info(&channel_id);
+            if unread {
+                self.mark_channel_read(&channel_id);
+            }
+
+            // History missed during a reconnect is merged into what is
+            // loaded rather than replacing it.
+            if let Some(gap) = self.pending_catch_up.remove(&channel_id) {
+                self.fetch_missed_messages(&channel_id, gap);
+                return;
             }
 
-            let ws = self.workspaces.get(self.active_workspace);
+            let ws = self.active_workspace();
             if let Some(ws) = ws {
                 let token = ws.workspace.xoxp_token.clone();
                 let api = self.slack_api.clone();
-                self.spawn_app_task(async move {
+                self.spawn_view_task(async move {
                     match api.get_history(&token, &channel_id, 50).await {
                         Ok(messages) => AppAsyncEvent::ChannelHistoryLoaded {
                             channel_id,
@@ -73,227 +226,2180 @@ impl App {
         }
     }
 
-    pub(super) fn handle_input_submit(&mut self) -> Result<()> {
-        let text = self.input.buffer.clone();
-        if text.is_empty() {
-            return Ok(());
+    /// Clears the channel's unread badge and moves Slack's read cursor to the
+    /// newest message we have.
+    pub(super) fn mark_channel_read(&mut self, channel_id: &str) {
+        let channel_id = channel_id.to_string();
+        let latest = self
+            .messages
+            .get(&channel_id)
+            .and_then(|messages| messages.iter().rev().find(|m| !m.is_unconfirmed()))
+            .map(|m| m.ts.clone());
+        self.update_channel(&channel_id, |ch| {
+            ch.unread_count = 0;
+            ch.mention_count = 0;
+        });
+        // Without loaded history Slack gets "now", which marks everything read.
+        let ts = latest.unwrap_or_else(|| format!("{}.000000", Utc::now().timestamp()));
+        self.send_read_mark(channel_id, ts);
+    }
+
+    /// Like Slack's "mark unread": the newest message becomes unread again.
+    pub(super) fn mark_channel_unread(&mut self, channel_id: &str) {
+        let channel_id = channel_id.to_string();
+        let Some(messages) = self.messages.get(&channel_id).filter(|m| !m.is_empty()) else {
+            self.report_error(
+                "Cannot mark unread",
+                "open the channel once so its messages are loaded",
+            );
+            return;
+        };
+        let ts = messages
+            .len()
+            .checked_sub(2)
+            .and_then(|idx| messages.get(idx))
+            .map(|m| m.ts.clone())
+            .unwrap_or_else(|| "0".to_string());
+        self.update_channel(&channel_id, |ch| ch.unread_count = ch.unread_count.max(1));
+        self.send_read_mark(channel_id, ts);
+    }
+
+    /// Leaves muted channels as they are.
+    pub(super) fn mark_all_channels_read(&mut self) {
+        let unread: Vec<String> = self
+            .channels
+            .iter()
+            .filter(|ch| ch.unread_count > 0 && !self.is_muted(&ch.id))
+            .map(|ch| ch.id.clone())
+            .collect();
+        for channel_id in unread {
+            self.mark_channel_read(&channel_id);
         }
+    }
 
-        match self.input.mode {
-            InputMode::Normal => {
-                if let Some(channel) = self.get_active_channel_id() {
-                    if let Some(ws) = self.workspaces.get(self.active_workspace) {
-                        let token = ws.workspace.xoxp_token.clone();
-                        let thread_ts = self.active_threads.get(&channel).cloned();
-                        let context = "Failed to send message".to_string();
-                        let api = self.slack_api.clone();
-                        let channel_id_for_event = channel.clone();
-                        self.spawn_app_task(async move {
-                            let result = if let Some(ts) = thread_ts {
-                                api.send_message_to_thread(&token, &channel, &text, &ts)
-                                    .await
-                            } else {
-                                api.send_message(&token, &channel, &text).await
-                            };
-                            AppAsyncEvent::SlackSendResult {
-                                context,
-                                channel_id: Some(channel_id_for_event),
-                                error: result.err().map(|e| App::actionable_error(&e)),
-                            }
-                        });
-                    }
-                }
+    fn send_read_mark(&mut self, channel_id: String, ts: String) {
+        let Some(ws) = self.active_workspace() else {
+            return;
+        };
+        let token = ws.workspace.xoxp_token.clone();
+        let api = self.slack_api.clone();
+        self.spawn_app_task(async move {
+            let error = api
+                .mark_read(&token, &channel_id, &ts)
+                .await
+                .err()
+                .map(|e| App::actionable_error(&e));
+            AppAsyncEvent::ChannelMarked { channel_id, error }
+        });
+    }
+
+    /// Fetches member count, creation details and posting restrictions,
+    /// once until the channel is opened again.
+    fn fetch_channel_info(&mut self, channel_id: &str) {
+        if !self.channel_info_requested.insert(channel_id.to_string()) {
+            return;
+        }
+        let Some(ws) = self.active_workspace() else {
+            return;
+        };
+        let token = ws.workspace.xoxp_token.clone();
+        let channel_id = channel_id.to_string();
+        let api = self.slack_api.clone();
+        self.spawn_app_task(async move {
+            match api.get_channel_info(&token, &channel_id).await {
+                Ok(info) => AppAsyncEvent::ChannelInfoLoaded {
+                    channel_id,
+                    info: Some(info),
+                    error: None,
+                },
+                Err(e) => AppAsyncEvent::ChannelInfoLoaded {
+                    channel_id,
+                    info: None,
+                    error: Some(App::actionable_error(&e)),
+                },
             }
-            InputMode::AgentCommand => {
-                self.handle_agent_command(&text)?;
+        });
+    }
+
+    /// In a DM, asks `conversations.info` again how far the other person
+    /// has read.
+    pub(super) fn refresh_read_receipt(&mut self, channel_id: &str) {
+        if !self.config.ui.read_receipts {
+            return;
+        }
+        if self
+            .channels
+            .iter()
+            .any(|ch| ch.id == channel_id && ch.is_im)
+        {
+            self.channel_info_requested.remove(channel_id);
+            self.fetch_channel_info(channel_id);
+        }
+    }
+
+    /// In a DM, the newest of my messages at or before the other person's
+    /// last read position, which gets the "seen" marker.
+    pub(super) fn seen_message_ts(&self, channel: &Channel) -> Option<String> {
+        if !self.config.ui.read_receipts || !channel.is_im {
+            return None;
+        }
+        let last_read = channel.last_read.as_deref()?;
+        let me = self.current_user_id()?;
+        self.messages
+            .get(&channel.id)?
+            .iter()
+            .rev()
+            .find(|m| {
+                m.user_id == me
+                    && !m.is_deleted
+                    && !m.is_unconfirmed()
+                    && m.ts.as_str() <= last_read
+            })
+            .map(|m| m.ts.clone())
+    }
+
+    /// Tells the open channel we are typing when the input asks for it.
+    /// Workspaces whose token cannot use RTM are skipped after the first
+    /// refusal.
+    pub(super) fn signal_typing(&mut self) {
+        if !self.input.take_typing_signal() {
+            return;
+        }
+        let Some(channel_id) = self.get_active_channel_id() else {
+            return;
+        };
+        let Some(ws) = self.active_workspace() else {
+            return;
+        };
+        let team_id = ws.workspace.team_id.clone();
+        let token = ws.workspace.xoxp_token.clone();
+        if self.typing_unsupported.contains(&team_id) {
+            return;
+        }
+        if let Some(tx) = self.typing_senders.get(&team_id) {
+            if tx.send(channel_id.clone()).is_ok() {
+                return;
             }
-            InputMode::AgentMention => {
-                if let Some(channel) = self.get_active_channel_id() {
-                    if let Some(ws) = self.workspaces.get(self.active_workspace) {
-                        let token = ws.workspace.xoxp_token.clone();
-                        let thread_ts = self.active_threads.get(&channel).cloned();
-                        let context = "Failed to send mention".to_string();
-                        let api = self.slack_api.clone();
-                        let channel_id_for_event = channel.clone();
-                        self.spawn_app_task(async move {
-                            let result = if let Some(ts) = thread_ts {
-                                api.send_message_to_thread(&token, &channel, &text, &ts)
-                                    .await
-                            } else {
-                                api.send_message(&token, &channel, &text).await
-                            };
-                            AppAsyncEvent::SlackSendResult {
-                                context,
-                                channel_id: Some(channel_id_for_event),
-                                error: result.err().map(|e| App::actionable_error(&e)),
-                            }
-                        });
+        }
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let _ = tx.send(channel_id);
+        self.typing_senders.insert(team_id.clone(), tx);
+        let api = self.slack_api.clone();
+        let rtm_api = Self::build_slack_api(&self.config);
+        self.spawn_app_task(async move {
+            let url = match api.rtm_connect(&token).await {
+                Ok(url) => url,
+                Err(e) => {
+                    return AppAsyncEvent::TypingConnectionEnded {
+                        team_id,
+                        error: Some(e.to_string()),
+                        unsupported: true,
                     }
                 }
+            };
+            let result = slack_zc_slack::socket::run_rtm_typing(&rtm_api, &url, rx).await;
+            AppAsyncEvent::TypingConnectionEnded {
+                team_id,
+                error: result.err().map(|e| e.to_string()),
+                unsupported: false,
             }
-        }
+        });
+    }
 
-        self.input.clear();
-        Ok(())
+    /// Looks up an unknown user once per session so their name can replace
+    /// the raw id wherever it is shown.
+    pub(super) fn fetch_user_info(&mut self, user_id: &str) {
+        let Some(ws) = self.active_workspace() else {
+            return;
+        };
+        if ws.users.contains_key(user_id) {
+            return;
+        }
+        let token = ws.workspace.xoxp_token.clone();
+        let team_id = ws.workspace.team_id.clone();
+        if !self.user_info_requested.insert(user_id.to_string()) {
+            return;
+        }
+        let user_id = user_id.to_string();
+        let api = self.slack_api.clone();
+        self.spawn_app_task(async move {
+            match api.get_user(&token, &user_id).await {
+                Ok(user) => AppAsyncEvent::UserInfoLoaded {
+                    team_id,
+                    user_id,
+                    user: Some(user),
+                    error: None,
+                },
+                Err(e) => AppAsyncEvent::UserInfoLoaded {
+                    team_id,
+                    user_id,
+                    user: None,
+                    error: Some(App::actionable_error(&e)),
+                },
+            }
+        });
     }
 
-    pub(super) fn handle_agent_command(&mut self, text: &str) -> Result<()> {
-        let text = text.trim();
-        if text.is_empty() {
-            return Ok(());
+    pub(super) fn toggle_sidebar(&mut self) {
+        let visible = !self.config.ui.show_sidebar;
+        self.config.ui.show_sidebar = visible;
+        self.layout.set_sidebar_visible(visible);
+        if !visible && self.focus == Focus::Sidebar {
+            self.focus = Focus::Messages;
         }
+        self.save_config();
+    }
 
-        let mut parts = text.splitn(2, ' ');
-        let command = parts.next().unwrap_or_default();
-        let raw_prompt = parts.next().unwrap_or_default();
+    pub(super) fn toggle_agent_panel(&mut self) {
+        let visible = !self.config.ui.show_agent_panel;
+        self.config.ui.show_agent_panel = visible;
+        self.layout.set_agent_visible(visible);
+        self.save_config();
+    }
 
-        if matches!(command, "/résume" | "/draft" | "/cherche") {
-            let (prompt, context_channel) = Self::extract_context_channel(raw_prompt);
-            self.confirmation_dialog = Some(ConfirmationDialog {
-                command: command.to_string(),
-                prompt,
-                context_channel,
-                is_editing: true,
-            });
-            return Ok(());
+    /// Writes the config back so layout preferences survive restarts.
+    fn save_config(&mut self) {
+        let Some(path) = self.config_path.clone() else {
+            return;
+        };
+        if let Err(e) = self.config.save(&path) {
+            self.report_error("Failed to save config", e);
         }
+    }
 
-        self.execute_agent_command(text)
+    /// Applies `update` to the channel in both the visible list and its workspace.
+    pub(super) fn update_channel(&mut self, channel_id: &str, update: impl Fn(&mut Channel)) {
+        for channel in self
+            .channels
+            .iter_mut()
+            .chain(
+                self.workspaces
+                    .iter_mut()
+                    .flat_map(|ws| ws.channels.iter_mut()),
+            )
+            .filter(|ch| ch.id == channel_id)
+        {
+            update(channel);
+        }
     }
 
-    pub(super) fn dispatch_confirmed_command(&mut self, dialog: &ConfirmationDialog) -> Result<()> {
-        let mut command_text = dialog.command.clone();
-        if !dialog.prompt.is_empty() {
-            command_text.push(' ');
-            command_text.push_str(dialog.prompt.trim());
+    /// [`App::read_only_reason`] for a post typed in the open channel or
+    /// its open thread.
+    pub(super) fn read_only_here(&self) -> Option<String> {
+        let channel_id = self.get_active_channel_id()?;
+        self.read_only_reason(&channel_id, self.active_threads.contains_key(&channel_id))
+    }
+
+    /// A post of `text` to the open channel, or its open thread.
+    fn pending_send_here(&self, text: String) -> Option<PendingSend> {
+        let channel_id = self.get_active_channel_id()?;
+        Some(PendingSend {
+            team_id: self.active_workspace_id.clone()?,
+            thread_ts: self.active_threads.get(&channel_id).cloned(),
+            channel_id,
+            text,
+            local_id: None,
+        })
+    }
+
+    /// Posts `send` in the background; a failure is reported under
+    /// `context` with the post attached for `R` to retry.
+    pub(super) fn dispatch_send(&mut self, mut send: PendingSend, context: &str) {
+        if self.is_archived_channel(&send.channel_id) {
+            self.report_error(context, "channel is archived");
+            return;
         }
-        if let Some(channel) = &dialog.context_channel {
-            command_text.push(' ');
-            command_text.push('#');
-            command_text.push_str(channel);
+        if let Some(reason) = self.read_only_reason(&send.channel_id, send.thread_ts.is_some()) {
+            self.report_error(context, reason);
+            return;
+        }
+        let Some(token) = self.token_for(&send.team_id) else {
+            self.report_error(context, "workspace is no longer signed in");
+            return;
+        };
+        self.subscribe_thread(&send);
+        self.track_delivery(&mut send);
+        let context = context.to_string();
+        let api = self.slack_api.clone();
+        self.spawn_app_task_as(TaskKind::Send, async move {
+            let result = post_pending(api.as_ref(), &token, &send).await;
+            Self::send_result(context, send, result)
+        });
+    }
+
+    /// Shows a channel post in the channel as `Sending` until its result
+    /// comes back. A post sent again reuses its local copy; thread replies
+    /// get none, as the thread pane reloads on its own.
+    fn track_delivery(&mut self, send: &mut PendingSend) {
+        if send.thread_ts.is_some() {
+            return;
         }
+        if let Some(id) = send.local_id {
+            self.failed_sends.remove(&id);
+            if let Some(msg) = self.local_message_mut(&send.channel_id, id) {
+                msg.delivery = Some(DeliveryState::Sending);
+                return;
+            }
+        }
+        let id = self.next_local_id;
+        self.next_local_id += 1;
+        send.local_id = Some(id);
 
-        self.execute_agent_command(command_text.trim())
+        let user_id = self.current_user_id().unwrap_or_default();
+        let messages = self.messages.entry(send.channel_id.clone()).or_default();
+        let username = messages
+            .iter()
+            .rev()
+            .find(|m| m.user_id == user_id)
+            .map(|m| m.username.clone())
+            .unwrap_or_else(|| "you".to_string());
+        messages.push_back(Message {
+            ts: local_ts(id),
+            user_id,
+            username,
+            text: send.text.clone(),
+            thread_ts: None,
+            timestamp: Utc::now(),
+            is_agent: false,
+            is_bot: false,
+            reactions: Vec::new(),
+            is_edited: false,
+            is_deleted: false,
+            files: Vec::new(),
+            reply_count: None,
+            latest_reply: None,
+            reply_users: Vec::new(),
+            last_read: None,
+            rendered_blocks: Vec::new(),
+            is_system: false,
+            subtype: None,
+            call: None,
+            raw: None,
+            delivery: Some(DeliveryState::Sending),
+        });
+        self.scroll_offset = 0;
     }
 
-    pub(super) fn fetch_channel_history(&mut self, channel_id: &str) -> Result<()> {
-        if let Some(ws) = self.workspaces.get(self.active_workspace) {
-            let token = ws.workspace.xoxp_token.clone();
-            let channel_id = channel_id.to_string();
-            let api = self.slack_api.clone();
-            self.spawn_app_task(async move {
-                match api.get_history(&token, &channel_id, 50).await {
-                    Ok(messages) => AppAsyncEvent::ChannelHistoryLoaded {
-                        channel_id,
-                        messages,
-                        error: None,
-                    },
-                    Err(e) => AppAsyncEvent::ChannelHistoryLoaded {
-                        channel_id,
-                        messages: Vec::new(),
-                        error: Some(App::actionable_error(&e)),
-                    },
+    fn local_message_mut(&mut self, channel_id: &str, id: u64) -> Option<&mut Message> {
+        let ts = local_ts(id);
+        self.messages
+            .get_mut(channel_id)?
+            .iter_mut()
+            .find(|m| m.ts == ts)
+    }
+
+    /// Applies a post's result to its local copy: a refused post stays
+    /// as `Failed` with the post kept for Enter; an accepted one takes
+    /// Slack's ts, or gives way to the real message if it already arrived.
+    pub(super) fn settle_delivery(
+        &mut self,
+        channel_id: &str,
+        id: u64,
+        result: Result<String, PendingSend>,
+    ) {
+        match result {
+            Err(send) => {
+                if let Some(msg) = self.local_message_mut(channel_id, id) {
+                    msg.delivery = Some(DeliveryState::Failed);
+                    self.failed_sends.insert(id, send);
                 }
-            });
+            }
+            Ok(ts) => {
+                let Some(messages) = self.messages.get_mut(channel_id) else {
+                    return;
+                };
+                let local = local_ts(id);
+                if let Some(real) = messages.iter_mut().find(|m| m.ts == ts) {
+                    real.delivery = Some(DeliveryState::Sent);
+                    messages.retain(|m| m.ts != local);
+                } else if let Some(msg) = messages.iter_mut().find(|m| m.ts == local) {
+                    msg.ts = ts;
+                    msg.delivery = Some(DeliveryState::Sent);
+                }
+            }
         }
-
-        Ok(())
     }
 
-    pub(super) fn insert_channel_reference(&mut self, channel_name: &str, trigger_position: usize) {
-        if trigger_position >= self.input.buffer.len() {
+    /// Carries tracked posts over a history reload: the `Sent` mark onto
+    /// the real message, unconfirmed copies onto the end.
+    pub(super) fn keep_local_copies(&self, channel_id: &str, fresh: &mut VecDeque<Message>) {
+        let Some(old) = self.messages.get(channel_id) else {
             return;
+        };
+        for msg in old.iter().filter(|m| m.delivery.is_some()) {
+            if msg.is_unconfirmed() {
+                fresh.push_back(msg.clone());
+            } else if let Some(real) = fresh.iter_mut().find(|m| m.ts == msg.ts) {
+                real.delivery = msg.delivery;
+            }
         }
+    }
 
-        let replacement = format!("#{} ", channel_name);
-        let replace_end = trigger_position
-            .saturating_add(1)
-            .min(self.input.buffer.len());
-        self.input
-            .buffer
-            .replace_range(trigger_position..replace_end, &replacement);
+    /// The local id of the failed post under the cursor, if that's what it is.
+    pub(super) fn failed_message_at_cursor(&self) -> Option<u64> {
+        let msg = self.selected_or_cursor_message()?;
+        if msg.delivery != Some(DeliveryState::Failed) {
+            return None;
+        }
+        msg.ts.strip_prefix(LOCAL_TS_PREFIX)?.parse().ok()
     }
 
-    fn extract_context_channel(prompt: &str) -> (String, Option<String>) {
-        let mut context_channel = None;
-        let filtered_parts: Vec<&str> = prompt
-            .split_whitespace()
-            .filter(|part| {
-                if context_channel.is_none() && part.starts_with('#') && part.len() > 1 {
-                    context_channel = Some(part.trim_start_matches('#').to_string());
-                    false
-                } else {
-                    true
-                }
-            })
-            .collect();
+    /// Sends a failed post again from its local copy.
+    pub(super) fn retry_failed_message(&mut self, id: u64) {
+        let Some(send) = self.failed_sends.get(&id).cloned() else {
+            return;
+        };
+        self.forget_send_retry(id);
+        self.dispatch_send(send, "Failed to send message");
+    }
 
-        (filtered_parts.join(" "), context_channel)
+    /// Drops a failed post and its local copy.
+    pub(super) fn discard_failed_message(&mut self, id: u64) {
+        let Some(send) = self.failed_sends.remove(&id) else {
+            return;
+        };
+        self.forget_send_retry(id);
+        let ts = local_ts(id);
+        if let Some(messages) = self.messages.get_mut(&send.channel_id) {
+            messages.retain(|m| m.ts != ts);
+        }
+        self.selected_message = None;
     }
 
-    fn execute_agent_command(&mut self, text: &str) -> Result<()> {
-        use slack_zc_agent::commands::{process_command, CommandType};
+    /// Stops `R` from sending a post that was retried or discarded
+    /// from its local copy.
+    fn forget_send_retry(&mut self, id: u64) {
+        for entry in &mut self.error_history {
+            if entry
+                .retry
+                .as_ref()
+                .is_some_and(|send| send.local_id == Some(id))
+            {
+                entry.retry = None;
+            }
+        }
+    }
 
-        let (cmd_name, args) = match process_command(text) {
-            Some((cmd, args)) => (cmd, args),
-            None => return Ok(()),
-        };
+    fn token_for(&self, team_id: &str) -> Option<String> {
+        self.workspaces
+            .iter()
+            .find(|ws| ws.workspace.team_id == team_id)
+            .map(|ws| ws.workspace.xoxp_token.clone())
+    }
 
-        let command = CommandType::from_command(&cmd_name, &args);
-        let channel_id = self.get_active_channel_id().unwrap_or_default();
-        let channel_name = self
-            .selected_channel
-            .and_then(|idx| self.channels.get(idx).map(|ch| ch.name.clone()))
-            .unwrap_or_else(|| channel_id.clone());
-        let user_id = self
-            .workspaces
-            .get(self.active_workspace)
-            .and_then(|ws| ws.workspace.user_id.clone())
-            .unwrap_or_else(|| "UNKNOWN_USER".to_string());
-        let (history_messages, history_chars, timeout_secs) = match command {
-            CommandType::Resume { .. } => (12, 220, self.config.zeroclaw.timeout_seconds.max(60)),
-            CommandType::Search { .. } => (12, 260, self.config.zeroclaw.timeout_seconds.max(45)),
-            _ => (16, 280, self.config.zeroclaw.timeout_seconds),
+    /// The event for a finished post: on failure it carries the post for
+    /// `R` and, for a refusal with a fix, the fix to offer.
+    fn send_result(context: String, send: PendingSend, result: Result<String>) -> AppAsyncEvent {
+        let error = match result {
+            Ok(ts) => {
+                return AppAsyncEvent::SlackSendResult {
+                    context,
+                    channel_id: Some(send.channel_id),
+                    error: None,
+                    retry: None,
+                    recovery: None,
+                    local_id: send.local_id,
+                    ts: Some(ts),
+                }
+            }
+            Err(error) => error,
         };
-        let history_context =
-            self.build_agent_history_context(&channel_id, history_messages, history_chars);
-        let payload = serde_json::json!({
-            "message": command.to_agent_prompt(&channel_name, &history_context, &user_id)
-        });
-        tracing::info!(
-            "Dispatching agent command {} for channel {} ({})",
-            cmd_name,
-            channel_name,
-            channel_id
-        );
+        let recovery = match slack_zc_slack::error::map_anyhow_error_ref(&error) {
+            ApiError::NotInChannel(_) => Some(SendRecovery::JoinChannel),
+            ApiError::MessageTooLong(_) => Some(SendRecovery::SplitMessage),
+            _ => None,
+        };
+        AppAsyncEvent::SlackSendResult {
+            context,
+            channel_id: Some(send.channel_id.clone()),
+            error: Some(App::actionable_error(&error)),
+            local_id: send.local_id,
+            ts: None,
+            retry: Some(send),
+            recovery,
+        }
+    }
 
-        if let Some(ref mut runner) = self.agent_runner {
-            if let Some(gateway) = runner.get_gateway().cloned() {
-                self.agent_processing = true;
-                let command_text = text.to_string();
-                self.loading_start_time = Some(Instant::now());
-                self.loading_command = Some(command_text.clone());
-                let channel = self.get_active_channel_id();
-                let post_to_slack = self.config.zeroclaw.post_to_slack;
-                let token = self
-                    .workspaces
-                    .get(self.active_workspace)
-                    .map(|ws| ws.workspace.xoxp_token.clone());
-                let thread_ts = channel
-                    .as_ref()
-                    .and_then(|ch| self.active_threads.get(ch).cloned());
+    /// Answers the prompt raised by a refused post: joins the channel or
+    /// splits the message, then posts it again.
+    pub(super) fn resolve_send_recovery(&mut self, accept: bool) {
+        let Some(SendRecoveryPrompt { recovery, send }) = self.send_recovery.take() else {
+            return;
+        };
+        if !accept {
+            return;
+        }
+        let Some(token) = self.token_for(&send.team_id) else {
+            self.report_error("Failed to send message", "workspace is no longer signed in");
+            return;
+        };
+        let api = self.slack_api.clone();
+        match recovery {
+            SendRecovery::JoinChannel => self.spawn_app_task_as(TaskKind::Send, async move {
+                if let Err(e) = api.join_channel(&token, &send.channel_id).await {
+                    return Self::send_result("Failed to join channel".to_string(), send, Err(e));
+                }
+                let result = post_pending(api.as_ref(), &token, &send).await;
+                Self::send_result("Failed to send message".to_string(), send, result)
+            }),
+            SendRecovery::SplitMessage => self.dispatch_chunked_send(send),
+        }
+    }
+
+    /// Posts an over-long message as several, one after another so they
+    /// land in order. A failure names the part that didn't go out.
+    pub(super) fn dispatch_chunked_send(&mut self, mut send: PendingSend) {
+        let Some(token) = self.token_for(&send.team_id) else {
+            self.report_error("Failed to send message", "workspace is no longer signed in");
+            return;
+        };
+        self.track_delivery(&mut send);
+        let api = self.slack_api.clone();
+        self.spawn_app_task_as(TaskKind::Send, async move {
+            let chunks = split_message(&send.text, MAX_MESSAGE_CHARS);
+            let total = chunks.len();
+            let mut last_ts = String::new();
+            for (idx, chunk) in chunks.iter().enumerate() {
+                let part = PendingSend {
+                    text: chunk.clone(),
+                    ..send.clone()
+                };
+                let e = match post_pending(api.as_ref(), &token, &part).await {
+                    Ok(ts) => {
+                        last_ts = ts;
+                        continue;
+                    }
+                    Err(e) => e,
+                };
+                let mut context = format!("Failed to send part {} of {}", idx + 1, total);
+                if idx > 0 {
+                    context.push_str(&format!(" (parts 1-{idx} were posted)"));
+                }
+                // `R` resends what didn't go out, not the whole message.
+                let rest = PendingSend {
+                    text: chunks[idx..].join("\n"),
+                    ..send
+                };
+                return Self::send_result(context, rest, Err(e));
+            }
+            Self::send_result("Failed to send message".to_string(), send, Ok(last_ts))
+        });
+    }
+
+    /// Uploads an over-long message as a text snippet instead.
+    fn dispatch_snippet_upload(&mut self, send: PendingSend) {
+        let Some(token) = self.token_for(&send.team_id) else {
+            self.report_error(
+                "Failed to upload snippet",
+                "workspace is no longer signed in",
+            );
+            return;
+        };
+        let api = self.slack_api.clone();
+        self.spawn_app_task_as(TaskKind::Send, async move {
+            let error = api
+                .upload_snippet(
+                    &token,
+                    &send.channel_id,
+                    send.thread_ts.as_deref(),
+                    "message.txt",
+                    &send.text,
+                )
+                .await
+                .err()
+                .map(|e| App::actionable_error(&e));
+            AppAsyncEvent::SlackSendResult {
+                context: "Failed to upload snippet".to_string(),
+                channel_id: Some(send.channel_id),
+                error,
+                retry: None,
+                recovery: None,
+                local_id: None,
+                ts: None,
+            }
+        });
+    }
+
+    /// Answers the long-message dialog: `Some(true)` uploads a snippet,
+    /// `Some(false)` sends it in parts, `None` goes back to editing.
+    pub(super) fn resolve_long_message(&mut self, upload: Option<bool>) {
+        let Some(send) = self.long_message.take() else {
+            return;
+        };
+        match upload {
+            Some(true) => self.dispatch_snippet_upload(send),
+            Some(false) => self.dispatch_chunked_send(send),
+            None => return,
+        }
+        self.finish_submit();
+    }
+
+    /// Sends the post attached to `error_history[idx]` again, once.
+    pub(super) fn retry_send(&mut self, idx: usize) -> bool {
+        let Some(send) = self
+            .error_history
+            .get_mut(idx)
+            .and_then(|entry| entry.retry.take())
+        else {
+            return false;
+        };
+        self.dispatch_send(send, "Failed to send message");
+        true
+    }
+
+    /// The newest error still shown as a toast, when it can be retried.
+    pub(super) fn retryable_toast(&self) -> Option<usize> {
+        let entry = self.error_history.front()?;
+        (entry.retry.is_some() && entry.toast_until > Instant::now()).then_some(0)
+    }
+
+    pub(super) fn handle_input_submit(&mut self) -> Result<()> {
+        let text = self.input.buffer.clone();
+        if text.is_empty() {
+            return Ok(());
+        }
+        // Kept in the input so it can be pasted elsewhere.
+        let archived = self
+            .get_active_channel_id()
+            .is_some_and(|ch| self.is_archived_channel(&ch));
+        if archived && self.input.mode != InputMode::AgentCommand {
+            self.report_error("Can't send", "channel is archived");
+            return Ok(());
+        }
+        if self.input.mode != InputMode::AgentCommand {
+            if let Some(reason) = self.read_only_here() {
+                self.report_error("Can't send", reason);
+                return Ok(());
+            }
+        }
+
+        match self.input.mode {
+            InputMode::Normal => {
+                if let Some(send) = self.pending_send_here(text) {
+                    if send.text.chars().count() > LONG_MESSAGE_CHARS {
+                        // Stays in the input until the dialog is answered.
+                        self.long_message = Some(send);
+                        return Ok(());
+                    }
+                    self.dispatch_send(send, "Failed to send message");
+                }
+            }
+            InputMode::AgentCommand => {
+                self.handle_agent_command(&text)?;
+            }
+            InputMode::AgentMention if self.agent_is_active() && self.agent_enabled() => {
+                self.execute_agent_mention(text);
+            }
+            InputMode::AgentMention => {
+                if !self.offline_mention_noticed {
+                    self.offline_mention_noticed = true;
+                    let reason = if self.agent_enabled() {
+                        "ZeroClaw is offline"
+                    } else {
+                        "ZeroClaw is off in this workspace"
+                    };
+                    self.report_error(reason, "the mention was sent to Slack as a plain message");
+                }
+                if let Some(send) = self.pending_send_here(text) {
+                    self.dispatch_send(send, "Failed to send mention");
+                }
+            }
+        }
+
+        self.finish_submit();
+        Ok(())
+    }
+
+    fn finish_submit(&mut self) {
+        self.input.clear();
+        // Whatever an agent draft displaced comes back once the draft is sent.
+        if let Some(stashed) = self.stashed_input.take() {
+            self.input.set_text(stashed);
+        }
+    }
+
+    pub(super) fn handle_agent_command(&mut self, text: &str) -> Result<()> {
+        self.submit_agent_command(text, false)
+    }
+
+    /// `rerun` marks a command replayed by `/again`; it goes through the
+    /// same checks and confirmation as when it was typed.
+    fn submit_agent_command(&mut self, text: &str, rerun: bool) -> Result<()> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Ok(());
+        }
+
+        let mut parts = text.splitn(2, ' ');
+        let command = parts.next().unwrap_or_default();
+        let raw_prompt = parts.next().unwrap_or_default();
+
+        if command == "/agent" {
+            match raw_prompt.trim() {
+                "restart" => {
+                    self.zeroclaw_restart_attempts = 0;
+                    self.agent_status = AgentStatus::Starting;
+                    self.restart_zeroclaw();
+                }
+                "logs" => {
+                    self.agent_logs = Some(
+                        self.agent_runner
+                            .as_ref()
+                            .map(|runner| runner.recent_output(usize::MAX))
+                            .unwrap_or_default(),
+                    );
+                }
+                "on" => self.set_agent_enabled(true),
+                "off" => self.set_agent_enabled(false),
+                _ => self.report_error(
+                    "Agent command",
+                    "usage: /agent restart | /agent logs | /agent on | /agent off",
+                ),
+            }
+            return Ok(());
+        }
+
+        if command == "/history" {
+            self.open_agent_history();
+            return Ok(());
+        }
+
+        if command == "/again" {
+            match raw_prompt.trim() {
+                "" => self.rerun_agent_command(1),
+                n => match n.parse::<usize>() {
+                    Ok(nth) if nth > 0 => self.rerun_agent_command(nth),
+                    _ => self.report_error("Agent command", "usage: /again [n]"),
+                },
+            }
+            return Ok(());
+        }
+
+        if command == "/remind" {
+            self.add_reminder(raw_prompt);
+            return Ok(());
+        }
+
+        if command == "/reminders" {
+            self.open_reminders(None);
+            return Ok(());
+        }
+
+        if command == "/away" || command == "/active" {
+            self.set_presence_away(command == "/away");
+            return Ok(());
+        }
+
+        // Channel actions run here and never reach the agent.
+        if matches!(command, "/join" | "/leave" | "/topic" | "/mute") {
+            match command {
+                "/join" => self.join_channel_named(raw_prompt.trim()),
+                "/leave" => self.leave_active_channel(),
+                "/topic" => self.set_or_show_topic(raw_prompt.trim()),
+                _ => self.toggle_active_channel_mute(),
+            }
+            return Ok(());
+        }
+
+        if SlashCommand::find(command).is_none() && !self.config.zeroclaw.forward_unknown_commands {
+            self.report_error(
+                "Unknown command",
+                format!("{command} — set forward_unknown_commands to ask ZeroClaw instead"),
+            );
+            return Ok(());
+        }
+
+        if !self.agent_enabled() {
+            let name = self
+                .active_workspace()
+                .map(|ws| ws.workspace.team_name.clone())
+                .unwrap_or_default();
+            self.report_error(
+                "Agent disabled",
+                format!("ZeroClaw is off in {}; `/agent on` enables it here", name),
+            );
+            return Ok(());
+        }
+
+        // Everything below needs the gateway; offer to connect rather than
+        // let the command fail after it was composed.
+        if !self.agent_is_active() {
+            self.agent_offline_prompt = Some(text.to_string());
+            return Ok(());
+        }
+        self.remember_agent_command(text);
+
+        if matches!(command, "/résume" | "/draft" | "/cherche") {
+            let (prompt, context_channel) = Self::extract_context_channel(raw_prompt);
+            let target = self.post_target(None);
+            let thread_parent = target.as_ref().and_then(|target| {
+                let parent_ts = target.thread_ts.as_deref()?;
+                let parent = self
+                    .messages
+                    .get(&target.channel_id)?
+                    .iter()
+                    .find(|message| message.ts == parent_ts)?;
+                let first_line = parent.text.lines().next().unwrap_or_default();
+                Some(format!("{}: {}", parent.username, first_line))
+            });
+            // Drafts land in the input bar and are never posted.
+            let can_post = self.config.zeroclaw.post_to_slack && command != "/draft";
+            let reply = match &target {
+                Some(target) if can_post && target.thread_ts.is_some() => ReplyTarget::Thread,
+                Some(_) if can_post => ReplyTarget::Channel,
+                _ => ReplyTarget::PanelOnly,
+            };
+            self.confirmation_dialog = Some(ConfirmationDialog {
+                command: command.to_string(),
+                prompt,
+                context_channel,
+                is_editing: true,
+                target,
+                thread_parent,
+                reply,
+                can_post,
+                rerun,
+            });
+            return Ok(());
+        }
+
+        self.run_agent_command(text, None, rerun)
+    }
+
+    /// Puts `text` first in the open channel's agent command history.
+    fn remember_agent_command(&mut self, text: &str) {
+        let Some(channel_id) = self.get_active_channel_id() else {
+            return;
+        };
+        let history = self.agent_command_history.entry(channel_id).or_default();
+        history.retain(|command| command != text);
+        history.push_front(text.to_string());
+        history.truncate(AGENT_RERUN_HISTORY);
+    }
+
+    /// The open channel's agent commands, most recent first.
+    pub(super) fn channel_agent_commands(&self) -> Vec<String> {
+        self.get_active_channel_id()
+            .and_then(|channel_id| self.agent_command_history.get(&channel_id))
+            .map(|history| history.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Runs the open channel's `nth` most recent agent command again, with
+    /// the channel as it is now for context.
+    pub(super) fn rerun_agent_command(&mut self, nth: usize) {
+        let Some(text) = self
+            .channel_agent_commands()
+            .get(nth.saturating_sub(1))
+            .cloned()
+        else {
+            self.report_error(
+                "Nothing to re-run",
+                format!("no agent command #{} in this channel yet", nth),
+            );
+            return;
+        };
+        if let Err(e) = self.submit_agent_command(&text, true) {
+            self.report_error("Agent command failed", e);
+        }
+    }
+
+    pub(super) fn agent_is_active(&self) -> bool {
+        matches!(self.agent_status, AgentStatus::Active)
+    }
+
+    /// The active workspace's choice from `/agent on|off`, else the config.
+    pub(super) fn agent_enabled(&self) -> bool {
+        self.active_workspace_id
+            .as_deref()
+            .and_then(|team_id| self.session.as_ref()?.agent_enabled_for(team_id))
+            .unwrap_or(self.config.zeroclaw.agent_enabled)
+    }
+
+    /// `/agent on|off`: remembers the choice for the active workspace.
+    fn set_agent_enabled(&mut self, enabled: bool) {
+        let (Some(team_id), Some(session)) =
+            (self.active_workspace_id.clone(), self.session.as_mut())
+        else {
+            self.report_error("Agent command", "sign in to a workspace first");
+            return;
+        };
+        session.agent_mut(&team_id).enabled = Some(enabled);
+        if let Err(e) = session.save() {
+            self.report_error("Failed to save agent setting", e);
+        }
+        if enabled && self.agent_runner.is_none() {
+            self.start_zeroclaw_auto();
+        }
+    }
+
+    /// Answers the offline prompt: connect (and pair if needed) then run the
+    /// command, or hand the command back to the input.
+    pub(super) fn resolve_agent_offline_prompt(&mut self, connect: bool) {
+        let Some(command) = self.agent_offline_prompt.take() else {
+            return;
+        };
+        if connect {
+            self.pending_agent_command = Some(command);
+            self.zeroclaw_restart_attempts = 0;
+            self.agent_status = AgentStatus::Starting;
+            self.restart_zeroclaw();
+        } else {
+            self.input.set_text(command);
+            self.focus = Focus::Input;
+        }
+    }
+
+    /// `/away` and `/active`. Applies to every signed-in workspace, since
+    /// the pings that `/away` suppresses go to all of them.
+    pub(super) fn set_presence_away(&mut self, away: bool) {
+        let tokens = self.presence_tokens();
+        if tokens.is_empty() {
+            self.report_error("Can't set presence", "no workspace is signed in");
+            return;
+        }
+        self.presence_away = away;
+        // `/active` already tells Slack; the next ping is a full interval off.
+        self.presence_pinged_at = Some(Instant::now());
+        let presence = if away { "away" } else { "auto" };
+        self.send_presence(tokens, presence, true);
+    }
+
+    /// Fetches the active workspace's channels and DMs again and merges
+    /// them into the sidebar; `manual` reports the outcome.
+    pub(super) fn refresh_channel_list(&mut self, manual: bool) {
+        let Some(ws) = self.active_workspace() else {
+            return;
+        };
+        let team_id = ws.workspace.team_id.clone();
+        let token = ws.workspace.xoxp_token.clone();
+        self.channels_refreshed_at = Instant::now();
+        if self.channels_loading.contains(&team_id)
+            || !self.channels_refreshing.insert(team_id.clone())
+        {
+            if manual {
+                self.report_notice("The channel list is already loading");
+            }
+            return;
+        }
+        let include_archived = self.show_archived;
+        let api = self.slack_api.clone();
+        self.spawn_app_task(async move {
+            let result = async {
+                let mut channels = Vec::new();
+                let mut cursor: Option<String> = None;
+                loop {
+                    let (page, next) = api
+                        .list_channels_page(&token, cursor.as_deref(), include_archived)
+                        .await?;
+                    channels.extend(page);
+                    cursor = match next {
+                        Some(next) => Some(next),
+                        None => break,
+                    };
+                }
+                cursor = None;
+                loop {
+                    let (page, next) = api.list_dms_page(&token, cursor.as_deref()).await?;
+                    channels.extend(page);
+                    cursor = match next {
+                        Some(next) => Some(next),
+                        None => break,
+                    };
+                }
+                anyhow::Ok(channels)
+            }
+            .await;
+            match result {
+                Ok(channels) => AppAsyncEvent::ChannelListRefreshed {
+                    team_id,
+                    channels,
+                    manual,
+                    error: None,
+                },
+                Err(e) => AppAsyncEvent::ChannelListRefreshed {
+                    team_id,
+                    channels: Vec::new(),
+                    manual,
+                    error: Some(App::actionable_error(&e)),
+                },
+            }
+        });
+    }
+
+    /// `ui.channel_refresh_minutes`: refreshes the channel list in the
+    /// background once that long has passed since the last fetch.
+    pub(super) fn refresh_channels_periodically(&mut self, now: Instant) {
+        let minutes = self.config.ui.channel_refresh_minutes;
+        if minutes == 0 || self.is_loading {
+            return;
+        }
+        let interval = Duration::from_secs(minutes.saturating_mul(60));
+        if now.saturating_duration_since(self.channels_refreshed_at) >= interval {
+            self.refresh_channel_list(false);
+        }
+    }
+
+    /// Replaces a workspace's channel list with a fresh one, keeping what
+    /// the app tracks itself (unread counts, activity, details fetched
+    /// separately). The open channel stays, flagged, if it is gone.
+    /// Returns how many channels were added and removed.
+    pub(super) fn merge_channel_list(
+        &mut self,
+        team_id: &str,
+        fresh: Vec<Channel>,
+    ) -> (usize, usize) {
+        let Some(ws_idx) = self
+            .workspaces
+            .iter()
+            .position(|ws| ws.workspace.team_id == team_id)
+        else {
+            return (0, 0);
+        };
+        let is_active = self.active_workspace_id.as_deref() == Some(team_id);
+        let old = std::mem::take(&mut self.workspaces[ws_idx].channels);
+        let mut merged: Vec<Channel> = fresh
+            .into_iter()
+            .map(|mut channel| {
+                if let Some(known) = old.iter().find(|ch| ch.id == channel.id) {
+                    Self::keep_local_channel_state(&mut channel, known);
+                }
+                channel
+            })
+            .collect();
+        let added = merged
+            .iter()
+            .filter(|ch| !old.iter().any(|known| known.id == ch.id))
+            .count();
+        let mut removed = 0;
+        for channel in old {
+            if merged.iter().any(|ch| ch.id == channel.id) {
+                continue;
+            }
+            removed += 1;
+            if is_active && self.selected_channel_id.as_deref() == Some(channel.id.as_str()) {
+                self.removed_channel = Some(channel.id.clone());
+                merged.push(channel);
+            }
+        }
+        if is_active
+            && self.removed_channel.as_ref().is_some_and(|gone| {
+                merged.iter().any(|ch| ch.id == *gone)
+                    && self.selected_channel_id.as_ref() != Some(gone)
+            })
+        {
+            self.removed_channel = None;
+        }
+        merged.sort_by_key(App::sidebar_section);
+        self.workspaces[ws_idx].channels = merged.clone();
+        if is_active {
+            let cursor_id = self.cursor_channel_id();
+            self.channels = merged;
+            if let Some(idx) =
+                cursor_id.and_then(|id| self.channels.iter().position(|ch| ch.id == id))
+            {
+                self.sidebar_cursor = idx;
+            }
+            self.sort_channels_by_section();
+            self.sidebar_cursor = self
+                .sidebar_cursor
+                .min(self.channels.len().saturating_sub(1));
+            self.refresh_channel_picker();
+        }
+        (added, removed)
+    }
+
+    /// Carries over from `known` what a channel list does not say.
+    fn keep_local_channel_state(channel: &mut Channel, known: &Channel) {
+        channel.unread_count = known.unread_count;
+        channel.mention_count = known.mention_count;
+        channel.member_count = channel.member_count.or(known.member_count);
+        channel.created = channel.created.or(known.created);
+        channel.creator = channel.creator.take().or_else(|| known.creator.clone());
+        channel.last_read = channel.last_read.take().or_else(|| known.last_read.clone());
+        channel.latest_ts = channel.latest_ts.take().max(known.latest_ts.clone());
+        channel.is_member = channel.is_member.or(known.is_member);
+        if channel.posting_restricted_to.is_none() {
+            channel.posting_restricted_to = known.posting_restricted_to.clone();
+        }
+        if channel.threads_restricted_to.is_none() {
+            channel.threads_restricted_to = known.threads_restricted_to.clone();
+        }
+    }
+
+    /// Takes a channel out of every sidebar list.
+    fn drop_channel(&mut self, channel_id: &str) {
+        let cursor_id = self.cursor_channel_id();
+        self.channels.retain(|ch| ch.id != channel_id);
+        for ws in &mut self.workspaces {
+            ws.channels.retain(|ch| ch.id != channel_id);
+        }
+        if let Some(idx) = cursor_id.and_then(|id| self.channels.iter().position(|ch| ch.id == id))
+        {
+            self.sidebar_cursor = idx;
+        }
+        self.sidebar_cursor = self
+            .sidebar_cursor
+            .min(self.channels.len().saturating_sub(1));
+        self.refresh_channel_picker();
+    }
+
+    /// With `[presence] keep_active`, re-asserts `auto` presence every few
+    /// minutes while there has been input within `idle_minutes`.
+    pub(super) fn keep_presence_active(&mut self, now: Instant) {
+        let presence = &self.config.presence;
+        if !presence.keep_active || self.presence_away {
+            return;
+        }
+        let idle_after = Duration::from_secs(presence.idle_minutes.saturating_mul(60));
+        if now.saturating_duration_since(self.last_input_at) >= idle_after {
+            return;
+        }
+        if self
+            .presence_pinged_at
+            .is_some_and(|at| now.saturating_duration_since(at) < PRESENCE_PING_INTERVAL)
+        {
+            return;
+        }
+        let tokens = self.presence_tokens();
+        if tokens.is_empty() {
+            return;
+        }
+        self.presence_pinged_at = Some(now);
+        self.send_presence(tokens, "auto", false);
+    }
+
+    fn presence_tokens(&self) -> Vec<String> {
+        self.workspaces
+            .iter()
+            .map(|ws| ws.workspace.xoxp_token.clone())
+            .filter(|token| !token.is_empty())
+            .collect()
+    }
+
+    fn send_presence(&self, tokens: Vec<String>, presence: &'static str, manual: bool) {
+        let api = self.slack_api.clone();
+        self.spawn_app_task(async move {
+            let mut error = None;
+            for token in tokens {
+                if let Err(e) = api.set_presence(&token, presence).await {
+                    error = Some(App::actionable_error(&e));
+                }
+            }
+            AppAsyncEvent::PresenceSet { manual, error }
+        });
+    }
+
+    /// `/join #name`: joins a channel of the active workspace and opens it.
+    fn join_channel_named(&mut self, name: &str) {
+        let name = name.trim_start_matches('#');
+        if name.is_empty() {
+            self.report_error("Can't join", "usage: /join #channel");
+            return;
+        }
+        let Some(channel) = self
+            .channels
+            .iter()
+            .find(|ch| !ch.is_dm && (ch.name.eq_ignore_ascii_case(name) || ch.id == name))
+            .cloned()
+        else {
+            self.report_error("Can't join", format!("no channel named #{name} here"));
+            return;
+        };
+        self.run_channel_command(&channel, ChannelCommand::Join);
+    }
+
+    /// `/leave`: leaves the open channel.
+    fn leave_active_channel(&mut self) {
+        match self.selected_channel().cloned() {
+            Some(channel) if channel.is_dm => {
+                self.report_error("Can't leave", "direct messages can't be left");
+            }
+            Some(channel) => self.run_channel_command(&channel, ChannelCommand::Leave),
+            None => self.report_error("Can't leave", "no channel is open"),
+        }
+    }
+
+    /// `/topic text` sets the open channel's topic; `/topic` alone shows it.
+    fn set_or_show_topic(&mut self, topic: &str) {
+        let Some(channel) = self.selected_channel().cloned() else {
+            self.report_error("Can't set topic", "no channel is open");
+            return;
+        };
+        if !topic.is_empty() {
+            self.run_channel_command(&channel, ChannelCommand::SetTopic(topic.to_string()));
+            return;
+        }
+        match channel.topic.as_deref().filter(|topic| !topic.is_empty()) {
+            Some(topic) => self.report_notice(format!("#{} topic: {}", channel.name, topic)),
+            None => self.report_notice(format!("#{} has no topic", channel.name)),
+        }
+    }
+
+    fn run_channel_command(&mut self, channel: &Channel, command: ChannelCommand) {
+        let Some(ws) = self.active_workspace() else {
+            self.report_error("Channel command", "no workspace is signed in");
+            return;
+        };
+        let team_id = ws.workspace.team_id.clone();
+        let token = ws.workspace.xoxp_token.clone();
+        let channel_id = channel.id.clone();
+        let channel_name = channel.name.clone();
+        let api = self.slack_api.clone();
+        self.spawn_app_task(async move {
+            let result = match &command {
+                ChannelCommand::Join => api.join_channel(&token, &channel_id).await,
+                ChannelCommand::Leave => api.leave_channel(&token, &channel_id).await,
+                ChannelCommand::SetTopic(topic) => {
+                    api.set_channel_topic(&token, &channel_id, topic).await
+                }
+            };
+            AppAsyncEvent::ChannelCommandDone {
+                team_id,
+                channel_id,
+                channel_name,
+                command,
+                error: result.err().map(|e| e.to_string()),
+            }
+        });
+    }
+
+    /// `/mute`: mutes the open channel, or unmutes it. Kept with the
+    /// session; nothing is sent to Slack.
+    fn toggle_active_channel_mute(&mut self) {
+        let Some(channel) = self.selected_channel().cloned() else {
+            self.report_error("Can't mute", "no channel is open");
+            return;
+        };
+        let Some(ws) = self.active_workspace_mut() else {
+            return;
+        };
+        let muted = ws.workspace.toggle_mute(&channel.id);
+        let team_id = ws.workspace.team_id.clone();
+        let muted_channels = ws.workspace.muted_channels.clone();
+        if let Some(session) = self.session.as_mut() {
+            if let Some(saved) = session.workspaces.iter_mut().find(|w| w.team_id == team_id) {
+                saved.muted_channels = muted_channels;
+            }
+            if let Err(e) = session.save() {
+                tracing::error!("Failed to save muted channels: {}", e);
+            }
+        }
+        let verb = if muted { "Muted" } else { "Unmuted" };
+        self.report_notice(format!("{verb} #{}", channel.name));
+    }
+
+    /// Registered commands matching the command name being typed; empty
+    /// once an argument has started.
+    pub(super) fn slash_suggestions(&self) -> Vec<&'static SlashCommand> {
+        let buffer = &self.input.buffer;
+        if self.input.mode != InputMode::AgentCommand || buffer.contains(char::is_whitespace) {
+            return Vec::new();
+        }
+        SlashCommand::completions(buffer)
+    }
+
+    /// Tab in the input: completes the command name being typed to the
+    /// first suggestion. False when there is nothing to complete.
+    pub(super) fn complete_slash_command(&mut self) -> bool {
+        let Some(command) = self.slash_suggestions().first().copied() else {
+            return false;
+        };
+        self.input.set_text(format!("{} ", command.name));
+        true
+    }
+
+    /// Whether the active workspace muted `channel_id` with `/mute`.
+    pub(super) fn is_muted(&self, channel_id: &str) -> bool {
+        self.active_workspace()
+            .is_some_and(|ws| ws.workspace.is_muted(channel_id))
+    }
+
+    fn active_token(&self) -> Option<String> {
+        self.active_workspace()
+            .map(|ws| ws.workspace.xoxp_token.clone())
+    }
+
+    /// `/remind me in 20m to …`: sets a Slack reminder, then lists
+    /// reminders with the new one selected.
+    pub(super) fn add_reminder(&mut self, args: &str) {
+        let (text, at) = match Self::parse_reminder(args, Local::now()) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                self.report_error("Can't set reminder", e);
+                return;
+            }
+        };
+        let Some(token) = self.active_token() else {
+            self.report_error("Can't set reminder", "no workspace is signed in");
+            return;
+        };
+        let api = self.slack_api.clone();
+        self.spawn_app_task(async move {
+            match api.add_reminder(&token, &text, at.timestamp()).await {
+                Ok(id) => AppAsyncEvent::ReminderAdded {
+                    id: Some(id),
+                    error: None,
+                },
+                Err(e) => AppAsyncEvent::ReminderAdded {
+                    id: None,
+                    error: Some(App::actionable_error(&e)),
+                },
+            }
+        });
+    }
+
+    pub(super) fn open_reminders(&mut self, select: Option<String>) {
+        let Some(token) = self.active_token() else {
+            self.report_error("Can't list reminders", "no workspace is signed in");
+            return;
+        };
+        self.reminders = Some(RemindersPopup {
+            loading: true,
+            ..Default::default()
+        });
+        let api = self.slack_api.clone();
+        self.spawn_app_task(async move {
+            match api.list_reminders(&token).await {
+                Ok(reminders) => AppAsyncEvent::RemindersLoaded {
+                    reminders,
+                    select,
+                    error: None,
+                },
+                Err(e) => AppAsyncEvent::RemindersLoaded {
+                    reminders: Vec::new(),
+                    select: None,
+                    error: Some(App::actionable_error(&e)),
+                },
+            }
+        });
+    }
+
+    /// Pending reminders, soonest first; recurring ones last.
+    pub(super) fn show_reminders(
+        &mut self,
+        mut reminders: Vec<slack_zc_slack::types::Reminder>,
+        select: Option<String>,
+    ) {
+        let Some(popup) = self.reminders.as_mut() else {
+            return;
+        };
+        reminders.retain(|r| !r.complete);
+        reminders.sort_by_key(|r| (r.time.is_none(), r.time));
+        popup.selected = select
+            .and_then(|id| reminders.iter().position(|r| r.id == id))
+            .unwrap_or(0);
+        popup.reminders = reminders;
+        popup.loading = false;
+    }
+
+    pub(super) fn delete_selected_reminder(&mut self) {
+        let Some(id) = self
+            .reminders
+            .as_ref()
+            .and_then(|popup| popup.reminders.get(popup.selected))
+            .map(|r| r.id.clone())
+        else {
+            return;
+        };
+        let Some(token) = self.active_token() else {
+            return;
+        };
+        let api = self.slack_api.clone();
+        self.spawn_app_task(async move {
+            let error = api
+                .delete_reminder(&token, &id)
+                .await
+                .err()
+                .map(|e| App::actionable_error(&e));
+            AppAsyncEvent::ReminderDeleted { id, error }
+        });
+    }
+
+    /// Re-fetches the active workspace's saved messages, which back the
+    /// Saved sidebar entry and the bookmark glyph on messages.
+    pub(super) fn refresh_saved(&mut self) {
+        let Some(ws) = self.active_workspace() else {
+            return;
+        };
+        let team_id = ws.workspace.team_id.clone();
+        let token = ws.workspace.xoxp_token.clone();
+        let api = self.slack_api.clone();
+        self.spawn_app_task(async move {
+            match api.list_saved(&token).await {
+                Ok(items) => AppAsyncEvent::SavedLoaded {
+                    team_id,
+                    items,
+                    error: None,
+                },
+                Err(e) => AppAsyncEvent::SavedLoaded {
+                    team_id,
+                    items: Vec::new(),
+                    error: Some(App::actionable_error(&e)),
+                },
+            }
+        });
+    }
+
+    /// `S` / "Save for later": saves the message under the cursor, or
+    /// removes it when it is already saved. The local list changes at once;
+    /// a failed request re-fetches it.
+    pub(super) fn toggle_saved_selected(&mut self) {
+        let Some(channel_id) = self.get_active_channel_id() else {
+            return;
+        };
+        let Some(message) = self.selected_or_cursor_message().cloned() else {
+            return;
+        };
+        let Some(token) = self.active_token() else {
+            return;
+        };
+        let ts = message.ts.clone();
+        let saved = self.is_saved(&channel_id, &ts);
+        if saved {
+            self.saved_items
+                .retain(|item| !(item.channel_id == channel_id && item.message.ts == ts));
+        } else {
+            self.saved_items.insert(
+                0,
+                SavedItem {
+                    channel_id: channel_id.clone(),
+                    message,
+                },
+            );
+        }
+        let api = self.slack_api.clone();
+        self.spawn_app_task(async move {
+            let result = if saved {
+                api.unsave_message(&token, &channel_id, &ts).await
+            } else {
+                api.save_message(&token, &channel_id, &ts).await
+            };
+            AppAsyncEvent::SavedToggled {
+                error: result.err().map(|e| App::actionable_error(&e)),
+            }
+        });
+    }
+
+    pub(super) fn open_saved_view(&mut self) {
+        self.saved_view = Some(SavedView::default());
+        self.refresh_saved();
+    }
+
+    /// `u` in the Saved view.
+    pub(super) fn unsave_selected_saved(&mut self) {
+        let Some(view) = self.saved_view.as_mut() else {
+            return;
+        };
+        if view.selected >= self.saved_items.len() {
+            return;
+        }
+        let item = self.saved_items.remove(view.selected);
+        view.selected = view.selected.min(self.saved_items.len().saturating_sub(1));
+        let Some(token) = self.active_token() else {
+            return;
+        };
+        let api = self.slack_api.clone();
+        self.spawn_app_task(async move {
+            let error = api
+                .unsave_message(&token, &item.channel_id, &item.message.ts)
+                .await
+                .err()
+                .map(|e| App::actionable_error(&e));
+            AppAsyncEvent::SavedToggled { error }
+        });
+    }
+
+    /// Enter in the Saved view: opens the message's channel; the message is
+    /// selected once the channel's history arrives.
+    pub(super) fn jump_to_saved(&mut self) {
+        let Some(item) = self
+            .saved_view
+            .as_ref()
+            .and_then(|view| self.saved_items.get(view.selected))
+            .cloned()
+        else {
+            return;
+        };
+        let Some(idx) = self.channels.iter().position(|ch| ch.id == item.channel_id) else {
+            self.report_error(
+                "Can't open saved message",
+                "its channel is not in this workspace's channel list",
+            );
+            return;
+        };
+        self.saved_view = None;
+        self.sidebar_cursor = idx;
+        self.sidebar_pinned = None;
+        self.pending_jump = Some(JumpTarget {
+            channel_id: item.channel_id.clone(),
+            ts: item.message.ts.clone(),
+            open_thread: false,
+        });
+        self.select_channel(&item.channel_id);
+        self.focus = Focus::Messages;
+    }
+
+    pub(super) fn open_threads_view(&mut self) {
+        self.threads_view = Some(ThreadsView::default());
+    }
+
+    /// Enter in the Threads view: opens the thread's channel, then the
+    /// thread itself once the parent is loaded.
+    pub(super) fn jump_to_thread(&mut self) {
+        let Some(thread) = self.threads_view.as_ref().and_then(|view| {
+            self.active_workspace()?
+                .workspace
+                .threads
+                .get(view.selected)
+                .cloned()
+        }) else {
+            return;
+        };
+        let Some(idx) = self
+            .channels
+            .iter()
+            .position(|ch| ch.id == thread.channel_id)
+        else {
+            self.report_error(
+                "Can't open thread",
+                "its channel is not in this workspace's channel list",
+            );
+            return;
+        };
+        self.threads_view = None;
+        self.sidebar_cursor = idx;
+        self.sidebar_pinned = None;
+        self.pending_jump = Some(JumpTarget {
+            channel_id: thread.channel_id.clone(),
+            ts: thread.thread_ts.clone(),
+            open_thread: true,
+        });
+        self.select_channel(&thread.channel_id);
+        self.focus = Focus::Messages;
+    }
+
+    /// Enter in the agent history: opens the channel the selected command ran
+    /// in, and its thread when it had one.
+    pub(super) fn jump_to_agent_response(&mut self) {
+        let Some(entry) = self.agent_history.take().and_then(|popup| {
+            let selected = popup.selected;
+            popup.matches().get(selected).map(|entry| (*entry).clone())
+        }) else {
+            return;
+        };
+        let Some(channel_id) = entry.channel_id else {
+            return;
+        };
+        let Some(idx) = self.channels.iter().position(|ch| ch.id == channel_id) else {
+            self.report_error(
+                "Can't open the response's channel",
+                "it is not in this workspace's channel list",
+            );
+            return;
+        };
+        self.sidebar_cursor = idx;
+        self.sidebar_pinned = None;
+        if let Some(thread_ts) = entry.thread_ts {
+            self.pending_jump = Some(JumpTarget {
+                channel_id: channel_id.clone(),
+                ts: thread_ts,
+                open_thread: true,
+            });
+        }
+        self.select_channel(&channel_id);
+        self.focus = Focus::Messages;
+    }
+
+    /// `u` in the Threads view: stops following the selected thread.
+    pub(super) fn unfollow_selected_thread(&mut self) {
+        let Some(selected) = self.threads_view.as_ref().map(|view| view.selected) else {
+            return;
+        };
+        let Some(ws) = self.active_workspace_mut() else {
+            return;
+        };
+        if selected >= ws.workspace.threads.len() {
+            return;
+        }
+        ws.workspace.threads.remove(selected);
+        let remaining = ws.workspace.threads.len();
+        let team_id = ws.workspace.team_id.clone();
+        if let Some(view) = self.threads_view.as_mut() {
+            view.selected = selected.min(remaining.saturating_sub(1));
+        }
+        self.save_followed_threads(&team_id);
+    }
+
+    /// Selects the message a jump is waiting on and flashes it, loading the
+    /// conversation around it when it is older than the latest page.
+    pub(super) fn finish_jump(&mut self) {
+        let Some(target) = self.pending_jump.take() else {
+            return;
+        };
+        let loaded = self
+            .messages
+            .get(&target.channel_id)
+            .is_some_and(|messages| messages.iter().any(|m| m.ts == target.ts));
+        if !loaded {
+            self.fetch_context(target);
+            return;
+        }
+        if target.open_thread {
+            self.open_thread(&target.channel_id, &target.ts);
+        }
+        self.context_anchor = Some(ContextAnchor {
+            channel_id: target.channel_id.clone(),
+            ts: target.ts.clone(),
+            highlight_until: Instant::now() + CONTEXT_HIGHLIGHT,
+        });
+        self.select_message_by_ts(target.channel_id, target.ts);
+    }
+
+    /// Loads the messages on both sides of a jump target.
+    fn fetch_context(&mut self, target: JumpTarget) {
+        let Some(token) = self
+            .active_workspace()
+            .map(|ws| ws.workspace.xoxp_token.clone())
+        else {
+            return;
+        };
+        let api = self.slack_api.clone();
+        self.spawn_app_task_as(TaskKind::History, async move {
+            match api
+                .get_history_around(
+                    &token,
+                    &target.channel_id,
+                    &target.ts,
+                    CONTEXT_BEFORE,
+                    CONTEXT_AFTER,
+                )
+                .await
+            {
+                Ok(messages) => AppAsyncEvent::ContextLoaded {
+                    target,
+                    messages,
+                    error: None,
+                },
+                Err(e) => AppAsyncEvent::ContextLoaded {
+                    target,
+                    messages: Vec::new(),
+                    error: Some(App::actionable_error(&e)),
+                },
+            }
+        });
+    }
+
+    /// Merges a context window and selects its anchor. When the window stops
+    /// short of the present, scrolling down past it pages in the rest.
+    pub(super) fn merge_context(&mut self, target: JumpTarget, messages: Vec<Message>) {
+        if !messages.iter().any(|m| m.ts == target.ts) {
+            self.report_error("Can't open message", "it is no longer in the channel");
+            return;
+        }
+        let newer = messages.iter().filter(|m| m.ts > target.ts).count();
+        let last_ts = messages.last().map(|m| m.ts.clone());
+        self.merge_messages(&target.channel_id, messages);
+        match last_ts {
+            Some(ts) if newer >= CONTEXT_AFTER as usize => {
+                self.newer_history.insert(target.channel_id.clone(), ts);
+            }
+            _ => {
+                self.newer_history.remove(&target.channel_id);
+            }
+        }
+        self.pending_jump = Some(target);
+        self.finish_jump();
+    }
+
+    /// Fetches the page after the part of a context window that is loaded.
+    pub(super) fn fetch_newer_history(&mut self, channel_id: &str) {
+        // Taken while the page is in flight so scrolling doesn't refetch it.
+        let Some(after_ts) = self.newer_history.remove(channel_id) else {
+            return;
+        };
+        let Some(token) = self
+            .active_workspace()
+            .map(|ws| ws.workspace.xoxp_token.clone())
+        else {
+            return;
+        };
+        let channel_id = channel_id.to_string();
+        let api = self.slack_api.clone();
+        self.spawn_app_task_as(TaskKind::History, async move {
+            match api
+                .get_history_around(&token, &channel_id, &after_ts, 0, CONTEXT_AFTER)
+                .await
+            {
+                Ok(messages) => AppAsyncEvent::NewerHistoryLoaded {
+                    channel_id,
+                    after_ts,
+                    messages,
+                    error: None,
+                },
+                Err(e) => AppAsyncEvent::NewerHistoryLoaded {
+                    channel_id,
+                    after_ts,
+                    messages: Vec::new(),
+                    error: Some(App::actionable_error(&e)),
+                },
+            }
+        });
+    }
+
+    /// Adds a page below a context window. Reaching messages that were
+    /// already loaded, or a short page, means the gap is closed.
+    pub(super) fn merge_newer_history(
+        &mut self,
+        channel_id: &str,
+        after_ts: &str,
+        messages: Vec<Message>,
+    ) {
+        let page: Vec<Message> = messages
+            .into_iter()
+            .filter(|m| m.ts.as_str() > after_ts)
+            .collect();
+        let fetched = page.len();
+        let last_ts = page.last().map(|m| m.ts.clone());
+        let added = self.merge_messages(channel_id, page);
+        if let Some(ts) = last_ts.filter(|_| added == fetched && fetched >= CONTEXT_AFTER as usize)
+        {
+            self.newer_history.insert(channel_id.to_string(), ts);
+        }
+        self.trim_channel_history(channel_id);
+    }
+
+    /// Follows the thread of a reply the user is sending.
+    fn subscribe_thread(&mut self, send: &PendingSend) {
+        let Some(thread_ts) = send.thread_ts.as_deref() else {
+            return;
+        };
+        // The reply's own ts is not known yet; its echo on the socket
+        // replaces this one.
+        let ts = format!("{}.000000", Utc::now().timestamp());
+        let Some(ws) = self
+            .workspaces
+            .iter_mut()
+            .find(|ws| ws.workspace.team_id == send.team_id)
+        else {
+            return;
+        };
+        ws.workspace
+            .subscribe_thread(&send.channel_id, thread_ts, &ts, &send.text);
+        self.save_followed_threads(&send.team_id);
+    }
+
+    /// Copies a workspace's followed threads into the session and saves it.
+    pub(super) fn save_followed_threads(&mut self, team_id: &str) {
+        let Some(threads) = self
+            .workspaces
+            .iter()
+            .find(|ws| ws.workspace.team_id == team_id)
+            .map(|ws| ws.workspace.threads.clone())
+        else {
+            return;
+        };
+        let Some(session) = self.session.as_mut() else {
+            return;
+        };
+        if let Some(saved) = session.workspaces.iter_mut().find(|w| w.team_id == team_id) {
+            saved.threads = threads;
+        }
+        if let Err(e) = session.save() {
+            tracing::error!("Failed to save followed threads: {}", e);
+        }
+    }
+
+    pub(super) fn open_agent_history(&mut self) {
+        self.agent_history = Some(AgentHistoryPopup {
+            entries: self.agent_responses.iter().cloned().collect(),
+            loading: true,
+            ..Default::default()
+        });
+        self.spawn_app_task(async move {
+            match tokio::task::spawn_blocking(crate::history::load_all).await {
+                Ok(Ok(entries)) => AppAsyncEvent::AgentHistoryLoaded {
+                    entries,
+                    error: None,
+                },
+                Ok(Err(e)) => AppAsyncEvent::AgentHistoryLoaded {
+                    entries: Vec::new(),
+                    error: Some(e.to_string()),
+                },
+                Err(e) => AppAsyncEvent::AgentHistoryLoaded {
+                    entries: Vec::new(),
+                    error: Some(e.to_string()),
+                },
+            }
+        });
+    }
+
+    /// Opens the connection status overlay and times a fresh gateway
+    /// health check for it.
+    pub(super) fn open_connection_status(&mut self) {
+        let gateway = self
+            .agent_runner
+            .as_ref()
+            .and_then(|runner| runner.get_gateway().cloned());
+        let Some(gateway) = gateway else {
+            self.connection_status = Some(ConnectionStatusPanel {
+                gateway_check: Some(Err("no gateway connected".to_string())),
+            });
+            return;
+        };
+        self.connection_status = Some(ConnectionStatusPanel::default());
+        self.spawn_app_task(async move {
+            let started = Instant::now();
+            let result = match gateway.health_check().await {
+                Ok(true) => Ok(started.elapsed()),
+                Ok(false) => Err("gateway reports unhealthy".to_string()),
+                Err(e) => Err(App::actionable_error(&e)),
+            };
+            AppAsyncEvent::GatewayHealthChecked { result }
+        });
+    }
+
+    pub(super) fn dispatch_confirmed_command(&mut self, dialog: &ConfirmationDialog) -> Result<()> {
+        let mut command_text = dialog.command.clone();
+        if !dialog.prompt.is_empty() {
+            command_text.push(' ');
+            command_text.push_str(dialog.prompt.trim());
+        }
+        if let Some(channel) = &dialog.context_channel {
+            command_text.push(' ');
+            command_text.push('#');
+            command_text.push_str(channel);
+        }
+
+        self.run_agent_command(
+            command_text.trim(),
+            Some(dialog.chosen_target()),
+            dialog.rerun,
+        )
+    }
+
+    pub(super) fn fetch_channel_history(&mut self, channel_id: &str) -> Result<()> {
+        if let Some(ws) = self.active_workspace() {
+            let token = ws.workspace.xoxp_token.clone();
+            let channel_id = channel_id.to_string();
+            let api = self.slack_api.clone();
+            self.spawn_view_task(async move {
+                match api.get_history(&token, &channel_id, 50).await {
+                    Ok(messages) => AppAsyncEvent::ChannelHistoryLoaded {
+                        channel_id,
+                        messages,
+                        error: None,
+                    },
+                    Err(e) => AppAsyncEvent::ChannelHistoryLoaded {
+                        channel_id,
+                        messages: Vec::new(),
+                        error: Some(App::actionable_error(&e)),
+                    },
+                }
+            });
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn insert_channel_reference(&mut self, channel_name: &str, trigger_position: usize) {
+        if trigger_position >= self.input.buffer.len() {
+            return;
+        }
+
+        let replacement = format!("#{} ", channel_name);
+        let replace_end = trigger_position
+            .saturating_add(1)
+            .min(self.input.buffer.len());
+        self.input
+            .buffer
+            .replace_range(trigger_position..replace_end, &replacement);
+    }
+
+    fn extract_context_channel(prompt: &str) -> (String, Option<String>) {
+        let mut context_channel = None;
+        let filtered_parts: Vec<&str> = prompt
+            .split_whitespace()
+            .filter(|part| {
+                if context_channel.is_none() && part.starts_with('#') && part.len() > 1 {
+                    context_channel = Some(part.trim_start_matches('#').to_string());
+                    false
+                } else {
+                    true
+                }
+            })
+            .collect();
+
+        (filtered_parts.join(" "), context_channel)
+    }
+
+    fn execute_agent_command(&mut self, text: &str) -> Result<()> {
+        self.run_agent_command(text, None, false)
+    }
+
+    /// `chosen` is the target picked in the confirmation dialog and whether
+    /// to post there; without it the reply follows the current thread and
+    /// `zeroclaw.post_to_slack`.
+    fn run_agent_command(
+        &mut self,
+        text: &str,
+        chosen: Option<(Option<PostTarget>, bool)>,
+        rerun: bool,
+    ) -> Result<()> {
+        use slack_zc_agent::commands::{process_command, CommandType};
+
+        let (cmd_name, args) = match process_command(text) {
+            Some((cmd, args)) => (cmd, args),
+            None => return Ok(()),
+        };
+
+        let command = match CommandType::from_command(&cmd_name, &args)
+            .and_then(|command| command.resolve_thread(self.selected_thread_ts()))
+            .and_then(|command| {
+                command.resolve_translation(
+                    &self.config.zeroclaw.translate_target,
+                    self.selected_or_cursor_message()
+                        .map(|message| message.text.clone()),
+                )
+            }) {
+            Ok(command) => command,
+            Err(e) => {
+                self.report_error("Agent command", e);
+                return Ok(());
+            }
+        };
+        let translation_ts = match &command {
+            CommandType::Translate { .. } => self
+                .selected_or_cursor_message()
+                .map(|message| message.ts.clone()),
+            _ => None,
+        };
+        let tldr_thread = match &command {
+            CommandType::Tldr { thread_ts } => thread_ts.clone(),
+            _ => None,
+        };
+        let channel_id = self.get_active_channel_id().unwrap_or_default();
+        let channel_name = self
+            .selected_channel()
+            .map(|ch| ch.name.clone())
+            .unwrap_or_else(|| channel_id.clone());
+        let user_id = self
+            .active_workspace()
+            .and_then(|ws| ws.workspace.user_id.clone())
+            .unwrap_or_else(|| "UNKNOWN_USER".to_string());
+        let (history_limits, timeout_secs) = match command {
+            CommandType::Resume { .. } => ((12, 220), self.config.zeroclaw.timeout_seconds.max(60)),
+            CommandType::Search { .. } => ((12, 260), self.config.zeroclaw.timeout_seconds.max(45)),
+            _ => ((16, 280), self.config.zeroclaw.timeout_seconds),
+        };
+        let context_messages = self
+            .config
+            .zeroclaw
+            .share_context
+            .then_some(self.config.zeroclaw.context_messages);
+        // `/resume #other` needs that channel's history, and a time range
+        // needs the messages in that window; both are fetched in the task below.
+        let window = match &command {
+            CommandType::Resume { since, until, .. } if since.is_some() || until.is_some() => {
+                let slack_ts =
+                    |at: &chrono::DateTime<chrono::Utc>| format!("{}.000000", at.timestamp());
+                Some((since.as_ref().map(slack_ts), until.as_ref().map(slack_ts)))
+            }
+            _ => None,
+        };
+        let remote_channel = match (&command, context_messages) {
+            (CommandType::Resume { channel, .. }, Some(_)) => {
+                match channel.as_ref().filter(|name| **name != channel_name) {
+                    Some(name) => self
+                        .channels
+                        .iter()
+                        .find(|ch| ch.name == *name && ch.id != channel_id)
+                        .map(|ch| (ch.id.clone(), ch.name.clone())),
+                    None => window
+                        .is_some()
+                        .then(|| (channel_id.clone(), channel_name.clone())),
+                }
+            }
+            _ => None,
+        };
+        let loaded: Vec<&Message> = self
+            .messages
+            .get(&channel_id)
+            .map(|messages| messages.iter().collect())
+            .unwrap_or_default();
+        let payload = if matches!(command, CommandType::Translate { .. }) {
+            // A translation only needs the message itself, not the channel.
+            command.to_webhook_payload(&channel_name, "", &user_id, None)
+        } else {
+            Self::build_agent_payload(
+                &command,
+                &channel_name,
+                &user_id,
+                &loaded,
+                history_limits,
+                context_messages,
+            )
+        };
+        tracing::info!(
+            "Dispatching agent command {} for channel {} ({})",
+            cmd_name,
+            channel_name,
+            channel_id
+        );
+
+        if let Some(ref mut runner) = self.agent_runner {
+            if let Some(gateway) = runner.get_gateway().cloned() {
+                // The HTTP layer gets a small grace period so our own timeout
+                // fires first and produces the actionable message below.
+                let gateway = gateway
+                    .with_request_timeout(Duration::from_secs(timeout_secs.saturating_add(2)));
+                self.agent_processing = true;
+                let command_text = text.to_string();
+                self.loading_start_time = Some(Instant::now());
+                self.loading_command = Some(command_text.clone());
+                self.loading_rerun = rerun;
+                self.loading_timeout_secs = Some(timeout_secs);
+                self.pending_translation = translation_ts.clone();
+                self.pending_draft = matches!(command, CommandType::Draft { .. });
+                // Translations and drafts stay local; they are never posted
+                // back to Slack.
+                // Everything the reply needs is captured now: the task must not
+                // look at `App` state that may change while the agent works.
+                // A thread summary always goes back into its thread.
+                let (target, post_chosen) = match chosen {
+                    Some((target, post)) => (target, post),
+                    None => (self.post_target(tldr_thread.clone()), true),
+                };
+                let post_to_slack = self.config.zeroclaw.post_to_slack
+                    && post_chosen
+                    && translation_ts.is_none()
+                    && !self.pending_draft;
                 let api = self.slack_api.clone();
-                self.spawn_app_task(async move {
-                    let response = match timeout(
-                        Duration::from_secs(timeout_secs),
-                        gateway.send_to_agent(&payload),
-                    )
-                    .await
-                    {
+                let app_async_tx = self.app_async_tx.clone();
+                let origin_channel = target.as_ref().map(|t| t.channel_id.clone());
+                let origin_name = target.as_ref().map(|t| t.channel_name.clone());
+                let origin_thread = target.as_ref().and_then(|t| t.thread_ts.clone());
+                self.spawn_app_task_as(TaskKind::Agent, async move {
+                    let payload = match (tldr_thread, target.as_ref()) {
+                        (Some(parent_ts), Some(target)) => {
+                            match api
+                                .get_thread_replies(&target.token, &target.channel_id, &parent_ts)
+                                .await
+                            {
+                                Ok(mut thread) => {
+                                    thread.sort_by(|a, b| a.ts.cmp(&b.ts));
+                                    let thread = thread
+                                        .iter()
+                                        .filter(|message| !message.is_deleted)
+                                        .map(App::agent_context_message)
+                                        .collect::<Vec<_>>();
+                                    command.to_thread_payload(&channel_name, &user_id, &thread)
+                                }
+                                Err(e) => {
+                                    return AppAsyncEvent::AgentCommandFinished {
+                                        command: command_text,
+                                        channel_id: origin_channel,
+                                        channel_name: origin_name,
+                                        thread_ts: origin_thread,
+                                        response: None,
+                                        error: Some(format!(
+                                            "Could not load thread replies for /tldr: {}\n\nPress R to retry",
+                                            App::actionable_error(&e)
+                                        )),
+                                    }
+                                }
+                            }
+                        }
+                        _ => payload,
+                    };
+                    let payload = match (remote_channel, target.as_ref()) {
+                        (Some((remote_id, remote_name)), Some(target)) => {
+                            let limit = context_messages.unwrap_or_default().max(history_limits.0) as u32;
+                            let fetched = match &window {
+                                Some((oldest, latest)) => {
+                                    api.get_history_between(
+                                        &target.token,
+                                        &remote_id,
+                                        oldest.as_deref(),
+                                        latest.as_deref(),
+                                        limit,
+                                    )
+                                    .await
+                                }
+                                None => api.get_history(&target.token, &remote_id, limit).await,
+                            };
+                            match fetched {
+                                Ok(mut fetched) => {
+                                    fetched.sort_by(|a, b| a.ts.cmp(&b.ts));
+                                    let fetched: Vec<&Message> = fetched.iter().collect();
+                                    App::build_agent_payload(
+                                        &command,
+                                        &remote_name,
+                                        &user_id,
+                                        &fetched,
+                                        history_limits,
+                                        context_messages,
+                                    )
+                                }
+                                Err(e) => {
+                                    return AppAsyncEvent::AgentCommandFinished {
+                                        command: command_text,
+                                        channel_id: origin_channel,
+                                        channel_name: origin_name,
+                                        thread_ts: origin_thread,
+                                        response: None,
+                                        error: Some(format!(
+                                            "Could not load #{} history for the agent: {}\n\nPress R to retry",
+                                            remote_name, e
+                                        )),
+                                    }
+                                }
+                            }
+                        }
+                        _ => payload,
+                    };
+                    let streamed = timeout(Duration::from_secs(timeout_secs), async {
+                        use futures::StreamExt;
+
+                        let mut deltas = std::pin::pin!(gateway.send_to_agent_streaming(&payload));
+                        let mut response = String::new();
+                        while let Some(delta) = deltas.next().await {
+                            let delta = delta?;
+                            response.push_str(&delta);
+                            if let Some(ref tx) = app_async_tx {
+                                let _ = App::send_app_event(
+                                    tx,
+                                    AppAsyncEvent::AgentCommandChunk {
+                                        command: command_text.clone(),
+                                        delta,
+                                    },
+                                );
+                            }
+                        }
+                        Ok::<_, anyhow::Error>(response)
+                    })
+                    .await;
+
+                    let response = match streamed {
                         Ok(Ok(text)) => text,
                         Ok(Err(e)) => {
+                            if matches!(
+                                e.downcast_ref::<slack_zc_agent::GatewayError>(),
+                                Some(slack_zc_agent::GatewayError::BearerExpired)
+                            ) {
+                                if let Some(ref tx) = app_async_tx {
+                                    let _ = App::send_app_event(
+                                        tx,
+                                        AppAsyncEvent::ZeroClawBearerRejected,
+                                    );
+                                }
+                            }
                             return AppAsyncEvent::AgentCommandFinished {
                                 command: command_text,
+                                channel_id: origin_channel,
+                                channel_name: origin_name,
+                                thread_ts: origin_thread,
                                 response: None,
                                 error: Some(format!(
                                     "Agent command failed after {}s: {}\n\nPress R to retry",
@@ -304,9 +2410,12 @@ impl App {
                         Err(_) => {
                             return AppAsyncEvent::AgentCommandFinished {
                                 command: command_text,
+                                channel_id: origin_channel,
+                                channel_name: origin_name,
+                                thread_ts: origin_thread,
                                 response: None,
                                 error: Some(format!(
-                                    "Agent command timed out after {}s.\n\n\
+                                    "Agent command timed out after {}s — increase zeroclaw.timeout_seconds.\n\n\
                                          What was tried: Gateway webhook call to agent\n\
                                          Suggestions: Check agent status, try again\n\n\
                                          Press R to retry",
@@ -317,7 +2426,13 @@ impl App {
                     };
 
                     if post_to_slack {
-                        if let (Some(channel_id), Some(xoxp_token)) = (channel, token) {
+                        if let Some(PostTarget {
+                            channel_id,
+                            token: xoxp_token,
+                            thread_ts,
+                            ..
+                        }) = target
+                        {
                             let post_result = if let Some(ts) = thread_ts {
                                 api.send_message_to_thread(&xoxp_token, &channel_id, &response, &ts)
                                     .await
@@ -332,6 +2447,9 @@ impl App {
                                 );
                                 return AppAsyncEvent::AgentCommandFinished {
                                     command: command_text,
+                                    channel_id: origin_channel,
+                                    channel_name: origin_name,
+                                    thread_ts: origin_thread,
                                     response: None,
                                     error: Some(format!(
                                         "Failed to post agent response: {}",
@@ -353,6 +2471,9 @@ impl App {
                     tracing::info!("Agent command completed successfully");
                     AppAsyncEvent::AgentCommandFinished {
                         command: command_text,
+                        channel_id: origin_channel,
+                        channel_name: origin_name,
+                        thread_ts: origin_thread,
                         response: Some(response),
                         error: None,
                     }
@@ -365,21 +2486,151 @@ impl App {
         Ok(())
     }
 
+    /// Posts an `@zeroclaw` message as written, then asks the agent and
+    /// threads its reply under that message.
+    fn execute_agent_mention(&mut self, text: String) {
+        use slack_zc_agent::commands::{strip_agent_mention, CommandType};
+
+        let gateway = self
+            .agent_runner
+            .as_ref()
+            .and_then(|runner| runner.get_gateway().cloned());
+        let (Some(gateway), Some(target)) = (gateway, self.post_target(None)) else {
+            if let Some(send) = self.pending_send_here(text) {
+                self.dispatch_send(send, "Failed to send mention");
+            }
+            return;
+        };
+        let command = CommandType::Mention {
+            text: strip_agent_mention(&text),
+        };
+        let user_id = self
+            .current_user_id()
+            .unwrap_or_else(|| "UNKNOWN_USER".to_string());
+        let loaded: Vec<&Message> = self
+            .messages
+            .get(&target.channel_id)
+            .map(|messages| messages.iter().collect())
+            .unwrap_or_default();
+        let context_messages = self
+            .config
+            .zeroclaw
+            .share_context
+            .then_some(self.config.zeroclaw.context_messages);
+        let payload = Self::build_agent_payload(
+            &command,
+            &target.channel_name,
+            &user_id,
+            &loaded,
+            (12, 220),
+            context_messages,
+        );
+        let timeout_secs = self.config.zeroclaw.timeout_seconds;
+        let gateway =
+            gateway.with_request_timeout(Duration::from_secs(timeout_secs.saturating_add(2)));
+        self.agent_processing = true;
+        self.loading_start_time = Some(Instant::now());
+        self.loading_command = Some(text.clone());
+        self.loading_rerun = false;
+        self.loading_timeout_secs = Some(timeout_secs);
+        tracing::info!("Routing agent mention in channel {}", target.channel_id);
+
+        let api = self.slack_api.clone();
+        let app_async_tx = self.app_async_tx.clone();
+        self.spawn_app_task_as(TaskKind::Agent, async move {
+            let finished = |response: Option<String>, error: Option<String>| {
+                AppAsyncEvent::AgentCommandFinished {
+                    command: text.clone(),
+                    channel_id: Some(target.channel_id.clone()),
+                    channel_name: Some(target.channel_name.clone()),
+                    thread_ts: target.thread_ts.clone(),
+                    response,
+                    error,
+                }
+            };
+            // The user's message goes out first; the reply threads under it,
+            // or under the open thread's parent when replying in one.
+            let posted = match target.thread_ts.as_deref() {
+                Some(ts) => {
+                    api.send_message_to_thread(&target.token, &target.channel_id, &text, ts)
+                        .await
+                }
+                None => api.send_message(&target.token, &target.channel_id, &text).await,
+            };
+            let reply_ts = match posted {
+                Ok(ts) => target.thread_ts.clone().unwrap_or(ts),
+                Err(e) => {
+                    return finished(
+                        None,
+                        Some(format!(
+                            "Failed to send mention: {}",
+                            App::actionable_error(&e)
+                        )),
+                    )
+                }
+            };
+
+            let response =
+                match timeout(Duration::from_secs(timeout_secs), gateway.send_to_agent(&payload))
+                    .await
+                {
+                    Ok(Ok(response)) => response,
+                    Ok(Err(e)) => {
+                        if matches!(
+                            e.downcast_ref::<slack_zc_agent::GatewayError>(),
+                            Some(slack_zc_agent::GatewayError::BearerExpired)
+                        ) {
+                            if let Some(ref tx) = app_async_tx {
+                                let _ = App::send_app_event(tx, AppAsyncEvent::ZeroClawBearerRejected);
+                            }
+                        }
+                        return finished(
+                            None,
+                            Some(format!(
+                                "Your message was posted, but ZeroClaw did not answer: {}",
+                                App::actionable_error(&e)
+                            )),
+                        );
+                    }
+                    Err(_) => {
+                        return finished(
+                            None,
+                            Some(format!(
+                                "Your message was posted, but ZeroClaw did not answer within {}s — increase zeroclaw.timeout_seconds.",
+                                timeout_secs
+                            )),
+                        )
+                    }
+                };
+
+            if let Err(e) = api
+                .send_message_to_thread(&target.token, &target.channel_id, &response, &reply_ts)
+                .await
+            {
+                return finished(
+                    Some(response),
+                    Some(format!(
+                        "Failed to post agent reply: {}",
+                        App::actionable_error(&e)
+                    )),
+                );
+            }
+            finished(Some(response), None)
+        });
+    }
+
     fn build_agent_history_context(
-        &self,
-        channel_id: &str,
+        messages: &[&Message],
         max_messages: usize,
         max_chars: usize,
     ) -> String {
-        let Some(messages) = self.messages.get(channel_id) else {
-            return "No recent Slack messages are loaded for this channel yet.".to_string();
-        };
+        let start = messages.len().saturating_sub(max_messages);
 
         let mut lines = Vec::new();
-        for message in messages.iter().rev().take(max_messages).rev() {
+        for message in &messages[start..] {
             let mut text = message.text.trim().replace('\n', " ");
-            if text.len() > max_chars {
-                text.truncate(max_chars);
+            if text.chars().count() > max_chars {
+                text = text.chars().take(max_chars).collect();
                 text.push_str("...");
             }
             if text.is_empty() {
@@ -400,9 +2651,60 @@ impl App {
             lines.join("\n")
         }
     }
+
+    fn agent_context_message(message: &Message) -> slack_zc_agent::commands::ContextMessage {
+        slack_zc_agent::commands::ContextMessage {
+            ts: message.ts.clone(),
+            user: message.username.clone(),
+            text: message.text.clone(),
+            thread_ts: message.thread_ts.clone(),
+        }
+    }
+
+    fn build_agent_channel_context(
+        channel_name: &str,
+        messages: &[&Message],
+        max_messages: usize,
+    ) -> slack_zc_agent::commands::ChannelContext {
+        use slack_zc_agent::commands::{ChannelContext, MAX_CONTEXT_BYTES};
+
+        let start = messages.len().saturating_sub(max_messages);
+        let entries = messages[start..]
+            .iter()
+            .filter(|message| !message.is_deleted)
+            .map(|message| Self::agent_context_message(message))
+            .collect();
+        ChannelContext::new(channel_name, entries, MAX_CONTEXT_BYTES)
+    }
+
+    /// Builds the webhook payload for one channel. `context_messages` is
+    /// `None` when context sharing is disabled in the config.
+    fn build_agent_payload(
+        command: &slack_zc_agent::commands::CommandType,
+        channel_name: &str,
+        user_id: &str,
+        messages: &[&Message],
+        history_limits: (usize, usize),
+        context_messages: Option<usize>,
+    ) -> serde_json::Value {
+        let Some(context_messages) = context_messages else {
+            return command.to_webhook_payload(
+                channel_name,
+                "Channel context sharing is disabled in slack-zc settings.",
+                user_id,
+                None,
+            );
+        };
+
+        let (history_messages, history_chars) = history_limits;
+        let history = Self::build_agent_history_context(messages, history_messages, history_chars);
+        let context = (!messages.is_empty())
+            .then(|| Self::build_agent_channel_context(channel_name, messages, context_messages));
+        command.to_webhook_payload(channel_name, &history, user_id, context.as_ref())
+    }
+
     pub(super) fn get_active_channel_id(&self) -> Option<String> {
-        self.selected_channel
-            .and_then(|idx| self.channels.get(idx).map(|ch| ch.id.clone()))
+        self.selected_channel_id.clone()
     }
     pub(super) fn toggle_thread_collapse(&mut self, channel_id: &str) {
         if let Some(threads) = self.threads.get_mut(channel_id) {
@@ -412,152 +2714,427 @@ impl App {
         }
     }
 
+    pub(super) fn current_user_id(&self) -> Option<String> {
+        self.active_workspace()
+            .and_then(|ws| ws.workspace.user_id.clone())
+    }
+
+    /// The selected or cursor message, provided the current user wrote it.
+    fn own_target_message(&self, action: &str) -> Result<(String, Message)> {
+        let channel_id = self
+            .get_active_channel_id()
+            .ok_or_else(|| anyhow!("No channel selected"))?;
+        let msg = self
+            .selected_or_cursor_message()
+            .ok_or_else(|| anyhow!("No message selected"))?;
+        if self.current_user_id().as_ref() != Some(&msg.user_id) {
+            return Err(anyhow!(
+                "You can only {} your own messages — this one is from {}",
+                action,
+                msg.username
+            ));
+        }
+        if msg.is_unconfirmed() {
+            return Err(anyhow!("This message hasn't reached Slack yet"));
+        }
+        Ok((channel_id, msg.clone()))
+    }
+
+    fn begin_edit(&mut self, channel_id: String, msg: &Message) {
+        let saved_draft = match self.edit_message.take() {
+            Some(previous) => previous.saved_draft,
+            None => self.input.buffer.clone(),
+        };
+        self.edit_message = Some(EditState {
+            channel_id,
+            ts: msg.ts.clone(),
+            original_text: msg.text.clone(),
+            saved_draft,
+        });
+        self.input.set_text(msg.text.clone());
+    }
+
+    /// Puts an agent draft in the input for editing, stashing whatever was
+    /// typed there so Esc can bring it back.
+    pub(super) fn insert_agent_draft(&mut self, draft: &str) {
+        self.cancel_edit_message();
+        if !self.input.buffer.trim().is_empty() && self.stashed_input.is_none() {
+            self.stashed_input = Some(self.input.buffer.clone());
+        }
+        self.input.set_text(draft.trim().to_string());
+        self.focus = Focus::Input;
+    }
+
+    /// Leaves edit mode and puts the pre-edit draft back in the input.
+    pub(super) fn cancel_edit_message(&mut self) {
+        if let Some(edit_state) = self.edit_message.take() {
+            self.input.set_text(edit_state.saved_draft);
+        }
+    }
+
     pub(super) fn start_edit_message(&mut self) -> Result<()> {
-        if let Some(ref channel) = self.selected_channel {
-            if let Some(ch) = self.channels.get(*channel) {
-                if let Some(messages) = self.messages.get(&ch.id) {
-                    if let Some(msg) = messages.back() {
-                        let current_user = self
-                            .workspaces
-                            .get(self.active_workspace)
-                            .and_then(|ws| ws.workspace.user_id.clone());
-
-                        if current_user.as_ref() == Some(&msg.user_id) {
-                            self.edit_message = Some(EditState {
-                                channel_id: ch.id.clone(),
-                                ts: msg.ts.clone(),
-                                original_text: msg.text.clone(),
-                            });
-                            self.input.buffer = msg.text.clone();
-                        }
-                    }
+        match self.own_target_message("edit") {
+            Ok((channel_id, msg)) => self.begin_edit(channel_id, &msg),
+            Err(e) => self.report_error("Cannot edit message", e),
+        }
+        Ok(())
+    }
+
+    /// Opens the newest message the current user posted in this channel.
+    pub(super) fn start_edit_last_own_message(&mut self) {
+        let Some(channel_id) = self.get_active_channel_id() else {
+            return;
+        };
+        let Some(user_id) = self.current_user_id() else {
+            return;
+        };
+        let last_own = self.messages.get(&channel_id).and_then(|messages| {
+            messages
+                .iter()
+                .rev()
+                .find(|msg| msg.user_id == user_id && !msg.is_deleted && !msg.is_unconfirmed())
+                .cloned()
+        });
+        match last_own {
+            Some(msg) => self.begin_edit(channel_id, &msg),
+            None => self.report_error(
+                "Cannot edit message",
+                "you have no recent messages in this channel",
+            ),
+        }
+    }
+
+    pub(super) fn delete_selected_message(&mut self) -> Result<()> {
+        let (channel_id, msg) = match self.own_target_message("delete") {
+            Ok(target) => target,
+            Err(e) => {
+                self.report_error("Cannot delete message", e);
+                return Ok(());
+            }
+        };
+        if let Some(ws) = self.active_workspace() {
+            let ts = msg.ts;
+            let token = ws.workspace.xoxp_token.clone();
+            let api = self.slack_api.clone();
+            self.spawn_app_task_as(TaskKind::Send, async move {
+                let error = api
+                    .delete_message(&token, &channel_id, &ts)
+                    .await
+                    .err()
+                    .map(|e| App::actionable_error(&e));
+                AppAsyncEvent::SlackSendResult {
+                    context: "Failed to delete message".to_string(),
+                    channel_id: None,
+                    error,
+                    retry: None,
+                    recovery: None,
+                    local_id: None,
+                    ts: None,
+                }
+            });
+        }
+        Ok(())
+    }
+
+    /// Starts a reply that quotes the selected message as a Slack blockquote.
+    pub(super) fn quote_selected_message(&mut self) {
+        let Some(msg) = self.selected_or_cursor_message() else {
+            return;
+        };
+        let mut names: HashMap<String, String> = HashMap::new();
+        if let Some(messages) = self.current_channel_messages() {
+            for m in messages {
+                names.insert(m.user_id.clone(), m.username.clone());
+            }
+        }
+        if let Some(ws) = self.active_workspace() {
+            for (id, user) in &ws.users {
+                names.insert(id.clone(), user.display_name());
+            }
+        }
+        let quote = Self::format_quote(
+            &msg.text,
+            &msg.username,
+            self.config.ui.quote_max_lines,
+            &names,
+        );
+
+        let draft = self.input.buffer.trim().to_string();
+        if draft.is_empty() {
+            self.input.set_text(quote);
+        } else {
+            self.input.set_text(format!("{}{}", quote, draft));
+        }
+        self.focus = Focus::Input;
+    }
+
+    /// Renders `text` as a `> ` blockquote with an attribution line. Mentions
+    /// become plain `@name` so quoting does not ping anyone again.
+    pub(super) fn format_quote(
+        text: &str,
+        author: &str,
+        max_lines: usize,
+        names: &HashMap<String, String>,
+    ) -> String {
+        static MENTION_RE: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+        let re =
+            MENTION_RE.get_or_init(|| regex::Regex::new(r"<@([A-Z0-9]+)(?:\|([^>]*))?>").unwrap());
+        let plain = re.replace_all(text, |caps: &regex::Captures| {
+            let id = &caps[1];
+            let name = caps
+                .get(2)
+                .map(|m| m.as_str().to_string())
+                .filter(|name| !name.is_empty())
+                .or_else(|| names.get(id).cloned())
+                .unwrap_or_else(|| id.to_string());
+            format!("@{}", name)
+        });
+
+        let lines: Vec<&str> = plain.lines().collect();
+        let max_lines = max_lines.max(1);
+        let mut quote = String::new();
+        for line in lines.iter().take(max_lines) {
+            quote.push_str("> ");
+            quote.push_str(line);
+            quote.push('\n');
+        }
+        if lines.len() > max_lines {
+            quote.push_str("> …\n");
+        }
+        quote.push_str(&format!("> — @{}\n", author));
+        quote
+    }
+
+    pub(super) fn show_reaction_picker(&mut self) -> Result<()> {
+        let Some(channel_id) = self.get_active_channel_id() else {
+            return Ok(());
+        };
+        let Some(msg) = self.selected_or_cursor_message() else {
+            return Ok(());
+        };
+        let ts = msg.ts.clone();
+        // Reactions already on the message can be toggled from here too.
+        let on_message = msg.reactions.iter().map(|r| r.name.clone());
+        let mut names: Vec<String> = Vec::new();
+        for name in self
+            .recent_reactions()
+            .into_iter()
+            .chain(on_message)
+            .chain(REACTION_CATALOG.iter().map(|(_, name)| name.to_string()))
+        {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+
+        // Pin the target so the choice applies to this message even if the cursor moves.
+        self.selected_message = Some((channel_id, ts));
+        self.reaction_picker = Some(ReactionPicker {
+            names,
+            ..ReactionPicker::default()
+        });
+        Ok(())
+    }
+
+    /// The active workspace's most used reactions, topped up from the
+    /// catalog to at least the number-key shortcuts.
+    pub(super) fn recent_reactions(&self) -> Vec<String> {
+        let now = Utc::now().timestamp();
+        let mut names: Vec<String> = self
+            .active_workspace()
+            .map(|ws| {
+                ws.workspace
+                    .top_reactions(now)
+                    .into_iter()
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+        for (_, name) in REACTION_CATALOG {
+            if names.len() >= QUICK_REACTIONS {
+                break;
+            }
+            if !names.iter().any(|known| known == name) {
+                names.push(name.to_string());
+            }
+        }
+        names
+    }
+
+    /// Whether the current user has reacted with `name` to the picker's
+    /// message, for the ✓ beside it.
+    pub(super) fn has_own_reaction(&self, name: &str) -> bool {
+        let user_id = self.current_user_id();
+        self.selected_or_cursor_message().is_some_and(|msg| {
+            msg.reactions
+                .iter()
+                .any(|r| r.name == name && user_id.as_ref().is_some_and(|u| r.users.contains(u)))
+        })
+    }
+
+    /// Counts a reaction the user added in the workspace they added it
+    /// from, and saves it with the session.
+    pub(super) fn note_reaction_used(&mut self, user_id: &str, name: &str) {
+        let now = Utc::now().timestamp();
+        let Some(ws) = self
+            .workspaces
+            .iter_mut()
+            .find(|ws| ws.workspace.user_id.as_deref() == Some(user_id))
+        else {
+            return;
+        };
+        ws.workspace.note_reaction(name, now);
+        let team_id = ws.workspace.team_id.clone();
+        let recent = ws.workspace.recent_reactions.clone();
+        if let Some(session) = self.session.as_mut() {
+            if let Some(saved) = session.workspaces.iter_mut().find(|w| w.team_id == team_id) {
+                saved.recent_reactions = recent;
+            }
+            if let Err(e) = session.save() {
+                tracing::error!("Failed to save recent reactions: {}", e);
+            }
+        }
+    }
+
+    pub(super) fn copy_selected_message(&mut self) -> Result<()> {
+        if let Some(ch) = self.selected_channel() {
+            if let Some(messages) = self.messages.get(&ch.id) {
+                if let Some(msg) = messages.back() {
+                    let text = msg.text.clone();
+                    self.copy_to_clipboard(&text, "Failed to copy message to clipboard");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// `v`: anchors a selection on the message under the cursor.
+    pub(super) fn start_visual_selection(&mut self) {
+        let Some(channel_id) = self.get_active_channel_id() else {
+            return;
+        };
+        let anchor = self
+            .current_message_index()
+            .and_then(|idx| self.messages.get(&channel_id)?.get(idx))
+            .map(|m| m.ts.clone());
+        if let Some(ts) = anchor {
+            self.visual_selection = Some((channel_id, ts));
+        }
+    }
+
+    /// `y` in a selection: copies it and leaves visual mode.
+    pub(super) fn yank_visual_selection(&mut self) {
+        let count = self.visual_selection_messages().len();
+        let text = self.visual_selection_text();
+        self.visual_selection = None;
+        if count == 0 {
+            return;
+        }
+        if self.copy_to_clipboard(&text, "Failed to copy messages to clipboard") {
+            let plural = if count == 1 { "" } else { "s" };
+            self.report_notice(format!("Copied {count} message{plural}"));
+        }
+    }
+
+    /// Sanitized like anything drawn, then piped to the platform's
+    /// clipboard tool; true once it took the text.
+    fn copy_to_clipboard(&mut self, text: &str, context: &str) -> bool {
+        use std::io::Write;
+        use std::process::{Command, Stdio};
+
+        let clean = sanitize_text(text);
+        let clipped = if clean.chars().count() > 16_384 {
+            clean.chars().take(16_384).collect::<String>()
+        } else {
+            clean.into_owned()
+        };
+        let mut command = if cfg!(target_os = "macos") {
+            Command::new("pbcopy")
+        } else {
+            let mut xclip = Command::new("xclip");
+            xclip.args(["-selection", "clipboard", "-i"]);
+            xclip
+        };
+        let program = command.get_program().to_string_lossy().into_owned();
+        let result = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .and_then(|mut child| {
+                if let Some(mut stdin) = child.stdin.take() {
+                    stdin.write_all(clipped.as_bytes())?;
                 }
+                child.wait()
+            });
+        match result {
+            Ok(status) if status.success() => {
+                self.clear_error();
+                true
+            }
+            Ok(status) => {
+                self.report_error(context, format!("{program} exited with {status}"));
+                false
+            }
+            Err(e) => {
+                self.report_error(context, e);
+                false
             }
         }
-        Ok(())
     }
 
-    pub(super) fn delete_selected_message(&mut self) -> Result<()> {
-        if let Some(ref channel) = self.selected_channel {
-            if let Some(ch) = self.channels.get(*channel) {
-                if let Some(messages) = self.messages.get(&ch.id) {
-                    if let Some(msg) = messages.back() {
-                        let current_user = self
-                            .workspaces
-                            .get(self.active_workspace)
-                            .and_then(|ws| ws.workspace.user_id.clone());
-
-                        if current_user.as_ref() == Some(&msg.user_id) {
-                            if let Some(ws) = self.workspaces.get(self.active_workspace) {
-                                let channel_id = ch.id.clone();
-                                let ts = msg.ts.clone();
-                                let token = ws.workspace.xoxp_token.clone();
-                                let api = self.slack_api.clone();
-                                self.spawn_app_task(async move {
-                                    let error = api
-                                        .delete_message(&token, &channel_id, &ts)
-                                        .await
-                                        .err()
-                                        .map(|e| App::actionable_error(&e));
-                                    AppAsyncEvent::SlackSendResult {
-                                        context: "Failed to delete message".to_string(),
-                                        channel_id: None,
-                                        error,
-                                    }
-                                });
-                            }
-                        }
-                    }
-                }
-            }
+    /// `o`: opens the selected message's link, or lets the user pick one
+    /// when it has several.
+    pub(super) fn open_selected_message_links(&mut self) {
+        let Some(links) = self.selected_or_cursor_message().map(Self::message_links) else {
+            return;
+        };
+        match links.as_slice() {
+            [] => self.report_notice("No links in this message"),
+            [link] => self.open_link(&link.url),
+            _ => self.link_chooser = Some(LinkChooser { links, selected: 0 }),
         }
-        Ok(())
     }
 
-    pub(super) fn show_reaction_picker(&mut self) -> Result<()> {
-        self.context_menu = Some(ContextMenu {
-            x: 10,
-            y: 10,
-            items: vec![
-                ContextMenuItem {
-                    label: "👍 +1".to_string(),
-                    action: ContextMenuAction::React,
-                },
-                ContextMenuItem {
-                    label: "❤️ heart".to_string(),
-                    action: ContextMenuAction::React,
-                },
-                ContextMenuItem {
-                    label: "😄 laugh".to_string(),
-                    action: ContextMenuAction::React,
-                },
-                ContextMenuItem {
-                    label: "😮 wow".to_string(),
-                    action: ContextMenuAction::React,
-                },
-                ContextMenuItem {
-                    label: "😢 sad".to_string(),
-                    action: ContextMenuAction::React,
-                },
-                ContextMenuItem {
-                    label: "😡 angry".to_string(),
-                    action: ContextMenuAction::React,
-                },
-            ],
-            selected: 0,
+    pub(super) fn open_link(&mut self, url: &str) {
+        match crate::ui::links::open_in_browser(url) {
+            Ok(()) => self.report_notice(format!("Opening {}", url)),
+            Err(e) => self.report_error("Failed to open link", e),
+        }
+    }
+
+    /// Opens the original payload of the selected message, kept when
+    /// `[debug] keep_raw` is on.
+    pub(super) fn open_raw_inspector(&mut self) {
+        let Some((ts, raw)) = self
+            .selected_or_cursor_message()
+            .map(|msg| (msg.ts.clone(), msg.raw.clone()))
+        else {
+            return;
+        };
+        let Some(raw) = raw else {
+            let hint = if self.config.debug.keep_raw {
+                "it was loaded before keep_raw was on; reload the channel"
+            } else {
+                "set keep_raw = true under [debug] in the config"
+            };
+            self.report_error("No raw JSON for this message", hint);
+            return;
+        };
+        let json = serde_json::from_str::<serde_json::Value>(&raw)
+            .and_then(|value| serde_json::to_string_pretty(&value))
+            .unwrap_or(raw);
+        self.raw_inspector = Some(RawInspector {
+            ts,
+            json,
+            scroll: 0,
         });
-        Ok(())
     }
 
-    pub(super) fn copy_selected_message(&mut self) -> Result<()> {
-        if let Some(ref channel) = self.selected_channel {
-            if let Some(ch) = self.channels.get(*channel) {
-                if let Some(messages) = self.messages.get(&ch.id) {
-                    if let Some(msg) = messages.back() {
-                        let clipped = if msg.text.chars().count() > 16_384 {
-                            msg.text.chars().take(16_384).collect::<String>()
-                        } else {
-                            msg.text.clone()
-                        };
-                        #[cfg(target_os = "linux")]
-                        {
-                            let result = std::process::Command::new("xclip")
-                                .arg("-selection")
-                                .arg("clipboard")
-                                .arg("-i")
-                                .arg(&clipped)
-                                .output();
-                            match result {
-                                Ok(output) if output.status.success() => self.clear_error(),
-                                Ok(output) => self.report_error(
-                                    "Failed to copy message to clipboard",
-                                    format!("xclip exited with {}", output.status),
-                                ),
-                                Err(e) => {
-                                    self.report_error("Failed to copy message to clipboard", e)
-                                }
-                            }
-                        }
-                        #[cfg(target_os = "macos")]
-                        {
-                            let result =
-                                std::process::Command::new("pbcopy").arg(&clipped).output();
-                            match result {
-                                Ok(output) if output.status.success() => self.clear_error(),
-                                Ok(output) => self.report_error(
-                                    "Failed to copy message to clipboard",
-                                    format!("pbcopy exited with {}", output.status),
-                                ),
-                                Err(e) => {
-                                    self.report_error("Failed to copy message to clipboard", e)
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+    pub(super) fn copy_raw_json(&mut self) {
+        if let Some(json) = self.raw_inspector.as_ref().map(|i| i.json.clone()) {
+            self.copy_to_clipboard(&json, "Failed to copy JSON to clipboard");
         }
-        Ok(())
     }
 
     pub(super) fn handle_context_menu_action(&mut self) {
@@ -567,16 +3144,17 @@ impl App {
 
             match action {
                 ContextMenuAction::Reply => {
-                    if let Some(ref channel) = self.selected_channel {
-                        if let Some(ch) = self.channels.get(*channel) {
-                            if let Some(messages) = self.messages.get(&ch.id) {
-                                if let Some(msg) = messages.back() {
-                                    self.active_threads.insert(ch.id.clone(), msg.ts.clone());
-                                }
+                    if let Some(ch) = self.selected_channel() {
+                        if let Some(messages) = self.messages.get(&ch.id) {
+                            if let Some(msg) = messages.back() {
+                                self.active_threads.insert(ch.id.clone(), msg.ts.clone());
                             }
                         }
                     }
                 }
+                ContextMenuAction::Quote => {
+                    self.quote_selected_message();
+                }
                 ContextMenuAction::Edit => {
                     if let Err(e) = self.start_edit_message() {
                         self.report_error("Failed to start editing message", e);
@@ -593,190 +3171,844 @@ impl App {
                     }
                 }
                 ContextMenuAction::ViewThread => {
-                    if let Some(ref channel) = self.selected_channel {
-                        if let Some(ch) = self.channels.get(*channel) {
-                            let channel_id = ch.id.clone();
-                            self.load_thread(&channel_id);
-                        }
-                    }
+                    self.open_thread_at_cursor();
                 }
                 ContextMenuAction::React => {
-                    self.add_reaction_to_message("+1");
+                    if let Err(e) = self.show_reaction_picker() {
+                        self.report_error("Failed to open reaction picker", e);
+                    }
+                }
+                ContextMenuAction::Translate => {
+                    if let Err(e) = self.execute_agent_command("/translate") {
+                        self.report_error("Failed to translate message", e);
+                    }
                 }
+                ContextMenuAction::InspectJson => self.open_raw_inspector(),
+                ContextMenuAction::ToggleSaved => self.toggle_saved_selected(),
             }
         }
     }
 
     pub(super) fn save_edited_message(&mut self) -> Result<()> {
-        if let Some(ref edit_state) = self.edit_message {
-            if let Some(ws) = self.workspaces.get(self.active_workspace) {
-                let text = self.input.buffer.clone();
-                let channel_id = edit_state.channel_id.clone();
-                let ts = edit_state.ts.clone();
-                let token = ws.workspace.xoxp_token.clone();
-                let api = self.slack_api.clone();
-                self.spawn_app_task(async move {
-                    let error = api
-                        .update_message(&token, &channel_id, &ts, &text)
-                        .await
-                        .err()
-                        .map(|e| App::actionable_error(&e));
-                    AppAsyncEvent::SlackSendResult {
-                        context: "Failed to update message".to_string(),
-                        channel_id: None,
-                        error,
-                    }
-                });
-            }
-            self.edit_message = None;
-            self.input.clear();
+        let Some(edit_state) = self.edit_message.take() else {
+            return Ok(());
+        };
+        let text = self.input.buffer.trim_end().to_string();
+        if text.trim().is_empty() {
+            self.edit_message = Some(edit_state);
+            self.report_error(
+                "Cannot save edit",
+                "the message is empty — press d to delete it instead",
+            );
+            return Ok(());
+        }
+        self.input.set_text(edit_state.saved_draft);
+        if text == edit_state.original_text {
+            return Ok(());
+        }
+        let Some(ws) = self.active_workspace() else {
+            return Ok(());
+        };
+        let token = ws.workspace.xoxp_token.clone();
+
+        // Show the new text right away; the result event reverts it on failure.
+        let mut was_edited = false;
+        if let Some(msg) = self
+            .messages
+            .get_mut(&edit_state.channel_id)
+            .and_then(|messages| messages.iter_mut().find(|m| m.ts == edit_state.ts))
+        {
+            was_edited = msg.is_edited;
+            msg.text = text.clone();
+            msg.is_edited = true;
         }
+
+        let EditState {
+            channel_id,
+            ts,
+            original_text,
+            ..
+        } = edit_state;
+        let api = self.slack_api.clone();
+        self.spawn_app_task_as(TaskKind::Send, async move {
+            let error = api
+                .update_message(&token, &channel_id, &ts, &text)
+                .await
+                .err()
+                .map(|e| App::actionable_error(&e));
+            AppAsyncEvent::MessageEditFinished {
+                channel_id,
+                ts,
+                previous_text: original_text,
+                was_edited,
+                error,
+            }
+        });
         Ok(())
     }
 
-    pub(super) fn add_reaction_to_message(&mut self, reaction: &str) {
-        if let Some(ref channel) = self.selected_channel {
-            if let Some(ch) = self.channels.get(*channel) {
-                if let Some(messages) = self.messages.get(&ch.id) {
-                    if let Some(msg) = messages.back() {
-                        if let Some(ws) = self.workspaces.get(self.active_workspace) {
-                            let channel_id = ch.id.clone();
-                            let ts = msg.ts.clone();
-                            let token = ws.workspace.xoxp_token.clone();
-                            let reaction = reaction.to_string();
-                            let api = self.slack_api.clone();
-                            self.spawn_app_task(async move {
-                                let error = api
-                                    .add_reaction(&token, &channel_id, &ts, &reaction)
-                                    .await
-                                    .err()
-                                    .map(|e| App::actionable_error(&e));
-                                AppAsyncEvent::SlackSendResult {
-                                    context: "Failed to add reaction".to_string(),
-                                    channel_id: None,
-                                    error,
-                                }
-                            });
-                        }
-                    }
+    /// Adds the reaction to the selected message, or removes it when the
+    /// current user has already reacted with it. Slack refusing because the
+    /// reaction is already in that state (a stale local copy) is not an
+    /// error: the local toggle already shows the state asked for.
+    pub(super) fn toggle_reaction(&mut self, name: &str) {
+        let Some(channel_id) = self.get_active_channel_id() else {
+            return;
+        };
+        let Some(ts) = self.selected_or_cursor_message().map(|m| m.ts.clone()) else {
+            return;
+        };
+        let Some(ws) = self.active_workspace() else {
+            return;
+        };
+        let token = ws.workspace.xoxp_token.clone();
+        let user_id = self.current_user_id().unwrap_or_default();
+
+        let mut remove = false;
+        if let Some(msg) = self
+            .messages
+            .get_mut(&channel_id)
+            .and_then(|messages| messages.iter_mut().find(|m| m.ts == ts))
+        {
+            remove = Self::toggle_local_reaction(msg, name, &user_id);
+        }
+        if !remove {
+            self.note_reaction_used(&user_id, name);
+        }
+
+        let reaction = name.to_string();
+        let api = self.slack_api.clone();
+        self.spawn_app_task_as(TaskKind::Send, async move {
+            let (result, context, settled) = if remove {
+                (
+                    api.remove_reaction(&token, &channel_id, &ts, &reaction)
+                        .await,
+                    "Failed to remove reaction",
+                    "no_reaction",
+                )
+            } else {
+                (
+                    api.add_reaction(&token, &channel_id, &ts, &reaction).await,
+                    "Failed to add reaction",
+                    "already_reacted",
+                )
+            };
+            let result = result.or_else(|e| {
+                if e.to_string().ends_with(settled) {
+                    Ok(())
+                } else {
+                    Err(e)
+                }
+            });
+            AppAsyncEvent::SlackSendResult {
+                context: context.to_string(),
+                channel_id: None,
+                error: result.err().map(|e| App::actionable_error(&e)),
+                retry: None,
+                recovery: None,
+                local_id: None,
+                ts: None,
+            }
+        });
+    }
+
+    /// Applies a reaction toggle to the local copy of a message. Returns
+    /// true when the user's reaction was removed.
+    pub(super) fn toggle_local_reaction(msg: &mut Message, name: &str, user_id: &str) -> bool {
+        let existing = msg.reactions.iter().position(|r| r.name == name);
+        match existing {
+            Some(idx) if msg.reactions[idx].users.iter().any(|u| u == user_id) => {
+                let reaction = &mut msg.reactions[idx];
+                reaction.users.retain(|u| u != user_id);
+                reaction.count = reaction.count.saturating_sub(1);
+                if reaction.count == 0 {
+                    msg.reactions.remove(idx);
                 }
+                true
+            }
+            Some(idx) => {
+                let reaction = &mut msg.reactions[idx];
+                reaction.users.push(user_id.to_string());
+                reaction.count += 1;
+                false
+            }
+            None => {
+                msg.reactions.push(slack_zc_slack::types::Reaction {
+                    name: name.to_string(),
+                    count: 1,
+                    users: vec![user_id.to_string()],
+                });
+                false
+            }
+        }
+    }
+
+    /// Applies a `reaction_added`/`reaction_removed` event to the local
+    /// copy of a message. Returns false when it was already applied, as
+    /// for the echo of a toggle made here.
+    pub(super) fn apply_reaction_event(
+        msg: &mut Message,
+        name: &str,
+        user_id: &str,
+        added: bool,
+    ) -> bool {
+        let reacted = msg
+            .reactions
+            .iter()
+            .any(|r| r.name == name && r.users.iter().any(|u| u == user_id));
+        if reacted == added {
+            return false;
+        }
+        Self::toggle_local_reaction(msg, name, user_id);
+        true
+    }
+
+    /// Opens the thread of the message under the cursor, if it has one.
+    pub(super) fn open_thread_at_cursor(&mut self) {
+        let Some(channel_id) = self.get_active_channel_id() else {
+            return;
+        };
+        let parent_ts = self.selected_or_cursor_message().and_then(|msg| {
+            msg.thread_ts.clone().or_else(|| {
+                msg.reply_count
+                    .filter(|count| *count > 0)
+                    .map(|_| msg.ts.clone())
+            })
+        });
+        if let Some(parent_ts) = parent_ts {
+            self.open_thread(&channel_id, &parent_ts);
+        }
+    }
+
+    /// Expands a thread, fetching its replies the first time and refreshing
+    /// them in the background when the parent's reply count has grown.
+    pub(super) fn open_thread(&mut self, channel_id: &str, parent_ts: &str) {
+        if let Some(ws) = self.active_workspace_mut() {
+            if ws.workspace.mark_thread_read(channel_id, parent_ts) {
+                let team_id = ws.workspace.team_id.clone();
+                self.save_followed_threads(&team_id);
+            }
+        }
+        let reply_count = self
+            .messages
+            .get(channel_id)
+            .and_then(|messages| messages.iter().find(|m| m.ts == parent_ts))
+            .and_then(|parent| parent.reply_count)
+            .unwrap_or(0) as usize;
+        let cached = self
+            .threads
+            .get_mut(channel_id)
+            .and_then(|threads| threads.iter_mut().find(|t| t.parent_ts == parent_ts));
+        let stale = match cached {
+            Some(thread) => {
+                thread.is_collapsed = false;
+                thread.replies.len() < reply_count
             }
+            None => true,
+        };
+        if stale {
+            self.fetch_thread_replies(channel_id, parent_ts);
         }
     }
 
-    pub(super) fn load_thread(&mut self, channel_id: &str) {
-        let token = match self.workspaces.get(self.active_workspace) {
+    fn fetch_thread_replies(&mut self, channel_id: &str, parent_ts: &str) {
+        let token = match self.active_workspace() {
             Some(ws) => ws.workspace.xoxp_token.clone(),
             None => return,
         };
+        let channel_id = channel_id.to_string();
+        let parent_ts = parent_ts.to_string();
+        let api = self.slack_api.clone();
+        self.spawn_app_task_as(TaskKind::History, async move {
+            match api
+                .get_thread_replies(&token, &channel_id, &parent_ts)
+                .await
+            {
+                Ok(replies) => AppAsyncEvent::ThreadRepliesLoaded {
+                    channel_id,
+                    parent_ts,
+                    replies,
+                    error: None,
+                },
+                Err(e) => AppAsyncEvent::ThreadRepliesLoaded {
+                    channel_id,
+                    parent_ts,
+                    replies: Vec::new(),
+                    error: Some(App::actionable_error(&e)),
+                },
+            }
+        });
+    }
+
+    /// Message drawn at a screen cell in the last frame, as (channel id, ts).
+    /// Borders, the header line and spacer rows hit nothing.
+    pub(super) fn hit_test_message(&self, col: u16, row: u16) -> Option<(String, String)> {
+        let channel_id = self.get_active_channel_id()?;
+        if self.message_rows.channel_id != channel_id {
+            return None;
+        }
+        let ts = self.message_rows.message_at(col, row)?;
+        Some((channel_id, ts.to_string()))
+    }
+
+    /// Moves the message cursor onto a message and makes it the selection.
+    pub(super) fn select_message_by_ts(&mut self, channel_id: String, ts: String) {
+        let hidden = self
+            .messages
+            .get(&channel_id)
+            .and_then(|messages| messages.iter().find(|m| m.ts == ts))
+            .is_some_and(|m| !self.message_filter.matches(m));
+        if hidden {
+            self.clear_message_filter();
+        }
+        if let Some(messages) = self.messages.get(&channel_id) {
+            let visible: Vec<&Message> = messages
+                .iter()
+                .filter(|m| self.message_filter.matches(m))
+                .collect();
+            if let Some(pos) = visible.iter().position(|m| m.ts == ts) {
+                self.scroll_offset = visible.len() - 1 - pos;
+            }
+        }
+        self.selected_message = Some((channel_id, ts));
+    }
+
+    /// Jumps to the message closest to the typed time, fetching history
+    /// around it when it is outside what is loaded. Parse errors stay in the
+    /// popup; returns whether the popup can close.
+    pub(super) fn jump_to_timestamp(&mut self) -> bool {
+        let target = match Self::parse_jump_target(&self.jump_to_time_buffer, Local::now()) {
+            Ok(target) => target.with_timezone(&Utc),
+            Err(e) => {
+                self.jump_to_time_error = Some(e);
+                return false;
+            }
+        };
+        let Some(channel_id) = self.get_active_channel_id() else {
+            return true;
+        };
 
-        let shared_api = self.slack_api.clone();
-        if let Some(messages) = self.messages.get(channel_id).cloned() {
-            for msg in messages.iter() {
-                if msg.reply_count.is_some_and(|c| c > 0) {
-                    let channel_id = channel_id.to_string();
-                    let thread_ts = msg.ts.clone();
-                    let token = token.clone();
-                    let api = shared_api.clone();
-                    self.spawn_app_task(async move {
-                        match api
-                            .get_thread_replies(&token, &channel_id, &thread_ts)
-                            .await
-                        {
-                            Ok(replies) => AppAsyncEvent::ThreadRepliesLoaded {
-                                channel_id,
-                                parent_ts: thread_ts,
-                                replies,
-                                error: None,
-                            },
-                            Err(e) => AppAsyncEvent::ThreadRepliesLoaded {
-                                channel_id,
-                                parent_ts: thread_ts,
-                                replies: Vec::new(),
-                                error: Some(App::actionable_error(&e)),
-                            },
-                        }
-                    });
+        let loaded = self
+            .messages
+            .get(&channel_id)
+            .and_then(|messages| Some((messages.front()?.timestamp, messages.back()?.timestamp)));
+        match loaded {
+            Some((oldest, newest)) if oldest <= target && target <= newest => {
+                self.scroll_to_closest(&channel_id, target);
+            }
+            _ => self.fetch_history_around(channel_id, target),
+        }
+        true
+    }
+
+    /// Accepts `14:00`, `yesterday 14:00`, `today`, `2024-05-01`,
+    /// `2024-05-01 14:00`, ISO/RFC 3339 datetimes and raw Slack ts values.
+    pub(super) fn parse_jump_target(
+        input: &str,
+        now: chrono::DateTime<Local>,
+    ) -> std::result::Result<chrono::DateTime<Local>, String> {
+        use chrono::{Duration as ChronoDuration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+
+        let input = input.trim();
+        let local = |naive: NaiveDateTime| Local.from_local_datetime(&naive).earliest();
+        let time_of = |text: &str| {
+            NaiveTime::parse_from_str(text, "%H:%M")
+                .or_else(|_| NaiveTime::parse_from_str(text, "%H:%M:%S"))
+                .ok()
+        };
+
+        let lower = input.to_lowercase();
+        let (day, rest) = if let Some(rest) = lower.strip_prefix("yesterday") {
+            (
+                Some(now.date_naive() - ChronoDuration::days(1)),
+                rest.trim(),
+            )
+        } else if let Some(rest) = lower.strip_prefix("today") {
+            (Some(now.date_naive()), rest.trim())
+        } else {
+            (None, lower.as_str())
+        };
+        let parsed = match day {
+            Some(day) if rest.is_empty() => local(day.and_time(NaiveTime::MIN)),
+            Some(day) => time_of(rest).and_then(|time| local(day.and_time(time))),
+            None => time_of(input)
+                .and_then(|time| local(now.date_naive().and_time(time)))
+                .or_else(|| {
+                    NaiveDate::parse_from_str(input, "%Y-%m-%d")
+                        .ok()
+                        .and_then(|date| local(date.and_time(NaiveTime::MIN)))
+                })
+                .or_else(|| {
+                    ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M"]
+                        .iter()
+                        .find_map(|format| NaiveDateTime::parse_from_str(input, format).ok())
+                        .and_then(local)
+                })
+                .or_else(|| {
+                    chrono::DateTime::parse_from_rfc3339(input)
+                        .ok()
+                        .map(|at| at.with_timezone(&Local))
+                })
+                .or_else(|| {
+                    let secs = input.split('.').next()?.parse::<i64>().ok()?;
+                    (secs > 1_000_000_000)
+                        .then(|| chrono::DateTime::from_timestamp(secs, 0))
+                        .flatten()
+                        .map(|at| at.with_timezone(&Local))
+                }),
+        };
+        parsed.ok_or_else(|| {
+            format!(
+                "Can't read \"{}\" — try 14:00, yesterday 14:00 or 2024-05-01",
+                input
+            )
+        })
+    }
+
+    /// Reads `me in 20m to …`, `me in 2 hours to …`, `me tomorrow 9am to …`
+    /// and `me at 15:30 to …` into the reminder text and when it fires. A
+    /// bare `tomorrow` means 9:00; a time of day already past means tomorrow.
+    pub(super) fn parse_reminder(
+        input: &str,
+        now: chrono::DateTime<Local>,
+    ) -> std::result::Result<(String, chrono::DateTime<Local>), String> {
+        use chrono::{Duration as ChronoDuration, NaiveTime, TimeZone};
+
+        const HINT: &str =
+            "try /remind me in 20m to stretch, or /remind me tomorrow 9am to call Sam";
+        let mut words: Vec<&str> = input.split_whitespace().collect();
+        match words.first() {
+            Some(&"me") => {
+                words.remove(0);
+            }
+            Some(target) if target.starts_with(['@', '#']) => {
+                return Err("Only reminders for yourself are supported: /remind me …".to_string());
+            }
+            _ => {}
+        }
+        let lower: Vec<String> = words.iter().map(|w| w.to_lowercase()).collect();
+        let word = |idx: usize| lower.get(idx).map(String::as_str).unwrap_or_default();
+
+        let clock = |idx: usize| -> Option<(NaiveTime, usize)> {
+            let (text, used) = match word(idx + 1) {
+                suffix @ ("am" | "pm") => (format!("{}{}", word(idx), suffix), 2),
+                _ => (word(idx).to_string(), 1),
+            };
+            let (digits, offset) = match text.strip_suffix("am").or(text.strip_suffix("pm")) {
+                Some(digits) => (digits, Some(text.ends_with("pm"))),
+                None => (text.as_str(), None),
+            };
+            let (hour, minute) = match digits.split_once(':') {
+                Some((h, m)) => (h.parse::<u32>().ok()?, m.parse::<u32>().ok()?),
+                None if offset.is_some() => (digits.parse::<u32>().ok()?, 0),
+                None => return None,
+            };
+            let hour = match offset {
+                Some(_) if !(1..=12).contains(&hour) => return None,
+                Some(pm) => hour % 12 + if pm { 12 } else { 0 },
+                None => hour,
+            };
+            Some((NaiveTime::from_hms_opt(hour, minute, 0)?, used))
+        };
+        let on_day = |day: chrono::NaiveDate, time: NaiveTime| {
+            Local.from_local_datetime(&day.and_time(time)).earliest()
+        };
+
+        let (at, used) = match word(0) {
+            "in" => {
+                let (amount, unit, used) = match word(1).find(|c: char| !c.is_ascii_digit()) {
+                    Some(split) if split > 0 => (&word(1)[..split], &word(1)[split..], 2),
+                    _ => (word(1), word(2), 3),
+                };
+                let amount = amount.parse::<i64>().ok().filter(|n| *n > 0).ok_or(HINT)?;
+                let span = match unit {
+                    "m" | "min" | "mins" | "minute" | "minutes" => ChronoDuration::minutes(amount),
+                    "h" | "hr" | "hrs" | "hour" | "hours" => ChronoDuration::hours(amount),
+                    "d" | "day" | "days" => ChronoDuration::days(amount),
+                    _ => return Err(format!("Unknown unit \"{unit}\" — use m, h or d; {HINT}")),
+                };
+                (now + span, used)
+            }
+            "tomorrow" => {
+                let day = now.date_naive() + ChronoDuration::days(1);
+                let start = if word(1) == "at" { 2 } else { 1 };
+                match clock(start) {
+                    Some((time, used)) => (on_day(day, time).ok_or(HINT)?, start + used),
+                    None => (
+                        on_day(day, NaiveTime::from_hms_opt(9, 0, 0).unwrap()).ok_or(HINT)?,
+                        1,
+                    ),
                 }
             }
+            "at" => {
+                let (time, used) = clock(1).ok_or(HINT)?;
+                let today = on_day(now.date_naive(), time).ok_or(HINT)?;
+                let at = if today > now {
+                    today
+                } else {
+                    on_day(now.date_naive() + ChronoDuration::days(1), time).ok_or(HINT)?
+                };
+                (at, 1 + used)
+            }
+            _ => return Err(format!("Say when first; {HINT}")),
+        };
+
+        let mut rest = &words[used.min(words.len())..];
+        if rest.first().is_some_and(|w| w.eq_ignore_ascii_case("to")) {
+            rest = &rest[1..];
+        }
+        if rest.is_empty() {
+            return Err(format!("Say what to be reminded of; {HINT}"));
         }
+        Ok((rest.join(" "), at))
     }
 
-    pub(super) fn hit_test_message(&self, col: u16, row: u16) -> Option<(String, String)> {
-        if let Some(ref channel) = self.selected_channel {
-            if let Some(ch) = self.channels.get(*channel) {
-                if let Some(messages) = self.messages.get(&ch.id) {
-                    let layout = self.layout.get_panels();
-                    for panel in layout {
-                        if panel.panel_type == PanelType::Messages {
-                            if col < panel.rect.x || col >= panel.rect.x + panel.rect.width {
-                                continue;
-                            }
-                            let relative_row = row.saturating_sub(panel.rect.y);
-                            let msg_index = (relative_row as usize + self.scroll_offset)
-                                .saturating_sub(panel.rect.y as usize);
+    fn scroll_to_closest(&mut self, channel_id: &str, target: chrono::DateTime<Utc>) {
+        let closest = self.messages.get(channel_id).and_then(|messages| {
+            messages
+                .iter()
+                .min_by_key(|m| (m.timestamp - target).num_seconds().abs())
+                .map(|m| m.ts.clone())
+        });
+        if let Some(ts) = closest {
+            self.select_message_by_ts(channel_id.to_string(), ts);
+        }
+    }
 
-                            if let Some(msg) = messages.get(msg_index) {
-                                return Some((ch.id.clone(), msg.ts.clone()));
-                            }
+    /// `a` in the sidebar: shows or hides the ARCHIVED section, fetching the
+    /// active workspace's archived channels the first time.
+    pub(super) fn toggle_show_archived(&mut self) {
+        self.show_archived = !self.show_archived;
+        if !self.show_archived {
+            return;
+        }
+        let Some((team_id, token)) = self.active_workspace().map(|ws| {
+            (
+                ws.workspace.team_id.clone(),
+                ws.workspace.xoxp_token.clone(),
+            )
+        }) else {
+            return;
+        };
+        if !self.archived_loaded.insert(team_id.clone()) {
+            return;
+        }
+        let api = self.slack_api.clone();
+        self.spawn_app_task(async move {
+            let mut archived = Vec::new();
+            let mut cursor: Option<String> = None;
+            loop {
+                match api
+                    .list_channels_page(&token, cursor.as_deref(), true)
+                    .await
+                {
+                    Ok((channels, next_cursor)) => {
+                        archived.extend(channels.into_iter().filter(|ch| ch.is_archived));
+                        if next_cursor.is_none() {
+                            break;
+                        }
+                        cursor = next_cursor;
+                    }
+                    Err(e) => {
+                        return AppAsyncEvent::ArchivedChannelsLoaded {
+                            team_id,
+                            channels: Vec::new(),
+                            error: Some(App::actionable_error(&e)),
                         }
                     }
                 }
             }
+            AppAsyncEvent::ArchivedChannelsLoaded {
+                team_id,
+                channels: archived,
+                error: None,
+            }
+        });
+    }
+
+    pub(super) fn merge_archived_channels(&mut self, team_id: &str, channels: Vec<Channel>) {
+        let Some(ws) = self
+            .workspaces
+            .iter_mut()
+            .find(|ws| ws.workspace.team_id == team_id)
+        else {
+            return;
+        };
+        for channel in channels {
+            if !ws.channels.iter().any(|ch| ch.id == channel.id) {
+                ws.channels.push(channel);
+            }
+        }
+        ws.channels.sort_by_key(App::sidebar_section);
+        if self.active_workspace_id.as_deref() == Some(team_id) {
+            let merged = ws.channels.clone();
+            let cursor_id = self.cursor_channel_id();
+            self.channels = merged;
+            if let Some(idx) =
+                cursor_id.and_then(|id| self.channels.iter().position(|ch| ch.id == id))
+            {
+                self.sidebar_cursor = idx;
+            }
         }
-        None
     }
 
-    pub(super) fn jump_to_timestamp(&mut self) -> Result<()> {
-        let target_ts = &self.jump_to_time_buffer;
+    /// Looks up the workspace icon once; failures only matter to the log,
+    /// since the topbar never needs it.
+    pub(super) fn fetch_team_icon(&mut self, team_id: &str, token: &str) {
+        let team_id = team_id.to_string();
+        let token = token.to_string();
+        let api = self.slack_api.clone();
+        self.spawn_app_task(async move {
+            let icon_url = api.team_icon(&token).await.unwrap_or_else(|e| {
+                tracing::debug!("team.info failed for {}: {}", team_id, e);
+                None
+            });
+            AppAsyncEvent::TeamIconLoaded { team_id, icon_url }
+        });
+    }
 
-        if let Some(ref channel) = self.selected_channel {
-            if let Some(ch) = self.channels.get(*channel) {
-                if let Some(messages) = self.messages.get(&ch.id) {
-                    for (idx, msg) in messages.iter().enumerate() {
-                        let msg_time = msg.timestamp.format("%H:%M").to_string();
-                        let msg_date = msg.timestamp.format("%Y-%m-%d").to_string();
+    /// Fetches the page before the oldest loaded message of the open channel,
+    /// bringing back history dropped by the retention cap.
+    pub(super) fn fetch_older_history(&mut self) {
+        const PAGE: u32 = 50;
 
-                        if msg_time == *target_ts
-                            || msg_date == *target_ts
-                            || msg.ts.starts_with(target_ts)
-                        {
-                            self.scroll_offset = idx.saturating_sub(5);
-                            return Ok(());
-                        }
+        let Some(channel_id) = self.selected_channel_id.clone() else {
+            return;
+        };
+        if self.older_history_loading.contains(&channel_id)
+            || self.history_exhausted.contains(&channel_id)
+        {
+            return;
+        }
+        let Some(oldest) = self
+            .messages
+            .get(&channel_id)
+            .and_then(|messages| messages.front())
+            .map(|m| m.ts.clone())
+        else {
+            return;
+        };
+        let Some(ws) = self.active_workspace() else {
+            return;
+        };
+        let token = ws.workspace.xoxp_token.clone();
+        let api = self.slack_api.clone();
+        self.older_history_loading.insert(channel_id.clone());
+        self.spawn_app_task_as(TaskKind::History, async move {
+            match api
+                .get_history_between(&token, &channel_id, None, Some(&oldest), PAGE)
+                .await
+            {
+                Ok(messages) => AppAsyncEvent::OlderHistoryLoaded {
+                    channel_id,
+                    messages,
+                    error: None,
+                },
+                Err(e) => AppAsyncEvent::OlderHistoryLoaded {
+                    channel_id,
+                    messages: Vec::new(),
+                    error: Some(App::actionable_error(&e)),
+                },
+            }
+        });
+    }
+
+    /// Loads messages on both sides of `target` and merges them in.
+    fn fetch_history_around(&mut self, channel_id: String, target: chrono::DateTime<Utc>) {
+        const WINDOW: u32 = 25;
+
+        let Some(ws) = self.active_workspace() else {
+            return;
+        };
+        let token = ws.workspace.xoxp_token.clone();
+        let api = self.slack_api.clone();
+        let target_ts = format!("{}.000000", target.timestamp());
+        let day_after = format!("{}.000000", target.timestamp() + 86_400);
+        self.spawn_app_task_as(TaskKind::History, async move {
+            let before = api
+                .get_history_between(&token, &channel_id, None, Some(&target_ts), WINDOW)
+                .await;
+            let after = api
+                .get_history_between(
+                    &token,
+                    &channel_id,
+                    Some(&target_ts),
+                    Some(&day_after),
+                    WINDOW,
+                )
+                .await;
+            match (before, after) {
+                (Ok(mut messages), Ok(after)) => {
+                    messages.extend(after);
+                    AppAsyncEvent::HistoryWindowLoaded {
+                        channel_id,
+                        target,
+                        messages,
+                        error: None,
                     }
                 }
+                (Err(e), _) | (_, Err(e)) => AppAsyncEvent::HistoryWindowLoaded {
+                    channel_id,
+                    target,
+                    messages: Vec::new(),
+                    error: Some(App::actionable_error(&e)),
+                },
+            }
+        });
+    }
+
+    /// Adds fetched messages to the channel, keeping it sorted and free of
+    /// duplicates. Returns how many were new.
+    pub(super) fn merge_messages(&mut self, channel_id: &str, fetched: Vec<Message>) -> usize {
+        let messages = self.messages.entry(channel_id.to_string()).or_default();
+        let before = messages.len();
+        for message in fetched {
+            if !messages.iter().any(|m| m.ts == message.ts) {
+                messages.push_back(message);
             }
         }
+        messages
+            .make_contiguous()
+            .sort_by(|a, b| a.timestamp.cmp(&b.timestamp).then_with(|| a.ts.cmp(&b.ts)));
+        messages.len() - before
+    }
 
-        Ok(())
+    /// Merges fetched messages, then moves the cursor to the one nearest
+    /// `target`.
+    pub(super) fn merge_history_window(
+        &mut self,
+        channel_id: &str,
+        fetched: Vec<Message>,
+        target: chrono::DateTime<Utc>,
+    ) {
+        self.merge_messages(channel_id, fetched);
+        self.scroll_to_closest(channel_id, target);
+    }
+
+    /// After `team_id`'s socket reconnects at `at`, re-syncs each of its
+    /// channels with loaded history from the newest message before the gap:
+    /// the open channel now, the others when next opened.
+    pub(super) fn schedule_catch_up(&mut self, team_id: &str, at: chrono::DateTime<Utc>) {
+        let Some(ws) = self
+            .workspaces
+            .iter()
+            .find(|ws| ws.workspace.team_id == team_id)
+        else {
+            return;
+        };
+        let mut channel_ids: Vec<String> = ws.channels.iter().map(|ch| ch.id.clone()).collect();
+        if self.active_workspace_id.as_deref() == Some(team_id) {
+            channel_ids.extend(self.channels.iter().map(|ch| ch.id.clone()));
+        }
+        channel_ids.sort();
+        channel_ids.dedup();
+
+        let until_ts = format!("{}.000000", at.timestamp() + 1);
+        let selected = self.get_active_channel_id();
+        for channel_id in channel_ids {
+            // Messages pushed since the reconnect are not part of the gap.
+            let Some(after) = self.messages.get(&channel_id).and_then(|messages| {
+                messages
+                    .iter()
+                    .rev()
+                    .find(|m| m.timestamp <= at && m.thread_ts.is_none())
+            }) else {
+                continue;
+            };
+            let gap = HistoryGap {
+                after_ts: after.ts.clone(),
+                until_ts: until_ts.clone(),
+            };
+            if selected.as_deref() == Some(channel_id.as_str()) {
+                self.fetch_missed_messages(&channel_id, gap);
+            } else {
+                self.pending_catch_up.insert(channel_id, gap);
+            }
+        }
+    }
+
+    /// Fetches what the channel missed during `gap`, with the active
+    /// workspace's token.
+    pub(super) fn fetch_missed_messages(&mut self, channel_id: &str, gap: HistoryGap) {
+        const CATCH_UP_LIMIT: u32 = 200;
+
+        let Some(token) = self
+            .active_workspace()
+            .map(|ws| ws.workspace.xoxp_token.clone())
+        else {
+            return;
+        };
+        self.catching_up.insert(channel_id.to_string());
+        let channel_id = channel_id.to_string();
+        let api = self.slack_api.clone();
+        self.spawn_app_task_as(TaskKind::History, async move {
+            let fetched = api
+                .get_history_between(
+                    &token,
+                    &channel_id,
+                    Some(&gap.after_ts),
+                    Some(&gap.until_ts),
+                    CATCH_UP_LIMIT,
+                )
+                .await;
+            match fetched {
+                Ok(messages) => AppAsyncEvent::MissedMessagesLoaded {
+                    channel_id,
+                    gap,
+                    messages,
+                    error: None,
+                },
+                Err(e) => AppAsyncEvent::MissedMessagesLoaded {
+                    channel_id,
+                    gap,
+                    messages: Vec::new(),
+                    error: Some(App::actionable_error(&e)),
+                },
+            }
+        });
     }
 
     pub(super) fn load_history_for_date(&mut self) -> Result<()> {
-        if let Some(ref channel) = self.selected_channel {
-            if let Some(ch) = self.channels.get(*channel) {
-                if let Some(ws) = self.workspaces.get(self.active_workspace) {
-                    let channel_id = ch.id.clone();
-                    let token = ws.workspace.xoxp_token.clone();
-
-                    let api = self.slack_api.clone();
-                    self.spawn_app_task(async move {
-                        match api.get_history(&token, &channel_id, 100).await {
-                            Ok(messages) => AppAsyncEvent::ChannelHistoryLoaded {
-                                channel_id,
-                                messages,
-                                error: None,
-                            },
-                            Err(e) => AppAsyncEvent::ChannelHistoryLoaded {
-                                channel_id,
-                                messages: Vec::new(),
-                                error: Some(App::actionable_error(&e)),
-                            },
-                        }
-                    });
-                }
+        if let Some(ch) = self.selected_channel() {
+            if let Some(ws) = self.active_workspace() {
+                let channel_id = ch.id.clone();
+                let token = ws.workspace.xoxp_token.clone();
+
+                let api = self.slack_api.clone();
+                self.spawn_view_task(async move {
+                    match api.get_history(&token, &channel_id, 100).await {
+                        Ok(messages) => AppAsyncEvent::ChannelHistoryLoaded {
+                            channel_id,
+                            messages,
+                            error: None,
+                        },
+                        Err(e) => AppAsyncEvent::ChannelHistoryLoaded {
+                            channel_id,
+                            messages: Vec::new(),
+                            error: Some(App::actionable_error(&e)),
+                        },
+                    }
+                });
             }
         }
         Ok(())
     }
 }
+
+/// Posts `send` to its channel, or to its thread when it has one.
+const LOCAL_TS_PREFIX: &str = "local-";
+
+/// Stand-in ts for a post's local copy until Slack gives it one.
+fn local_ts(id: u64) -> String {
+    format!("{LOCAL_TS_PREFIX}{id}")
+}
+
+async fn post_pending(api: &dyn SlackClient, token: &str, send: &PendingSend) -> Result<String> {
+    match send.thread_ts.as_deref() {
+        Some(ts) => {
+            api.send_message_to_thread(token, &send.channel_id, &send.text, ts)
+                .await
+        }
+        None => api.send_message(token, &send.channel_id, &send.text).await,
+    }
+}
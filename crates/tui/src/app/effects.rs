@@ -2,6 +2,21 @@ use super::*;
 use std::time::Duration;
 use tokio::time::sleep;
 
+const MAX_STREAMING_RESPONSE_BYTES: usize = 80_000;
+const MAX_AGENT_RESPONSES: usize = 200;
+const MAX_ZEROCLAW_RESTARTS: u32 = 3;
+const MAX_THREADS_PER_CHANNEL: usize = 50;
+/// Socket events applied per frame, so a replay burst after a reconnect
+/// spreads over several frames instead of freezing one.
+const SLACK_EVENTS_PER_TICK: usize = 200;
+/// Budgeted apart from socket events so a flood of those never holds back
+/// one-off results such as a finished agent command.
+const APP_EVENTS_PER_TICK: usize = 100;
+/// How long quitting waits for posts still on their way to Slack.
+const SEND_DRAIN_GRACE: Duration = Duration::from_secs(3);
+/// A gateway that stayed up this long gets a fresh restart budget.
+const ZEROCLAW_STABLE_UPTIME: Duration = Duration::from_secs(60);
+
 async fn with_init_retry<T, F, Fut>(operation: F, context: &str) -> Result<T, anyhow::Error>
 where
     F: Fn() -> Fut,
@@ -41,25 +56,40 @@ where
 impl App {
     pub async fn init(&mut self, _config: &Config) -> Result<()> {
         tracing::info!("Starting app initialization...");
-        let mut session_opt = Session::load()?;
+        let mut session_opt = match Session::load() {
+            Ok(session) => session,
+            Err(e) => {
+                self.set_init_stage(InitStage::Session, StageStatus::Failed(e.to_string()));
+                return Err(e);
+            }
+        };
+
+        match crate::history::load_recent(MAX_AGENT_RESPONSES) {
+            Ok(entries) => self.agent_responses = entries.into(),
+            Err(e) => tracing::warn!("Failed to load agent history: {}", e),
+        }
 
-        if session_opt.is_some() {
+        self.resolve_credentials(&mut session_opt);
+        let has_workspaces = |session: &Option<Session>| {
+            session
+                .as_ref()
+                .is_some_and(|session| !session.workspaces.is_empty())
+        };
+
+        if has_workspaces(&session_opt) {
             tracing::info!("Session loaded successfully");
         } else {
             tracing::warn!("No session found, checking environment variables...");
         }
 
-        if session_opt.is_none() {
+        if !has_workspaces(&session_opt) {
             if let (Ok(app_token), Ok(user_token)) = (
                 std::env::var("SLACK_APP_TOKEN"),
                 std::env::var("SLACK_USER_TOKENS"),
             ) {
                 match self.slack_api.test_auth(&user_token).await {
                     Ok((team_id, team_name, user_id)) => {
-                        let mut session = Session {
-                            workspaces: Vec::new(),
-                            zeroclaw_bearer: None,
-                        };
+                        let mut session = session_opt.clone().unwrap_or_default();
                         let workspace = Workspace {
                             team_id,
                             team_name,
@@ -68,6 +98,10 @@ impl App {
                             user_id: Some(user_id),
                             active: true,
                             last_channel_id: None,
+                            icon_url: None,
+                            threads: Vec::new(),
+                            muted_channels: Vec::new(),
+                            recent_reactions: Vec::new(),
                         };
                         session.add_workspace(workspace);
                         if let Err(e) = session.save() {
@@ -83,12 +117,22 @@ impl App {
             }
         }
 
-        if let Some(session) = session_opt {
+        self.set_init_stage(InitStage::Session, StageStatus::Done);
+
+        if has_workspaces(&session_opt) {
+            let session = session_opt.unwrap_or_default();
             tracing::info!(
                 "Initializing with {} workspace(s)",
                 session.workspaces.len()
             );
             self.session = Some(session.clone());
+            let api_base_url = self.config.slack_api_base_url();
+            if api_base_url != slack_zc_slack::api::SLACK_API_BASE {
+                tracing::info!("Using Slack API base URL {}", api_base_url);
+            }
+            if !self.config.network.proxy.is_empty() {
+                tracing::info!("Using the proxy from [network] for Slack traffic");
+            }
 
             for (ws_idx, workspace) in session.workspaces.iter().enumerate() {
                 tracing::info!(
@@ -98,31 +142,6 @@ impl App {
                     workspace.team_id
                 );
                 let mut ws_state = WorkspaceState::new(workspace.clone());
-                let token = workspace.xoxp_token.clone();
-                let api = self.slack_api.clone();
-
-                // Test auth first
-                match api.test_auth(&token).await {
-                    Ok((_, team_name, _)) => {
-                        tracing::info!("Auth test passed for {}", team_name);
-                    }
-                    Err(e) => {
-                        tracing::error!("Auth test failed: {}", e);
-                        self.report_error("Slack authentication failed", e);
-                        continue;
-                    }
-                }
-
-                if let Some(ref event_tx) = self.event_tx {
-                    let socket_client = slack_zc_slack::socket::SocketModeClient::new(
-                        workspace.xapp_token.clone(),
-                        workspace.xoxp_token.clone(),
-                        event_tx.clone(),
-                    );
-                    ws_state.socket_task = Some(tokio::spawn(async move {
-                        socket_client.run().await;
-                    }));
-                }
 
                 match crate::cache::load_workspace_channels(&workspace.team_id) {
                     Ok(Some(cached_channels)) => {
@@ -131,6 +150,11 @@ impl App {
                             cached_channels.len(),
                             workspace.team_name
                         );
+                        for channel in &cached_channels {
+                            if let Some(ts) = channel.latest_ts.as_deref() {
+                                self.note_channel_activity(&channel.id, ts);
+                            }
+                        }
                         ws_state.channels = cached_channels;
                     }
                     Ok(None) => {}
@@ -144,132 +168,7 @@ impl App {
                 }
 
                 self.workspaces.push(ws_state);
-
-                let team_id = workspace.team_id.clone();
-                let team_name = workspace.team_name.clone();
-                let token = workspace.xoxp_token.clone();
-                let api = self.slack_api.clone();
-                let app_async_tx = self.app_async_tx.clone();
-                self.spawn_app_task(async move {
-                    tracing::info!("Loading channels for {} in background...", team_name);
-                    let mut channel_cursor: Option<String> = None;
-                    let mut dm_cursor: Option<String> = None;
-                    let mut loaded_total = 0usize;
-                    let Some(app_async_tx) = app_async_tx else {
-                        return AppAsyncEvent::WorkspaceChannelsLoaded {
-                            team_id,
-                            channels: Vec::new(),
-                            append: true,
-                            done: true,
-                            error: Some("Internal app event channel unavailable".to_string()),
-                        };
-                    };
-
-                    loop {
-                        match with_init_retry(
-                            || async {
-                                api.list_channels_page(&token, channel_cursor.as_deref())
-                                    .await
-                            },
-                            "Channel loading",
-                        )
-                        .await
-                        {
-                            Ok((channels, next_cursor)) => {
-                                loaded_total += channels.len();
-                                tracing::info!(
-                                    "Loaded {} regular channels for workspace {} (total: {})",
-                                    channels.len(),
-                                    team_name,
-                                    loaded_total
-                                );
-                                let append = channel_cursor.is_some();
-                                let done = false;
-                                if !channels.is_empty() || !append || done {
-                                    let _ = App::send_app_event(
-                                        &app_async_tx,
-                                        AppAsyncEvent::WorkspaceChannelsLoaded {
-                                            team_id: team_id.clone(),
-                                            channels,
-                                            append,
-                                            done,
-                                            error: None,
-                                        },
-                                    );
-                                }
-                                if next_cursor.is_none() {
-                                    break;
-                                }
-                                channel_cursor = next_cursor;
-                            }
-                            Err(e) => {
-                                tracing::error!("Failed to load channels for {}: {}", team_name, e);
-                                return AppAsyncEvent::WorkspaceChannelsLoaded {
-                                    team_id,
-                                    channels: Vec::new(),
-                                    append: true,
-                                    done: true,
-                                    error: Some(format!("Failed to load channels: {}", e)),
-                                };
-                            }
-                        }
-                    }
-
-                    loop {
-                        match with_init_retry(
-                            || async { api.list_dms_page(&token, dm_cursor.as_deref()).await },
-                            "DM loading",
-                        )
-                        .await
-                        {
-                            Ok((dms, next_cursor)) => {
-                                loaded_total += dms.len();
-                                tracing::info!(
-                                    "Loaded {} DMs for workspace {} (total: {})",
-                                    dms.len(),
-                                    team_name,
-                                    loaded_total
-                                );
-                                let done = next_cursor.is_none();
-                                if !dms.is_empty() || done {
-                                    let _ = App::send_app_event(
-                                        &app_async_tx,
-                                        AppAsyncEvent::WorkspaceChannelsLoaded {
-                                            team_id: team_id.clone(),
-                                            channels: dms,
-                                            append: true,
-                                            done,
-                                            error: None,
-                                        },
-                                    );
-                                }
-                                if next_cursor.is_none() {
-                                    break;
-                                }
-                                dm_cursor = next_cursor;
-                            }
-                            Err(e) => {
-                                tracing::error!("Failed to load DMs for {}: {}", team_name, e);
-                                return AppAsyncEvent::WorkspaceChannelsLoaded {
-                                    team_id,
-                                    channels: Vec::new(),
-                                    append: true,
-                                    done: true,
-                                    error: Some(format!("Failed to load DMs: {}", e)),
-                                };
-                            }
-                        }
-                    }
-
-                    tracing::info!("Finished background channel loading for {}", team_name);
-                    AppAsyncEvent::WorkspaceChannelsLoaded {
-                        team_id,
-                        channels: Vec::new(),
-                        append: true,
-                        done: true,
-                        error: None,
-                    }
-                });
+                self.connect_workspace(self.workspaces.len() - 1);
             }
 
             let active_team_id = session
@@ -288,54 +187,376 @@ impl App {
                 .or_else(|| (!self.workspaces.is_empty()).then_some(0));
 
             if let Some(active_idx) = resolved_active_idx {
-                self.active_workspace = active_idx;
+                self.active_workspace_id =
+                    Some(self.workspaces[active_idx].workspace.team_id.clone());
+                self.workspace_picker_cursor = active_idx;
                 self.channels = self.workspaces[active_idx].channels.clone();
             } else {
                 tracing::warn!("No workspace could be initialized successfully");
                 self.channels.clear();
-                self.selected_channel = None;
+                self.selected_channel_id = None;
             }
 
-            self.is_loading = false;
+            // Cached channels make the main screen usable right away;
+            // otherwise the splash stays until the first page arrives.
+            if !self.config.ui.splash || !self.channels.is_empty() || resolved_active_idx.is_none()
+            {
+                self.finish_splash();
+            }
             self.clear_error();
 
             // Auto-start zeroclaw agent
             self.start_zeroclaw_auto();
         } else {
-            self.onboarding = Some(OnboardingState::new());
-            self.is_loading = false;
+            // Keep any stored credentials so OAuth completion saves them back.
+            self.session = session_opt;
+            let mut onboarding = OnboardingState::new();
+            onboarding.client_id = self.config.slack.client_id.clone();
+            onboarding.client_secret = self.config.slack.client_secret.clone();
+            self.onboarding = Some(onboarding);
+            self.finish_splash();
         }
 
         Ok(())
     }
+
+    /// Records a startup step for the splash; nothing once it is gone.
+    pub(super) fn set_init_stage(&mut self, stage: InitStage, status: StageStatus) {
+        if !self.is_loading {
+            return;
+        }
+        match self.init_stages.iter_mut().find(|(s, _)| *s == stage) {
+            Some((_, existing)) => *existing = status,
+            None => self.init_stages.push((stage, status)),
+        }
+    }
+
+    /// Past the session step, after which the splash can be skipped.
+    pub(super) fn session_read(&self) -> bool {
+        self.init_stages
+            .iter()
+            .any(|(stage, status)| *stage == InitStage::Session && *status != StageStatus::Running)
+    }
+
+    /// Leaves the splash for the main screen; the remaining steps go on in
+    /// the background.
+    pub(super) fn finish_splash(&mut self) {
+        self.is_loading = false;
+        self.init_stages.clear();
+    }
+
+    /// Starts the socket and the background channel load for a workspace.
+    /// Each runs on its own task so a slow or failing workspace never holds
+    /// up the first frame or the others; a token that fails `auth.test`
+    /// lands in the Workspace issues panel instead of loading.
+    pub(super) fn connect_workspace(&mut self, ws_idx: usize) {
+        let Some(ws) = self.workspaces.get_mut(ws_idx) else {
+            return;
+        };
+        if let Some(task) = ws.socket_task.take() {
+            task.abort();
+        }
+        let workspace = ws.workspace.clone();
+        let team = || workspace.team_id.clone();
+        for stage in [
+            InitStage::Auth(team()),
+            InitStage::Channels(team()),
+            InitStage::Dms(team()),
+        ] {
+            self.set_init_stage(stage, StageStatus::Pending);
+        }
+
+        // The socket names message authors from the same directory the
+        // sidebar and the API client use.
+        let users = self.slack_api.user_directory(&workspace.xoxp_token);
+        self.user_directories.insert(
+            workspace.team_id.clone(),
+            (users.clone(), users.subscribe()),
+        );
+        users.persist_as(&workspace.team_id);
+
+        if let Some(ref event_tx) = self.event_tx {
+            let socket_client = slack_zc_slack::socket::SocketModeClient::new(
+                workspace.xapp_token.clone(),
+                workspace.xoxp_token.clone(),
+                event_tx.clone(),
+            )
+            .with_api(Self::build_slack_api(&self.config))
+            .with_users(users)
+            .with_team_id(workspace.team_id.clone());
+            self.workspaces[ws_idx].socket_task = Some(tokio::spawn(async move {
+                socket_client.run().await;
+            }));
+            self.set_init_stage(InitStage::Socket(team()), StageStatus::Running);
+        }
+
+        self.channels_loading.insert(workspace.team_id.clone());
+        if workspace.icon_url.is_none() {
+            self.fetch_team_icon(&workspace.team_id, &workspace.xoxp_token);
+        }
+
+        let include_archived = self.show_archived;
+        if include_archived {
+            self.archived_loaded.insert(workspace.team_id.clone());
+        }
+
+        let team_id = workspace.team_id.clone();
+        let team_name = workspace.team_name.clone();
+        let token = workspace.xoxp_token.clone();
+        let api = self.slack_api.clone();
+        let app_async_tx = self.app_async_tx.clone();
+        self.spawn_app_task(async move {
+            tracing::info!("Loading channels for {} in background...", team_name);
+            let mut channel_cursor: Option<String> = None;
+            let mut dm_cursor: Option<String> = None;
+            let mut loaded_total = 0usize;
+            let Some(app_async_tx) = app_async_tx else {
+                return AppAsyncEvent::WorkspaceChannelsLoaded {
+                    team_id,
+                    channels: Vec::new(),
+                    append: true,
+                    done: true,
+                    error: Some("Internal app event channel unavailable".to_string()),
+                };
+            };
+
+            let progress = |stage: fn(String) -> InitStage, status| {
+                let _ = App::send_app_event(
+                    &app_async_tx,
+                    AppAsyncEvent::InitProgress {
+                        stage: stage(team_id.clone()),
+                        status,
+                    },
+                );
+            };
+
+            progress(InitStage::Auth, StageStatus::Running);
+            if let Err(e) = api.test_auth(&token).await {
+                tracing::error!("Auth test failed for {}: {}", team_name, e);
+                let reason = App::workspace_issue_reason(&e);
+                progress(InitStage::Auth, StageStatus::Failed(reason.clone()));
+                return AppAsyncEvent::WorkspaceAuthFailed { team_id, reason };
+            }
+            progress(InitStage::Auth, StageStatus::Done);
+            progress(InitStage::Channels, StageStatus::Running);
+
+            loop {
+                match with_init_retry(
+                    || async {
+                        api.list_channels_page(&token, channel_cursor.as_deref(), include_archived)
+                            .await
+                    },
+                    "Channel loading",
+                )
+                .await
+                {
+                    Ok((channels, next_cursor)) => {
+                        loaded_total += channels.len();
+                        tracing::info!(
+                            "Loaded {} regular channels for workspace {} (total: {})",
+                            channels.len(),
+                            team_name,
+                            loaded_total
+                        );
+                        let append = channel_cursor.is_some();
+                        let done = false;
+                        if !channels.is_empty() || !append || done {
+                            let _ = App::send_app_event(
+                                &app_async_tx,
+                                AppAsyncEvent::WorkspaceChannelsLoaded {
+                                    team_id: team_id.clone(),
+                                    channels,
+                                    append,
+                                    done,
+                                    error: None,
+                                },
+                            );
+                        }
+                        if next_cursor.is_none() {
+                            break;
+                        }
+                        channel_cursor = next_cursor;
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to load channels for {}: {}", team_name, e);
+                        progress(InitStage::Channels, StageStatus::Failed(e.to_string()));
+                        return AppAsyncEvent::WorkspaceChannelsLoaded {
+                            team_id,
+                            channels: Vec::new(),
+                            append: true,
+                            done: true,
+                            error: Some(format!("Failed to load channels: {}", e)),
+                        };
+                    }
+                }
+            }
+
+            progress(InitStage::Channels, StageStatus::Done);
+            progress(InitStage::Dms, StageStatus::Running);
+            loop {
+                match with_init_retry(
+                    || async { api.list_dms_page(&token, dm_cursor.as_deref()).await },
+                    "DM loading",
+                )
+                .await
+                {
+                    Ok((dms, next_cursor)) => {
+                        loaded_total += dms.len();
+                        tracing::info!(
+                            "Loaded {} DMs for workspace {} (total: {})",
+                            dms.len(),
+                            team_name,
+                            loaded_total
+                        );
+                        let done = next_cursor.is_none();
+                        if !dms.is_empty() || done {
+                            let _ = App::send_app_event(
+                                &app_async_tx,
+                                AppAsyncEvent::WorkspaceChannelsLoaded {
+                                    team_id: team_id.clone(),
+                                    channels: dms,
+                                    append: true,
+                                    done,
+                                    error: None,
+                                },
+                            );
+                        }
+                        if next_cursor.is_none() {
+                            break;
+                        }
+                        dm_cursor = next_cursor;
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to load DMs for {}: {}", team_name, e);
+                        progress(InitStage::Dms, StageStatus::Failed(e.to_string()));
+                        return AppAsyncEvent::WorkspaceChannelsLoaded {
+                            team_id,
+                            channels: Vec::new(),
+                            append: true,
+                            done: true,
+                            error: Some(format!("Failed to load DMs: {}", e)),
+                        };
+                    }
+                }
+            }
+
+            progress(InitStage::Dms, StageStatus::Done);
+            tracing::info!("Finished background channel loading for {}", team_name);
+            AppAsyncEvent::WorkspaceChannelsLoaded {
+                team_id,
+                channels: Vec::new(),
+                append: true,
+                done: true,
+                error: None,
+            }
+        });
+    }
+
+    /// Resolves app credentials from the environment or the encrypted
+    /// session, and strips plaintext copies out of config.toml.
+    fn resolve_credentials(&mut self, session_opt: &mut Option<Session>) {
+        let mut session = session_opt.take().unwrap_or_default();
+        let had_plaintext = self
+            .config
+            .resolve_credentials(&mut session.credentials, |var| std::env::var(var).ok());
+
+        if had_plaintext {
+            // Only drop the plaintext copy once the encrypted one is on disk.
+            if let Err(e) = session.save() {
+                tracing::warn!("Failed to move credentials into the session: {}", e);
+            } else if let Some(path) = self.config_path.clone() {
+                match self.config.save(&path) {
+                    Ok(()) => tracing::info!("Moved plaintext credentials out of config.toml"),
+                    Err(e) => tracing::warn!("Failed to rewrite config without secrets: {}", e),
+                }
+            }
+        }
+
+        let is_empty = session.workspaces.is_empty()
+            && session.zeroclaw_bearer.is_none()
+            && session.agents.is_empty()
+            && session.credentials.client_id.is_none()
+            && session.credentials.client_secret.is_none()
+            && session.credentials.llm_api_key.is_none();
+        if !is_empty {
+            *session_opt = Some(session);
+        }
+    }
     pub(super) fn start_zeroclaw_auto(&mut self) {
-        if !self.config.zeroclaw.auto_start {
+        if !self.config.zeroclaw.auto_start || !self.agent_enabled() {
             return;
         }
+        self.set_init_stage(InitStage::Agent, StageStatus::Running);
+        self.connect_zeroclaw();
+    }
+
+    /// Restarts the gateway we manage, or connects from scratch when there is
+    /// no runner yet.
+    pub(super) fn restart_zeroclaw(&mut self) {
+        let Some(mut runner) = self.agent_runner.take() else {
+            self.connect_zeroclaw();
+            return;
+        };
+        self.zeroclaw_connected_at = None;
+        self.spawn_app_task(async move {
+            match runner.restart().await {
+                Ok(_) => AppAsyncEvent::ZeroClawConnectionFinished {
+                    runner: Some(runner),
+                    error: None,
+                },
+                Err(e) => AppAsyncEvent::ZeroClawConnectionFinished {
+                    runner: None,
+                    error: Some(format!("ZeroClaw restart failed: {}", e)),
+                },
+            }
+        });
+    }
 
+    fn watch_zeroclaw_exit(&self) {
+        let Some(mut exited) = self
+            .agent_runner
+            .as_ref()
+            .and_then(|runner| runner.exit_watcher())
+        else {
+            return;
+        };
+        let Some(tx) = self.app_async_tx.clone() else {
+            return;
+        };
+        // Ends quietly when the runner stops the process itself.
+        tokio::spawn(async move {
+            if exited.changed().await.is_ok() {
+                let status = exited.borrow().clone().unwrap_or_default();
+                let _ = App::send_app_event(&tx, AppAsyncEvent::ZeroClawExited { status });
+            }
+        });
+    }
+
+    fn connect_zeroclaw(&mut self) {
         let binary_path = self.config.zeroclaw.binary_path.clone();
+        let pairing_timeout = Duration::from_secs(self.config.zeroclaw.pairing_timeout_seconds);
         let gateway_port = slack_zc_slack::auth::load_zeroclaw_gateway_port()
             .unwrap_or(self.config.zeroclaw.gateway_port);
 
-        // Try to get bearer from session first, then from OpenClaw config
-        let bearer = self
-            .session
-            .as_ref()
-            .and_then(|s| s.zeroclaw_bearer.clone())
-            .or_else(|| {
+        // Try to get bearer from session first, then from OpenClaw config.
+        // After the gateway rejected a bearer we go straight to pairing.
+        let session_bearer = self.session.as_ref().and_then(|s| {
+            s.zeroclaw_bearer_for(self.active_workspace_id.as_deref())
+                .map(String::from)
+        });
+        let bearer = if self.zeroclaw_bearer_rejected {
+            None
+        } else {
+            session_bearer.clone().or_else(|| {
                 tracing::info!("No bearer in session, trying to load from OpenClaw config");
                 slack_zc_slack::auth::load_openclaw_bearer()
-            });
+            })
+        };
 
         if bearer.is_some() {
             tracing::info!(
                 "Found ZeroClaw bearer token (source: {})",
-                if self
-                    .session
-                    .as_ref()
-                    .and_then(|s| s.zeroclaw_bearer.clone())
-                    .is_some()
-                {
+                if session_bearer.is_some() {
                     "session"
                 } else {
                     "openclaw config"
@@ -345,7 +566,52 @@ impl App {
 
         self.agent_status = AgentStatus::Starting;
         self.spawn_app_task(async move {
-            let mut runner = AgentRunner::new(binary_path, gateway_port);
+            let mut runner =
+                AgentRunner::new(binary_path, gateway_port).with_pairing_timeout(pairing_timeout);
+
+            // Reuse a gateway the user started themselves instead of spawning
+            // a second process that cannot bind the port.
+            if runner.probe_running_gateway().await {
+                if let Some(ref bearer) = bearer {
+                    match runner.connect_with_bearer(bearer).await {
+                        Ok(_) => {
+                            return AppAsyncEvent::ZeroClawConnectionFinished {
+                                runner: Some(runner),
+                                error: None,
+                            }
+                        }
+                        Err(e) => tracing::warn!(
+                            "Running gateway rejected the stored bearer ({}), falling back to pairing",
+                            e
+                        ),
+                    }
+                }
+                return match runner.connect_to_running_gateway().await {
+                    Ok(_) => AppAsyncEvent::ZeroClawConnectionFinished {
+                        runner: Some(runner),
+                        error: None,
+                    },
+                    Err(e)
+                        if matches!(
+                            e.downcast_ref::<GatewayError>(),
+                            Some(GatewayError::PairingRequired)
+                        ) =>
+                    {
+                        AppAsyncEvent::ZeroClawPairingRequired {
+                            runner,
+                            error: None,
+                        }
+                    }
+                    Err(e) => AppAsyncEvent::ZeroClawConnectionFinished {
+                        runner: None,
+                        error: Some(format!(
+                            "Could not attach to the ZeroClaw gateway running on port {}: {}",
+                            gateway_port, e
+                        )),
+                    },
+                };
+            }
+
             if let Err(e) = runner.check_binary().await {
                 return AppAsyncEvent::ZeroClawConnectionFinished {
                     runner: None,
@@ -354,44 +620,45 @@ impl App {
             }
 
             if let Some(bearer) = bearer {
-                match runner.connect_with_bearer(&bearer).await {
+                match runner.start_with_bearer(&bearer).await {
                     Ok(_) => AppAsyncEvent::ZeroClawConnectionFinished {
                         runner: Some(runner),
                         error: None,
                     },
-                    Err(e) => {
-                        tracing::warn!(
-                            "Existing gateway connection with bearer failed ({}), attempting to start a new gateway",
-                            e
-                        );
-                        match runner.start_with_bearer(&bearer).await {
-                            Ok(_) => AppAsyncEvent::ZeroClawConnectionFinished {
-                                runner: Some(runner),
-                                error: None,
-                            },
-                            Err(start_err) => match runner.start_embedded_gateway().await {
-                                Ok(_) => AppAsyncEvent::ZeroClawConnectionFinished {
-                                    runner: Some(runner),
-                                    error: None,
-                                },
-                                Err(embedded_err) => AppAsyncEvent::ZeroClawConnectionFinished {
-                                    runner: None,
-                                    error: Some(format!(
-                                        "ZeroClaw bearer auth failed: {}; embedded gateway start failed: {}. Run `zeroclaw gateway --port {}` or refresh your ZeroClaw login with `zeroclaw onboard`.",
-                                        start_err, embedded_err, gateway_port
-                                    )),
-                                },
-                            },
-                        }
-                    }
+                    Err(start_err) => match runner.start_embedded_gateway().await {
+                        Ok(_) => AppAsyncEvent::ZeroClawConnectionFinished {
+                            runner: Some(runner),
+                            error: None,
+                        },
+                        Err(embedded_err) => AppAsyncEvent::ZeroClawConnectionFinished {
+                            runner: None,
+                            error: Some(format!(
+                                "ZeroClaw bearer auth failed: {}; embedded gateway start failed: {}. Run `zeroclaw gateway --port {}` or refresh your ZeroClaw login with `zeroclaw onboard`.",
+                                start_err, embedded_err, gateway_port
+                            )),
+                        },
+                    },
                 }
             } else {
-                match runner.connect_to_running_gateway().await {
+                match runner.start_and_pair().await {
                     Ok(_) => AppAsyncEvent::ZeroClawConnectionFinished {
                         runner: Some(runner),
                         error: None,
                     },
-                    Err(connect_err) => match runner.start_embedded_gateway().await {
+                    // The gateway is up but its code never appeared in the
+                    // output; let the user type it in.
+                    Err(e)
+                        if matches!(
+                            e.downcast_ref::<GatewayError>(),
+                            Some(GatewayError::PairingRequired)
+                        ) =>
+                    {
+                        AppAsyncEvent::ZeroClawPairingRequired {
+                            runner,
+                            error: None,
+                        }
+                    }
+                    Err(pair_err) => match runner.start_embedded_gateway().await {
                         Ok(_) => AppAsyncEvent::ZeroClawConnectionFinished {
                             runner: Some(runner),
                             error: None,
@@ -400,7 +667,7 @@ impl App {
                             runner: None,
                             error: Some(format!(
                                 "ZeroClaw auto-connect failed: {}; embedded gateway start failed: {}. Run `zeroclaw onboard` to create local credentials, then start `zeroclaw gateway --port {}`.",
-                                connect_err, embedded_err, gateway_port
+                                pair_err, embedded_err, gateway_port
                             )),
                         },
                     },
@@ -409,14 +676,39 @@ impl App {
         });
     }
 
+    pub(super) fn submit_pairing_code(&mut self) {
+        let Some(prompt) = self.pairing_prompt.take() else {
+            return;
+        };
+        let Some(mut runner) = self.agent_runner.take() else {
+            self.connect_zeroclaw();
+            return;
+        };
+        self.agent_status = AgentStatus::Pairing;
+        self.spawn_app_task(async move {
+            match runner.pair_with_running_gateway(&prompt.code).await {
+                Ok(_) => AppAsyncEvent::ZeroClawConnectionFinished {
+                    runner: Some(runner),
+                    error: None,
+                },
+                Err(e) => AppAsyncEvent::ZeroClawPairingRequired {
+                    runner,
+                    error: Some(e.to_string()),
+                },
+            }
+        });
+    }
+
     pub(super) fn complete_oauth(&mut self, code: &str) -> Result<()> {
         if let Some(ref onboarding) = self.onboarding {
             let client_id = onboarding.client_id.clone();
             let client_secret = onboarding.client_secret.clone();
             let code = code.to_string();
             let redirect_port = self.config.slack.redirect_port;
+            let api = Self::build_slack_api(&self.config);
             self.spawn_app_task(async move {
                 let result = slack_zc_slack::auth::exchange_oauth_code(
+                    &api,
                     &client_id,
                     &client_secret,
                     &code,
@@ -434,6 +726,10 @@ impl App {
                             user_id: Some(response.authed_user.id),
                             active: true,
                             last_channel_id: None,
+                            icon_url: None,
+                            threads: Vec::new(),
+                            muted_channels: Vec::new(),
+                            recent_reactions: Vec::new(),
                         }),
                         error: None,
                     },
@@ -446,63 +742,471 @@ impl App {
         }
         Ok(())
     }
+    /// Stops background work before the terminal is restored: the socket
+    /// loops and app tasks, sends still in flight (for a few seconds), a
+    /// final session save, then any ZeroClaw gateway we spawned.
+    pub async fn shutdown(&mut self) {
+        for ws in &mut self.workspaces {
+            if let Some(task) = ws.socket_task.take() {
+                task.abort();
+            }
+        }
+        self.tasks.abort_all();
+        self.tasks.drain_sends(SEND_DRAIN_GRACE).await;
+
+        if let Some(ref session) = self.session {
+            if let Err(e) = session.save() {
+                tracing::warn!("Failed to save session on exit: {}", e);
+            }
+        }
+        // Activity seen this session goes into the channel cache.
+        if self.session.is_some() {
+            for ws_idx in 0..self.workspaces.len() {
+                if self.workspaces[ws_idx].channels.is_empty() {
+                    continue;
+                }
+                self.stamp_channel_activity(ws_idx);
+                let ws = &self.workspaces[ws_idx];
+                if let Err(e) =
+                    crate::cache::save_workspace_channels(&ws.workspace.team_id, &ws.channels)
+                {
+                    tracing::warn!(
+                        "Failed to save cached channels for workspace {}: {}",
+                        ws.workspace.team_name,
+                        e
+                    );
+                }
+            }
+        }
+
+        if let Some(mut runner) = self.agent_runner.take() {
+            runner.shutdown().await;
+        }
+        tracing::info!("Shutdown complete");
+    }
+
+    /// Whether the last `process_slack_events` left events over its budget,
+    /// so the main loop can come straight back instead of waiting for input.
+    pub fn has_queued_events(&self) -> bool {
+        self.event_rx.as_ref().is_some_and(|rx| !rx.is_empty())
+            || self.app_async_rx.as_ref().is_some_and(|rx| !rx.is_empty())
+    }
+
+    /// Counted in every workspace's channel list, active or not, so the
+    /// topbar badges stay current in the background.
+    fn apply_unread_deltas(
+        &mut self,
+        deltas: HashMap<String, (u32, Vec<String>)>,
+        own_user: Option<&String>,
+    ) {
+        for (channel, (count, mentioned)) in deltas {
+            let mentions_of = |user_id: Option<&String>| {
+                user_id.map_or(0, |id| mentioned.iter().filter(|m| *m == id).count() as u32)
+            };
+            let own_mentions = mentions_of(own_user);
+            for ch in self.channels.iter_mut().filter(|ch| ch.id == channel) {
+                ch.unread_count += count;
+                ch.mention_count += own_mentions;
+            }
+            for ws in self.workspaces.iter_mut() {
+                let mentions = mentions_of(ws.workspace.user_id.as_ref());
+                for ch in ws.channels.iter_mut().filter(|ch| ch.id == channel) {
+                    ch.unread_count += count;
+                    ch.mention_count += mentions;
+                }
+            }
+        }
+    }
+
+    /// Copies newly resolved users into their workspaces, so names drawn
+    /// as raw ids fill in on the next frame.
+    fn apply_user_directory_changes(&mut self) {
+        for (team_id, (users, changes)) in &mut self.user_directories {
+            if !changes.has_changed().unwrap_or(false) {
+                continue;
+            }
+            changes.mark_unchanged();
+            if let Some(ws) = self
+                .workspaces
+                .iter_mut()
+                .find(|ws| ws.workspace.team_id == *team_id)
+            {
+                ws.users.extend(users.snapshot());
+            }
+        }
+    }
+
     pub fn process_slack_events(&mut self) {
+        self.apply_user_directory_changes();
+        let open_channel = self.get_active_channel_id();
+        let own_user = self.current_user_id();
+        let own_users: Vec<String> = self
+            .workspaces
+            .iter()
+            .filter_map(|ws| ws.workspace.user_id.clone())
+            .collect();
+        let mut typing_user_ids = Vec::new();
+        let mut grown_channels: Vec<String> = Vec::new();
+        // Channel and message ts of each new message, for the sidebar ages.
+        let mut activity: Vec<(String, String)> = Vec::new();
+        let mut reconnected = Vec::new();
+        let mut socket_stages: Vec<(String, StageStatus)> = Vec::new();
+        // Reactions the user added from other clients, for the picker.
+        let mut used_reactions: Vec<(String, String)> = Vec::new();
+        let mut resort_sections = false;
+        // Workspaces whose followed threads changed and need saving.
+        let mut threads_changed: HashSet<String> = HashSet::new();
+        // Unread messages per channel and the own users they mention,
+        // applied to the channel lists once after the burst.
+        let mut unread_deltas: HashMap<String, (u32, Vec<String>)> = HashMap::new();
+        // Messages from others: channel, author, text and whether it
+        // mentions an own user, for the window title and notifications.
+        let mut arrivals: Vec<(String, String, String, bool)> = Vec::new();
         if let Some(ref mut rx) = self.event_rx {
-            while let Ok(event) = rx.try_recv() {
+            for event in std::iter::from_fn(|| rx.try_recv().ok()).take(SLACK_EVENTS_PER_TICK) {
                 match event {
                     SlackEvent::Message { channel, message } => {
+                        let unread = open_channel.as_deref() != Some(channel.as_str())
+                            && !message.is_system
+                            && !own_users.contains(&message.user_id);
+                        if unread {
+                            let delta = unread_deltas.entry(channel.clone()).or_default();
+                            delta.0 += 1;
+                            delta.1.extend(
+                                own_users
+                                    .iter()
+                                    .filter(|id| message.text.contains(&format!("<@{}>", id)))
+                                    .cloned(),
+                            );
+                        }
+                        if !message.is_system && !own_users.contains(&message.user_id) {
+                            let mention = own_users
+                                .iter()
+                                .any(|id| message.text.contains(&format!("<@{}>", id)));
+                            arrivals.push((
+                                channel.clone(),
+                                message.user_id.clone(),
+                                message.text.clone(),
+                                mention,
+                            ));
+                        }
+                        let is_reply = message
+                            .thread_ts
+                            .as_deref()
+                            .is_some_and(|thread_ts| thread_ts != message.ts);
+                        if is_reply {
+                            for ws in self
+                                .workspaces
+                                .iter_mut()
+                                .filter(|ws| ws.channels.iter().any(|ch| ch.id == channel))
+                            {
+                                let own = ws.workspace.user_id.as_ref() == Some(&message.user_id);
+                                if own {
+                                    if let Some(thread_ts) = message.thread_ts.as_deref() {
+                                        ws.workspace.subscribe_thread(
+                                            &channel,
+                                            thread_ts,
+                                            &message.ts,
+                                            &message.text,
+                                        );
+                                    }
+                                } else if !ws
+                                    .workspace
+                                    .note_thread_reply(&channel, &message, unread)
+                                {
+                                    continue;
+                                }
+                                threads_changed.insert(ws.workspace.team_id.clone());
+                                // A reply in a followed thread counts like a
+                                // mention of the user.
+                                let Some(user_id) = ws.workspace.user_id.as_ref() else {
+                                    continue;
+                                };
+                                if !own
+                                    && unread
+                                    && !message.text.contains(&format!("<@{}>", user_id))
+                                {
+                                    let delta = unread_deltas.entry(channel.clone()).or_default();
+                                    delta.1.push(user_id.clone());
+                                }
+                            }
+                        }
                         if let Some(ref thread_ts) = message.thread_ts {
                             self.active_threads
                                 .insert(channel.clone(), thread_ts.clone());
                             self.threads.entry(channel.clone()).or_default();
                         }
-                        self.messages.entry(channel).or_default().push_back(message);
+                        // Sending a message ends that user's typing.
+                        if let Some(users) = self.typing_users.get_mut(&channel) {
+                            users.retain(|(id, _)| *id != message.user_id);
+                        }
+                        if !message.is_system {
+                            activity.push((channel.clone(), message.ts.clone()));
+                        }
+                        let messages = self.messages.entry(channel.clone()).or_default();
+                        // A new reply freshens its cached parent's summary;
+                        // echoes and redeliveries are already counted.
+                        if is_reply && !messages.iter().any(|m| m.ts == message.ts) {
+                            if let Some(parent) = messages
+                                .iter_mut()
+                                .find(|m| message.thread_ts.as_deref() == Some(m.ts.as_str()))
+                            {
+                                parent.note_reply(&message.user_id, &message.ts);
+                            }
+                        }
+                        // The echo of a post already shown as sent replaces
+                        // its local copy, keeping the mark.
+                        match messages
+                            .iter_mut()
+                            .find(|m| m.ts == message.ts && m.delivery.is_some())
+                        {
+                            Some(local) => {
+                                let delivery = local.delivery;
+                                *local = *message;
+                                local.delivery = delivery;
+                            }
+                            None => messages.push_back(*message),
+                        }
+                        if !grown_channels.contains(&channel) {
+                            grown_channels.push(channel);
+                        }
+                    }
+                    SlackEvent::UserTyping { channel, user } => {
+                        tracing::debug!("User {} typing in {}", user, channel);
+                        // Ids are stored and named at render time, so a late
+                        // lookup fixes the indicator up without re-keying.
+                        let users = self.typing_users.entry(channel).or_default();
+                        match users.iter_mut().find(|(id, _)| *id == user) {
+                            Some((_, seen)) => *seen = Instant::now(),
+                            None => users.push((user.clone(), Instant::now())),
+                        }
+                        typing_user_ids.push(user);
+                    }
+                    SlackEvent::CallUpdated { channel, ts, call } => {
+                        if let Some(message) = self
+                            .messages
+                            .get_mut(&channel)
+                            .and_then(|messages| messages.iter_mut().find(|m| m.ts == ts))
+                        {
+                            message.call = Some(call);
+                        }
+                    }
+                    SlackEvent::ChannelTopicChanged {
+                        channel,
+                        topic,
+                        purpose,
+                    } => {
+                        for ch in self
+                            .channels
+                            .iter_mut()
+                            .chain(
+                                self.workspaces
+                                    .iter_mut()
+                                    .flat_map(|ws| ws.channels.iter_mut()),
+                            )
+                            .filter(|ch| ch.id == channel)
+                        {
+                            if topic.is_some() {
+                                ch.topic = topic.clone();
+                            }
+                            if purpose.is_some() {
+                                ch.purpose = purpose.clone();
+                            }
+                        }
+                    }
+                    SlackEvent::ChannelArchived { channel, archived } => {
+                        for ch in self
+                            .channels
+                            .iter_mut()
+                            .chain(
+                                self.workspaces
+                                    .iter_mut()
+                                    .flat_map(|ws| ws.channels.iter_mut()),
+                            )
+                            .filter(|ch| ch.id == channel)
+                        {
+                            ch.is_archived = archived;
+                        }
+                        resort_sections = true;
+                    }
+                    SlackEvent::ImMarked { channel, ts } => {
+                        if !self.config.ui.read_receipts {
+                            continue;
+                        }
+                        for ch in self
+                            .channels
+                            .iter_mut()
+                            .chain(
+                                self.workspaces
+                                    .iter_mut()
+                                    .flat_map(|ws| ws.channels.iter_mut()),
+                            )
+                            .filter(|ch| ch.id == channel && ch.is_im)
+                        {
+                            if ch.last_read.as_deref() < Some(ts.as_str()) {
+                                ch.last_read = Some(ts.clone());
+                            }
+                        }
                     }
-                    SlackEvent::UserTyping { channel, user } => {
-                        tracing::debug!("User {} typing in {}", user, channel);
-                        let channel_key = channel.clone();
-                        let user_value = user.clone();
-                        self.typing_users.entry(channel_key.clone()).or_default();
-                        if let Some(users) = self.typing_users.get_mut(&channel_key) {
-                            if !users.contains(&user_value) {
-                                users.push(user_value);
+                    SlackEvent::ConnectionState { team_id, state } => {
+                        tracing::info!("Socket Mode for {}: {:?}", team_id, state);
+                        let was_down = self.socket_status.get(&team_id).is_some_and(|status| {
+                            matches!(status.state, ConnectionState::Reconnecting { .. })
+                        });
+                        if was_down && state == ConnectionState::Connected {
+                            reconnected.push((team_id.clone(), Utc::now()));
+                        }
+                        match &state {
+                            ConnectionState::Connected => {
+                                socket_stages.push((team_id.clone(), StageStatus::Done))
                             }
+                            ConnectionState::Reconnecting { retry_in, .. } => socket_stages.push((
+                                team_id.clone(),
+                                StageStatus::Failed(format!("retrying in {}s", retry_in.as_secs())),
+                            )),
+                            ConnectionState::Connecting => {}
                         }
+                        let last_event = self
+                            .socket_status
+                            .get(&team_id)
+                            .and_then(|status| status.last_event);
+                        self.socket_status.insert(
+                            team_id,
+                            SocketStatus {
+                                state,
+                                since: Instant::now(),
+                                last_event,
+                            },
+                        );
                     }
-                    SlackEvent::Connected => {
-                        tracing::info!("Socket Mode connected");
+                    SlackEvent::Activity { team_id } => {
+                        if let Some(status) = self.socket_status.get_mut(&team_id) {
+                            status.last_event = Some(Instant::now());
+                        }
                     }
-                    SlackEvent::Disconnected => {
-                        tracing::info!("Socket Mode disconnected");
+                    SlackEvent::ReactionChanged {
+                        channel,
+                        ts,
+                        user,
+                        name,
+                        added,
+                    } => {
+                        let thread_replies = self
+                            .threads
+                            .get_mut(&channel)
+                            .into_iter()
+                            .flatten()
+                            .flat_map(|thread| thread.replies.iter_mut());
+                        let target = self
+                            .messages
+                            .get_mut(&channel)
+                            .into_iter()
+                            .flatten()
+                            .chain(thread_replies)
+                            .find(|m| m.ts == ts);
+                        // A toggle made here is already applied and counted.
+                        let changed = match target {
+                            Some(msg) => App::apply_reaction_event(msg, &name, &user, added),
+                            None => true,
+                        };
+                        if changed && added && own_users.contains(&user) {
+                            used_reactions.push((user, name));
+                        }
                     }
                     _ => {}
                 }
             }
         }
+        self.apply_unread_deltas(unread_deltas, own_user.as_ref());
+        for (channel_id, user_id, text, mention) in arrivals {
+            self.note_arrival(&channel_id, &user_id, &text, mention);
+        }
+        for (user_id, name) in used_reactions {
+            self.note_reaction_used(&user_id, &name);
+        }
+        for (team_id, at) in reconnected {
+            self.schedule_catch_up(&team_id, at);
+        }
+        for (team_id, status) in socket_stages {
+            self.set_init_stage(InitStage::Socket(team_id), status);
+        }
+        for user_id in typing_user_ids {
+            self.fetch_user_info(&user_id);
+        }
+        for team_id in threads_changed {
+            self.save_followed_threads(&team_id);
+        }
+        for channel_id in grown_channels {
+            self.trim_channel_history(&channel_id);
+        }
+        for (channel_id, ts) in activity {
+            resort_sections |=
+                self.note_channel_activity(&channel_id, &ts) && self.config.ui.sort_by_activity;
+        }
+        if resort_sections {
+            for ws in &mut self.workspaces {
+                ws.channels.sort_by_key(App::sidebar_section);
+            }
+            self.sort_channels_by_section();
+        }
+        self.expire_typing(Instant::now());
+        self.keep_presence_active(Instant::now());
+        self.refresh_channels_periodically(Instant::now());
 
         let mut async_events = Vec::new();
         if let Some(ref mut rx) = self.app_async_rx {
-            while let Ok(event) = rx.try_recv() {
-                async_events.push(event);
-            }
+            async_events
+                .extend(std::iter::from_fn(|| rx.try_recv().ok()).take(APP_EVENTS_PER_TICK));
         }
+        let mut async_events: Vec<AppAsyncEvent> = async_events
+            .into_iter()
+            .filter_map(|event| self.tasks.accept(event))
+            .collect();
+        coalesce_history_loads(&mut async_events);
 
         for event in async_events {
             match event {
+                // Unwrapped by `TaskSupervisor::accept` above.
+                AppAsyncEvent::Stamped { .. } => {}
                 AppAsyncEvent::SlackSendResult {
                     context,
                     channel_id,
                     error,
+                    retry,
+                    recovery,
+                    local_id,
+                    ts,
                 } => {
                     if let Some(err) = error {
-                        self.report_error(&context, err);
+                        match retry {
+                            Some(send) => {
+                                if let Some(id) = local_id {
+                                    let channel_id = send.channel_id.clone();
+                                    self.settle_delivery(&channel_id, id, Err(send.clone()));
+                                }
+                                if let Some(recovery) = recovery {
+                                    self.send_recovery = Some(SendRecoveryPrompt {
+                                        recovery,
+                                        send: send.clone(),
+                                    });
+                                }
+                                self.report_send_error(&context, err, send)
+                            }
+                            None => self.report_error(&context, err),
+                        }
                     } else {
                         self.clear_error();
+                        if let (Some(id), Some(ch_id), Some(ts)) = (local_id, &channel_id, ts) {
+                            self.settle_delivery(ch_id, id, Ok(ts));
+                        }
+                        if let (Some(_), Some(ch_id)) = (local_id, &channel_id) {
+                            self.refresh_read_receipt(ch_id);
+                        }
                         if let Some(ch_id) = channel_id {
-                            if let Some(ws) = self.workspaces.get(self.active_workspace) {
+                            if let Some(ws) = self.active_workspace() {
                                 let token = ws.workspace.xoxp_token.clone();
                                 let api = self.slack_api.clone();
-                                self.spawn_app_task(async move {
+                                self.spawn_view_task(async move {
                                     match api.get_history(&token, &ch_id, 50).await {
                                         Ok(messages) => AppAsyncEvent::ChannelHistoryLoaded {
                                             channel_id: ch_id,
@@ -528,9 +1232,22 @@ impl App {
                     if let Some(err) = error {
                         self.report_error("Failed to load channel history", err);
                     } else {
-                        self.messages.insert(channel_id, messages.into());
+                        self.history_exhausted.remove(&channel_id);
+                        self.newer_history.remove(&channel_id);
+                        let mut messages = messages.into();
+                        self.keep_local_copies(&channel_id, &mut messages);
+                        self.messages.insert(channel_id.clone(), messages);
+                        self.trim_channel_history(&channel_id);
+                        self.note_loaded_activity(&channel_id);
                         self.clear_error();
                     }
+                    if self
+                        .pending_jump
+                        .as_ref()
+                        .is_some_and(|target| target.channel_id == channel_id)
+                    {
+                        self.finish_jump();
+                    }
                 }
                 AppAsyncEvent::ThreadRepliesLoaded {
                     channel_id,
@@ -541,6 +1258,21 @@ impl App {
                     if let Some(err) = error {
                         self.report_error("Failed to load thread replies", err);
                     } else if !replies.is_empty() {
+                        // conversations.replies includes the parent; it is already shown.
+                        let mut replies = replies;
+                        replies.retain(|reply| reply.ts != parent_ts);
+                        replies.sort_by(|a, b| a.ts.cmp(&b.ts));
+                        if let Some(parent) = self
+                            .messages
+                            .get_mut(&channel_id)
+                            .and_then(|messages| messages.iter_mut().find(|m| m.ts == parent_ts))
+                        {
+                            let count = replies.len() as u32;
+                            parent.reply_count = Some(parent.reply_count.unwrap_or(0).max(count));
+                            if let Some(last) = replies.last() {
+                                parent.latest_reply = Some(last.ts.clone());
+                            }
+                        }
                         let threads = self.threads.entry(channel_id.clone()).or_default();
                         if let Some(existing) =
                             threads.iter_mut().find(|t| t.parent_ts == parent_ts)
@@ -550,27 +1282,73 @@ impl App {
                             let mut thread = Thread::new(&parent_ts, &channel_id);
                             thread.replies = replies;
                             threads.push(thread);
+                            if threads.len() > MAX_THREADS_PER_CHANNEL {
+                                // Oldest loaded first; the open thread stays.
+                                let open = self.active_threads.get(&channel_id);
+                                if let Some(pos) =
+                                    threads.iter().position(|t| Some(&t.parent_ts) != open)
+                                {
+                                    threads.remove(pos);
+                                }
+                            }
                         }
                         self.clear_error();
                     }
                 }
+                AppAsyncEvent::AgentCommandChunk { command, delta } => {
+                    if self.loading_command.as_deref() == Some(command.as_str())
+                        && self.streaming_response.len() < MAX_STREAMING_RESPONSE_BYTES
+                    {
+                        self.streaming_response.push_str(&delta);
+                    }
+                }
                 AppAsyncEvent::AgentCommandFinished {
                     command,
+                    channel_id,
+                    channel_name,
+                    thread_ts,
                     response,
                     error,
                 } => {
                     self.agent_processing = false;
                     self.loading_start_time = None;
                     self.loading_command = None;
+                    self.loading_rerun = false;
+                    self.loading_timeout_secs = None;
+                    self.streaming_response.clear();
+                    let translated_ts = self.pending_translation.take();
+                    let drafted = std::mem::take(&mut self.pending_draft);
                     if let Some(err) = error {
                         self.report_error("Agent command failed", err);
                     } else if let Some(resp) = response {
-                        self.agent_responses.push_front(AgentResponse {
+                        if let Some(ts) = translated_ts {
+                            if self.config.zeroclaw.translation_annotations {
+                                self.translations.insert(ts, resp.trim().to_string());
+                            }
+                        }
+                        if drafted && !resp.trim().is_empty() {
+                            self.insert_agent_draft(&resp);
+                        }
+                        let entry = AgentResponse {
                             command,
                             response: resp,
                             timestamp: Utc::now(),
+                            channel_id,
+                            channel_name,
+                            thread_ts,
+                        };
+                        let retention_days = self.config.zeroclaw.history_retention_days;
+                        let max_entries = self.config.zeroclaw.history_max_entries;
+                        let persisted = entry.clone();
+                        tokio::task::spawn_blocking(move || {
+                            if let Err(e) =
+                                crate::history::append(&persisted, retention_days, max_entries)
+                            {
+                                tracing::warn!("Failed to persist agent response: {}", e);
+                            }
                         });
-                        if self.agent_responses.len() > 50 {
+                        self.agent_responses.push_front(entry);
+                        if self.agent_responses.len() > MAX_AGENT_RESPONSES {
                             self.agent_responses.pop_back();
                         }
                         self.clear_error();
@@ -578,6 +1356,111 @@ impl App {
                         self.clear_error();
                     }
                 }
+                AppAsyncEvent::AgentHistoryLoaded { entries, error } => {
+                    if let Some(err) = error {
+                        self.agent_history = None;
+                        self.report_error("Failed to load agent history", err);
+                    } else if let Some(popup) = self.agent_history.as_mut() {
+                        popup.entries = entries;
+                        popup.selected = 0;
+                        popup.loading = false;
+                    }
+                }
+                AppAsyncEvent::RemindersLoaded {
+                    reminders,
+                    select,
+                    error,
+                } => {
+                    if let Some(err) = error {
+                        self.reminders = None;
+                        self.report_error("Failed to list reminders", err);
+                    } else {
+                        self.show_reminders(reminders, select);
+                    }
+                }
+                AppAsyncEvent::ReminderAdded { id, error } => match error {
+                    Some(err) => self.report_error("Failed to set reminder", err),
+                    None => {
+                        self.clear_error();
+                        self.open_reminders(id);
+                    }
+                },
+                AppAsyncEvent::PresenceSet { manual, error } => match error {
+                    Some(err) if manual => self.report_error("Failed to set presence", err),
+                    Some(err) => tracing::warn!("Presence ping failed: {}", err),
+                    None => {}
+                },
+                AppAsyncEvent::ChannelCommandDone {
+                    team_id,
+                    channel_id,
+                    channel_name,
+                    command,
+                    error,
+                } => {
+                    let failed = match &command {
+                        ChannelCommand::Join => "Failed to join",
+                        ChannelCommand::Leave => "Failed to leave",
+                        ChannelCommand::SetTopic(_) => "Failed to set topic",
+                    };
+                    if let Some(err) = error {
+                        self.report_error(&format!("{failed} #{channel_name}"), err);
+                        continue;
+                    }
+                    match command {
+                        ChannelCommand::Join => {
+                            self.report_notice(format!("Joined #{channel_name}"));
+                            if self.active_workspace_id.as_deref() == Some(team_id.as_str()) {
+                                if let Some(idx) =
+                                    self.channels.iter().position(|ch| ch.id == channel_id)
+                                {
+                                    self.sidebar_cursor = idx;
+                                }
+                                self.select_channel(&channel_id);
+                                if let Err(e) = self.fetch_channel_history(&channel_id) {
+                                    self.report_error("Failed to load channel", e);
+                                }
+                            }
+                        }
+                        ChannelCommand::Leave => {
+                            self.report_notice(format!("Left #{channel_name}"));
+                        }
+                        ChannelCommand::SetTopic(topic) => {
+                            self.update_channel(&channel_id, |ch| ch.topic = Some(topic.clone()));
+                            self.report_notice(format!("Set the topic of #{channel_name}"));
+                        }
+                    }
+                }
+                AppAsyncEvent::SavedLoaded {
+                    team_id,
+                    items,
+                    error,
+                } => {
+                    if let Some(err) = error {
+                        self.report_error("Failed to load saved items", err);
+                    } else if self.active_workspace_id.as_deref() == Some(team_id.as_str()) {
+                        self.saved_items = items;
+                        if let Some(view) = self.saved_view.as_mut() {
+                            view.selected =
+                                view.selected.min(self.saved_items.len().saturating_sub(1));
+                        }
+                    }
+                }
+                AppAsyncEvent::SavedToggled { error } => {
+                    if let Some(err) = error {
+                        self.report_error("Failed to update saved items", err);
+                        self.refresh_saved();
+                    }
+                }
+                AppAsyncEvent::ReminderDeleted { id, error } => match error {
+                    Some(err) => self.report_error("Failed to delete reminder", err),
+                    None => {
+                        if let Some(popup) = self.reminders.as_mut() {
+                            popup.reminders.retain(|r| r.id != id);
+                            popup.selected =
+                                popup.selected.min(popup.reminders.len().saturating_sub(1));
+                        }
+                    }
+                },
                 AppAsyncEvent::OAuthCompleted { workspace, error } => {
                     if let Some(err) = error {
                         self.report_error("OAuth completion failed", err.clone());
@@ -585,19 +1468,46 @@ impl App {
                             onboarding.error_message = Some(err);
                         }
                     } else if let Some(workspace) = workspace {
-                        let mut session = self.session.take().unwrap_or(Session {
-                            workspaces: Vec::new(),
-                            zeroclaw_bearer: None,
-                        });
+                        let mut session = self.session.take().unwrap_or_default();
+                        if let Some(ref onboarding) = self.onboarding {
+                            // Env-provided credentials stay in the environment.
+                            let entered = |var: &str, value: &str| {
+                                (std::env::var(var).is_err() && !value.is_empty())
+                                    .then(|| value.to_string())
+                            };
+                            if let Some(id) = entered("SLACK_CLIENT_ID", &onboarding.client_id) {
+                                session.credentials.client_id = Some(id);
+                            }
+                            if let Some(secret) =
+                                entered("SLACK_CLIENT_SECRET", &onboarding.client_secret)
+                            {
+                                session.credentials.client_secret = Some(secret);
+                            }
+                        }
                         for w in &mut session.workspaces {
                             w.active = false;
                         }
+                        let team_id = workspace.team_id.clone();
                         session.add_workspace(workspace);
                         if let Err(e) = session.save() {
                             self.report_error("Failed to persist OAuth session", e);
                         } else {
+                            let reauth = self
+                                .onboarding
+                                .as_ref()
+                                .is_some_and(|o| o.reauth_team.is_some());
                             self.session = Some(session);
-                            if let Some(ref mut onboarding) = self.onboarding {
+                            if reauth {
+                                self.onboarding = None;
+                                self.reconnect_workspace(&team_id);
+                                if let Some(idx) = self
+                                    .workspaces
+                                    .iter()
+                                    .position(|ws| ws.workspace.team_id == team_id)
+                                {
+                                    self.switch_workspace(idx);
+                                }
+                            } else if let Some(ref mut onboarding) = self.onboarding {
                                 onboarding.error_message = None;
                                 onboarding.next_screen();
                             }
@@ -605,6 +1515,66 @@ impl App {
                         }
                     }
                 }
+                AppAsyncEvent::WorkspaceAuthFailed { team_id, reason } => {
+                    self.channels_loading.remove(&team_id);
+                    let Some(ws) = self
+                        .workspaces
+                        .iter_mut()
+                        .find(|ws| ws.workspace.team_id == team_id)
+                    else {
+                        continue;
+                    };
+                    // A rejected token only makes the socket loop fail over and over.
+                    if let Some(task) = ws.socket_task.take() {
+                        task.abort();
+                    }
+                    let issue = WorkspaceIssue {
+                        team_id: team_id.clone(),
+                        team_name: ws.workspace.team_name.clone(),
+                        reason,
+                    };
+                    tracing::warn!(
+                        "Workspace {} needs attention: {}",
+                        issue.team_name,
+                        issue.reason
+                    );
+                    match self
+                        .workspace_issues
+                        .iter_mut()
+                        .find(|i| i.team_id == team_id)
+                    {
+                        Some(existing) => *existing = issue,
+                        None => self.workspace_issues.push(issue),
+                    }
+                    if self.workspace_issues_view.is_none() {
+                        self.workspace_issues_view = Some(WorkspaceIssuesView::default());
+                    }
+                }
+                AppAsyncEvent::ChannelListRefreshed {
+                    team_id,
+                    channels,
+                    manual,
+                    error,
+                } => {
+                    self.channels_refreshing.remove(&team_id);
+                    if let Some(err) = error {
+                        if manual {
+                            self.report_error("Failed to refresh channels", err);
+                        } else {
+                            tracing::warn!("Background channel refresh failed: {}", err);
+                        }
+                        continue;
+                    }
+                    let (added, removed) = self.merge_channel_list(&team_id, channels);
+                    if manual {
+                        self.report_notice(format!(
+                            "Channel list refreshed: {added} new, {removed} gone"
+                        ));
+                    }
+                }
+                AppAsyncEvent::InitProgress { stage, status } => {
+                    self.set_init_stage(stage, status);
+                }
                 AppAsyncEvent::WorkspaceChannelsLoaded {
                     team_id,
                     channels,
@@ -612,6 +1582,18 @@ impl App {
                     done,
                     error,
                 } => {
+                    if done {
+                        self.channels_loading.remove(&team_id);
+                    }
+                    // The first page for the active workspace is enough to
+                    // use the main screen; a failure stays on the splash.
+                    if self.is_loading
+                        && error.is_none()
+                        && (done || !channels.is_empty())
+                        && self.active_workspace_id.as_deref() == Some(team_id.as_str())
+                    {
+                        self.finish_splash();
+                    }
                     if let Some(ws_idx) = self
                         .workspaces
                         .iter()
@@ -622,6 +1604,9 @@ impl App {
                         } else {
                             self.workspaces[ws_idx].channels = channels.clone();
                         }
+                        self.workspaces[ws_idx]
+                            .channels
+                            .sort_by_key(App::sidebar_section);
                         tracing::info!(
                             "Workspace {} channels updated: {} entries (done: {})",
                             self.workspaces[ws_idx].workspace.team_name,
@@ -629,19 +1614,20 @@ impl App {
                             done
                         );
 
-                        if ws_idx == self.active_workspace {
+                        if self.active_workspace_id.as_deref() == Some(team_id.as_str()) {
                             if append {
                                 self.channels.extend(channels);
                             } else {
                                 self.channels = channels;
                             }
+                            self.sort_channels_by_section();
                             if self.sidebar_cursor >= self.channels.len()
                                 && !self.channels.is_empty()
                             {
                                 self.sidebar_cursor = self.channels.len() - 1;
                             }
 
-                            if self.selected_channel.is_none() {
+                            if self.selected_channel_id.is_none() {
                                 if let Some(last_channel_id) =
                                     self.workspaces[ws_idx].workspace.last_channel_id.clone()
                                 {
@@ -649,12 +1635,13 @@ impl App {
                                         self.channels.iter().position(|c| c.id == last_channel_id)
                                     {
                                         self.sidebar_cursor = channel_idx;
-                                        self.selected_channel = Some(channel_idx);
+                                        self.selected_channel_id = Some(last_channel_id.clone());
+                                        self.note_external_channel(&last_channel_id);
                                         let channel_id = last_channel_id;
                                         let token =
                                             self.workspaces[ws_idx].workspace.xoxp_token.clone();
                                         let api = self.slack_api.clone();
-                                        self.spawn_app_task(async move {
+                                        self.spawn_view_task(async move {
                                             match api.get_history(&token, &channel_id, 50).await {
                                                 Ok(messages) => {
                                                     AppAsyncEvent::ChannelHistoryLoaded {
@@ -673,13 +1660,14 @@ impl App {
                                     } else if !self.channels.is_empty() && done {
                                         self.sidebar_cursor =
                                             self.sidebar_cursor.min(self.channels.len() - 1);
-                                        self.selected_channel = Some(self.sidebar_cursor);
                                         let channel_id =
                                             self.channels[self.sidebar_cursor].id.clone();
+                                        self.selected_channel_id = Some(channel_id.clone());
+                                        self.note_external_channel(&channel_id);
                                         let token =
                                             self.workspaces[ws_idx].workspace.xoxp_token.clone();
                                         let api = self.slack_api.clone();
-                                        self.spawn_app_task(async move {
+                                        self.spawn_view_task(async move {
                                             match api.get_history(&token, &channel_id, 50).await {
                                                 Ok(messages) => {
                                                     AppAsyncEvent::ChannelHistoryLoaded {
@@ -702,16 +1690,26 @@ impl App {
                                 }
                             }
                         }
+                        self.refresh_channel_picker();
                     }
 
                     if let Some(err) = error {
-                        self.report_error("Workspace channel refresh failed", err);
+                        let team_name = self
+                            .workspaces
+                            .iter()
+                            .find(|ws| ws.workspace.team_id == team_id)
+                            .map_or(team_id.clone(), |ws| ws.workspace.team_name.clone());
+                        self.report_error(&format!("Workspace {}", team_name), err);
                     } else {
+                        if done && self.active_workspace_id.as_deref() == Some(team_id.as_str()) {
+                            self.refresh_saved();
+                        }
                         if let Some(ws_idx) = self
                             .workspaces
                             .iter()
                             .position(|ws| ws.workspace.team_id == team_id)
                         {
+                            self.stamp_channel_activity(ws_idx);
                             if let Err(e) = crate::cache::save_workspace_channels(
                                 &team_id,
                                 &self.workspaces[ws_idx].channels,
@@ -738,29 +1736,370 @@ impl App {
                         self.clear_error();
                     }
                 }
+                AppAsyncEvent::ZeroClawPairingRequired { runner, error } => {
+                    self.agent_status = AgentStatus::Pairing;
+                    self.agent_runner = Some(runner);
+                    self.pairing_prompt = Some(PairingPrompt {
+                        code: String::new(),
+                        error,
+                    });
+                }
+                AppAsyncEvent::ZeroClawExited { status } => {
+                    if self
+                        .zeroclaw_connected_at
+                        .is_some_and(|since| since.elapsed() >= ZEROCLAW_STABLE_UPTIME)
+                    {
+                        self.zeroclaw_restart_attempts = 0;
+                    }
+                    self.zeroclaw_connected_at = None;
+
+                    if self.zeroclaw_restart_attempts < MAX_ZEROCLAW_RESTARTS {
+                        self.zeroclaw_restart_attempts += 1;
+                        tracing::warn!(
+                            "ZeroClaw gateway crashed ({}), restart {}/{}",
+                            status,
+                            self.zeroclaw_restart_attempts,
+                            MAX_ZEROCLAW_RESTARTS
+                        );
+                        self.agent_status = AgentStatus::Restarting {
+                            attempt: self.zeroclaw_restart_attempts,
+                            max: MAX_ZEROCLAW_RESTARTS,
+                        };
+                        self.restart_zeroclaw();
+                    } else {
+                        let message = format!(
+                            "gateway crashed ({}) after {} restarts — run /agent restart",
+                            status, MAX_ZEROCLAW_RESTARTS
+                        );
+                        self.agent_status = AgentStatus::Error(message.clone());
+                        self.agent_runner = None;
+                        self.report_error("ZeroClaw stopped", message);
+                    }
+                }
+                AppAsyncEvent::ChannelInfoLoaded {
+                    channel_id,
+                    info,
+                    error,
+                } => {
+                    if let Some(info) = info {
+                        self.update_channel(&channel_id, |ch| {
+                            ch.member_count = info.member_count.or(ch.member_count);
+                            ch.created = info.created.or(ch.created);
+                            ch.creator = info.creator.clone().or(ch.creator.take());
+                            ch.topic = info.topic.clone().or(ch.topic.take());
+                            ch.purpose = info.purpose.clone().or(ch.purpose.take());
+                            ch.is_shared |= info.is_shared;
+                            ch.is_ext_shared |= info.is_ext_shared;
+                            ch.is_org_shared |= info.is_org_shared;
+                            ch.last_read = info.last_read.clone().or(ch.last_read.take());
+                            ch.is_member = info.is_member.or(ch.is_member);
+                            ch.posting_restricted_to = info.posting_restricted_to.clone();
+                            ch.threads_restricted_to = info.threads_restricted_to.clone();
+                        });
+                        if let Some(ts) = info.latest_ts.as_deref() {
+                            if self.note_channel_activity(&channel_id, ts)
+                                && self.config.ui.sort_by_activity
+                            {
+                                self.sort_channels_by_section();
+                            }
+                        }
+                        if self.selected_channel_id.as_deref() == Some(channel_id.as_str()) {
+                            self.note_external_channel(&channel_id);
+                        }
+                    } else if let Some(err) = error {
+                        // The header just goes without a member count.
+                        tracing::warn!("Failed to load info for {}: {}", channel_id, err);
+                    }
+                }
+                AppAsyncEvent::UserInfoLoaded {
+                    team_id,
+                    user_id,
+                    user,
+                    error,
+                } => {
+                    if let Some(user) = user {
+                        if let Some(ws) = self
+                            .workspaces
+                            .iter_mut()
+                            .find(|ws| ws.workspace.team_id == team_id)
+                        {
+                            ws.users.insert(user_id, user);
+                        }
+                    } else if let Some(err) = error {
+                        // The raw id keeps standing in for the name.
+                        tracing::warn!("Failed to load user {}: {}", user_id, err);
+                    }
+                }
+                AppAsyncEvent::TypingConnectionEnded {
+                    team_id,
+                    error,
+                    unsupported,
+                } => {
+                    // A newer connection may have replaced this one already.
+                    if self
+                        .typing_senders
+                        .get(&team_id)
+                        .is_some_and(|tx| tx.is_closed())
+                    {
+                        self.typing_senders.remove(&team_id);
+                    }
+                    if unsupported {
+                        tracing::info!(
+                            "Not sending typing indicators for {}: {}",
+                            team_id,
+                            error.as_deref().unwrap_or("unsupported")
+                        );
+                        self.typing_unsupported.insert(team_id);
+                    } else if let Some(err) = error {
+                        tracing::debug!("Typing connection for {} ended: {}", team_id, err);
+                    }
+                }
+                AppAsyncEvent::ArchivedChannelsLoaded {
+                    team_id,
+                    channels,
+                    error,
+                } => {
+                    if let Some(err) = error {
+                        self.archived_loaded.remove(&team_id);
+                        self.report_error("Failed to load archived channels", err);
+                    } else {
+                        self.merge_archived_channels(&team_id, channels);
+                    }
+                }
+                AppAsyncEvent::TeamIconLoaded {
+                    team_id,
+                    icon_url: Some(icon_url),
+                } => {
+                    for ws in self
+                        .workspaces
+                        .iter_mut()
+                        .filter(|ws| ws.workspace.team_id == team_id)
+                    {
+                        ws.workspace.icon_url = Some(icon_url.clone());
+                    }
+                    if let Some(session) = self.session.as_mut() {
+                        for ws in session
+                            .workspaces
+                            .iter_mut()
+                            .filter(|w| w.team_id == team_id)
+                        {
+                            ws.icon_url = Some(icon_url.clone());
+                        }
+                        if let Err(e) = session.save() {
+                            tracing::error!("Failed to save workspace icon: {}", e);
+                        }
+                    }
+                }
+                AppAsyncEvent::TeamIconLoaded { icon_url: None, .. } => {}
+                AppAsyncEvent::OlderHistoryLoaded {
+                    channel_id,
+                    messages,
+                    error,
+                } => {
+                    self.older_history_loading.remove(&channel_id);
+                    if let Some(err) = error {
+                        self.report_error("Failed to load older messages", err);
+                    } else if messages.is_empty() {
+                        self.history_exhausted.insert(channel_id);
+                    } else {
+                        // The offset counts from the newest message, so
+                        // prepending keeps the cursor where it was.
+                        let loaded = self.messages.entry(channel_id).or_default();
+                        for message in messages {
+                            if !loaded.iter().any(|m| m.ts == message.ts) {
+                                loaded.push_front(message);
+                            }
+                        }
+                        loaded.make_contiguous().sort_by(|a, b| {
+                            a.timestamp.cmp(&b.timestamp).then_with(|| a.ts.cmp(&b.ts))
+                        });
+                    }
+                }
+                AppAsyncEvent::ContextLoaded {
+                    target,
+                    messages,
+                    error,
+                } => {
+                    if let Some(err) = error {
+                        self.report_error(
+                            "Failed to load the conversation around that message",
+                            err,
+                        );
+                    } else if self.selected_channel_id.as_deref()
+                        == Some(target.channel_id.as_str())
+                    {
+                        self.merge_context(target, messages);
+                    }
+                }
+                AppAsyncEvent::NewerHistoryLoaded {
+                    channel_id,
+                    after_ts,
+                    messages,
+                    error,
+                } => {
+                    if let Some(err) = error {
+                        self.newer_history.insert(channel_id, after_ts);
+                        self.report_error("Failed to load newer messages", err);
+                    } else {
+                        self.merge_newer_history(&channel_id, &after_ts, messages);
+                    }
+                }
+                AppAsyncEvent::HistoryWindowLoaded {
+                    channel_id,
+                    target,
+                    messages,
+                    error,
+                } => {
+                    if let Some(err) = error {
+                        self.report_error("Failed to load history around that time", err);
+                    } else if messages.is_empty() {
+                        self.report_error("Jump to time", "no messages found around that time");
+                    } else {
+                        self.merge_history_window(&channel_id, messages, target);
+                    }
+                }
+                AppAsyncEvent::ChannelMarked { channel_id, error } => {
+                    if let Some(err) = error {
+                        self.report_error(
+                            &format!("Failed to update read state of {}", channel_id),
+                            err,
+                        );
+                    }
+                }
+                AppAsyncEvent::MessageEditFinished {
+                    channel_id,
+                    ts,
+                    previous_text,
+                    was_edited,
+                    error,
+                } => {
+                    if let Some(err) = error {
+                        if let Some(msg) = self
+                            .messages
+                            .get_mut(&channel_id)
+                            .and_then(|messages| messages.iter_mut().find(|m| m.ts == ts))
+                        {
+                            msg.text = previous_text;
+                            msg.is_edited = was_edited;
+                        }
+                        self.report_error("Failed to update message", err);
+                    } else {
+                        self.clear_error();
+                    }
+                }
+                AppAsyncEvent::ZeroClawBearerRejected => {
+                    if self.zeroclaw_bearer_rejected {
+                        self.report_error(
+                            "ZeroClaw rejected the bearer again after re-pairing",
+                            "run `zeroclaw onboard`, then restart slack-zc",
+                        );
+                    } else {
+                        tracing::warn!("ZeroClaw bearer rejected; clearing it and re-pairing");
+                        self.zeroclaw_bearer_rejected = true;
+                        if let Some(ref mut session) = self.session {
+                            session.zeroclaw_bearer = None;
+                            if let Some(team_id) = self.active_workspace_id.as_deref() {
+                                session.agent_mut(team_id).bearer = None;
+                            }
+                            if let Err(e) = session.save() {
+                                tracing::error!("Failed to clear zeroclaw bearer: {}", e);
+                            }
+                        }
+                        self.agent_runner = None;
+                        self.start_zeroclaw_auto();
+                    }
+                }
                 AppAsyncEvent::ZeroClawConnectionFinished { runner, error } => {
+                    let status = match &error {
+                        Some(err) => StageStatus::Failed(err.clone()),
+                        None => StageStatus::Done,
+                    };
+                    self.set_init_stage(InitStage::Agent, status);
                     if let Some(err) = error {
                         self.agent_status = AgentStatus::Error(err.clone());
                         self.report_error("ZeroClaw connection failed", err);
+                        // Hand a waiting command back rather than drop it.
+                        if let Some(command) = self.pending_agent_command.take() {
+                            if self.input.buffer.is_empty() {
+                                self.input.set_text(command);
+                            }
+                        }
                     } else if let Some(runner) = runner {
                         self.agent_status = AgentStatus::Active;
+                        // A later expiry is a new one: the saved bearer is
+                        // tried again and re-pairing is allowed.
+                        self.zeroclaw_bearer_rejected = false;
 
-                        if let Some(gateway) = runner.get_gateway() {
-                            if let Some(bearer) = gateway.get_bearer() {
-                                if let Some(ref mut session) = self.session {
-                                    session.zeroclaw_bearer = Some(bearer.clone());
-                                    if let Err(e) = session.save() {
-                                        tracing::error!("Failed to save zeroclaw bearer: {}", e);
+                        // Kept for the workspace that connected; others keep theirs.
+                        if let (Some(bearer), Some(session)) =
+                            (runner.bearer(), self.session.as_mut())
+                        {
+                            let team_id = self.active_workspace_id.as_deref();
+                            if session.zeroclaw_bearer_for(team_id) != Some(bearer) {
+                                match team_id {
+                                    Some(team_id) => {
+                                        session.agent_mut(team_id).bearer = Some(bearer.to_string())
                                     }
+                                    None => session.zeroclaw_bearer = Some(bearer.to_string()),
+                                }
+                                if let Err(e) = session.save() {
+                                    tracing::error!("Failed to save zeroclaw bearer: {}", e);
                                 }
                             }
                         }
 
                         self.agent_runner = Some(runner);
+                        self.zeroclaw_connected_at = Some(std::time::Instant::now());
+                        self.watch_zeroclaw_exit();
                         self.clear_error();
+                        if let Some(command) = self.pending_agent_command.take() {
+                            if let Err(e) = self.handle_agent_command(&command) {
+                                self.report_error("Agent command failed", e);
+                            }
+                        }
+                    }
+                }
+                AppAsyncEvent::MissedMessagesLoaded {
+                    channel_id,
+                    gap,
+                    messages,
+                    error,
+                } => {
+                    self.catching_up.remove(&channel_id);
+                    if let Some(err) = error {
+                        self.report_error("Failed to sync missed messages", err);
+                    } else if self.merge_messages(&channel_id, messages) > 0 {
+                        tracing::info!("Synced missed messages in {}", channel_id);
+                        self.history_gaps.insert(channel_id.clone(), gap.after_ts);
+                        self.trim_channel_history(&channel_id);
+                        self.note_loaded_activity(&channel_id);
+                    }
+                }
+                AppAsyncEvent::GatewayHealthChecked { result } => {
+                    if let Some(panel) = self.connection_status.as_mut() {
+                        panel.gateway_check = Some(result);
                     }
                 }
             }
         }
     }
 }
+
+/// Drops all but the newest `ChannelHistoryLoaded` per channel; flipping
+/// through channels quickly queues loads whose results would be replaced
+/// straight away.
+fn coalesce_history_loads(events: &mut Vec<AppAsyncEvent>) {
+    let mut seen = HashSet::new();
+    let keep: Vec<bool> = events
+        .iter()
+        .rev()
+        .map(|event| match event {
+            AppAsyncEvent::ChannelHistoryLoaded { channel_id, .. } => {
+                seen.insert(channel_id.clone())
+            }
+            _ => true,
+        })
+        .collect();
+    let mut keep = keep.into_iter().rev();
+    events.retain(|_| keep.next().unwrap_or(true));
+}
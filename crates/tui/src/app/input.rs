@@ -61,17 +61,98 @@ fn fuzzy_match(query: &str, target: &str) -> Option<i32> {
     }
 }
 
+/// Lift for channels of the active workspace when the switcher searches all
+/// of them, so a close match at home still outranks one elsewhere.
+const ACTIVE_WORKSPACE_BONUS: i32 = 20;
+
 impl App {
+    /// Recomputes the picker's matches from the query and the current
+    /// channel lists, so it also follows lists that refresh while open.
+    pub(super) fn refresh_channel_picker(&mut self) {
+        let Some(picker) = self.channel_picker.as_mut() else {
+            return;
+        };
+        let switcher = picker.trigger_position == 0;
+        let (everywhere, query) = match picker.query.strip_prefix('*') {
+            Some(rest) if switcher => (true, rest),
+            _ => (switcher && picker.all_workspaces, picker.query.as_str()),
+        };
+        let query = query.to_lowercase();
+
+        let active = self.active_workspace_id.as_deref();
+        let mut candidates: Vec<(i32, PickerEntry)> = self
+            .channels
+            .iter()
+            .map(|ch| (ACTIVE_WORKSPACE_BONUS, ch, None))
+            .chain(
+                self.workspaces
+                    .iter()
+                    .filter(|_| everywhere)
+                    .filter(|ws| Some(ws.workspace.team_id.as_str()) != active)
+                    .flat_map(|ws| {
+                        ws.channels
+                            .iter()
+                            .map(|ch| (0, ch, Some(ws.workspace.team_id.clone())))
+                    }),
+            )
+            .filter_map(|(bonus, ch, team_id)| {
+                let score = if query.is_empty() {
+                    0
+                } else {
+                    fuzzy_match(&query, &ch.name)?
+                };
+                Some((
+                    score + bonus,
+                    PickerEntry {
+                        channel: ch.clone(),
+                        team_id,
+                    },
+                ))
+            })
+            .collect();
+
+        // Sort by score descending (higher score = better match)
+        candidates.sort_by_key(|entry| std::cmp::Reverse(entry.0));
+
+        picker.filtered_channels = candidates.into_iter().map(|(_, entry)| entry).collect();
+        picker.selected_index = picker
+            .selected_index
+            .min(picker.filtered_channels.len().saturating_sub(1));
+    }
+
     pub fn handle_event(&mut self, event: Event) -> Result<bool> {
+        if matches!(event, Event::Key(_) | Event::Mouse(_)) {
+            self.last_input_at = std::time::Instant::now();
+        }
+        // Once the session is read, a key skips the rest of the splash.
+        if self.is_loading && matches!(event, Event::Key(_)) && self.session_read() {
+            self.finish_splash();
+            return Ok(false);
+        }
         match event {
             Event::Key(key) => self.handle_key_event(key),
             Event::Mouse(mouse) => self.handle_mouse_event(mouse),
             Event::Resize(_, _) => Ok(false),
+            Event::FocusGained => {
+                self.terminal_focused = true;
+                self.away_unread = 0;
+                self.away_mentions = 0;
+                Ok(false)
+            }
+            Event::FocusLost => {
+                self.terminal_focused = false;
+                Ok(false)
+            }
             _ => Ok(false),
         }
     }
 
     fn handle_key_event(&mut self, key: KeyEvent) -> Result<bool> {
+        if self.copy_mode {
+            self.copy_mode = false;
+            return Ok(false);
+        }
+
         if key.code == KeyCode::Char('q') && key.modifiers.contains(KeyModifiers::CONTROL) {
             return Ok(true);
         }
@@ -90,11 +171,127 @@ impl App {
             return Ok(false);
         }
 
+        if key.code == KeyCode::F(5) {
+            self.refresh_channel_list(true);
+            return Ok(false);
+        }
+
+        if key.code == KeyCode::F(9) {
+            self.copy_mode = true;
+            return Ok(false);
+        }
+
+        if key.code == KeyCode::F(10) {
+            if self.workspace_issues_view.take().is_none() && !self.workspace_issues.is_empty() {
+                self.workspace_issues_view = Some(WorkspaceIssuesView::default());
+            }
+            return Ok(false);
+        }
+
+        if let Some(view) = self.workspace_issues_view.as_mut() {
+            let selected = self
+                .workspace_issues
+                .get(view.selected)
+                .map(|issue| issue.team_id.clone());
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => self.workspace_issues_view = None,
+                KeyCode::Up | KeyCode::Char('k') => {
+                    view.selected = view.selected.saturating_sub(1);
+                }
+                KeyCode::Down | KeyCode::Char('j')
+                    if view.selected + 1 < self.workspace_issues.len() =>
+                {
+                    view.selected += 1;
+                }
+                KeyCode::Enter | KeyCode::Char('a') => {
+                    if let Some(team_id) = selected {
+                        self.reauthenticate_workspace(&team_id);
+                    }
+                }
+                KeyCode::Char('r') => {
+                    if let Some(team_id) = selected {
+                        self.reconnect_workspace(&team_id);
+                    }
+                }
+                KeyCode::Char('x') | KeyCode::Delete => {
+                    if let Some(team_id) = selected {
+                        self.remove_workspace(&team_id);
+                    }
+                }
+                _ => {}
+            }
+            return Ok(false);
+        }
+
+        if key.code == KeyCode::F(11) {
+            if self.connection_status.take().is_none() {
+                self.open_connection_status();
+            }
+            return Ok(false);
+        }
+
+        if self.connection_status.is_some() {
+            if key.code == KeyCode::Esc {
+                self.connection_status = None;
+            }
+            return Ok(false);
+        }
+
+        if key.code == KeyCode::F(12) {
+            self.show_memory_stats = !self.show_memory_stats;
+            return Ok(false);
+        }
+
+        if self.show_memory_stats {
+            if key.code == KeyCode::Esc {
+                self.show_memory_stats = false;
+            }
+            return Ok(false);
+        }
+
+        if self.command_palette.is_some() {
+            return self.handle_command_palette_key(key);
+        }
+
+        if self.user_filter_picker.is_some() {
+            self.handle_user_filter_key(key);
+            return Ok(false);
+        }
+
+        if self.reaction_picker.is_some() {
+            self.handle_reaction_picker_key(key);
+            return Ok(false);
+        }
+
+        if let Some(count) = self.rerun_count.take() {
+            match key.code {
+                KeyCode::Char('.') | KeyCode::Enter => self.rerun_agent_command(count),
+                KeyCode::Char(c) if self.rerun_slot(c).is_some() => {
+                    self.rerun_count = self.rerun_slot(c);
+                }
+                _ => {}
+            }
+            return Ok(false);
+        }
+
         if self.show_error_details {
             match key.code {
+                KeyCode::Enter | KeyCode::Char('R')
+                    if self.retry_send(self.error_history_scroll) =>
+                {
+                    self.show_error_details = false;
+                }
                 KeyCode::Esc | KeyCode::Enter | KeyCode::Char('E') => {
                     self.show_error_details = false;
                 }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.error_history_scroll = self.error_history_scroll.saturating_sub(1);
+                }
+                KeyCode::Down | KeyCode::Char('j')
+                    if self.error_history_scroll + 1 < self.error_history.len() =>
+                {
+                    self.error_history_scroll += 1;
+                }
                 _ => {}
             }
             return Ok(false);
@@ -108,7 +305,11 @@ impl App {
                             && !onboarding.client_id.is_empty()
                             && !onboarding.client_secret.is_empty()
                         {
-                            let _ = onboarding.generate_oauth_url(self.config.slack.redirect_port);
+                            let url =
+                                onboarding.generate_oauth_url(self.config.slack.redirect_port);
+                            if let Err(e) = crate::ui::links::open_in_browser(&url) {
+                                tracing::warn!("Could not open the OAuth URL in a browser: {}", e);
+                            }
                         } else if !onboarding.oauth_code.is_empty() {
                             let code = onboarding.oauth_code.clone();
                             if let Some(ref mut o) = self.onboarding {
@@ -127,7 +328,16 @@ impl App {
                     }
                 }
                 KeyCode::Esc => {
-                    if matches!(onboarding.current_screen, OnboardingScreen::Welcome) {
+                    if onboarding.reauth_team.is_some()
+                        && matches!(
+                            onboarding.current_screen,
+                            OnboardingScreen::Welcome | OnboardingScreen::SlackCredentials
+                        )
+                    {
+                        // Backing out of a re-authentication returns to the app.
+                        self.onboarding = None;
+                        return Ok(false);
+                    } else if matches!(onboarding.current_screen, OnboardingScreen::Welcome) {
                         self.should_quit = true;
                     } else if matches!(onboarding.current_screen, OnboardingScreen::OAuthFlow) {
                         onboarding.oauth_code.clear();
@@ -143,12 +353,11 @@ impl App {
                         onboarding.toggle_field();
                     }
                 }
-                KeyCode::Char('c') => {
+                KeyCode::Char('c')
                     if matches!(onboarding.current_screen, OnboardingScreen::OAuthFlow)
-                        && onboarding.oauth_url.is_some()
-                    {
-                        tracing::info!("OAuth URL generated (redacted)");
-                    }
+                        && onboarding.oauth_url.is_some() =>
+                {
+                    tracing::info!("OAuth URL generated (redacted)");
                 }
                 KeyCode::Char(c) => {
                     if matches!(
@@ -180,18 +389,16 @@ impl App {
         if self.show_workspace_picker {
             match key.code {
                 KeyCode::Esc => self.show_workspace_picker = false,
-                KeyCode::Up | KeyCode::Char('k') => {
-                    if self.active_workspace > 0 {
-                        self.active_workspace -= 1;
-                    }
+                KeyCode::Up | KeyCode::Char('k') if self.workspace_picker_cursor > 0 => {
+                    self.workspace_picker_cursor -= 1;
                 }
-                KeyCode::Down | KeyCode::Char('j') => {
-                    if self.active_workspace < self.workspaces.len().saturating_sub(1) {
-                        self.active_workspace += 1;
-                    }
+                KeyCode::Down | KeyCode::Char('j')
+                    if self.workspace_picker_cursor < self.workspaces.len().saturating_sub(1) =>
+                {
+                    self.workspace_picker_cursor += 1;
                 }
                 KeyCode::Enter => {
-                    self.switch_workspace(self.active_workspace);
+                    self.switch_workspace(self.workspace_picker_cursor);
                     self.show_workspace_picker = false;
                 }
                 _ => {}
@@ -214,22 +421,228 @@ impl App {
             return Ok(false);
         }
 
+        if self.agent_offline_prompt.is_some() {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Enter => self.resolve_agent_offline_prompt(true),
+                KeyCode::Char('n') | KeyCode::Esc => self.resolve_agent_offline_prompt(false),
+                _ => {}
+            }
+            return Ok(false);
+        }
+
+        if self.long_message.is_some() {
+            match key.code {
+                KeyCode::Char('s') | KeyCode::Enter => self.resolve_long_message(Some(false)),
+                KeyCode::Char('u') => self.resolve_long_message(Some(true)),
+                KeyCode::Esc => self.resolve_long_message(None),
+                _ => {}
+            }
+            return Ok(false);
+        }
+
+        if self.send_recovery.is_some() {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Enter => self.resolve_send_recovery(true),
+                KeyCode::Char('n') | KeyCode::Esc => self.resolve_send_recovery(false),
+                _ => {}
+            }
+            return Ok(false);
+        }
+
+        if self.confirm_mark_all_read {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Enter => {
+                    self.confirm_mark_all_read = false;
+                    self.mark_all_channels_read();
+                }
+                KeyCode::Char('n') | KeyCode::Esc => {
+                    self.confirm_mark_all_read = false;
+                }
+                _ => {}
+            }
+            return Ok(false);
+        }
+
+        if let Some(ref mut view) = self.saved_view {
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => self.saved_view = None,
+                KeyCode::Down | KeyCode::Char('j') => {
+                    view.selected =
+                        (view.selected + 1).min(self.saved_items.len().saturating_sub(1));
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    view.selected = view.selected.saturating_sub(1);
+                }
+                KeyCode::Enter => self.jump_to_saved(),
+                KeyCode::Char('u') | KeyCode::Delete => self.unsave_selected_saved(),
+                _ => {}
+            }
+            return Ok(false);
+        }
+
+        if self.threads_view.is_some() {
+            let followed = self
+                .active_workspace()
+                .map_or(0, |ws| ws.workspace.threads.len());
+            let Some(view) = self.threads_view.as_mut() else {
+                return Ok(false);
+            };
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => self.threads_view = None,
+                KeyCode::Down | KeyCode::Char('j') => {
+                    view.selected = (view.selected + 1).min(followed.saturating_sub(1));
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    view.selected = view.selected.saturating_sub(1);
+                }
+                KeyCode::Enter => self.jump_to_thread(),
+                KeyCode::Char('u') | KeyCode::Delete => self.unfollow_selected_thread(),
+                _ => {}
+            }
+            return Ok(false);
+        }
+
+        if let Some(ref mut chooser) = self.link_chooser {
+            let picked = match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.link_chooser = None;
+                    None
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    chooser.selected =
+                        (chooser.selected + 1).min(chooser.links.len().saturating_sub(1));
+                    None
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    chooser.selected = chooser.selected.saturating_sub(1);
+                    None
+                }
+                KeyCode::Enter => chooser.links.get(chooser.selected),
+                KeyCode::Char(c) => c
+                    .to_digit(10)
+                    .and_then(|n| chooser.links.get((n as usize).checked_sub(1)?)),
+                _ => None,
+            };
+            if let Some(url) = picked.map(|link| link.url.clone()) {
+                self.link_chooser = None;
+                self.open_link(&url);
+            }
+            return Ok(false);
+        }
+
+        if let Some(ref mut popup) = self.reminders {
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => self.reminders = None,
+                KeyCode::Down | KeyCode::Char('j') => {
+                    popup.selected =
+                        (popup.selected + 1).min(popup.reminders.len().saturating_sub(1));
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    popup.selected = popup.selected.saturating_sub(1);
+                }
+                KeyCode::Char('d') | KeyCode::Delete => self.delete_selected_reminder(),
+                _ => {}
+            }
+            return Ok(false);
+        }
+
+        if let Some(ref mut inspector) = self.raw_inspector {
+            let max_scroll = inspector.json.lines().count().saturating_sub(1) as u16;
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('J') => {
+                    self.raw_inspector = None;
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    inspector.scroll = (inspector.scroll + 1).min(max_scroll);
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    inspector.scroll = inspector.scroll.saturating_sub(1);
+                }
+                KeyCode::PageDown => inspector.scroll = (inspector.scroll + 20).min(max_scroll),
+                KeyCode::PageUp => inspector.scroll = inspector.scroll.saturating_sub(20),
+                KeyCode::Home => inspector.scroll = 0,
+                KeyCode::End => inspector.scroll = max_scroll,
+                KeyCode::Char('c') => self.copy_raw_json(),
+                _ => {}
+            }
+            return Ok(false);
+        }
+
+        if self.show_channel_info {
+            if matches!(key.code, KeyCode::Esc | KeyCode::Enter | KeyCode::Char('I')) {
+                self.show_channel_info = false;
+            }
+            return Ok(false);
+        }
+
         if self.show_jump_to_time {
             match key.code {
                 KeyCode::Esc => {
                     self.show_jump_to_time = false;
                     self.jump_to_time_buffer.clear();
+                    self.jump_to_time_error = None;
                 }
-                KeyCode::Enter => {
-                    self.jump_to_timestamp()?;
+                KeyCode::Enter if self.jump_to_timestamp() => {
                     self.show_jump_to_time = false;
                     self.jump_to_time_buffer.clear();
+                    self.jump_to_time_error = None;
                 }
                 KeyCode::Backspace => {
                     self.jump_to_time_buffer.pop();
+                    self.jump_to_time_error = None;
                 }
                 KeyCode::Char(c) => {
                     self.jump_to_time_buffer.push(c);
+                    self.jump_to_time_error = None;
+                }
+                _ => {}
+            }
+            return Ok(false);
+        }
+
+        if let Some(prompt) = self.pairing_prompt.as_mut() {
+            match key.code {
+                KeyCode::Esc => {
+                    self.pairing_prompt = None;
+                    self.agent_runner = None;
+                    self.agent_status = AgentStatus::Error(
+                        "pairing cancelled — run /agent restart to try again".to_string(),
+                    );
+                }
+                KeyCode::Enter if prompt.code.len() == 6 => self.submit_pairing_code(),
+                KeyCode::Char(c) if c.is_ascii_digit() && prompt.code.len() < 6 => {
+                    prompt.code.push(c);
+                }
+                KeyCode::Backspace => {
+                    prompt.code.pop();
+                }
+                _ => {}
+            }
+            return Ok(false);
+        }
+
+        if self.agent_logs.is_some() {
+            if matches!(key.code, KeyCode::Esc | KeyCode::Enter) {
+                self.agent_logs = None;
+            }
+            return Ok(false);
+        }
+
+        if let Some(popup) = self.agent_history.as_mut() {
+            match key.code {
+                KeyCode::Esc => self.agent_history = None,
+                KeyCode::Enter => self.jump_to_agent_response(),
+                KeyCode::Up => popup.selected = popup.selected.saturating_sub(1),
+                KeyCode::Down if popup.selected + 1 < popup.matches().len() => {
+                    popup.selected += 1;
+                }
+                KeyCode::Char(c) => {
+                    popup.query.push(c);
+                    popup.selected = 0;
+                }
+                KeyCode::Backspace => {
+                    popup.query.pop();
+                    popup.selected = 0;
                 }
                 _ => {}
             }
@@ -246,10 +659,17 @@ impl App {
                 KeyCode::Esc => {
                     self.confirmation_dialog = None;
                 }
+                KeyCode::Tab => {
+                    if let Some(dialog) = self.confirmation_dialog.as_mut() {
+                        dialog.is_editing = !dialog.is_editing;
+                    }
+                }
                 KeyCode::Char(c) => {
                     if let Some(dialog) = self.confirmation_dialog.as_mut() {
                         if dialog.is_editing {
                             dialog.prompt.push(c);
+                        } else if c == 'c' {
+                            dialog.cycle_reply();
                         }
                     }
                 }
@@ -287,67 +707,50 @@ impl App {
                 }
                 KeyCode::Enter => {
                     if let Some(picker) = self.channel_picker.take() {
-                        if let Some(ch) = picker.filtered_channels.get(picker.selected_index) {
+                        if let Some(entry) = picker.filtered_channels.get(picker.selected_index) {
+                            let ch = &entry.channel;
                             if picker.trigger_position > 0 {
                                 self.insert_channel_reference(&ch.name, picker.trigger_position);
                             }
-                            self.sidebar_cursor = self
-                                .channels
-                                .iter()
-                                .position(|c| c.id == ch.id)
-                                .unwrap_or(0);
-                            self.select_channel(self.sidebar_cursor);
+                            if let Some(team_id) = entry.team_id.as_deref() {
+                                if let Some(idx) = self
+                                    .workspaces
+                                    .iter()
+                                    .position(|ws| ws.workspace.team_id == team_id)
+                                {
+                                    self.switch_workspace(idx);
+                                }
+                            }
+                            if let Some(idx) = self.channels.iter().position(|c| c.id == ch.id) {
+                                self.sidebar_cursor = idx;
+                            }
+                            self.select_channel(&ch.id);
                             self.fetch_channel_history(&ch.id)?;
                         }
                     }
                 }
+                KeyCode::Tab => {
+                    if let Some(picker) = self.channel_picker.as_mut() {
+                        if picker.trigger_position == 0 {
+                            picker.all_workspaces = !picker.all_workspaces;
+                            picker.selected_index = 0;
+                        }
+                    }
+                    self.refresh_channel_picker();
+                }
                 KeyCode::Char(c) => {
                     if let Some(picker) = self.channel_picker.as_mut() {
                         picker.query.push(c);
-                        let query = picker.query.to_lowercase();
-
-                        // Use fuzzy matching with scoring
-                        let mut scored_channels: Vec<(i32, Channel)> = self
-                            .channels
-                            .iter()
-                            .filter_map(|ch| {
-                                fuzzy_match(&query, &ch.name).map(|score| (score, ch.clone()))
-                            })
-                            .collect();
-
-                        // Sort by score descending (higher score = better match)
-                        scored_channels.sort_by(|a, b| b.0.cmp(&a.0));
-
-                        picker.filtered_channels =
-                            scored_channels.into_iter().map(|(_, ch)| ch).collect();
                         picker.selected_index = 0;
                     }
+                    self.refresh_channel_picker();
                 }
                 KeyCode::Backspace => {
                     if let Some(picker) = self.channel_picker.as_mut() {
                         picker.query.pop();
-                        let query = picker.query.to_lowercase();
-
-                        if query.is_empty() {
-                            picker.filtered_channels = self.channels.clone();
-                        } else {
-                            // Use fuzzy matching with scoring
-                            let mut scored_channels: Vec<(i32, Channel)> = self
-                                .channels
-                                .iter()
-                                .filter_map(|ch| {
-                                    fuzzy_match(&query, &ch.name).map(|score| (score, ch.clone()))
-                                })
-                                .collect();
-
-                            // Sort by score descending (higher score = better match)
-                            scored_channels.sort_by(|a, b| b.0.cmp(&a.0));
-
-                            picker.filtered_channels =
-                                scored_channels.into_iter().map(|(_, ch)| ch).collect();
-                        }
                         picker.selected_index = 0;
                     }
+                    self.refresh_channel_picker();
                 }
                 _ => {}
             }
@@ -358,20 +761,35 @@ impl App {
         match key.code {
             KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                 self.show_workspace_picker = true;
+                self.workspace_picker_cursor = self.active_workspace_index().unwrap_or(0);
                 return Ok(false);
             }
             KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                 return Ok(false);
             }
+            KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.command_palette = Some(CommandPalette::default());
+                return Ok(false);
+            }
+            KeyCode::Char('k') | KeyCode::Char('K')
+                if key
+                    .modifiers
+                    .contains(KeyModifiers::CONTROL | KeyModifiers::SHIFT) =>
+            {
+                self.command_palette = Some(CommandPalette::default());
+                return Ok(false);
+            }
             KeyCode::Char('k') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                 self.channel_picker = Some(ChannelPicker {
                     query: String::new(),
-                    filtered_channels: self.channels.clone(),
+                    filtered_channels: Vec::new(),
                     selected_index: self
                         .sidebar_cursor
                         .min(self.channels.len().saturating_sub(1)),
                     trigger_position: 0,
+                    all_workspaces: false,
                 });
+                self.refresh_channel_picker();
                 return Ok(false);
             }
             KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
@@ -384,18 +802,19 @@ impl App {
         // Alt+Up/Down switches channels regardless of focus
         if key.modifiers.contains(KeyModifiers::ALT) {
             match key.code {
-                KeyCode::Up => {
-                    if self.sidebar_cursor > 0 {
-                        self.sidebar_cursor -= 1;
-                        self.select_channel(self.sidebar_cursor);
+                KeyCode::Char(c @ '1'..='9') => {
+                    let idx = c as usize - '1' as usize;
+                    if idx < self.workspaces.len() {
+                        self.switch_workspace(idx);
                     }
                     return Ok(false);
                 }
-                KeyCode::Down => {
-                    let max = self.channels.len().saturating_sub(1);
-                    if self.sidebar_cursor < max {
-                        self.sidebar_cursor += 1;
-                        self.select_channel(self.sidebar_cursor);
+                KeyCode::Up | KeyCode::Down => {
+                    let delta = if key.code == KeyCode::Up { -1 } else { 1 };
+                    if self.move_sidebar_cursor(delta) {
+                        if let Some(channel_id) = self.cursor_channel_id() {
+                            self.select_channel(&channel_id);
+                        }
                     }
                     return Ok(false);
                 }
@@ -403,12 +822,33 @@ impl App {
             }
         }
 
+        if key.code == KeyCode::Tab && self.focus == Focus::Input && self.complete_slash_command() {
+            return Ok(false);
+        }
+
         // Tab cycles focus
         if key.code == KeyCode::Tab {
             self.focus = self.focus.next();
+            if self.focus == Focus::Sidebar && self.layout.is_sidebar_hidden() {
+                self.focus = self.focus.next();
+            }
             return Ok(false);
         }
 
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            match key.code {
+                KeyCode::Char('b') => {
+                    self.toggle_sidebar();
+                    return Ok(false);
+                }
+                KeyCode::Char('g') => {
+                    self.toggle_agent_panel();
+                    return Ok(false);
+                }
+                _ => {}
+            }
+        }
+
         // Context menu takes priority when visible
         if self.context_menu.is_some() {
             match key.code {
@@ -440,17 +880,35 @@ impl App {
         // Edit message overlay takes priority
         if self.edit_message.is_some() {
             match key.code {
+                KeyCode::Enter if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                    self.input.handle_char('\n');
+                }
                 KeyCode::Enter => {
                     self.save_edited_message()?;
                 }
                 KeyCode::Esc => {
-                    self.edit_message = None;
+                    self.cancel_edit_message();
+                }
+                KeyCode::Backspace => {
+                    self.input.handle_backspace();
+                }
+                KeyCode::Char(c) => {
+                    self.input.handle_char(c);
                 }
                 _ => {}
             }
             return Ok(false);
         }
 
+        // While a failed send is toasted, `R` retries it (outside the input,
+        // where it would be typed).
+        if key.code == KeyCode::Char('R') && self.focus != Focus::Input {
+            if let Some(idx) = self.retryable_toast() {
+                self.retry_send(idx);
+                return Ok(false);
+            }
+        }
+
         // Dispatch based on current focus
         match self.focus {
             Focus::Sidebar => self.handle_sidebar_keys(key)?,
@@ -461,44 +919,225 @@ impl App {
         Ok(false)
     }
 
-    fn handle_sidebar_keys(&mut self, key: KeyEvent) -> Result<()> {
+    fn handle_command_palette_key(&mut self, key: KeyEvent) -> Result<bool> {
+        let Some(palette) = self.command_palette.as_mut() else {
+            return Ok(false);
+        };
+        let matches = self.keybinds.matching(&palette.query);
         match key.code {
-            KeyCode::Down | KeyCode::Char('j') => {
-                let max = self.channels.len().saturating_sub(1);
-                if self.sidebar_cursor < max {
-                    self.sidebar_cursor += 1;
+            KeyCode::Esc => {
+                self.command_palette = None;
+            }
+            KeyCode::Up => {
+                palette.selected = palette.selected.saturating_sub(1);
+            }
+            KeyCode::Down if palette.selected + 1 < matches.len() => {
+                palette.selected += 1;
+            }
+            KeyCode::Backspace => {
+                palette.query.pop();
+                palette.selected = 0;
+            }
+            KeyCode::Char(c) => {
+                palette.query.push(c);
+                palette.selected = 0;
+            }
+            KeyCode::Enter => {
+                let chosen = matches.get(palette.selected).copied();
+                self.command_palette = None;
+                if let Some(binding) = chosen {
+                    // Replay the binding's key so the action runs exactly as
+                    // if it had been pressed, prompts included.
+                    if let Some(focus) = binding.focus {
+                        self.focus = focus;
+                    }
+                    return self.handle_key_event(KeyEvent::new(binding.key, binding.modifiers));
                 }
             }
+            _ => {}
+        }
+        Ok(false)
+    }
+
+    /// `f` clears an active filter, otherwise opens the picker.
+    fn toggle_message_filter(&mut self) {
+        if self.message_filter.is_active() {
+            self.clear_message_filter();
+            return;
+        }
+        if self.selected_channel_id.is_none() {
+            return;
+        }
+        self.user_filter_picker = Some(UserFilterPicker {
+            authors: self.message_authors(),
+            ..UserFilterPicker::default()
+        });
+    }
+
+    /// Typing filters; with nothing typed, `1`–`5` react at once.
+    /// The count a digit stands for before `.`, when the channel has that
+    /// many agent commands to re-run.
+    fn rerun_slot(&self, c: char) -> Option<usize> {
+        let n = c.to_digit(10).filter(|&n| n > 0)? as usize;
+        (n <= self.channel_agent_commands().len()).then_some(n)
+    }
+
+    fn handle_reaction_picker_key(&mut self, key: KeyEvent) {
+        let Some(picker) = self.reaction_picker.as_mut() else {
+            return;
+        };
+        let count = picker.matches().len();
+        let chosen = match key.code {
+            KeyCode::Esc => {
+                self.reaction_picker = None;
+                None
+            }
+            KeyCode::Up => {
+                picker.selected = picker.selected.saturating_sub(1);
+                None
+            }
+            KeyCode::Down if picker.selected + 1 < count => {
+                picker.selected += 1;
+                None
+            }
+            KeyCode::Enter => picker.matches().get(picker.selected).map(|n| n.to_string()),
+            KeyCode::Char(c @ '1'..='9') if picker.query.is_empty() => {
+                let idx = c as usize - '1' as usize;
+                (idx < QUICK_REACTIONS)
+                    .then(|| picker.names.get(idx).cloned())
+                    .flatten()
+            }
+            KeyCode::Backspace => {
+                picker.query.pop();
+                picker.selected = 0;
+                None
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                picker.query.push(c);
+                picker.selected = 0;
+                None
+            }
+            _ => None,
+        };
+        if let Some(name) = chosen {
+            self.reaction_picker = None;
+            self.toggle_reaction(&name);
+        }
+    }
+
+    fn handle_user_filter_key(&mut self, key: KeyEvent) {
+        let Some(picker) = self.user_filter_picker.as_mut() else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => {
+                self.user_filter_picker = None;
+            }
             KeyCode::Up | KeyCode::Char('k') => {
-                if self.sidebar_cursor > 0 {
-                    self.sidebar_cursor -= 1;
+                picker.selected = picker.selected.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') if picker.selected + 1 < picker.row_count() => {
+                picker.selected += 1;
+            }
+            KeyCode::Enter | KeyCode::Char(' ') if picker.selected == 0 => {
+                picker.has_files = !picker.has_files;
+            }
+            KeyCode::Enter | KeyCode::Char(' ') if picker.selected == 1 => {
+                picker.in_threads = !picker.in_threads;
+            }
+            KeyCode::Enter => {
+                let user_id = picker
+                    .selected
+                    .checked_sub(UserFilterPicker::FIXED_ROWS)
+                    .and_then(|idx| picker.authors.get(idx))
+                    .map(|author| author.user_id.clone());
+                let filter = MessageFilter {
+                    user_id,
+                    show_threads: self.message_filter.show_threads,
+                    has_files: picker.has_files,
+                    in_threads: picker.in_threads,
+                    show_system: self.message_filter.show_system,
+                };
+                self.user_filter_picker = None;
+                self.set_message_filter(filter);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_sidebar_keys(&mut self, key: KeyEvent) -> Result<()> {
+        match key.code {
+            KeyCode::Down | KeyCode::Char('j') => match self.sidebar_pinned {
+                Some(PinnedEntry::Saved) => self.sidebar_pinned = Some(PinnedEntry::Threads),
+                Some(PinnedEntry::Threads) => self.sidebar_pinned = None,
+                None => {
+                    self.move_sidebar_cursor(1);
+                }
+            },
+            KeyCode::Up | KeyCode::Char('k') => match self.sidebar_pinned {
+                Some(_) => self.sidebar_pinned = Some(PinnedEntry::Saved),
+                // Past the first channel are the pinned entries.
+                None => {
+                    if !self.move_sidebar_cursor(-1) {
+                        self.sidebar_pinned = Some(PinnedEntry::Threads);
+                    }
                 }
+            },
+            KeyCode::Enter if self.sidebar_pinned == Some(PinnedEntry::Saved) => {
+                self.open_saved_view()
+            }
+            KeyCode::Enter if self.sidebar_pinned == Some(PinnedEntry::Threads) => {
+                self.open_threads_view()
             }
             KeyCode::Enter => {
-                self.select_channel(self.sidebar_cursor);
-                self.focus = Focus::Messages;
+                if let Some(channel_id) = self.cursor_channel_id() {
+                    self.select_channel(&channel_id);
+                    self.focus = Focus::Messages;
+                }
             }
             KeyCode::Char('i') => {
                 self.focus = Focus::Input;
             }
+            KeyCode::Char('R') => {
+                if let Some(channel_id) = self.cursor_channel_id() {
+                    self.mark_channel_read(&channel_id);
+                }
+            }
+            KeyCode::Char('U') => {
+                if let Some(channel_id) = self.cursor_channel_id() {
+                    self.mark_channel_unread(&channel_id);
+                }
+            }
+            KeyCode::Char('M') => {
+                self.confirm_mark_all_read = true;
+            }
+            KeyCode::Char('a') => self.toggle_show_archived(),
             // Single-letter shortcuts work in sidebar focus
             KeyCode::Char('t') => {
-                if let Some(ref channel) = self.selected_channel {
-                    if let Some(ch) = self.channels.get(*channel) {
-                        let channel_id = ch.id.clone();
-                        self.toggle_thread_collapse(&channel_id);
-                    }
+                if let Some(ch) = self.selected_channel() {
+                    let channel_id = ch.id.clone();
+                    self.toggle_thread_collapse(&channel_id);
                 }
             }
             KeyCode::Char('e') => {
                 self.start_edit_message()?;
             }
-            KeyCode::Char('d') => {
-                self.delete_selected_message()?;
-            }
+            KeyCode::Char('d') => match self.failed_message_at_cursor() {
+                Some(id) => self.discard_failed_message(id),
+                None => self.delete_selected_message()?,
+            },
             KeyCode::Char('D') => {
                 self.load_history_for_date()?;
             }
+            KeyCode::Char('A') => {
+                self.open_agent_history();
+            }
+            KeyCode::Char('J') => {
+                self.open_raw_inspector();
+            }
+            KeyCode::Char('>') => {
+                self.quote_selected_message();
+            }
             KeyCode::Char('r') => {
                 self.show_reaction_picker()?;
             }
@@ -506,26 +1145,10 @@ impl App {
                 self.show_jump_to_time = true;
                 self.jump_to_time_buffer.clear();
             }
-            KeyCode::Char('f') => {
-                self.show_user_filter = !self.show_user_filter;
-                if self.show_user_filter {
-                    if let Some(ref channel) = self.selected_channel {
-                        if let Some(ch) = self.channels.get(*channel) {
-                            if let Some(messages) = self.messages.get(&ch.id) {
-                                if let Some(msg) = messages.back() {
-                                    self.message_filter.user_id = Some(msg.user_id.clone());
-                                }
-                            }
-                        }
-                    }
-                } else {
-                    self.message_filter.user_id = None;
-                }
-            }
-            KeyCode::Char('E') => {
-                if self.last_error.is_some() {
-                    self.show_error_details = !self.show_error_details;
-                }
+            KeyCode::Char('f') => self.toggle_message_filter(),
+            KeyCode::Char('E') if !self.error_history.is_empty() => {
+                self.show_error_details = !self.show_error_details;
+                self.error_history_scroll = 0;
             }
             // Navigation shortcuts for channel sections
             KeyCode::Char('c') => {
@@ -549,92 +1172,118 @@ impl App {
     }
 
     fn handle_messages_keys(&mut self, key: KeyEvent) -> Result<()> {
+        if self.visual_selection.is_some() {
+            match key.code {
+                KeyCode::Down | KeyCode::Char('j') => self.scroll_messages(-1),
+                KeyCode::Up | KeyCode::Char('k') => self.scroll_messages(1),
+                KeyCode::Char('y') => self.yank_visual_selection(),
+                KeyCode::Esc | KeyCode::Char('v') => self.visual_selection = None,
+                _ => {}
+            }
+            return Ok(());
+        }
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
         match key.code {
+            KeyCode::Char('v') => self.start_visual_selection(),
+            KeyCode::Char('.') => self.rerun_agent_command(1),
+            KeyCode::Char(c) if self.rerun_slot(c).is_some() => {
+                self.rerun_count = self.rerun_slot(c);
+            }
+            KeyCode::Char('u') if ctrl => {
+                self.scroll_messages((self.messages_page_size() / 2).max(1) as isize);
+            }
+            KeyCode::Char('d') if ctrl => {
+                self.scroll_messages(-((self.messages_page_size() / 2).max(1) as isize));
+            }
+            KeyCode::PageUp => {
+                self.scroll_messages(self.messages_page_size() as isize);
+            }
+            KeyCode::PageDown => {
+                self.scroll_messages(-(self.messages_page_size() as isize));
+            }
             KeyCode::Down | KeyCode::Char('j') => {
-                if self.scroll_offset > 0 {
-                    self.scroll_offset -= 1;
-                }
+                self.scroll_messages(-1);
             }
             KeyCode::Up | KeyCode::Char('k') => {
-                let max_scroll = self.max_scroll_offset();
-                if self.scroll_offset < max_scroll {
-                    self.scroll_offset += 1;
-                }
+                self.scroll_messages(1);
             }
             KeyCode::Char('i') => {
                 self.focus = Focus::Input;
             }
+            KeyCode::Esc if self.message_filter.is_active() => {
+                self.clear_message_filter();
+            }
             KeyCode::Esc => {
                 self.focus = Focus::Sidebar;
             }
+            KeyCode::Enter => match self.failed_message_at_cursor() {
+                Some(id) => self.retry_failed_message(id),
+                None => self.open_thread_at_cursor(),
+            },
+            KeyCode::Char('I') if self.selected_channel_id.is_some() => {
+                self.show_channel_info = true;
+            }
             // Single-letter shortcuts work in messages focus
             KeyCode::Char('t') => {
                 // Enter thread reply mode for the message at current scroll position
-                if let Some(ref channel) = self.selected_channel {
-                    if let Some(ch) = self.channels.get(*channel) {
-                        if let Some(messages) = self.messages.get(&ch.id) {
-                            let msg_index = self
-                                .current_message_index()
-                                .unwrap_or_else(|| messages.len().saturating_sub(1));
-                            if let Some(msg) = messages.get(msg_index) {
-                                // Only enter thread mode if message has replies or is a thread parent
-                                let has_replies = msg.reply_count.is_some_and(|c| c > 0);
-                                let is_thread_parent = msg.thread_ts.is_some() || has_replies;
-
-                                if is_thread_parent {
-                                    let thread_ts =
-                                        msg.thread_ts.clone().unwrap_or_else(|| msg.ts.clone());
-
-                                    // Set active thread for reply mode
-                                    self.active_threads.insert(ch.id.clone(), thread_ts.clone());
-
-                                    // Auto-load thread replies if not already loaded
-                                    let needs_load = self
-                                        .threads
-                                        .get(&ch.id)
-                                        .map(|threads| {
-                                            !threads.iter().any(|t| t.parent_ts == thread_ts)
-                                        })
-                                        .unwrap_or(true);
-
-                                    if needs_load {
-                                        let _token = ch.id.clone(); // Actually need workspace token
-                                        let channel_id = ch.id.clone();
-                                        let api = self.slack_api.clone();
-                                        let ws_token = self
-                                            .workspaces
-                                            .get(self.active_workspace)
-                                            .map(|ws| ws.workspace.xoxp_token.clone())
-                                            .unwrap_or_default();
-
-                                        self.spawn_app_task(async move {
-                                            match api
-                                                .get_thread_replies(
-                                                    &ws_token,
-                                                    &channel_id,
-                                                    &thread_ts,
-                                                )
-                                                .await
-                                            {
-                                                Ok(replies) => AppAsyncEvent::ThreadRepliesLoaded {
-                                                    channel_id,
-                                                    parent_ts: thread_ts,
-                                                    replies,
-                                                    error: None,
-                                                },
-                                                Err(e) => AppAsyncEvent::ThreadRepliesLoaded {
-                                                    channel_id,
-                                                    parent_ts: thread_ts,
-                                                    replies: Vec::new(),
-                                                    error: Some(App::actionable_error(&e)),
-                                                },
-                                            }
-                                        });
-                                    }
+                if let Some(ch) = self.selected_channel().cloned() {
+                    if let Some(messages) = self.messages.get(&ch.id) {
+                        let msg_index = self
+                            .current_message_index()
+                            .unwrap_or_else(|| messages.len().saturating_sub(1));
+                        if let Some(msg) = messages.get(msg_index) {
+                            // Only enter thread mode if message has replies or is a thread parent
+                            let has_replies = msg.reply_count.is_some_and(|c| c > 0);
+                            let is_thread_parent = msg.thread_ts.is_some() || has_replies;
 
-                                    // Switch to input focus to type the reply
-                                    self.focus = Focus::Input;
+                            if is_thread_parent {
+                                let thread_ts =
+                                    msg.thread_ts.clone().unwrap_or_else(|| msg.ts.clone());
+
+                                // Set active thread for reply mode
+                                self.active_threads.insert(ch.id.clone(), thread_ts.clone());
+
+                                // Auto-load thread replies if not already loaded
+                                let needs_load = self
+                                    .threads
+                                    .get(&ch.id)
+                                    .map(|threads| {
+                                        !threads.iter().any(|t| t.parent_ts == thread_ts)
+                                    })
+                                    .unwrap_or(true);
+
+                                if needs_load {
+                                    let _token = ch.id.clone(); // Actually need workspace token
+                                    let channel_id = ch.id.clone();
+                                    let api = self.slack_api.clone();
+                                    let ws_token = self
+                                        .active_workspace()
+                                        .map(|ws| ws.workspace.xoxp_token.clone())
+                                        .unwrap_or_default();
+
+                                    self.spawn_app_task_as(TaskKind::History, async move {
+                                        match api
+                                            .get_thread_replies(&ws_token, &channel_id, &thread_ts)
+                                            .await
+                                        {
+                                            Ok(replies) => AppAsyncEvent::ThreadRepliesLoaded {
+                                                channel_id,
+                                                parent_ts: thread_ts,
+                                                replies,
+                                                error: None,
+                                            },
+                                            Err(e) => AppAsyncEvent::ThreadRepliesLoaded {
+                                                channel_id,
+                                                parent_ts: thread_ts,
+                                                replies: Vec::new(),
+                                                error: Some(App::actionable_error(&e)),
+                                            },
+                                        }
+                                    });
                                 }
+
+                                // Switch to input focus to type the reply
+                                self.focus = Focus::Input;
                             }
                         }
                     }
@@ -642,21 +1291,19 @@ impl App {
             }
             KeyCode::Char('T') => {
                 // Toggle thread expansion/collapse for current message
-                if let Some(ref channel) = self.selected_channel {
-                    if let Some(ch) = self.channels.get(*channel) {
-                        if let Some(messages) = self.messages.get(&ch.id) {
-                            let msg_index = self
-                                .current_message_index()
-                                .unwrap_or_else(|| messages.len().saturating_sub(1));
-                            if let Some(msg) = messages.get(msg_index) {
-                                let thread_key = msg.thread_ts.clone().or(Some(msg.ts.clone()));
-                                if let Some(thread_key) = thread_key {
-                                    if let Some(threads) = self.threads.get_mut(&ch.id) {
-                                        if let Some(thread) =
-                                            threads.iter_mut().find(|t| t.parent_ts == thread_key)
-                                        {
-                                            thread.toggle_collapse();
-                                        }
+                if let Some(ch) = self.selected_channel().cloned() {
+                    if let Some(messages) = self.messages.get(&ch.id) {
+                        let msg_index = self
+                            .current_message_index()
+                            .unwrap_or_else(|| messages.len().saturating_sub(1));
+                        if let Some(msg) = messages.get(msg_index) {
+                            let thread_key = msg.thread_ts.clone().or(Some(msg.ts.clone()));
+                            if let Some(thread_key) = thread_key {
+                                if let Some(threads) = self.threads.get_mut(&ch.id) {
+                                    if let Some(thread) =
+                                        threads.iter_mut().find(|t| t.parent_ts == thread_key)
+                                    {
+                                        thread.toggle_collapse();
                                     }
                                 }
                             }
@@ -667,12 +1314,24 @@ impl App {
             KeyCode::Char('e') => {
                 self.start_edit_message()?;
             }
-            KeyCode::Char('d') => {
-                self.delete_selected_message()?;
-            }
+            KeyCode::Char('d') => match self.failed_message_at_cursor() {
+                Some(id) => self.discard_failed_message(id),
+                None => self.delete_selected_message()?,
+            },
             KeyCode::Char('D') => {
                 self.load_history_for_date()?;
             }
+            KeyCode::Char('A') => {
+                self.open_agent_history();
+            }
+            KeyCode::Char('J') => {
+                self.open_raw_inspector();
+            }
+            KeyCode::Char('o') => self.open_selected_message_links(),
+            KeyCode::Char('S') => self.toggle_saved_selected(),
+            KeyCode::Char('>') => {
+                self.quote_selected_message();
+            }
             KeyCode::Char('r') => {
                 self.show_reaction_picker()?;
             }
@@ -680,26 +1339,10 @@ impl App {
                 self.show_jump_to_time = true;
                 self.jump_to_time_buffer.clear();
             }
-            KeyCode::Char('f') => {
-                self.show_user_filter = !self.show_user_filter;
-                if self.show_user_filter {
-                    if let Some(ref channel) = self.selected_channel {
-                        if let Some(ch) = self.channels.get(*channel) {
-                            if let Some(messages) = self.messages.get(&ch.id) {
-                                if let Some(msg) = messages.back() {
-                                    self.message_filter.user_id = Some(msg.user_id.clone());
-                                }
-                            }
-                        }
-                    }
-                } else {
-                    self.message_filter.user_id = None;
-                }
-            }
-            KeyCode::Char('E') => {
-                if self.last_error.is_some() {
-                    self.show_error_details = !self.show_error_details;
-                }
+            KeyCode::Char('f') => self.toggle_message_filter(),
+            KeyCode::Char('E') if !self.error_history.is_empty() => {
+                self.show_error_details = !self.show_error_details;
+                self.error_history_scroll = 0;
             }
             _ => {}
         }
@@ -708,6 +1351,12 @@ impl App {
 
     fn handle_input_keys(&mut self, key: KeyEvent) -> Result<()> {
         match key.code {
+            KeyCode::Esc if self.stashed_input.is_some() => {
+                // Cancels an agent draft and restores what it replaced.
+                if let Some(stashed) = self.stashed_input.take() {
+                    self.input.set_text(stashed);
+                }
+            }
             KeyCode::Esc => {
                 // If thread mode is active, deactivate it first (keep input focused)
                 if let Some(ch_id) = self.get_active_channel_id() {
@@ -725,6 +1374,9 @@ impl App {
             KeyCode::Backspace => {
                 self.input.handle_backspace();
             }
+            KeyCode::Up if self.input.buffer.is_empty() => {
+                self.start_edit_last_own_message();
+            }
             KeyCode::Char('#') => {
                 let should_trigger =
                     self.input.buffer.is_empty() || self.input.buffer.ends_with(' ');
@@ -732,10 +1384,12 @@ impl App {
                 if should_trigger {
                     self.channel_picker = Some(ChannelPicker {
                         query: String::new(),
-                        filtered_channels: self.channels.clone(),
+                        filtered_channels: Vec::new(),
                         selected_index: 0,
                         trigger_position: self.input.buffer.len().saturating_sub(1),
+                        all_workspaces: false,
                     });
+                    self.refresh_channel_picker();
                 }
             }
             KeyCode::Char(c) => {
@@ -743,21 +1397,33 @@ impl App {
             }
             _ => {}
         }
+        self.signal_typing();
         Ok(())
     }
 
-    fn handle_mouse_event(&mut self, mouse: MouseEvent) -> Result<bool> {
+    pub(super) fn handle_mouse_event(&mut self, mouse: MouseEvent) -> Result<bool> {
         match mouse.kind {
             MouseEventKind::Down(MouseButton::Left) => {
                 self.last_mouse_pos = (mouse.column, mouse.row);
 
                 if let Some(target) = self.hit_test(mouse.column, mouse.row) {
                     match target {
-                        HitTarget::Channel(idx) => {
-                            self.sidebar_cursor = idx;
-                            self.select_channel(idx);
+                        HitTarget::Channel(channel_id) => {
+                            if let Some(idx) = self.channels.iter().position(|c| c.id == channel_id)
+                            {
+                                self.sidebar_cursor = idx;
+                            }
+                            self.select_channel(&channel_id);
                             self.focus = Focus::Messages;
                         }
+                        HitTarget::Saved => {
+                            self.sidebar_pinned = Some(PinnedEntry::Saved);
+                            self.open_saved_view();
+                        }
+                        HitTarget::Threads => {
+                            self.sidebar_pinned = Some(PinnedEntry::Threads);
+                            self.open_threads_view();
+                        }
                         HitTarget::WorkspaceTab(idx) => {
                             self.switch_workspace(idx);
                         }
@@ -769,6 +1435,11 @@ impl App {
                         }
                         HitTarget::Messages => {
                             self.focus = Focus::Messages;
+                            if let Some((channel_id, ts)) =
+                                self.hit_test_message(mouse.column, mouse.row)
+                            {
+                                self.select_message_by_ts(channel_id, ts);
+                            }
                         }
                         HitTarget::InputBar => {
                             self.focus = Focus::Input;
@@ -781,37 +1452,65 @@ impl App {
                 self.context_menu = None;
             }
             MouseEventKind::Down(MouseButton::Right) => {
-                if let Some(target) = self.hit_test_message(mouse.column, mouse.row) {
-                    self.selected_message = Some(target);
+                if let Some((channel_id, ts)) = self.hit_test_message(mouse.column, mouse.row) {
+                    self.select_message_by_ts(channel_id, ts);
+                    let mut items = vec![
+                        ContextMenuItem {
+                            label: "Reply".to_string(),
+                            action: ContextMenuAction::Reply,
+                        },
+                        ContextMenuItem {
+                            label: "Quote".to_string(),
+                            action: ContextMenuAction::Quote,
+                        },
+                        ContextMenuItem {
+                            label: "React".to_string(),
+                            action: ContextMenuAction::React,
+                        },
+                        ContextMenuItem {
+                            label: "Edit".to_string(),
+                            action: ContextMenuAction::Edit,
+                        },
+                        ContextMenuItem {
+                            label: "Delete".to_string(),
+                            action: ContextMenuAction::Delete,
+                        },
+                        ContextMenuItem {
+                            label: "Copy".to_string(),
+                            action: ContextMenuAction::Copy,
+                        },
+                        ContextMenuItem {
+                            label: "View Thread".to_string(),
+                            action: ContextMenuAction::ViewThread,
+                        },
+                        ContextMenuItem {
+                            label: "Translate".to_string(),
+                            action: ContextMenuAction::Translate,
+                        },
+                    ];
+                    let saved = self
+                        .selected_message
+                        .as_ref()
+                        .is_some_and(|(channel_id, ts)| self.is_saved(channel_id, ts));
+                    items.push(ContextMenuItem {
+                        label: if saved {
+                            "Remove from saved"
+                        } else {
+                            "Save for later"
+                        }
+                        .to_string(),
+                        action: ContextMenuAction::ToggleSaved,
+                    });
+                    if self.config.debug.keep_raw {
+                        items.push(ContextMenuItem {
+                            label: "Inspect JSON".to_string(),
+                            action: ContextMenuAction::InspectJson,
+                        });
+                    }
                     self.context_menu = Some(ContextMenu {
                         x: mouse.column,
                         y: mouse.row,
-                        items: vec![
-                            ContextMenuItem {
-                                label: "Reply".to_string(),
-                                action: ContextMenuAction::Reply,
-                            },
-                            ContextMenuItem {
-                                label: "React".to_string(),
-                                action: ContextMenuAction::React,
-                            },
-                            ContextMenuItem {
-                                label: "Edit".to_string(),
-                                action: ContextMenuAction::Edit,
-                            },
-                            ContextMenuItem {
-                                label: "Delete".to_string(),
-                                action: ContextMenuAction::Delete,
-                            },
-                            ContextMenuItem {
-                                label: "Copy".to_string(),
-                                action: ContextMenuAction::Copy,
-                            },
-                            ContextMenuItem {
-                                label: "View Thread".to_string(),
-                                action: ContextMenuAction::ViewThread,
-                            },
-                        ],
+                        items,
                         selected: 0,
                     });
                 }
@@ -827,15 +1526,10 @@ impl App {
                 self.drag_target = None;
             }
             MouseEventKind::ScrollDown => {
-                if self.scroll_offset > 0 {
-                    self.scroll_offset -= 1;
-                }
+                self.scroll_messages(-(self.config.ui.scroll_lines as isize));
             }
             MouseEventKind::ScrollUp => {
-                let max_scroll = self.max_scroll_offset();
-                if self.scroll_offset < max_scroll {
-                    self.scroll_offset += 1;
-                }
+                self.scroll_messages(self.config.ui.scroll_lines as isize);
             }
             _ => {}
         }
@@ -843,8 +1537,11 @@ impl App {
     }
 
     fn hit_test(&self, col: u16, row: u16) -> Option<HitTarget> {
-        if let (Some(sidebar_rect), Some(agent_rect)) =
-            (self.layout.get_sidebar_rect(), self.layout.get_agent_rect())
+        // Auto-hidden or collapsed panels have no drag handle.
+        if let Some(sidebar_rect) = self
+            .layout
+            .get_sidebar_rect()
+            .filter(|_| !self.layout.is_sidebar_collapsed())
         {
             if row >= sidebar_rect.y && row < sidebar_rect.y + sidebar_rect.height {
                 let sidebar_divider = sidebar_rect.x + sidebar_rect.width;
@@ -852,6 +1549,8 @@ impl App {
                     return Some(HitTarget::SidebarDivider);
                 }
             }
+        }
+        if let Some(agent_rect) = self.layout.get_agent_rect() {
             if row >= agent_rect.y && row < agent_rect.y + agent_rect.height {
                 let agent_divider = agent_rect.x.saturating_sub(1);
                 if col == agent_divider {
@@ -878,17 +1577,26 @@ impl App {
     }
 
     fn hit_sidebar(&self, rect: Rect, _col: u16, row: u16) -> Option<HitTarget> {
-        let relative_row = row.saturating_sub(rect.y + 2);
-        let channel_start = 1u16;
-
-        if relative_row >= channel_start
-            && relative_row < channel_start + self.channels.len() as u16
-        {
-            let idx = (relative_row - channel_start) as usize;
-            return Some(HitTarget::Channel(idx));
+        if self.layout.is_sidebar_collapsed() {
+            let idx = (row - rect.y) as usize + self.sidebar_icon_offset(rect.height);
+            return self
+                .channels
+                .get(idx)
+                .map(|ch| HitTarget::Channel(ch.id.clone()));
         }
 
-        None
+        // Below the top border: the Saved and Threads entries, then the
+        // sections `render_sidebar` draws.
+        let relative_row = row.checked_sub(rect.y + 1)? as usize;
+        match relative_row {
+            0 => return Some(HitTarget::Saved),
+            1 => return Some(HitTarget::Threads),
+            _ => {}
+        }
+        let idx = (*self.sidebar_rows().get(relative_row - 2)?)?;
+        self.channels
+            .get(idx)
+            .map(|ch| HitTarget::Channel(ch.id.clone()))
     }
 
     fn hit_topbar(&self, _rect: Rect, col: u16, row: u16) -> Option<HitTarget> {
@@ -896,9 +1604,10 @@ impl App {
             return None;
         }
 
+        // Tabs follow the " ● " prefix, separated by one space.
         let mut current_col = 3u16;
-        for (idx, ws) in self.workspaces.iter().enumerate() {
-            let tab_width = ws.workspace.team_name.len() as u16 + 4;
+        for idx in 0..self.workspaces.len() {
+            let tab_width = self.workspace_tab_label(idx).chars().count() as u16;
             if col >= current_col && col < current_col + tab_width {
                 return Some(HitTarget::WorkspaceTab(idx));
             }
@@ -913,9 +1622,11 @@ impl App {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 enum HitTarget {
-    Channel(usize),
+    Channel(String),
+    Saved,
+    Threads,
     WorkspaceTab(usize),
     SidebarDivider,
     AgentDivider,
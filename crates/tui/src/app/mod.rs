@@ -2,24 +2,32 @@ use crate::input::{InputMode, InputState};
 use crate::keybinds::Keybinds;
 use crate::onboarding::{OnboardingScreen, OnboardingState};
 use crate::ui::layout::{DragTarget, LayoutState};
+use crate::ui::links::{Link, ScreenLink};
 use crate::ui::panel::PanelType;
+use crate::ui::sanitize::truncate_display;
+use crate::ui::theme::Theme;
 use crate::Config;
 use anyhow::Result;
-use chrono::Utc;
+use chrono::{Local, Utc};
 use ratatui::crossterm::event::{
     Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
 };
 use ratatui::layout::Rect;
 use ratatui::Frame;
-use slack_zc_agent::{AgentRunner, AgentStatus};
+use slack_zc_agent::{AgentRunner, AgentStatus, GatewayError};
 use slack_zc_slack::api::SlackApi;
 use slack_zc_slack::auth::Session;
-use slack_zc_slack::socket::SlackEvent;
-use slack_zc_slack::types::{Channel, Message, Thread, Workspace, WorkspaceState};
-use std::collections::{HashMap, VecDeque};
+use slack_zc_slack::socket::{ConnectionState, SlackEvent};
+use slack_zc_slack::types::{
+    Channel, DeliveryState, Message, SavedItem, Thread, Workspace, WorkspaceState,
+};
+use slack_zc_slack::users::UserDirectory;
+use slack_zc_slack::SlackClient;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::future::Future;
-use std::time::Duration;
-use tokio::sync::mpsc;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, watch};
 use tokio::time::timeout;
 
 mod actions;
@@ -27,25 +35,93 @@ mod effects;
 mod input;
 mod render;
 mod state;
+mod tasks;
+#[cfg(test)]
+mod test_support;
 mod types;
 
-pub use state::{App, ChannelPicker, ConfirmationDialog, Focus};
+pub use state::{App, ChannelPicker, ConfirmationDialog, Focus, PickerEntry};
+pub use tasks::{TaskKind, TaskSupervisor};
 pub use types::{
-    AgentResponse, AppAsyncEvent, ContextMenu, ContextMenuAction, ContextMenuItem, EditState,
-    MessageFilter,
+    reaction_glyph, AgentHistoryPopup, AgentResponse, AppAsyncEvent, ChannelCommand, ChannelMemory,
+    CommandPalette, ConnectionStatusPanel, ContextAnchor, ContextMenu, ContextMenuAction,
+    ContextMenuItem, EditState, ErrorEntry, FilterAuthor, HistoryGap, InitStage, JumpTarget,
+    LinkChooser, MessageFilter, MessageRowMap, PairingPrompt, PendingSend, PinnedEntry, PostTarget,
+    RawInspector, ReactionPicker, RemindersPopup, ReplyTarget, SavedView, SendRecovery,
+    SendRecoveryPrompt, SocketStatus, StageStatus, ThreadsView, UserFilterPicker, WorkspaceIssue,
+    WorkspaceIssuesView, AGENT_RERUN_HISTORY, QUICK_REACTIONS, REACTION_CATALOG,
 };
 
+/// Errors kept for the history popup; older ones are dropped.
+const ERROR_HISTORY_LIMIT: usize = 100;
+/// How long a typing indicator lasts without a fresh `user_typing` event.
+/// Slack re-sends the event every few seconds while someone keeps typing.
+const TYPING_TIMEOUT: Duration = Duration::from_secs(6);
+
 impl App {
+    /// The single way errors reach the user: a toast, the history, and `last_error`.
     pub(super) fn report_error(&mut self, context: &str, error: impl std::fmt::Display) {
         let message = format!("{context}: {}", Self::redact_sensitive(&error.to_string()));
         self.last_error = Some(message.clone());
         tracing::warn!("{message}");
+
+        self.error_history.push_front(ErrorEntry {
+            at: chrono::Local::now(),
+            message,
+            toast_until: std::time::Instant::now()
+                + Duration::from_secs(self.config.ui.toast_seconds),
+            retry: None,
+        });
+        self.error_history.truncate(ERROR_HISTORY_LIMIT);
+    }
+
+    /// Toasts the outcome of something the user asked for.
+    pub(super) fn report_notice(&mut self, message: impl Into<String>) {
+        let until = std::time::Instant::now() + Duration::from_secs(self.config.ui.toast_seconds);
+        self.notice = Some((message.into(), until));
+    }
+
+    /// Reports a failed post and keeps it for `R` to retry.
+    pub(super) fn report_send_error(
+        &mut self,
+        context: &str,
+        error: impl std::fmt::Display,
+        send: PendingSend,
+    ) {
+        self.report_error(context, error);
+        if let Some(entry) = self.error_history.front_mut() {
+            entry.retry = Some(send);
+        }
     }
 
     pub(super) fn actionable_error(error: &anyhow::Error) -> String {
-        slack_zc_slack::error::map_anyhow_error_ref(error)
-            .user_message()
-            .to_string()
+        match slack_zc_slack::error::map_anyhow_error_ref(error) {
+            slack_zc_slack::ApiError::MissingScope { scope } if !scope.is_empty() => format!(
+                "The Slack app lacks the {scope} scope. Add it under OAuth & Permissions → \
+                 User Token Scopes at api.slack.com/apps, reinstall the app, then sign in again."
+            ),
+            mapped => mapped.user_message().to_string(),
+        }
+    }
+
+    /// Short reason shown for a workspace whose token check failed.
+    pub(super) fn workspace_issue_reason(error: &anyhow::Error) -> String {
+        let raw = error.to_string();
+        if raw.contains("token_expired") {
+            "Token expired. Re-authenticate to keep using this workspace.".to_string()
+        } else if raw.contains("token_revoked") {
+            "Token revoked, or the app was uninstalled. Re-authenticate.".to_string()
+        } else {
+            match slack_zc_slack::error::map_anyhow_error_ref(error) {
+                slack_zc_slack::ApiError::Auth(_) => {
+                    "Slack rejected the stored token. Re-authenticate.".to_string()
+                }
+                slack_zc_slack::ApiError::Network(_) | slack_zc_slack::ApiError::Timeout(_) => {
+                    "Could not reach Slack. Check the connection, then retry.".to_string()
+                }
+                _ => Self::actionable_error(error),
+            }
+        }
     }
 
     pub(super) fn clear_error(&mut self) {
@@ -54,25 +130,47 @@ impl App {
     }
 
     fn redact_sensitive(input: &str) -> String {
-        input
-            .replace("xoxp-", "xoxp-[REDACTED]-")
-            .replace("xapp-", "xapp-[REDACTED]-")
-            .replace("xoxb-", "xoxb-[REDACTED]-")
-            .replace("Bearer ", "Bearer [REDACTED]")
+        slack_zc_slack::redact::redact_sensitive(input)
+    }
+
+    /// Web API client with the configured base URL and proxy applied.
+    pub(super) fn build_slack_api(config: &Config) -> SlackApi {
+        SlackApi::new()
+            .with_base_url(config.slack_api_base_url())
+            .with_proxy(config.network.proxy.clone())
     }
 
     pub(super) fn spawn_app_task<F>(&self, future: F)
+    where
+        F: Future<Output = AppAsyncEvent> + Send + 'static,
+    {
+        self.spawn_app_task_as(TaskKind::Other, future);
+    }
+
+    pub(super) fn spawn_app_task_as<F>(&self, kind: TaskKind, future: F)
     where
         F: Future<Output = AppAsyncEvent> + Send + 'static,
     {
         if let Some(tx) = self.app_async_tx.clone() {
-            tokio::spawn(async move {
-                let event = future.await;
-                let _ = tx.send(event);
-            });
+            self.tasks.spawn(kind, tx, future);
         }
     }
 
+    /// A history load for the workspace on screen; its result is dropped
+    /// if the user switches workspace before it arrives.
+    pub(super) fn spawn_view_task<F>(&self, future: F)
+    where
+        F: Future<Output = AppAsyncEvent> + Send + 'static,
+    {
+        let generation = self.tasks.generation();
+        self.spawn_app_task_as(TaskKind::History, async move {
+            AppAsyncEvent::Stamped {
+                generation,
+                event: Box::new(future.await),
+            }
+        });
+    }
+
     pub(super) fn send_app_event(
         tx: &mpsc::UnboundedSender<AppAsyncEvent>,
         event: AppAsyncEvent,
@@ -81,39 +179,767 @@ impl App {
             .map_err(|e| anyhow::anyhow!("failed to send app event: {}", e))
     }
 
+    pub(super) fn active_workspace(&self) -> Option<&WorkspaceState> {
+        let team_id = self.active_workspace_id.as_deref()?;
+        self.workspaces
+            .iter()
+            .find(|ws| ws.workspace.team_id == team_id)
+    }
+
+    pub(super) fn active_workspace_mut(&mut self) -> Option<&mut WorkspaceState> {
+        let team_id = self.active_workspace_id.as_deref()?;
+        self.workspaces
+            .iter_mut()
+            .find(|ws| ws.workspace.team_id == team_id)
+    }
+
+    /// Current position of the active workspace, for drawing tabs and pickers.
+    pub(super) fn active_workspace_index(&self) -> Option<usize> {
+        let team_id = self.active_workspace_id.as_deref()?;
+        self.workspaces
+            .iter()
+            .position(|ws| ws.workspace.team_id == team_id)
+    }
+
+    pub(super) fn selected_channel(&self) -> Option<&Channel> {
+        let id = self.selected_channel_id.as_deref()?;
+        self.channels.iter().find(|ch| ch.id == id)
+    }
+
+    /// Current position of the open channel in `channels`, which is re-sorted
+    /// and extended while pages load.
+    pub(super) fn selected_channel_index(&self) -> Option<usize> {
+        let id = self.selected_channel_id.as_deref()?;
+        self.channels.iter().position(|ch| ch.id == id)
+    }
+
+    /// Id of the channel under the sidebar cursor; `None` on a pinned entry.
+    pub(super) fn cursor_channel_id(&self) -> Option<String> {
+        if self.sidebar_pinned.is_some() {
+            return None;
+        }
+        self.channels
+            .get(self.sidebar_cursor)
+            .map(|ch| ch.id.clone())
+    }
+
+    /// Positions in `channels` the sidebar lists under the current search.
+    pub(super) fn sidebar_channel_indices(&self) -> Vec<usize> {
+        let query = self.search_query.to_lowercase();
+        self.channels
+            .iter()
+            .enumerate()
+            .filter(|(_, ch)| self.show_archived || !ch.is_archived)
+            .filter(|(_, ch)| {
+                query.is_empty()
+                    || ch.name.to_lowercase().contains(&query)
+                    || ch
+                        .user
+                        .as_ref()
+                        .is_some_and(|u| u.to_lowercase().contains(&query))
+            })
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// Channel position behind each sidebar row below the pinned entries, `None`
+    /// for headers, spacers and placeholders. Mirrors the layout drawn by
+    /// `render_sidebar`.
+    pub(super) fn sidebar_rows(&self) -> Vec<Option<usize>> {
+        let visible = self.sidebar_channel_indices();
+        let mut rows = Vec::new();
+        let sections = if self.show_archived { 4 } else { 3 };
+        for section in 0..sections {
+            if section > 0 {
+                rows.push(None);
+            }
+            rows.push(None);
+            let start = rows.len();
+            rows.extend(
+                visible
+                    .iter()
+                    .filter(|idx| Self::sidebar_section(&self.channels[**idx]) == section)
+                    .map(|idx| Some(*idx)),
+            );
+            if rows.len() == start {
+                rows.push(None);
+            }
+        }
+        rows
+    }
+
+    /// Moves the sidebar cursor `delta` rows through the visible channels.
+    pub(super) fn move_sidebar_cursor(&mut self, delta: isize) -> bool {
+        let visible = self.sidebar_channel_indices();
+        let Some(last) = visible.len().checked_sub(1) else {
+            return false;
+        };
+        let current = visible
+            .iter()
+            .position(|idx| *idx == self.sidebar_cursor)
+            .unwrap_or(0);
+        let target = current.saturating_add_signed(delta).min(last);
+        let moved = visible[target] != self.sidebar_cursor;
+        self.sidebar_cursor = visible[target];
+        if moved {
+            self.sidebar_pinned = None;
+        }
+        moved
+    }
+
+    /// Whether `message` is drawn under `previous`'s header: same author,
+    /// same day, within `ui.message_group_minutes`, and neither is a system
+    /// line, call, deleted message or part of a thread. A reconnect gap
+    /// after `previous` also starts a new group.
+    pub(super) fn continues_group(
+        &self,
+        channel_id: &str,
+        previous: &Message,
+        message: &Message,
+    ) -> bool {
+        let window = chrono::Duration::minutes(self.config.ui.message_group_minutes as i64);
+        let plain = |m: &Message| {
+            !m.is_system
+                && !m.is_deleted
+                && m.call.is_none()
+                && m.thread_ts.is_none()
+                && m.reply_count.unwrap_or(0) == 0
+        };
+        let elapsed = message.timestamp - previous.timestamp;
+        window > chrono::Duration::zero()
+            && plain(previous)
+            && plain(message)
+            && previous.user_id == message.user_id
+            && elapsed >= chrono::Duration::zero()
+            && elapsed <= window
+            && previous.timestamp.date_naive() == message.timestamp.date_naive()
+            && self.history_gaps.get(channel_id) != Some(&previous.ts)
+    }
+
+    /// Name to show for `user_id`: the workspace directory, then the name on
+    /// any message they wrote, then the id itself.
+    pub(super) fn user_display_name(&self, user_id: &str) -> String {
+        self.active_workspace()
+            .and_then(|ws| ws.users.get(user_id))
+            .map(|user| user.display_name())
+            .or_else(|| {
+                self.messages
+                    .values()
+                    .flatten()
+                    .find(|m| m.user_id == user_id && m.username != user_id)
+                    .map(|m| m.username.clone())
+            })
+            .unwrap_or_else(|| user_id.to_string())
+    }
+
+    /// Distinct names of the users typing in `channel_id`, in arrival order.
+    pub(super) fn typing_names(&self, channel_id: &str) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for (user_id, _) in self.typing_users.get(channel_id).into_iter().flatten() {
+            let name = self.user_display_name(user_id);
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        names
+    }
+
+    /// "alice is typing…", "alice and bob are typing…" or "3 people are
+    /// typing…" for the bottom of the messages panel.
+    pub(super) fn typing_status(&self, channel_id: &str) -> Option<String> {
+        let names = self.typing_names(channel_id);
+        match names.as_slice() {
+            [] => None,
+            [one] => Some(format!("{} is typing…", one)),
+            [one, two] => Some(format!("{} and {} are typing…", one, two)),
+            _ => Some(format!("{} people are typing…", names.len())),
+        }
+    }
+
+    /// "14 replies · alice, bob +3 · 2h ago" for a thread parent, naming
+    /// the first two participants. Parts Slack did not send are left out.
+    pub(super) fn thread_summary(
+        &self,
+        parent: &Message,
+        now: chrono::DateTime<Utc>,
+    ) -> Option<String> {
+        let count = parent.reply_count.filter(|&count| count > 0)?;
+        let noun = if count == 1 { "reply" } else { "replies" };
+        let mut parts = vec![format!("{} {}", count, noun)];
+        if !parent.reply_users.is_empty() {
+            let names: Vec<String> = parent
+                .reply_users
+                .iter()
+                .take(2)
+                .map(|id| self.user_display_name(id))
+                .collect();
+            let mut who = names.join(", ");
+            if parent.reply_users.len() > names.len() {
+                who.push_str(&format!(" +{}", parent.reply_users.len() - names.len()));
+            }
+            parts.push(who);
+        }
+        if let Some(latest) = parent.latest_reply.as_deref() {
+            parts.push(match Self::activity_age(latest, now).as_str() {
+                "now" => "just now".to_string(),
+                age => format!("{} ago", age),
+            });
+        }
+        Some(parts.join(" · "))
+    }
+
+    /// "slack-zc — #channel (workspace)", with what arrived while the
+    /// terminal was in the background.
+    pub fn window_title(&self) -> String {
+        let mut title = "slack-zc".to_string();
+        let workspace = self.active_workspace().map(|ws| &ws.workspace.team_name);
+        match (self.selected_channel(), workspace) {
+            (Some(channel), Some(workspace)) => {
+                title.push_str(&format!(
+                    " — {} ({})",
+                    Self::title_channel_name(channel),
+                    workspace
+                ));
+            }
+            (None, Some(workspace)) => title.push_str(&format!(" — {}", workspace)),
+            _ => {}
+        }
+        if self.away_unread > 0 {
+            title.push_str(&format!(" · {} new", self.away_unread));
+            if self.away_mentions > 0 {
+                title.push_str(&format!(", {} @", self.away_mentions));
+            }
+        }
+        title
+    }
+
+    fn title_channel_name(channel: &Channel) -> String {
+        if channel.is_im || channel.is_dm {
+            format!("@{}", channel.name)
+        } else {
+            format!("#{}", channel.name)
+        }
+    }
+
+    /// Counts a message from someone else towards the window title while
+    /// the terminal is in the background, and queues a notification for a
+    /// mention or DM there.
+    fn note_arrival(&mut self, channel_id: &str, user_id: &str, text: &str, mention: bool) {
+        if self.terminal_focused {
+            return;
+        }
+        let channel = self
+            .channels
+            .iter()
+            .chain(self.workspaces.iter().flat_map(|ws| ws.channels.iter()))
+            .find(|ch| ch.id == channel_id);
+        let direct = channel.is_some_and(|ch| ch.is_im || ch.is_dm || ch.is_mpim);
+        let muted = self.is_muted(channel_id);
+        if !muted {
+            self.away_unread += 1;
+        }
+        if !(mention || direct && !muted) {
+            return;
+        }
+        self.away_mentions += 1;
+        if self.terminal_notifications {
+            let title = channel
+                .map(Self::title_channel_name)
+                .unwrap_or_else(|| channel_id.to_string());
+            let mut first_line = text.lines().next().unwrap_or_default().to_string();
+            for ws in &self.workspaces {
+                if let Some(id) = &ws.workspace.user_id {
+                    first_line = first_line.replace(&format!("<@{}>", id), "@you");
+                }
+            }
+            let body = format!(
+                "{}: {}",
+                self.user_display_name(user_id),
+                truncate_display(&first_line, 120)
+            );
+            self.pending_notifications.push((title, body));
+        }
+    }
+
+    /// Notifications queued since the last call, oldest first.
+    pub fn take_notifications(&mut self) -> Vec<(String, String)> {
+        std::mem::take(&mut self.pending_notifications)
+    }
+
+    /// Whether anyone is typing in `channel_id`.
+    pub(super) fn is_typing_in(&self, channel_id: &str) -> bool {
+        self.typing_users
+            .get(channel_id)
+            .is_some_and(|users| !users.is_empty())
+    }
+
+    /// Drops typing indicators nobody has refreshed within `TYPING_TIMEOUT`.
+    pub(super) fn expire_typing(&mut self, now: Instant) {
+        self.typing_users.retain(|_, users| {
+            users.retain(|(_, seen)| now.duration_since(*seen) < TYPING_TIMEOUT);
+            !users.is_empty()
+        });
+    }
+
     pub(super) fn current_channel_messages(&self) -> Option<&VecDeque<Message>> {
-        let selected = self.selected_channel?;
-        let channel = self.channels.get(selected)?;
-        self.messages.get(&channel.id)
+        self.messages.get(self.selected_channel_id.as_deref()?)
+    }
+
+    /// Positions in the current channel's history that pass the message filter.
+    pub(super) fn visible_message_indices(&self) -> Vec<usize> {
+        self.current_channel_messages()
+            .map(|messages| {
+                messages
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, m)| self.message_filter.matches(m))
+                    .map(|(idx, _)| idx)
+                    .collect()
+            })
+            .unwrap_or_default()
     }
 
     pub(super) fn current_message_index(&self) -> Option<usize> {
-        let messages = self.current_channel_messages()?;
-        if messages.is_empty() {
+        let visible = self.visible_message_indices();
+        let last = visible.len().checked_sub(1)?;
+        visible.get(last - self.scroll_offset.min(last)).copied()
+    }
+
+    pub(super) fn is_saved(&self, channel_id: &str, ts: &str) -> bool {
+        self.saved_items
+            .iter()
+            .any(|item| item.channel_id == channel_id && item.message.ts == ts)
+    }
+
+    /// Messages from the `v` anchor to the cursor, oldest first, as the
+    /// filter shows them; empty once the anchor's channel is not open.
+    pub(super) fn visual_selection_messages(&self) -> Vec<&Message> {
+        let Some((channel_id, anchor_ts)) = self.visual_selection.as_ref() else {
+            return Vec::new();
+        };
+        if self.get_active_channel_id().as_ref() != Some(channel_id) {
+            return Vec::new();
+        }
+        let (Some(messages), Some(cursor)) =
+            (self.messages.get(channel_id), self.current_message_index())
+        else {
+            return Vec::new();
+        };
+        let Some(anchor) = messages.iter().position(|m| m.ts == *anchor_ts) else {
+            return Vec::new();
+        };
+        messages
+            .range(anchor.min(cursor)..=anchor.max(cursor))
+            .filter(|m| self.message_filter.matches(m))
+            .collect()
+    }
+
+    /// The selection as text to copy: each message under a
+    /// `[date time] author:` line.
+    pub(super) fn visual_selection_text(&self) -> String {
+        self.visual_selection_messages()
+            .iter()
+            .map(|m| {
+                let time = m.timestamp.with_timezone(&chrono::Local);
+                format!(
+                    "[{}] {}: {}",
+                    time.format("%Y-%m-%d %H:%M"),
+                    m.username,
+                    m.text
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Right-click selection in the active channel, else the message under the cursor.
+    pub(super) fn selected_or_cursor_message(&self) -> Option<&Message> {
+        let channel_id = self.get_active_channel_id()?;
+        let messages = self.messages.get(&channel_id)?;
+        self.selected_message
+            .as_ref()
+            .filter(|(channel, _)| *channel == channel_id)
+            .and_then(|(_, ts)| messages.iter().find(|message| message.ts == *ts))
+            .or_else(|| {
+                self.current_message_index()
+                    .and_then(|idx| messages.get(idx))
+            })
+    }
+
+    /// Links in a message's text and blocks, each URL once, in order.
+    pub(super) fn message_links(message: &Message) -> Vec<Link> {
+        let mut links: Vec<Link> = Vec::new();
+        for text in std::iter::once(&message.text).chain(&message.rendered_blocks) {
+            for link in crate::ui::links::linkify(text).links {
+                if !links.iter().any(|known| known.url == link.url) {
+                    links.push(link);
+                }
+            }
+        }
+        links
+    }
+
+    /// Thread under the message cursor, falling back to the open thread.
+    pub(super) fn selected_thread_ts(&self) -> Option<String> {
+        let channel_id = self.get_active_channel_id()?;
+        let messages = self.messages.get(&channel_id);
+        let thread_of = |message: &Message| {
+            message.thread_ts.clone().or_else(|| {
+                message
+                    .reply_count
+                    .filter(|count| *count > 0)
+                    .map(|_| message.ts.clone())
+            })
+        };
+
+        let selected = self
+            .selected_message
+            .as_ref()
+            .filter(|(channel, _)| *channel == channel_id)
+            .and_then(|(_, ts)| messages?.iter().find(|message| message.ts == *ts));
+        let under_cursor = self
+            .current_message_index()
+            .and_then(|idx| messages?.get(idx));
+
+        selected
+            .and_then(thread_of)
+            .or_else(|| under_cursor.and_then(thread_of))
+            .or_else(|| self.active_threads.get(&channel_id).cloned())
+    }
+
+    /// Snapshot of where a reply to the current channel should be posted.
+    /// An explicit thread wins; otherwise the channel's active thread, if any.
+    pub(super) fn post_target(&self, explicit_thread: Option<String>) -> Option<PostTarget> {
+        let channel = self.selected_channel()?;
+        let token = self.active_workspace()?.workspace.xoxp_token.clone();
+        let thread_ts = explicit_thread.or_else(|| self.active_threads.get(&channel.id).cloned());
+        Some(PostTarget {
+            channel_id: channel.id.clone(),
+            channel_name: channel.name.clone(),
+            thread_ts,
+            token,
+        })
+    }
+
+    /// Sidebar section a conversation is listed under: channels, DMs, group
+    /// DMs, archived.
+    pub(super) fn sidebar_section(channel: &Channel) -> u8 {
+        match (channel.is_dm, channel.is_mpim) {
+            _ if channel.is_archived => 3,
+            (_, true) => 2,
+            (true, false) => 1,
+            (false, false) => 0,
+        }
+    }
+
+    pub(super) fn is_archived_channel(&self, channel_id: &str) -> bool {
+        self.channels
+            .iter()
+            .any(|ch| ch.id == channel_id && ch.is_archived)
+    }
+
+    /// Why the current user can't post in the channel (or, with
+    /// `in_thread`, reply in its threads), when Slack restricts who may.
+    pub(super) fn read_only_reason(&self, channel_id: &str, in_thread: bool) -> Option<String> {
+        let restriction = self
+            .channels
+            .iter()
+            .find(|ch| ch.id == channel_id)?
+            .posting_restriction(in_thread)?;
+        let me = self.current_user_id().unwrap_or_default();
+        let (is_admin, is_owner) = self
+            .active_workspace()
+            .and_then(|ws| ws.users.get(&me))
+            .map_or((false, false), |user| (user.is_admin, user.is_owner));
+        if restriction.allows(&me, is_admin, is_owner) {
             return None;
         }
+        let action = if in_thread { "reply" } else { "post" };
+        Some(format!(
+            "read-only channel: only {} can {}",
+            restriction.describe(),
+            action
+        ))
+    }
+
+    /// Keeps `channels` in sidebar order so cursor movement matches what is
+    /// drawn, without losing the cursor.
+    pub(super) fn sort_channels_by_section(&mut self) {
+        let cursor_id = self.cursor_channel_id();
+
+        if self.config.ui.sort_by_activity {
+            let activity = &self.channel_activity;
+            self.channels.sort_by(|a, b| {
+                Self::sidebar_section(a)
+                    .cmp(&Self::sidebar_section(b))
+                    .then_with(|| activity.get(&b.id).cmp(&activity.get(&a.id)))
+            });
+        } else {
+            self.channels.sort_by_key(Self::sidebar_section);
+        }
+
+        if let Some(id) = cursor_id {
+            if let Some(idx) = self.channels.iter().position(|ch| ch.id == id) {
+                self.sidebar_cursor = idx;
+            }
+        }
+    }
+
+    /// Records `ts` as the newest message in `channel_id` when it is newer
+    /// than what is known; true when it was.
+    pub(super) fn note_channel_activity(&mut self, channel_id: &str, ts: &str) -> bool {
+        let newer = self
+            .channel_activity
+            .get(channel_id)
+            .is_none_or(|known| known.as_str() < ts);
+        if newer {
+            self.channel_activity
+                .insert(channel_id.to_string(), ts.to_string());
+        }
+        newer
+    }
 
-        Some(
+    /// The newest message loaded into `channel_id` counts as its activity.
+    pub(super) fn note_loaded_activity(&mut self, channel_id: &str) {
+        let newest = self.messages.get(channel_id).and_then(|messages| {
             messages
-                .len()
-                .saturating_sub(1 + self.scroll_offset.min(messages.len().saturating_sub(1))),
-        )
+                .iter()
+                .filter(|m| !m.is_system && !m.is_unconfirmed())
+                .map(|m| m.ts.clone())
+                .max()
+        });
+        if let Some(ts) = newest {
+            if self.note_channel_activity(channel_id, &ts) && self.config.ui.sort_by_activity {
+                self.sort_channels_by_section();
+            }
+        }
+    }
+
+    /// Copies known activity into a workspace's channel list, so the next
+    /// cache save keeps it.
+    pub(super) fn stamp_channel_activity(&mut self, ws_idx: usize) {
+        let activity = &self.channel_activity;
+        if let Some(ws) = self.workspaces.get_mut(ws_idx) {
+            for channel in &mut ws.channels {
+                if let Some(ts) = activity.get(&channel.id) {
+                    channel.latest_ts = Some(ts.clone());
+                }
+            }
+        }
+    }
+
+    /// "now", "5m", "3h", "2d", "3w", "4mo" or "1y" since `ts`.
+    pub(super) fn activity_age(ts: &str, now: chrono::DateTime<Utc>) -> String {
+        let secs = ts
+            .split('.')
+            .next()
+            .and_then(|secs| secs.parse::<i64>().ok())
+            .unwrap_or(0);
+        let minutes = (now.timestamp() - secs).max(0) / 60;
+        match minutes {
+            0 => "now".to_string(),
+            m if m < 60 => format!("{}m", m),
+            m if m < 60 * 24 => format!("{}h", m / 60),
+            m if m < 60 * 24 * 7 => format!("{}d", m / (60 * 24)),
+            m if m < 60 * 24 * 30 => format!("{}w", m / (60 * 24 * 7)),
+            m if m < 60 * 24 * 365 => format!("{}mo", m / (60 * 24 * 30)),
+            m => format!("{}y", m / (60 * 24 * 365)),
+        }
     }
 
     pub(super) fn max_scroll_offset(&self) -> usize {
-        self.current_channel_messages()
-            .map(|messages| messages.len().saturating_sub(1))
-            .unwrap_or(0)
+        self.visible_message_indices().len().saturating_sub(1)
+    }
+
+    /// Applies `filter`, keeping the cursor on the same message when it stays
+    /// visible and otherwise falling back to the newest visible one.
+    pub(super) fn set_message_filter(&mut self, filter: MessageFilter) {
+        let cursor_ts = self
+            .current_message_index()
+            .and_then(|idx| Some(self.current_channel_messages()?.get(idx)?.ts.clone()));
+        self.message_filter = filter;
+
+        let visible = self.visible_message_indices();
+        let position = cursor_ts.and_then(|ts| {
+            let messages = self.current_channel_messages()?;
+            visible.iter().position(|idx| messages[*idx].ts == ts)
+        });
+        self.scroll_offset = match position {
+            Some(pos) => visible.len() - 1 - pos,
+            None => 0,
+        };
+    }
+
+    pub(super) fn clear_message_filter(&mut self) {
+        self.set_message_filter(MessageFilter {
+            show_threads: self.message_filter.show_threads,
+            show_system: self.message_filter.show_system,
+            ..MessageFilter::default()
+        });
+    }
+
+    /// Distinct authors in the loaded history, most active first.
+    pub(super) fn message_authors(&self) -> Vec<FilterAuthor> {
+        let mut authors: Vec<FilterAuthor> = Vec::new();
+        for message in self.current_channel_messages().into_iter().flatten() {
+            if message.is_deleted || message.is_system || message.user_id.is_empty() {
+                continue;
+            }
+            match authors.iter_mut().find(|a| a.user_id == message.user_id) {
+                Some(author) => author.count += 1,
+                None => authors.push(FilterAuthor {
+                    user_id: message.user_id.clone(),
+                    name: message.username.clone(),
+                    count: 1,
+                }),
+            }
+        }
+        authors.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+        authors
+    }
+
+    /// Moves the message cursor `delta` messages towards older history
+    /// (negative towards the bottom), clamped to the channel.
+    /// Scrolling past the oldest loaded message fetches the page before it.
+    /// Scrolling down past the end of a context window fetches the page
+    /// after it.
+    pub(super) fn scroll_messages(&mut self, delta: isize) {
+        let target = self.scroll_offset.saturating_add_signed(delta);
+        if delta > 0 && target > self.max_scroll_offset() {
+            self.fetch_older_history();
+        }
+        if delta < 0 && self.newer_history_offset().is_some_and(|at| target <= at) {
+            if let Some(channel_id) = self.selected_channel_id.clone() {
+                self.fetch_newer_history(&channel_id);
+            }
+        }
+        self.scroll_offset = target.min(self.max_scroll_offset());
+        if delta != 0 {
+            self.context_anchor = None;
+        }
+    }
+
+    /// Cursor position, counted from the bottom like `scroll_offset`, of
+    /// the last message before the unloaded part of a context window.
+    fn newer_history_offset(&self) -> Option<usize> {
+        let channel_id = self.selected_channel_id.as_deref()?;
+        let after_ts = self.newer_history.get(channel_id)?;
+        self.messages
+            .get(channel_id)?
+            .iter()
+            .filter(|m| self.message_filter.matches(m))
+            .rev()
+            .position(|m| &m.ts == after_ts)
+    }
+
+    /// Raises the external-sharing banner the first time a Slack Connect
+    /// channel is open this session; any other channel clears it.
+    pub(super) fn note_external_channel(&mut self, channel_id: &str) {
+        if self.external_banner.as_deref() != Some(channel_id) {
+            self.external_banner = None;
+        }
+        let external = self
+            .channels
+            .iter()
+            .any(|ch| ch.id == channel_id && ch.is_ext_shared);
+        if external
+            && self.config.ui.warn_external_channels
+            && self.external_warned.insert(channel_id.to_string())
+        {
+            self.external_banner = Some(channel_id.to_string());
+        }
+    }
+
+    /// Drops the oldest messages past `ui.max_messages_per_channel`. The open
+    /// channel is left alone while scrolled back, so the view doesn't shift
+    /// under the reader; the next message after returning trims it.
+    pub(super) fn trim_channel_history(&mut self, channel_id: &str) {
+        if self.selected_channel_id.as_deref() == Some(channel_id) && self.scroll_offset > 0 {
+            return;
+        }
+        let cap = self.config.ui.max_messages_per_channel.max(1);
+        let Some(messages) = self.messages.get_mut(channel_id) else {
+            return;
+        };
+        if messages.len() > cap {
+            messages.drain(..messages.len() - cap);
+            self.history_exhausted.remove(channel_id);
+        }
+    }
+
+    /// Loaded messages and threads per channel with a rough byte count,
+    /// largest first, for the F12 stats view.
+    pub(super) fn memory_stats(&self) -> Vec<ChannelMemory> {
+        let mut stats: Vec<ChannelMemory> = self
+            .messages
+            .iter()
+            .map(|(channel_id, messages)| {
+                let threads = self.threads.get(channel_id);
+                let replies = threads.into_iter().flatten().flat_map(|t| &t.replies);
+                ChannelMemory {
+                    name: self
+                        .channels
+                        .iter()
+                        .find(|ch| ch.id == *channel_id)
+                        .map(|ch| ch.name.clone())
+                        .unwrap_or_else(|| channel_id.clone()),
+                    messages: messages.len(),
+                    threads: threads.map_or(0, Vec::len),
+                    bytes: messages
+                        .iter()
+                        .chain(replies)
+                        .map(approx_message_bytes)
+                        .sum(),
+                }
+            })
+            .collect();
+        stats.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.name.cmp(&b.name)));
+        stats
+    }
+
+    /// Messages that fit on one screen, from the last frame's row layout,
+    /// so a resize changes the paging stride.
+    pub(super) fn messages_page_size(&self) -> usize {
+        let mut visible: Vec<&String> = self.message_rows.rows.iter().flatten().collect();
+        visible.dedup();
+        if visible.is_empty() {
+            (self.message_rows.area.height as usize / 2).max(1)
+        } else {
+            visible.len()
+        }
     }
 }
 
+/// Heap and inline size of a message, ignoring allocator slack.
+fn approx_message_bytes(message: &Message) -> usize {
+    let strings = [
+        &message.ts,
+        &message.user_id,
+        &message.username,
+        &message.text,
+    ];
+    std::mem::size_of::<Message>()
+        + strings.iter().map(|s| s.len()).sum::<usize>()
+        + message.thread_ts.as_ref().map_or(0, String::len)
+        + message.raw.as_ref().map_or(0, String::len)
+        + message
+            .rendered_blocks
+            .iter()
+            .map(String::len)
+            .sum::<usize>()
+        + message.reactions.len() * std::mem::size_of::<slack_zc_slack::types::Reaction>()
+        + message.files.len() * std::mem::size_of::<slack_zc_slack::types::File>()
+}
+
 #[cfg(test)]
 mod tests {
-    use super::App;
+    use super::{App, Focus};
+    use crate::ui::links::ScreenLink;
     use crate::Config;
     use chrono::Utc;
+    use ratatui::crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
     use slack_zc_slack::socket::SlackEvent;
-    use slack_zc_slack::types::Message;
+    use slack_zc_slack::types::{Channel, Message, Workspace, WorkspaceState};
 
     fn sample_message(thread_ts: Option<&str>) -> Message {
         Message {
@@ -124,12 +950,21 @@ mod tests {
             thread_ts: thread_ts.map(str::to_string),
             timestamp: Utc::now(),
             is_agent: false,
+            is_bot: false,
             reactions: Vec::new(),
             is_edited: false,
             is_deleted: false,
             files: Vec::new(),
             reply_count: None,
+            latest_reply: None,
+            reply_users: Vec::new(),
             last_read: None,
+            rendered_blocks: Vec::new(),
+            is_system: false,
+            subtype: None,
+            call: None,
+            raw: None,
+            delivery: None,
         }
     }
 
@@ -140,12 +975,12 @@ mod tests {
 
         tx.send(SlackEvent::Message {
             channel: "C_ONE".to_string(),
-            message: sample_message(None),
+            message: Box::new(sample_message(None)),
         })
         .expect("send first event");
         tx.send(SlackEvent::Message {
             channel: "C_TWO".to_string(),
-            message: sample_message(Some("1730000000.100000")),
+            message: Box::new(sample_message(Some("1730000000.100000"))),
         })
         .expect("send second event");
 
@@ -162,12 +997,12 @@ mod tests {
 
         tx.send(SlackEvent::Message {
             channel: "C_ONE".to_string(),
-            message: sample_message(Some("1000.1")),
+            message: Box::new(sample_message(Some("1000.1"))),
         })
         .expect("send first thread event");
         tx.send(SlackEvent::Message {
             channel: "C_TWO".to_string(),
-            message: sample_message(Some("2000.2")),
+            message: Box::new(sample_message(Some("2000.2"))),
         })
         .expect("send second thread event");
 
@@ -182,4 +1017,2916 @@ mod tests {
             Some("2000.2")
         );
     }
+
+    #[test]
+    fn post_target_is_unaffected_by_later_thread_events() {
+        let mut app = App::new(Config::default());
+        app.workspaces.push(WorkspaceState::new(Workspace {
+            team_id: "T1".to_string(),
+            team_name: "team".to_string(),
+            xoxp_token: "xoxp-test".to_string(),
+            xapp_token: "xapp-test".to_string(),
+            user_id: None,
+            active: true,
+            last_channel_id: None,
+            icon_url: None,
+            threads: Vec::new(),
+            muted_channels: Vec::new(),
+            recent_reactions: Vec::new(),
+        }));
+        app.active_workspace_id = Some("T1".to_string());
+        app.channels.push(Channel {
+            id: "C_ONE".to_string(),
+            name: "general".to_string(),
+            is_dm: false,
+            is_group: false,
+            is_im: false,
+            is_mpim: false,
+            unread_count: 0,
+            mention_count: 0,
+            purpose: None,
+            topic: None,
+            user: None,
+            member_count: None,
+            created: None,
+            creator: None,
+            is_shared: false,
+            is_ext_shared: false,
+            is_org_shared: false,
+            is_archived: false,
+            last_read: None,
+            latest_ts: None,
+            is_member: None,
+            posting_restricted_to: None,
+            threads_restricted_to: None,
+        });
+        app.selected_channel_id = Some("C_ONE".to_string());
+
+        let target = app.post_target(None).expect("post target");
+
+        // Someone else starts a thread while the agent is still working.
+        let tx = app.event_tx.as_ref().expect("event tx").clone();
+        tx.send(SlackEvent::Message {
+            channel: "C_ONE".to_string(),
+            message: Box::new(sample_message(Some("3000.3"))),
+        })
+        .expect("send thread event");
+        app.process_slack_events();
+        assert_eq!(
+            app.active_threads.get("C_ONE").map(String::as_str),
+            Some("3000.3")
+        );
+
+        assert_eq!(target.channel_id, "C_ONE");
+        assert_eq!(target.channel_name, "general");
+        assert_eq!(target.thread_ts, None);
+        assert_eq!(target.token, "xoxp-test");
+    }
+
+    #[test]
+    fn reacting_twice_toggles_the_reaction_off() {
+        let mut message = sample_message(None);
+
+        assert!(!App::toggle_local_reaction(&mut message, "+1", "U1"));
+        assert!(!App::toggle_local_reaction(&mut message, "+1", "U2"));
+        assert_eq!(message.reactions[0].count, 2);
+
+        assert!(App::toggle_local_reaction(&mut message, "+1", "U1"));
+        assert_eq!(message.reactions[0].count, 1);
+        assert_eq!(message.reactions[0].users, vec!["U2".to_string()]);
+
+        assert!(App::toggle_local_reaction(&mut message, "+1", "U2"));
+        assert!(message.reactions.is_empty());
+    }
+
+    #[test]
+    fn quotes_strip_mentions_and_truncate() {
+        let mut names = std::collections::HashMap::new();
+        names.insert("U2".to_string(), "bob".to_string());
+
+        let quote = App::format_quote(
+            "hey <@U2> and <@U3|carol>\nline two\nline three",
+            "alice",
+            2,
+            &names,
+        );
+
+        assert_eq!(
+            quote,
+            "> hey @bob and @carol\n> line two\n> …\n> — @alice\n"
+        );
+        assert!(!quote.contains("<@"));
+    }
+
+    #[test]
+    fn background_messages_count_as_unread_until_marked_read() {
+        let mut app = App::new(Config::default());
+        for id in ["C_ONE", "C_TWO"] {
+            app.channels.push(Channel {
+                id: id.to_string(),
+                name: id.to_lowercase(),
+                is_dm: false,
+                is_group: false,
+                is_im: false,
+                is_mpim: false,
+                unread_count: 0,
+                mention_count: 0,
+                purpose: None,
+                topic: None,
+                user: None,
+                member_count: None,
+                created: None,
+                creator: None,
+                is_shared: false,
+                is_ext_shared: false,
+                is_org_shared: false,
+                is_archived: false,
+                last_read: None,
+                latest_ts: None,
+                is_member: None,
+                posting_restricted_to: None,
+                threads_restricted_to: None,
+            });
+        }
+        app.selected_channel_id = Some("C_ONE".to_string());
+
+        let tx = app.event_tx.as_ref().expect("event tx").clone();
+        for channel in ["C_ONE", "C_TWO", "C_TWO"] {
+            tx.send(SlackEvent::Message {
+                channel: channel.to_string(),
+                message: Box::new(sample_message(None)),
+            })
+            .expect("send event");
+        }
+        app.process_slack_events();
+
+        assert_eq!(app.channels[0].unread_count, 0);
+        assert_eq!(app.channels[1].unread_count, 2);
+
+        app.mark_channel_read("C_TWO");
+        assert_eq!(app.channels[1].unread_count, 0);
+
+        app.mark_channel_unread("C_TWO");
+        assert_eq!(app.channels[1].unread_count, 1);
+    }
+
+    #[test]
+    fn system_messages_skip_unread_counts_and_filters() {
+        let mut app = App::new(Config::default());
+        app.channels
+            .push(super::test_support::channel("C_ONE", "one"));
+        app.channels
+            .push(super::test_support::channel("C_TWO", "two"));
+        app.selected_channel_id = Some("C_TWO".to_string());
+
+        let mut join = sample_message(None);
+        join.ts = "1730000000.200000".to_string();
+        join.user_id = "U_NEW".to_string();
+        join.text = "joined".to_string();
+        join.is_system = true;
+        join.subtype = Some("channel_join".to_string());
+        let tx = app.event_tx.as_ref().expect("event tx").clone();
+        tx.send(SlackEvent::Message {
+            channel: "C_ONE".to_string(),
+            message: Box::new(join),
+        })
+        .expect("send event");
+        tx.send(SlackEvent::Message {
+            channel: "C_ONE".to_string(),
+            message: Box::new(sample_message(None)),
+        })
+        .expect("send event");
+        app.process_slack_events();
+        assert_eq!(app.channels[0].unread_count, 1);
+
+        app.selected_channel_id = Some("C_ONE".to_string());
+        assert_eq!(app.visible_message_indices().len(), 2);
+        let authors = app.message_authors();
+        assert_eq!(authors.len(), 1);
+        assert_eq!(authors[0].user_id, "U123");
+
+        app.message_filter.user_id = Some("U123".to_string());
+        assert_eq!(app.visible_message_indices(), vec![1]);
+        app.clear_message_filter();
+        app.message_filter.show_system = false;
+        assert_eq!(app.visible_message_indices(), vec![1]);
+    }
+
+    #[tokio::test]
+    async fn inactive_workspace_tabs_show_unread_and_mentions() {
+        let mut app = App::new(Config::default());
+        for (team, channel) in [("acme", "C_ACME"), ("globex", "C_GLOBEX")] {
+            let mut ws = WorkspaceState::new(Workspace {
+                team_id: team.to_uppercase(),
+                team_name: team.to_string(),
+                xoxp_token: "xoxp-test".to_string(),
+                xapp_token: "xapp-test".to_string(),
+                user_id: Some(format!("U_{}", team.to_uppercase())),
+                active: true,
+                last_channel_id: None,
+                icon_url: None,
+                threads: Vec::new(),
+                muted_channels: Vec::new(),
+                recent_reactions: Vec::new(),
+            });
+            ws.channels.push(Channel {
+                id: channel.to_string(),
+                name: team.to_string(),
+                is_dm: false,
+                is_group: false,
+                is_im: false,
+                is_mpim: false,
+                unread_count: 0,
+                mention_count: 0,
+                purpose: None,
+                topic: None,
+                user: None,
+                member_count: None,
+                created: None,
+                creator: None,
+                is_shared: false,
+                is_ext_shared: false,
+                is_org_shared: false,
+                is_archived: false,
+                last_read: None,
+                latest_ts: None,
+                is_member: None,
+                posting_restricted_to: None,
+                threads_restricted_to: None,
+            });
+            app.workspaces.push(ws);
+        }
+        app.switch_workspace(0);
+
+        let mut mention = sample_message(None);
+        mention.text = "ping <@U_GLOBEX>".to_string();
+        let tx = app.event_tx.as_ref().expect("event tx").clone();
+        for message in [sample_message(None), mention] {
+            tx.send(SlackEvent::Message {
+                channel: "C_GLOBEX".to_string(),
+                message: Box::new(message),
+            })
+            .expect("send event");
+        }
+        app.process_slack_events();
+
+        assert_eq!(app.workspace_tab_label(0), "[acme]");
+        assert_eq!(app.workspace_tab_label(1), " GL (2)● ");
+    }
+
+    #[test]
+    fn workspace_initials_tell_similar_names_apart() {
+        assert_eq!(App::workspace_initials("Acme Eng"), "AE");
+        assert_eq!(App::workspace_initials("acme-ops"), "AO");
+        assert_eq!(App::workspace_initials("globex"), "GL");
+        assert_eq!(App::workspace_initials("x"), "X");
+        assert_eq!(App::workspace_initials(" "), "?");
+    }
+
+    #[test]
+    fn every_reported_error_is_kept_newest_first() {
+        let mut app = App::new(Config::default());
+
+        app.report_error("Failed to send", "token xoxp-123 rejected");
+        app.report_error("Failed to load history", "timeout");
+
+        assert_eq!(app.error_history.len(), 2);
+        assert_eq!(
+            app.error_history[0].message,
+            "Failed to load history: timeout"
+        );
+        assert!(app.error_history[1].message.contains("xoxp-[REDACTED]"));
+        assert!(app.error_history[0].toast_until > std::time::Instant::now());
+    }
+
+    #[test]
+    fn jump_targets_accept_relative_and_absolute_times() {
+        use chrono::{Local, TimeZone};
+
+        let now = Local.with_ymd_and_hms(2024, 5, 2, 9, 30, 0).unwrap();
+        let at = |y, mo, d, h, mi| Local.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap();
+
+        let parse = |input| App::parse_jump_target(input, now);
+        assert_eq!(parse("14:00"), Ok(at(2024, 5, 2, 14, 0)));
+        assert_eq!(parse("yesterday 14:00"), Ok(at(2024, 5, 1, 14, 0)));
+        assert_eq!(parse("Yesterday"), Ok(at(2024, 5, 1, 0, 0)));
+        assert_eq!(parse("2024-04-30"), Ok(at(2024, 4, 30, 0, 0)));
+        assert_eq!(parse("2024-04-30 08:15"), Ok(at(2024, 4, 30, 8, 15)));
+        assert_eq!(parse("2024-04-30T08:15"), Ok(at(2024, 4, 30, 8, 15)));
+        assert!(parse("next tuesday")
+            .unwrap_err()
+            .contains("yesterday 14:00"));
+    }
+
+    #[test]
+    fn reminder_times_read_durations_and_times_of_day() {
+        use chrono::{Duration, Local, TimeZone};
+
+        let now = Local.with_ymd_and_hms(2024, 5, 2, 9, 30, 0).unwrap();
+        let at = |d, h, mi| Local.with_ymd_and_hms(2024, 5, d, h, mi, 0).unwrap();
+        let parse = |input| App::parse_reminder(input, now);
+
+        assert_eq!(
+            parse("me in 20m to follow up on the deploy"),
+            Ok((
+                "follow up on the deploy".to_string(),
+                now + Duration::minutes(20)
+            ))
+        );
+        assert_eq!(
+            parse("me in 2 hours check CI").map(|(_, when)| when),
+            Ok(now + Duration::hours(2))
+        );
+        assert_eq!(parse("in 3d to renew").unwrap().1, at(5, 9, 30));
+        assert_eq!(
+            parse("me tomorrow 9am to call Sam"),
+            Ok(("call Sam".to_string(), at(3, 9, 0)))
+        );
+        assert_eq!(
+            parse("me tomorrow at 2:30 pm to demo").unwrap().1,
+            at(3, 14, 30)
+        );
+        assert_eq!(parse("me tomorrow to stretch").unwrap().1, at(3, 9, 0));
+        assert_eq!(parse("me at 15:00 to leave").unwrap().1, at(2, 15, 0));
+        // Already past today, so tomorrow.
+        assert_eq!(parse("me at 8am to run").unwrap().1, at(3, 8, 0));
+
+        assert!(parse("me in 20m")
+            .unwrap_err()
+            .contains("what to be reminded"));
+        assert!(parse("me in 5 weeks to x").unwrap_err().contains("weeks"));
+        assert!(parse("@sam in 5m to x").unwrap_err().contains("yourself"));
+        assert!(parse("me to stretch").is_err());
+    }
+
+    #[test]
+    fn history_window_merges_in_order_and_selects_nearest() {
+        let mut app = App::new(Config::default());
+        let at = |secs: i64| {
+            let mut message = sample_message(None);
+            message.ts = format!("{}.000000", secs);
+            message.timestamp = chrono::DateTime::from_timestamp(secs, 0).unwrap();
+            message
+        };
+        app.messages
+            .insert("C1".to_string(), vec![at(1_000), at(5_000)].into());
+
+        app.merge_history_window(
+            "C1",
+            vec![at(2_000), at(1_000), at(3_000)],
+            at(2_900).timestamp,
+        );
+
+        let order: Vec<_> = app.messages["C1"].iter().map(|m| m.ts.as_str()).collect();
+        assert_eq!(
+            order,
+            ["1000.000000", "2000.000000", "3000.000000", "5000.000000"]
+        );
+        assert_eq!(
+            app.selected_message,
+            Some(("C1".to_string(), "3000.000000".to_string()))
+        );
+        assert_eq!(app.scroll_offset, 1);
+    }
+
+    #[test]
+    fn message_filter_limits_scrolling_to_matching_messages() {
+        use super::MessageFilter;
+
+        let mut app = App::new(Config::default());
+        app.channels.push(Channel {
+            id: "C1".to_string(),
+            name: "general".to_string(),
+            is_dm: false,
+            is_group: false,
+            is_im: false,
+            is_mpim: false,
+            unread_count: 0,
+            mention_count: 0,
+            purpose: None,
+            topic: None,
+            user: None,
+            member_count: None,
+            created: None,
+            creator: None,
+            is_shared: false,
+            is_ext_shared: false,
+            is_org_shared: false,
+            is_archived: false,
+            last_read: None,
+            latest_ts: None,
+            is_member: None,
+            posting_restricted_to: None,
+            threads_restricted_to: None,
+        });
+        app.selected_channel_id = Some("C1".to_string());
+        let by = |user: &str, ts: &str| {
+            let mut message = sample_message(None);
+            message.user_id = user.to_string();
+            message.username = user.to_lowercase();
+            message.ts = ts.to_string();
+            message
+        };
+        app.messages.insert(
+            "C1".to_string(),
+            vec![by("UA", "1"), by("UB", "2"), by("UA", "3"), by("UB", "4")].into(),
+        );
+
+        let authors = app.message_authors();
+        assert_eq!(authors.len(), 2);
+        assert_eq!(authors[0].count, 2);
+
+        app.scroll_offset = 3;
+        app.set_message_filter(MessageFilter {
+            user_id: Some("UB".to_string()),
+            ..MessageFilter::default()
+        });
+        assert_eq!(app.max_scroll_offset(), 1);
+        assert_eq!(app.scroll_offset, 0);
+        app.scroll_messages(5);
+        assert_eq!(
+            app.selected_or_cursor_message().map(|m| m.ts.as_str()),
+            Some("2")
+        );
+
+        app.clear_message_filter();
+        assert_eq!(
+            app.selected_or_cursor_message().map(|m| m.ts.as_str()),
+            Some("2")
+        );
+        assert_eq!(app.scroll_offset, 2);
+    }
+
+    mod with_mock_client {
+        use super::super::test_support::{
+            app_with, channel, message, settle, MockSlackClient, ARCHIVED_CHANNEL, CHANNEL, ME,
+        };
+        use super::super::TYPING_TIMEOUT;
+        use crate::app::{AppAsyncEvent, Focus, JumpTarget, PendingSend, PinnedEntry};
+        use ratatui::crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+        use slack_zc_slack::socket::SlackEvent;
+        use slack_zc_slack::types::DeliveryState;
+        use std::sync::Arc;
+        use std::time::{Duration, Instant};
+
+        fn texts(app: &super::App) -> Vec<String> {
+            app.messages[CHANNEL]
+                .iter()
+                .map(|m| m.text.clone())
+                .collect()
+        }
+
+        #[tokio::test]
+        async fn selecting_a_channel_loads_its_history() {
+            let client = Arc::new(MockSlackClient::default().with_history(
+                CHANNEL,
+                vec![
+                    message("1730000000.000100", "U_ALICE", "morning"),
+                    message("1730000060.000100", "U_BOB", "hi"),
+                ],
+            ));
+            let mut app = app_with(client.clone());
+
+            app.select_channel(CHANNEL);
+            settle(&mut app).await;
+
+            assert_eq!(texts(&app), ["morning", "hi"]);
+            assert!(client.calls().contains(&format!("get_history {CHANNEL}")));
+            assert!(app.error_history.is_empty());
+        }
+
+        #[tokio::test]
+        async fn sent_messages_show_up_in_the_channel() {
+            let client = Arc::new(MockSlackClient::default());
+            let mut app = app_with(client.clone());
+            app.select_channel(CHANNEL);
+            settle(&mut app).await;
+
+            app.input.set_text("hello there".to_string());
+            app.handle_input_submit().unwrap();
+            assert!(app.input.buffer.is_empty());
+            settle(&mut app).await;
+
+            assert!(client
+                .calls()
+                .contains(&format!("send_message {CHANNEL} hello there")));
+            assert_eq!(texts(&app), ["hello there"]);
+        }
+
+        #[tokio::test]
+        async fn edits_apply_immediately_and_revert_on_failure() {
+            let client = Arc::new(
+                MockSlackClient::default()
+                    .with_history(CHANNEL, vec![message("1730000000.000100", ME, "helo")]),
+            );
+            let mut app = app_with(client.clone());
+            app.select_channel(CHANNEL);
+            settle(&mut app).await;
+
+            app.start_edit_message().unwrap();
+            app.input.set_text("hello".to_string());
+            app.save_edited_message().unwrap();
+            assert_eq!(texts(&app), ["hello"]);
+            settle(&mut app).await;
+            assert!(client
+                .calls()
+                .contains(&format!("update_message {CHANNEL} 1730000000.000100 hello")));
+            assert!(app.messages[CHANNEL][0].is_edited);
+
+            client.fail("update_message");
+            app.start_edit_message().unwrap();
+            app.input.set_text("hello!!".to_string());
+            app.save_edited_message().unwrap();
+            assert_eq!(texts(&app), ["hello!!"]);
+            settle(&mut app).await;
+
+            assert_eq!(texts(&app), ["hello"]);
+            assert!(app.error_history[0]
+                .message
+                .starts_with("Failed to update message"));
+        }
+
+        #[tokio::test]
+        async fn editing_loads_the_text_with_the_cursor_at_the_end() {
+            let client = Arc::new(MockSlackClient::default().with_history(
+                CHANNEL,
+                vec![message("1730000000.000100", ME, "héllo wörld")],
+            ));
+            let mut app = app_with(client);
+            app.select_channel(CHANNEL);
+            settle(&mut app).await;
+            app.input.set_text("draft".to_string());
+            app.focus = Focus::Messages;
+
+            app.handle_event(Event::Key(KeyEvent::new(
+                KeyCode::Char('e'),
+                KeyModifiers::NONE,
+            )))
+            .unwrap();
+            assert_eq!(app.input.buffer, "héllo wörld");
+            app.input.handle_char('!');
+            assert_eq!(app.input.buffer, "héllo wörld!");
+
+            app.cancel_edit_message();
+            assert_eq!(app.input.buffer, "draft");
+        }
+
+        #[tokio::test]
+        async fn only_own_messages_can_be_deleted() {
+            let client = Arc::new(MockSlackClient::default().with_history(
+                CHANNEL,
+                vec![
+                    message("1730000000.000100", ME, "mine"),
+                    message("1730000060.000100", "U_BOB", "theirs"),
+                ],
+            ));
+            let mut app = app_with(client.clone());
+            app.select_channel(CHANNEL);
+            settle(&mut app).await;
+
+            app.delete_selected_message().unwrap();
+            assert!(app.error_history[0].message.contains("your own messages"));
+
+            app.scroll_messages(1);
+            app.delete_selected_message().unwrap();
+            settle(&mut app).await;
+            let deletes: Vec<_> = client
+                .calls()
+                .into_iter()
+                .filter(|call| call.starts_with("delete_message"))
+                .collect();
+            assert_eq!(
+                deletes,
+                [format!("delete_message {CHANNEL} 1730000000.000100")]
+            );
+        }
+
+        #[tokio::test]
+        async fn typing_shows_names_once_the_user_is_known() {
+            let client = Arc::new(MockSlackClient::default());
+            let mut app = app_with(client.clone());
+            app.select_channel(CHANNEL);
+            let tx = app.event_tx.clone().unwrap();
+            for _ in 0..2 {
+                tx.send(SlackEvent::UserTyping {
+                    channel: CHANNEL.to_string(),
+                    user: "U_ALICE".to_string(),
+                })
+                .unwrap();
+            }
+
+            app.process_slack_events();
+            assert_eq!(app.typing_names(CHANNEL), ["U_ALICE"]);
+            settle(&mut app).await;
+
+            assert_eq!(app.typing_names(CHANNEL), ["u_alice"]);
+            let lookups = client
+                .calls()
+                .into_iter()
+                .filter(|call| call == "get_user U_ALICE")
+                .count();
+            assert_eq!(lookups, 1);
+        }
+
+        #[tokio::test]
+        async fn typing_indicators_expire_and_clear_on_message() {
+            let client = Arc::new(MockSlackClient::default());
+            let mut app = app_with(client.clone());
+            app.select_channel(CHANNEL);
+            app.typing_users.insert(
+                "C_RANDOM".to_string(),
+                vec![("U_BOB".to_string(), Instant::now())],
+            );
+            app.typing_users.insert(
+                "C_STALE".to_string(),
+                vec![(
+                    "U_CAROL".to_string(),
+                    Instant::now() - TYPING_TIMEOUT - Duration::from_secs(1),
+                )],
+            );
+            let tx = app.event_tx.clone().unwrap();
+            tx.send(SlackEvent::UserTyping {
+                channel: CHANNEL.to_string(),
+                user: "U_ALICE".to_string(),
+            })
+            .unwrap();
+
+            app.process_slack_events();
+            assert!(app.is_typing_in(CHANNEL));
+            assert!(app.is_typing_in("C_RANDOM"));
+            assert!(!app.is_typing_in("C_STALE"));
+            assert_eq!(
+                app.typing_status(CHANNEL).as_deref(),
+                Some("U_ALICE is typing…")
+            );
+
+            tx.send(SlackEvent::Message {
+                channel: CHANNEL.to_string(),
+                message: Box::new(message("1.000001", "U_ALICE", "hi")),
+            })
+            .unwrap();
+            app.process_slack_events();
+            assert!(!app.is_typing_in(CHANNEL));
+            assert!(app.is_typing_in("C_RANDOM"));
+        }
+
+        #[tokio::test]
+        async fn typing_is_signalled_once_and_disabled_when_refused() {
+            let client = Arc::new(MockSlackClient::default());
+            client.fail("rtm_connect");
+            let mut app = app_with(client.clone());
+            app.select_channel(CHANNEL);
+            app.focus = Focus::Input;
+            let key = |c| Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+
+            for c in "hi".chars() {
+                app.handle_event(key(c)).unwrap();
+            }
+            settle(&mut app).await;
+            assert!(app.typing_unsupported.contains("T_TEST"));
+
+            app.input.clear();
+            for c in "again".chars() {
+                app.handle_event(key(c)).unwrap();
+            }
+            settle(&mut app).await;
+            let attempts = client
+                .calls()
+                .into_iter()
+                .filter(|call| call.starts_with("rtm_connect"))
+                .count();
+            assert_eq!(attempts, 1);
+        }
+
+        #[tokio::test]
+        async fn failed_send_is_retried_to_its_original_channel() {
+            let client = Arc::new(MockSlackClient::default());
+            client.fail("send_message");
+            let mut app = app_with(client.clone());
+            app.channels.push(channel("C_RANDOM", "random"));
+            app.select_channel(CHANNEL);
+            app.focus = Focus::Input;
+            let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+            for c in "hello".chars() {
+                app.handle_event(key(KeyCode::Char(c))).unwrap();
+            }
+            app.handle_event(key(KeyCode::Enter)).unwrap();
+            settle(&mut app).await;
+            assert!(app.error_history[0].retry.is_some());
+
+            app.select_channel("C_RANDOM");
+            client.recover("send_message");
+            app.focus = Focus::Messages;
+            app.handle_event(key(KeyCode::Char('R'))).unwrap();
+            settle(&mut app).await;
+
+            let sends: Vec<_> = client
+                .calls()
+                .into_iter()
+                .filter(|call| call.starts_with("send_message"))
+                .collect();
+            assert_eq!(
+                sends,
+                [
+                    "send_message C_GENERAL hello",
+                    "send_message C_GENERAL hello"
+                ]
+            );
+            assert!(app.error_history[0].retry.is_none());
+        }
+
+        #[tokio::test]
+        async fn offline_agent_command_is_offered_back_instead_of_failing() {
+            let client = Arc::new(MockSlackClient::default());
+            let mut app = app_with(client.clone());
+            app.select_channel(CHANNEL);
+            app.focus = Focus::Input;
+            let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+            for c in "/draft a reply".chars() {
+                app.handle_event(key(KeyCode::Char(c))).unwrap();
+            }
+            app.handle_event(key(KeyCode::Enter)).unwrap();
+            assert_eq!(app.agent_offline_prompt.as_deref(), Some("/draft a reply"));
+            assert!(app.confirmation_dialog.is_none());
+
+            app.handle_event(key(KeyCode::Esc)).unwrap();
+            assert!(app.agent_offline_prompt.is_none());
+            assert_eq!(app.input.buffer, "/draft a reply");
+            assert_eq!(app.focus, Focus::Input);
+
+            // A mention still goes to Slack, with the notice shown once.
+            app.input.clear();
+            for _ in 0..2 {
+                for c in "@zeroclaw hi".chars() {
+                    app.handle_event(key(KeyCode::Char(c))).unwrap();
+                }
+                app.handle_event(key(KeyCode::Enter)).unwrap();
+                app.focus = Focus::Input;
+            }
+            settle(&mut app).await;
+            let sends = client
+                .calls()
+                .into_iter()
+                .filter(|call| call.starts_with("send_message"))
+                .count();
+            assert_eq!(sends, 2);
+            let notices = app
+                .error_history
+                .iter()
+                .filter(|entry| entry.message.starts_with("ZeroClaw is offline"))
+                .count();
+            assert_eq!(notices, 1);
+        }
+
+        #[tokio::test]
+        async fn capped_history_is_fetched_back_on_scroll() {
+            let history: Vec<_> = (1..=5)
+                .map(|n| message(&format!("{n}.000001"), "U_BOB", &format!("m{n}")))
+                .collect();
+            let client = Arc::new(MockSlackClient::default().with_history(CHANNEL, history));
+            let mut app = app_with(client.clone());
+            app.config.ui.max_messages_per_channel = 3;
+            app.select_channel(CHANNEL);
+            settle(&mut app).await;
+            assert_eq!(texts(&app), ["m3", "m4", "m5"]);
+
+            let tx = app.event_tx.clone().unwrap();
+            tx.send(SlackEvent::Message {
+                channel: CHANNEL.to_string(),
+                message: Box::new(message("6.000001", "U_BOB", "m6")),
+            })
+            .unwrap();
+            app.process_slack_events();
+            assert_eq!(texts(&app), ["m4", "m5", "m6"]);
+
+            // Scrolling past the oldest message pages the dropped ones back in.
+            app.scroll_messages(5);
+            settle(&mut app).await;
+            assert_eq!(texts(&app), ["m1", "m2", "m3", "m4", "m5", "m6"]);
+            assert_eq!(app.scroll_offset, 2);
+
+            // Trimming waits until the reader is back at the bottom.
+            tx.send(SlackEvent::Message {
+                channel: CHANNEL.to_string(),
+                message: Box::new(message("7.000001", "U_BOB", "m7")),
+            })
+            .unwrap();
+            app.process_slack_events();
+            assert_eq!(app.messages[CHANNEL].len(), 7);
+            app.scroll_messages(-10);
+            tx.send(SlackEvent::Message {
+                channel: CHANNEL.to_string(),
+                message: Box::new(message("8.000001", "U_BOB", "m8")),
+            })
+            .unwrap();
+            app.process_slack_events();
+            assert_eq!(texts(&app), ["m6", "m7", "m8"]);
+        }
+
+        #[tokio::test]
+        async fn external_channel_warning_shows_once_per_session() {
+            let client = Arc::new(MockSlackClient::default());
+            let mut app = app_with(client.clone());
+            let mut partner = channel("C_PARTNER", "partner-launch");
+            partner.is_shared = true;
+            partner.is_ext_shared = true;
+            app.channels.push(partner);
+
+            app.select_channel("C_PARTNER");
+            assert_eq!(app.external_banner.as_deref(), Some("C_PARTNER"));
+            app.select_channel(CHANNEL);
+            assert_eq!(app.external_banner, None);
+            app.select_channel("C_PARTNER");
+            assert_eq!(app.external_banner, None);
+
+            let mut app = app_with(client);
+            app.config.ui.warn_external_channels = false;
+            let mut partner = channel("C_PARTNER", "partner-launch");
+            partner.is_ext_shared = true;
+            app.channels.push(partner);
+            app.select_channel("C_PARTNER");
+            assert_eq!(app.external_banner, None);
+        }
+
+        #[tokio::test]
+        async fn archived_channels_are_listed_on_request_and_read_only() {
+            let client = Arc::new(MockSlackClient::default());
+            let mut app = app_with(client.clone());
+            app.focus = Focus::Sidebar;
+            let press = |c| Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+            let listed = |app: &super::App| -> Vec<String> {
+                app.sidebar_channel_indices()
+                    .into_iter()
+                    .map(|idx| app.channels[idx].id.clone())
+                    .collect()
+            };
+
+            app.handle_event(press('a')).unwrap();
+            settle(&mut app).await;
+            assert_eq!(listed(&app), [CHANNEL, ARCHIVED_CHANNEL]);
+
+            app.select_channel(ARCHIVED_CHANNEL);
+            settle(&mut app).await;
+            app.input.set_text("still here?".to_string());
+            app.handle_input_submit().unwrap();
+            settle(&mut app).await;
+            assert_eq!(app.input.buffer, "still here?");
+            assert!(!client.calls().iter().any(|c| c.starts_with("send_message")));
+            assert!(app.error_history[0].message.contains("archived"));
+
+            // Unarchiving moves it out of the hidden section live.
+            app.event_tx
+                .clone()
+                .unwrap()
+                .send(SlackEvent::ChannelArchived {
+                    channel: ARCHIVED_CHANNEL.to_string(),
+                    archived: false,
+                })
+                .unwrap();
+            app.process_slack_events();
+            app.handle_event(press('a')).unwrap();
+            assert_eq!(listed(&app), [CHANNEL, ARCHIVED_CHANNEL]);
+            assert!(!app.is_archived_channel(ARCHIVED_CHANNEL));
+        }
+
+        #[tokio::test]
+        async fn event_bursts_are_spread_over_frames_without_starving_results() {
+            let client = Arc::new(MockSlackClient::default());
+            let mut app = app_with(client);
+            app.channels.push(channel("C_OTHER", "other"));
+            app.select_channel(CHANNEL);
+            settle(&mut app).await;
+
+            let tx = app.event_tx.clone().unwrap();
+            for n in 0..250 {
+                let text = if n == 0 {
+                    format!("hi <@{ME}>")
+                } else {
+                    "x".to_string()
+                };
+                tx.send(SlackEvent::Message {
+                    channel: "C_OTHER".to_string(),
+                    message: Box::new(message(&format!("{}.000001", 100 + n), "U_BOB", &text)),
+                })
+                .unwrap();
+            }
+            // A stale failed load followed by a newer one for the same channel.
+            let async_tx = app.app_async_tx.clone().unwrap();
+            for (text, error) in [("old", Some("timed out".to_string())), ("new", None)] {
+                async_tx
+                    .send(AppAsyncEvent::ChannelHistoryLoaded {
+                        channel_id: CHANNEL.to_string(),
+                        messages: vec![message("1.000001", "U_BOB", text)],
+                        error,
+                    })
+                    .unwrap();
+            }
+
+            app.process_slack_events();
+            assert_eq!(app.messages["C_OTHER"].len(), 200);
+            assert!(app.has_queued_events());
+            assert_eq!(texts(&app), ["new"]);
+            assert!(app.error_history.is_empty());
+            let other = app.channels.iter().find(|ch| ch.id == "C_OTHER").unwrap();
+            assert_eq!((other.unread_count, other.mention_count), (200, 1));
+
+            app.process_slack_events();
+            assert_eq!(app.messages["C_OTHER"].len(), 250);
+            assert!(!app.has_queued_events());
+            let other = app.channels.iter().find(|ch| ch.id == "C_OTHER").unwrap();
+            assert_eq!(other.unread_count, 250);
+        }
+
+        #[tokio::test]
+        async fn each_bearer_expiry_re_pairs_once() {
+            let mut app = app_with(Arc::new(MockSlackClient::default()));
+            app.config.zeroclaw.auto_start = false;
+            let send = |app: &mut crate::app::App, event: AppAsyncEvent| {
+                app.app_async_tx.clone().unwrap().send(event).unwrap();
+                app.process_slack_events();
+            };
+
+            for _ in 0..2 {
+                send(&mut app, AppAsyncEvent::ZeroClawBearerRejected);
+                assert!(app.zeroclaw_bearer_rejected);
+                assert!(app.last_error.is_none());
+                send(
+                    &mut app,
+                    AppAsyncEvent::ZeroClawConnectionFinished {
+                        runner: Some(slack_zc_agent::AgentRunner::new(
+                            "zeroclaw".to_string(),
+                            42617,
+                        )),
+                        error: None,
+                    },
+                );
+                assert!(!app.zeroclaw_bearer_rejected);
+            }
+
+            // Rejected again before any reconnect: that one is terminal.
+            send(&mut app, AppAsyncEvent::ZeroClawBearerRejected);
+            send(&mut app, AppAsyncEvent::ZeroClawBearerRejected);
+            assert!(app
+                .last_error
+                .as_deref()
+                .is_some_and(|err| err.contains("again after re-pairing")));
+        }
+
+        #[tokio::test]
+        async fn agent_draft_lands_in_the_input_and_esc_restores_the_old_text() {
+            let client = Arc::new(MockSlackClient::default());
+            let mut app = app_with(client.clone());
+            let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+            let finish = |app: &mut crate::app::App, response: &str| {
+                app.pending_draft = true;
+                app.app_async_tx
+                    .clone()
+                    .unwrap()
+                    .send(AppAsyncEvent::AgentCommandFinished {
+                        command: "/draft nudge".to_string(),
+                        channel_id: Some(CHANNEL.to_string()),
+                        channel_name: Some("general".to_string()),
+                        thread_ts: None,
+                        response: Some(response.to_string()),
+                        error: None,
+                    })
+                    .unwrap();
+                app.process_slack_events();
+            };
+            app.select_channel(CHANNEL);
+            settle(&mut app).await;
+
+            app.input.set_text("half a thought".to_string());
+            app.focus = Focus::Sidebar;
+            finish(&mut app, "Could you take a look at the review?\n");
+            assert_eq!(app.input.buffer, "Could you take a look at the review?");
+            assert_eq!(app.focus, Focus::Input);
+            assert_eq!(
+                app.agent_responses.front().map(|r| r.command.as_str()),
+                Some("/draft nudge")
+            );
+            app.handle_event(key(KeyCode::Esc)).unwrap();
+            assert_eq!(app.input.buffer, "half a thought");
+            assert!(app.stashed_input.is_none());
+
+            // Sending the draft brings the displaced text back, and nothing
+            // reached Slack before that.
+            finish(&mut app, "Gentle nudge");
+            assert!(!client.calls().iter().any(|call| call.starts_with("send")));
+            app.handle_event(key(KeyCode::Enter)).unwrap();
+            settle(&mut app).await;
+            assert!(client
+                .calls()
+                .contains(&format!("send_message {CHANNEL} Gentle nudge")));
+            assert_eq!(app.input.buffer, "half a thought");
+        }
+
+        #[tokio::test]
+        async fn connection_status_tracks_sockets_per_workspace() {
+            use slack_zc_slack::socket::ConnectionState;
+
+            let client = Arc::new(MockSlackClient::default());
+            let mut app = app_with(client.clone());
+            let tx = app.event_tx.clone().unwrap();
+            let state = |state| SlackEvent::ConnectionState {
+                team_id: "T_TEST".to_string(),
+                state,
+            };
+            tx.send(state(ConnectionState::Connected)).unwrap();
+            tx.send(SlackEvent::Activity {
+                team_id: "T_TEST".to_string(),
+            })
+            .unwrap();
+            tx.send(state(ConnectionState::Reconnecting {
+                attempt: 2,
+                retry_in: Duration::from_secs(4),
+            }))
+            .unwrap();
+            app.process_slack_events();
+
+            let status = &app.socket_status["T_TEST"];
+            assert_eq!(
+                status.state,
+                ConnectionState::Reconnecting {
+                    attempt: 2,
+                    retry_in: Duration::from_secs(4)
+                }
+            );
+            // The last event survives the state change.
+            assert!(status.last_event.is_some());
+
+            let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+            app.handle_event(key(KeyCode::F(11))).unwrap();
+            let panel = app.connection_status.as_ref().unwrap();
+            assert!(matches!(panel.gateway_check, Some(Err(_))));
+            app.handle_event(key(KeyCode::Esc)).unwrap();
+            assert!(app.connection_status.is_none());
+        }
+
+        #[tokio::test]
+        async fn reconnect_fetches_the_gap_without_duplicates() {
+            use slack_zc_slack::socket::ConnectionState;
+
+            let history = ["a", "b", "c", "d"]
+                .iter()
+                .enumerate()
+                .map(|(i, text)| message(&format!("{}.000001", 100 + i), "U_BOB", text))
+                .collect::<Vec<_>>();
+            let client = Arc::new(MockSlackClient::default().with_history(CHANNEL, history));
+            let mut app = app_with(client.clone());
+            app.channels.push(channel("C_OTHER", "other"));
+            app.select_channel(CHANNEL);
+            settle(&mut app).await;
+            // Only the first two made it before the socket dropped.
+            app.messages.get_mut(CHANNEL).unwrap().truncate(2);
+            app.messages.insert(
+                "C_OTHER".to_string(),
+                vec![message("100.000001", "U_BOB", "x")].into(),
+            );
+
+            let tx = app.event_tx.clone().unwrap();
+            for state in [
+                ConnectionState::Connected,
+                ConnectionState::Reconnecting {
+                    attempt: 1,
+                    retry_in: Duration::from_secs(1),
+                },
+                ConnectionState::Connected,
+            ] {
+                tx.send(SlackEvent::ConnectionState {
+                    team_id: "T_TEST".to_string(),
+                    state,
+                })
+                .unwrap();
+            }
+            app.process_slack_events();
+            assert!(app.catching_up.contains(CHANNEL));
+            assert!(app.pending_catch_up.contains_key("C_OTHER"));
+
+            settle(&mut app).await;
+            assert!(app.catching_up.is_empty());
+            assert_eq!(texts(&app), ["a", "b", "c", "d"]);
+            assert_eq!(
+                app.history_gaps.get(CHANNEL).map(String::as_str),
+                Some("101.000001")
+            );
+
+            // Other channels catch up when opened, merging rather than reloading.
+            app.select_channel("C_OTHER");
+            settle(&mut app).await;
+            let calls = client.calls();
+            assert!(calls.contains(&"get_history_between C_OTHER".to_string()));
+            assert!(!calls.contains(&"get_history C_OTHER".to_string()));
+            assert!(app.pending_catch_up.is_empty());
+        }
+
+        #[tokio::test]
+        async fn refused_posts_offer_to_join_or_split() {
+            let client = Arc::new(MockSlackClient::default());
+            let mut app = app_with(client.clone());
+            let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+            app.select_channel(CHANNEL);
+            settle(&mut app).await;
+            app.focus = Focus::Input;
+
+            client.fail_with("send_message", "not_in_channel");
+            app.input.set_text("hello".to_string());
+            app.handle_event(key(KeyCode::Enter)).unwrap();
+            settle(&mut app).await;
+            assert_eq!(
+                app.send_recovery.as_ref().map(|p| p.recovery),
+                Some(crate::app::SendRecovery::JoinChannel)
+            );
+            assert!(app.last_error.as_deref().unwrap().contains("not a member"));
+            client.recover("send_message");
+            app.handle_event(key(KeyCode::Char('y'))).unwrap();
+            settle(&mut app).await;
+            let calls = client.calls();
+            let joined = calls
+                .iter()
+                .position(|c| *c == format!("join_channel {CHANNEL}"))
+                .unwrap();
+            assert_eq!(calls[joined + 1], format!("send_message {CHANNEL} hello"));
+
+            // Longer than Slack allows but short enough to skip the
+            // long-message dialog, as with text that grows on the server.
+            let long = "word ".repeat(1000);
+            client.fail_with("send_message", "msg_too_long");
+            app.dispatch_send(
+                PendingSend {
+                    team_id: "T_TEST".to_string(),
+                    channel_id: CHANNEL.to_string(),
+                    text: long.trim().to_string(),
+                    thread_ts: None,
+                    local_id: None,
+                },
+                "Failed to send message",
+            );
+            settle(&mut app).await;
+            assert_eq!(
+                app.send_recovery.as_ref().map(|p| p.recovery),
+                Some(crate::app::SendRecovery::SplitMessage)
+            );
+            client.recover("send_message");
+            let before = client.calls().len();
+            app.handle_event(key(KeyCode::Char('y'))).unwrap();
+            settle(&mut app).await;
+            let parts: Vec<String> = client.calls()[before..]
+                .iter()
+                .filter_map(|c| c.strip_prefix(&format!("send_message {CHANNEL} ")))
+                .map(str::to_string)
+                .collect();
+            assert_eq!(parts.len(), 2);
+            assert!(parts.iter().all(|p| p.chars().count() <= 4000));
+            assert_eq!(parts.join(" "), long.trim());
+        }
+
+        #[tokio::test]
+        async fn long_input_asks_whether_to_split_or_upload() {
+            let client = Arc::new(MockSlackClient::default());
+            let mut app = app_with(client.clone());
+            let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+            app.select_channel(CHANNEL);
+            settle(&mut app).await;
+            let trace: Vec<String> = (0..300)
+                .map(|i| format!("    at frame_{i} (src/lib.rs:{i})"))
+                .collect();
+            let long = format!("```\n{}\n```", trace.join("\n"));
+            let sends = |client: &MockSlackClient| {
+                client
+                    .calls()
+                    .into_iter()
+                    .filter(|c| c.starts_with("send_message") || c.starts_with("upload"))
+                    .count()
+            };
+
+            // Nothing goes out until a choice is made, and Esc keeps the text.
+            app.focus = Focus::Input;
+            app.input.set_text(long.clone());
+            app.handle_event(key(KeyCode::Enter)).unwrap();
+            assert!(app.long_message.is_some());
+            app.handle_event(key(KeyCode::Esc)).unwrap();
+            settle(&mut app).await;
+            assert_eq!(app.input.buffer, long);
+            assert_eq!(sends(&client), 0);
+
+            app.focus = Focus::Input;
+            app.handle_event(key(KeyCode::Enter)).unwrap();
+            app.handle_event(key(KeyCode::Char('u'))).unwrap();
+            settle(&mut app).await;
+            assert!(client
+                .calls()
+                .contains(&format!("upload_snippet {CHANNEL} message.txt")));
+            assert!(app.input.buffer.is_empty());
+
+            app.focus = Focus::Input;
+            app.input.set_text(long.clone());
+            let before = client.calls().len();
+            app.handle_event(key(KeyCode::Enter)).unwrap();
+            app.handle_event(key(KeyCode::Char('s'))).unwrap();
+            settle(&mut app).await;
+            let parts: Vec<String> = client.calls()[before..]
+                .iter()
+                .filter_map(|c| c.strip_prefix(&format!("send_message {CHANNEL} ")))
+                .map(str::to_string)
+                .collect();
+            assert!(parts.len() > 1);
+            for part in &parts {
+                assert!(part.chars().count() <= 4000);
+                assert!(part.starts_with("```") && part.ends_with("```"));
+            }
+            assert!(parts[0].contains("frame_0 ") && parts.last().unwrap().contains("frame_299 "));
+
+            // A failure names the part that didn't go out.
+            client.fail("send_message");
+            app.focus = Focus::Input;
+            app.input.set_text(long);
+            app.handle_event(key(KeyCode::Enter)).unwrap();
+            app.handle_event(key(KeyCode::Char('s'))).unwrap();
+            settle(&mut app).await;
+            let error = app.last_error.clone().unwrap();
+            assert!(error.starts_with("Failed to send part 1 of "), "{error}");
+        }
+
+        #[tokio::test]
+        async fn raw_json_inspector_shows_the_original_payload() {
+            let mut inspected = message("1730000000.000100", "U_OTHER", "deploy done");
+            inspected.raw = Some(r#"{"ts":"1730000000.000100","metadata":{"id":7}}"#.into());
+            let plain = message("1730000000.000200", "U_OTHER", "thanks");
+            let client =
+                Arc::new(MockSlackClient::default().with_history(CHANNEL, vec![inspected, plain]));
+            let mut app = app_with(client);
+            let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+            app.select_channel(CHANNEL);
+            settle(&mut app).await;
+            app.focus = Focus::Messages;
+
+            // The newest message came without a payload: say how to get one.
+            app.handle_event(key(KeyCode::Char('J'))).unwrap();
+            assert!(app.raw_inspector.is_none());
+            assert!(app.last_error.as_deref().unwrap().contains("keep_raw"));
+
+            app.selected_message = Some((CHANNEL.to_string(), "1730000000.000100".to_string()));
+            app.handle_event(key(KeyCode::Char('J'))).unwrap();
+            let inspector = app.raw_inspector.as_ref().unwrap();
+            assert_eq!(inspector.ts, "1730000000.000100");
+            assert!(inspector
+                .json
+                .contains("\n  \"metadata\": {\n    \"id\": 7"));
+
+            app.handle_event(key(KeyCode::End)).unwrap();
+            let last_line = app.raw_inspector.as_ref().unwrap().json.lines().count() - 1;
+            assert_eq!(
+                app.raw_inspector.as_ref().unwrap().scroll as usize,
+                last_line
+            );
+            app.handle_event(key(KeyCode::Esc)).unwrap();
+            assert!(app.raw_inspector.is_none());
+        }
+
+        #[tokio::test]
+        async fn remind_sets_a_reminder_and_the_list_deletes_it() {
+            let client = Arc::new(MockSlackClient::default());
+            let mut app = app_with(client.clone());
+            let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+            app.focus = Focus::Input;
+
+            app.input
+                .set_text("/remind me in 20m to follow up on the deploy".to_string());
+            app.handle_event(key(KeyCode::Enter)).unwrap();
+            settle(&mut app).await;
+            assert!(client
+                .calls()
+                .iter()
+                .any(|c| c.starts_with("add_reminder follow up on the deploy ")));
+            let popup = app.reminders.as_ref().unwrap();
+            assert_eq!(
+                popup.reminders[popup.selected].text,
+                "follow up on the deploy"
+            );
+
+            app.handle_event(key(KeyCode::Char('d'))).unwrap();
+            settle(&mut app).await;
+            assert!(client.calls().contains(&"delete_reminder Rm1".to_string()));
+            assert!(app.reminders.as_ref().unwrap().reminders.is_empty());
+            app.handle_event(key(KeyCode::Esc)).unwrap();
+            assert!(app.reminders.is_none());
+
+            // A token without the scope says which one to add.
+            client.fail_with("add_reminder", "missing_scope (needed: reminders:write)");
+            app.focus = Focus::Input;
+            app.input
+                .set_text("/remind me tomorrow to stretch".to_string());
+            app.handle_event(key(KeyCode::Enter)).unwrap();
+            settle(&mut app).await;
+            let error = app.last_error.clone().unwrap();
+            assert!(error.contains("reminders:write scope"), "{error}");
+        }
+
+        #[tokio::test]
+        async fn saved_messages_are_listed_and_opened_from_the_sidebar() {
+            let older = "1730000000.000100";
+            let client = Arc::new(
+                MockSlackClient::default()
+                    .with_history(
+                        CHANNEL,
+                        vec![
+                            message(older, "U_OTHER", "the runbook is here"),
+                            message("1730000000.000200", "U_OTHER", "thanks"),
+                        ],
+                    )
+                    .with_history("C_DEV", vec![message("1730000001.000100", "U_OTHER", "hi")]),
+            );
+            let mut app = app_with(client.clone());
+            app.channels.push(channel("C_DEV", "dev"));
+            let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+            app.select_channel(CHANNEL);
+            settle(&mut app).await;
+            app.focus = Focus::Messages;
+
+            app.selected_message = Some((CHANNEL.to_string(), older.to_string()));
+            app.handle_event(key(KeyCode::Char('S'))).unwrap();
+            settle(&mut app).await;
+            assert!(client
+                .calls()
+                .contains(&format!("save_message {CHANNEL} {older}")));
+            assert!(app.is_saved(CHANNEL, older));
+
+            // From another channel, the Saved entry sits above Threads,
+            // above the first row.
+            app.select_channel("C_DEV");
+            settle(&mut app).await;
+            app.focus = Focus::Sidebar;
+            app.sidebar_cursor = 0;
+            app.handle_event(key(KeyCode::Up)).unwrap();
+            app.handle_event(key(KeyCode::Up)).unwrap();
+            assert_eq!(app.sidebar_pinned, Some(PinnedEntry::Saved));
+            assert_eq!(app.cursor_channel_id(), None);
+            // Refresh has its own key; `R` here has no channel to mark.
+            app.handle_event(key(KeyCode::Char('R'))).unwrap();
+            settle(&mut app).await;
+            assert!(!client
+                .calls()
+                .iter()
+                .any(|call| call.starts_with("list_channels")));
+            app.handle_event(key(KeyCode::Enter)).unwrap();
+            settle(&mut app).await;
+            assert!(app.saved_view.is_some());
+            assert_eq!(app.saved_items.len(), 1);
+
+            app.handle_event(key(KeyCode::Enter)).unwrap();
+            settle(&mut app).await;
+            assert!(app.saved_view.is_none());
+            assert_eq!(app.selected_channel_id.as_deref(), Some(CHANNEL));
+            assert_eq!(
+                app.selected_message,
+                Some((CHANNEL.to_string(), older.to_string()))
+            );
+
+            app.handle_event(key(KeyCode::Char('S'))).unwrap();
+            settle(&mut app).await;
+            assert!(client
+                .calls()
+                .contains(&format!("unsave_message {CHANNEL} {older}")));
+            assert!(!app.is_saved(CHANNEL, older));
+        }
+
+        #[tokio::test]
+        async fn agent_commands_are_refused_where_the_agent_is_off() {
+            let client = Arc::new(MockSlackClient::default());
+            let mut app = app_with(client.clone());
+            app.config.zeroclaw.agent_enabled = false;
+            app.select_channel(CHANNEL);
+            app.focus = Focus::Input;
+            let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+            for c in "/draft a reply".chars() {
+                app.handle_event(key(KeyCode::Char(c))).unwrap();
+            }
+            app.handle_event(key(KeyCode::Enter)).unwrap();
+            // Refused outright: no offer to connect, no dialog.
+            assert!(app.agent_offline_prompt.is_none());
+            assert!(app.confirmation_dialog.is_none());
+            let error = app.last_error.clone().unwrap();
+            assert!(error.contains("off in test"), "{error}");
+            assert!(error.contains("/agent on"), "{error}");
+
+            // Without a session there is nowhere to keep a per-workspace choice.
+            app.input.set_text("/agent on".to_string());
+            app.focus = Focus::Input;
+            app.handle_event(key(KeyCode::Enter)).unwrap();
+            assert!(!app.agent_enabled());
+        }
+
+        #[tokio::test]
+        async fn keep_active_pings_until_idle_or_away() {
+            let client = Arc::new(MockSlackClient::default());
+            let mut app = app_with(client.clone());
+            let pings = |client: &MockSlackClient| {
+                client
+                    .calls()
+                    .iter()
+                    .filter(|c| c.as_str() == "set_presence auto")
+                    .count()
+            };
+
+            // Off unless asked for.
+            app.keep_presence_active(Instant::now());
+            settle(&mut app).await;
+            assert_eq!(pings(&client), 0);
+
+            app.config.presence.keep_active = true;
+            let start = Instant::now();
+            app.last_input_at = start;
+            app.keep_presence_active(start);
+            app.keep_presence_active(start + Duration::from_secs(60));
+            settle(&mut app).await;
+            assert_eq!(pings(&client), 1);
+
+            // Ten quiet minutes: no more pings until the next key press.
+            let later = start + Duration::from_secs(11 * 60);
+            app.keep_presence_active(later);
+            settle(&mut app).await;
+            assert_eq!(pings(&client), 1);
+            app.last_input_at = later;
+            app.keep_presence_active(later);
+            settle(&mut app).await;
+            assert_eq!(pings(&client), 2);
+
+            let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+            app.focus = Focus::Input;
+            app.input.set_text("/away".to_string());
+            app.handle_event(key(KeyCode::Enter)).unwrap();
+            settle(&mut app).await;
+            assert!(app.presence_away);
+            assert!(client.calls().contains(&"set_presence away".to_string()));
+            let after_away = Instant::now() + Duration::from_secs(6 * 60);
+            app.last_input_at = after_away;
+            app.keep_presence_active(after_away);
+            settle(&mut app).await;
+            assert_eq!(pings(&client), 2);
+
+            app.focus = Focus::Input;
+            app.input.set_text("/active".to_string());
+            app.handle_event(key(KeyCode::Enter)).unwrap();
+            settle(&mut app).await;
+            assert!(!app.presence_away);
+            assert_eq!(pings(&client), 3);
+        }
+
+        #[tokio::test]
+        async fn replies_in_followed_threads_are_flagged_and_listed() {
+            let parent = "1730000000.000100";
+            let mut root = message(parent, "U_OTHER", "who can look at the deploy?");
+            root.reply_count = Some(1);
+            let client = Arc::new(MockSlackClient::default().with_history(CHANNEL, vec![root]));
+            let mut app = app_with(client.clone());
+            app.workspaces[0].channels.push(channel("C_DEV", "dev"));
+            app.channels.push(channel("C_DEV", "dev"));
+            let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+
+            app.dispatch_send(
+                PendingSend {
+                    team_id: "T_TEST".to_string(),
+                    channel_id: CHANNEL.to_string(),
+                    text: "on it".to_string(),
+                    thread_ts: Some(parent.to_string()),
+                    local_id: None,
+                },
+                "Failed to send reply",
+            );
+            settle(&mut app).await;
+            let followed = &app.workspaces[0].workspace.threads;
+            assert_eq!(followed.len(), 1);
+            assert_eq!(followed[0].thread_ts, parent);
+            assert_eq!(followed[0].unread, 0);
+
+            // Replies arrive while another channel is open; only the one in
+            // the followed thread counts as a mention.
+            app.select_channel("C_DEV");
+            settle(&mut app).await;
+            let tx = app.event_tx.clone().unwrap();
+            let mut reply = message("1730000100.000100", "U_OTHER", "thanks!");
+            reply.thread_ts = Some(parent.to_string());
+            let mut elsewhere = message("1730000200.000100", "U_OTHER", "unrelated");
+            elsewhere.thread_ts = Some("1720000000.000100".to_string());
+            for message in [reply, elsewhere] {
+                tx.send(SlackEvent::Message {
+                    channel: CHANNEL.to_string(),
+                    message: Box::new(message),
+                })
+                .unwrap();
+            }
+            app.process_slack_events();
+            let general = app.channels.iter().find(|ch| ch.id == CHANNEL).unwrap();
+            assert_eq!(general.unread_count, 2);
+            assert_eq!(general.mention_count, 1);
+            let followed = &app.workspaces[0].workspace.threads[0];
+            assert_eq!(followed.unread, 1);
+            assert_eq!(followed.last_text, "thanks!");
+
+            app.focus = Focus::Sidebar;
+            app.sidebar_cursor = 0;
+            app.handle_event(key(KeyCode::Up)).unwrap();
+            assert_eq!(app.sidebar_pinned, Some(PinnedEntry::Threads));
+            app.handle_event(key(KeyCode::Enter)).unwrap();
+            assert!(app.threads_view.is_some());
+            app.handle_event(key(KeyCode::Enter)).unwrap();
+            settle(&mut app).await;
+            assert!(app.threads_view.is_none());
+            assert_eq!(app.selected_channel_id.as_deref(), Some(CHANNEL));
+            assert_eq!(
+                app.selected_message,
+                Some((CHANNEL.to_string(), parent.to_string()))
+            );
+            assert!(client
+                .calls()
+                .contains(&format!("get_thread_replies {CHANNEL} {parent}")));
+            assert_eq!(app.workspaces[0].workspace.threads[0].unread, 0);
+        }
+
+        #[tokio::test]
+        async fn quick_switcher_reaches_channels_in_other_workspaces() {
+            let client = Arc::new(MockSlackClient::default());
+            let mut app = app_with(client.clone());
+            app.workspaces[0].channels.push(channel("C_DEV", "dev"));
+            app.channels.push(channel("C_DEV", "dev"));
+            let mut other = super::WorkspaceState::new(super::Workspace {
+                team_id: "T_OTHER".to_string(),
+                team_name: "other".to_string(),
+                xoxp_token: "xoxp-other".to_string(),
+                xapp_token: "xapp-other".to_string(),
+                user_id: Some(ME.to_string()),
+                active: false,
+                last_channel_id: None,
+                icon_url: None,
+                threads: Vec::new(),
+                muted_channels: Vec::new(),
+                recent_reactions: Vec::new(),
+            });
+            other.channels.push(channel("C_DEPLOYS", "deploys"));
+            app.workspaces.push(other);
+            let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+            let names = |app: &super::App| -> Vec<String> {
+                app.channel_picker
+                    .as_ref()
+                    .unwrap()
+                    .filtered_channels
+                    .iter()
+                    .map(|entry| entry.channel.name.clone())
+                    .collect()
+            };
+
+            app.handle_event(Event::Key(KeyEvent::new(
+                KeyCode::Char('k'),
+                KeyModifiers::CONTROL,
+            )))
+            .unwrap();
+            app.handle_event(key(KeyCode::Char('d'))).unwrap();
+            app.handle_event(key(KeyCode::Char('e'))).unwrap();
+            assert_eq!(names(&app), vec!["dev"]);
+
+            // Tab widens the search; the active workspace still ranks first.
+            app.handle_event(key(KeyCode::Tab)).unwrap();
+            assert_eq!(names(&app), vec!["dev", "deploys"]);
+            assert_eq!(
+                app.channel_picker.as_ref().unwrap().filtered_channels[1]
+                    .team_id
+                    .as_deref(),
+                Some("T_OTHER")
+            );
+
+            // Channel lists refreshing while the popup is open show up in it.
+            app.app_async_tx
+                .clone()
+                .unwrap()
+                .send(AppAsyncEvent::WorkspaceChannelsLoaded {
+                    team_id: "T_OTHER".to_string(),
+                    channels: vec![channel("C_DESIGN", "design")],
+                    append: true,
+                    done: true,
+                    error: None,
+                })
+                .unwrap();
+            app.process_slack_events();
+            assert_eq!(names(&app).len(), 3);
+
+            app.handle_event(key(KeyCode::Char('p'))).unwrap();
+            assert_eq!(names(&app), vec!["deploys"]);
+            app.handle_event(key(KeyCode::Enter)).unwrap();
+            assert_eq!(app.active_workspace_id.as_deref(), Some("T_OTHER"));
+            assert_eq!(app.selected_channel_id.as_deref(), Some("C_DEPLOYS"));
+            settle(&mut app).await;
+            assert!(client
+                .calls()
+                .contains(&"get_history C_DEPLOYS".to_string()));
+
+            // A leading `*` searches everywhere without Tab.
+            app.handle_event(Event::Key(KeyEvent::new(
+                KeyCode::Char('k'),
+                KeyModifiers::CONTROL,
+            )))
+            .unwrap();
+            for c in "*dev".chars() {
+                app.handle_event(key(KeyCode::Char(c))).unwrap();
+            }
+            assert_eq!(names(&app), vec!["dev"]);
+            assert_eq!(
+                app.channel_picker.as_ref().unwrap().filtered_channels[0]
+                    .team_id
+                    .as_deref(),
+                Some("T_TEST")
+            );
+        }
+
+        #[tokio::test]
+        async fn rejected_tokens_surface_in_the_workspace_issues_panel() {
+            let client = Arc::new(MockSlackClient::default());
+            let mut app = app_with(client.clone());
+            // No Socket Mode connection in tests; only the Web API is mocked.
+            app.event_tx = None;
+            let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+
+            client.fail_with("test_auth", "token_revoked");
+            app.connect_workspace(0);
+            settle(&mut app).await;
+            assert_eq!(app.workspace_issues.len(), 1);
+            assert!(app.workspace_issues[0].reason.contains("revoked"));
+            assert!(app.workspace_issues_view.is_some());
+            assert!(!app.channels_loading.contains("T_TEST"));
+            assert!(!client
+                .calls()
+                .iter()
+                .any(|call| call.starts_with("list_channels_page")));
+
+            // Once Slack accepts the token again, retrying loads the workspace.
+            client.recover("test_auth");
+            app.handle_event(key(KeyCode::Char('r'))).unwrap();
+            assert!(app.workspace_issues.is_empty());
+            assert!(app.workspace_issues_view.is_none());
+            settle(&mut app).await;
+            assert!(client
+                .calls()
+                .contains(&"list_channels_page xoxp-test".to_string()));
+
+            client.fail_with("test_auth", "invalid_auth");
+            app.connect_workspace(0);
+            settle(&mut app).await;
+            app.handle_event(key(KeyCode::Char('x'))).unwrap();
+            assert!(app.workspaces.is_empty());
+            assert!(app.workspace_issues.is_empty());
+            assert_eq!(app.active_workspace_id, None);
+        }
+
+        #[tokio::test]
+        async fn jumps_load_the_conversation_around_the_message() {
+            use ratatui::backend::TestBackend;
+            use ratatui::Terminal;
+
+            let ts = |i: usize| format!("{}.000100", 1_730_000_000 + i * 60);
+            let history: Vec<_> = (0..60)
+                .map(|i| message(&ts(i), "U_OTHER", &format!("message {i}")))
+                .collect();
+            let client = Arc::new(MockSlackClient::default().with_history(CHANNEL, history));
+            let mut app = app_with(client.clone());
+            app.select_channel(CHANNEL);
+            settle(&mut app).await;
+            // Only the latest page is loaded.
+            app.messages.get_mut(CHANNEL).unwrap().drain(..55);
+
+            app.pending_jump = Some(JumpTarget {
+                channel_id: CHANNEL.to_string(),
+                ts: ts(10),
+                open_thread: false,
+            });
+            app.finish_jump();
+            settle(&mut app).await;
+            assert!(client
+                .calls()
+                .contains(&format!("get_history_around {CHANNEL} {}", ts(10))));
+            assert_eq!(app.messages[CHANNEL].len(), 31 + 5);
+            assert_eq!(app.selected_message, Some((CHANNEL.to_string(), ts(10))));
+            assert_eq!(app.newer_history.get(CHANNEL), Some(&ts(30)));
+
+            app.is_loading = false;
+            let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+            terminal.draw(|frame| app.render(frame)).unwrap();
+            let screen: String = terminal
+                .backend()
+                .buffer()
+                .content()
+                .iter()
+                .map(|cell| cell.symbol())
+                .collect();
+            assert!(screen.contains("▌"));
+
+            // Scrolling down to the end of the window pages in what follows,
+            // until it meets the messages already loaded.
+            app.scroll_messages(-20);
+            assert!(app.context_anchor.is_none());
+            settle(&mut app).await;
+            assert_eq!(app.newer_history.get(CHANNEL), Some(&ts(50)));
+            app.scroll_messages(-1);
+            settle(&mut app).await;
+            assert_eq!(app.messages[CHANNEL].len(), 60);
+            assert!(app.newer_history.is_empty());
+        }
+
+        #[tokio::test]
+        async fn sent_messages_track_their_delivery() {
+            let client = Arc::new(MockSlackClient::default());
+            client.fail("send_message");
+            let mut app = app_with(client.clone());
+            app.select_channel(CHANNEL);
+            settle(&mut app).await;
+            let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+            let send = |app: &mut super::App, text: &str| {
+                app.focus = Focus::Input;
+                for c in text.chars() {
+                    app.handle_event(key(KeyCode::Char(c))).unwrap();
+                }
+                app.handle_event(key(KeyCode::Enter)).unwrap();
+            };
+            let delivery = |app: &super::App, text: &str| {
+                app.messages[CHANNEL]
+                    .iter()
+                    .filter(|m| m.text == text)
+                    .map(|m| m.delivery)
+                    .collect::<Vec<_>>()
+            };
+
+            send(&mut app, "hello");
+            assert_eq!(delivery(&app, "hello"), [Some(DeliveryState::Sending)]);
+            settle(&mut app).await;
+            assert_eq!(delivery(&app, "hello"), [Some(DeliveryState::Failed)]);
+
+            // Enter on the failed copy sends it again and `R` no longer can.
+            client.recover("send_message");
+            app.focus = Focus::Messages;
+            app.handle_event(key(KeyCode::Enter)).unwrap();
+            assert!(app.error_history[0].retry.is_none());
+            settle(&mut app).await;
+            assert_eq!(delivery(&app, "hello"), [Some(DeliveryState::Sent)]);
+            assert!(!app.messages[CHANNEL]
+                .iter()
+                .any(|m| m.ts.starts_with("local-")));
+
+            client.fail("send_message");
+            send(&mut app, "oops");
+            settle(&mut app).await;
+            app.focus = Focus::Messages;
+            app.handle_event(key(KeyCode::Char('d'))).unwrap();
+            assert!(delivery(&app, "oops").is_empty());
+            assert!(app.failed_sends.is_empty());
+            let sends = client
+                .calls()
+                .into_iter()
+                .filter(|call| call.starts_with("send_message"))
+                .count();
+            assert_eq!(sends, 3);
+        }
+
+        #[tokio::test]
+        async fn channel_commands_run_locally_and_toast_their_result() {
+            let client = Arc::new(MockSlackClient::default());
+            let mut app = app_with(client.clone());
+            app.workspaces[0]
+                .channels
+                .push(channel("C_RANDOM", "random"));
+            app.channels.push(channel("C_RANDOM", "random"));
+            app.select_channel(CHANNEL);
+            let notice = |app: &super::App| app.notice.as_ref().map(|(text, _)| text.clone());
+            async fn submit(app: &mut super::App, text: &str) {
+                app.focus = Focus::Input;
+                app.input.set_text(text.to_string());
+                let enter = Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+                app.handle_event(enter).unwrap();
+                settle(app).await;
+            }
+
+            // Tab completes the command name the border suggests.
+            app.focus = Focus::Input;
+            app.input.set_text("/jo".to_string());
+            assert_eq!(app.slash_suggestions()[0].usage(), "/join #channel");
+            app.handle_event(Event::Key(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE)))
+                .unwrap();
+            assert_eq!(app.input.buffer, "/join ");
+
+            submit(&mut app, "/join #random").await;
+            assert!(client
+                .calls()
+                .contains(&"join_channel C_RANDOM".to_string()));
+            assert_eq!(app.selected_channel_id.as_deref(), Some("C_RANDOM"));
+            assert_eq!(notice(&app).as_deref(), Some("Joined #random"));
+
+            submit(&mut app, "/topic Release week").await;
+            assert!(client
+                .calls()
+                .contains(&"set_channel_topic C_RANDOM Release week".to_string()));
+            let calls = client.calls().len();
+            submit(&mut app, "/topic").await;
+            assert_eq!(client.calls().len(), calls);
+            assert_eq!(notice(&app).as_deref(), Some("#random topic: Release week"));
+
+            submit(&mut app, "/mute").await;
+            assert!(app.is_muted("C_RANDOM"));
+            assert!(app.workspaces[0].workspace.muted_channels == ["C_RANDOM"]);
+            submit(&mut app, "/mute").await;
+            assert!(!app.is_muted("C_RANDOM"));
+
+            client.fail_with("leave_channel", "cant_leave_general");
+            submit(&mut app, "/leave").await;
+            let error = app.last_error.clone().unwrap();
+            assert!(error.starts_with("Failed to leave #random"), "{error}");
+            assert!(error.contains("cant_leave_general"), "{error}");
+
+            // Unknown commands stay local unless forwarding is turned on.
+            submit(&mut app, "/giphy cats").await;
+            assert!(app
+                .last_error
+                .clone()
+                .unwrap()
+                .starts_with("Unknown command"));
+            assert!(app.agent_offline_prompt.is_none());
+            app.config.zeroclaw.forward_unknown_commands = true;
+            submit(&mut app, "/giphy cats").await;
+            assert_eq!(app.agent_offline_prompt.as_deref(), Some("/giphy cats"));
+        }
+
+        #[tokio::test]
+        async fn dm_shows_seen_under_the_last_message_read() {
+            let client = Arc::new(MockSlackClient::default().with_history(
+                "D_BOB",
+                vec![
+                    message("100.000001", ME, "first"),
+                    message("200.000001", "U_BOB", "reply"),
+                    message("300.000001", ME, "second"),
+                ],
+            ));
+            let mut app = app_with(client.clone());
+            let mut dm = channel("D_BOB", "bob");
+            dm.is_im = true;
+            app.workspaces[0].channels.push(dm.clone());
+            app.channels.push(dm);
+            app.select_channel("D_BOB");
+            settle(&mut app).await;
+            assert!(client
+                .calls()
+                .contains(&"get_channel_info D_BOB".to_string()));
+            let seen = |app: &super::App| {
+                let dm = app.channels.iter().find(|ch| ch.id == "D_BOB").unwrap();
+                app.seen_message_ts(dm)
+            };
+            assert_eq!(seen(&app), None);
+
+            let tx = app.event_tx.as_ref().expect("event tx").clone();
+            let marked = |ts: &str| SlackEvent::ImMarked {
+                channel: "D_BOB".to_string(),
+                ts: ts.to_string(),
+            };
+            tx.send(marked("250.000001")).unwrap();
+            app.process_slack_events();
+            assert_eq!(seen(&app).as_deref(), Some("100.000001"));
+
+            // An older mark never moves the marker back.
+            tx.send(marked("150.000001")).unwrap();
+            app.process_slack_events();
+            assert_eq!(seen(&app).as_deref(), Some("100.000001"));
+
+            tx.send(marked("300.000001")).unwrap();
+            app.process_slack_events();
+            assert_eq!(seen(&app).as_deref(), Some("300.000001"));
+
+            app.config.ui.read_receipts = false;
+            assert_eq!(seen(&app), None);
+        }
+
+        #[tokio::test]
+        async fn sidebar_shows_and_sorts_by_last_activity() {
+            use ratatui::backend::TestBackend;
+            use ratatui::Terminal;
+
+            let now = chrono::Utc::now();
+            let ts = |ago: i64| format!("{}.000100", now.timestamp() - ago);
+            let client = Arc::new(
+                MockSlackClient::default()
+                    .with_history(CHANNEL, vec![message(&ts(3 * 86_400), "U_BOB", "old news")]),
+            );
+            let mut app = app_with(client);
+            for (id, name) in [("C_RANDOM", "random"), ("C_QUIET", "quiet")] {
+                app.workspaces[0].channels.push(channel(id, name));
+                app.channels.push(channel(id, name));
+            }
+            app.select_channel(CHANNEL);
+            settle(&mut app).await;
+            assert_eq!(app.channel_activity.get(CHANNEL), Some(&ts(3 * 86_400)));
+
+            let tx = app.event_tx.as_ref().expect("event tx").clone();
+            tx.send(SlackEvent::Message {
+                channel: "C_RANDOM".to_string(),
+                message: Box::new(message(&ts(300), "U_BOB", "hi")),
+            })
+            .unwrap();
+            app.process_slack_events();
+            assert_eq!(app.channel_activity.get("C_RANDOM"), Some(&ts(300)));
+
+            let age = |ago| super::App::activity_age(&ts(ago), now);
+            assert_eq!(
+                [30, 300, 2 * 3_600, 8 * 86_400, 40 * 86_400, 400 * 86_400].map(age),
+                ["now", "5m", "2h", "1w", "1mo", "1y"]
+            );
+
+            app.is_loading = false;
+            let sidebar_rows = |app: &mut super::App| -> Vec<String> {
+                let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+                terminal.draw(|frame| app.render(frame)).unwrap();
+                let buffer = terminal.backend().buffer();
+                let area = app.layout.get_sidebar_rect().unwrap();
+                (area.y..area.y + area.height)
+                    .map(|y| {
+                        (area.x + 1..area.x + area.width - 1)
+                            .map(|x| buffer[(x, y)].symbol())
+                            .collect::<String>()
+                    })
+                    .collect()
+            };
+            let row_of = |rows: &[String], name: &str| {
+                rows.iter()
+                    .find(|row| row.contains(&format!("# {}", name)))
+                    .map(|row| row.trim_end().to_string())
+                    .unwrap()
+            };
+            let rows = sidebar_rows(&mut app);
+            assert!(row_of(&rows, "random").ends_with(" 5m"), "{rows:?}");
+            assert!(row_of(&rows, "general").ends_with(" 3d"), "{rows:?}");
+            assert!(row_of(&rows, "quiet").ends_with(" •"), "{rows:?}");
+
+            app.config.ui.sort_by_activity = true;
+            app.sort_channels_by_section();
+            let order: Vec<&str> = app.channels.iter().map(|ch| ch.name.as_str()).collect();
+            assert_eq!(order, ["random", "general", "quiet"]);
+
+            app.config.ui.sidebar_activity = false;
+            let rows = sidebar_rows(&mut app);
+            assert!(row_of(&rows, "random").ends_with("# random 1"), "{rows:?}");
+        }
+
+        #[tokio::test]
+        async fn confirmation_dialog_shows_and_cycles_the_reply_target() {
+            use crate::app::ReplyTarget;
+            use ratatui::{backend::TestBackend, Terminal};
+
+            let mut parent = message(
+                "1000.1",
+                "U_ALICE",
+                "Who owns billing?\nAsking for a friend",
+            );
+            parent.reply_count = Some(2);
+            let client = Arc::new(MockSlackClient::default().with_history(CHANNEL, vec![parent]));
+            let mut app = app_with(client.clone());
+            app.select_channel(CHANNEL);
+            settle(&mut app).await;
+            app.is_loading = false;
+            app.agent_status = slack_zc_agent::AgentStatus::Active;
+            app.config.zeroclaw.post_to_slack = true;
+            app.active_threads
+                .insert(CHANNEL.to_string(), "1000.1".to_string());
+            app.focus = Focus::Input;
+            let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+            // An id where the `#` picker would have put the name.
+            app.input.set_text("/résume #C_GENERAL".to_string());
+            app.handle_event(key(KeyCode::Enter)).unwrap();
+            let dialog = app.confirmation_dialog.clone().expect("dialog");
+            assert_eq!(dialog.reply, ReplyTarget::Thread);
+            assert_eq!(
+                dialog.thread_parent.as_deref(),
+                Some("u_alice: Who owns billing?")
+            );
+
+            let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+            terminal.draw(|frame| app.render(frame)).unwrap();
+            let screen: String = terminal
+                .backend()
+                .buffer()
+                .content()
+                .iter()
+                .map(|cell| cell.symbol())
+                .collect();
+            assert!(screen.contains("Context: # general"), "{screen}");
+            assert!(
+                screen.contains("thread in # general — u_alice: Who owns billing?"),
+                "{screen}"
+            );
+
+            // `c` types into the prompt until Tab leaves it.
+            app.handle_event(key(KeyCode::Char('c'))).unwrap();
+            assert_eq!(app.confirmation_dialog.as_ref().unwrap().prompt, "c");
+            app.handle_event(key(KeyCode::Backspace)).unwrap();
+            app.handle_event(key(KeyCode::Tab)).unwrap();
+            app.handle_event(key(KeyCode::Char('c'))).unwrap();
+            let dialog = app.confirmation_dialog.as_ref().unwrap();
+            assert_eq!(dialog.reply, ReplyTarget::Channel);
+            let (target, post) = dialog.chosen_target();
+            assert_eq!(target.unwrap().thread_ts, None);
+            assert!(post);
+
+            app.handle_event(key(KeyCode::Char('c'))).unwrap();
+            let (target, post) = app.confirmation_dialog.as_ref().unwrap().chosen_target();
+            assert_eq!(target.unwrap().channel_id, CHANNEL);
+            assert!(!post);
+
+            // Back to the thread captured when the dialog opened, whatever
+            // happened to the channel's active thread since.
+            app.active_threads.remove(CHANNEL);
+            app.handle_event(key(KeyCode::Char('c'))).unwrap();
+            let (target, post) = app.confirmation_dialog.as_ref().unwrap().chosen_target();
+            assert_eq!(target.unwrap().thread_ts.as_deref(), Some("1000.1"));
+            assert!(post);
+        }
+
+        #[tokio::test]
+        async fn agent_commands_rerun_per_channel_through_the_confirmation() {
+            use ratatui::{backend::TestBackend, Terminal};
+
+            let client = Arc::new(MockSlackClient::default());
+            let mut app = app_with(client);
+            app.select_channel(CHANNEL);
+            settle(&mut app).await;
+            app.is_loading = false;
+            app.agent_status = slack_zc_agent::AgentStatus::Active;
+            let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+            for command in ["/résume 2h", "/draft standup notes"] {
+                app.focus = Focus::Input;
+                app.input.set_text(command.to_string());
+                app.handle_event(key(KeyCode::Enter)).unwrap();
+                assert!(!app.confirmation_dialog.as_ref().unwrap().rerun);
+                app.handle_event(key(KeyCode::Esc)).unwrap();
+            }
+            assert_eq!(
+                app.channel_agent_commands(),
+                ["/draft standup notes", "/résume 2h"]
+            );
+
+            // `.` asks again for the latest, like the original did.
+            app.focus = Focus::Messages;
+            app.handle_event(key(KeyCode::Char('.'))).unwrap();
+            let dialog = app.confirmation_dialog.clone().expect("dialog");
+            assert_eq!((dialog.command.as_str(), dialog.rerun), ("/draft", true));
+            app.handle_event(key(KeyCode::Esc)).unwrap();
+
+            // A count lists the history and picks from it.
+            app.handle_event(key(KeyCode::Char('2'))).unwrap();
+            assert_eq!(app.rerun_count, Some(2));
+            let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+            terminal.draw(|frame| app.render(frame)).unwrap();
+            let screen: String = terminal
+                .backend()
+                .buffer()
+                .content()
+                .iter()
+                .map(|cell| cell.symbol())
+                .collect();
+            assert!(screen.contains("2 /résume 2h"), "{screen}");
+            app.handle_event(key(KeyCode::Char('.'))).unwrap();
+            let dialog = app.confirmation_dialog.clone().expect("dialog");
+            assert_eq!(dialog.command, "/résume");
+            assert_eq!(dialog.prompt, "2h");
+            app.handle_event(key(KeyCode::Esc)).unwrap();
+            assert_eq!(app.channel_agent_commands()[0], "/résume 2h");
+
+            app.handle_agent_command("/again 3").unwrap();
+            assert!(app.confirmation_dialog.is_none());
+            assert!(app.last_error.as_deref().unwrap().contains("#3"));
+            // Digits past the history are left alone.
+            app.handle_event(key(KeyCode::Char('7'))).unwrap();
+            assert_eq!(app.rerun_count, None);
+        }
+
+        #[tokio::test]
+        async fn splash_lists_startup_steps_until_channels_arrive() {
+            use crate::app::{App, InitStage, StageStatus};
+            use ratatui::{backend::TestBackend, Terminal};
+
+            let client = Arc::new(MockSlackClient::default());
+            let mut app = app_with(client.clone());
+            app.channels.clear();
+            app.workspaces[0].channels.clear();
+            assert!(app.is_loading);
+            app.set_init_stage(InitStage::Session, StageStatus::Done);
+            app.connect_workspace(0);
+            app.set_init_stage(
+                InitStage::Agent,
+                StageStatus::Failed("gateway not found".to_string()),
+            );
+
+            let screen = |app: &mut App| {
+                let mut terminal = Terminal::new(TestBackend::new(60, 20)).unwrap();
+                terminal.draw(|frame| app.render(frame)).unwrap();
+                let buffer = terminal.backend().buffer().clone();
+                (0..buffer.area.height)
+                    .map(|y| {
+                        (0..buffer.area.width)
+                            .map(|x| buffer[(x, y)].symbol())
+                            .collect::<String>()
+                            .trim_end()
+                            .to_string()
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            };
+            let shown = screen(&mut app);
+            for row in [
+                "│  ✓ Unlock session",
+                "│  test",
+                "│    · Check token",
+                "│    · Load channels",
+                "│  ✗ Start ZeroClaw",
+                "│    gateway not found",
+            ] {
+                assert!(shown.contains(row), "{row}\n{shown}");
+            }
+
+            // Channels for the active workspace end the splash; the rest
+            // carries on behind the main screen.
+            settle(&mut app).await;
+            assert!(!app.is_loading);
+            assert!(app.init_stages.is_empty());
+            assert!(client
+                .calls()
+                .iter()
+                .any(|c| c.starts_with("list_channels_page")));
+
+            // A key skips the wait once the session is read.
+            let mut app = app_with(client);
+            app.handle_event(Event::Key(KeyEvent::new(
+                KeyCode::Char('j'),
+                KeyModifiers::NONE,
+            )))
+            .unwrap();
+            assert!(app.is_loading);
+            app.set_init_stage(InitStage::Session, StageStatus::Done);
+            app.handle_event(Event::Key(KeyEvent::new(
+                KeyCode::Char('j'),
+                KeyModifiers::NONE,
+            )))
+            .unwrap();
+            assert!(!app.is_loading);
+        }
+
+        #[tokio::test]
+        async fn agent_responses_show_and_open_where_they_ran() {
+            use crate::app::{AgentHistoryPopup, AgentResponse};
+            use ratatui::{backend::TestBackend, Terminal};
+
+            let mut parent = message("1000.1", "U_OTHER", "deploy plan");
+            parent.reply_count = Some(1);
+            let client = Arc::new(MockSlackClient::default().with_history(CHANNEL, vec![parent]));
+            let mut app = app_with(client.clone());
+            app.channels.push(channel("C_RANDOM", "random"));
+            app.select_channel("C_RANDOM");
+            settle(&mut app).await;
+            app.is_loading = false;
+            let response = AgentResponse {
+                command: "/résume".to_string(),
+                response: "Ship on Friday".to_string(),
+                timestamp: chrono::Utc::now(),
+                channel_id: Some(CHANNEL.to_string()),
+                channel_name: Some("general".to_string()),
+                thread_ts: Some("1000.1".to_string()),
+            };
+            app.agent_responses.push_front(response.clone());
+
+            let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+            terminal.draw(|frame| app.render(frame)).unwrap();
+            let screen: String = terminal
+                .backend()
+                .buffer()
+                .content()
+                .iter()
+                .map(|cell| cell.symbol())
+                .collect();
+            assert!(screen.contains("#general (thread)"), "{screen}");
+
+            // The persisted form keeps the thread; older lines without it
+            // still load.
+            let line = serde_json::to_string(&response).unwrap();
+            assert!(line.contains(r#""thread_ts":"1000.1""#));
+            let old: AgentResponse = serde_json::from_str(
+                r#"{"command":"/draft","response":"hi","timestamp":"2026-01-01T00:00:00Z"}"#,
+            )
+            .unwrap();
+            assert!(old.thread_ts.is_none() && old.channel_id.is_none());
+
+            app.agent_history = Some(AgentHistoryPopup {
+                entries: vec![response],
+                ..Default::default()
+            });
+            app.handle_event(Event::Key(KeyEvent::new(
+                KeyCode::Enter,
+                KeyModifiers::NONE,
+            )))
+            .unwrap();
+            assert!(app.agent_history.is_none());
+            assert_eq!(app.selected_channel_id.as_deref(), Some(CHANNEL));
+            assert_eq!(app.focus, Focus::Messages);
+            settle(&mut app).await;
+            assert!(client
+                .calls()
+                .iter()
+                .any(|call| call.starts_with(&format!("get_thread_replies {CHANNEL} 1000.1"))));
+        }
+
+        #[tokio::test]
+        async fn visual_selection_spans_cursor_moves_and_copy_mode_eats_a_key() {
+            use ratatui::{backend::TestBackend, Terminal};
+
+            let client = Arc::new(MockSlackClient::default().with_history(
+                CHANNEL,
+                vec![
+                    message("1000.1", "U_ANN", "first"),
+                    message("1000.2", "U_BOB", "second"),
+                    message("1000.3", "U_ANN", "third"),
+                ],
+            ));
+            let mut app = app_with(client);
+            app.select_channel(CHANNEL);
+            settle(&mut app).await;
+            app.is_loading = false;
+            app.focus = Focus::Messages;
+            let press = |app: &mut crate::app::App, code: KeyCode| {
+                app.handle_event(Event::Key(KeyEvent::new(code, KeyModifiers::NONE)))
+                    .unwrap();
+            };
+
+            press(&mut app, KeyCode::Char('v'));
+            press(&mut app, KeyCode::Char('k'));
+            let lines: Vec<String> = app
+                .visual_selection_text()
+                .lines()
+                .map(|line| line.split_once("] ").unwrap().1.to_string())
+                .collect();
+            assert_eq!(lines, ["u_bob: second", "u_ann: third"]);
+
+            let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+            terminal.draw(|frame| app.render(frame)).unwrap();
+            let screen: String = terminal
+                .backend()
+                .buffer()
+                .content()
+                .iter()
+                .map(|cell| cell.symbol())
+                .collect();
+            assert!(screen.contains("VISUAL · 2 selected"), "{screen}");
+
+            press(&mut app, KeyCode::Esc);
+            assert!(app.visual_selection.is_none());
+            assert!(app.visual_selection_text().is_empty());
+
+            press(&mut app, KeyCode::F(9));
+            assert!(app.copy_mode);
+            terminal.draw(|frame| app.render(frame)).unwrap();
+            let screen: String = terminal
+                .backend()
+                .buffer()
+                .content()
+                .iter()
+                .map(|cell| cell.symbol())
+                .collect();
+            assert!(screen.contains("copy mode — mouse selection enabled"));
+            // The key that ends copy mode does nothing else.
+            press(&mut app, KeyCode::Char('v'));
+            assert!(!app.copy_mode);
+            assert!(app.visual_selection.is_none());
+        }
+
+        #[tokio::test]
+        async fn restricted_channels_block_posts_but_allow_open_threads() {
+            use ratatui::{backend::TestBackend, Terminal};
+            use slack_zc_slack::types::PostingRestriction;
+
+            let mut parent = message("1000.1", "U_BOB", "all hands at 3");
+            parent.reply_count = Some(1);
+            let client = Arc::new(MockSlackClient::default().with_history(CHANNEL, vec![parent]));
+            let mut announcements = channel(CHANNEL, "general");
+            announcements.posting_restricted_to = Some(PostingRestriction::admins_only());
+            client.set_channel_info(announcements);
+            let mut app = app_with(client.clone());
+            app.channels.push(channel("C_RANDOM", "random"));
+            app.select_channel(CHANNEL);
+            settle(&mut app).await;
+            app.is_loading = false;
+
+            let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+            terminal.draw(|frame| app.render(frame)).unwrap();
+            let screen: String = terminal
+                .backend()
+                .buffer()
+                .content()
+                .iter()
+                .map(|cell| cell.symbol())
+                .collect();
+            assert!(
+                screen.contains("read-only channel: only admins can post"),
+                "{screen}"
+            );
+
+            app.input.set_text("can I post?".to_string());
+            app.handle_input_submit().unwrap();
+            settle(&mut app).await;
+            assert_eq!(app.input.buffer, "can I post?");
+            assert!(!client.calls().iter().any(|c| c.starts_with("send_message")));
+            assert!(app.error_history[0]
+                .message
+                .contains("only admins can post"));
+
+            // Replies are only restricted when Slack says so.
+            app.active_threads
+                .insert(CHANNEL.to_string(), "1000.1".to_string());
+            assert!(app.read_only_here().is_none());
+            app.handle_input_submit().unwrap();
+            settle(&mut app).await;
+            assert!(client
+                .calls()
+                .iter()
+                .any(|c| c.starts_with("send_message_to_thread")));
+            app.active_threads.remove(CHANNEL);
+
+            // Opening the channel again picks up changed permissions.
+            client.set_channel_info(channel(CHANNEL, "general"));
+            app.select_channel("C_RANDOM");
+            app.select_channel(CHANNEL);
+            settle(&mut app).await;
+            assert!(app.read_only_here().is_none());
+        }
+
+        #[tokio::test]
+        async fn reaction_picker_puts_recent_reactions_on_number_keys() {
+            let client = Arc::new(
+                MockSlackClient::default()
+                    .with_history(CHANNEL, vec![message("1000.1", "U_BOB", "shipped")]),
+            );
+            let mut app = app_with(client.clone());
+            app.select_channel(CHANNEL);
+            settle(&mut app).await;
+            app.focus = Focus::Messages;
+            let press = |app: &mut crate::app::App, code: KeyCode| {
+                app.handle_event(Event::Key(KeyEvent::new(code, KeyModifiers::NONE)))
+                    .unwrap();
+            };
+            let reactions = |app: &crate::app::App| -> Vec<(String, u32)> {
+                app.messages[CHANNEL][0]
+                    .reactions
+                    .iter()
+                    .map(|r| (r.name.clone(), r.count))
+                    .collect()
+            };
+            let uses = |app: &crate::app::App, name: &str| {
+                app.workspaces[0]
+                    .workspace
+                    .recent_reactions
+                    .iter()
+                    .find(|used| used.name == name)
+                    .map_or(0, |used| used.uses)
+            };
+
+            // Without history the shortcuts come from the catalog.
+            press(&mut app, KeyCode::Char('r'));
+            assert_eq!(
+                app.reaction_picker.as_ref().unwrap().names[..2],
+                ["+1", "heart"]
+            );
+            press(&mut app, KeyCode::Char('2'));
+            settle(&mut app).await;
+            assert!(app.reaction_picker.is_none());
+            assert_eq!(reactions(&app), [("heart".to_string(), 1)]);
+            assert_eq!(uses(&app, "heart"), 1);
+
+            // The reaction just used leads, and picking it again removes it.
+            press(&mut app, KeyCode::Char('r'));
+            assert_eq!(app.reaction_picker.as_ref().unwrap().names[0], "heart");
+            press(&mut app, KeyCode::Char('1'));
+            settle(&mut app).await;
+            assert!(reactions(&app).is_empty());
+            assert_eq!(uses(&app, "heart"), 1);
+            let calls = client.calls();
+            assert!(calls.contains(&format!("add_reaction {CHANNEL} 1000.1 heart")));
+            assert!(calls.contains(&format!("remove_reaction {CHANNEL} 1000.1 heart")));
+
+            // Typing searches the catalog.
+            press(&mut app, KeyCode::Char('r'));
+            for c in "rock".chars() {
+                press(&mut app, KeyCode::Char(c));
+            }
+            press(&mut app, KeyCode::Enter);
+            settle(&mut app).await;
+            assert_eq!(reactions(&app), [("rocket".to_string(), 1)]);
+
+            // Reactions added from another client count too, once.
+            let tx = app.event_tx.clone().unwrap();
+            for _ in 0..2 {
+                tx.send(SlackEvent::ReactionChanged {
+                    channel: CHANNEL.to_string(),
+                    ts: "1000.1".to_string(),
+                    user: ME.to_string(),
+                    name: "eyes".to_string(),
+                    added: true,
+                })
+                .unwrap();
+            }
+            app.process_slack_events();
+            assert_eq!(
+                reactions(&app),
+                [("rocket".to_string(), 1), ("eyes".to_string(), 1)]
+            );
+            assert_eq!(uses(&app, "eyes"), 1);
+        }
+
+        #[tokio::test]
+        async fn channel_refresh_merges_new_and_removed_channels() {
+            let client = Arc::new(MockSlackClient::default());
+            let mut app = app_with(client.clone());
+            app.is_loading = false;
+            for id in ["C_LEFT", "C_OPEN"] {
+                app.workspaces[0].channels.push(channel(id, id));
+                app.channels.push(channel(id, id));
+            }
+            app.update_channel(CHANNEL, |ch| ch.unread_count = 3);
+            app.select_channel("C_OPEN");
+            settle(&mut app).await;
+            client.list_channel(channel("C_NEW", "new"));
+            let listed = |app: &crate::app::App| -> Vec<String> {
+                let mut ids: Vec<String> = app.channels.iter().map(|ch| ch.id.clone()).collect();
+                ids.sort();
+                ids
+            };
+
+            app.handle_event(Event::Key(KeyEvent::new(KeyCode::F(5), KeyModifiers::NONE)))
+                .unwrap();
+            settle(&mut app).await;
+            // The open channel stays, flagged, until another is opened.
+            assert_eq!(listed(&app), ["C_GENERAL", "C_NEW", "C_OPEN"]);
+            assert_eq!(app.removed_channel.as_deref(), Some("C_OPEN"));
+            assert_eq!(app.channels_refreshing.len(), 0);
+            let general = app.channels.iter().find(|ch| ch.id == CHANNEL).unwrap();
+            assert_eq!(general.unread_count, 3);
+            assert_eq!(
+                app.workspaces[0].channels.len(),
+                app.channels.len(),
+                "the workspace list is merged too"
+            );
+
+            app.select_channel("C_NEW");
+            assert_eq!(listed(&app), ["C_GENERAL", "C_NEW"]);
+            assert!(app.removed_channel.is_none());
+            assert!(app.sidebar_cursor < app.channels.len());
+
+            // In the background once the interval has passed.
+            let pages = |client: &MockSlackClient| {
+                client
+                    .calls()
+                    .iter()
+                    .filter(|c| c.starts_with("list_channels_page"))
+                    .count()
+            };
+            let before = pages(&client);
+            app.config.ui.channel_refresh_minutes = 1;
+            let now = Instant::now();
+            app.refresh_channels_periodically(now);
+            settle(&mut app).await;
+            assert_eq!(pages(&client), before);
+            app.refresh_channels_periodically(now + Duration::from_secs(61));
+            settle(&mut app).await;
+            assert_eq!(pages(&client), before + 1);
+        }
+
+        #[tokio::test]
+        async fn thread_summaries_name_participants_and_follow_live_replies() {
+            let now = chrono::Utc::now();
+            let ts = |ago: i64| format!("{}.000100", now.timestamp() - ago);
+            let mut parent = message(&ts(3 * 3_600), "U_ALICE", "release?");
+            parent.reply_count = Some(14);
+            parent.reply_users = ["U_BOB", "U_CAROL", "U_DAN", "U_ERIN", "U_FRED"]
+                .map(String::from)
+                .to_vec();
+            parent.latest_reply = Some(ts(2 * 3_600));
+            let mut bare = message(&ts(60), "U_CAROL", "review?");
+            bare.reply_count = Some(1);
+            let client = Arc::new(
+                MockSlackClient::default()
+                    .with_history(CHANNEL, vec![parent, bare, message(&ts(30), "U_BOB", "hi")]),
+            );
+            let mut app = app_with(client);
+            app.select_channel(CHANNEL);
+            settle(&mut app).await;
+
+            let summary = |app: &crate::app::App, ago| {
+                let messages = app.messages.get(CHANNEL).unwrap();
+                let parent = messages.iter().find(|m| m.ts == ts(ago)).unwrap();
+                app.thread_summary(parent, now)
+            };
+            assert_eq!(
+                summary(&app, 3 * 3_600).as_deref(),
+                Some("14 replies · u_bob, u_carol +3 · 2h ago")
+            );
+            assert_eq!(summary(&app, 60).as_deref(), Some("1 reply"));
+            assert_eq!(summary(&app, 30), None);
+
+            let mut reply = message(&ts(0), "U_GINA", "on it");
+            reply.thread_ts = Some(ts(60));
+            let tx = app.event_tx.clone().unwrap();
+            for _ in 0..2 {
+                tx.send(SlackEvent::Message {
+                    channel: CHANNEL.to_string(),
+                    message: Box::new(reply.clone()),
+                })
+                .unwrap();
+            }
+            app.process_slack_events();
+            assert_eq!(
+                summary(&app, 60).as_deref(),
+                Some("2 replies · u_gina · just now")
+            );
+        }
+
+        #[tokio::test]
+        async fn own_reactions_are_marked_and_stale_toggles_are_not_errors() {
+            use ratatui::backend::TestBackend;
+            use ratatui::Terminal;
+
+            let mut shipped = message("1000.1", "U_BOB", "shipped");
+            for (name, users) in [("eyes", vec!["U_BOB"]), ("tada", vec!["U_BOB", ME])] {
+                shipped.reactions.push(slack_zc_slack::types::Reaction {
+                    name: name.to_string(),
+                    count: users.len() as u32,
+                    users: users.into_iter().map(String::from).collect(),
+                });
+            }
+            let client = Arc::new(MockSlackClient::default().with_history(CHANNEL, vec![shipped]));
+            let mut app = app_with(client.clone());
+            app.select_channel(CHANNEL);
+            settle(&mut app).await;
+            app.focus = Focus::Messages;
+            app.is_loading = false;
+
+            let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+            terminal.draw(|frame| app.render(frame)).unwrap();
+            let buffer = terminal.backend().buffer();
+            let screen: String = (0..40)
+                .map(|y| {
+                    (0..120)
+                        .map(|x| buffer[(x, y)].symbol())
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            assert!(screen.contains("[eyes:1 ⟨tada:2⟩]"), "{screen}");
+
+            // Someone else's reaction is added, not removed, even when Slack
+            // already has ours from elsewhere.
+            client.fail_with("add_reaction", "already_reacted");
+            app.toggle_reaction("eyes");
+            settle(&mut app).await;
+            app.toggle_reaction("tada");
+            settle(&mut app).await;
+            let calls = client.calls();
+            assert!(calls.contains(&format!("add_reaction {CHANNEL} 1000.1 eyes")));
+            assert!(calls.contains(&format!("remove_reaction {CHANNEL} 1000.1 tada")));
+            assert!(app.last_error.is_none(), "{:?}", app.last_error);
+
+            client.fail_with("remove_reaction", "not_in_channel");
+            app.toggle_reaction("eyes");
+            settle(&mut app).await;
+            assert!(app.last_error.is_some());
+        }
+
+        #[tokio::test]
+        async fn background_arrivals_show_in_the_title_and_notify_mentions() {
+            let client = Arc::new(MockSlackClient::default());
+            let mut app = app_with(client);
+            app.select_channel(CHANNEL);
+            settle(&mut app).await;
+            app.terminal_notifications = true;
+            assert_eq!(app.window_title(), "slack-zc — #general (test)");
+
+            let tx = app.event_tx.clone().unwrap();
+            let send = |ts: &str, user: &str, text: &str| {
+                tx.send(SlackEvent::Message {
+                    channel: CHANNEL.to_string(),
+                    message: Box::new(message(ts, user, text)),
+                })
+                .unwrap();
+            };
+            // Nothing is counted while the terminal has focus.
+            send("2000.1", "U_BOB", &format!("hi <@{ME}>"));
+            app.process_slack_events();
+            assert!(app.take_notifications().is_empty());
+
+            app.handle_event(Event::FocusLost).unwrap();
+            send("2000.2", "U_BOB", "standup?");
+            send("2000.3", ME, "my own post");
+            send("2000.4", "U_BOB", &format!("<@{ME}> ping\nsecond line"));
+            app.process_slack_events();
+            assert_eq!(
+                app.window_title(),
+                "slack-zc — #general (test) · 2 new, 1 @"
+            );
+            assert_eq!(
+                app.take_notifications(),
+                [("#general".to_string(), "u_bob: @you ping".to_string())]
+            );
+
+            app.handle_event(Event::FocusGained).unwrap();
+            assert_eq!(app.window_title(), "slack-zc — #general (test)");
+        }
+
+        #[tokio::test]
+        async fn filtered_sidebar_opens_the_channel_it_shows() {
+            let client = Arc::new(MockSlackClient::default());
+            let mut app = app_with(client.clone());
+            app.channels.push(channel("C_RANDOM", "random"));
+            app.channels.push(channel("C_DEV", "dev"));
+            app.focus = Focus::Sidebar;
+            app.search_query = "dev".to_string();
+            let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+
+            // Only #dev is listed, so its row sits right under the header.
+            assert_eq!(app.sidebar_rows()[1], Some(2));
+            app.handle_event(key(KeyCode::Down)).unwrap();
+            app.handle_event(key(KeyCode::Enter)).unwrap();
+            assert_eq!(app.selected_channel_id.as_deref(), Some("C_DEV"));
+
+            // Reordering the list keeps the same channel open.
+            let mut dm = channel("D_ALICE", "alice");
+            dm.is_dm = true;
+            app.channels.insert(0, dm);
+            app.sort_channels_by_section();
+            assert_eq!(
+                app.selected_channel().map(|ch| ch.name.as_str()),
+                Some("dev")
+            );
+            settle(&mut app).await;
+            assert!(client.calls().contains(&"get_history C_DEV".to_string()));
+        }
+    }
+
+    #[test]
+    fn consecutive_messages_from_one_author_share_a_header() {
+        use ratatui::backend::TestBackend;
+        use ratatui::Terminal;
+
+        let mut app = App::new(Config::default());
+        app.is_loading = false;
+        app.channels
+            .push(super::test_support::channel("C_ONE", "general"));
+        app.selected_channel_id = Some("C_ONE".to_string());
+        let at = |ts: &str, user: &str, text: &str| {
+            let mut m = sample_message(None);
+            m.ts = ts.to_string();
+            m.user_id = user.to_string();
+            m.username = user.to_lowercase();
+            m.text = text.to_string();
+            m.timestamp = chrono::DateTime::from_timestamp(ts[..10].parse().unwrap(), 0).unwrap();
+            m
+        };
+        let first = at("1730000000.000100", "U_ANN", "deploying now");
+        let second = at("1730000060.000100", "U_ANN", "step two");
+        // Past the five-minute window.
+        let later = at("1730000700.000100", "U_ANN", "done");
+        let mut parent = at("1730000720.000100", "U_ANN", "questions go here");
+        parent.reply_count = Some(2);
+        let messages = vec![first, second, later, parent];
+        app.messages
+            .insert("C_ONE".to_string(), messages.clone().into());
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).expect("terminal");
+        terminal.draw(|frame| app.render(frame)).expect("draw");
+        let area = app.message_rows.area;
+        let hits: Vec<Option<String>> = (area.y..area.y + area.height)
+            .map(|row| app.hit_test_message(area.x + 2, row).map(|(_, ts)| ts))
+            .collect();
+        let ts = |i: usize| Some(messages[i].ts.clone());
+        // The second message follows the first with no spacer; the late one
+        // and the thread parent start groups of their own.
+        assert_eq!(hits[..3], [ts(0), ts(1), None]);
+        assert_eq!(hits[3..5], [ts(2), None]);
+        assert_eq!(hits[5], ts(3));
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert_eq!(screen.matches("u_ann:").count(), 3);
+
+        // 0 turns grouping off.
+        app.config.ui.message_group_minutes = 0;
+        assert!(!app.continues_group("C_ONE", &messages[0], &messages[1]));
+    }
+
+    #[test]
+    fn links_are_underlined_placed_and_opened_from_the_message() {
+        use ratatui::backend::TestBackend;
+        use ratatui::style::Modifier;
+        use ratatui::Terminal;
+
+        let mut app = App::new(Config::default());
+        app.is_loading = false;
+        app.hyperlinks = true;
+        app.channels
+            .push(super::test_support::channel("C_ONE", "general"));
+        app.selected_channel_id = Some("C_ONE".to_string());
+        let label = "a very long pull request title ".repeat(4);
+        let mut linked = sample_message(None);
+        linked.text = format!(
+            "PR <https://github.com/acme/api/pull/42|{}> and https://example.com/docs.",
+            label.trim()
+        );
+        app.messages
+            .insert("C_ONE".to_string(), vec![linked].into());
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).expect("terminal");
+        terminal.draw(|frame| app.render(frame)).expect("draw");
+        let buffer = terminal.backend().buffer().clone();
+        let drawn = |link: &ScreenLink| -> String {
+            (0..link.text.chars().count() as u16)
+                .map(|i| buffer[(link.x + i, link.y)].symbol().to_string())
+                .collect()
+        };
+        let pr: Vec<&ScreenLink> = app
+            .screen_links
+            .iter()
+            .filter(|link| link.url == "https://github.com/acme/api/pull/42")
+            .collect();
+        // The wrapped label is one link per line, each where it is drawn.
+        assert!(pr.len() > 1, "{:?}", app.screen_links);
+        for link in app.screen_links.iter() {
+            assert_eq!(drawn(link), link.text);
+            assert!(buffer[(link.x, link.y)]
+                .modifier
+                .contains(Modifier::UNDERLINED));
+        }
+        let docs = app.screen_links.last().unwrap();
+        assert_eq!(
+            (docs.text.as_str(), docs.url.as_str()),
+            ("https://example.com/docs", "https://example.com/docs")
+        );
+
+        // Several links: `o` asks which one.
+        app.focus = Focus::Messages;
+        let key = |c| Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        app.handle_event(key('o')).unwrap();
+        let chooser = app.link_chooser.as_ref().expect("chooser");
+        assert_eq!(chooser.links.len(), 2);
+        assert_eq!(chooser.links[0].label, label.trim());
+        app.handle_event(key('j')).unwrap();
+        assert_eq!(app.link_chooser.as_ref().unwrap().selected, 1);
+        app.handle_event(Event::Key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)))
+            .unwrap();
+        assert!(app.link_chooser.is_none());
+
+        app.messages.get_mut("C_ONE").unwrap()[0].text = "no links here".to_string();
+        app.handle_event(key('o')).unwrap();
+        assert!(app.link_chooser.is_none());
+        assert_eq!(
+            app.notice.as_ref().map(|(text, _)| text.as_str()),
+            Some("No links in this message")
+        );
+    }
+
+    #[test]
+    fn mouse_hits_follow_wrapped_message_rows() {
+        use ratatui::backend::TestBackend;
+        use ratatui::Terminal;
+
+        let mut app = App::new(Config::default());
+        app.is_loading = false;
+        app.channels.push(Channel {
+            id: "C_ONE".to_string(),
+            name: "general".to_string(),
+            is_dm: false,
+            is_group: false,
+            is_im: false,
+            is_mpim: false,
+            unread_count: 0,
+            mention_count: 0,
+            purpose: None,
+            topic: None,
+            user: None,
+            member_count: None,
+            created: None,
+            creator: None,
+            is_shared: false,
+            is_ext_shared: false,
+            is_org_shared: false,
+            is_archived: false,
+            last_read: None,
+            latest_ts: None,
+            is_member: None,
+            posting_restricted_to: None,
+            threads_restricted_to: None,
+        });
+        app.selected_channel_id = Some("C_ONE".to_string());
+        let short = sample_message(None);
+        let mut long = sample_message(None);
+        long.ts = "1730000001.000000".to_string();
+        long.text = "word ".repeat(60);
+        // Another author, so the two get a header each.
+        long.user_id = "U456".to_string();
+        app.messages.insert(
+            "C_ONE".to_string(),
+            vec![short.clone(), long.clone()].into(),
+        );
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 40)).expect("terminal");
+        terminal.draw(|frame| app.render(frame)).expect("draw");
+
+        let area = app.message_rows.area;
+        let col = area.x + 2;
+        let hits: Vec<Option<String>> = (area.y.saturating_sub(2)..area.y + area.height)
+            .map(|row| app.hit_test_message(col, row).map(|(_, ts)| ts))
+            .collect();
+
+        // Top border and header line hit nothing.
+        assert_eq!(hits[0], None);
+        assert_eq!(hits[1], None);
+        assert_eq!(hits[2].as_deref(), Some(short.ts.as_str()));
+        // Spacer between the two messages.
+        assert_eq!(hits[3], None);
+        let long_rows = hits
+            .iter()
+            .filter(|hit| hit.as_deref() == Some(long.ts.as_str()))
+            .count();
+        assert!(long_rows > 1, "wrapped message should span rows");
+
+        app.handle_mouse_event(ratatui::crossterm::event::MouseEvent {
+            kind: ratatui::crossterm::event::MouseEventKind::Down(
+                ratatui::crossterm::event::MouseButton::Right,
+            ),
+            column: col,
+            row: area.y,
+            modifiers: ratatui::crossterm::event::KeyModifiers::NONE,
+        })
+        .expect("right click");
+        assert_eq!(
+            app.selected_message,
+            Some(("C_ONE".to_string(), short.ts.clone()))
+        );
+        assert!(app.context_menu.is_some());
+    }
+
+    #[test]
+    fn control_sequences_never_reach_the_terminal() {
+        use ratatui::backend::TestBackend;
+        use ratatui::Terminal;
+
+        let mut app = App::new(Config::default());
+        app.is_loading = false;
+        app.channels.push(Channel {
+            id: "C_ONE".to_string(),
+            name: "general".to_string(),
+            is_dm: false,
+            is_group: false,
+            is_im: false,
+            is_mpim: false,
+            unread_count: 0,
+            mention_count: 0,
+            purpose: None,
+            topic: None,
+            user: None,
+            member_count: None,
+            created: None,
+            creator: None,
+            is_shared: false,
+            is_ext_shared: false,
+            is_org_shared: false,
+            is_archived: false,
+            last_read: None,
+            latest_ts: None,
+            is_member: None,
+            posting_restricted_to: None,
+            threads_restricted_to: None,
+        });
+        app.selected_channel_id = Some("C_ONE".to_string());
+        let mut pasted = sample_message(None);
+        pasted.username = "mallory\x1b[2J".to_string();
+        pasted.text = "\x1b[31mFAILED\x1b[0m build\x07 done\rover".to_string();
+        app.messages
+            .insert("C_ONE".to_string(), vec![pasted].into());
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).expect("terminal");
+        terminal.draw(|frame| app.render(frame)).expect("draw");
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(!screen.contains('\x1b'));
+        assert!(!screen.contains('\x07'));
+        assert!(!screen.contains('\r'));
+        assert!(screen.contains("FAILED build␇ done over"));
+    }
 }
@@ -1,8 +1,25 @@
 use super::*;
+use crate::ui::links::{linkify, LinkedText};
+use crate::ui::sanitize::{sanitize_text, truncate_display};
+
+/// Narrowest sidebar, inside its borders, that shows channel ages.
+const SIDEBAR_ACTIVITY_MIN_WIDTH: usize = 16;
+
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Where a link landed in the lines of one message.
+struct LinkPlacement {
+    line: usize,
+    /// Display column within the line, prefix included.
+    col: usize,
+    text: String,
+    url: String,
+}
 
 impl App {
     pub fn render(&mut self, frame: &mut Frame) {
         let area = frame.area();
+        self.screen_links.clear();
 
         if self.is_loading {
             self.render_loading(frame, area);
@@ -30,12 +47,19 @@ impl App {
         }
 
         self.layout.calculate_layout(area);
+        if self.layout.is_too_small() {
+            self.render_too_small(frame, area);
+            return;
+        }
 
         let panels = self.layout.get_panels().to_vec();
 
         for panel in panels {
             match panel.panel_type {
                 PanelType::Topbar => self.render_topbar(frame, panel.rect),
+                PanelType::Sidebar if self.layout.is_sidebar_collapsed() => {
+                    self.render_sidebar_icons(frame, panel.rect)
+                }
                 PanelType::Sidebar => self.render_sidebar(frame, panel.rect),
                 PanelType::Messages => self.render_messages(frame, panel.rect),
                 PanelType::AgentPanel => self.render_agent_panel(frame, panel.rect),
@@ -43,6 +67,13 @@ impl App {
             }
         }
 
+        // The confirmation normally lives in the agent panel; float it when hidden.
+        if let Some(ref dialog) = self.confirmation_dialog {
+            if self.layout.is_agent_hidden() {
+                self.render_confirmation_dialog(frame, self.centered_rect(60, 40, area), dialog);
+            }
+        }
+
         if let Some(ref context_menu) = self.context_menu {
             self.render_context_menu(frame, area, context_menu);
         }
@@ -55,17 +86,172 @@ impl App {
             self.render_jump_to_time(frame, area);
         }
 
+        if self.show_channel_info {
+            self.render_channel_info(frame, area);
+        }
+
+        if let Some(ref inspector) = self.raw_inspector {
+            self.render_raw_inspector(frame, area, inspector);
+        }
+
+        if self.confirm_mark_all_read {
+            self.render_mark_all_read_confirm(frame, area);
+        }
+
+        if let Some(ref command) = self.agent_offline_prompt {
+            self.render_agent_offline_prompt(frame, area, command);
+        }
+
+        if let Some(ref prompt) = self.send_recovery {
+            self.render_send_recovery(frame, area, prompt);
+        }
+
+        if let Some(ref send) = self.long_message {
+            self.render_long_message(frame, area, send);
+        }
+
+        if let Some(ref palette) = self.command_palette {
+            self.render_command_palette(frame, area, palette);
+        }
+
+        if let Some(ref picker) = self.user_filter_picker {
+            self.render_user_filter_picker(frame, area, picker);
+        }
+
+        if let Some(ref picker) = self.reaction_picker {
+            self.render_reaction_picker(frame, area, picker);
+        }
+
+        if let Some(count) = self.rerun_count {
+            self.render_rerun_menu(frame, area, count);
+        }
+
+        if let Some(ref popup) = self.agent_history {
+            self.render_agent_history(frame, area, popup);
+        }
+
+        if let Some(ref popup) = self.reminders {
+            self.render_reminders(frame, area, popup);
+        }
+
+        if let Some(ref chooser) = self.link_chooser {
+            self.render_link_chooser(frame, area, chooser);
+        }
+
+        if let Some(ref view) = self.saved_view {
+            self.render_saved(frame, area, view);
+        }
+
+        if let Some(ref view) = self.threads_view {
+            self.render_threads(frame, area, view);
+        }
+
+        if let Some(ref view) = self.workspace_issues_view {
+            self.render_workspace_issues(frame, area, view);
+        }
+
+        if let Some(ref prompt) = self.pairing_prompt {
+            self.render_pairing_prompt(frame, area, prompt);
+        }
+
+        if let Some(ref lines) = self.agent_logs {
+            self.render_agent_logs(frame, area, lines);
+        }
+
         if self.show_error_details {
             self.render_error_details(frame, area);
         }
+
+        if self.show_memory_stats {
+            self.render_memory_stats(frame, area);
+        }
+
+        if let Some(ref panel) = self.connection_status {
+            self.render_connection_status(frame, area, panel);
+        }
+
+        self.render_error_toasts(frame, area);
+
+        if self.copy_mode {
+            self.render_copy_mode_banner(frame, area);
+        }
+    }
+
+    fn render_copy_mode_banner(&self, frame: &mut Frame, area: Rect) {
+        use ratatui::style::{Color, Modifier, Style};
+        use ratatui::widgets::Paragraph;
+
+        frame.render_widget(
+            Paragraph::new("copy mode — mouse selection enabled, press any key to resume").style(
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Rect { height: 1, ..area },
+        );
     }
 
+    /// The startup checklist: one row per step, grouped by workspace, with
+    /// a failed step's error under it.
     fn render_loading(&self, frame: &mut Frame, area: Rect) {
-        use ratatui::widgets::{Block, Borders, Paragraph};
-        let text = format!("\n\n  {}  \n\n", self.loading_message);
-        let paragraph = Paragraph::new(text)
-            .block(Block::default().borders(Borders::ALL).title(" slack-zc "))
-            .centered();
+        use ratatui::text::{Line, Span};
+        use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+
+        let spinner = SPINNER[(Utc::now().timestamp_millis() / 100) as usize % SPINNER.len()];
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(format!("  {}", self.loading_message)),
+            Line::from(""),
+        ];
+        let mut team_shown: Option<&str> = None;
+        for (stage, status) in &self.init_stages {
+            let indent = match stage.team_id() {
+                Some(team_id) => {
+                    if team_shown != Some(team_id) {
+                        let name = self
+                            .workspaces
+                            .iter()
+                            .find(|ws| ws.workspace.team_id == team_id)
+                            .map_or(team_id, |ws| ws.workspace.team_name.as_str());
+                        lines.push(Line::styled(format!("  {}", name), self.theme.section));
+                        team_shown = Some(team_id);
+                    }
+                    "    "
+                }
+                None => {
+                    team_shown = None;
+                    "  "
+                }
+            };
+            let (glyph, style) = match status {
+                StageStatus::Pending => ("·", self.theme.dim),
+                StageStatus::Running => (spinner, self.theme.delivery_pending),
+                StageStatus::Done => ("✓", self.theme.delivery_sent),
+                StageStatus::Failed(_) => ("✗", self.theme.error),
+            };
+            lines.push(Line::from(vec![
+                Span::raw(indent),
+                Span::styled(glyph, style),
+                Span::raw(format!(" {}", stage.label())),
+            ]));
+            if let StageStatus::Failed(error) = status {
+                lines.push(Line::styled(
+                    format!("{}  {}", indent, error),
+                    self.theme.error,
+                ));
+            }
+        }
+        if self.session_read() {
+            lines.push(Line::from(""));
+            lines.push(Line::styled(
+                "  Press any key to continue without waiting",
+                self.theme.dim,
+            ));
+        }
+        let paragraph = Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(Block::default().borders(Borders::ALL).title(" slack-zc "));
         frame.render_widget(paragraph, area);
     }
 
@@ -97,7 +283,7 @@ impl App {
                 if state.oauth_code.is_empty() {
                     if let Some(ref url) = state.oauth_url {
                         format!(
-                            "\n\n  OAuth authentication:\n\n  1. Visit: {}\n\n  2. Authorize the app\n\n  3. Copy the code from URL and enter below:\n\n  Code: [enter code here]\n\n  Press [Enter] to exchange code for tokens,\n  [c] to copy URL to clipboard,\n  [Esc] to go back\n",
+                            "\n\n  OAuth authentication:\n\n  1. Visit (opened in your browser if one is available): {}\n\n  2. Authorize the app\n\n  3. Copy the code from URL and enter below:\n\n  Code: [enter code here]\n\n  Press [Enter] to exchange code for tokens,\n  [c] to copy URL to clipboard,\n  [Esc] to go back\n",
                             url
                         )
                     } else {
@@ -174,7 +360,7 @@ impl App {
             .iter()
             .enumerate()
             .map(|(i, ws)| {
-                let prefix = if i == self.active_workspace {
+                let prefix = if i == self.workspace_picker_cursor {
                     "> "
                 } else {
                     "  "
@@ -205,49 +391,135 @@ impl App {
         );
     }
 
+    /// Unread and mention totals across a workspace's channels; muted
+    /// channels add only their mentions.
+    pub(super) fn workspace_unread(&self, idx: usize) -> (u32, u32) {
+        self.workspaces.get(idx).map_or((0, 0), |ws| {
+            ws.channels.iter().fold((0, 0), |(unread, mentions), ch| {
+                let count = if ws.workspace.is_muted(&ch.id) {
+                    0
+                } else {
+                    ch.unread_count
+                };
+                (unread + count, mentions + ch.mention_count)
+            })
+        })
+    }
+
+    /// Topbar tab text; inactive tabs carry an unread badge and a `●` for mentions.
+    pub(super) fn workspace_tab_label(&self, idx: usize) -> String {
+        let Some(ws) = self.workspaces.get(idx) else {
+            return String::new();
+        };
+        if Some(idx) == self.active_workspace_index() {
+            return format!("[{}]", ws.workspace.team_name);
+        }
+        let (unread, mentions) = self.workspace_unread(idx);
+        let mut label = format!(" {}", Self::workspace_initials(&ws.workspace.team_name));
+        if unread > 0 {
+            label.push_str(&format!(" ({})", unread));
+        }
+        if mentions > 0 {
+            label.push('●');
+        }
+        label.push(' ');
+        label
+    }
+
+    /// Two letters for a workspace tab: the first letters of the first two
+    /// words ("Acme Eng" → "AE"), else the first two letters ("globex" → "GL").
+    pub(super) fn workspace_initials(name: &str) -> String {
+        let words: Vec<&str> = name
+            .split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+            .filter(|word| !word.is_empty())
+            .collect();
+        let initials: String = match words.as_slice() {
+            [first, second, ..] => first
+                .chars()
+                .take(1)
+                .chain(second.chars().take(1))
+                .collect(),
+            [only] => only.chars().take(2).collect(),
+            [] => "?".to_string(),
+        };
+        initials.to_uppercase()
+    }
+
     fn render_topbar(&self, frame: &mut Frame, area: Rect) {
+        use ratatui::layout::{Alignment, Constraint, Direction, Layout};
+        use ratatui::style::{Color, Modifier, Style};
+        use ratatui::text::{Line, Span};
         use ratatui::widgets::{Block, Paragraph};
 
-        let workspace_tabs: Vec<String> = self
-            .workspaces
-            .iter()
-            .enumerate()
-            .map(|(i, ws)| {
-                if i == self.active_workspace {
-                    format!("[{}]", ws.workspace.team_name)
-                } else {
-                    format!(" {} ", ws.workspace.team_name)
+        let mut spans = vec![Span::raw(" ● ")];
+        if !slack_zc_slack::profile::is_default() {
+            spans.push(Span::styled(
+                format!("[{}] ", slack_zc_slack::profile::current()),
+                Style::default().fg(Color::Magenta),
+            ));
+        }
+        if slack_zc_slack::lock::is_read_only() {
+            spans.push(Span::styled(
+                "[read-only] ",
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        if self.presence_away {
+            spans.push(Span::styled("[away] ", Style::default().fg(Color::Yellow)));
+        }
+        if !self.workspace_issues.is_empty() {
+            spans.push(Span::styled(
+                format!("[{} workspace issue(s): F10] ", self.workspace_issues.len()),
+                self.theme.error,
+            ));
+        }
+        for idx in 0..self.workspaces.len() {
+            if idx > 0 {
+                spans.push(Span::raw(" "));
+            }
+            let label = self.workspace_tab_label(idx);
+            match label.strip_suffix("● ") {
+                Some(name) => {
+                    spans.push(Span::raw(name.to_string()));
+                    spans.push(Span::styled("●", self.theme.mention));
+                    spans.push(Span::raw(" "));
                 }
-            })
-            .collect();
+                None => spans.push(Span::raw(label)),
+            }
+        }
+        if let Some(channel) = self.selected_channel() {
+            let sigil = if channel.is_dm { "@" } else { "#" };
+            spans.push(Span::raw(" ▸ "));
+            spans.push(Span::styled(
+                format!("{}{}", sigil, sanitize_text(&channel.name)),
+                Style::default().add_modifier(Modifier::BOLD),
+            ));
+        }
 
-        let agent_indicator = match self.agent_status {
+        let mut agent_indicator = match self.agent_status {
             AgentStatus::Active => "zeroclaw: ● active",
             _ => "zeroclaw: ○ inactive",
-        };
+        }
+        .to_string();
+        if self.layout.is_agent_hidden() {
+            agent_indicator.push_str(&self.hidden_agent_activity());
+        }
 
-        let typing_indicator = if let Some(ref channel) = self.selected_channel {
-            if let Some(ch) = self.channels.get(*channel) {
-                if let Some(users) = self.typing_users.get(&ch.id) {
-                    if !users.is_empty() {
-                        let typing_names: Vec<String> = users.iter().take(3).cloned().collect();
-                        let typing_str = typing_names.join(", ");
-                        if users.len() > 3 {
-                            format!(" typing: {}...", typing_str)
-                        } else {
-                            format!(" typing: {}", typing_str)
-                        }
-                    } else {
-                        String::new()
-                    }
-                } else {
-                    String::new()
-                }
+        let typing_names = self
+            .selected_channel_id
+            .as_deref()
+            .map(|channel_id| self.typing_names(channel_id))
+            .unwrap_or_default();
+        let typing_indicator = if typing_names.is_empty() {
+            String::new()
+        } else {
+            let typing_str =
+                sanitize_text(&typing_names[..typing_names.len().min(3)].join(", ")).into_owned();
+            if typing_names.len() > 3 {
+                format!(" typing: {}...", typing_str)
             } else {
-                String::new()
+                format!(" typing: {}", typing_str)
             }
-        } else {
-            String::new()
         };
 
         let focus_indicator = match self.focus {
@@ -256,70 +528,259 @@ impl App {
             Focus::Input => "[input]",
         };
 
+        // Status items sit in their own right-aligned chunk so a long
+        // breadcrumb or typing list can't push them off screen.
+        let mut status = vec![Span::styled(typing_indicator, self.theme.dim)];
+        status.push(Span::raw(format!("   {}", agent_indicator)));
+        if self.last_error.is_some() {
+            status.push(Span::styled(
+                format!("   ⚠ {} [E]", self.error_history.len()),
+                self.theme.error,
+            ));
+        }
+        status.push(Span::raw(format!(
+            "   {}   [Tab] focus   [?] help ",
+            focus_indicator
+        )));
+        let status_width: usize = status.iter().map(|span| span.content.chars().count()).sum();
+
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Min(0),
+                Constraint::Length((status_width as u16).min(area.width)),
+            ])
+            .split(area);
+        frame.render_widget(
+            Paragraph::new(Line::from(spans)).block(Block::default()),
+            chunks[0],
+        );
+        frame.render_widget(
+            Paragraph::new(Line::from(status)).alignment(Alignment::Right),
+            chunks[1],
+        );
+    }
+    /// Topbar stand-in for the agent panel: a spinner while a command runs,
+    /// otherwise the first line of the latest response.
+    fn hidden_agent_activity(&self) -> String {
+        const PREVIEW_CHARS: usize = 40;
+
+        if let (Some(start), Some(cmd)) = (self.loading_start_time, &self.loading_command) {
+            let frame = (start.elapsed().as_millis() / 100) as usize % SPINNER.len();
+            let rerun = if self.loading_rerun { "↻ " } else { "" };
+            return format!(" {} {}{}", SPINNER[frame], rerun, cmd);
+        }
+        let Some(latest) = self.agent_responses.front() else {
+            return String::new();
+        };
+        let first_line = latest.response.lines().next().unwrap_or_default();
+        format!(" — {}", truncate_display(first_line, PREVIEW_CHARS))
+    }
+
+    fn render_too_small(&self, frame: &mut Frame, area: Rect) {
+        use crate::ui::layout::{MIN_TERMINAL_HEIGHT, MIN_TERMINAL_WIDTH};
+        use ratatui::layout::Alignment;
+        use ratatui::widgets::Paragraph;
+
         let text = format!(
-            " ● {}{}   {}{}   {}   [Tab] focus   [?] help",
-            workspace_tabs.join(" "),
-            typing_indicator,
-            agent_indicator,
-            if self.last_error.is_some() {
-                "   ⚠ error"
-            } else {
-                ""
-            },
-            focus_indicator,
+            "terminal too small (need {}×{}, have {}×{})",
+            MIN_TERMINAL_WIDTH, MIN_TERMINAL_HEIGHT, area.width, area.height
         );
+        let row = Rect {
+            y: area.y + area.height / 2,
+            height: area.height.min(1),
+            ..area
+        };
+        frame.render_widget(Paragraph::new(text).alignment(Alignment::Center), row);
+    }
 
-        frame.render_widget(Paragraph::new(text).block(Block::default()), area);
+    /// First channel shown in the collapsed sidebar, keeping the cursor visible.
+    pub(super) fn sidebar_icon_offset(&self, height: u16) -> usize {
+        (self.sidebar_cursor + 1).saturating_sub(height as usize)
     }
-    fn render_sidebar(&mut self, frame: &mut Frame, area: Rect) {
-        use ratatui::style::{Color, Modifier, Style};
+
+    /// Narrow-terminal sidebar: one glyph per channel, `•` when unread.
+    fn render_sidebar_icons(&self, frame: &mut Frame, area: Rect) {
+        use ratatui::style::Style;
         use ratatui::widgets::{Block, Borders, List, ListItem};
 
         let is_focused = self.focus == Focus::Sidebar;
+        let selected = self.selected_channel_index();
+        let items: Vec<ListItem> = self
+            .channels
+            .iter()
+            .enumerate()
+            .skip(self.sidebar_icon_offset(area.height))
+            .filter(|(_, channel)| self.show_archived || !channel.is_archived)
+            .map(|(idx, channel)| {
+                let glyph = if self.is_typing_in(&channel.id) {
+                    "✎"
+                } else if channel.unread_count > 0 && !self.is_muted(&channel.id) {
+                    "•"
+                } else if channel.is_any_shared() {
+                    "⇄"
+                } else if channel.is_dm {
+                    "@"
+                } else {
+                    "#"
+                };
+                let style = if idx == self.sidebar_cursor && is_focused {
+                    self.theme.cursor
+                } else if Some(idx) == selected {
+                    self.theme.selected
+                } else {
+                    Style::default()
+                };
+                ListItem::new(format!(" {}", glyph)).style(style)
+            })
+            .collect();
+
+        frame.render_widget(
+            List::new(items).block(Block::default().borders(Borders::RIGHT)),
+            area,
+        );
+    }
 
-        // Filter channels by search query
-        let filtered_channels: Vec<_> = if self.search_query.is_empty() {
-            self.channels.clone()
+    /// Unread count after a channel's name, `@` when it has mentions,
+    /// `✎` while someone types and `(muted)` after `/mute`, so none of it
+    /// rests on colour. Muted channels show mentions but no count.
+    fn sidebar_badge(&self, channel: &Channel) -> String {
+        let muted = self.is_muted(&channel.id);
+        let mut badge = if channel.unread_count > 0 && !muted {
+            format!(" {}", channel.unread_count)
         } else {
-            let query = self.search_query.to_lowercase();
-            self.channels
-                .iter()
-                .filter(|ch| {
-                    ch.name.to_lowercase().contains(&query)
-                        || (ch
-                            .user
-                            .as_ref()
-                            .is_some_and(|u| u.to_lowercase().contains(&query)))
-                })
-                .cloned()
-                .collect()
+            String::new()
         };
+        if channel.mention_count > 0 {
+            badge.push_str(" @");
+        }
+        if self.is_typing_in(&channel.id) {
+            badge.push_str(" ✎");
+        }
+        if muted {
+            badge.push_str(" (muted)");
+        }
+        badge
+    }
 
-        // Ensure sidebar_cursor stays in bounds
-        if self.sidebar_cursor >= filtered_channels.len() && !filtered_channels.is_empty() {
-            self.sidebar_cursor = filtered_channels.len() - 1;
+    /// `row` with the channel's last activity right-aligned in `width`
+    /// (`•` when unknown), cutting the name short to make room. Left out
+    /// when `ui.sidebar_activity` is off or the sidebar is too narrow.
+    fn sidebar_row(&self, row: String, channel: &Channel, width: usize) -> String {
+        use unicode_width::UnicodeWidthStr;
+
+        if !self.config.ui.sidebar_activity || width < SIDEBAR_ACTIVITY_MIN_WIDTH {
+            return row;
         }
+        let age = self
+            .channel_activity
+            .get(&channel.id)
+            .map_or_else(|| "•".to_string(), |ts| Self::activity_age(ts, Utc::now()));
+        let row = truncate_display(&row, width.saturating_sub(age.width() + 1));
+        let pad = width.saturating_sub(row.width() + age.width());
+        format!("{}{}{}", row, " ".repeat(pad), age)
+    }
 
-        // Separate for display but keep global indices
-        let regular_channels: Vec<(usize, &Channel)> = filtered_channels
-            .iter()
-            .enumerate()
-            .filter(|(_, ch)| !ch.is_dm)
-            .collect();
-        let dm_channels: Vec<(usize, &Channel)> = filtered_channels
-            .iter()
-            .enumerate()
-            .filter(|(_, ch)| ch.is_dm)
-            .collect();
+    /// The cursor, then the open channel, then mentions, muted channels
+    /// and unread messages; `base` for the rest.
+    fn sidebar_row_style(
+        &self,
+        channel: &Channel,
+        is_cursor: bool,
+        is_selected: bool,
+        base: ratatui::style::Style,
+    ) -> ratatui::style::Style {
+        if is_cursor {
+            self.theme.cursor
+        } else if is_selected {
+            self.theme.selected
+        } else if channel.mention_count > 0 {
+            self.theme.mention
+        } else if self.is_muted(&channel.id) {
+            base.patch(self.theme.dim)
+        } else if channel.unread_count > 0 {
+            base.patch(self.theme.unread)
+        } else {
+            base
+        }
+    }
+
+    fn render_sidebar(&mut self, frame: &mut Frame, area: Rect) {
+        use ratatui::style::{Modifier, Style};
+        use ratatui::widgets::{Block, Borders, List, ListItem};
+
+        let is_focused = self.focus == Focus::Sidebar;
+
+        // Filter by search query, keeping positions into `channels` so the
+        // cursor and selection mean the same channel whatever is hidden.
+        let visible = self.sidebar_channel_indices();
+        if !visible.is_empty() && !visible.contains(&self.sidebar_cursor) {
+            self.sidebar_cursor = visible[0];
+        }
+        let selected = self.selected_channel_index();
+        let cursor_on_channels = is_focused && self.sidebar_pinned.is_none();
+
+        let section = |section: u8| -> Vec<(usize, &Channel)> {
+            visible
+                .iter()
+                .map(|idx| (*idx, &self.channels[*idx]))
+                .filter(|(_, ch)| Self::sidebar_section(ch) == section)
+                .collect()
+        };
+        let regular_channels = section(0);
+        let dm_channels = section(1);
+        let group_channels = section(2);
+        let archived_channels = section(3);
 
         // Build display items with proper index mapping
         let mut items: Vec<ListItem> = vec![];
+        let row_width = area.width.saturating_sub(2) as usize;
         let search_indicator = if self.search_query.is_empty() {
             String::new()
         } else {
             format!(" [filter: {}]", self.search_query)
         };
 
+        // Saved-for-later and followed-thread entries above the sections
+        let on_pinned = |entry| is_focused && self.sidebar_pinned == Some(entry);
+        let saved_style = if on_pinned(PinnedEntry::Saved) {
+            self.theme.cursor
+        } else {
+            self.theme.pinned
+        };
+        let saved_prefix = if on_pinned(PinnedEntry::Saved) {
+            " > "
+        } else {
+            "   "
+        };
+        items.push(
+            ListItem::new(format!(
+                "{}⚑ Saved ({})",
+                saved_prefix,
+                self.saved_items.len()
+            ))
+            .style(saved_style),
+        );
+        let thread_replies: u32 = self
+            .active_workspace()
+            .map_or(0, |ws| ws.workspace.threads.iter().map(|t| t.unread).sum());
+        let threads_style = match (on_pinned(PinnedEntry::Threads), thread_replies > 0) {
+            (true, _) => self.theme.cursor,
+            (false, true) => self.theme.mention,
+            (false, false) => self.theme.pinned,
+        };
+        let threads_prefix = if on_pinned(PinnedEntry::Threads) {
+            " > "
+        } else {
+            "   "
+        };
+        let threads_label = if thread_replies > 0 {
+            format!("{}⤷ Threads ({} new)", threads_prefix, thread_replies)
+        } else {
+            format!("{}⤷ Threads", threads_prefix)
+        };
+        items.push(ListItem::new(threads_label).style(threads_style));
+
         // Channels Section Header
         items.push(
             ListItem::new(format!(
@@ -327,36 +788,30 @@ impl App {
                 regular_channels.len(),
                 search_indicator
             ))
-            .style(
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            ),
+            .style(self.theme.section),
         );
 
-        if let Some(ref error) = self.last_error {
+        let active_loading = self
+            .active_workspace()
+            .is_some_and(|ws| self.channels_loading.contains(&ws.workspace.team_id));
+        let loading_error = self
+            .last_error
+            .as_ref()
+            .filter(|_| self.channels.is_empty() && !active_loading);
+
+        if self.channels.is_empty() && active_loading {
+            items.push(ListItem::new("  (loading channels...)").style(self.theme.dim));
+        } else if let Some(error) = loading_error {
             // Show error in sidebar if loading failed
-            items.push(ListItem::new("  ⚠ Error loading").style(Style::default().fg(Color::Red)));
-            let error_short = if error.len() > 30 {
-                &error[..30]
-            } else {
-                error
-            };
-            items.push(
-                ListItem::new(format!("  {}", error_short)).style(Style::default().fg(Color::Red)),
-            );
-        } else if !self.workspaces.is_empty() && self.channels.is_empty() {
-            items.push(
-                ListItem::new("  (loading channels...)")
-                    .style(Style::default().fg(Color::DarkGray)),
-            );
+            items.push(ListItem::new("  ⚠ Error loading").style(self.theme.error));
+            let error_short = truncate_display(error, 30);
+            items.push(ListItem::new(format!("  {}", error_short)).style(self.theme.error));
         } else if regular_channels.is_empty() {
-            items
-                .push(ListItem::new("  (no channels)").style(Style::default().fg(Color::DarkGray)));
+            items.push(ListItem::new("  (no channels)").style(self.theme.dim));
         } else {
-            for (global_idx, channel) in regular_channels {
-                let is_selected = Some(global_idx) == self.selected_channel;
-                let is_cursor = global_idx == self.sidebar_cursor && is_focused;
+            for &(global_idx, channel) in regular_channels.iter() {
+                let is_selected = Some(global_idx) == selected;
+                let is_cursor = global_idx == self.sidebar_cursor && cursor_on_channels;
 
                 let prefix = if is_cursor && is_selected {
                     ">> "
@@ -368,52 +823,55 @@ impl App {
                     "   "
                 };
 
-                let name = format!("# {}", channel.name);
-                let unread = if channel.unread_count > 0 {
-                    format!(" {}", channel.unread_count)
+                let shared = channel.is_any_shared();
+                let name = if shared {
+                    format!("⇄ {}", channel.name)
                 } else {
-                    String::new()
+                    format!("# {}", channel.name)
                 };
+                let unread = self.sidebar_badge(channel);
 
-                let style = if is_cursor {
-                    Style::default()
-                        .fg(Color::Yellow)
-                        .add_modifier(Modifier::BOLD)
-                } else if is_selected {
-                    Style::default().fg(Color::Cyan)
+                let base = if shared {
+                    self.theme.shared
                 } else {
                     Style::default()
                 };
+                let style = self.sidebar_row_style(channel, is_cursor, is_selected, base);
 
-                items.push(ListItem::new(format!("{}{}{}", prefix, name, unread)).style(style));
+                let row = format!("{}{}{}", prefix, sanitize_text(&name), unread);
+                items.push(ListItem::new(self.sidebar_row(row, channel, row_width)).style(style));
             }
         }
 
-        // DMs Section Header
-        items.push(ListItem::new(""));
-        items.push(
-            ListItem::new(format!(
-                "─ DIRECT MESSAGES (@{}) {}",
-                dm_channels.len(),
-                search_indicator
-            ))
-            .style(
-                Style::default()
-                    .fg(Color::Magenta)
-                    .add_modifier(Modifier::BOLD),
-            ),
-        );
-
-        if !self.workspaces.is_empty() && self.channels.is_empty() && self.last_error.is_none() {
+        // DM sections: one-to-one, then group DMs
+        let loading_dms = self.channels.is_empty() && active_loading;
+        let dm_sections = [
+            ("DIRECT MESSAGES", "(no DMs)", &dm_channels),
+            ("GROUP DMS", "(no group DMs)", &group_channels),
+        ];
+        for (title, empty_label, section) in dm_sections {
+            items.push(ListItem::new(""));
             items.push(
-                ListItem::new("  (loading DMs...)").style(Style::default().fg(Color::DarkGray)),
+                ListItem::new(format!(
+                    "─ {} (@{}) {}",
+                    title,
+                    section.len(),
+                    search_indicator
+                ))
+                .style(self.theme.section),
             );
-        } else if dm_channels.is_empty() {
-            items.push(ListItem::new("  (no DMs)").style(Style::default().fg(Color::DarkGray)));
-        } else {
-            for (global_idx, channel) in dm_channels {
-                let is_selected = Some(global_idx) == self.selected_channel;
-                let is_cursor = global_idx == self.sidebar_cursor && is_focused;
+
+            if loading_dms {
+                items.push(ListItem::new("  (loading DMs...)").style(self.theme.dim));
+                continue;
+            }
+            if section.is_empty() {
+                items.push(ListItem::new(format!("  {}", empty_label)).style(self.theme.dim));
+                continue;
+            }
+            for &(global_idx, channel) in section.iter() {
+                let is_selected = Some(global_idx) == selected;
+                let is_cursor = global_idx == self.sidebar_cursor && cursor_on_channels;
 
                 let prefix = if is_cursor && is_selected {
                     ">> "
@@ -426,53 +884,89 @@ impl App {
                 };
 
                 let name = format!("@ {}", channel.name);
-                let unread = if channel.unread_count > 0 {
-                    format!(" {}", channel.unread_count)
+                let unread = self.sidebar_badge(channel);
+
+                let style =
+                    self.sidebar_row_style(channel, is_cursor, is_selected, Style::default());
+
+                let row = format!("{}{}{}", prefix, sanitize_text(&name), unread);
+                items.push(ListItem::new(self.sidebar_row(row, channel, row_width)).style(style));
+            }
+        }
+
+        // Archived channels, read-only, only while `a` has them shown
+        if self.show_archived {
+            items.push(ListItem::new(""));
+            items.push(
+                ListItem::new(format!(
+                    "─ ARCHIVED ({}) read-only {}",
+                    archived_channels.len(),
+                    search_indicator
+                ))
+                .style(self.theme.dim.add_modifier(Modifier::BOLD)),
+            );
+            if archived_channels.is_empty() {
+                let loading = self
+                    .active_workspace_id
+                    .as_ref()
+                    .is_some_and(|team_id| !self.archived_loaded.contains(team_id));
+                let placeholder = if loading {
+                    "  (loading archived...)"
                 } else {
-                    String::new()
+                    "  (no archived channels)"
+                };
+                items.push(ListItem::new(placeholder).style(self.theme.dim));
+            }
+            for &(global_idx, channel) in archived_channels.iter() {
+                let is_selected = Some(global_idx) == selected;
+                let is_cursor = global_idx == self.sidebar_cursor && cursor_on_channels;
+                let prefix = if is_cursor && is_selected {
+                    ">> "
+                } else if is_cursor {
+                    " > "
+                } else if is_selected {
+                    " # "
+                } else {
+                    "   "
                 };
-
                 let style = if is_cursor {
-                    Style::default()
-                        .fg(Color::Yellow)
-                        .add_modifier(Modifier::BOLD)
+                    self.theme.cursor
                 } else if is_selected {
-                    Style::default().fg(Color::Magenta)
+                    self.theme.selected
                 } else {
-                    Style::default()
+                    self.theme.dim
                 };
-
-                items.push(ListItem::new(format!("{}{}{}", prefix, name, unread)).style(style));
+                let name = sanitize_text(&channel.name);
+                items.push(ListItem::new(format!("{}# {}", prefix, name)).style(style));
             }
         }
 
         // Calculate scroll to keep cursor visible
         let visible_rows = area.height.saturating_sub(3) as usize;
-        let cursor_visual_pos = if filtered_channels.is_empty() {
+        let cursor_visual_pos = if visible.is_empty() || self.sidebar_pinned.is_some() {
             0
         } else {
-            // Find visual position of cursor in the rendered list
-            let mut pos = 1; // Start after first header
-            for (idx, ch) in filtered_channels.iter().enumerate() {
-                if idx == self.sidebar_cursor {
-                    break;
+            // The Saved and Threads entries, then a header per section;
+            // later sections also a blank line.
+            let mut pos = 3;
+            let sections = [
+                &regular_channels,
+                &dm_channels,
+                &group_channels,
+                &archived_channels,
+            ];
+            for (section_idx, section) in sections.into_iter().enumerate() {
+                if section_idx > 0 {
+                    pos += 2;
                 }
-                if !ch.is_dm {
-                    pos += 1;
-                }
-            }
-            // Add DM section offset if cursor is in DM section
-            if self.sidebar_cursor < filtered_channels.len() {
-                let cursor_ch = &filtered_channels[self.sidebar_cursor];
-                if cursor_ch.is_dm {
-                    pos += 2; // Empty line + DM header
-                              // Count regular channels before this DM
-                    for ch in filtered_channels.iter().take(self.sidebar_cursor) {
-                        if ch.is_dm {
-                            pos += 1;
-                        }
-                    }
+                if let Some(rank) = section
+                    .iter()
+                    .position(|(idx, _)| *idx == self.sidebar_cursor)
+                {
+                    pos += rank;
+                    break;
                 }
+                pos += section.len().max(1);
             }
             pos
         };
@@ -486,7 +980,7 @@ impl App {
         }
 
         let border_style = if is_focused {
-            Style::default().fg(Color::Yellow)
+            self.theme.focus_border
         } else {
             Style::default()
         };
@@ -495,40 +989,87 @@ impl App {
             List::new(items).block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title(format!(" {} total ", filtered_channels.len()))
+                    .title(format!(" {} total ", visible.len()))
                     .border_style(border_style),
             ),
             area,
         );
     }
 
-    fn render_messages(&self, frame: &mut Frame, area: Rect) {
+    fn render_messages(&mut self, frame: &mut Frame, area: Rect) {
         use ratatui::style::{Color, Modifier, Style};
         use ratatui::text::{Line, Span};
-        use ratatui::widgets::{Block, Borders, List, ListItem, ListState};
+        use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
 
         let is_messages_focused = self.focus == Focus::Messages;
         let content_width = area.width.saturating_sub(4) as usize;
+        let current_user = self.current_user_id();
+        let selected_ts: HashSet<String> = self
+            .visual_selection_messages()
+            .iter()
+            .map(|m| m.ts.clone())
+            .collect();
+        // Message ts behind each list item, and whether it ends in a spacer row.
+        let mut item_keys: Vec<(String, bool)> = Vec::new();
+        // Links drawn in each list item, by item index.
+        let mut item_links: HashMap<usize, Vec<LinkPlacement>> = HashMap::new();
 
-        let items: Vec<ListItem> = if let Some(ref channel) = self.selected_channel {
+        let items: Vec<ListItem> = if let Some(channel) = self.selected_channel_index() {
             self.channels
-                .get(*channel)
+                .get(channel)
                 .and_then(|ch| {
                     self.messages.get(&ch.id).map(|msgs| {
                         let mut list_items = Vec::new();
+                        let seen_ts = self.seen_message_ts(ch);
+                        let visible: Vec<&Message> = msgs
+                            .iter()
+                            .filter(|m| self.message_filter.matches(m))
+                            .collect();
+                        // Whether each message sits under the previous one's header.
+                        let grouped: Vec<bool> = visible
+                            .iter()
+                            .enumerate()
+                            .map(|(i, m)| i > 0 && self.continues_group(&ch.id, visible[i - 1], m))
+                            .collect();
+                        // Width of the current group's header prefix, which
+                        // the rest of the group is indented to.
+                        let mut group_indent: usize = 0;
+
+                        for (i, m) in visible.iter().copied().enumerate() {
+                            let ends_group = !grouped.get(i + 1).copied().unwrap_or(false);
+                            if m.is_deleted {
+                                list_items.push(ListItem::new(vec![Line::from(vec![
+                                    Span::raw(format!("{} ", m.timestamp.format("%H:%M"))),
+                                    Span::raw("[message deleted]"),
+                                ])]));
+                                item_keys.push((m.ts.clone(), false));
+                                continue;
+                            }
 
-                        for m in msgs.iter() {
-                            if let Some(ref user_id) = self.message_filter.user_id {
-                                if &m.user_id != user_id {
-                                    continue;
-                                }
+                            if m.is_system {
+                                let line = sanitize_text(&m.system_line()).into_owned();
+                                let pad = content_width.saturating_sub(line.chars().count()) / 2;
+                                list_items.push(ListItem::new(Line::from(Span::styled(
+                                    format!("{}{}", " ".repeat(pad), line),
+                                    self.theme.dim.add_modifier(Modifier::ITALIC),
+                                ))));
+                                item_keys.push((m.ts.clone(), false));
+                                continue;
                             }
 
-                            if m.is_deleted {
+                            if let Some(ref call) = m.call {
+                                let style = if call.ended {
+                                    self.theme.dim
+                                } else {
+                                    Style::default().fg(Color::Green)
+                                };
+                                let summary =
+                                    sanitize_text(&call.summary(&m.username)).into_owned();
                                 list_items.push(ListItem::new(vec![Line::from(vec![
                                     Span::raw(format!("{} ", m.timestamp.format("%H:%M"))),
-                                    Span::raw("[message deleted]"),
+                                    Span::styled(summary, style),
                                 ])]));
+                                item_keys.push((m.ts.clone(), false));
                                 continue;
                             }
 
@@ -541,51 +1082,153 @@ impl App {
                             };
 
                             let edited_indicator = if m.is_edited { " (edited)" } else { "" };
-                            let prefix = format!(
-                                "{}{} {}{}: ",
-                                thread_indicator,
-                                m.timestamp.format("%H:%M"),
-                                m.username,
-                                edited_indicator
-                            );
+                            let app_badge = if m.is_bot { " APP" } else { "" };
+                            let saved_indicator = if self.is_saved(&ch.id, &m.ts) {
+                                " ⚑"
+                            } else {
+                                ""
+                            };
+                            let delivery_mark = m.delivery.map(|state| match state {
+                                DeliveryState::Sending => {
+                                    let elapsed = (Utc::now() - m.timestamp).num_milliseconds();
+                                    let frame = (elapsed.max(0) / 100) as usize % SPINNER.len();
+                                    (SPINNER[frame], self.theme.delivery_pending)
+                                }
+                                DeliveryState::Sent => ("✓", self.theme.delivery_sent),
+                                DeliveryState::Failed => ("✗", self.theme.delivery_failed),
+                            });
+                            let mark = delivery_mark
+                                .map(|(glyph, _)| format!("{glyph} "))
+                                .unwrap_or_default();
+                            // Later messages in a group show only their
+                            // time, dimmed, in the header's gutter.
+                            let (prefix, prefix_style) = if grouped[i] {
+                                let gutter = format!(
+                                    "{}{}{}{} ",
+                                    mark,
+                                    m.timestamp.format("%H:%M"),
+                                    edited_indicator,
+                                    saved_indicator
+                                );
+                                let pad = group_indent.saturating_sub(gutter.chars().count());
+                                (format!("{}{}", gutter, " ".repeat(pad)), self.theme.dim)
+                            } else {
+                                let header = format!(
+                                    "{}{}{} {}{}{}{}: ",
+                                    mark,
+                                    thread_indicator,
+                                    m.timestamp.format("%H:%M"),
+                                    m.username,
+                                    app_badge,
+                                    edited_indicator,
+                                    saved_indicator
+                                );
+                                group_indent = header.chars().count();
+                                (header, self.theme.author)
+                            };
                             let continuation_prefix = " ".repeat(prefix.chars().count());
+                            let anchor = self
+                                .context_anchor
+                                .as_ref()
+                                .filter(|a| a.channel_id == ch.id && a.ts == m.ts);
+                            // The anchor's gutter marker takes a column.
+                            let content_width = if anchor.is_some() {
+                                content_width.saturating_sub(1)
+                            } else {
+                                content_width
+                            };
 
-                            let mut lines = Self::wrap_prefixed_lines(
+                            let (mut lines, mut placements) = Self::wrap_linked_lines(
                                 &prefix,
                                 &continuation_prefix,
-                                &m.text,
+                                &linkify(&sanitize_text(&m.text)),
                                 content_width,
-                                Style::default().fg(Color::Gray),
-                                Style::default(),
+                                (prefix_style, Style::default(), self.theme.link),
                             );
+                            // The delivery mark leads the prefix in its own colour.
+                            if let Some((glyph, style)) = delivery_mark {
+                                if let Some(first) = lines.first_mut() {
+                                    let rest = first.spans[0].content.strip_prefix(glyph);
+                                    if let Some(rest) = rest.map(str::to_string) {
+                                        first.spans[0] = Span::styled(rest, prefix_style);
+                                        first.spans.insert(0, Span::styled(glyph, style));
+                                    }
+                                }
+                            }
+                            if m.delivery == Some(DeliveryState::Failed) {
+                                lines.extend(Self::wrap_prefixed_lines(
+                                    &continuation_prefix,
+                                    &continuation_prefix,
+                                    "not sent — Enter to retry, d to discard",
+                                    content_width,
+                                    Style::default(),
+                                    self.theme.delivery_failed,
+                                ));
+                            }
+
+                            // Blocks and attachments sit under the author line behind a rule.
+                            let block_prefix = format!("{}│ ", continuation_prefix);
+                            for block in m
+                                .rendered_blocks
+                                .iter()
+                                .filter(|b| b.trim_matches('*') != m.text)
+                            {
+                                let (block_lines, block_links) = Self::wrap_linked_lines(
+                                    &block_prefix,
+                                    &block_prefix,
+                                    &linkify(&sanitize_text(block)),
+                                    content_width,
+                                    (self.theme.dim, Style::default(), self.theme.link),
+                                );
+                                placements.extend(block_links.into_iter().map(|p| LinkPlacement {
+                                    line: p.line + lines.len(),
+                                    ..p
+                                }));
+                                lines.extend(block_lines);
+                            }
 
                             if !m.reactions.is_empty() {
-                                let reactions_str: Vec<String> = m
-                                    .reactions
-                                    .iter()
-                                    .map(|r| format!("{}:{}", r.name, r.count))
-                                    .collect();
+                                lines.extend(self.reaction_lines(
+                                    &m.reactions,
+                                    current_user.as_deref(),
+                                    content_width,
+                                ));
+                            }
+
+                            if seen_ts.as_deref() == Some(m.ts.as_str()) {
+                                lines.push(Line::from(vec![
+                                    Span::raw(continuation_prefix.clone()),
+                                    Span::styled(
+                                        "seen",
+                                        self.theme.dim.add_modifier(Modifier::ITALIC),
+                                    ),
+                                ]));
+                            }
+
+                            if let Some(translation) = self.translations.get(&m.ts) {
                                 lines.extend(Self::wrap_prefixed_lines(
-                                    "",
-                                    "",
-                                    &format!("[{}]", reactions_str.join(" ")),
+                                    &continuation_prefix,
+                                    &continuation_prefix,
+                                    &format!("⟶ {}", translation),
                                     content_width,
-                                    Style::default().fg(Color::Cyan),
-                                    Style::default().fg(Color::Cyan),
+                                    Style::default(),
+                                    self.theme.dim.add_modifier(Modifier::ITALIC),
                                 ));
                             }
 
-                            if let Some(reply_count) = m.reply_count {
-                                if reply_count > 0 {
-                                    lines.extend(Self::wrap_prefixed_lines(
-                                        "",
-                                        "",
-                                        &format!("(+{} replies)", reply_count),
-                                        content_width,
-                                        Style::default().fg(Color::Magenta),
-                                        Style::default().fg(Color::Magenta),
-                                    ));
-                                }
+                            if let Some(summary) = self.thread_summary(m, chrono::Utc::now()) {
+                                let opened = self.threads.get(&ch.id).is_some_and(|threads| {
+                                    threads.iter().any(|t| t.parent_ts == m.ts)
+                                });
+                                let hint = if opened { "" } else { " — Enter to open" };
+                                lines.extend(Self::wrap_prefixed_lines(
+                                    "",
+                                    "",
+                                    &format!("  ↳ {}{}", summary, hint),
+                                    content_width,
+                                    self.theme.replies,
+                                    self.theme.replies,
+                                ));
                             }
 
                             if self.message_filter.show_threads {
@@ -609,8 +1252,8 @@ impl App {
                                                         &reply_continuation,
                                                         &reply.text,
                                                         content_width,
-                                                        Style::default().fg(Color::DarkGray),
-                                                        Style::default().fg(Color::DarkGray),
+                                                        self.theme.dim,
+                                                        self.theme.dim,
                                                     ));
                                                 }
                                             } else {
@@ -622,8 +1265,8 @@ impl App {
                                                         thread.replies.len()
                                                     ),
                                                     content_width,
-                                                    Style::default().fg(Color::DarkGray),
-                                                    Style::default().fg(Color::DarkGray),
+                                                    self.theme.dim,
+                                                    self.theme.dim,
                                                 ));
                                             }
                                         }
@@ -631,9 +1274,45 @@ impl App {
                                 }
                             }
 
-                            // We add a bit of vertical spacing between messages
-                            lines.push(Line::from(""));
-                            list_items.push(ListItem::new(lines));
+                            let mut item_style = Style::default();
+                            if let Some(anchor) = anchor {
+                                for line in &mut lines {
+                                    line.spans
+                                        .insert(0, Span::styled("▌", self.theme.context_marker));
+                                }
+                                for placement in &mut placements {
+                                    placement.col += 1;
+                                }
+                                if Instant::now() < anchor.highlight_until {
+                                    item_style = self.theme.context_highlight;
+                                }
+                            }
+                            if selected_ts.contains(&m.ts) {
+                                item_style = self.theme.selection;
+                            }
+
+                            // A blank line between groups; a group's
+                            // messages stack without one.
+                            if ends_group {
+                                lines.push(Line::from(""));
+                            }
+                            if self.history_gaps.get(&ch.id) == Some(&m.ts) {
+                                lines.push(Line::from(Span::styled(
+                                    "── reconnected · missed messages below ──",
+                                    self.theme.dim,
+                                )));
+                            }
+                            if self.newer_history.get(&ch.id) == Some(&m.ts) {
+                                lines.push(Line::from(Span::styled(
+                                    "── newer messages load as you scroll down ──",
+                                    self.theme.dim,
+                                )));
+                            }
+                            if !placements.is_empty() {
+                                item_links.insert(list_items.len(), placements);
+                            }
+                            list_items.push(ListItem::new(lines).style(item_style));
+                            item_keys.push((m.ts.clone(), ends_group));
                         }
 
                         list_items
@@ -647,14 +1326,20 @@ impl App {
         };
 
         let border_style = if is_messages_focused {
-            Style::default().fg(Color::Yellow)
+            self.theme.focus_border
         } else {
             Style::default()
         };
 
-        let mut list_state = ListState::default();
+        let channel_id = self.get_active_channel_id().unwrap_or_default();
+        let previous_offset = if self.message_rows.channel_id == channel_id {
+            self.message_rows.offset
+        } else {
+            0
+        };
+        let mut list_state = ListState::default().with_offset(previous_offset);
 
-        if items.len() > 0 {
+        if !items.is_empty() {
             // scroll_offset represents how many items from the bottom we are.
             // 0 means bottom-most message is selected.
             let selected_idx = items.len().saturating_sub(1 + self.scroll_offset);
@@ -662,28 +1347,583 @@ impl App {
         }
 
         let highlight_style = if is_messages_focused {
-            Style::default()
-                .add_modifier(Modifier::BOLD)
-                .bg(Color::Rgb(40, 40, 40))
+            self.theme.message_cursor
         } else {
-            Style::default().bg(Color::Rgb(30, 30, 30))
+            self.theme.message_cursor_unfocused
         };
 
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(border_style);
+        if self.visual_selection.is_some() {
+            block = block.title_bottom(Line::from(Span::styled(
+                format!(
+                    " VISUAL · {} selected · j/k extend · y copy · Esc cancel ",
+                    selected_ts.len()
+                ),
+                self.theme.section,
+            )));
+        }
+        if self.catching_up.contains(&channel_id) {
+            block = block.title(
+                Line::from(Span::styled(
+                    " syncing missed messages… ",
+                    self.theme.dim.add_modifier(Modifier::ITALIC),
+                ))
+                .right_aligned(),
+            );
+        }
+        let inner = block.inner(area);
+
+        let header_style = if self.selected_channel().is_some_and(Channel::is_any_shared) {
+            self.theme.shared
+        } else {
+            self.theme.dim
+        };
+        let list_area = match self.channel_header_line(inner.width as usize) {
+            Some(header) if inner.height > 1 => {
+                frame.render_widget(
+                    Paragraph::new(header).style(header_style),
+                    Rect { height: 1, ..inner },
+                );
+                Rect {
+                    y: inner.y + 1,
+                    height: inner.height - 1,
+                    ..inner
+                }
+            }
+            _ => inner,
+        };
+        let list_area = match self.removed_channel.as_deref() {
+            Some(gone) if gone == channel_id && list_area.height > 1 => {
+                frame.render_widget(
+                    Paragraph::new(
+                        "This channel is no longer in your list (archived, deleted or left) \
+                         — it goes once you open another",
+                    )
+                    .style(self.theme.error),
+                    Rect {
+                        height: 1,
+                        ..list_area
+                    },
+                );
+                Rect {
+                    y: list_area.y + 1,
+                    height: list_area.height - 1,
+                    ..list_area
+                }
+            }
+            _ => list_area,
+        };
+        let list_area = match self.external_banner.as_deref() {
+            Some(banner_channel) if banner_channel == channel_id && list_area.height > 1 => {
+                frame.render_widget(
+                    Paragraph::new(
+                        "⚠ External people can read this channel — check links before sharing",
+                    )
+                    .style(
+                        Style::default()
+                            .fg(Color::Black)
+                            .bg(Color::Yellow)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Rect {
+                        height: 1,
+                        ..list_area
+                    },
+                );
+                Rect {
+                    y: list_area.y + 1,
+                    height: list_area.height - 1,
+                    ..list_area
+                }
+            }
+            _ => list_area,
+        };
+        let list_area = match self.typing_status(&channel_id) {
+            Some(status) if list_area.height > 1 => {
+                let status_area = Rect {
+                    y: list_area.y + list_area.height - 1,
+                    height: 1,
+                    ..list_area
+                };
+                frame.render_widget(
+                    Paragraph::new(sanitize_text(&status).into_owned())
+                        .style(self.theme.dim.add_modifier(Modifier::ITALIC)),
+                    status_area,
+                );
+                Rect {
+                    height: list_area.height - 1,
+                    ..list_area
+                }
+            }
+            _ => list_area,
+        };
+
+        let heights: Vec<usize> = items.iter().map(ListItem::height).collect();
         let list = List::new(items)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .border_style(border_style)
-                    .title(" Messages "),
-            )
             .highlight_style(highlight_style)
             .highlight_symbol(if is_messages_focused { "▶ " } else { "  " });
 
-        frame.render_stateful_widget(list, area, &mut list_state);
+        frame.render_stateful_widget(list, list_area, &mut list_state);
+
+        let offset = list_state.offset();
+        let visible_rows = list_area.height as usize;
+        let mut rows = Vec::with_capacity(visible_rows);
+        let mut last_visible = offset;
+        for (idx, height) in heights.iter().enumerate().skip(offset) {
+            for line in 0..*height {
+                if rows.len() == visible_rows {
+                    break;
+                }
+                last_visible = idx;
+                // The highlight symbol takes the first two columns.
+                let y = list_area.y + rows.len() as u16;
+                for placement in item_links.get(&idx).into_iter().flatten() {
+                    if placement.line == line {
+                        self.screen_links.push(ScreenLink {
+                            x: list_area.x + 2 + placement.col as u16,
+                            y,
+                            text: placement.text.clone(),
+                            url: placement.url.clone(),
+                        });
+                    }
+                }
+                rows.push(item_keys.get(idx).and_then(|(ts, spacer)| {
+                    (!(*spacer && line + 1 == *height)).then(|| ts.clone())
+                }));
+            }
+        }
+        self.message_rows = MessageRowMap {
+            channel_id,
+            area: list_area,
+            offset,
+            rows,
+        };
+
+        // Drawn last so the title can show which messages ended up visible.
+        let mut title = if self.scroll_offset > 0 && !item_keys.is_empty() {
+            format!(
+                " Messages {}–{} of {} ",
+                offset + 1,
+                last_visible + 1,
+                item_keys.len()
+            )
+        } else {
+            " Messages ".to_string()
+        };
+        if let Some(summary) = self.message_filter_summary() {
+            title.push_str(&format!("· filtered: {} (Esc to clear) ", summary));
+        }
+        frame.render_widget(block.title(title), area);
+    }
+
+    /// "@alice, has files, in threads" for the active message filter.
+    fn message_filter_summary(&self) -> Option<String> {
+        if !self.message_filter.is_active() {
+            return None;
+        }
+        let mut parts = Vec::new();
+        if let Some(ref user_id) = self.message_filter.user_id {
+            let name = self
+                .current_channel_messages()
+                .and_then(|messages| messages.iter().find(|m| m.user_id == *user_id))
+                .map(|m| m.username.clone())
+                .unwrap_or_else(|| user_id.clone());
+            parts.push(format!("@{}", name));
+        }
+        if self.message_filter.has_files {
+            parts.push("has files".to_string());
+        }
+        if self.message_filter.in_threads {
+            parts.push("in threads".to_string());
+        }
+        Some(parts.join(", "))
+    }
+
+    /// "#general · 214 members · Topic: …", cut to `width` columns.
+    fn channel_header_line(&self, width: usize) -> Option<String> {
+        let channel = self.selected_channel()?;
+        let sigil = if channel.is_dm { "@" } else { "#" };
+        let mut parts = vec![format!("{}{}", sigil, channel.name)];
+        if channel.is_ext_shared {
+            parts[0].insert_str(0, "⇄ ");
+            parts.push("shared with external organizations".to_string());
+        } else if channel.is_any_shared() {
+            parts[0].insert_str(0, "⇄ ");
+        }
+        if let Some(count) = channel.member_count {
+            parts.push(format!(
+                "{} member{}",
+                count,
+                if count == 1 { "" } else { "s" }
+            ));
+        }
+        let non_empty = |value: &Option<String>| value.clone().filter(|v| !v.trim().is_empty());
+        if let Some(topic) = non_empty(&channel.topic) {
+            parts.push(format!("Topic: {}", topic));
+        } else if let Some(purpose) = non_empty(&channel.purpose) {
+            parts.push(format!("Purpose: {}", purpose));
+        }
+
+        let line = parts.join(" · ").replace('\n', " ");
+        Some(truncate_display(&line, width).into_owned())
+    }
+
+    fn render_command_palette(&self, frame: &mut Frame, area: Rect, palette: &CommandPalette) {
+        use ratatui::layout::{Constraint, Direction, Layout};
+
+        use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph};
+
+        let popup_area = self.centered_rect(50, 60, area);
+        frame.render_widget(Clear, popup_area);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" Command Palette ");
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(2), Constraint::Min(1)])
+            .split(inner);
+        frame.render_widget(Paragraph::new(format!("> {}▏", palette.query)), chunks[0]);
+
+        let width = inner.width as usize;
+        let items: Vec<ListItem> = self
+            .keybinds
+            .matching(&palette.query)
+            .into_iter()
+            .map(|binding| {
+                let key = binding.key_label();
+                let pad =
+                    width.saturating_sub(binding.label.chars().count() + key.chars().count() + 3);
+                ListItem::new(format!(" {}{}{} ", binding.label, " ".repeat(pad), key))
+            })
+            .collect();
+        let mut state = ListState::default();
+        if !items.is_empty() {
+            state.select(Some(palette.selected));
+        }
+        frame.render_stateful_widget(
+            List::new(items).highlight_style(self.theme.cursor),
+            chunks[1],
+            &mut state,
+        );
+    }
+
+    fn render_user_filter_picker(&self, frame: &mut Frame, area: Rect, picker: &UserFilterPicker) {
+        use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState};
+
+        let check = |on: bool| if on { "[x]" } else { "[ ]" };
+        let mut items = vec![
+            ListItem::new(format!(" {} Has files", check(picker.has_files))),
+            ListItem::new(format!(" {} In threads", check(picker.in_threads))),
+            ListItem::new(" Anyone"),
+        ];
+        items.extend(
+            picker
+                .authors
+                .iter()
+                .map(|author| ListItem::new(format!(" @{}  ({})", author.name, author.count))),
+        );
+
+        let popup_area = self.centered_rect(40, 50, area);
+        frame.render_widget(Clear, popup_area);
+        let mut state = ListState::default().with_selected(Some(picker.selected));
+        frame.render_stateful_widget(
+            List::new(items)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(" Filter Messages ")
+                        .title_bottom(" [Space] toggle  [Enter] apply  [Esc] cancel "),
+                )
+                .highlight_style(self.theme.cursor),
+            popup_area,
+            &mut state,
+        );
+    }
+
+    fn render_reaction_picker(&self, frame: &mut Frame, area: Rect, picker: &ReactionPicker) {
+        use ratatui::text::Line;
+        use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph};
+
+        let popup_area = self.centered_rect(40, 50, area);
+        frame.render_widget(Clear, popup_area);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" React ")
+            .title_bottom(" [1-5] quick  [Enter] toggle  [Esc] cancel ");
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+        if inner.height < 2 {
+            return;
+        }
+
+        frame.render_widget(
+            Paragraph::new(Line::from(format!(" :{}▏", picker.query))),
+            Rect { height: 1, ..inner },
+        );
+        let quick = picker.query.is_empty();
+        let items: Vec<ListItem> = picker
+            .matches()
+            .into_iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let key = if quick && i < QUICK_REACTIONS {
+                    format!("{} ", i + 1)
+                } else {
+                    "  ".to_string()
+                };
+                let glyph = reaction_glyph(name).unwrap_or(" ");
+                let mark = if self.has_own_reaction(name) {
+                    "  ✓"
+                } else {
+                    ""
+                };
+                ListItem::new(format!(" {}{} :{}:{}", key, glyph, name, mark))
+            })
+            .collect();
+        let mut state = ListState::default().with_selected(Some(picker.selected));
+        frame.render_stateful_widget(
+            List::new(items).highlight_style(self.theme.cursor),
+            Rect {
+                y: inner.y + 1,
+                height: inner.height - 1,
+                ..inner
+            },
+            &mut state,
+        );
+    }
+
+    /// The channel's recent agent commands while a count for `.` is typed.
+    fn render_rerun_menu(&self, frame: &mut Frame, area: Rect, count: usize) {
+        use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState};
+
+        let commands = self.channel_agent_commands();
+        let height = (commands.len() as u16 + 2).min(area.height);
+        let popup_area = Rect {
+            height,
+            ..self.centered_rect(50, 100, area)
+        };
+        let popup_area = Rect {
+            y: area.y + area.height.saturating_sub(height) / 2,
+            ..popup_area
+        };
+        frame.render_widget(Clear, popup_area);
+        let items: Vec<ListItem> = commands
+            .iter()
+            .enumerate()
+            .map(|(i, command)| {
+                ListItem::new(format!(" {} {}", i + 1, truncate_display(command, 60)))
+            })
+            .collect();
+        let mut state = ListState::default().with_selected(Some(count.saturating_sub(1)));
+        frame.render_stateful_widget(
+            List::new(items)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(" Re-run ")
+                        .title_bottom(" [.] run  [Esc] cancel "),
+                )
+                .highlight_style(self.theme.cursor),
+            popup_area,
+            &mut state,
+        );
+    }
+
+    fn render_mark_all_read_confirm(&self, frame: &mut Frame, area: Rect) {
+        use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+        let unread = self
+            .channels
+            .iter()
+            .filter(|ch| ch.unread_count > 0 && !self.is_muted(&ch.id))
+            .count();
+        let text = format!(
+            "Mark {} channel{} as read?\n\n[y] Yes  [n/Esc] Cancel",
+            unread,
+            if unread == 1 { "" } else { "s" }
+        );
+        let popup_area = self.centered_rect(40, 20, area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(
+            Paragraph::new(text).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Mark All Read "),
+            ),
+            popup_area,
+        );
+    }
+
+    fn render_agent_offline_prompt(&self, frame: &mut Frame, area: Rect, command: &str) {
+        use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+
+        let text = format!(
+            "ZeroClaw is not connected, so this can't run yet:\n\n  {}\n\n\
+             Start ZeroClaw now (pairing if it asks)? The command runs as soon as it \
+             connects.\n\n[y/Enter] Connect  [n/Esc] Back to the input",
+            sanitize_text(command)
+        );
+        let popup_area = self.centered_rect(60, 35, area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(
+            Paragraph::new(text).wrap(Wrap { trim: false }).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Agent Offline "),
+            ),
+            popup_area,
+        );
+    }
+
+    fn render_send_recovery(&self, frame: &mut Frame, area: Rect, prompt: &SendRecoveryPrompt) {
+        use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+        use slack_zc_slack::chunk::{split_message, MAX_MESSAGE_CHARS};
+
+        let channel = self
+            .channels
+            .iter()
+            .find(|c| c.id == prompt.send.channel_id)
+            .map(|c| c.name.clone())
+            .unwrap_or_else(|| prompt.send.channel_id.clone());
+        let text = match prompt.recovery {
+            SendRecovery::JoinChannel => format!(
+                "Slack refused the message because you're not in #{0}.\n\n\
+                 Join #{0} and send it?\n\n[y/Enter] Join and send  [n/Esc] Cancel",
+                sanitize_text(&channel)
+            ),
+            SendRecovery::SplitMessage => format!(
+                "The message is {} characters; Slack takes at most {}.\n\n\
+                 Send it as {} messages, in order?\n\n[y/Enter] Split and send  [n/Esc] Cancel",
+                prompt.send.text.chars().count(),
+                MAX_MESSAGE_CHARS,
+                split_message(&prompt.send.text, MAX_MESSAGE_CHARS).len()
+            ),
+        };
+        let popup_area = self.centered_rect(60, 30, area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(
+            Paragraph::new(text).wrap(Wrap { trim: false }).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Send Failed "),
+            ),
+            popup_area,
+        );
+    }
+
+    fn render_long_message(&self, frame: &mut Frame, area: Rect, send: &PendingSend) {
+        use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+        use slack_zc_slack::chunk::{split_message, MAX_MESSAGE_CHARS};
+
+        let text = format!(
+            "This message is {} characters; Slack takes at most {} per message.\n\n\
+             [s/Enter] Send as {} messages, in order\n\
+             [u] Upload as a text snippet\n\
+             [Esc] Back to editing",
+            send.text.chars().count(),
+            MAX_MESSAGE_CHARS,
+            split_message(&send.text, MAX_MESSAGE_CHARS).len()
+        );
+        let popup_area = self.centered_rect(50, 30, area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(
+            Paragraph::new(text).wrap(Wrap { trim: false }).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Long Message "),
+            ),
+            popup_area,
+        );
+    }
+
+    fn render_raw_inspector(&self, frame: &mut Frame, area: Rect, inspector: &RawInspector) {
+        use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+        let popup_area = self.centered_rect(80, 80, area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(
+            Paragraph::new(sanitize_text(&inspector.json))
+                .scroll((inspector.scroll, 0))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(format!(" Raw JSON · {} ", inspector.ts))
+                        .title_bottom(" ↑↓/PgUp/PgDn scroll · [c] copy · [Esc] close "),
+                ),
+            popup_area,
+        );
+    }
+
+    fn render_channel_info(&self, frame: &mut Frame, area: Rect) {
+        use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+
+        let Some(channel) = self.selected_channel() else {
+            return;
+        };
+        let or_none = |value: &Option<String>| {
+            value
+                .clone()
+                .filter(|v| !v.trim().is_empty())
+                .unwrap_or_else(|| "(none)".to_string())
+        };
+        let creator = channel.creator.as_ref().map(|id| {
+            self.active_workspace()
+                .and_then(|ws| ws.users.get(id))
+                .map(|user| user.display_name())
+                .or_else(|| {
+                    self.messages
+                        .values()
+                        .flatten()
+                        .find(|m| m.user_id == *id)
+                        .map(|m| m.username.clone())
+                })
+                .unwrap_or_else(|| id.clone())
+        });
+        let created = channel
+            .created
+            .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+            .map(|at| at.format("%Y-%m-%d").to_string());
+
+        let mut text = format!(
+            "{}\n\nTopic: {}\n\nPurpose: {}\n\nMembers: {}\n",
+            channel.display_name(),
+            or_none(&channel.topic),
+            or_none(&channel.purpose),
+            channel
+                .member_count
+                .map(|count| count.to_string())
+                .unwrap_or_else(|| "loading…".to_string()),
+        );
+        match (created, creator) {
+            (Some(created), Some(creator)) => {
+                text.push_str(&format!("Created: {} by @{}\n", created, creator))
+            }
+            (Some(created), None) => text.push_str(&format!("Created: {}\n", created)),
+            _ => {}
+        }
+        text.push_str("\n[Esc] close");
+
+        let popup_area = self.centered_rect(60, 50, area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(
+            Paragraph::new(text).wrap(Wrap { trim: false }).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Channel Info "),
+            ),
+            popup_area,
+        );
     }
 
     fn render_agent_panel(&self, frame: &mut Frame, area: Rect) {
         use ratatui::layout::Alignment;
+        use ratatui::style::Style;
+        use ratatui::text::{Line, Span};
         use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
 
         if let Some(ref dialog) = self.confirmation_dialog {
@@ -693,31 +1933,85 @@ impl App {
 
         if let (Some(start_time), Some(cmd)) = (self.loading_start_time, &self.loading_command) {
             let elapsed = start_time.elapsed().as_secs();
-            let loading_text = format!("Processing {}... ({}s)", cmd, elapsed);
+            let verb = if self.loading_rerun {
+                "Re-running"
+            } else {
+                "Processing"
+            };
+            let loading_text = match self.loading_timeout_secs {
+                Some(limit) => format!("{} {}... ({}s / {}s)", verb, cmd, elapsed, limit),
+                None => format!("{} {}... ({}s)", verb, cmd, elapsed),
+            };
+
+            if self.streaming_response.is_empty() {
+                frame.render_widget(
+                    Paragraph::new(loading_text)
+                        .block(Block::default().borders(Borders::ALL).title(" Agent "))
+                        .alignment(Alignment::Center),
+                    area,
+                );
+                return;
+            }
+
+            // Keep the tail of the partial response visible as chunks arrive.
+            let content_width = area.width.saturating_sub(2) as usize;
+            let visible_lines = area.height.saturating_sub(4) as usize;
+            let wrapped = Self::wrap_plain_lines(&self.streaming_response, content_width);
+            let tail = wrapped
+                .iter()
+                .skip(wrapped.len().saturating_sub(visible_lines))
+                .cloned()
+                .collect::<Vec<_>>()
+                .join("\n");
 
             frame.render_widget(
-                Paragraph::new(loading_text)
-                    .block(Block::default().borders(Borders::ALL).title(" Agent "))
-                    .alignment(Alignment::Center),
+                Paragraph::new(format!("{}\n\n{}", loading_text, tail))
+                    .block(Block::default().borders(Borders::ALL).title(" Agent ")),
                 area,
             );
             return;
         }
 
         let status = match self.agent_status {
+            _ if !self.agent_enabled() => "○ off in this workspace (/agent on)",
             AgentStatus::Unavailable => "⚠ unavailable",
             AgentStatus::Starting => "▶ starting...",
             AgentStatus::Pairing => "⚙ pairing...",
             AgentStatus::Active => "● active",
+            AgentStatus::Restarting { attempt, max } => {
+                &format!("✗ crashed — restarting ({}/{})", attempt, max)
+            }
             AgentStatus::Error(ref e) => &format!("✗ {}", e),
         };
 
-        let mut text = format!("⚡ ZEROCLAW\n\nStatus: {}\n\n", status);
+        let mut text = format!("⚡ ZEROCLAW\n\nStatus: {}\n", status);
+        let configured_port = self.config.zeroclaw.gateway_port;
+        match self
+            .agent_runner
+            .as_ref()
+            .map(|runner| runner.gateway_port())
+        {
+            Some(port) if port != configured_port => text.push_str(&format!(
+                "Port: {} ({} was taken)\n\n",
+                port, configured_port
+            )),
+            Some(port) => text.push_str(&format!("Port: {}\n\n", port)),
+            None => text.push('\n'),
+        }
 
+        // The command list is greyed out while nothing can run it.
+        let commands_dimmed = !self.agent_is_active() || !self.agent_enabled();
+        let head = std::mem::take(&mut text);
         text.push_str("Commands:\n");
         text.push_str("  /résume [#channel]\n");
-        text.push_str("  /draft [intent]\n");
-        text.push_str("  /cherche [text]\n\n");
+        text.push_str("  /draft [intent] → input\n");
+        text.push_str("  /cherche [text]\n");
+        text.push_str("  /tldr (selected thread)\n");
+        text.push_str("  /translate [lang]\n");
+        text.push_str("  /agent restart\n");
+        text.push_str("  /agent on | off\n");
+        let commands = std::mem::take(&mut text);
+        text.push('\n');
         text.push_str(&format!(
             "Post to Slack: {}\n\n",
             if self.config.zeroclaw.post_to_slack {
@@ -737,28 +2031,41 @@ impl App {
             }
         }
 
+        let mut recent = Vec::new();
         if !self.agent_responses.is_empty() {
-            text.push_str("── Recent ──\n");
+            recent.push(Line::from("── Recent ──"));
             for resp in self.agent_responses.iter().take(5) {
                 let time = resp.timestamp.format("%H:%M").to_string();
                 let content_width = area.width.saturating_sub(4) as usize;
-                let prefix = format!("{} {}: ", time, resp.command);
-                let continuation = " ".repeat(prefix.chars().count());
-                let wrapped = Self::wrap_plain_with_prefix(
-                    &prefix,
-                    &continuation,
-                    &resp.response,
-                    content_width,
-                );
-                text.push_str(&format!(
-                    "{}\n",
-                    wrapped
-                ));
+                let mut header = vec![Span::raw(format!("{} {}", time, resp.command))];
+                if let Some(origin) = Self::response_origin(resp) {
+                    header.push(Span::styled(format!(" {}", origin), self.theme.dim));
+                }
+                recent.push(Line::from(header));
+                let wrapped =
+                    Self::wrap_plain_with_prefix("  ", "  ", &resp.response, content_width);
+                recent.extend(wrapped.lines().map(|line| Line::from(line.to_string())));
             }
         }
 
+        let command_style = if commands_dimmed {
+            self.theme.dim
+        } else {
+            Style::default()
+        };
+        let lines: Vec<Line> = head
+            .lines()
+            .map(|line| Line::from(line.to_string()))
+            .chain(
+                commands
+                    .lines()
+                    .map(|line| Line::styled(line.to_string(), command_style)),
+            )
+            .chain(text.lines().map(|line| Line::from(line.to_string())))
+            .chain(recent)
+            .collect();
         frame.render_widget(
-            Paragraph::new(text)
+            Paragraph::new(lines)
                 .wrap(Wrap { trim: false })
                 .block(Block::default().borders(Borders::ALL).title(" Agent ")),
             area,
@@ -771,18 +2078,56 @@ impl App {
         area: Rect,
         dialog: &ConfirmationDialog,
     ) {
-        use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+        use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
 
         frame.render_widget(Clear, area);
 
-        let context = dialog.context_channel.as_deref().unwrap_or("none");
+        // `#name` from the prompt, or an id pasted in its place.
+        let context = match dialog.context_channel.as_deref() {
+            Some(reference) => self
+                .channels
+                .iter()
+                .find(|ch| ch.id == reference || ch.name == reference)
+                .map(|ch| ch.display_name())
+                .unwrap_or_else(|| format!("#{}", reference)),
+            None => "this channel".to_string(),
+        };
+        let channel = dialog
+            .target
+            .as_ref()
+            .map(|target| {
+                self.channels
+                    .iter()
+                    .find(|ch| ch.id == target.channel_id)
+                    .map(|ch| ch.display_name())
+                    .unwrap_or_else(|| format!("# {}", target.channel_name))
+            })
+            .unwrap_or_default();
+        let target = match dialog.reply {
+            ReplyTarget::Thread => {
+                let parent = dialog
+                    .thread_parent
+                    .as_deref()
+                    .map(|parent| truncate_display(parent, 50).into_owned())
+                    .unwrap_or_else(|| "thread".to_string());
+                format!("thread in {} — {}", channel, parent)
+            }
+            ReplyTarget::Channel => channel,
+            ReplyTarget::PanelOnly if dialog.can_post => "don't post (panel only)".to_string(),
+            ReplyTarget::PanelOnly => "panel only".to_string(),
+        };
+        let (prompt_label, keys) = if dialog.is_editing {
+            ("Prompt (editing)", "[Tab] Options")
+        } else {
+            ("Prompt", "[Tab] Edit prompt  [c] Change target")
+        };
         let content = format!(
-            "Command: {}\n\nPrompt (editable): {}\n\nContext: {}\n\n[Enter] Confirm  [Esc] Cancel",
-            dialog.command, dialog.prompt, context
+            "Command: {}\n\n{}: {}\n\nContext: {}\nPost to: {}\n\n[Enter] Confirm  {}  [Esc] Cancel",
+            dialog.command, prompt_label, dialog.prompt, context, target, keys
         );
 
         frame.render_widget(
-            Paragraph::new(content).block(
+            Paragraph::new(content).wrap(Wrap { trim: false }).block(
                 Block::default()
                     .borders(Borders::ALL)
                     .title(" Confirm Command "),
@@ -793,6 +2138,7 @@ impl App {
 
     fn render_channel_picker(&self, frame: &mut Frame, input_area: Rect, picker: &ChannelPicker) {
         use ratatui::style::{Modifier, Style};
+        use ratatui::text::{Line, Span};
         use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState};
 
         let max_visible = 8u16;
@@ -814,7 +2160,23 @@ impl App {
         let items: Vec<ListItem> = picker
             .filtered_channels
             .iter()
-            .map(|ch| ListItem::new(format!("#{}", ch.name)))
+            .map(|entry| {
+                let team = entry.team_id.as_deref().and_then(|team_id| {
+                    self.workspaces
+                        .iter()
+                        .find(|ws| ws.workspace.team_id == team_id)
+                });
+                match team {
+                    Some(ws) => ListItem::new(Line::from(vec![
+                        Span::raw(format!("#{}", entry.channel.name)),
+                        Span::styled(
+                            format!(" — {}", ws.workspace.team_name),
+                            Style::default().add_modifier(Modifier::DIM),
+                        ),
+                    ])),
+                    None => ListItem::new(format!("#{}", entry.channel.name)),
+                }
+            })
             .collect();
 
         let mut list_state = ListState::default();
@@ -828,7 +2190,20 @@ impl App {
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title(format!(" Channel Picker: {} ", picker.query)),
+                    .title(
+                        if picker.trigger_position == 0
+                            && (picker.all_workspaces || picker.query.starts_with('*'))
+                        {
+                            format!(" All Workspaces: {} ", picker.query)
+                        } else {
+                            format!(" Channel Picker: {} ", picker.query)
+                        },
+                    )
+                    .title_bottom(if picker.trigger_position == 0 {
+                        " Tab: all workspaces "
+                    } else {
+                        ""
+                    }),
             )
             .highlight_style(Style::default().add_modifier(Modifier::BOLD));
 
@@ -836,12 +2211,13 @@ impl App {
     }
 
     fn render_input_bar(&self, frame: &mut Frame, area: Rect) {
-        use ratatui::style::{Color, Style};
+        use ratatui::style::Style;
+        use ratatui::text::{Line, Span};
         use ratatui::widgets::{Block, Borders, Paragraph};
 
         let in_thread = self
             .get_active_channel_id()
-            .map_or(false, |ch| self.active_threads.contains_key(&ch));
+            .is_some_and(|ch| self.active_threads.contains_key(&ch));
 
         let mode_indicator = if in_thread {
             "[↩]"
@@ -853,7 +2229,11 @@ impl App {
             }
         };
 
-        let text = format!("{} > {}", mode_indicator, self.input.buffer);
+        // Agent modes still accept typing while offline, but say so up front.
+        let agent_offline = self.input.mode != InputMode::Normal
+            && !(self.agent_is_active() && self.agent_enabled());
+        let dimmed = self.theme.dim;
+        let text = format!(" > {}", self.input.buffer);
         let text = if self.agent_processing {
             format!("{}   [agent processing]", text)
         } else if self.focus == Focus::Input {
@@ -861,21 +2241,58 @@ impl App {
         } else {
             text
         };
+        let archived = self
+            .get_active_channel_id()
+            .is_some_and(|ch| self.is_archived_channel(&ch));
+        let read_only = self
+            .read_only_here()
+            .filter(|_| self.input.mode != InputMode::AgentCommand);
+        let text = if archived {
+            Line::from(vec![
+                Span::styled(mode_indicator, dimmed),
+                Span::styled(" channel is archived", dimmed),
+            ])
+        } else if let Some(reason) = read_only {
+            Line::from(vec![
+                Span::styled(mode_indicator, dimmed),
+                Span::styled(format!(" {}", reason), dimmed),
+            ])
+        } else if agent_offline {
+            let note = if self.agent_enabled() {
+                " (agent offline)"
+            } else {
+                " (agent off here)"
+            };
+            Line::from(vec![
+                Span::styled(mode_indicator, dimmed),
+                Span::styled(note, dimmed),
+                Span::raw(text),
+            ])
+        } else {
+            Line::from(vec![Span::raw(mode_indicator), Span::raw(text)])
+        };
 
         let border_style = if self.focus == Focus::Input {
-            Style::default().fg(Color::Yellow)
+            self.theme.focus_border
         } else {
             Style::default()
         };
 
-        frame.render_widget(
-            Paragraph::new(text).block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .border_style(border_style),
-            ),
-            area,
-        );
+        // While a command name is typed, the commands it could be, with
+        // their arguments; Tab completes the first.
+        let suggestions = self
+            .slash_suggestions()
+            .iter()
+            .map(|command| command.usage())
+            .collect::<Vec<_>>()
+            .join(" · ");
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(border_style);
+        if !suggestions.is_empty() {
+            block = block.title(Span::styled(format!(" {} ", suggestions), dimmed));
+        }
+        frame.render_widget(Paragraph::new(text).block(block), area);
 
         if let Some(ref picker) = self.channel_picker {
             self.render_channel_picker(frame, area, picker);
@@ -888,84 +2305,431 @@ impl App {
         let menu_width = menu.items.iter().map(|i| i.label.len()).max().unwrap_or(10) as u16 + 4;
         let menu_height = menu.items.len() as u16 + 2;
 
-        let menu_area = Rect::new(
-            menu.x,
-            menu.y,
-            menu_width.min(area.width.saturating_sub(menu.x)),
-            menu_height.min(area.height.saturating_sub(menu.y)),
+        let menu_area = Rect::new(
+            menu.x,
+            menu.y,
+            menu_width.min(area.width.saturating_sub(menu.x)),
+            menu_height.min(area.height.saturating_sub(menu.y)),
+        );
+
+        frame.render_widget(Clear, menu_area);
+
+        let items: Vec<ListItem> = menu
+            .items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                if i == menu.selected {
+                    ListItem::new(format!(" > {} ", item.label))
+                } else {
+                    ListItem::new(format!("   {} ", item.label))
+                }
+            })
+            .collect();
+
+        frame.render_widget(
+            List::new(items).block(Block::default().borders(Borders::ALL).title(" Actions ")),
+            menu_area,
+        );
+    }
+
+    fn render_edit_message(&self, frame: &mut Frame, area: Rect, edit_state: &EditState) {
+        use ratatui::style::{Color, Style};
+        use ratatui::text::{Line, Span};
+        use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+
+        let popup_area = self.centered_rect(70, 50, area);
+
+        frame.render_widget(Clear, popup_area);
+
+        // Diff-style review: the original above, the live buffer below.
+        let removed = self.theme.error;
+        let added = Style::default().fg(Color::Green);
+        let mut lines = Vec::new();
+        for line in edit_state.original_text.lines() {
+            lines.push(Line::from(Span::styled(format!("- {}", line), removed)));
+        }
+        let buffer = format!("{}▏", self.input.buffer);
+        for line in buffer.split('\n') {
+            lines.push(Line::from(Span::styled(format!("+ {}", line), added)));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(
+            "[Enter] save  [Shift+Enter] newline  [Esc] cancel and restore draft",
+        ));
+
+        frame.render_widget(
+            Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Edit Message "),
+            ),
+            popup_area,
+        );
+    }
+
+    fn render_pairing_prompt(&self, frame: &mut Frame, area: Rect, prompt: &PairingPrompt) {
+        use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+
+        let popup_area = self.centered_rect(50, 30, area);
+        let mut text = format!(
+            "ZeroClaw is waiting to be paired.\n\
+             Enter the 6-digit pairing code printed by `zeroclaw gateway`:\n\n  {}\n",
+            prompt.code
+        );
+        if let Some(error) = prompt.error.as_deref() {
+            text.push_str(&format!("\n✗ {}\n", Self::redact_sensitive(error)));
+        }
+        text.push_str("\n[Enter] pair, [Esc] cancel");
+
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(
+            Paragraph::new(text).wrap(Wrap { trim: false }).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Pair with ZeroClaw "),
+            ),
+            popup_area,
+        );
+    }
+
+    fn render_agent_logs(&self, frame: &mut Frame, area: Rect, lines: &[String]) {
+        use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+        let popup_area = self.centered_rect(80, 70, area);
+        let content_width = popup_area.width.saturating_sub(2) as usize;
+        let content_lines = popup_area.height.saturating_sub(4) as usize;
+
+        // Show the tail: the newest output is what explains a failure.
+        let mut wrapped: Vec<String> = lines
+            .iter()
+            .flat_map(|line| Self::wrap_plain_lines(&Self::redact_sensitive(line), content_width))
+            .collect();
+        if wrapped.is_empty() {
+            wrapped.push("No ZeroClaw output captured yet.".to_string());
+        }
+        let start = wrapped.len().saturating_sub(content_lines);
+        let text = format!(
+            "{}\n\n[Esc] or [Enter] to close",
+            wrapped[start..].join("\n")
+        );
+
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(
+            Paragraph::new(text).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(" ZeroClaw Output ({} lines) ", lines.len())),
+            ),
+            popup_area,
         );
+    }
 
-        frame.render_widget(Clear, menu_area);
+    fn render_error_details(&self, frame: &mut Frame, area: Rect) {
+        use ratatui::text::{Line, Span};
+        use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 
-        let items: Vec<ListItem> = menu
-            .items
-            .iter()
-            .enumerate()
-            .map(|(i, item)| {
-                if i == menu.selected {
-                    ListItem::new(format!(" > {} ", item.label))
+        let popup_area = self.centered_rect(70, 60, area);
+        let content_width = popup_area.width.saturating_sub(2) as usize;
+        let mut lines = Vec::new();
+        if self.error_history.is_empty() {
+            lines.push(Line::from("No error details available."));
+        }
+        for entry in self.error_history.iter().skip(self.error_history_scroll) {
+            let stamp = entry.at.format("%H:%M:%S").to_string();
+            let wrapped = Self::wrap_plain_with_prefix(
+                &format!("{} ", stamp),
+                &" ".repeat(stamp.len() + 1),
+                &entry.message,
+                content_width,
+            );
+            let wrapped = if entry.retry.is_some() {
+                format!(
+                    "{}\n{}[R/Enter] retry this send",
+                    wrapped,
+                    " ".repeat(stamp.len() + 1)
+                )
+            } else {
+                wrapped
+            };
+            for (idx, line) in wrapped.lines().enumerate() {
+                if idx == 0 {
+                    let split = line
+                        .char_indices()
+                        .nth(stamp.chars().count())
+                        .map_or(line.len(), |(at, _)| at);
+                    let (time, rest) = line.split_at(split);
+                    lines.push(Line::from(vec![
+                        Span::styled(time.to_string(), self.theme.dim),
+                        Span::raw(rest.to_string()),
+                    ]));
                 } else {
-                    ListItem::new(format!("   {} ", item.label))
+                    lines.push(Line::from(line.to_string()));
                 }
-            })
-            .collect();
+            }
+        }
 
+        frame.render_widget(Clear, popup_area);
         frame.render_widget(
-            List::new(items).block(Block::default().borders(Borders::ALL).title(" Actions ")),
-            menu_area,
+            Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(format!(
+                " Errors ({}) — ↑/↓ scroll, Esc close ",
+                self.error_history.len()
+            ))),
+            popup_area,
         );
     }
 
-    fn render_edit_message(&self, frame: &mut Frame, area: Rect, edit_state: &EditState) {
+    fn render_connection_status(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        panel: &ConnectionStatusPanel,
+    ) {
+        use ratatui::style::{Color, Modifier, Style};
+        use ratatui::text::{Line, Span};
         use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+        use slack_zc_slack::socket::ConnectionState;
+
+        let ago = |at: Instant| format!("{}s ago", at.elapsed().as_secs());
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let mut lines = Vec::new();
+        for ws in &self.workspaces {
+            lines.push(Line::from(Span::styled(
+                ws.workspace.team_name.clone(),
+                bold,
+            )));
+            let (socket, color) = match self.socket_status.get(&ws.workspace.team_id) {
+                None => ("not started".to_string(), Color::DarkGray),
+                Some(status) => match &status.state {
+                    ConnectionState::Connecting => ("connecting…".to_string(), Color::Yellow),
+                    ConnectionState::Connected => (
+                        format!(
+                            "connected · last event {}",
+                            status
+                                .last_event
+                                .map(ago)
+                                .unwrap_or_else(|| "none yet".to_string())
+                        ),
+                        Color::Green,
+                    ),
+                    ConnectionState::Reconnecting { attempt, retry_in } => (
+                        format!(
+                            "reconnecting · attempt {} · next try in {}s",
+                            attempt,
+                            retry_in.saturating_sub(status.since.elapsed()).as_secs()
+                        ),
+                        Color::Red,
+                    ),
+                },
+            };
+            lines.push(Line::from(vec![
+                Span::raw("  Socket  "),
+                Span::styled(socket, Style::default().fg(color)),
+            ]));
+        }
 
-        let popup_area = self.centered_rect(60, 20, area);
-
-        frame.render_widget(Clear, popup_area);
+        let health = self.slack_api.api_health();
+        let now = chrono::Utc::now();
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Slack API (all workspaces)", bold)));
+        lines.push(Line::from(format!(
+            "  Last success  {}",
+            health
+                .last_success
+                .map(|at| format!(
+                    "{} ({}s ago)",
+                    at.with_timezone(&chrono::Local).format("%H:%M:%S"),
+                    (now - at).num_seconds().max(0)
+                ))
+                .unwrap_or_else(|| "none yet".to_string())
+        )));
+        if let Some(until) = health.rate_limited_until.filter(|until| *until > now) {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "  Rate limited  {}s left",
+                    (until - now).num_seconds().max(1)
+                ),
+                Style::default().fg(Color::Yellow),
+            )));
+        }
 
-        let text = format!(
-            "Editing message:\n\n{}\n\n[Enter] to save, [Esc] to cancel",
-            edit_state.original_text
-        );
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("ZeroClaw", bold)));
+        let status = match self.agent_status {
+            AgentStatus::Unavailable => "unavailable".to_string(),
+            AgentStatus::Starting => "starting".to_string(),
+            AgentStatus::Pairing => "pairing".to_string(),
+            AgentStatus::Active => "active".to_string(),
+            AgentStatus::Restarting { attempt, max } => {
+                format!("restarting ({}/{})", attempt, max)
+            }
+            AgentStatus::Error(ref e) => format!("error: {}", e),
+        };
+        lines.push(Line::from(format!("  Status   {}", status)));
+        let (gateway, color) = match &panel.gateway_check {
+            None => ("checking…".to_string(), Color::Yellow),
+            Some(Ok(latency)) => (
+                format!("healthy · {} ms", latency.as_millis()),
+                Color::Green,
+            ),
+            Some(Err(e)) => (e.clone(), Color::Red),
+        };
+        lines.push(Line::from(vec![
+            Span::raw("  Gateway  "),
+            Span::styled(gateway, Style::default().fg(color)),
+        ]));
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Background tasks", bold)));
+        for (kind, running, waiting) in self.tasks.counts() {
+            let waiting = if waiting > 0 {
+                format!(" · {} waiting", waiting)
+            } else {
+                String::new()
+            };
+            lines.push(Line::from(format!(
+                "  {:<8} {} running{}",
+                kind.label(),
+                running,
+                waiting
+            )));
+        }
 
+        let popup_area = self.centered_rect(60, 60, area);
+        frame.render_widget(Clear, popup_area);
         frame.render_widget(
-            Paragraph::new(text).block(
+            Paragraph::new(lines).block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title(" Edit Message "),
+                    .title(" Connections — F11/Esc close "),
             ),
             popup_area,
         );
     }
 
-    fn render_error_details(&self, frame: &mut Frame, area: Rect) {
+    fn render_memory_stats(&self, frame: &mut Frame, area: Rect) {
+        use ratatui::style::{Color, Modifier, Style};
+        use ratatui::text::{Line, Span};
         use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 
-        let popup_area = self.centered_rect(60, 20, area);
-        let details = self
-            .last_error
-            .as_deref()
-            .unwrap_or("No error details available.");
-        let content_width = popup_area.width.saturating_sub(2) as usize;
-        let content_lines = popup_area.height.saturating_sub(4) as usize;
-        let wrapped_details = Self::wrap_and_truncate_text(details, content_width, content_lines);
-        let text = format!("{}\n\n[Esc] or [Enter] to close", wrapped_details);
+        let stats = self.memory_stats();
+        let total: usize = stats.iter().map(|row| row.bytes).sum();
+        let kib = |bytes: usize| format!("{:.1} KiB", bytes as f64 / 1024.0);
+
+        let popup_area = self.centered_rect(60, 60, area);
+        let mut lines = vec![
+            Line::from(format!(
+                "Cap {} messages per channel · {} channels · ~{}",
+                self.config.ui.max_messages_per_channel,
+                stats.len(),
+                kib(total)
+            )),
+            Line::from(format!(
+                "Agent responses {} · typing entries {}",
+                self.agent_responses.len(),
+                self.typing_users.values().map(Vec::len).sum::<usize>()
+            )),
+            Line::from(""),
+            Line::from(Span::styled(
+                format!(
+                    "{:<24} {:>8} {:>8} {:>12}",
+                    "Channel", "Msgs", "Threads", "Approx"
+                ),
+                Style::default().add_modifier(Modifier::BOLD),
+            )),
+        ];
+        for row in &stats {
+            let name = truncate_display(&row.name, 24);
+            let style = if row.messages >= self.config.ui.max_messages_per_channel {
+                Style::default().fg(Color::Yellow)
+            } else {
+                Style::default()
+            };
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "{:<24} {:>8} {:>8} {:>12}",
+                    name,
+                    row.messages,
+                    row.threads,
+                    kib(row.bytes)
+                ),
+                style,
+            )));
+        }
 
         frame.render_widget(Clear, popup_area);
         frame.render_widget(
-            Paragraph::new(text).block(
+            Paragraph::new(lines).block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title(" Error Details "),
+                    .title(" Memory — F12/Esc close "),
             ),
             popup_area,
         );
     }
 
+    /// Recent errors as banners stacked above the input bar until they expire.
+    fn render_error_toasts(&self, frame: &mut Frame, area: Rect) {
+        use ratatui::style::Modifier;
+        use ratatui::widgets::{Clear, Paragraph};
+
+        const MAX_TOASTS: usize = 3;
+
+        let now = std::time::Instant::now();
+        let width = area.width.saturating_sub(4).min(80);
+        let mut bottom = area.y + area.height.saturating_sub(3);
+        let notice = self
+            .notice
+            .as_ref()
+            .filter(|(_, until)| *until > now)
+            .map(|(message, _)| ("✓", message.as_str(), ratatui::style::Style::default()));
+        let errors = self
+            .error_history
+            .iter()
+            .take_while(|entry| entry.toast_until > now)
+            .take(MAX_TOASTS)
+            .map(|entry| {
+                // Leading, so truncating a long message never hides it.
+                let hint = if entry.retry.is_some() {
+                    "⚠ [R] retry ·"
+                } else {
+                    "⚠"
+                };
+                (hint, entry.message.as_str(), self.theme.error)
+            });
+        for (mark, message, style) in notice.into_iter().chain(errors) {
+            if bottom <= area.y + 1 {
+                break;
+            }
+            bottom -= 1;
+            let toast_area = Rect {
+                x: area.x + area.width.saturating_sub(width + 2),
+                y: bottom,
+                width,
+                height: 1,
+            };
+            let message = format!("{} {}", mark, message.replace('\n', " "));
+            let mut text: String = format!(" {}", message)
+                .chars()
+                .take(width as usize)
+                .collect();
+            if message.chars().count() + 1 > width as usize {
+                text.pop();
+                text.push('…');
+            }
+            frame.render_widget(Clear, toast_area);
+            frame.render_widget(
+                Paragraph::new(text).style(style.add_modifier(Modifier::REVERSED)),
+                toast_area,
+            );
+        }
+    }
+
     fn wrap_and_truncate_text(input: &str, width: usize, max_lines: usize) -> String {
         if width == 0 || max_lines == 0 {
             return "... (truncated)".to_string();
         }
+        let input = &*sanitize_text(input);
 
         let mut out = Vec::new();
         let mut truncated = false;
@@ -1054,6 +2818,7 @@ impl App {
         if width == 0 {
             return vec![String::new()];
         }
+        let input = &*sanitize_text(input);
 
         let mut out = Vec::new();
 
@@ -1128,6 +2893,8 @@ impl App {
             return vec![String::new()];
         }
 
+        let first_prefix = &*sanitize_text(first_prefix);
+        let input = &*sanitize_text(input);
         let first_width = width.saturating_sub(first_prefix.chars().count()).max(1);
         let continuation_width = width
             .saturating_sub(continuation_prefix.chars().count())
@@ -1160,7 +2927,6 @@ impl App {
                 };
                 wrapped.push(format!("{prefix}{chunk}"));
             }
-
         }
 
         if wrapped.is_empty() {
@@ -1170,6 +2936,154 @@ impl App {
         wrapped
     }
 
+    /// [`Self::wrap_prefixed_lines`] for text with links, each drawn in
+    /// `styles.2` and reported with where it landed. Wrapping a link splits
+    /// it into one placement per line.
+    fn wrap_linked_lines(
+        first_prefix: &str,
+        continuation_prefix: &str,
+        text: &LinkedText,
+        width: usize,
+        styles: (
+            ratatui::style::Style,
+            ratatui::style::Style,
+            ratatui::style::Style,
+        ),
+    ) -> (Vec<ratatui::text::Line<'static>>, Vec<LinkPlacement>) {
+        use ratatui::text::{Line, Span};
+        use unicode_width::UnicodeWidthStr;
+
+        let (prefix_style, text_style, link_style) = styles;
+        if text.links.is_empty() {
+            let lines = Self::wrap_prefixed_lines(
+                first_prefix,
+                continuation_prefix,
+                &text.text,
+                width,
+                prefix_style,
+                text_style,
+            );
+            return (lines, Vec::new());
+        }
+
+        let first_prefix = &*sanitize_text(first_prefix);
+        let first_width = width.saturating_sub(first_prefix.chars().count()).max(1);
+        let continuation_width = width
+            .saturating_sub(continuation_prefix.chars().count())
+            .max(1);
+        let owners = text.owners();
+        let mut lines = Vec::new();
+        let mut placements = Vec::new();
+        let mut line_start = 0;
+
+        for raw_line in text.text.lines() {
+            let source: Vec<char> = raw_line.chars().collect();
+            let owner_at = |i: usize| owners.get(line_start + i).copied().flatten();
+            let chunk_width = if lines.is_empty() {
+                first_width
+            } else {
+                continuation_width
+            };
+            // Chunks drop the whitespace they were split at and join words
+            // with one space; `next` walks the source alongside.
+            let mut next = 0;
+            for chunk in Self::wrap_plain_lines(raw_line, chunk_width) {
+                let prefix = if lines.is_empty() {
+                    first_prefix
+                } else {
+                    continuation_prefix
+                };
+                while next < source.len() && source[next].is_whitespace() {
+                    next += 1;
+                }
+                let mut runs: Vec<(Option<usize>, String)> = Vec::new();
+                for c in chunk.chars() {
+                    let owner = owner_at(next);
+                    if c == ' ' {
+                        while next < source.len() && source[next].is_whitespace() {
+                            next += 1;
+                        }
+                    } else {
+                        next += 1;
+                    }
+                    match runs.last_mut() {
+                        Some((run_owner, run)) if *run_owner == owner => run.push(c),
+                        _ => runs.push((owner, c.to_string())),
+                    }
+                }
+
+                let mut spans = vec![Span::styled(prefix.to_string(), prefix_style)];
+                let mut col = prefix.width();
+                for (owner, run) in runs {
+                    let run_width = run.width();
+                    match owner {
+                        Some(link) => {
+                            placements.push(LinkPlacement {
+                                line: lines.len(),
+                                col,
+                                text: run.clone(),
+                                url: text.links[link].url.clone(),
+                            });
+                            spans.push(Span::styled(run, link_style));
+                        }
+                        None => spans.push(Span::styled(run, text_style)),
+                    }
+                    col += run_width;
+                }
+                lines.push(Line::from(spans));
+            }
+            line_start += source.len() + 1;
+        }
+
+        (lines, placements)
+    }
+
+    /// `[thumbsup:4 ⟨eyes:2⟩]` under a message, the user's own reactions
+    /// bracketed and styled apart, wrapped between reactions.
+    fn reaction_lines(
+        &self,
+        reactions: &[slack_zc_slack::types::Reaction],
+        user_id: Option<&str>,
+        width: usize,
+    ) -> Vec<ratatui::text::Line<'static>> {
+        use ratatui::text::{Line, Span};
+        use unicode_width::UnicodeWidthStr;
+
+        let last = reactions.len().saturating_sub(1);
+        let mut lines = Vec::new();
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        let mut used = 0;
+        for (i, reaction) in reactions.iter().enumerate() {
+            let name = sanitize_text(&reaction.name);
+            let mine = user_id.is_some_and(|me| reaction.users.iter().any(|u| u == me));
+            let (label, style) = if mine {
+                (
+                    format!("⟨{}:{}⟩", name, reaction.count),
+                    self.theme.own_reaction,
+                )
+            } else {
+                (format!("{}:{}", name, reaction.count), self.theme.reactions)
+            };
+            let open = if i == 0 { "[" } else { " " };
+            let close = if i == last { "]" } else { "" };
+            let needed = open.width() + label.width() + close.width();
+            if used > 0 && used + needed > width {
+                lines.push(Line::from(std::mem::take(&mut spans)));
+                used = 0;
+            }
+            spans.push(Span::styled(open, self.theme.reactions));
+            spans.push(Span::styled(label, style));
+            if !close.is_empty() {
+                spans.push(Span::styled(close, self.theme.reactions));
+            }
+            used += needed;
+        }
+        if !spans.is_empty() {
+            lines.push(Line::from(spans));
+        }
+        lines
+    }
+
     fn wrap_prefixed_lines(
         first_prefix: &str,
         continuation_prefix: &str,
@@ -1178,6 +3092,8 @@ impl App {
         prefix_style: ratatui::style::Style,
         text_style: ratatui::style::Style,
     ) -> Vec<ratatui::text::Line<'static>> {
+        let first_prefix = &*sanitize_text(first_prefix);
+        let input = &*sanitize_text(input);
         let first_width = width.saturating_sub(first_prefix.chars().count()).max(1);
         let continuation_width = width
             .saturating_sub(continuation_prefix.chars().count())
@@ -1219,25 +3135,350 @@ impl App {
     fn render_jump_to_time(&self, frame: &mut Frame, area: Rect) {
         use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 
-        let popup_area = self.centered_rect(40, 15, area);
+        let popup_area = self.centered_rect(50, 25, area);
 
         frame.render_widget(Clear, popup_area);
 
-        let text = format!(
-            "Jump to time (HH:MM or YYYY-MM-DD):\n\n{}\n\n[Enter] to jump, [Esc] to cancel",
+        let mut text = format!(
+            "Jump to time (14:00, yesterday 14:00, 2024-05-01, ISO datetime):\n\n{}▏\n\n",
             self.jump_to_time_buffer
         );
+        if let Some(ref error) = self.jump_to_time_error {
+            text.push_str(&format!("⚠ {}\n\n", error));
+        }
+        text.push_str("[Enter] to jump, [Esc] to cancel");
 
         frame.render_widget(
-            Paragraph::new(text).block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title(" Jump to Message "),
-            ),
+            Paragraph::new(text)
+                .wrap(ratatui::widgets::Wrap { trim: false })
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(" Jump to Message "),
+                ),
             popup_area,
         );
     }
 
+    fn render_reminders(&self, frame: &mut Frame, area: Rect, popup: &RemindersPopup) {
+        use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph};
+
+        let popup_area = self.centered_rect(60, 50, area);
+        frame.render_widget(Clear, popup_area);
+        let title = if popup.loading {
+            " Reminders — loading… ".to_string()
+        } else {
+            format!(" Reminders ({}) ", popup.reminders.len())
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .title_bottom(" ↑↓ select · [d] delete · [Esc] close ");
+        if popup.reminders.is_empty() {
+            let text = if popup.loading {
+                ""
+            } else {
+                "No pending reminders. Set one with /remind me in 20m to …"
+            };
+            frame.render_widget(Paragraph::new(text).block(block), popup_area);
+            return;
+        }
+
+        let width = popup_area.width.saturating_sub(4) as usize;
+        let items: Vec<ListItem> = popup
+            .reminders
+            .iter()
+            .map(|reminder| {
+                let when = match reminder.time {
+                    Some(at) => at
+                        .with_timezone(&Local)
+                        .format("%a %d %b %H:%M")
+                        .to_string(),
+                    None => "recurring".to_string(),
+                };
+                let line = format!("{:<16}  {}", when, sanitize_text(&reminder.text));
+                ListItem::new(truncate_display(&line, width).into_owned())
+            })
+            .collect();
+        let mut list_state = ListState::default();
+        list_state.select(Some(popup.selected.min(items.len() - 1)));
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(self.theme.highlight)
+            .highlight_symbol("▶ ");
+        frame.render_stateful_widget(list, popup_area, &mut list_state);
+    }
+
+    fn render_link_chooser(&self, frame: &mut Frame, area: Rect, chooser: &LinkChooser) {
+        use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState};
+
+        let popup_area = self.centered_rect(70, 40, area);
+        frame.render_widget(Clear, popup_area);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" Open link ({}) ", chooser.links.len()))
+            .title_bottom(" 1-9 or ↑↓ Enter open · [Esc] close ");
+        let width = popup_area.width.saturating_sub(4) as usize;
+        let items: Vec<ListItem> = chooser
+            .links
+            .iter()
+            .enumerate()
+            .map(|(i, link)| {
+                let line = if link.label == link.url {
+                    format!("{}. {}", i + 1, link.url)
+                } else {
+                    format!("{}. {} — {}", i + 1, link.label, link.url)
+                };
+                ListItem::new(truncate_display(&sanitize_text(&line), width).into_owned())
+            })
+            .collect();
+        let mut list_state = ListState::default();
+        list_state.select(Some(chooser.selected.min(items.len().saturating_sub(1))));
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(self.theme.highlight)
+            .highlight_symbol("▶ ");
+        frame.render_stateful_widget(list, popup_area, &mut list_state);
+    }
+
+    fn render_saved(&self, frame: &mut Frame, area: Rect, view: &SavedView) {
+        use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph};
+
+        let popup_area = self.centered_rect(70, 60, area);
+        frame.render_widget(Clear, popup_area);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" Saved ({}) ", self.saved_items.len()))
+            .title_bottom(" ↑↓ select · [Enter] open · [u] remove · [Esc] close ");
+        if self.saved_items.is_empty() {
+            frame.render_widget(
+                Paragraph::new("Nothing saved. Press S on a message to save it for later.")
+                    .block(block),
+                popup_area,
+            );
+            return;
+        }
+
+        let width = popup_area.width.saturating_sub(4) as usize;
+        let items: Vec<ListItem> = self
+            .saved_items
+            .iter()
+            .map(|item| {
+                let channel = match self.channels.iter().find(|ch| ch.id == item.channel_id) {
+                    Some(ch) if ch.is_dm => format!("@{}", ch.name),
+                    Some(ch) => format!("#{}", ch.name),
+                    None => item.channel_id.clone(),
+                };
+                let snippet = item.message.text.lines().next().unwrap_or_default();
+                let line = format!(
+                    "{:<16}  {:<12}  {}",
+                    sanitize_text(&channel),
+                    sanitize_text(&item.message.username),
+                    sanitize_text(snippet)
+                );
+                ListItem::new(truncate_display(&line, width).into_owned())
+            })
+            .collect();
+        let mut list_state = ListState::default();
+        list_state.select(Some(view.selected.min(items.len() - 1)));
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(self.theme.highlight)
+            .highlight_symbol("▶ ");
+        frame.render_stateful_widget(list, popup_area, &mut list_state);
+    }
+
+    fn render_threads(&self, frame: &mut Frame, area: Rect, view: &ThreadsView) {
+        use ratatui::style::{Modifier, Style};
+        use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph};
+
+        let threads = self
+            .active_workspace()
+            .map(|ws| ws.workspace.threads.as_slice())
+            .unwrap_or_default();
+        let popup_area = self.centered_rect(70, 60, area);
+        frame.render_widget(Clear, popup_area);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" Threads ({}) ", threads.len()))
+            .title_bottom(" ↑↓ select · [Enter] open · [u] unfollow · [Esc] close ");
+        if threads.is_empty() {
+            frame.render_widget(
+                Paragraph::new("No followed threads. Reply in a thread to follow it.").block(block),
+                popup_area,
+            );
+            return;
+        }
+
+        let width = popup_area.width.saturating_sub(4) as usize;
+        let items: Vec<ListItem> = threads
+            .iter()
+            .map(|thread| {
+                let channel = match self.channels.iter().find(|ch| ch.id == thread.channel_id) {
+                    Some(ch) if ch.is_dm => format!("@{}", ch.name),
+                    Some(ch) => format!("#{}", ch.name),
+                    None => thread.channel_id.clone(),
+                };
+                let author = thread
+                    .last_user
+                    .as_deref()
+                    .map(|id| self.user_display_name(id))
+                    .unwrap_or_default();
+                let snippet = thread.last_text.lines().next().unwrap_or_default();
+                let badge = if thread.unread > 0 {
+                    format!("({} new) ", thread.unread)
+                } else {
+                    String::new()
+                };
+                let line = format!(
+                    "{:<16}  {:<12}  {}{}",
+                    sanitize_text(&channel),
+                    sanitize_text(&author),
+                    badge,
+                    sanitize_text(snippet)
+                );
+                let item = ListItem::new(truncate_display(&line, width).into_owned());
+                if thread.unread > 0 {
+                    item.style(Style::default().add_modifier(Modifier::BOLD))
+                } else {
+                    item
+                }
+            })
+            .collect();
+        let mut list_state = ListState::default();
+        list_state.select(Some(view.selected.min(items.len() - 1)));
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(self.theme.highlight)
+            .highlight_symbol("▶ ");
+        frame.render_stateful_widget(list, popup_area, &mut list_state);
+    }
+
+    fn render_workspace_issues(&self, frame: &mut Frame, area: Rect, view: &WorkspaceIssuesView) {
+        use ratatui::style::{Modifier, Style};
+        use ratatui::text::{Line, Span};
+        use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState};
+
+        let popup_area = self.centered_rect(70, 50, area);
+        frame.render_widget(Clear, popup_area);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" Workspace issues ")
+            .title_bottom(
+                " ↑↓ select · [Enter/a] re-authenticate · [r] retry · [x] remove · [Esc] close ",
+            );
+        let items: Vec<ListItem> = self
+            .workspace_issues
+            .iter()
+            .map(|issue| {
+                ListItem::new(vec![
+                    Line::from(Span::styled(
+                        sanitize_text(&issue.team_name),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    )),
+                    Line::from(Span::styled(
+                        format!("  {}", issue.reason),
+                        self.theme.error,
+                    )),
+                ])
+            })
+            .collect();
+        let mut list_state = ListState::default();
+        if !items.is_empty() {
+            list_state.select(Some(view.selected.min(items.len() - 1)));
+        }
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(self.theme.highlight)
+            .highlight_symbol("▶ ");
+        frame.render_stateful_widget(list, popup_area, &mut list_state);
+    }
+
+    /// "in #general", or "in #general (thread)", for where an agent
+    /// command ran.
+    fn response_origin(resp: &AgentResponse) -> Option<String> {
+        let name = resp.channel_name.as_deref()?;
+        Some(match resp.thread_ts {
+            Some(_) => format!("in #{} (thread)", name),
+            None => format!("in #{}", name),
+        })
+    }
+
+    fn render_agent_history(&self, frame: &mut Frame, area: Rect, popup: &AgentHistoryPopup) {
+        use ratatui::layout::{Constraint, Direction, Layout};
+        use ratatui::text::{Line, Span};
+        use unicode_width::UnicodeWidthStr;
+
+        use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph};
+
+        let popup_area = self.centered_rect(70, 70, area);
+        frame.render_widget(Clear, popup_area);
+
+        let matches = popup.matches();
+        let title = if popup.loading {
+            format!(" Agent History — loading… — /{} ", popup.query)
+        } else {
+            format!(
+                " Agent History ({}/{}) — /{} ",
+                matches.len(),
+                popup.entries.len(),
+                popup.query
+            )
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .title_bottom(" [Enter] Open its channel  [Esc] Close ");
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(inner);
+
+        let width = chunks[0].width.saturating_sub(2) as usize;
+        let items: Vec<ListItem> = matches
+            .iter()
+            .map(|entry| {
+                let first_line = entry.response.lines().next().unwrap_or_default();
+                let head = format!(
+                    "{}  {}",
+                    entry.timestamp.format("%Y-%m-%d %H:%M"),
+                    entry.command
+                );
+                let origin = Self::response_origin(entry)
+                    .map(|origin| format!(" {}", origin))
+                    .unwrap_or_default();
+                let head_width = head.width() + origin.width();
+                let rest = truncate_display(first_line, width.saturating_sub(head_width + 2));
+                ListItem::new(Line::from(vec![
+                    Span::raw(head),
+                    Span::styled(origin, self.theme.dim),
+                    Span::raw(format!("  {}", rest)),
+                ]))
+            })
+            .collect();
+        let mut list_state = ListState::default();
+        if !items.is_empty() {
+            list_state.select(Some(popup.selected.min(items.len() - 1)));
+        }
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::BOTTOM))
+            .highlight_style(self.theme.highlight)
+            .highlight_symbol("▶ ");
+        frame.render_stateful_widget(list, chunks[0], &mut list_state);
+
+        let detail = matches
+            .get(popup.selected)
+            .map(|entry| {
+                let text_width = chunks[1].width.saturating_sub(1) as usize;
+                Self::wrap_plain_lines(&entry.response, text_width).join("\n")
+            })
+            .unwrap_or_else(|| "No matching responses. [Esc] to close".to_string());
+        frame.render_widget(Paragraph::new(detail), chunks[1]);
+    }
+
     fn centered_rect(&self, percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         let popup_layout = ratatui::layout::Layout::default()
             .direction(ratatui::layout::Direction::Vertical)
@@ -23,29 +23,91 @@ pub struct ConfirmationDialog {
     pub command: String,
     pub prompt: String,
     pub context_channel: Option<String>,
+    /// Typing goes to the prompt; Tab leaves it so `c` changes the target.
     pub is_editing: bool,
+    /// Where the reply would go, captured when the dialog opened.
+    pub target: Option<PostTarget>,
+    /// "author: first line" of the thread parent, when a thread is targeted.
+    pub thread_parent: Option<String>,
+    pub reply: ReplyTarget,
+    /// `zeroclaw.post_to_slack`; when off the reply only ever shows in the
+    /// panel.
+    pub can_post: bool,
+    /// Opened by `/again` or `.` rather than typed.
+    pub rerun: bool,
+}
+
+impl ConfirmationDialog {
+    /// Thread, channel, panel only; the thread is skipped when there is
+    /// none and posting is never offered when it is turned off.
+    pub fn cycle_reply(&mut self) {
+        let has_thread = self
+            .target
+            .as_ref()
+            .is_some_and(|target| target.thread_ts.is_some());
+        self.reply = match self.reply {
+            _ if !self.can_post || self.target.is_none() => ReplyTarget::PanelOnly,
+            ReplyTarget::Thread => ReplyTarget::Channel,
+            ReplyTarget::Channel => ReplyTarget::PanelOnly,
+            ReplyTarget::PanelOnly if has_thread => ReplyTarget::Thread,
+            ReplyTarget::PanelOnly => ReplyTarget::Channel,
+        };
+    }
+
+    /// The target the command is dispatched with, and whether the reply is
+    /// posted there or kept in the panel.
+    pub fn chosen_target(&self) -> (Option<PostTarget>, bool) {
+        let mut target = self.target.clone();
+        if self.reply != ReplyTarget::Thread {
+            if let Some(target) = target.as_mut() {
+                target.thread_ts = None;
+            }
+        }
+        (target, self.reply != ReplyTarget::PanelOnly)
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct ChannelPicker {
     pub query: String,
-    pub filtered_channels: Vec<Channel>,
+    pub filtered_channels: Vec<PickerEntry>,
     pub selected_index: usize,
     pub trigger_position: usize,
+    /// Tab in the Ctrl+K switcher searches every loaded workspace.
+    pub all_workspaces: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct PickerEntry {
+    pub channel: Channel,
+    /// Workspace the channel lives in when it is not the active one.
+    pub team_id: Option<String>,
 }
 
 pub struct App {
     pub should_quit: bool,
     pub session: Option<Session>,
     pub config: Config,
+    /// Where panel toggles are saved; unset in tests.
+    pub config_path: Option<std::path::PathBuf>,
     pub workspaces: Vec<WorkspaceState>,
-    pub active_workspace: usize,
+    /// `team_id` of the workspace on screen; positions shift as workspaces load.
+    pub active_workspace_id: Option<String>,
+    /// Row highlighted in the Ctrl+W picker, separate from the active workspace.
+    pub workspace_picker_cursor: usize,
     pub layout: LayoutState,
     pub input: InputState,
     pub keybinds: Keybinds,
+    pub theme: Theme,
     pub agent_runner: Option<AgentRunner>,
     pub agent_status: AgentStatus,
+    pub zeroclaw_bearer_rejected: bool,
+    pub zeroclaw_restart_attempts: u32,
+    pub zeroclaw_connected_at: Option<Instant>,
     pub agent_responses: VecDeque<AgentResponse>,
+    pub agent_history: Option<AgentHistoryPopup>,
+    pub agent_logs: Option<Vec<String>>,
+    pub pairing_prompt: Option<PairingPrompt>,
     pub messages: HashMap<String, VecDeque<Message>>,
     pub threads: HashMap<String, Vec<Thread>>,
     pub scroll_offset: usize,
@@ -56,29 +118,172 @@ pub struct App {
     pub search_query: String,
     pub drag_target: Option<DragTarget>,
     pub last_mouse_pos: (u16, u16),
-    pub slack_api: SlackApi,
+    pub slack_api: Arc<dyn SlackClient>,
     pub event_tx: Option<mpsc::UnboundedSender<SlackEvent>>,
     pub event_rx: Option<mpsc::UnboundedReceiver<SlackEvent>>,
     pub app_async_tx: Option<mpsc::UnboundedSender<AppAsyncEvent>>,
     pub app_async_rx: Option<mpsc::UnboundedReceiver<AppAsyncEvent>>,
+    /// Runs everything sent back through `app_async_tx`.
+    pub tasks: TaskSupervisor,
     pub channels: Vec<Channel>,
-    pub selected_channel: Option<usize>,
+    /// Id of the open channel; resolve to a position with `selected_channel_index`.
+    pub selected_channel_id: Option<String>,
     pub active_threads: HashMap<String, String>,
     pub agent_processing: bool,
     pub loading_start_time: Option<Instant>,
     pub loading_command: Option<String>,
+    /// The running command is a re-run, shown with `↻`.
+    pub loading_rerun: bool,
+    pub loading_timeout_secs: Option<u64>,
+    pub streaming_response: String,
+    pub pending_translation: Option<String>,
+    /// Set while a `/draft` runs; its reply goes into the input, not Slack.
+    pub pending_draft: bool,
+    /// What the input held before an agent draft replaced it; Esc in the
+    /// input puts it back.
+    pub stashed_input: Option<String>,
+    pub translations: HashMap<String, String>,
+    /// The splash is up: startup has not yet produced the active
+    /// workspace's channels.
     pub is_loading: bool,
     pub loading_message: String,
-    pub typing_users: HashMap<String, Vec<String>>,
+    /// Splash rows in the order they were first reported.
+    pub init_stages: Vec<(InitStage, StageStatus)>,
+    /// Users typing per channel id, with when each was last seen typing.
+    /// Channel ids are unique across workspaces, so one map serves them all.
+    pub typing_users: HashMap<String, Vec<(String, Instant)>>,
+    /// Open typing connections by team id; each takes channel ids to send
+    /// typing events for.
+    pub typing_senders: HashMap<String, mpsc::UnboundedSender<String>>,
+    /// Team ids whose token cannot send typing events.
+    pub typing_unsupported: HashSet<String>,
     pub context_menu: Option<ContextMenu>,
     pub selected_message: Option<(String, String)>,
     pub edit_message: Option<EditState>,
     pub message_filter: MessageFilter,
     pub show_jump_to_time: bool,
+    pub show_channel_info: bool,
+    pub raw_inspector: Option<RawInspector>,
+    pub reminders: Option<RemindersPopup>,
+    /// `o` on a message with several links: which one to open.
+    pub link_chooser: Option<LinkChooser>,
+    /// Saved-for-later messages in the active workspace, newest first.
+    pub saved_items: Vec<SavedItem>,
+    /// The pinned entry above the channels the sidebar cursor is on.
+    pub sidebar_pinned: Option<PinnedEntry>,
+    pub saved_view: Option<SavedView>,
+    pub threads_view: Option<ThreadsView>,
+    /// Workspaces whose stored token failed `auth.test`; they stay out of
+    /// the way until re-authenticated or removed.
+    pub workspace_issues: Vec<WorkspaceIssue>,
+    pub workspace_issues_view: Option<WorkspaceIssuesView>,
+    /// Saved message or followed thread to open once its channel's
+    /// history arrives.
+    pub pending_jump: Option<JumpTarget>,
+    pub context_anchor: Option<ContextAnchor>,
+    /// Channels showing a context window that stops short of the present,
+    /// with the ts of the last message before the part not loaded yet.
+    pub newer_history: HashMap<String, String>,
+    pub confirm_mark_all_read: bool,
+    /// Agent command typed while ZeroClaw was offline, awaiting the
+    /// "connect now?" answer.
+    pub agent_offline_prompt: Option<String>,
+    pub send_recovery: Option<SendRecoveryPrompt>,
+    /// An over-long post waiting on the split-or-upload dialog.
+    pub long_message: Option<PendingSend>,
+    /// Each workspace's shared user directory and its change feed.
+    pub user_directories: HashMap<String, (Arc<UserDirectory>, watch::Receiver<u64>)>,
+    /// Id for the next local copy of a post.
+    pub next_local_id: u64,
+    /// Posts whose local copy shows as failed, for Enter to send again.
+    pub failed_sends: HashMap<u64, PendingSend>,
+    /// Agent command to run once ZeroClaw connects.
+    pub pending_agent_command: Option<String>,
+    /// The "sent as a plain message" notice for offline mentions was shown.
+    pub offline_mention_noticed: bool,
+    /// Last key press or mouse event, for `[presence] keep_active`.
+    pub last_input_at: Instant,
+    pub presence_pinged_at: Option<Instant>,
+    /// Set by `/away`; no activity pings until `/active`.
+    pub presence_away: bool,
+    /// Team ids whose channel list is still being fetched.
+    pub channels_loading: HashSet<String>,
+    pub command_palette: Option<CommandPalette>,
+    pub message_rows: MessageRowMap,
+    /// Wrap drawn links in OSC 8 so the terminal makes them clickable.
+    pub hyperlinks: bool,
+    /// Queue mentions for the terminal to show as notifications.
+    pub terminal_notifications: bool,
+    /// From focus reports; a terminal that sends none counts as focused.
+    pub terminal_focused: bool,
+    /// Messages and mentions that arrived while the terminal was in the
+    /// background, for the window title.
+    pub away_unread: u32,
+    pub away_mentions: u32,
+    /// Title and body of each notification not yet written out; the event
+    /// loop drains them between frames.
+    pub pending_notifications: Vec<(String, String)>,
+    /// Links drawn in the message list by the last render.
+    pub screen_links: Vec<ScreenLink>,
+    /// Channels whose `conversations.info` has been requested this session.
+    pub channel_info_requested: HashSet<String>,
+    /// `ts` of the newest message known per channel id, across workspaces;
+    /// written back to the channel cache as `latest_ts`.
+    pub channel_activity: HashMap<String, String>,
+    /// Users whose `users.info` has been requested this session.
+    pub user_info_requested: HashSet<String>,
     pub jump_to_time_buffer: String,
-    pub show_user_filter: bool,
+    pub jump_to_time_error: Option<String>,
+    pub user_filter_picker: Option<UserFilterPicker>,
+    pub reaction_picker: Option<ReactionPicker>,
+    /// Agent commands run per channel id, newest first, for `/again`.
+    pub agent_command_history: HashMap<String, VecDeque<String>>,
+    /// Count typed before `.` in the messages panel; while set, the
+    /// channel's recent agent commands are listed to pick from.
+    pub rerun_count: Option<usize>,
     pub last_error: Option<String>,
     pub show_error_details: bool,
+    /// Newest first.
+    pub error_history: VecDeque<ErrorEntry>,
+    /// A confirmation toasted until the instant, e.g. after `/join`; not
+    /// kept in the error history.
+    pub notice: Option<(String, Instant)>,
+    pub error_history_scroll: usize,
+    /// Channels with a page of older history being fetched for scroll-back.
+    pub older_history_loading: HashSet<String>,
+    /// Channels whose history has been fetched back to the first message.
+    pub history_exhausted: HashSet<String>,
+    pub show_memory_stats: bool,
+    /// Mouse capture is off so the terminal can select text; the next key
+    /// turns it back on.
+    pub copy_mode: bool,
+    /// Channel id and anchor ts of a `v` selection, which runs from the
+    /// anchor to the message under the cursor.
+    pub visual_selection: Option<(String, String)>,
+    /// Socket state by team id.
+    pub socket_status: HashMap<String, SocketStatus>,
+    pub connection_status: Option<ConnectionStatusPanel>,
+    /// Channels to re-sync when next opened, after their socket reconnected.
+    pub pending_catch_up: HashMap<String, HistoryGap>,
+    /// Channels with a catch-up fetch in flight.
+    pub catching_up: HashSet<String>,
+    /// Where each channel's last connection gap was, as the ts of the last
+    /// message before it; the messages panel draws a divider there.
+    pub history_gaps: HashMap<String, String>,
+    /// Slack Connect channels already warned about this session.
+    pub external_warned: HashSet<String>,
+    /// Channel whose external-sharing banner is showing.
+    pub external_banner: Option<String>,
+    /// Open channel a refresh no longer lists; it stays, with a banner,
+    /// until another channel is opened.
+    pub removed_channel: Option<String>,
+    /// Last channel list fetch, for `ui.channel_refresh_minutes`.
+    pub channels_refreshed_at: Instant,
+    /// Team ids whose channel list is being fetched again.
+    pub channels_refreshing: HashSet<String>,
+    pub show_archived: bool,
+    /// Team ids whose archived channels have been fetched.
+    pub archived_loaded: HashSet<String>,
     pub confirmation_dialog: Option<ConfirmationDialog>,
     pub channel_picker: Option<ChannelPicker>,
     pub focus: Focus,
@@ -94,21 +299,43 @@ impl Default for App {
 
 impl App {
     pub fn new(config: Config) -> Self {
+        slack_zc_slack::types::set_keep_raw(config.debug.keep_raw);
+        let api = Self::build_slack_api(&config);
+        Self::with_slack_client(config, Arc::new(api))
+    }
+
+    /// Builds the app around `slack_api`; tests pass a fake client here.
+    pub fn with_slack_client(config: Config, slack_api: Arc<dyn SlackClient>) -> Self {
         let (event_tx, event_rx) = mpsc::unbounded_channel();
         let (app_async_tx, app_async_rx) = mpsc::unbounded_channel();
+        let mut layout = LayoutState::default();
+        layout.set_sidebar_visible(config.ui.show_sidebar);
+        layout.set_agent_visible(config.ui.show_agent_panel);
+        let theme = Theme::resolve(config.ui.theme.as_deref(), || None);
+        let show_archived = config.ui.show_archived;
+        let show_system = config.ui.show_system_messages;
 
         Self {
             should_quit: false,
             session: None,
             config,
+            config_path: None,
             workspaces: Vec::new(),
-            active_workspace: 0,
-            layout: LayoutState::default(),
+            active_workspace_id: None,
+            workspace_picker_cursor: 0,
+            layout,
             input: InputState::new(),
             keybinds: Keybinds,
+            theme,
             agent_runner: None,
             agent_status: AgentStatus::Unavailable,
+            zeroclaw_bearer_rejected: false,
+            zeroclaw_restart_attempts: 0,
+            zeroclaw_connected_at: None,
             agent_responses: VecDeque::new(),
+            agent_history: None,
+            agent_logs: None,
+            pairing_prompt: None,
             messages: HashMap::new(),
             threads: HashMap::new(),
             scroll_offset: 0,
@@ -119,29 +346,105 @@ impl App {
             search_query: String::new(),
             drag_target: None,
             last_mouse_pos: (0, 0),
-            slack_api: SlackApi::new(),
+            slack_api,
             event_tx: Some(event_tx),
             event_rx: Some(event_rx),
             app_async_tx: Some(app_async_tx),
             app_async_rx: Some(app_async_rx),
+            tasks: TaskSupervisor::new(),
             channels: Vec::new(),
-            selected_channel: None,
+            selected_channel_id: None,
             active_threads: HashMap::new(),
             agent_processing: false,
             loading_start_time: None,
             loading_command: None,
+            loading_rerun: false,
+            loading_timeout_secs: None,
+            streaming_response: String::new(),
+            pending_translation: None,
+            pending_draft: false,
+            stashed_input: None,
+            translations: HashMap::new(),
             is_loading: true,
-            loading_message: "Loading...".to_string(),
+            loading_message: "Starting slack-zc…".to_string(),
+            init_stages: vec![(InitStage::Session, StageStatus::Running)],
             typing_users: HashMap::new(),
+            typing_senders: HashMap::new(),
+            typing_unsupported: HashSet::new(),
             context_menu: None,
             selected_message: None,
             edit_message: None,
-            message_filter: MessageFilter::default(),
+            message_filter: MessageFilter {
+                show_system,
+                ..MessageFilter::default()
+            },
             show_jump_to_time: false,
+            show_channel_info: false,
+            raw_inspector: None,
+            reminders: None,
+            link_chooser: None,
+            saved_items: Vec::new(),
+            sidebar_pinned: None,
+            saved_view: None,
+            threads_view: None,
+            workspace_issues: Vec::new(),
+            workspace_issues_view: None,
+            pending_jump: None,
+            context_anchor: None,
+            newer_history: HashMap::new(),
+            confirm_mark_all_read: false,
+            agent_offline_prompt: None,
+            send_recovery: None,
+            long_message: None,
+            user_directories: HashMap::new(),
+            next_local_id: 1,
+            failed_sends: HashMap::new(),
+            pending_agent_command: None,
+            offline_mention_noticed: false,
+            last_input_at: Instant::now(),
+            presence_pinged_at: None,
+            presence_away: false,
+            channels_loading: HashSet::new(),
+            command_palette: None,
+            message_rows: MessageRowMap::default(),
+            hyperlinks: false,
+            terminal_notifications: false,
+            terminal_focused: true,
+            away_unread: 0,
+            away_mentions: 0,
+            pending_notifications: Vec::new(),
+            screen_links: Vec::new(),
+            channel_info_requested: HashSet::new(),
+            channel_activity: HashMap::new(),
+            user_info_requested: HashSet::new(),
             jump_to_time_buffer: String::new(),
-            show_user_filter: false,
+            jump_to_time_error: None,
+            user_filter_picker: None,
+            reaction_picker: None,
+            agent_command_history: HashMap::new(),
+            rerun_count: None,
             last_error: None,
+            notice: None,
             show_error_details: false,
+            error_history: VecDeque::new(),
+            error_history_scroll: 0,
+            older_history_loading: HashSet::new(),
+            history_exhausted: HashSet::new(),
+            show_memory_stats: false,
+            copy_mode: false,
+            visual_selection: None,
+            socket_status: HashMap::new(),
+            connection_status: None,
+            pending_catch_up: HashMap::new(),
+            catching_up: HashSet::new(),
+            history_gaps: HashMap::new(),
+            external_warned: HashSet::new(),
+            external_banner: None,
+            removed_channel: None,
+            channels_refreshed_at: Instant::now(),
+            channels_refreshing: HashSet::new(),
+            show_archived,
+            archived_loaded: HashSet::new(),
             confirmation_dialog: None,
             channel_picker: None,
             focus: Focus::Sidebar,
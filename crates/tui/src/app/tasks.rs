@@ -0,0 +1,249 @@
+//! Background work started by the app: a concurrency limit per kind,
+//! counts for the connection panel, generations so results for a workspace
+//! left behind are dropped, and a clean stop on shutdown.
+
+use super::AppAsyncEvent;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{mpsc, Semaphore};
+use tokio::task::JoinHandle;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskKind {
+    /// Channel, thread and context history loads.
+    History,
+    /// Posts, edits, deletes and reactions. Never aborted: shutdown waits
+    /// for them.
+    Send,
+    /// ZeroClaw commands and mentions.
+    Agent,
+    Other,
+}
+
+impl TaskKind {
+    pub const ALL: [TaskKind; 4] = [Self::History, Self::Send, Self::Agent, Self::Other];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::History => "history",
+            Self::Send => "sends",
+            Self::Agent => "agent",
+            Self::Other => "other",
+        }
+    }
+
+    /// Most tasks of the kind running at once; the rest wait their turn.
+    fn limit(self) -> Option<usize> {
+        match self {
+            Self::History => Some(4),
+            Self::Agent => Some(2),
+            Self::Send | Self::Other => None,
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+#[derive(Default)]
+struct Counts {
+    running: AtomicUsize,
+    waiting: AtomicUsize,
+}
+
+/// Holds one in `running` or `waiting` for as long as it lives, so an
+/// aborted task still leaves the counts right.
+struct Counted(Arc<[Counts; 4]>, usize, fn(&Counts) -> &AtomicUsize);
+
+impl Counted {
+    fn new(counts: &Arc<[Counts; 4]>, kind: TaskKind, field: fn(&Counts) -> &AtomicUsize) -> Self {
+        field(&counts[kind.index()]).fetch_add(1, Ordering::Relaxed);
+        Self(counts.clone(), kind.index(), field)
+    }
+}
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        (self.2)(&self.0[self.1]).fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+pub struct TaskSupervisor {
+    limits: [Option<Arc<Semaphore>>; 4],
+    counts: Arc<[Counts; 4]>,
+    handles: Mutex<Vec<(TaskKind, JoinHandle<()>)>>,
+    generation: AtomicU64,
+}
+
+impl Default for TaskSupervisor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TaskSupervisor {
+    pub fn new() -> Self {
+        Self {
+            limits: TaskKind::ALL.map(|kind| kind.limit().map(|n| Arc::new(Semaphore::new(n)))),
+            counts: Arc::default(),
+            handles: Mutex::default(),
+            generation: AtomicU64::new(0),
+        }
+    }
+
+    /// Runs `future` once its kind has room, then hands its event to `tx`.
+    pub fn spawn<F>(&self, kind: TaskKind, tx: mpsc::UnboundedSender<AppAsyncEvent>, future: F)
+    where
+        F: Future<Output = AppAsyncEvent> + Send + 'static,
+    {
+        let limit = self.limits[kind.index()].clone();
+        let waiting = Counted::new(&self.counts, kind, |c| &c.waiting);
+        let counts = self.counts.clone();
+        let handle = tokio::spawn(async move {
+            let _permit = match limit {
+                Some(limit) => limit.acquire_owned().await.ok(),
+                None => None,
+            };
+            drop(waiting);
+            let _running = Counted::new(&counts, kind, |c| &c.running);
+            let _ = tx.send(future.await);
+        });
+        let mut handles = self.handles.lock().unwrap_or_else(|e| e.into_inner());
+        handles.retain(|(_, handle)| !handle.is_finished());
+        handles.push((kind, handle));
+    }
+
+    /// The generation results are stamped with; see [`Self::invalidate`].
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Relaxed)
+    }
+
+    /// Drops every stamped result not yet applied, e.g. on leaving a
+    /// workspace.
+    pub fn invalidate(&self) {
+        self.generation.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Unwraps a stamped event, or `None` when it is from an older
+    /// generation.
+    pub fn accept(&self, event: AppAsyncEvent) -> Option<AppAsyncEvent> {
+        match event {
+            AppAsyncEvent::Stamped { generation, event } => {
+                if generation == self.generation() {
+                    Some(*event)
+                } else {
+                    tracing::debug!("Dropping a result from task generation {}", generation);
+                    None
+                }
+            }
+            event => Some(event),
+        }
+    }
+
+    /// Running and waiting tasks of each kind.
+    pub fn counts(&self) -> Vec<(TaskKind, usize, usize)> {
+        TaskKind::ALL
+            .iter()
+            .map(|&kind| {
+                let counts = &self.counts[kind.index()];
+                (
+                    kind,
+                    counts.running.load(Ordering::Relaxed),
+                    counts.waiting.load(Ordering::Relaxed),
+                )
+            })
+            .collect()
+    }
+
+    /// Aborts every task except sends, which are left to finish.
+    pub fn abort_all(&self) {
+        let mut handles = self.handles.lock().unwrap_or_else(|e| e.into_inner());
+        handles.retain(|(kind, handle)| {
+            if *kind != TaskKind::Send {
+                handle.abort();
+            }
+            *kind == TaskKind::Send && !handle.is_finished()
+        });
+    }
+
+    /// Waits up to `grace` for sends still in flight.
+    pub async fn drain_sends(&self, grace: Duration) {
+        let sends: Vec<JoinHandle<()>> = {
+            let mut handles = self.handles.lock().unwrap_or_else(|e| e.into_inner());
+            let (sends, rest) = std::mem::take(&mut *handles)
+                .into_iter()
+                .partition(|(kind, _)| *kind == TaskKind::Send);
+            *handles = rest;
+            sends.into_iter().map(|(_, handle)| handle).collect()
+        };
+        if sends.is_empty() {
+            return;
+        }
+        if tokio::time::timeout(grace, futures::future::join_all(sends))
+            .await
+            .is_err()
+        {
+            tracing::warn!("Exiting with sends still in flight after {:?}", grace);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn idle() -> AppAsyncEvent {
+        AppAsyncEvent::PresenceSet {
+            manual: false,
+            error: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn limits_kinds_and_drains_sends_on_abort() {
+        let tasks = TaskSupervisor::new();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        for _ in 0..6 {
+            tasks.spawn(TaskKind::History, tx.clone(), async {
+                std::future::pending::<()>().await;
+                idle()
+            });
+        }
+        tasks.spawn(TaskKind::Send, tx.clone(), async {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            idle()
+        });
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        let counts = tasks.counts();
+        assert_eq!(counts[0], (TaskKind::History, 4, 2));
+        assert_eq!(counts[1], (TaskKind::Send, 1, 0));
+
+        tasks.abort_all();
+        tasks.drain_sends(Duration::from_secs(1)).await;
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert!(tasks
+            .counts()
+            .iter()
+            .all(|&(_, running, waiting)| running + waiting == 0));
+        // Only the send got to report.
+        assert!(rx.try_recv().is_ok());
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn results_from_an_older_generation_are_dropped() {
+        let tasks = TaskSupervisor::new();
+        let stamped = |generation| AppAsyncEvent::Stamped {
+            generation,
+            event: Box::new(idle()),
+        };
+        assert!(tasks.accept(stamped(tasks.generation())).is_some());
+        let before = tasks.generation();
+        tasks.invalidate();
+        assert!(tasks.accept(stamped(before)).is_none());
+        assert!(tasks.accept(idle()).is_some());
+    }
+}
@@ -0,0 +1,462 @@
+//! In-memory Slack client and fixtures for driving `App` without a network.
+
+use super::App;
+use crate::Config;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use slack_zc_slack::types::{
+    Channel, Message, Reminder, SavedItem, User, Workspace, WorkspaceState,
+};
+use slack_zc_slack::SlackClient;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+pub const CHANNEL: &str = "C_GENERAL";
+pub const ME: &str = "U_ME";
+/// Listed by `list_channels_page` only when archived channels are asked for.
+pub const ARCHIVED_CHANNEL: &str = "C_OLD";
+
+/// Records every call as `"method arg…"` and serves history from memory.
+/// Sent messages are appended to the channel so a refetch sees them.
+#[derive(Default)]
+pub struct MockSlackClient {
+    calls: Mutex<Vec<String>>,
+    history: Mutex<HashMap<String, Vec<Message>>>,
+    failing: Mutex<HashMap<&'static str, &'static str>>,
+    reminders: Mutex<Vec<Reminder>>,
+    saved: Mutex<Vec<SavedItem>>,
+    channel_info: Mutex<HashMap<String, Channel>>,
+    listed: Mutex<Vec<Channel>>,
+}
+
+impl MockSlackClient {
+    pub fn with_history(self, channel_id: &str, messages: Vec<Message>) -> Self {
+        self.history
+            .lock()
+            .unwrap()
+            .insert(channel_id.to_string(), messages);
+        self
+    }
+
+    /// Lists `channel` after the general one from now on, as if it had
+    /// been created or joined elsewhere.
+    pub fn list_channel(&self, channel: Channel) {
+        self.listed.lock().unwrap().push(channel);
+    }
+
+    /// What `get_channel_info` returns for the channel from now on.
+    pub fn set_channel_info(&self, info: Channel) {
+        self.channel_info
+            .lock()
+            .unwrap()
+            .insert(info.id.clone(), info);
+    }
+
+    /// Makes every later call to `method` fail.
+    pub fn fail(&self, method: &'static str) {
+        self.fail_with(method, "channel_not_found");
+    }
+
+    /// Makes every later call to `method` fail with Slack's `error` code.
+    pub fn fail_with(&self, method: &'static str, error: &'static str) {
+        self.failing.lock().unwrap().insert(method, error);
+    }
+
+    /// Undoes [`MockSlackClient::fail`] for `method`.
+    pub fn recover(&self, method: &'static str) {
+        self.failing.lock().unwrap().remove(method);
+    }
+
+    pub fn calls(&self) -> Vec<String> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    fn record(&self, method: &'static str, args: &[&str]) -> Result<()> {
+        let mut call = method.to_string();
+        for arg in args {
+            call.push(' ');
+            call.push_str(arg);
+        }
+        self.calls.lock().unwrap().push(call);
+        if let Some(error) = self.failing.lock().unwrap().get(method) {
+            return Err(anyhow!("{method} failed: {error}"));
+        }
+        Ok(())
+    }
+
+    fn history_of(&self, channel_id: &str) -> Vec<Message> {
+        self.history
+            .lock()
+            .unwrap()
+            .get(channel_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn post(&self, channel_id: &str, text: &str, thread_ts: Option<&str>) -> String {
+        let mut history = self.history.lock().unwrap();
+        let messages = history.entry(channel_id.to_string()).or_default();
+        let ts = format!("{}.000100", 1_730_000_000 + messages.len());
+        let mut posted = message(&ts, ME, text);
+        posted.thread_ts = thread_ts.map(str::to_string);
+        messages.push(posted);
+        ts
+    }
+}
+
+#[async_trait]
+impl SlackClient for MockSlackClient {
+    async fn test_auth(&self, token: &str) -> Result<(String, String, String)> {
+        self.record("test_auth", &[token])?;
+        Ok(("T_TEST".into(), "test".into(), ME.into()))
+    }
+
+    async fn list_channels_page(
+        &self,
+        token: &str,
+        _cursor: Option<&str>,
+        include_archived: bool,
+    ) -> Result<(Vec<Channel>, Option<String>)> {
+        self.record("list_channels_page", &[token])?;
+        let mut channels = vec![channel(CHANNEL, "general")];
+        channels.extend(self.listed.lock().unwrap().iter().cloned());
+        if include_archived {
+            let mut archived = channel(ARCHIVED_CHANNEL, "old-incident");
+            archived.is_archived = true;
+            channels.push(archived);
+        }
+        Ok((channels, None))
+    }
+
+    async fn list_dms_page(
+        &self,
+        token: &str,
+        _cursor: Option<&str>,
+    ) -> Result<(Vec<Channel>, Option<String>)> {
+        self.record("list_dms_page", &[token])?;
+        Ok((Vec::new(), None))
+    }
+
+    async fn list_channels(&self, token: &str) -> Result<Vec<Channel>> {
+        self.record("list_channels", &[token])?;
+        Ok(vec![channel(CHANNEL, "general")])
+    }
+
+    async fn get_channel_info(&self, _token: &str, channel_id: &str) -> Result<Channel> {
+        self.record("get_channel_info", &[channel_id])?;
+        let info = self.channel_info.lock().unwrap().get(channel_id).cloned();
+        Ok(info.unwrap_or_else(|| channel(channel_id, "general")))
+    }
+
+    async fn get_history(
+        &self,
+        _token: &str,
+        channel_id: &str,
+        _limit: u32,
+    ) -> Result<Vec<Message>> {
+        self.record("get_history", &[channel_id])?;
+        Ok(self.history_of(channel_id))
+    }
+
+    async fn get_history_between(
+        &self,
+        _token: &str,
+        channel_id: &str,
+        _oldest: Option<&str>,
+        _latest: Option<&str>,
+        _limit: u32,
+    ) -> Result<Vec<Message>> {
+        self.record("get_history_between", &[channel_id])?;
+        Ok(self.history_of(channel_id))
+    }
+
+    async fn get_history_around(
+        &self,
+        _token: &str,
+        channel_id: &str,
+        ts: &str,
+        before: u32,
+        after: u32,
+    ) -> Result<Vec<Message>> {
+        self.record("get_history_around", &[channel_id, ts])?;
+        let history = self.history_of(channel_id);
+        let split = history.partition_point(|m| m.ts.as_str() <= ts);
+        let start = split.saturating_sub(before as usize + 1);
+        let end = (split + after as usize).min(history.len());
+        Ok(history[start..end].to_vec())
+    }
+
+    async fn send_message(&self, _token: &str, channel_id: &str, text: &str) -> Result<String> {
+        self.record("send_message", &[channel_id, text])?;
+        Ok(self.post(channel_id, text, None))
+    }
+
+    async fn send_message_to_thread(
+        &self,
+        _token: &str,
+        channel_id: &str,
+        text: &str,
+        thread_ts: &str,
+    ) -> Result<String> {
+        self.record("send_message_to_thread", &[channel_id, thread_ts, text])?;
+        Ok(self.post(channel_id, text, Some(thread_ts)))
+    }
+
+    async fn update_message(
+        &self,
+        _token: &str,
+        channel_id: &str,
+        ts: &str,
+        text: &str,
+    ) -> Result<()> {
+        self.record("update_message", &[channel_id, ts, text])
+    }
+
+    async fn delete_message(&self, _token: &str, channel_id: &str, ts: &str) -> Result<()> {
+        self.record("delete_message", &[channel_id, ts])
+    }
+
+    async fn add_reaction(
+        &self,
+        _token: &str,
+        channel_id: &str,
+        ts: &str,
+        reaction: &str,
+    ) -> Result<()> {
+        self.record("add_reaction", &[channel_id, ts, reaction])
+    }
+
+    async fn remove_reaction(
+        &self,
+        _token: &str,
+        channel_id: &str,
+        ts: &str,
+        reaction: &str,
+    ) -> Result<()> {
+        self.record("remove_reaction", &[channel_id, ts, reaction])
+    }
+
+    async fn mark_read(&self, _token: &str, channel_id: &str, ts: &str) -> Result<()> {
+        self.record("mark_read", &[channel_id, ts])
+    }
+
+    async fn join_channel(&self, _token: &str, channel_id: &str) -> Result<()> {
+        self.record("join_channel", &[channel_id])
+    }
+
+    async fn leave_channel(&self, _token: &str, channel_id: &str) -> Result<()> {
+        self.record("leave_channel", &[channel_id])
+    }
+
+    async fn set_channel_topic(&self, _token: &str, channel_id: &str, topic: &str) -> Result<()> {
+        self.record("set_channel_topic", &[channel_id, topic])
+    }
+
+    async fn add_reminder(&self, _token: &str, text: &str, time: i64) -> Result<String> {
+        self.record("add_reminder", &[text, &time.to_string()])?;
+        let mut reminders = self.reminders.lock().unwrap();
+        let id = format!("Rm{}", reminders.len() + 1);
+        reminders.push(Reminder {
+            id: id.clone(),
+            text: text.to_string(),
+            time: chrono::DateTime::from_timestamp(time, 0),
+            recurring: false,
+            complete: false,
+        });
+        Ok(id)
+    }
+
+    async fn list_reminders(&self, token: &str) -> Result<Vec<Reminder>> {
+        self.record("list_reminders", &[token])?;
+        Ok(self.reminders.lock().unwrap().clone())
+    }
+
+    async fn delete_reminder(&self, _token: &str, reminder_id: &str) -> Result<()> {
+        self.record("delete_reminder", &[reminder_id])?;
+        self.reminders
+            .lock()
+            .unwrap()
+            .retain(|r| r.id != reminder_id);
+        Ok(())
+    }
+
+    async fn set_presence(&self, _token: &str, presence: &str) -> Result<()> {
+        self.record("set_presence", &[presence])
+    }
+
+    async fn save_message(&self, _token: &str, channel_id: &str, ts: &str) -> Result<()> {
+        self.record("save_message", &[channel_id, ts])?;
+        let found = self.history_of(channel_id).into_iter().find(|m| m.ts == ts);
+        if let Some(message) = found {
+            self.saved.lock().unwrap().insert(
+                0,
+                SavedItem {
+                    channel_id: channel_id.to_string(),
+                    message,
+                },
+            );
+        }
+        Ok(())
+    }
+
+    async fn unsave_message(&self, _token: &str, channel_id: &str, ts: &str) -> Result<()> {
+        self.record("unsave_message", &[channel_id, ts])?;
+        self.saved
+            .lock()
+            .unwrap()
+            .retain(|item| !(item.channel_id == channel_id && item.message.ts == ts));
+        Ok(())
+    }
+
+    async fn list_saved(&self, token: &str) -> Result<Vec<SavedItem>> {
+        self.record("list_saved", &[token])?;
+        Ok(self.saved.lock().unwrap().clone())
+    }
+
+    async fn upload_snippet(
+        &self,
+        _token: &str,
+        channel_id: &str,
+        _thread_ts: Option<&str>,
+        filename: &str,
+        _content: &str,
+    ) -> Result<String> {
+        self.record("upload_snippet", &[channel_id, filename])?;
+        Ok("F_SNIPPET".to_string())
+    }
+
+    async fn get_thread_replies(
+        &self,
+        _token: &str,
+        channel_id: &str,
+        thread_ts: &str,
+    ) -> Result<Vec<Message>> {
+        self.record("get_thread_replies", &[channel_id, thread_ts])?;
+        Ok(self
+            .history_of(channel_id)
+            .into_iter()
+            .filter(|m| m.ts == thread_ts || m.thread_ts.as_deref() == Some(thread_ts))
+            .collect())
+    }
+
+    async fn list_users(&self, token: &str) -> Result<Vec<User>> {
+        self.record("list_users", &[token])?;
+        Ok(Vec::new())
+    }
+
+    async fn get_user(&self, _token: &str, user_id: &str) -> Result<User> {
+        self.record("get_user", &[user_id])?;
+        Ok(User {
+            id: user_id.to_string(),
+            name: user_id.to_lowercase(),
+            display_name: String::new(),
+            real_name: String::new(),
+            email: None,
+            is_admin: false,
+            is_owner: false,
+        })
+    }
+
+    async fn rtm_connect(&self, token: &str) -> Result<String> {
+        self.record("rtm_connect", &[token])?;
+        // Nothing listens here, so the typing connection fails to open.
+        Ok("ws://127.0.0.1:9/".to_string())
+    }
+
+    async fn team_icon(&self, token: &str) -> Result<Option<String>> {
+        self.record("team_icon", &[token])?;
+        Ok(None)
+    }
+}
+
+pub fn channel(id: &str, name: &str) -> Channel {
+    Channel {
+        id: id.to_string(),
+        name: name.to_string(),
+        is_dm: false,
+        is_group: false,
+        is_im: false,
+        is_mpim: false,
+        unread_count: 0,
+        mention_count: 0,
+        purpose: None,
+        topic: None,
+        user: None,
+        member_count: None,
+        created: None,
+        creator: None,
+        is_shared: false,
+        is_ext_shared: false,
+        is_org_shared: false,
+        is_archived: false,
+        last_read: None,
+        latest_ts: None,
+        is_member: None,
+        posting_restricted_to: None,
+        threads_restricted_to: None,
+    }
+}
+
+pub fn message(ts: &str, user_id: &str, text: &str) -> Message {
+    let secs = ts
+        .split('.')
+        .next()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0);
+    Message {
+        ts: ts.to_string(),
+        user_id: user_id.to_string(),
+        username: user_id.to_lowercase(),
+        text: text.to_string(),
+        thread_ts: None,
+        timestamp: chrono::DateTime::from_timestamp(secs, 0).unwrap_or_else(Utc::now),
+        is_agent: false,
+        is_bot: false,
+        reactions: Vec::new(),
+        is_edited: false,
+        is_deleted: false,
+        files: Vec::new(),
+        reply_count: None,
+        latest_reply: None,
+        reply_users: Vec::new(),
+        last_read: None,
+        rendered_blocks: Vec::new(),
+        is_system: false,
+        subtype: None,
+        call: None,
+        raw: None,
+        delivery: None,
+    }
+}
+
+/// An app signed in as [`ME`] with one workspace holding [`CHANNEL`].
+pub fn app_with(client: Arc<MockSlackClient>) -> App {
+    let mut app = App::with_slack_client(Config::default(), client);
+    let mut ws = WorkspaceState::new(Workspace {
+        team_id: "T_TEST".to_string(),
+        team_name: "test".to_string(),
+        xoxp_token: "xoxp-test".to_string(),
+        xapp_token: "xapp-test".to_string(),
+        user_id: Some(ME.to_string()),
+        active: true,
+        last_channel_id: None,
+        icon_url: None,
+        threads: Vec::new(),
+        muted_channels: Vec::new(),
+        recent_reactions: Vec::new(),
+    });
+    ws.channels.push(channel(CHANNEL, "general"));
+    app.workspaces.push(ws);
+    app.switch_workspace(0);
+    app
+}
+
+/// Lets spawned API tasks finish and applies their results.
+pub async fn settle(app: &mut App) {
+    for _ in 0..5 {
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        app.process_slack_events();
+    }
+}
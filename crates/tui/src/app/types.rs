@@ -1,15 +1,95 @@
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct AgentResponse {
     pub command: String,
     pub response: String,
     pub timestamp: chrono::DateTime<chrono::Utc>,
+    #[serde(default)]
+    pub channel_id: Option<String>,
+    #[serde(default)]
+    pub channel_name: Option<String>,
+    /// Thread the command ran in, when it targeted one.
+    #[serde(default)]
+    pub thread_ts: Option<String>,
+}
+
+/// Where an agent reply goes, fixed when the command is dispatched.
+#[derive(Debug, Clone)]
+pub struct PostTarget {
+    pub channel_id: String,
+    pub channel_name: String,
+    pub thread_ts: Option<String>,
+    pub token: String,
+}
+
+/// What the confirmation dialog does with the agent's reply; `c` cycles it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplyTarget {
+    Thread,
+    Channel,
+    PanelOnly,
+}
+
+/// A startup step listed on the splash; workspace steps carry the team id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InitStage {
+    Session,
+    Auth(String),
+    Channels(String),
+    Dms(String),
+    Socket(String),
+    Agent,
+}
+
+impl InitStage {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Session => "Unlock session",
+            Self::Auth(_) => "Check token",
+            Self::Channels(_) => "Load channels",
+            Self::Dms(_) => "Load DMs",
+            Self::Socket(_) => "Connect Socket Mode",
+            Self::Agent => "Start ZeroClaw",
+        }
+    }
+
+    pub fn team_id(&self) -> Option<&str> {
+        match self {
+            Self::Auth(team_id)
+            | Self::Channels(team_id)
+            | Self::Dms(team_id)
+            | Self::Socket(team_id) => Some(team_id),
+            Self::Session | Self::Agent => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StageStatus {
+    Pending,
+    Running,
+    Done,
+    Failed(String),
 }
 
 pub enum AppAsyncEvent {
+    /// A result that only applies while the task generation it was started
+    /// in is current; see `TaskSupervisor::invalidate`.
+    Stamped {
+        generation: u64,
+        event: Box<AppAsyncEvent>,
+    },
     SlackSendResult {
         context: String,
         channel_id: Option<String>,
         error: Option<String>,
+        /// Set on a failed post so it can be retried.
+        retry: Option<PendingSend>,
+        /// Set when the refusal has a fix to offer.
+        recovery: Option<SendRecovery>,
+        /// The local copy of the post this result belongs to.
+        local_id: Option<u64>,
+        /// The ts Slack gave an accepted post.
+        ts: Option<String>,
     },
     ChannelHistoryLoaded {
         channel_id: String,
@@ -22,8 +102,15 @@ pub enum AppAsyncEvent {
         replies: Vec<slack_zc_slack::types::Message>,
         error: Option<String>,
     },
+    AgentCommandChunk {
+        command: String,
+        delta: String,
+    },
     AgentCommandFinished {
         command: String,
+        channel_id: Option<String>,
+        channel_name: Option<String>,
+        thread_ts: Option<String>,
         response: Option<String>,
         error: Option<String>,
     },
@@ -31,6 +118,16 @@ pub enum AppAsyncEvent {
         workspace: Option<slack_zc_slack::types::Workspace>,
         error: Option<String>,
     },
+    /// A startup step moved on; drives the splash.
+    InitProgress {
+        stage: InitStage,
+        status: StageStatus,
+    },
+    /// A stored workspace's token failed `auth.test`.
+    WorkspaceAuthFailed {
+        team_id: String,
+        reason: String,
+    },
     WorkspaceChannelsLoaded {
         team_id: String,
         channels: Vec<slack_zc_slack::types::Channel>,
@@ -38,10 +135,314 @@ pub enum AppAsyncEvent {
         done: bool,
         error: Option<String>,
     },
+    /// Every channel and DM of a workspace, fetched again; `manual` when
+    /// asked for rather than on the timer.
+    ChannelListRefreshed {
+        team_id: String,
+        channels: Vec<slack_zc_slack::types::Channel>,
+        manual: bool,
+        error: Option<String>,
+    },
+    AgentHistoryLoaded {
+        entries: Vec<AgentResponse>,
+        error: Option<String>,
+    },
+    RemindersLoaded {
+        reminders: Vec<slack_zc_slack::types::Reminder>,
+        /// Reminder to put the cursor on, e.g. one just added.
+        select: Option<String>,
+        error: Option<String>,
+    },
+    ReminderAdded {
+        id: Option<String>,
+        error: Option<String>,
+    },
+    ReminderDeleted {
+        id: String,
+        error: Option<String>,
+    },
+    /// A `users.setPresence` call finished; `manual` for `/away` and
+    /// `/active`, whose failures are shown rather than only logged.
+    PresenceSet {
+        manual: bool,
+        error: Option<String>,
+    },
+    /// A `/join`, `/leave` or `/topic` call finished.
+    ChannelCommandDone {
+        team_id: String,
+        channel_id: String,
+        channel_name: String,
+        command: ChannelCommand,
+        error: Option<String>,
+    },
+    SavedLoaded {
+        team_id: String,
+        items: Vec<slack_zc_slack::types::SavedItem>,
+        error: Option<String>,
+    },
+    /// A save or unsave finished; on error the list is re-fetched to undo
+    /// the optimistic change.
+    SavedToggled {
+        error: Option<String>,
+    },
+    ChannelInfoLoaded {
+        channel_id: String,
+        info: Option<slack_zc_slack::types::Channel>,
+        error: Option<String>,
+    },
+    UserInfoLoaded {
+        team_id: String,
+        user_id: String,
+        user: Option<slack_zc_slack::types::User>,
+        error: Option<String>,
+    },
+    /// The typing connection for `team_id` closed; `unsupported` when
+    /// `rtm.connect` refused the token, so typing stays off for it.
+    TypingConnectionEnded {
+        team_id: String,
+        error: Option<String>,
+        unsupported: bool,
+    },
+    /// The conversation around a jump target.
+    ContextLoaded {
+        target: JumpTarget,
+        messages: Vec<slack_zc_slack::types::Message>,
+        error: Option<String>,
+    },
+    /// The page after `after_ts`, loaded while scrolling down from a
+    /// context window towards the present.
+    NewerHistoryLoaded {
+        channel_id: String,
+        after_ts: String,
+        messages: Vec<slack_zc_slack::types::Message>,
+        error: Option<String>,
+    },
+    HistoryWindowLoaded {
+        channel_id: String,
+        target: chrono::DateTime<chrono::Utc>,
+        messages: Vec<slack_zc_slack::types::Message>,
+        error: Option<String>,
+    },
+    ArchivedChannelsLoaded {
+        team_id: String,
+        channels: Vec<slack_zc_slack::types::Channel>,
+        error: Option<String>,
+    },
+    TeamIconLoaded {
+        team_id: String,
+        icon_url: Option<String>,
+    },
+    OlderHistoryLoaded {
+        channel_id: String,
+        messages: Vec<slack_zc_slack::types::Message>,
+        error: Option<String>,
+    },
+    ChannelMarked {
+        channel_id: String,
+        error: Option<String>,
+    },
+    MessageEditFinished {
+        channel_id: String,
+        ts: String,
+        previous_text: String,
+        was_edited: bool,
+        error: Option<String>,
+    },
+    ZeroClawBearerRejected,
+    ZeroClawPairingRequired {
+        runner: slack_zc_agent::AgentRunner,
+        error: Option<String>,
+    },
+    ZeroClawExited {
+        status: String,
+    },
     ZeroClawConnectionFinished {
         runner: Option<slack_zc_agent::AgentRunner>,
         error: Option<String>,
     },
+    MissedMessagesLoaded {
+        channel_id: String,
+        gap: HistoryGap,
+        messages: Vec<slack_zc_slack::types::Message>,
+        error: Option<String>,
+    },
+    /// Round trip of the gateway health check, or why it failed.
+    GatewayHealthChecked {
+        result: Result<std::time::Duration, String>,
+    },
+}
+
+/// `/reminders`: the signed-in user's pending reminders.
+#[derive(Debug, Clone, Default)]
+pub struct RemindersPopup {
+    pub reminders: Vec<slack_zc_slack::types::Reminder>,
+    pub selected: usize,
+    pub loading: bool,
+}
+
+/// Links of the selected message, numbered, to pick the one to open.
+#[derive(Debug, Clone, Default)]
+pub struct LinkChooser {
+    pub links: Vec<crate::ui::links::Link>,
+    pub selected: usize,
+}
+
+/// The Saved sidebar entry, opened: saved messages across channels.
+#[derive(Debug, Clone, Default)]
+pub struct SavedView {
+    pub selected: usize,
+}
+
+/// Entries above the channel sections in the sidebar, top to bottom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PinnedEntry {
+    Saved,
+    Threads,
+}
+
+/// The Threads sidebar entry, opened: followed threads, most recently
+/// active first.
+#[derive(Debug, Clone, Default)]
+pub struct ThreadsView {
+    pub selected: usize,
+}
+
+/// A stored workspace that failed its startup token check.
+#[derive(Debug, Clone)]
+pub struct WorkspaceIssue {
+    pub team_id: String,
+    pub team_name: String,
+    pub reason: String,
+}
+
+/// The Workspace issues panel (F10): broken workspaces and what to do
+/// about them.
+#[derive(Debug, Clone, Default)]
+pub struct WorkspaceIssuesView {
+    pub selected: usize,
+}
+
+/// A message to select once its channel's history arrives; when it is not
+/// in the latest page, the conversation around it is loaded first.
+#[derive(Debug, Clone)]
+pub struct JumpTarget {
+    pub channel_id: String,
+    pub ts: String,
+    /// Expand the message's thread too.
+    pub open_thread: bool,
+}
+
+/// A message opened in context: flashed when it arrives, then marked in
+/// the gutter until the view moves off it.
+#[derive(Debug, Clone)]
+pub struct ContextAnchor {
+    pub channel_id: String,
+    pub ts: String,
+    pub highlight_until: std::time::Instant,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AgentHistoryPopup {
+    pub entries: Vec<AgentResponse>,
+    pub query: String,
+    pub selected: usize,
+    pub loading: bool,
+}
+
+impl AgentHistoryPopup {
+    /// Entries whose command or response contains the query, case-insensitively.
+    pub fn matches(&self) -> Vec<&AgentResponse> {
+        let query = self.query.to_lowercase();
+        self.entries
+            .iter()
+            .filter(|entry| {
+                query.is_empty()
+                    || entry.command.to_lowercase().contains(&query)
+                    || entry.response.to_lowercase().contains(&query)
+            })
+            .collect()
+    }
+}
+
+/// Which message each visible row of the messages list showed in the last
+/// frame, so mouse clicks resolve against what is actually on screen.
+#[derive(Debug, Clone, Default)]
+pub struct MessageRowMap {
+    pub channel_id: String,
+    pub area: ratatui::layout::Rect,
+    /// First list item drawn; carried into the next frame to keep the view still.
+    pub offset: usize,
+    /// One entry per visible row; `None` for spacer and placeholder rows.
+    pub rows: Vec<Option<String>>,
+}
+
+impl MessageRowMap {
+    pub fn message_at(&self, col: u16, row: u16) -> Option<&str> {
+        let area = self.area;
+        if col < area.x || col >= area.x + area.width || row < area.y {
+            return None;
+        }
+        self.rows.get((row - area.y) as usize)?.as_deref()
+    }
+}
+
+/// One reported error, kept for the `E` history popup.
+#[derive(Debug, Clone)]
+pub struct ErrorEntry {
+    pub at: chrono::DateTime<chrono::Local>,
+    pub message: String,
+    /// While in the future the error is also shown as a toast.
+    pub toast_until: std::time::Instant,
+    /// The post that failed, when `R` can send it again.
+    pub retry: Option<PendingSend>,
+}
+
+/// A message post, kept so a failed one can be sent again unchanged. It
+/// names its workspace and channel, so switching away doesn't retarget it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingSend {
+    pub team_id: String,
+    pub channel_id: String,
+    pub text: String,
+    pub thread_ts: Option<String>,
+    /// The local copy shown in the channel while the post is tracked.
+    pub local_id: Option<u64>,
+}
+
+/// What can be done about a post Slack refused, when something can.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SendRecovery {
+    /// `not_in_channel`: join, then post again.
+    JoinChannel,
+    /// `msg_too_long`: post it as several messages.
+    SplitMessage,
+}
+
+/// A channel action typed in the input that Slack carries out.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChannelCommand {
+    Join,
+    Leave,
+    SetTopic(String),
+}
+
+/// Asks whether to apply a [`SendRecovery`] to a refused post.
+#[derive(Debug, Clone)]
+pub struct SendRecoveryPrompt {
+    pub recovery: SendRecovery,
+    pub send: PendingSend,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CommandPalette {
+    pub query: String,
+    pub selected: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PairingPrompt {
+    pub code: String,
+    pub error: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -58,14 +459,106 @@ pub struct ContextMenuItem {
     pub action: ContextMenuAction,
 }
 
+/// Reactions the picker lists after the recently used ones: the glyph
+/// shown and the Slack short name sent. The first five are the number-key
+/// shortcuts until the user has reacted enough to have their own.
+pub const REACTION_CATALOG: &[(&str, &str)] = &[
+    ("👍", "+1"),
+    ("❤️", "heart"),
+    ("😄", "smile"),
+    ("😮", "open_mouth"),
+    ("😢", "cry"),
+    ("😡", "rage"),
+    ("🎉", "tada"),
+    ("👀", "eyes"),
+    ("🙏", "pray"),
+    ("🔥", "fire"),
+    ("✅", "white_check_mark"),
+    ("👏", "clap"),
+    ("🙌", "raised_hands"),
+    ("🚀", "rocket"),
+    ("💯", "100"),
+    ("😂", "joy"),
+    ("🤔", "thinking_face"),
+    ("👎", "-1"),
+    ("💪", "muscle"),
+    ("😅", "sweat_smile"),
+    ("😍", "heart_eyes"),
+    ("🤯", "exploding_head"),
+    ("🥳", "partying_face"),
+    ("😬", "grimacing"),
+    ("🙈", "see_no_evil"),
+    ("✨", "sparkles"),
+    ("⭐", "star"),
+    ("⚠️", "warning"),
+    ("❌", "x"),
+    ("➕", "heavy_plus_sign"),
+    ("👋", "wave"),
+    ("🤝", "handshake"),
+    ("💡", "bulb"),
+    ("📌", "pushpin"),
+    ("🐛", "bug"),
+    ("☕", "coffee"),
+    ("😎", "sunglasses"),
+    ("🙂", "slightly_smiling_face"),
+];
+
+/// Number-key shortcuts in the reaction picker.
+pub const QUICK_REACTIONS: usize = 5;
+
+/// Agent commands kept per channel for `/again`.
+pub const AGENT_RERUN_HISTORY: usize = 5;
+
+/// The glyph for a catalog reaction; custom and unlisted ones have none.
+pub fn reaction_glyph(name: &str) -> Option<&'static str> {
+    REACTION_CATALOG
+        .iter()
+        .find(|(_, known)| *known == name)
+        .map(|(glyph, _)| *glyph)
+}
+
+/// `r`: recently used reactions, those already on the message, then the
+/// catalog, filtered by what is typed.
+#[derive(Debug, Clone, Default)]
+pub struct ReactionPicker {
+    /// Every reaction offered, in order, before filtering.
+    pub names: Vec<String>,
+    pub query: String,
+    pub selected: usize,
+}
+
+impl ReactionPicker {
+    pub fn matches(&self) -> Vec<&str> {
+        let query = self.query.trim_matches(':').to_lowercase();
+        self.names
+            .iter()
+            .map(String::as_str)
+            .filter(|name| name.contains(&query))
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum ContextMenuAction {
     Reply,
+    Quote,
+    /// Opens the reaction picker.
     React,
     Edit,
     Delete,
     Copy,
     ViewThread,
+    Translate,
+    InspectJson,
+    ToggleSaved,
+}
+
+/// Pretty-printed original payload of one message, for `J`.
+#[derive(Debug, Clone)]
+pub struct RawInspector {
+    pub ts: String,
+    pub json: String,
+    pub scroll: u16,
 }
 
 #[derive(Debug, Clone)]
@@ -73,12 +566,18 @@ pub struct EditState {
     pub channel_id: String,
     pub ts: String,
     pub original_text: String,
+    /// Whatever was in the input before editing started; restored afterwards.
+    pub saved_draft: String,
 }
 
 #[derive(Debug, Clone)]
 pub struct MessageFilter {
     pub user_id: Option<String>,
     pub show_threads: bool,
+    pub has_files: bool,
+    pub in_threads: bool,
+    /// From `ui.show_system_messages`; kept when the filter is cleared.
+    pub show_system: bool,
 }
 
 impl Default for MessageFilter {
@@ -86,6 +585,87 @@ impl Default for MessageFilter {
         Self {
             user_id: None,
             show_threads: true,
+            has_files: false,
+            in_threads: false,
+            show_system: true,
         }
     }
 }
+
+impl MessageFilter {
+    pub fn is_active(&self) -> bool {
+        self.user_id.is_some() || self.has_files || self.in_threads
+    }
+
+    /// System lines (joins, topic changes) show only while no filter is set.
+    pub fn matches(&self, message: &slack_zc_slack::types::Message) -> bool {
+        if message.is_system {
+            return self.show_system && !self.is_active();
+        }
+        self.user_id
+            .as_ref()
+            .is_none_or(|id| message.user_id == *id)
+            && (!self.has_files || !message.files.is_empty())
+            && (!self.in_threads
+                || message.thread_ts.is_some()
+                || message.reply_count.is_some_and(|count| count > 0))
+    }
+}
+
+/// Author list opened with `f`: two toggles, "anyone", then each author.
+#[derive(Debug, Clone, Default)]
+pub struct UserFilterPicker {
+    pub authors: Vec<FilterAuthor>,
+    pub selected: usize,
+    pub has_files: bool,
+    pub in_threads: bool,
+}
+
+impl UserFilterPicker {
+    /// Rows above the author list: the two toggles and "anyone".
+    pub const FIXED_ROWS: usize = 3;
+
+    pub fn row_count(&self) -> usize {
+        Self::FIXED_ROWS + self.authors.len()
+    }
+}
+
+/// Messages a channel may have missed while its socket was down: everything
+/// after `after_ts`, the newest message it had, up to `until_ts`.
+#[derive(Debug, Clone)]
+pub struct HistoryGap {
+    pub after_ts: String,
+    pub until_ts: String,
+}
+
+/// A workspace's Socket Mode connection as last reported by its socket task.
+#[derive(Debug, Clone)]
+pub struct SocketStatus {
+    pub state: slack_zc_slack::socket::ConnectionState,
+    /// When `state` was entered.
+    pub since: std::time::Instant,
+    pub last_event: Option<std::time::Instant>,
+}
+
+/// The F11 connection status overlay. `gateway_check` is `None` while the
+/// health check sent when it opened is in flight.
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionStatusPanel {
+    pub gateway_check: Option<Result<std::time::Duration, String>>,
+}
+
+/// One row of the F12 memory stats view.
+#[derive(Debug, Clone)]
+pub struct ChannelMemory {
+    pub name: String,
+    pub messages: usize,
+    pub threads: usize,
+    pub bytes: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct FilterAuthor {
+    pub user_id: String,
+    pub name: String,
+    pub count: usize,
+}
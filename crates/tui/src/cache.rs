@@ -1,6 +1,5 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
 use slack_zc_slack::types::Channel;
 use std::fs;
@@ -14,9 +13,7 @@ struct WorkspaceChannelsCache {
 }
 
 fn cache_dir() -> Result<PathBuf> {
-    let proj_dirs = ProjectDirs::from("com", "slack-zc", "slack-zc")
-        .context("unable to resolve slack-zc cache directory")?;
-    Ok(proj_dirs.cache_dir().to_path_buf())
+    slack_zc_slack::profile::cache_dir().context("unable to resolve slack-zc cache directory")
 }
 
 fn workspace_cache_path(team_id: &str) -> Result<PathBuf> {
@@ -0,0 +1,226 @@
+//! Subcommands that run instead of the TUI.
+
+use anyhow::{anyhow, bail, Result};
+use slack_zc_slack::auth::Session;
+use slack_zc_slack::lock::{self, InstanceLock};
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+const SESSION_USAGE: &str =
+    "usage: slack-zc session export [--force] <file>\n       slack-zc session import <file>";
+const MIN_PASSPHRASE_LEN: usize = 8;
+
+/// Removes `--profile <name>` (or `--profile=<name>`) from `args` and
+/// returns the name.
+pub fn take_profile_arg(args: &mut Vec<String>) -> Result<Option<String>> {
+    let Some(pos) = args
+        .iter()
+        .position(|arg| arg == "--profile" || arg.starts_with("--profile="))
+    else {
+        return Ok(None);
+    };
+    let flag = args.remove(pos);
+    if let Some(name) = flag.strip_prefix("--profile=") {
+        return Ok(Some(name.to_string()));
+    }
+    if pos < args.len() {
+        Ok(Some(args.remove(pos)))
+    } else {
+        bail!("--profile needs a name")
+    }
+}
+
+/// Outcome of [`claim_session`].
+pub enum SessionClaim {
+    /// This instance owns the session until the lock is dropped.
+    Owned(InstanceLock),
+    /// Another instance owns it; this one never saves the session.
+    ReadOnly,
+    Quit,
+}
+
+/// Takes the session lock before the TUI starts. When another live instance
+/// holds it, asks whether to continue read-only, take over, or quit.
+pub fn claim_session() -> Result<SessionClaim> {
+    let holder = match InstanceLock::acquire()? {
+        Ok(lock) => return Ok(SessionClaim::Owned(lock)),
+        Err(holder) => holder,
+    };
+
+    let since = holder
+        .since
+        .map(|at| {
+            format!(
+                ", started {}",
+                at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+            )
+        })
+        .unwrap_or_default();
+    eprintln!(
+        "Another slack-zc instance (pid {}{}) appears to be using this session.\n\
+         Two instances saving at once can overwrite each other's changes.\n\
+         \n  [r] continue read-only (workspace and channel changes are not saved)\n  \
+         [t] take over (the other instance stops saving)\n  [q] quit",
+        holder.pid, since
+    );
+    loop {
+        eprint!("Choice [r/t/q]: ");
+        io::stderr().flush()?;
+        let mut answer = String::new();
+        if io::stdin().read_line(&mut answer)? == 0 {
+            return Ok(SessionClaim::Quit);
+        }
+        match answer.trim().to_lowercase().as_str() {
+            "r" => {
+                lock::set_read_only();
+                return Ok(SessionClaim::ReadOnly);
+            }
+            "t" => return Ok(SessionClaim::Owned(InstanceLock::take_over()?)),
+            "q" => return Ok(SessionClaim::Quit),
+            _ => {}
+        }
+    }
+}
+
+/// `slack-zc session export|import <file>`: moves the saved workspaces to
+/// another machine in a passphrase-protected archive.
+pub fn run_session_command(args: &[String]) -> Result<()> {
+    match args {
+        [command, file] if command == "export" => export_session(Path::new(file), false),
+        [command, flag, file] if command == "export" && flag == "--force" => {
+            export_session(Path::new(file), true)
+        }
+        [command, file] if command == "import" => import_session(Path::new(file)),
+        _ => bail!(SESSION_USAGE),
+    }
+}
+
+/// Refuses to replace an existing file unless `force` is set, since the
+/// archive is renamed over whatever is at `path`.
+fn export_session(path: &Path, force: bool) -> Result<()> {
+    if !force && path.exists() {
+        bail!(
+            "{} already exists; choose another file or pass --force to replace it",
+            path.display()
+        );
+    }
+    let session = Session::load()?
+        .filter(|session| !session.workspaces.is_empty())
+        .ok_or_else(|| anyhow!("No saved workspaces to export"))?;
+
+    eprintln!(
+        "WARNING: the archive holds live Slack tokens for {} workspace(s). Anyone with \
+         the file and the passphrase can act as you; delete it once imported.",
+        session.workspaces.len()
+    );
+    let passphrase = rpassword::prompt_password("Passphrase: ")?;
+    if passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+        bail!(
+            "Passphrase must be at least {} characters",
+            MIN_PASSPHRASE_LEN
+        );
+    }
+    if rpassword::prompt_password("Repeat passphrase: ")? != passphrase {
+        bail!("Passphrases do not match");
+    }
+
+    let archive = session.export_archive(&passphrase)?;
+    Session::write_secure_file(path, &archive)?;
+    ensure_private(path)?;
+    println!(
+        "Exported {} workspace(s) to {}",
+        session.workspaces.len(),
+        path.display()
+    );
+    Ok(())
+}
+
+/// Refuses while a running instance holds the session: its next save would
+/// drop the imported workspaces.
+fn import_session(path: &Path) -> Result<()> {
+    let _lock = match InstanceLock::acquire()? {
+        Ok(lock) => lock,
+        Err(holder) => bail!(
+            "slack-zc is running (pid {}); quit it before importing a session",
+            holder.pid
+        ),
+    };
+    let archive = fs::read(path)?;
+    let passphrase = rpassword::prompt_password("Passphrase: ")?;
+    let imported = Session::import_archive(&archive, &passphrase)?;
+    let names: Vec<String> = imported
+        .workspaces
+        .iter()
+        .map(|w| w.team_name.clone())
+        .collect();
+
+    let mut session = Session::load()?.unwrap_or_default();
+    session.merge(imported);
+    session.save()?;
+    println!(
+        "Imported {} workspace(s): {}",
+        names.len(),
+        names.join(", ")
+    );
+    Ok(())
+}
+
+/// Removes the archive again when the filesystem ignored the private mode
+/// (e.g. a FAT or network mount), rather than leave tokens readable.
+fn ensure_private(path: &Path) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(path)?.permissions().mode() & 0o777;
+        if mode & 0o077 != 0 {
+            let _ = fs::remove_file(path);
+            bail!(
+                "{} would be readable by other users (mode {:o}); choose another location",
+                path.display(),
+                mode
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run_session_command, take_profile_arg};
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn profile_flag_is_taken_out_of_the_arguments() {
+        let mut rest = args(&["--profile", "work", "session", "export", "f"]);
+        assert_eq!(
+            take_profile_arg(&mut rest).unwrap().as_deref(),
+            Some("work")
+        );
+        assert_eq!(rest, args(&["session", "export", "f"]));
+
+        let mut rest = args(&["--profile=community"]);
+        assert_eq!(
+            take_profile_arg(&mut rest).unwrap().as_deref(),
+            Some("community")
+        );
+        assert!(rest.is_empty());
+
+        assert!(take_profile_arg(&mut args(&["--profile"])).is_err());
+        assert_eq!(take_profile_arg(&mut args(&["session"])).unwrap(), None);
+    }
+
+    #[test]
+    fn export_refuses_to_replace_an_existing_file() {
+        let path = std::env::temp_dir().join(format!("slack-zc-export-{}", std::process::id()));
+        std::fs::write(&path, "earlier archive").unwrap();
+
+        let err = run_session_command(&args(&["export", &path.to_string_lossy()])).unwrap_err();
+        assert!(err.to_string().contains("pass --force"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "earlier archive");
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use slack_zc_slack::auth::StoredCredentials;
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,13 +10,28 @@ pub struct Config {
     pub zeroclaw: ZeroClawConfig,
     #[serde(default)]
     pub llm: LlmConfig,
+    #[serde(default)]
+    pub ui: UiConfig,
+    #[serde(default)]
+    pub network: NetworkConfig,
+    #[serde(default)]
+    pub debug: DebugConfig,
+    #[serde(default)]
+    pub presence: PresenceConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct SlackConfig {
+    /// Read for migration only; resolved from `SLACK_CLIENT_ID` or the
+    /// encrypted session and never written back.
+    #[serde(default, skip_serializing)]
     pub client_id: String,
+    #[serde(default, skip_serializing)]
     pub client_secret: String,
     pub redirect_port: u16,
+    /// Web API root; empty means slack.com. `SLACK_API_BASE_URL` wins.
+    #[serde(default)]
+    pub api_base_url: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -23,21 +39,261 @@ pub struct ZeroClawConfig {
     pub binary_path: String,
     pub gateway_port: u16,
     pub auto_start: bool,
+    /// Agent commands in workspaces that have not chosen with `/agent on`
+    /// or `/agent off`.
+    #[serde(default = "default_agent_enabled")]
+    pub agent_enabled: bool,
     pub timeout_seconds: u64,
     #[serde(default = "default_post_to_slack")]
     pub post_to_slack: bool,
+    #[serde(default = "default_share_context")]
+    pub share_context: bool,
+    #[serde(default = "default_context_messages")]
+    pub context_messages: usize,
+    #[serde(default = "default_translate_target")]
+    pub translate_target: String,
+    #[serde(default = "default_translation_annotations")]
+    pub translation_annotations: bool,
+    #[serde(default = "default_history_retention_days")]
+    pub history_retention_days: u32,
+    #[serde(default = "default_history_max_entries")]
+    pub history_max_entries: usize,
+    #[serde(default = "default_pairing_timeout_seconds")]
+    pub pairing_timeout_seconds: u64,
+    /// Send slash commands slack-zc does not know to ZeroClaw, which
+    /// answers with what it supports; otherwise they are refused locally.
+    #[serde(default)]
+    pub forward_unknown_commands: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct LlmConfig {
     pub provider: String,
+    /// Resolved from `LLM_API_KEY` or the encrypted session; never written back.
+    #[serde(default, skip_serializing)]
     pub api_key: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct NetworkConfig {
+    /// `http://host:port` for Slack traffic; empty falls back to the
+    /// `HTTPS_PROXY`/`HTTP_PROXY` environment variables.
+    #[serde(default)]
+    pub proxy: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct DebugConfig {
+    /// Keep each message's original JSON for the `J` inspector. Costs
+    /// memory roughly in proportion to the messages kept.
+    #[serde(default)]
+    pub keep_raw: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresenceConfig {
+    /// Tell Slack you are around while you use slack-zc, so it does not
+    /// mark you away after a few quiet minutes.
+    #[serde(default)]
+    pub keep_active: bool,
+    /// Minutes without a key press or click after which the pings stop.
+    #[serde(default = "default_idle_minutes")]
+    pub idle_minutes: u64,
+}
+
+impl Default for PresenceConfig {
+    fn default() -> Self {
+        Self {
+            keep_active: false,
+            idle_minutes: default_idle_minutes(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UiConfig {
+    #[serde(default = "default_quote_max_lines")]
+    pub quote_max_lines: usize,
+    #[serde(default = "default_show_panel")]
+    pub show_sidebar: bool,
+    #[serde(default = "default_show_panel")]
+    pub show_agent_panel: bool,
+    #[serde(default = "default_scroll_lines")]
+    pub scroll_lines: usize,
+    #[serde(default = "default_toast_seconds")]
+    pub toast_seconds: u64,
+    /// Messages kept in memory per channel; older ones are dropped and
+    /// fetched again when scrolled back to.
+    #[serde(default = "default_max_messages_per_channel")]
+    pub max_messages_per_channel: usize,
+    /// Banner the first time each Slack Connect channel is opened.
+    #[serde(default = "default_warn_external_channels")]
+    pub warn_external_channels: bool,
+    /// List archived channels, read-only, in their own sidebar section.
+    #[serde(default)]
+    pub show_archived: bool,
+    /// Join, leave, topic and rename notices in the message list.
+    #[serde(default = "default_show_system_messages")]
+    pub show_system_messages: bool,
+    /// Messages from one author this close together share a header; 0
+    /// gives every message its own.
+    #[serde(default = "default_message_group_minutes")]
+    pub message_group_minutes: u64,
+    /// `dark`, `light` or `high-contrast`; unset picks dark or light from
+    /// the terminal's background.
+    #[serde(default)]
+    pub theme: Option<String>,
+    /// "seen" under your newest message a DM's other person has read.
+    #[serde(default = "default_read_receipts")]
+    pub read_receipts: bool,
+    /// Clickable OSC 8 links; unset turns them on for terminals known to
+    /// support them.
+    #[serde(default)]
+    pub hyperlinks: Option<bool>,
+    /// How long ago each channel last had a message, right-aligned in its
+    /// sidebar row.
+    #[serde(default = "default_sidebar_activity")]
+    pub sidebar_activity: bool,
+    /// Order each sidebar section by latest message instead of as Slack
+    /// lists it.
+    #[serde(default)]
+    pub sort_by_activity: bool,
+    /// Startup checklist shown until the active workspace has channels;
+    /// off opens the main screen as soon as the session is read.
+    #[serde(default = "default_splash")]
+    pub splash: bool,
+    /// Minutes between background refreshes of the channel list, which
+    /// picks up channels created or joined elsewhere; 0 turns them off.
+    #[serde(default = "default_channel_refresh_minutes")]
+    pub channel_refresh_minutes: u64,
+    /// "slack-zc — #channel (workspace)" as the terminal's window title,
+    /// with a count of what arrived while the window was in the background.
+    #[serde(default = "default_window_title")]
+    pub window_title: bool,
+    /// Mentions and DMs announced with OSC 777/9 while the terminal is in
+    /// the background; unset turns them on for terminals known to show them.
+    #[serde(default)]
+    pub terminal_notifications: Option<bool>,
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            quote_max_lines: default_quote_max_lines(),
+            show_sidebar: default_show_panel(),
+            show_agent_panel: default_show_panel(),
+            scroll_lines: default_scroll_lines(),
+            toast_seconds: default_toast_seconds(),
+            max_messages_per_channel: default_max_messages_per_channel(),
+            warn_external_channels: default_warn_external_channels(),
+            show_archived: false,
+            show_system_messages: default_show_system_messages(),
+            message_group_minutes: default_message_group_minutes(),
+            theme: None,
+            read_receipts: default_read_receipts(),
+            hyperlinks: None,
+            sidebar_activity: default_sidebar_activity(),
+            sort_by_activity: false,
+            splash: default_splash(),
+            channel_refresh_minutes: default_channel_refresh_minutes(),
+            window_title: default_window_title(),
+            terminal_notifications: None,
+        }
+    }
+}
+
+fn default_show_panel() -> bool {
+    true
+}
+
+fn default_scroll_lines() -> usize {
+    3
+}
+
+fn default_toast_seconds() -> u64 {
+    5
+}
+
+fn default_warn_external_channels() -> bool {
+    true
+}
+
+fn default_show_system_messages() -> bool {
+    true
+}
+
+fn default_read_receipts() -> bool {
+    true
+}
+
+fn default_sidebar_activity() -> bool {
+    true
+}
+
+fn default_splash() -> bool {
+    true
+}
+
+fn default_channel_refresh_minutes() -> u64 {
+    60
+}
+
+fn default_window_title() -> bool {
+    true
+}
+
+fn default_message_group_minutes() -> u64 {
+    5
+}
+
+fn default_max_messages_per_channel() -> usize {
+    1000
+}
+
+fn default_quote_max_lines() -> usize {
+    4
+}
+
+fn default_idle_minutes() -> u64 {
+    10
+}
+
+fn default_agent_enabled() -> bool {
+    true
+}
+
 fn default_post_to_slack() -> bool {
     false
 }
 
+fn default_share_context() -> bool {
+    true
+}
+
+fn default_context_messages() -> usize {
+    50
+}
+
+fn default_translate_target() -> String {
+    "en".to_string()
+}
+
+fn default_translation_annotations() -> bool {
+    true
+}
+
+fn default_history_retention_days() -> u32 {
+    90
+}
+
+fn default_history_max_entries() -> usize {
+    2000
+}
+
+fn default_pairing_timeout_seconds() -> u64 {
+    30
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -45,23 +301,124 @@ impl Default for Config {
                 client_id: String::new(),
                 client_secret: String::new(),
                 redirect_port: 3000,
+                api_base_url: String::new(),
             },
             zeroclaw: ZeroClawConfig {
                 binary_path: "zeroclaw".to_string(),
                 gateway_port: 58080,
                 auto_start: true,
+                agent_enabled: default_agent_enabled(),
                 timeout_seconds: 30,
                 post_to_slack: false,
+                share_context: default_share_context(),
+                context_messages: default_context_messages(),
+                translate_target: default_translate_target(),
+                translation_annotations: default_translation_annotations(),
+                history_retention_days: default_history_retention_days(),
+                history_max_entries: default_history_max_entries(),
+                pairing_timeout_seconds: default_pairing_timeout_seconds(),
+                forward_unknown_commands: false,
             },
             llm: LlmConfig {
                 provider: "openrouter".to_string(),
                 api_key: String::new(),
             },
+            ui: UiConfig::default(),
+            network: NetworkConfig::default(),
+            debug: DebugConfig::default(),
+            presence: PresenceConfig::default(),
+        }
+    }
+}
+
+/// Where a credential was resolved from, for startup logging.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CredentialSource {
+    Environment,
+    Session,
+    /// Found in plaintext config.toml and moved into the session.
+    ConfigFile,
+    Unset,
+}
+
+/// Env var wins, then the encrypted session. A plaintext value from
+/// config.toml is moved into the session if it holds none yet.
+fn resolve_credential(
+    value: &mut String,
+    stored: &mut Option<String>,
+    from_env: Option<String>,
+) -> CredentialSource {
+    let plaintext = std::mem::take(value);
+    let migrated = !plaintext.is_empty() && stored.is_none();
+    if migrated {
+        *stored = Some(plaintext);
+    }
+
+    if let Some(env) = from_env.filter(|v| !v.is_empty()) {
+        *value = env;
+        CredentialSource::Environment
+    } else if let Some(saved) = stored.clone().filter(|v| !v.is_empty()) {
+        *value = saved;
+        if migrated {
+            CredentialSource::ConfigFile
+        } else {
+            CredentialSource::Session
         }
+    } else {
+        CredentialSource::Unset
     }
 }
 
 impl Config {
+    /// Fills the Slack client id/secret and LLM key from the environment or
+    /// `stored`, moving any plaintext copies out of the config. Returns
+    /// whether config.toml held secrets and should be rewritten.
+    pub fn resolve_credentials(
+        &mut self,
+        stored: &mut StoredCredentials,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> bool {
+        let had_plaintext = !self.slack.client_id.is_empty()
+            || !self.slack.client_secret.is_empty()
+            || !self.llm.api_key.is_empty();
+
+        let fields = [
+            (
+                "Slack client id",
+                "SLACK_CLIENT_ID",
+                &mut self.slack.client_id,
+                &mut stored.client_id,
+            ),
+            (
+                "Slack client secret",
+                "SLACK_CLIENT_SECRET",
+                &mut self.slack.client_secret,
+                &mut stored.client_secret,
+            ),
+            (
+                "LLM API key",
+                "LLM_API_KEY",
+                &mut self.llm.api_key,
+                &mut stored.llm_api_key,
+            ),
+        ];
+        for (label, var, value, stored) in fields {
+            let source = resolve_credential(value, stored, env(var));
+            tracing::info!("{} source: {:?}", label, source);
+        }
+        had_plaintext
+    }
+
+    /// Slack Web API root: `SLACK_API_BASE_URL`, then `[slack] api_base_url`,
+    /// then slack.com.
+    pub fn slack_api_base_url(&self) -> String {
+        std::env::var("SLACK_API_BASE_URL")
+            .ok()
+            .filter(|url| !url.trim().is_empty())
+            .or_else(|| Some(self.slack.api_base_url.clone()).filter(|url| !url.trim().is_empty()))
+            .unwrap_or_else(|| slack_zc_slack::api::SLACK_API_BASE.to_string())
+    }
+
     pub fn load(path: &PathBuf) -> Result<Self, Box<dyn std::error::Error>> {
         let content = std::fs::read_to_string(path)?;
         let config: Config = toml::from_str(&content)?;
@@ -71,4 +428,61 @@ impl Config {
     pub fn load_or_default(path: &PathBuf) -> Self {
         Self::load(path).unwrap_or_default()
     }
+
+    pub fn save(&self, path: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Config, CredentialSource};
+    use slack_zc_slack::auth::StoredCredentials;
+
+    #[test]
+    fn plaintext_secrets_move_into_the_session_and_are_not_saved() {
+        let mut config: Config = toml::from_str(
+            r#"
+            [slack]
+            client_id = "123.456"
+            client_secret = "s3cr3t-from-config"
+            redirect_port = 3000
+            [llm]
+            provider = "openrouter"
+            api_key = "sk-or-from-config"
+            "#,
+        )
+        .unwrap();
+        let mut stored = StoredCredentials::default();
+
+        let rewrite = config.resolve_credentials(&mut stored, |var| {
+            (var == "SLACK_CLIENT_ID").then(|| "from-env".to_string())
+        });
+
+        assert!(rewrite);
+        assert_eq!(config.slack.client_id, "from-env");
+        assert_eq!(config.slack.client_secret, "s3cr3t-from-config");
+        assert_eq!(stored.client_secret.as_deref(), Some("s3cr3t-from-config"));
+        assert_eq!(stored.llm_api_key.as_deref(), Some("sk-or-from-config"));
+
+        let saved = toml::to_string_pretty(&config).unwrap();
+        assert!(!saved.contains("s3cr3t"));
+        assert!(!saved.contains("sk-or"));
+        assert!(!saved.contains("from-env"));
+    }
+
+    #[test]
+    fn session_credentials_are_used_when_env_is_unset() {
+        let mut value = String::new();
+        let mut stored = Some("from-session".to_string());
+        assert_eq!(
+            super::resolve_credential(&mut value, &mut stored, None),
+            CredentialSource::Session
+        );
+        assert_eq!(value, "from-session");
+    }
 }
@@ -0,0 +1,328 @@
+//! `slack-zc --doctor`: checks the setup outside the TUI and prints one
+//! line per check, so a support request can start from its output.
+
+use crate::Config;
+use slack_zc_agent::runner::port_is_free;
+use slack_zc_agent::GatewayClient;
+use slack_zc_slack::api::SlackApi;
+use slack_zc_slack::auth::Session;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// The outcome of one check. `fix` says what to do about a warning or
+/// failure; neither field ever holds a token or session contents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub name: String,
+    pub status: Status,
+    pub detail: String,
+    pub fix: Option<String>,
+}
+
+impl Check {
+    fn pass(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: Status::Pass,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn warn(name: impl Into<String>, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: Status::Warn,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    fn fail(name: impl Into<String>, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            status: Status::Fail,
+            ..Self::warn(name, detail, fix)
+        }
+    }
+
+    /// "[pass] config: ..." with the remediation indented below it.
+    pub fn line(&self) -> String {
+        let mark = match self.status {
+            Status::Pass => "pass",
+            Status::Warn => "warn",
+            Status::Fail => "FAIL",
+        };
+        let mut line = format!("[{}] {}: {}", mark, self.name, self.detail);
+        if let Some(fix) = &self.fix {
+            line.push_str(&format!("\n       → {}", fix));
+        }
+        line
+    }
+}
+
+/// Runs every check, prints the checklist and returns whether none failed.
+pub fn run(config_path: &Path) -> anyhow::Result<bool> {
+    let (config_check, config) = check_config(config_path);
+    let mut checks = vec![config_check];
+
+    let session = match Session::load() {
+        Ok(Some(session)) => {
+            checks.push(Check::pass(
+                "session",
+                format!("decrypted, {} workspace(s)", session.workspaces.len()),
+            ));
+            Some(session)
+        }
+        Ok(None) => {
+            checks.push(Check::warn(
+                "session",
+                "no saved session",
+                "start slack-zc and sign in to a workspace",
+            ));
+            None
+        }
+        Err(err) => {
+            checks.push(Check::fail(
+                "session",
+                format!("could not be decrypted ({})", err),
+                "sign in again, or restore the key file it was saved with",
+            ));
+            None
+        }
+    };
+
+    let rt = tokio::runtime::Runtime::new()?;
+    rt.block_on(async {
+        let api = SlackApi::new().with_base_url(config.slack_api_base_url());
+        for workspace in session.iter().flat_map(|s| &s.workspaces) {
+            checks.extend(check_workspace(&api, workspace).await);
+        }
+        checks.extend(check_zeroclaw(&config).await);
+    });
+    checks.push(check_clipboard(|name| std::env::var_os(name)));
+    checks.extend(check_terminal(|name| std::env::var(name).ok()));
+
+    for check in &checks {
+        println!("{}", check.line());
+    }
+    let failed = checks.iter().filter(|c| c.status == Status::Fail).count();
+    let warned = checks.iter().filter(|c| c.status == Status::Warn).count();
+    println!(
+        "\n{} check(s): {} failed, {} warning(s)",
+        checks.len(),
+        failed,
+        warned
+    );
+    Ok(failed == 0)
+}
+
+/// The config at `path`, or the defaults the app would fall back to.
+fn check_config(path: &Path) -> (Check, Config) {
+    let shown = path.display();
+    if !path.exists() {
+        let check = Check::warn(
+            "config",
+            format!("{} not found, using defaults", shown),
+            "copy config/default.toml there to change settings",
+        );
+        return (check, Config::default());
+    }
+    match Config::load(&PathBuf::from(path)) {
+        Ok(config) => (Check::pass("config", format!("{} parsed", shown)), config),
+        Err(err) => {
+            let check = Check::fail(
+                "config",
+                format!("{} does not parse: {}", shown, err),
+                "fix the file; slack-zc would start with the defaults instead",
+            );
+            (check, Config::default())
+        }
+    }
+}
+
+/// `auth.test` with the user token, then `apps.connections.open` with the
+/// app token. Slack's error codes are shown; the tokens never are.
+async fn check_workspace(
+    api: &SlackApi,
+    workspace: &slack_zc_slack::types::Workspace,
+) -> Vec<Check> {
+    let name = format!("workspace {}", workspace.team_name);
+    let mut checks = Vec::new();
+    match api.test_auth(&workspace.xoxp_token).await {
+        Ok((_, team, user_id)) => checks.push(Check::pass(
+            name.clone(),
+            format!("auth.test ok for {} as {}", team, user_id),
+        )),
+        Err(err) => checks.push(Check::fail(
+            name.clone(),
+            format!("auth.test failed: {}", err),
+            "the token was revoked or expired; sign in to this workspace again",
+        )),
+    }
+    if workspace.xapp_token.is_empty() {
+        checks.push(Check::warn(
+            name,
+            "no app-level token, so no live updates",
+            "add an xapp- token with connections:write to use Socket Mode",
+        ));
+    } else {
+        match api.get_socket_mode_url(&workspace.xapp_token).await {
+            Ok(_) => checks.push(Check::pass(name, "apps.connections.open reachable")),
+            Err(err) => checks.push(Check::fail(
+                name,
+                format!("apps.connections.open failed: {}", err),
+                "check the app token and that Socket Mode is enabled for the app",
+            )),
+        }
+    }
+    checks
+}
+
+/// The ZeroClaw binary's version and whether the gateway port is usable.
+async fn check_zeroclaw(config: &Config) -> Vec<Check> {
+    let binary = &config.zeroclaw.binary_path;
+    let version = tokio::process::Command::new(binary)
+        .arg("--version")
+        .output()
+        .await
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+    let binary_check = match version {
+        Some(version) => Check::pass("zeroclaw", format!("{} ({})", version, binary)),
+        None if config.zeroclaw.agent_enabled => Check::fail(
+            "zeroclaw",
+            format!("{} not found or not runnable", binary),
+            "install ZeroClaw or set zeroclaw.binary_path",
+        ),
+        None => Check::warn(
+            "zeroclaw",
+            format!("{} not found; the agent is disabled", binary),
+            "install ZeroClaw to use the agent commands",
+        ),
+    };
+
+    let port = config.zeroclaw.gateway_port;
+    let port_check = if port_is_free(port) {
+        Check::pass("gateway port", format!("{} is free", port))
+    } else if GatewayClient::new(port)
+        .health_check()
+        .await
+        .unwrap_or(false)
+    {
+        Check::pass(
+            "gateway port",
+            format!("a ZeroClaw gateway already answers on {}", port),
+        )
+    } else {
+        Check::warn(
+            "gateway port",
+            format!("{} is taken by another service", port),
+            "set zeroclaw.gateway_port to a free port (a nearby one is tried otherwise)",
+        )
+    };
+    vec![binary_check, port_check]
+}
+
+/// The tool `y` and the copy actions pipe text into.
+fn check_clipboard(var: impl Fn(&str) -> Option<std::ffi::OsString>) -> Check {
+    let (program, fix) = if cfg!(target_os = "macos") {
+        ("pbcopy", "pbcopy ships with macOS; check PATH")
+    } else {
+        ("xclip", "install xclip to copy messages and links")
+    };
+    let found = var("PATH")
+        .map(|path| std::env::split_paths(&path).any(|dir| dir.join(program).is_file()))
+        .unwrap_or(false);
+    if found {
+        Check::pass("clipboard", format!("{} found", program))
+    } else {
+        Check::warn("clipboard", format!("{} not on PATH", program), fix)
+    }
+}
+
+/// Truecolor and kitty graphics support, judged from the environment.
+fn check_terminal(var: impl Fn(&str) -> Option<String>) -> Vec<Check> {
+    let colorterm = var("COLORTERM").unwrap_or_default();
+    let truecolor = if matches!(colorterm.as_str(), "truecolor" | "24bit") {
+        Check::pass("truecolor", format!("COLORTERM={}", colorterm))
+    } else {
+        Check::warn(
+            "truecolor",
+            "COLORTERM does not advertise 24-bit colour",
+            "colours may be approximated; set COLORTERM=truecolor if the terminal supports it",
+        )
+    };
+
+    let term = var("TERM").unwrap_or_default();
+    let term_program = var("TERM_PROGRAM").unwrap_or_default();
+    let kitty = var("KITTY_WINDOW_ID").is_some()
+        || term.contains("kitty")
+        || matches!(term_program.as_str(), "WezTerm" | "ghostty");
+    let graphics = if kitty {
+        Check::pass("kitty graphics", "supported")
+    } else {
+        Check::warn(
+            "kitty graphics",
+            "not detected",
+            "use kitty, WezTerm or Ghostty for inline graphics",
+        )
+    };
+    vec![truecolor, graphics]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_config, check_terminal, Status};
+    use std::collections::HashMap;
+
+    #[test]
+    fn missing_and_broken_configs_are_reported_with_their_path() {
+        let dir = std::env::temp_dir().join(format!("slack-zc-doctor-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+
+        let (check, _) = check_config(&path);
+        assert_eq!(check.status, Status::Warn);
+        assert!(check.detail.contains(&path.display().to_string()));
+
+        std::fs::write(&path, "[ui\nsplash = ").unwrap();
+        let (check, _) = check_config(&path);
+        assert_eq!(check.status, Status::Fail);
+        assert!(check.line().contains("→ fix the file"));
+
+        std::fs::write(&path, "").unwrap();
+        let (check, config) = check_config(&path);
+        assert_eq!(check.status, Status::Pass);
+        assert!(config.ui.splash);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn terminal_capabilities_come_from_the_environment() {
+        let statuses = |vars: &[(&str, &str)]| {
+            let vars: HashMap<String, String> = vars
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+            check_terminal(|name| vars.get(name).cloned())
+                .into_iter()
+                .map(|check| check.status)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            statuses(&[("COLORTERM", "truecolor"), ("TERM", "xterm-kitty")]),
+            [Status::Pass, Status::Pass]
+        );
+        assert_eq!(
+            statuses(&[("TERM", "xterm-256color")]),
+            [Status::Warn, Status::Warn]
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use crate::app::AgentResponse;
+use anyhow::{Context, Result};
+use chrono::{Duration, Utc};
+use slack_zc_slack::auth::Session;
+use slack_zc_slack::lock;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+fn history_path() -> Result<PathBuf> {
+    let data_dir =
+        slack_zc_slack::profile::data_dir().context("unable to resolve slack-zc data directory")?;
+    Ok(data_dir.join("agent_history.jsonl"))
+}
+
+/// Reads every stored response, oldest first. Malformed lines are skipped.
+fn read_entries() -> Result<Vec<AgentResponse>> {
+    read_entries_from(&history_path()?)
+}
+
+fn read_entries_from(path: &Path) -> Result<Vec<AgentResponse>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("failed to read agent history {}", path.display()))?;
+    Ok(content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Most recent `limit` responses, newest first.
+pub fn load_recent(limit: usize) -> Result<Vec<AgentResponse>> {
+    let mut entries = read_entries()?;
+    entries.reverse();
+    entries.truncate(limit);
+    Ok(entries)
+}
+
+/// Full on-disk history, newest first.
+pub fn load_all() -> Result<Vec<AgentResponse>> {
+    load_recent(usize::MAX)
+}
+
+/// Appends one response. Entries older than `retention_days` (0 keeps
+/// everything) or beyond `max_entries` are pruned on the first append of a
+/// session and every `PRUNE_EVERY` appends after it. Nothing is written
+/// when this instance runs read-only.
+pub fn append(entry: &AgentResponse, retention_days: u32, max_entries: usize) -> Result<()> {
+    if lock::is_read_only() {
+        return Ok(());
+    }
+    append_to(&history_path()?, entry, retention_days, max_entries)
+}
+
+/// Appends since the last prune. Held across the whole write, so
+/// completions that finish together go through one at a time.
+static WRITER: Mutex<Option<usize>> = Mutex::new(None);
+
+const PRUNE_EVERY: usize = 50;
+
+fn append_to(
+    path: &Path,
+    entry: &AgentResponse,
+    retention_days: u32,
+    max_entries: usize,
+) -> Result<()> {
+    let mut appended = WRITER
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).with_context(|| {
+            format!(
+                "failed to create agent history directory {}",
+                parent.display()
+            )
+        })?;
+    }
+
+    let mut line = serde_json::to_vec(entry)?;
+    line.push(b'\n');
+    let mut options = fs::OpenOptions::new();
+    options.append(true).create(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options
+        .open(path)
+        .and_then(|mut file| file.write_all(&line))
+        .with_context(|| format!("failed to write agent history {}", path.display()))?;
+
+    let count = appended.map_or(PRUNE_EVERY, |count| count + 1);
+    if count < PRUNE_EVERY {
+        *appended = Some(count);
+        return Ok(());
+    }
+    *appended = Some(0);
+    prune(path, retention_days, max_entries)
+}
+
+fn prune(path: &Path, retention_days: u32, max_entries: usize) -> Result<()> {
+    let mut entries = read_entries_from(path)?;
+    let before = entries.len();
+    if retention_days > 0 {
+        let cutoff = Utc::now() - Duration::days(i64::from(retention_days));
+        entries.retain(|entry| entry.timestamp >= cutoff);
+    }
+    if entries.len() > max_entries {
+        entries.drain(..entries.len() - max_entries);
+    }
+    if entries.len() == before {
+        return Ok(());
+    }
+
+    let mut content = Vec::new();
+    for entry in &entries {
+        serde_json::to_writer(&mut content, entry)?;
+        content.push(b'\n');
+    }
+    Session::write_secure_file(path, &content)
+        .with_context(|| format!("failed to write agent history {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn appends_that_finish_together_are_all_kept() {
+        let dir = std::env::temp_dir().join(format!("slack-zc-history-{}", std::process::id()));
+        let path = dir.join("agent_history.jsonl");
+        let _ = fs::remove_dir_all(&dir);
+
+        std::thread::scope(|scope| {
+            for i in 0..8 {
+                let path = &path;
+                scope.spawn(move || {
+                    let entry = AgentResponse {
+                        command: format!("/summarize {i}"),
+                        response: "ok".to_string(),
+                        timestamp: Utc::now(),
+                        channel_id: None,
+                        channel_name: None,
+                        thread_ts: None,
+                    };
+                    append_to(path, &entry, 30, 100).unwrap();
+                });
+            }
+        });
+
+        let mut commands: Vec<_> = read_entries_from(&path)
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.command)
+            .collect();
+        commands.sort();
+        assert_eq!(
+            commands,
+            (0..8)
+                .map(|i| format!("/summarize {i}"))
+                .collect::<Vec<_>>()
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,3 +1,8 @@
+use std::time::{Duration, Instant};
+
+/// Least time between two typing signals while composing.
+const TYPING_SIGNAL_INTERVAL: Duration = Duration::from_secs(3);
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum InputMode {
     Normal,
@@ -9,6 +14,8 @@ pub struct InputState {
     pub buffer: String,
     pub mode: InputMode,
     cursor_position: usize,
+    last_typing_signal: Option<Instant>,
+    typing_signal_pending: bool,
 }
 
 impl Default for InputState {
@@ -23,6 +30,8 @@ impl InputState {
             buffer: String::new(),
             mode: InputMode::Normal,
             cursor_position: 0,
+            last_typing_signal: None,
+            typing_signal_pending: false,
         }
     }
 
@@ -36,6 +45,7 @@ impl InputState {
         self.buffer.push(c);
         self.cursor_position = self.buffer.len();
         self.update_mode();
+        self.note_typing(Instant::now());
     }
 
     pub fn handle_backspace(&mut self) {
@@ -43,13 +53,50 @@ impl InputState {
             self.buffer.pop();
             self.cursor_position = self.buffer.len();
             self.update_mode();
+            if self.buffer.is_empty() {
+                self.reset_typing();
+            }
+        }
+    }
+
+    /// Whether a keystroke since the last call should tell the channel we
+    /// are typing. Raised at most every `TYPING_SIGNAL_INTERVAL` while a
+    /// message (not a command) is being written.
+    pub fn take_typing_signal(&mut self) -> bool {
+        std::mem::take(&mut self.typing_signal_pending)
+    }
+
+    fn note_typing(&mut self, now: Instant) {
+        if self.mode != InputMode::Normal || self.buffer.is_empty() {
+            return;
         }
+        if self
+            .last_typing_signal
+            .is_some_and(|at| now.duration_since(at) < TYPING_SIGNAL_INTERVAL)
+        {
+            return;
+        }
+        self.last_typing_signal = Some(now);
+        self.typing_signal_pending = true;
+    }
+
+    fn reset_typing(&mut self) {
+        self.last_typing_signal = None;
+        self.typing_signal_pending = false;
+    }
+
+    /// Replaces the whole buffer, e.g. when loading a message to edit.
+    pub fn set_text(&mut self, text: String) {
+        self.buffer = text;
+        self.cursor_position = self.buffer.len();
+        self.update_mode();
     }
 
     pub fn clear(&mut self) {
         self.buffer.clear();
         self.cursor_position = 0;
         self.mode = InputMode::Normal;
+        self.reset_typing();
     }
 
     fn update_mode(&mut self) {
@@ -1,3 +1,198 @@
+use crate::app::Focus;
+use ratatui::crossterm::event::{KeyCode, KeyModifiers};
+
+/// A named action and the key that triggers it. The command palette lists
+/// these and replays the key, so registering here is all a new action needs.
+#[derive(Debug, Clone, Copy)]
+pub struct Binding {
+    pub label: &'static str,
+    pub key: KeyCode,
+    pub modifiers: KeyModifiers,
+    /// Focus the key is handled in; `None` for global shortcuts.
+    pub focus: Option<Focus>,
+}
+
+const fn global(label: &'static str, key: KeyCode, modifiers: KeyModifiers) -> Binding {
+    Binding {
+        label,
+        key,
+        modifiers,
+        focus: None,
+    }
+}
+
+const fn in_focus(label: &'static str, c: char, focus: Focus) -> Binding {
+    Binding {
+        label,
+        key: KeyCode::Char(c),
+        modifiers: KeyModifiers::NONE,
+        focus: Some(focus),
+    }
+}
+
+pub const BINDINGS: &[Binding] = &[
+    global(
+        "Switch workspace",
+        KeyCode::Char('w'),
+        KeyModifiers::CONTROL,
+    ),
+    global("Search channels", KeyCode::Char('k'), KeyModifiers::CONTROL),
+    global("Toggle sidebar", KeyCode::Char('b'), KeyModifiers::CONTROL),
+    global(
+        "Toggle agent panel",
+        KeyCode::Char('g'),
+        KeyModifiers::CONTROL,
+    ),
+    global(
+        "Copy selected message",
+        KeyCode::Char('c'),
+        KeyModifiers::CONTROL,
+    ),
+    global("Show help", KeyCode::Char('?'), KeyModifiers::NONE),
+    global("Refresh channel list", KeyCode::F(5), KeyModifiers::NONE),
+    global(
+        "Copy mode (select with the mouse)",
+        KeyCode::F(9),
+        KeyModifiers::NONE,
+    ),
+    global("Workspace issues", KeyCode::F(10), KeyModifiers::NONE),
+    global("Connection status", KeyCode::F(11), KeyModifiers::NONE),
+    global("Memory stats", KeyCode::F(12), KeyModifiers::NONE),
+    global("Quit", KeyCode::Char('q'), KeyModifiers::CONTROL),
+    in_focus("Mark channel read", 'R', Focus::Sidebar),
+    in_focus("Mark channel unread", 'U', Focus::Sidebar),
+    in_focus("Mark all read", 'M', Focus::Sidebar),
+    in_focus("Show archived channels", 'a', Focus::Sidebar),
+    in_focus("Jump to time", 'g', Focus::Messages),
+    in_focus("Channel info", 'I', Focus::Messages),
+    in_focus("Edit message", 'e', Focus::Messages),
+    in_focus("Delete message", 'd', Focus::Messages),
+    in_focus("React to message", 'r', Focus::Messages),
+    in_focus("Quote-reply", '>', Focus::Messages),
+    in_focus("Toggle thread", 't', Focus::Messages),
+    in_focus(
+        "Filter messages by author, files or threads",
+        'f',
+        Focus::Messages,
+    ),
+    in_focus("Load history for date", 'D', Focus::Messages),
+    in_focus("Agent response history", 'A', Focus::Messages),
+    in_focus("Inspect raw JSON", 'J', Focus::Messages),
+    in_focus("Open link in message", 'o', Focus::Messages),
+    in_focus("Save for later", 'S', Focus::Messages),
+    in_focus("Show error details", 'E', Focus::Messages),
+    in_focus("Select messages to copy", 'v', Focus::Messages),
+    in_focus("Re-run last agent command", '.', Focus::Messages),
+];
+
+impl Binding {
+    pub fn key_label(&self) -> String {
+        let key = match self.key {
+            KeyCode::Char(c) => c.to_uppercase().to_string(),
+            KeyCode::Enter => "Enter".to_string(),
+            KeyCode::PageUp => "PgUp".to_string(),
+            KeyCode::PageDown => "PgDn".to_string(),
+            KeyCode::F(n) => format!("F{}", n),
+            other => format!("{:?}", other),
+        };
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            format!("Ctrl+{}", key)
+        } else if let KeyCode::Char(c) = self.key {
+            c.to_string()
+        } else {
+            key
+        }
+    }
+}
+
+/// A command typed in the input after `/`. The input bar suggests these,
+/// with their arguments, while the name is being typed.
+#[derive(Debug, Clone, Copy)]
+pub struct SlashCommand {
+    pub name: &'static str,
+    pub args: &'static str,
+    /// Other names that run the same command; not suggested.
+    pub aliases: &'static [&'static str],
+}
+
+const fn slash(name: &'static str, args: &'static str) -> SlashCommand {
+    SlashCommand {
+        name,
+        args,
+        aliases: &[],
+    }
+}
+
+pub const SLASH_COMMANDS: &[SlashCommand] = &[
+    SlashCommand {
+        name: "/résume",
+        args: "[#channel] [range]",
+        aliases: &["/resume", "/summarize"],
+    },
+    slash("/draft", "<intent>"),
+    SlashCommand {
+        name: "/cherche",
+        args: "<query>",
+        aliases: &["/search"],
+    },
+    slash("/tldr", ""),
+    slash("/translate", "[lang]"),
+    slash("/agent", "restart | logs | on | off"),
+    slash("/history", ""),
+    slash("/again", "[n]"),
+    slash("/remind", "me <when> to <what>"),
+    slash("/reminders", ""),
+    slash("/away", ""),
+    slash("/active", ""),
+    slash("/join", "#channel"),
+    slash("/leave", ""),
+    slash("/topic", "[new topic]"),
+    slash("/mute", ""),
+];
+
+impl SlashCommand {
+    /// The command as suggested, e.g. `/join #channel`.
+    pub fn usage(&self) -> String {
+        if self.args.is_empty() {
+            self.name.to_string()
+        } else {
+            format!("{} {}", self.name, self.args)
+        }
+    }
+
+    /// The registered command called `name` (with its `/`), by name or alias.
+    pub fn find(name: &str) -> Option<&'static SlashCommand> {
+        let name = name.to_lowercase();
+        SLASH_COMMANDS
+            .iter()
+            .find(|command| command.name == name || command.aliases.contains(&name.as_str()))
+    }
+
+    /// Commands whose name or an alias starts with `prefix`, in registry
+    /// order, so `/res` still offers `/résume`.
+    pub fn completions(prefix: &str) -> Vec<&'static SlashCommand> {
+        let prefix = prefix.to_lowercase();
+        SLASH_COMMANDS
+            .iter()
+            .filter(|command| {
+                std::iter::once(&command.name)
+                    .chain(command.aliases)
+                    .any(|name| name.starts_with(&prefix))
+            })
+            .collect()
+    }
+}
+
+/// Case-insensitive subsequence match, so "mar" finds "Mark all read".
+pub fn fuzzy_match(query: &str, label: &str) -> bool {
+    let mut label = label.chars().flat_map(char::to_lowercase);
+    query
+        .chars()
+        .flat_map(char::to_lowercase)
+        .filter(|c| !c.is_whitespace())
+        .all(|q| label.any(|c| c == q))
+}
+
 pub struct Keybinds;
 
 impl Default for Keybinds {
@@ -7,6 +202,14 @@ impl Default for Keybinds {
 }
 
 impl Keybinds {
+    /// Bindings whose label matches `query`, in registry order.
+    pub fn matching(&self, query: &str) -> Vec<&'static Binding> {
+        BINDINGS
+            .iter()
+            .filter(|binding| fuzzy_match(query, binding.label))
+            .collect()
+    }
+
     pub fn help_text(&self) -> String {
         r#"Keyboard Shortcuts:
 
@@ -19,10 +222,18 @@ Sidebar focus:
   j / Down      Move channel cursor down
   k / Up        Move channel cursor up
   Enter         Open highlighted channel
+  R / U         Mark highlighted channel read / unread
+                (R retries instead while a failed send is shown)
+  M             Mark all channels read
+  a             Show/hide archived channels (read-only)
 
 Messages focus:
   j / Down      Scroll down
   k / Up        Scroll up
+  Enter         Open the thread under the cursor
+  I             Channel topic, purpose and members
+  PgUp/PgDn     Scroll a page
+  Ctrl+U/D      Scroll half a page
 
 Input focus:
   (all keys go to input, no shortcuts)
@@ -32,19 +243,34 @@ Input focus:
 Global (any focus):
   Alt+Up/Down   Switch channel
   Ctrl+W        Workspace picker
-  Ctrl+K        Channel search
+  Ctrl+K        Channel search (Tab: all workspaces)
+  Ctrl+P        Command palette
+  Alt+1..9      Switch to workspace N
+  Ctrl+B        Show/hide the sidebar
+  Ctrl+G        Show/hide the agent panel
   Ctrl+C        Copy selected message
   Ctrl+Q        Quit
   ?             Toggle this help
+  F5            Refresh the channel list
+  F10           Workspaces whose token failed (re-auth, retry, remove)
+  F11           Socket, API and agent connection status
+  F12           Memory use per channel
 
 Shortcuts (Sidebar/Messages only):
   t  thread   e  edit   d  delete   D  history
   r  react    g  jump   f  filter   E  error
+  A  agent response history   >  quote-reply
 
 Agent (in Input focus):
-  /             Start agent command
+  /             Start a command (Tab completes its name)
   @zeroclaw     Mention agent
 
+Channel commands (in Input focus):
+  /join #name   Join a channel and open it
+  /leave        Leave the open channel
+  /topic [text] Set the open channel's topic, or show it
+  /mute         Mute or unmute the open channel
+
 Mouse:
   Click         Select channel / workspace
   Scroll        Scroll messages
@@ -53,3 +279,37 @@ Mouse:
         .to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_matches_subsequences() {
+        assert!(fuzzy_match("mar", "Mark all read"));
+        assert!(fuzzy_match("tap", "Toggle agent panel"));
+        assert!(!fuzzy_match("xyz", "Mark all read"));
+        assert_eq!(
+            Keybinds.matching("jump").first().map(|b| b.key_label()),
+            Some("g".to_string())
+        );
+    }
+
+    #[test]
+    fn slash_commands_complete_by_prefix_and_resolve_aliases() {
+        let names = |prefix| {
+            SlashCommand::completions(prefix)
+                .iter()
+                .map(|command| command.name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names("/re"), ["/résume", "/remind", "/reminders"]);
+        assert_eq!(names("/j"), ["/join"]);
+        assert_eq!(
+            SlashCommand::find("/join").unwrap().usage(),
+            "/join #channel"
+        );
+        assert_eq!(SlashCommand::find("/Search").unwrap().name, "/cherche");
+        assert!(SlashCommand::find("/giphy").is_none());
+    }
+}
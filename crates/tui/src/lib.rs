@@ -1,6 +1,9 @@
 pub mod app;
 pub mod cache;
+pub mod cli;
 pub mod config;
+pub mod doctor;
+pub mod history;
 pub mod input;
 pub mod keybinds;
 pub mod onboarding;
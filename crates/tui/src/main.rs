@@ -1,34 +1,38 @@
-mod app;
-mod cache;
-mod config;
-mod input;
-mod keybinds;
-mod onboarding;
-mod ui;
-
-use app::App;
-use config::Config;
-use directories::ProjectDirs;
 use dotenvy::dotenv;
 use ratatui::crossterm::event::{
-    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers,
+    self, DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture, Event,
+    KeyCode, KeyModifiers,
 };
 use ratatui::crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
+use slack_zc::app::App;
+use slack_zc::cli::SessionClaim;
+use slack_zc::ui::links::{detect_hyperlinks, HyperlinkWriter};
+use slack_zc::ui::terminal::{self as term, detect_notifications, NotifyProtocol, TitleWriter};
+use slack_zc::ui::theme::{detect_background, Theme};
+use slack_zc::Config;
 use std::io;
 use std::path::PathBuf;
 use std::time::Duration;
 
+/// Upper bound on the quit sequence so a hung child can't keep the app open.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(2);
+
 fn get_config_path() -> PathBuf {
-    if let Some(proj_dirs) = ProjectDirs::from("com", "slack-zc", "slack-zc") {
-        proj_dirs.config_dir().join("config.toml")
-    } else {
-        PathBuf::from("config/default.toml")
-    }
+    slack_zc_slack::profile::config_dir()
+        .map(|dir| dir.join("config.toml"))
+        .unwrap_or_else(|_| PathBuf::from("config/default.toml"))
 }
 
+/// Logs to the working directory, then the temp dir; with neither writable
+/// the app still starts, without a log.
 fn init_tracing() {
-    let log_file = std::fs::File::create("slack-zc.log")
-        .unwrap_or_else(|_| std::fs::File::create("/tmp/slack-zc.log").unwrap());
+    let log_name = slack_zc_slack::profile::log_file_name();
+    let log_file: Box<dyn io::Write + Send> = match std::fs::File::create(&log_name)
+        .or_else(|_| std::fs::File::create(std::env::temp_dir().join(&log_name)))
+    {
+        Ok(file) => Box::new(file),
+        Err(_) => Box::new(io::sink()),
+    };
 
     tracing_subscriber::fmt()
         .with_writer(std::sync::Mutex::new(log_file))
@@ -39,19 +43,82 @@ fn init_tracing() {
         .init();
 }
 
+/// Puts the terminal back before a panic message is printed, so a crash
+/// leaves a usable shell with the message readable instead of raw mode,
+/// mouse reporting and the alternate screen still on.
+fn install_panic_hook() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = ratatui::crossterm::execute!(
+            io::stdout(),
+            DisableMouseCapture,
+            DisableFocusChange,
+            LeaveAlternateScreen
+        );
+        let _ = terminal::disable_raw_mode();
+        tracing::error!("Panic: {}", info);
+        previous(info);
+    }));
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let _ = dotenv(); // Try to load .env file, ignore if not found
 
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let profile = slack_zc::cli::take_profile_arg(&mut args)?.or_else(|| {
+        std::env::var("SLACK_ZC_PROFILE")
+            .ok()
+            .filter(|name| !name.is_empty())
+    });
+    if let Some(profile) = profile {
+        slack_zc_slack::profile::set_profile(&profile)?;
+    }
+
     init_tracing();
-    tracing::info!("Starting slack-zc");
+    tracing::info!(
+        "Starting slack-zc (profile {})",
+        slack_zc_slack::profile::current()
+    );
+
+    if args.first().map(String::as_str) == Some("session") {
+        return slack_zc::cli::run_session_command(&args[1..]).map_err(Into::into);
+    }
+    if args.first().map(String::as_str) == Some("--doctor") {
+        // Non-zero on any failed check, for scripts.
+        if !slack_zc::doctor::run(&get_config_path())? {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    // Held until exit; dropping it releases the session for other instances.
+    let _instance_lock = match slack_zc::cli::claim_session()? {
+        SessionClaim::Owned(lock) => Some(lock),
+        SessionClaim::ReadOnly => {
+            tracing::warn!("Running read-only alongside another instance");
+            None
+        }
+        SessionClaim::Quit => return Ok(()),
+    };
 
     terminal::enable_raw_mode()?;
     let mut terminal = ratatui::init();
-    ratatui::crossterm::execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+    ratatui::crossterm::execute!(
+        io::stdout(),
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableFocusChange
+    )?;
+    install_panic_hook();
 
     let result = run(&mut terminal);
 
-    let _ = ratatui::crossterm::execute!(io::stdout(), DisableMouseCapture, LeaveAlternateScreen);
+    let _ = ratatui::crossterm::execute!(
+        io::stdout(),
+        DisableMouseCapture,
+        DisableFocusChange,
+        LeaveAlternateScreen
+    );
     let _ = terminal::disable_raw_mode();
     ratatui::restore();
 
@@ -68,21 +135,66 @@ fn run(
 
     // Enter the runtime context for the entire app lifecycle so that
     // tokio::spawn works from synchronous code (e.g. spawn_app_task).
-    let _guard = rt.enter();
+    let guard = rt.enter();
 
     let mut app = App::new(config.clone());
+    app.config_path = Some(config_path);
+    // Asked before the event loop starts reading input, as the terminal
+    // answers on stdin.
+    app.theme = Theme::resolve(config.ui.theme.as_deref(), detect_background);
+    app.hyperlinks = config.ui.hyperlinks.unwrap_or_else(detect_hyperlinks);
+    let mut hyperlink_writer = HyperlinkWriter::default();
+    let notify_protocol = detect_notifications();
+    app.terminal_notifications = config
+        .ui
+        .terminal_notifications
+        .unwrap_or(notify_protocol.is_some());
+    let mut title_writer = TitleWriter::default();
+    if config.ui.window_title {
+        title_writer.save(&mut io::stdout())?;
+    }
 
+    if config.ui.splash {
+        terminal.draw(|frame| app.render(frame))?;
+    }
     rt.block_on(async {
         if let Err(e) = app.init(&config).await {
             eprintln!("Failed to initialize app: {}", e);
         }
     });
 
+    let mut mouse_captured = true;
     loop {
-        terminal.draw(|frame| app.render(frame))?;
+        // Copy mode hands the mouse back to the terminal for selecting text.
+        if app.copy_mode == mouse_captured {
+            if app.copy_mode {
+                ratatui::crossterm::execute!(io::stdout(), DisableMouseCapture)?;
+            } else {
+                ratatui::crossterm::execute!(io::stdout(), EnableMouseCapture)?;
+            }
+            mouse_captured = !app.copy_mode;
+        }
+        let frame = terminal.draw(|frame| app.render(frame))?;
+        if app.hyperlinks {
+            hyperlink_writer.write(&mut io::stdout(), frame.buffer, &app.screen_links)?;
+        }
+        // Written after the frame, never inside it.
+        if config.ui.window_title {
+            title_writer.write(&mut io::stdout(), &app.window_title())?;
+        }
+        for (title, body) in app.take_notifications() {
+            let protocol = notify_protocol.unwrap_or(NotifyProtocol::Osc9);
+            term::notify(&mut io::stdout(), protocol, &title, &body)?;
+        }
 
-        if event::poll(Duration::from_millis(50))? {
+        // Skip the input wait while events are still queued from a burst.
+        let wait = if app.has_queued_events() { 0 } else { 50 };
+        if event::poll(Duration::from_millis(wait))? {
             let event = event::read()?;
+            // A resize redraws the whole screen, without the links.
+            if let Event::Resize(..) = event {
+                hyperlink_writer.invalidate();
+            }
 
             if let Event::Key(key) = &event {
                 if key.code == KeyCode::Char('q') && key.modifiers.contains(KeyModifiers::CONTROL) {
@@ -104,5 +216,19 @@ fn run(
         }
     }
 
+    rt.block_on(async {
+        if tokio::time::timeout(SHUTDOWN_TIMEOUT, app.shutdown())
+            .await
+            .is_err()
+        {
+            tracing::warn!("Shutdown timed out after {:?}", SHUTDOWN_TIMEOUT);
+        }
+    });
+    drop(guard);
+    rt.shutdown_timeout(Duration::from_millis(200));
+    if config.ui.window_title {
+        let _ = title_writer.restore(&mut io::stdout());
+    }
+
     Ok(())
 }
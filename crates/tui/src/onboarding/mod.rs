@@ -16,6 +16,9 @@ pub struct OnboardingState {
     pub oauth_url: Option<String>,
     pub oauth_code: String,
     pub error_message: Option<String>,
+    /// Workspace being signed in to again from the Workspace issues panel;
+    /// finishing OAuth returns straight to the main view.
+    pub reauth_team: Option<String>,
 }
 
 impl Default for OnboardingState {
@@ -34,12 +37,13 @@ impl OnboardingState {
             oauth_url: None,
             oauth_code: String::new(),
             error_message: None,
+            reauth_team: None,
         }
     }
 
     pub fn generate_oauth_url(&mut self, redirect_port: u16) -> String {
         let url = format!(
-            "https://slack.com/oauth/v2/authorize?client_id={}&scope=channels:read,channels:history,channels:write,groups:read,groups:history,groups:write,im:read,im:history,im:write,mpim:read,mpim:history,mpim:write,chat:write,users:read,reactions:read,connections:write&redirect_uri=http://localhost:{}",
+            "https://slack.com/oauth/v2/authorize?client_id={}&scope=channels:read,channels:history,channels:write,groups:read,groups:history,groups:write,im:read,im:history,im:write,mpim:read,mpim:history,mpim:write,chat:write,users:read,users:write,reactions:read,reminders:read,reminders:write,stars:read,stars:write,connections:write&redirect_uri=http://localhost:{}",
             self.client_id, redirect_port
         );
         self.oauth_url = Some(url.clone());
@@ -14,10 +14,24 @@ const MIN_AGENT_WIDTH: u16 = 20;
 const MAX_AGENT_WIDTH: u16 = 40;
 const TOPBAR_HEIGHT: u16 = 1;
 const INPUT_HEIGHT: u16 = 3;
+const MIN_MESSAGES_WIDTH: u16 = 40;
+
+/// Below this width the agent panel is hidden automatically.
+const AGENT_AUTO_HIDE_WIDTH: u16 = 110;
+/// Below this width the sidebar shrinks to an icon strip.
+const SIDEBAR_COLLAPSE_WIDTH: u16 = 90;
+const COLLAPSED_SIDEBAR_WIDTH: u16 = 3;
+pub const MIN_TERMINAL_WIDTH: u16 = 80;
+pub const MIN_TERMINAL_HEIGHT: u16 = 20;
 
 pub struct LayoutState {
     sidebar_width: u16,
     agent_width: u16,
+    show_sidebar: bool,
+    show_agent: bool,
+    agent_hidden: bool,
+    sidebar_collapsed: bool,
+    too_small: bool,
     cached_panels: Vec<Panel>,
 }
 
@@ -26,13 +40,43 @@ impl Default for LayoutState {
         Self {
             sidebar_width: 20,
             agent_width: 26,
+            show_sidebar: true,
+            show_agent: true,
+            agent_hidden: false,
+            sidebar_collapsed: false,
+            too_small: false,
             cached_panels: Vec::new(),
         }
     }
 }
 
 impl LayoutState {
+    /// Lays out the panels for `area`, degrading on small terminals. The
+    /// dragged widths are kept as-is so growing the terminal restores them.
     pub fn calculate_layout(&mut self, area: Rect) -> &[Panel] {
+        self.too_small = area.width < MIN_TERMINAL_WIDTH || area.height < MIN_TERMINAL_HEIGHT;
+        if self.too_small {
+            self.cached_panels.clear();
+            return &self.cached_panels;
+        }
+        self.agent_hidden = !self.show_agent
+            || area.width < AGENT_AUTO_HIDE_WIDTH
+            || area.width < self.sidebar_width + MIN_MESSAGES_WIDTH + self.agent_width;
+        self.sidebar_collapsed = area.width < SIDEBAR_COLLAPSE_WIDTH;
+
+        let sidebar_width = if !self.show_sidebar {
+            0
+        } else if self.sidebar_collapsed {
+            COLLAPSED_SIDEBAR_WIDTH
+        } else {
+            self.sidebar_width
+        };
+        let agent_width = if self.agent_hidden {
+            0
+        } else {
+            self.agent_width
+        };
+
         let main_layout = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
@@ -45,9 +89,9 @@ impl LayoutState {
         let content_layout = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([
-                Constraint::Length(self.sidebar_width),
-                Constraint::Min(40),
-                Constraint::Length(self.agent_width),
+                Constraint::Length(sidebar_width),
+                Constraint::Min(MIN_MESSAGES_WIDTH),
+                Constraint::Length(agent_width),
             ])
             .split(main_layout[1]);
 
@@ -56,23 +100,27 @@ impl LayoutState {
                 panel_type: PanelType::Topbar,
                 rect: main_layout[0],
             },
-            Panel {
-                panel_type: PanelType::Sidebar,
-                rect: content_layout[0],
-            },
             Panel {
                 panel_type: PanelType::Messages,
                 rect: content_layout[1],
             },
-            Panel {
-                panel_type: PanelType::AgentPanel,
-                rect: content_layout[2],
-            },
             Panel {
                 panel_type: PanelType::InputBar,
                 rect: main_layout[2],
             },
         ];
+        if self.show_sidebar {
+            self.cached_panels.push(Panel {
+                panel_type: PanelType::Sidebar,
+                rect: content_layout[0],
+            });
+        }
+        if !self.agent_hidden {
+            self.cached_panels.push(Panel {
+                panel_type: PanelType::AgentPanel,
+                rect: content_layout[2],
+            });
+        }
 
         &self.cached_panels
     }
@@ -81,8 +129,36 @@ impl LayoutState {
         &self.cached_panels
     }
 
+    pub fn is_sidebar_hidden(&self) -> bool {
+        !self.show_sidebar
+    }
+
+    pub fn set_sidebar_visible(&mut self, visible: bool) {
+        self.show_sidebar = visible;
+    }
+
+    /// The user's choice; the panel may still be auto-hidden on narrow terminals.
+    pub fn set_agent_visible(&mut self, visible: bool) {
+        self.show_agent = visible;
+        self.agent_hidden |= !visible;
+    }
+
+    pub fn is_too_small(&self) -> bool {
+        self.too_small
+    }
+
+    pub fn is_agent_hidden(&self) -> bool {
+        self.agent_hidden
+    }
+
+    pub fn is_sidebar_collapsed(&self) -> bool {
+        self.sidebar_collapsed
+    }
+
     pub fn handle_drag(&mut self, target: DragTarget, delta: i16) {
         match target {
+            DragTarget::Sidebar if self.sidebar_collapsed || !self.show_sidebar => {}
+            DragTarget::AgentPanel if self.agent_hidden => {}
             DragTarget::Sidebar => {
                 let new_width = (self.sidebar_width as i16 + delta)
                     .clamp(MIN_SIDEBAR_WIDTH as i16, MAX_SIDEBAR_WIDTH as i16)
@@ -112,3 +188,61 @@ impl LayoutState {
             .map(|p| p.rect)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn has(layout: &LayoutState, panel_type: PanelType) -> bool {
+        layout
+            .get_panels()
+            .iter()
+            .any(|p| p.panel_type == panel_type)
+    }
+
+    #[test]
+    fn degrades_and_restores_with_terminal_width() {
+        let mut layout = LayoutState::default();
+        layout.handle_drag(DragTarget::Sidebar, 5);
+
+        layout.calculate_layout(Rect::new(0, 0, 100, 30));
+        assert!(!has(&layout, PanelType::AgentPanel));
+        assert_eq!(layout.get_sidebar_rect().map(|r| r.width), Some(25));
+
+        // Dragging the hidden agent panel does nothing.
+        layout.handle_drag(DragTarget::AgentPanel, -10);
+
+        layout.calculate_layout(Rect::new(0, 0, 85, 30));
+        assert_eq!(
+            layout.get_sidebar_rect().map(|r| r.width),
+            Some(COLLAPSED_SIDEBAR_WIDTH)
+        );
+
+        layout.calculate_layout(Rect::new(0, 0, 70, 30));
+        assert!(layout.is_too_small());
+        assert!(layout.get_panels().is_empty());
+
+        layout.calculate_layout(Rect::new(0, 0, 160, 40));
+        assert!(!layout.is_too_small());
+        assert_eq!(layout.get_sidebar_rect().map(|r| r.width), Some(25));
+        assert_eq!(layout.get_agent_rect().map(|r| r.width), Some(26));
+    }
+
+    #[test]
+    fn toggled_panels_give_messages_the_full_width() {
+        let mut layout = LayoutState::default();
+        layout.set_sidebar_visible(false);
+        layout.set_agent_visible(false);
+
+        layout.calculate_layout(Rect::new(0, 0, 160, 40));
+
+        assert!(layout.get_sidebar_rect().is_none());
+        assert!(layout.get_agent_rect().is_none());
+        let messages = layout
+            .get_panels()
+            .iter()
+            .find(|p| p.panel_type == PanelType::Messages)
+            .map(|p| p.rect.width);
+        assert_eq!(messages, Some(160));
+    }
+}
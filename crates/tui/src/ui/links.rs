@@ -0,0 +1,368 @@
+//! Links in message text: Slack's `<url|label>` markup and bare URLs, the
+//! browser to open them in, and OSC 8 hyperlinks for terminals that
+//! understand them.
+
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::crossterm::cursor::{MoveTo, RestorePosition, SavePosition};
+use ratatui::crossterm::queue;
+use ratatui::crossterm::style::{
+    Attribute, Print, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor,
+};
+use ratatui::style::Modifier;
+use std::io::{self, Write};
+use std::ops::Range;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+use unicode_width::UnicodeWidthStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    pub url: String,
+    /// What the message shows for it: the markup's label, else the URL.
+    pub label: String,
+}
+
+/// Message text with link markup replaced by its label, and where each
+/// link ended up in it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LinkedText {
+    pub text: String,
+    pub links: Vec<Link>,
+    /// Char range in `text` of each of `links`, in the same order.
+    pub ranges: Vec<Range<usize>>,
+}
+
+impl LinkedText {
+    /// Which link, if any, the char at `index` of `text` belongs to.
+    pub fn owners(&self) -> Vec<Option<usize>> {
+        let mut owners = vec![None; self.text.chars().count()];
+        for (link, range) in self.ranges.iter().enumerate() {
+            for owner in owners.get_mut(range.clone()).into_iter().flatten() {
+                *owner = Some(link);
+            }
+        }
+        owners
+    }
+}
+
+/// Finds `<url>`, `<url|label>` and bare `http(s)://` URLs in `text`.
+/// Mentions and channel references in angle brackets are left alone.
+pub fn linkify(text: &str) -> LinkedText {
+    static LINK_RE: OnceLock<regex::Regex> = OnceLock::new();
+    let re = LINK_RE.get_or_init(|| {
+        regex::Regex::new(r"<((?:https?|mailto):[^|>\s]+)(?:\|([^>]*))?>|https?://[^\s<>]+")
+            .unwrap()
+    });
+
+    let mut out = LinkedText::default();
+    let mut chars = 0;
+    let mut last = 0;
+    for caps in re.captures_iter(text) {
+        let whole = caps.get(0).expect("match");
+        let (url, label, end) = match caps.get(1) {
+            Some(url) => {
+                let label = caps
+                    .get(2)
+                    .map(|label| label.as_str())
+                    .filter(|label| !label.trim().is_empty())
+                    .unwrap_or(url.as_str());
+                (url.as_str(), label, whole.end())
+            }
+            None => {
+                let url = trim_url(whole.as_str());
+                (url, url, whole.start() + url.len())
+            }
+        };
+        let before = &text[last..whole.start()];
+        out.text.push_str(before);
+        chars += before.chars().count();
+        out.text.push_str(label);
+        let label_chars = label.chars().count();
+        out.ranges.push(chars..chars + label_chars);
+        chars += label_chars;
+        out.links.push(Link {
+            url: unescape(url),
+            label: label.to_string(),
+        });
+        last = end;
+    }
+    out.text.push_str(&text[last..]);
+    out
+}
+
+/// Drops punctuation that ends the sentence rather than the URL, and a
+/// closing parenthesis the URL did not open.
+fn trim_url(url: &str) -> &str {
+    let mut url = url.trim_end_matches(['.', ',', ';', ':', '!', '?', '\'', '"']);
+    while url.ends_with(')') && url.matches(')').count() > url.matches('(').count() {
+        url = url[..url.len() - 1].trim_end_matches(['.', ',', ';', ':', '!', '?', '\'', '"']);
+    }
+    url
+}
+
+/// Slack sends `&`, `<` and `>` in text as HTML entities.
+fn unescape(url: &str) -> String {
+    url.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+/// Hands `url` to the desktop's browser. Only web and mail links are
+/// opened, so message text can never start a local program.
+pub fn open_in_browser(url: &str) -> io::Result<()> {
+    let scheme = url.split(':').next().unwrap_or_default();
+    if !matches!(
+        scheme.to_ascii_lowercase().as_str(),
+        "http" | "https" | "mailto"
+    ) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("not a web link: {}", url),
+        ));
+    }
+    #[cfg(target_os = "macos")]
+    let opener = "open";
+    #[cfg(not(target_os = "macos"))]
+    let opener = "xdg-open";
+    let mut child = Command::new(opener)
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    // Reaped off the UI thread so it never leaves a zombie behind.
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+/// Whether the terminal is one known to turn OSC 8 sequences into
+/// clickable links, from the variables it sets.
+pub fn detect_hyperlinks() -> bool {
+    supports_hyperlinks(|name| std::env::var(name).ok())
+}
+
+fn supports_hyperlinks(var: impl Fn(&str) -> Option<String>) -> bool {
+    let term_program = var("TERM_PROGRAM").unwrap_or_default();
+    let term = var("TERM").unwrap_or_default();
+    matches!(
+        term_program.as_str(),
+        "iTerm.app" | "WezTerm" | "vscode" | "ghostty" | "Hyper" | "Tabby"
+    ) || var("VTE_VERSION")
+        .and_then(|v| v.parse::<u32>().ok())
+        .is_some_and(|v| v >= 5000)
+        || var("KITTY_WINDOW_ID").is_some()
+        || var("WT_SESSION").is_some()
+        || ["kitty", "foot", "alacritty", "wezterm", "ghostty"]
+            .iter()
+            .any(|name| term.contains(name))
+}
+
+/// A link as drawn: `text` starting at column `x` of row `y`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScreenLink {
+    pub x: u16,
+    pub y: u16,
+    pub text: String,
+    pub url: String,
+}
+
+/// Reprints drawn links wrapped in OSC 8, after ratatui has drawn the
+/// frame, since ratatui cannot put escape sequences in a cell without
+/// miscounting its width.
+#[derive(Debug, Default)]
+pub struct HyperlinkWriter {
+    written: Vec<(ScreenLink, Vec<Cell>)>,
+}
+
+impl HyperlinkWriter {
+    /// Forgets what was written, e.g. once the terminal has been cleared.
+    pub fn invalidate(&mut self) {
+        self.written.clear();
+    }
+
+    /// Writes `links` over the cells `buffer` drew them in. Links hidden
+    /// under a popup no longer match their cells and are left out; nothing
+    /// is written when the links and their cells are unchanged.
+    pub fn write(
+        &mut self,
+        out: &mut impl Write,
+        buffer: &Buffer,
+        links: &[ScreenLink],
+    ) -> io::Result<()> {
+        let drawn: Vec<(ScreenLink, Vec<Cell>)> = links
+            .iter()
+            .filter_map(|link| {
+                let cells = cells_of(buffer, link)?;
+                let shown: String = cells.iter().map(Cell::symbol).collect();
+                (shown == link.text).then(|| (link.clone(), cells))
+            })
+            .collect();
+        if drawn == self.written {
+            return Ok(());
+        }
+
+        queue!(out, SavePosition)?;
+        for (link, cells) in &drawn {
+            let Some(url) = osc8_url(&link.url) else {
+                continue;
+            };
+            queue!(out, MoveTo(link.x, link.y))?;
+            write!(out, "\x1b]8;;{}\x1b\\", url)?;
+            for cell in cells {
+                queue!(
+                    out,
+                    SetAttribute(Attribute::Reset),
+                    SetForegroundColor(cell.fg.into()),
+                    SetBackgroundColor(cell.bg.into()),
+                )?;
+                for (modifier, attribute) in ATTRIBUTES {
+                    if cell.modifier.contains(modifier) {
+                        queue!(out, SetAttribute(attribute))?;
+                    }
+                }
+                queue!(out, Print(cell.symbol()))?;
+            }
+            write!(out, "\x1b]8;;\x1b\\")?;
+        }
+        queue!(
+            out,
+            SetAttribute(Attribute::Reset),
+            ResetColor,
+            RestorePosition
+        )?;
+        out.flush()?;
+        self.written = drawn;
+        Ok(())
+    }
+}
+
+const ATTRIBUTES: [(Modifier, Attribute); 6] = [
+    (Modifier::BOLD, Attribute::Bold),
+    (Modifier::DIM, Attribute::Dim),
+    (Modifier::ITALIC, Attribute::Italic),
+    (Modifier::UNDERLINED, Attribute::Underlined),
+    (Modifier::REVERSED, Attribute::Reverse),
+    (Modifier::CROSSED_OUT, Attribute::CrossedOut),
+];
+
+/// The cells `link` covers, skipping those under a wide character.
+fn cells_of(buffer: &Buffer, link: &ScreenLink) -> Option<Vec<Cell>> {
+    let mut cells = Vec::new();
+    let mut width = 0;
+    let mut x = link.x;
+    while width < link.text.width() {
+        let cell = buffer.cell((x, link.y))?;
+        let cell_width = cell.symbol().width().max(1);
+        cells.push(cell.clone());
+        width += cell_width;
+        x += cell_width as u16;
+    }
+    Some(cells)
+}
+
+/// `url` as OSC 8 allows it: printable ASCII, the rest percent-encoded.
+fn osc8_url(url: &str) -> Option<String> {
+    if url.chars().any(char::is_control) {
+        return None;
+    }
+    let mut out = String::with_capacity(url.len());
+    for byte in url.bytes() {
+        if byte.is_ascii_graphic() {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markup_and_bare_urls_become_links() {
+        let linked = linkify(
+            "PR <https://github.com/acme/api/pull/42|#42 Fix retries> by <@U1>, \
+             see https://example.com/a?b=1&amp;c=2. (docs: https://en.wikipedia.org/wiki/Rust_(language))",
+        );
+        assert_eq!(
+            linked.text,
+            "PR #42 Fix retries by <@U1>, see https://example.com/a?b=1&amp;c=2. \
+             (docs: https://en.wikipedia.org/wiki/Rust_(language))"
+        );
+        let urls: Vec<&str> = linked.links.iter().map(|l| l.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            [
+                "https://github.com/acme/api/pull/42",
+                "https://example.com/a?b=1&c=2",
+                "https://en.wikipedia.org/wiki/Rust_(language)",
+            ]
+        );
+        let shown: Vec<String> = linked
+            .ranges
+            .iter()
+            .map(|range| {
+                linked
+                    .text
+                    .chars()
+                    .skip(range.start)
+                    .take(range.len())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(shown[0], "#42 Fix retries");
+        assert_eq!(shown[1], "https://example.com/a?b=1&amp;c=2");
+        assert_eq!(linkify("<https://x.io>").text, "https://x.io");
+        assert!(linkify("<#C1|general> and <@U2>").links.is_empty());
+    }
+
+    #[test]
+    fn only_web_links_are_opened() {
+        assert!(open_in_browser("file:///etc/passwd").is_err());
+        assert!(open_in_browser("javascript:alert(1)").is_err());
+    }
+
+    #[test]
+    fn hyperlink_support_is_read_from_the_environment() {
+        let env = |pairs: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                pairs
+                    .iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+        assert!(supports_hyperlinks(env(&[("TERM_PROGRAM", "WezTerm")])));
+        assert!(supports_hyperlinks(env(&[("VTE_VERSION", "6003")])));
+        assert!(supports_hyperlinks(env(&[("TERM", "xterm-kitty")])));
+        assert!(!supports_hyperlinks(env(&[("VTE_VERSION", "4205")])));
+        assert!(!supports_hyperlinks(env(&[("TERM", "xterm-256color")])));
+    }
+
+    #[test]
+    fn links_are_reprinted_only_where_they_are_still_drawn() {
+        let mut buffer = Buffer::empty(ratatui::layout::Rect::new(0, 0, 20, 2));
+        buffer.set_string(2, 0, "see docs", ratatui::style::Style::default());
+        let link = |y, text: &str| ScreenLink {
+            x: 6,
+            y,
+            text: text.to_string(),
+            url: "https://example.com/é".to_string(),
+        };
+        let mut writer = HyperlinkWriter::default();
+        let mut out = Vec::new();
+        writer
+            .write(&mut out, &buffer, &[link(0, "docs"), link(1, "gone")])
+            .unwrap();
+        let written = String::from_utf8(out).unwrap();
+        assert!(written.contains("\x1b]8;;https://example.com/%C3%A9\x1b\\"));
+        assert_eq!(written.matches("\x1b]8;;https").count(), 1);
+
+        // The same frame again writes nothing.
+        let mut out = Vec::new();
+        writer.write(&mut out, &buffer, &[link(0, "docs")]).unwrap();
+        assert!(out.is_empty());
+    }
+}
@@ -1,2 +1,6 @@
 pub mod layout;
+pub mod links;
 pub mod panel;
+pub mod sanitize;
+pub mod terminal;
+pub mod theme;
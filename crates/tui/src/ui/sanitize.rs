@@ -0,0 +1,148 @@
+//! Makes untrusted text (messages, usernames, agent output) safe to draw.
+
+use std::borrow::Cow;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Spaces a tab expands to.
+const TAB_WIDTH: usize = 4;
+
+/// Drops ANSI CSI/OSC and other escape sequences, C1 controls and lone
+/// carriage returns, expands tabs, and shows any other C0 control as its
+/// Unicode control picture (BEL becomes `␇`) so nothing reaches the terminal
+/// that could move the cursor or change colours. Newlines are kept.
+pub fn sanitize_text(input: &str) -> Cow<'_, str> {
+    if !input
+        .chars()
+        .any(|c| c != '\n' && (c.is_control() || c == '\u{7f}'))
+    {
+        return Cow::Borrowed(input);
+    }
+
+    let mut out = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => out.push('\n'),
+            '\t' => out.push_str(&" ".repeat(TAB_WIDTH)),
+            // `\r\n` is a line break; a lone `\r` would rewind the line.
+            '\r' => {
+                if chars.peek() != Some(&'\n') {
+                    out.push(' ');
+                }
+            }
+            '\u{1b}' => skip_escape_sequence(&mut chars),
+            // C1 controls, including the single-byte CSI and OSC.
+            '\u{80}'..='\u{9f}' => {}
+            '\u{7f}' => out.push('␡'),
+            c if c.is_control() => {
+                out.push(char::from_u32(0x2400 + c as u32).unwrap_or('\u{fffd}'));
+            }
+            c => out.push(c),
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// Consumes the rest of an escape sequence whose ESC was just read.
+fn skip_escape_sequence(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) {
+    match chars.next() {
+        // CSI: parameters and intermediates, then one final byte.
+        Some('[') => {
+            for c in chars.by_ref() {
+                if ('\u{40}'..='\u{7e}').contains(&c) {
+                    break;
+                }
+            }
+        }
+        // OSC, DCS, SOS, PM, APC: run until BEL or the ST (`ESC \`).
+        Some(']' | 'P' | 'X' | '^' | '_') => {
+            while let Some(c) = chars.next() {
+                if c == '\u{7}' {
+                    break;
+                }
+                if c == '\u{1b}' {
+                    chars.next_if_eq(&'\\');
+                    break;
+                }
+            }
+        }
+        // Two-byte sequences such as `ESC c` (reset) or `ESC 7`.
+        _ => {}
+    }
+}
+
+/// Cuts `input` to at most `max_width` terminal columns, ending with `…`
+/// when anything was dropped. Cuts between grapheme clusters, so emoji,
+/// accents and wide CJK characters are never split.
+pub fn truncate_display(input: &str, max_width: usize) -> Cow<'_, str> {
+    if input.width() <= max_width {
+        return Cow::Borrowed(input);
+    }
+    let budget = max_width.saturating_sub(1);
+    let mut out = String::new();
+    let mut width = 0;
+    for grapheme in input.graphemes(true) {
+        let w = grapheme.width();
+        if width + w > budget {
+            break;
+        }
+        width += w;
+        out.push_str(grapheme);
+    }
+    if max_width > 0 {
+        out.push('…');
+    }
+    Cow::Owned(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{sanitize_text, truncate_display};
+    use unicode_width::UnicodeWidthStr;
+
+    #[test]
+    fn escape_sequences_and_controls_are_removed() {
+        let dirty = "\x1b[31mERROR\x1b[0m disk full\x07\r\n\x1b]0;pwned\x07next\tline\rover";
+        let clean = sanitize_text(dirty);
+        assert!(!clean.contains('\x1b'));
+        assert!(!clean.contains('\x07'));
+        assert!(!clean.contains('\r'));
+        assert_eq!(clean, "ERROR disk full␇\nnext    line over");
+    }
+
+    #[test]
+    fn plain_text_is_borrowed() {
+        assert!(matches!(
+            sanitize_text("héllo\nworld"),
+            std::borrow::Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn truncation_never_splits_a_character() {
+        // Family emoji (ZWJ sequence), combining accents and wide CJK, each
+        // cut at every width so a boundary lands inside them somewhere.
+        let samples = [
+            "ok 👨‍👩‍👧 done",
+            "cafe\u{301} e\u{301}te\u{301}",
+            "日本語のテキスト",
+            "🎉🎉🎉",
+        ];
+        for sample in samples {
+            for max in 0..=sample.width() + 1 {
+                let cut = truncate_display(sample, max);
+                assert!(cut.width() <= max, "{sample:?} at {max}: {cut:?}");
+                let kept = cut.trim_end_matches('…');
+                assert!(sample.starts_with(kept), "{sample:?} at {max}: {cut:?}");
+            }
+        }
+        assert_eq!(truncate_display("日本語", 5), "日本…");
+        assert_eq!(truncate_display("cafe\u{301}s", 5), "cafe\u{301}s");
+        assert_eq!(truncate_display("cafe\u{301}s!", 5), "cafe\u{301}…");
+        assert!(matches!(
+            truncate_display("short", 10),
+            std::borrow::Cow::Borrowed(_)
+        ));
+    }
+}
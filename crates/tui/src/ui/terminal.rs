@@ -0,0 +1,151 @@
+//! Window title and notifications, sent as escape sequences between frames
+//! so they never land in the middle of ratatui's output.
+
+use ratatui::crossterm::execute;
+use ratatui::crossterm::style::Print;
+use ratatui::crossterm::terminal::SetTitle;
+use std::io::{self, Write};
+
+/// XTWINOPS: saves the shell's title so it can be put back on exit.
+const PUSH_TITLE: &str = "\x1b[22;0t";
+const POP_TITLE: &str = "\x1b[23;0t";
+
+/// The notification sequence a terminal understands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotifyProtocol {
+    /// `OSC 9 ; body`: iTerm2, WezTerm, Ghostty, kitty.
+    Osc9,
+    /// `OSC 777 ; notify ; title ; body`: foot, urxvt with its notify
+    /// extension.
+    Osc777,
+}
+
+/// Notification support judged from the environment.
+pub fn detect_notifications() -> Option<NotifyProtocol> {
+    notification_protocol(|name| std::env::var(name).ok())
+}
+
+fn notification_protocol(var: impl Fn(&str) -> Option<String>) -> Option<NotifyProtocol> {
+    let term_program = var("TERM_PROGRAM").unwrap_or_default();
+    let term = var("TERM").unwrap_or_default();
+    if matches!(term_program.as_str(), "iTerm.app" | "WezTerm" | "ghostty")
+        || var("KITTY_WINDOW_ID").is_some()
+        || term.contains("kitty")
+    {
+        Some(NotifyProtocol::Osc9)
+    } else if term.contains("foot") || term.starts_with("rxvt") {
+        Some(NotifyProtocol::Osc777)
+    } else {
+        None
+    }
+}
+
+/// Sets the window title, writing it only when it changed.
+#[derive(Debug, Default)]
+pub struct TitleWriter {
+    written: Option<String>,
+}
+
+impl TitleWriter {
+    /// Saves the current title for [`TitleWriter::restore`].
+    pub fn save(&mut self, out: &mut impl Write) -> io::Result<()> {
+        execute!(out, Print(PUSH_TITLE))
+    }
+
+    pub fn write(&mut self, out: &mut impl Write, title: &str) -> io::Result<()> {
+        let title = strip_controls(title);
+        if self.written.as_deref() == Some(title.as_str()) {
+            return Ok(());
+        }
+        execute!(out, SetTitle(&title))?;
+        self.written = Some(title);
+        Ok(())
+    }
+
+    /// Puts back the title saved at startup.
+    pub fn restore(&mut self, out: &mut impl Write) -> io::Result<()> {
+        self.written = None;
+        execute!(out, Print(POP_TITLE))
+    }
+}
+
+/// Asks the terminal to show a notification.
+pub fn notify(
+    out: &mut impl Write,
+    protocol: NotifyProtocol,
+    title: &str,
+    body: &str,
+) -> io::Result<()> {
+    execute!(out, Print(notification_sequence(protocol, title, body)))
+}
+
+fn notification_sequence(protocol: NotifyProtocol, title: &str, body: &str) -> String {
+    let body = strip_controls(body);
+    match protocol {
+        NotifyProtocol::Osc9 => format!("\x1b]9;{}: {}\x1b\\", strip_controls(title), body),
+        // `;` separates the fields, so it may not appear in the title.
+        NotifyProtocol::Osc777 => format!(
+            "\x1b]777;notify;{};{}\x1b\\",
+            strip_controls(title).replace(';', ","),
+            body
+        ),
+    }
+}
+
+/// One line with no control characters, so text from Slack can't end the
+/// sequence early or start another.
+fn strip_controls(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .filter(|c| !c.is_control())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sequences_carry_no_controls_from_message_text() {
+        assert_eq!(
+            notification_sequence(
+                NotifyProtocol::Osc777,
+                "#ops; (Acme)",
+                "alice: deploy\nfailed\x07\x1b]0;pwned"
+            ),
+            "\x1b]777;notify;#ops, (Acme);alice: deploy failed]0;pwned\x1b\\"
+        );
+        assert_eq!(
+            notification_sequence(NotifyProtocol::Osc9, "#ops", "bob: hi"),
+            "\x1b]9;#ops: bob: hi\x1b\\"
+        );
+
+        let mut out = Vec::new();
+        let mut title = TitleWriter::default();
+        title.write(&mut out, "slack-zc — #ops").unwrap();
+        title.write(&mut out, "slack-zc — #ops").unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap().matches("slack-zc").count(),
+            1
+        );
+    }
+
+    #[test]
+    fn notification_support_comes_from_the_environment() {
+        let protocol = |vars: &[(&str, &str)]| {
+            notification_protocol(|name| {
+                vars.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            })
+        };
+        assert_eq!(
+            protocol(&[("TERM_PROGRAM", "iTerm.app")]),
+            Some(NotifyProtocol::Osc9)
+        );
+        assert_eq!(protocol(&[("TERM", "foot")]), Some(NotifyProtocol::Osc777));
+        assert_eq!(protocol(&[("TERM", "xterm-256color")]), None);
+    }
+}
@@ -0,0 +1,359 @@
+use ratatui::style::{Color, Modifier, Style};
+use std::time::Duration;
+
+/// Names accepted for `ui.theme`.
+pub const THEME_NAMES: [&str; 3] = ["dark", "light", "high-contrast"];
+
+/// Styles for states that views share, so they are defined in one place
+/// instead of at each render site. Every state also has a non-colour cue
+/// (a prefix, marker or modifier) so it reads without the colours.
+#[derive(Debug, Clone)]
+pub struct Theme {
+    /// Secondary text: times, placeholders, notices.
+    pub dim: Style,
+    /// Border of the focused panel.
+    pub focus_border: Style,
+    /// The row under the keyboard cursor, also marked with `>`.
+    pub cursor: Style,
+    /// The open channel, also marked with its `#` or `@`.
+    pub selected: Style,
+    /// Channels with unread messages, which also show the count.
+    pub unread: Style,
+    /// Mentions and new thread replies, also marked with `@` or `●`.
+    pub mention: Style,
+    /// Errors, also marked with `⚠`.
+    pub error: Style,
+    /// Sidebar section headers.
+    pub section: Style,
+    /// Slack Connect channels, also marked with `⇄`.
+    pub shared: Style,
+    /// The Saved and Threads entries.
+    pub pinned: Style,
+    /// Author line of a message.
+    pub author: Style,
+    /// Reaction and thread-reply lines under a message.
+    pub reactions: Style,
+    /// Reactions the current user added, also drawn in `⟨ ⟩`.
+    pub own_reaction: Style,
+    pub replies: Style,
+    /// Message under the cursor, with and without focus.
+    pub message_cursor: Style,
+    pub message_cursor_unfocused: Style,
+    /// Selected row in popups that show no `>` of their own.
+    pub highlight: Style,
+    /// Background flash on a message opened in context.
+    pub context_highlight: Style,
+    /// Gutter marker left on that message until the view moves off it.
+    pub context_marker: Style,
+    /// Messages in a visual selection.
+    pub selection: Style,
+    /// Delivery marks on posts typed here: on the way, accepted, refused.
+    pub delivery_pending: Style,
+    pub delivery_sent: Style,
+    pub delivery_failed: Style,
+    /// Links in message text, also underlined.
+    pub link: Style,
+}
+
+/// Whether the terminal draws on a dark or a light background.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Background {
+    Dark,
+    Light,
+}
+
+impl Theme {
+    pub fn dark() -> Self {
+        Self {
+            dim: Style::default().fg(Color::DarkGray),
+            focus_border: Style::default().fg(Color::Yellow),
+            cursor: Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+            selected: Style::default().fg(Color::Cyan),
+            unread: Style::default().add_modifier(Modifier::BOLD),
+            mention: Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            error: Style::default().fg(Color::Red),
+            section: Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+            shared: Style::default().fg(Color::LightBlue),
+            pinned: Style::default().fg(Color::LightYellow),
+            author: Style::default().fg(Color::Gray),
+            reactions: Style::default().fg(Color::Cyan),
+            own_reaction: Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+            replies: Style::default().fg(Color::Magenta),
+            message_cursor: Style::default()
+                .add_modifier(Modifier::BOLD)
+                .bg(Color::Rgb(40, 40, 40)),
+            message_cursor_unfocused: Style::default().bg(Color::Rgb(30, 30, 30)),
+            highlight: Style::default()
+                .add_modifier(Modifier::BOLD)
+                .bg(Color::Rgb(40, 40, 40)),
+            context_highlight: Style::default().bg(Color::Rgb(70, 60, 20)),
+            context_marker: Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+            selection: Style::default().bg(Color::Rgb(30, 50, 90)),
+            delivery_pending: Style::default().fg(Color::DarkGray),
+            delivery_sent: Style::default().fg(Color::Green),
+            delivery_failed: Style::default().fg(Color::Red),
+            link: Style::default()
+                .fg(Color::LightBlue)
+                .add_modifier(Modifier::UNDERLINED),
+        }
+    }
+
+    /// For light backgrounds, where dark gray and yellow wash out.
+    pub fn light() -> Self {
+        Self {
+            dim: Style::default().fg(Color::Rgb(96, 96, 96)),
+            focus_border: Style::default().fg(Color::Blue),
+            cursor: Style::default()
+                .fg(Color::Blue)
+                .add_modifier(Modifier::BOLD),
+            selected: Style::default().fg(Color::Rgb(0, 95, 135)),
+            mention: Style::default()
+                .fg(Color::Rgb(175, 0, 0))
+                .add_modifier(Modifier::BOLD),
+            error: Style::default().fg(Color::Rgb(175, 0, 0)),
+            section: Style::default()
+                .fg(Color::Rgb(0, 95, 135))
+                .add_modifier(Modifier::BOLD),
+            shared: Style::default().fg(Color::Rgb(0, 0, 175)),
+            pinned: Style::default().fg(Color::Rgb(135, 95, 0)),
+            author: Style::default().fg(Color::Rgb(68, 68, 68)),
+            reactions: Style::default().fg(Color::Rgb(0, 95, 135)),
+            own_reaction: Style::default()
+                .fg(Color::Rgb(135, 75, 0))
+                .add_modifier(Modifier::BOLD),
+            replies: Style::default().fg(Color::Rgb(135, 0, 135)),
+            message_cursor: Style::default()
+                .add_modifier(Modifier::BOLD)
+                .bg(Color::Rgb(218, 218, 218)),
+            message_cursor_unfocused: Style::default().bg(Color::Rgb(235, 235, 235)),
+            highlight: Style::default()
+                .add_modifier(Modifier::BOLD)
+                .bg(Color::Rgb(218, 218, 218)),
+            context_highlight: Style::default().bg(Color::Rgb(255, 240, 170)),
+            context_marker: Style::default()
+                .fg(Color::Rgb(135, 95, 0))
+                .add_modifier(Modifier::BOLD),
+            selection: Style::default().bg(Color::Rgb(200, 220, 250)),
+            delivery_pending: Style::default().fg(Color::Rgb(96, 96, 96)),
+            delivery_sent: Style::default().fg(Color::Rgb(0, 120, 0)),
+            delivery_failed: Style::default().fg(Color::Rgb(175, 0, 0)),
+            link: Style::default()
+                .fg(Color::Rgb(0, 0, 175))
+                .add_modifier(Modifier::UNDERLINED),
+            ..Self::dark()
+        }
+    }
+
+    /// Bold text and explicit foreground/background pairs, each at least
+    /// 7:1 contrast, for low vision. Plain text keeps the terminal's own
+    /// colours, whatever its background, and nothing is dimmed.
+    pub fn high_contrast() -> Self {
+        const BLACK: Color = Color::Rgb(0, 0, 0);
+        const WHITE: Color = Color::Rgb(255, 255, 255);
+        let on = |fg: Color, bg: Color| Style::default().fg(fg).bg(bg).add_modifier(Modifier::BOLD);
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        Self {
+            dim: Style::default().add_modifier(Modifier::ITALIC),
+            focus_border: on(BLACK, Color::Rgb(255, 255, 0)),
+            cursor: on(BLACK, Color::Rgb(255, 255, 0)),
+            selected: on(BLACK, WHITE).add_modifier(Modifier::UNDERLINED),
+            unread: bold,
+            mention: on(BLACK, Color::Rgb(255, 175, 0)),
+            error: on(WHITE, Color::Rgb(175, 0, 0)),
+            section: bold.add_modifier(Modifier::UNDERLINED),
+            shared: bold,
+            pinned: bold,
+            author: bold,
+            reactions: Style::default(),
+            own_reaction: bold.add_modifier(Modifier::UNDERLINED),
+            replies: Style::default(),
+            message_cursor: on(BLACK, WHITE),
+            message_cursor_unfocused: Style::default().add_modifier(Modifier::UNDERLINED),
+            highlight: on(BLACK, Color::Rgb(255, 255, 0)),
+            context_highlight: on(BLACK, Color::Rgb(0, 255, 255)),
+            context_marker: on(BLACK, Color::Rgb(255, 255, 0)),
+            selection: on(WHITE, Color::Rgb(0, 0, 175)),
+            delivery_pending: Style::default(),
+            delivery_sent: bold,
+            delivery_failed: on(WHITE, Color::Rgb(175, 0, 0)),
+            link: bold.add_modifier(Modifier::UNDERLINED),
+        }
+    }
+
+    /// A builtin by its `ui.theme` name.
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "high-contrast" | "high_contrast" => Some(Self::high_contrast()),
+            _ => None,
+        }
+    }
+
+    /// The configured theme, or with none (or an unknown name) the builtin
+    /// for the background `detect` reports, dark if it can't tell.
+    pub fn resolve(name: Option<&str>, detect: impl FnOnce() -> Option<Background>) -> Self {
+        if let Some(name) = name.filter(|name| !name.trim().is_empty()) {
+            match Self::by_name(name) {
+                Some(theme) => return theme,
+                None => tracing::warn!(
+                    "Unknown ui.theme {:?}; expected one of {}",
+                    name,
+                    THEME_NAMES.join(", ")
+                ),
+            }
+        }
+        match detect() {
+            Some(Background::Light) => Self::light(),
+            _ => Self::dark(),
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+/// Asks the terminal for its background colour (OSC 11), falling back to
+/// `COLORFGBG`. Needs raw mode, and must run before input is read. Only
+/// called when `ui.theme` doesn't name a theme.
+pub fn detect_background() -> Option<Background> {
+    query_background(Duration::from_millis(150)).or_else(|| {
+        std::env::var("COLORFGBG")
+            .ok()
+            .and_then(|value| parse_colorfgbg(&value))
+    })
+}
+
+#[cfg(unix)]
+fn query_background(wait: Duration) -> Option<Background> {
+    use std::io::{Read, Write};
+    use std::time::Instant;
+
+    // Device attributes (DA1) goes out second: every terminal answers it,
+    // after any OSC 11 reply, so its answer marks the end of what the
+    // queries send back.
+    let mut stdout = std::io::stdout();
+    stdout.write_all(b"\x1b]11;?\x07\x1b[c").ok()?;
+    stdout.flush().ok()?;
+
+    let deadline = Instant::now() + wait;
+    let mut reply = Vec::new();
+    let mut stdin = std::io::stdin();
+    let osc11 = loop {
+        if let Some(start) = da1_reply_start(&reply) {
+            break &reply[..start];
+        }
+        let left = deadline.saturating_duration_since(Instant::now());
+        let mut fd = libc::pollfd {
+            fd: libc::STDIN_FILENO,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        // SAFETY: one valid pollfd for the duration of the call.
+        let ready = unsafe { libc::poll(&mut fd, 1, left.as_millis() as libc::c_int) };
+        let mut byte = [0u8; 1];
+        if ready <= 0 || reply.len() > 256 || !matches!(stdin.read(&mut byte), Ok(1)) {
+            // No answer in time: discard whatever is queued so a reply
+            // that did arrive isn't read as keys by the event loop.
+            // SAFETY: plain call on the stdin descriptor.
+            unsafe { libc::tcflush(libc::STDIN_FILENO, libc::TCIFLUSH) };
+            return None;
+        }
+        reply.push(byte[0]);
+    };
+    parse_osc11(&String::from_utf8_lossy(osc11))
+}
+
+/// Where a complete DA1 reply (`ESC [ ? ... c`) starts in `reply`.
+#[cfg(unix)]
+fn da1_reply_start(reply: &[u8]) -> Option<usize> {
+    let start = reply.windows(3).position(|w| w == b"\x1b[?")?;
+    reply[start..].ends_with(b"c").then_some(start)
+}
+
+#[cfg(not(unix))]
+fn query_background(_wait: Duration) -> Option<Background> {
+    None
+}
+
+/// Reads `ESC ] 11 ; rgb:RRRR/GGGG/BBBB` (1 to 4 hex digits a channel).
+fn parse_osc11(reply: &str) -> Option<Background> {
+    let rgb = reply.split("rgb:").nth(1)?;
+    let rgb = rgb.trim_end_matches(['\x07', '\\', '\x1b']);
+    let mut channels = rgb.split('/').map(|hex| {
+        let value = u32::from_str_radix(hex, 16).ok()?;
+        let max = 16u32.checked_pow(hex.len() as u32)? - 1;
+        Some(value as f64 / max as f64)
+    });
+    let (r, g, b) = (channels.next()??, channels.next()??, channels.next()??);
+    let luminance = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+    Some(if luminance > 0.5 {
+        Background::Light
+    } else {
+        Background::Dark
+    })
+}
+
+/// `COLORFGBG` is `fg;bg` (sometimes `fg;default;bg`) in ANSI colour
+/// numbers; 7 and 9-15 are light backgrounds.
+fn parse_colorfgbg(value: &str) -> Option<Background> {
+    let bg: u8 = value.rsplit(';').next()?.trim().parse().ok()?;
+    Some(if bg == 7 || (9..=15).contains(&bg) {
+        Background::Light
+    } else {
+        Background::Dark
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn background_is_read_from_the_terminal_reply_or_colorfgbg() {
+        assert_eq!(
+            parse_osc11("\x1b]11;rgb:ffff/ffff/ffff\x07"),
+            Some(Background::Light)
+        );
+        assert_eq!(
+            parse_osc11("\x1b]11;rgb:1e1e/1e1e/2e2e\x1b\\"),
+            Some(Background::Dark)
+        );
+        assert_eq!(
+            parse_osc11("\x1b]11;rgb:f/f/e\x07"),
+            Some(Background::Light)
+        );
+        assert_eq!(parse_osc11("garbage"), None);
+
+        #[cfg(unix)]
+        {
+            let reply = b"\x1b]11;rgb:1c1c/1c1c/1c1c\x07\x1b[?62;22c";
+            assert_eq!(da1_reply_start(&reply[..30]), None);
+            assert_eq!(da1_reply_start(reply), Some(24));
+            assert_eq!(da1_reply_start(b"\x1b[?1;2c"), Some(0));
+        }
+
+        assert_eq!(parse_colorfgbg("15;0"), Some(Background::Dark));
+        assert_eq!(parse_colorfgbg("0;15"), Some(Background::Light));
+        assert_eq!(parse_colorfgbg("0;default;7"), Some(Background::Light));
+        assert_eq!(parse_colorfgbg("default"), None);
+
+        // An explicit name wins over detection; an unknown one falls back.
+        let light = Theme::resolve(Some("high-contrast"), || Some(Background::Light));
+        assert!(light.cursor.bg.is_some());
+        let detected = Theme::resolve(Some("solarized"), || Some(Background::Light));
+        assert_eq!(detected.cursor.fg, Some(Color::Blue));
+        assert_eq!(Theme::resolve(None, || None).cursor.fg, Some(Color::Yellow));
+    }
+}